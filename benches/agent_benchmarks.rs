@@ -19,7 +19,7 @@ use zeroclaw::config::MemoryConfig;
 use zeroclaw::memory;
 use zeroclaw::memory::{Memory, MemoryCategory};
 use zeroclaw::observability::{NoopObserver, Observer};
-use zeroclaw::providers::{ChatRequest, ChatResponse, Provider, ToolCall};
+use zeroclaw::providers::{ChatOutcome, ChatRequest, ChatResponse, Provider, ToolCall};
 use zeroclaw::tools::{Tool, ToolResult};
 
 use anyhow::Result;
@@ -41,6 +41,7 @@ impl BenchProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             }]),
         }
     }
@@ -57,12 +58,14 @@ impl BenchProvider {
                     }],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 },
                 ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 },
             ]),
         }
@@ -94,6 +97,7 @@ impl Provider for BenchProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
         Ok(guard.remove(0))
@@ -161,6 +165,7 @@ Let me know if you need more."#
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     let multi_tool = ChatResponse {
@@ -179,6 +184,7 @@ Let me know if you need more."#
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     c.bench_function("xml_parse_single_tool_call", |b| {
@@ -213,6 +219,7 @@ fn bench_native_parsing(c: &mut Criterion) {
         ],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     c.bench_function("native_parse_tool_calls", |b| {
@@ -347,7 +347,7 @@ impl Agent {
             .available_hints(available_hints)
             .route_model_by_hint(route_model_by_hint)
             .identity_config(config.identity.clone())
-            .skills(crate::skills::load_skills_with_config(
+            .skills(crate::skills::load_prompt_skills(
                 &config.workspace_dir,
                 config,
             ))
@@ -543,7 +543,7 @@ impl Agent {
             None
         };
 
-        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+        let now = crate::config::timezone::transcript_stamp_now();
         let stamped_user_message = format!("[{now}] {user_message}");
         let enriched = match (&context, &research_context) {
             (c, Some(r)) if !c.is_empty() => {
@@ -585,6 +585,7 @@ impl Agent {
                 Ok(resp) => resp,
                 Err(err) => return Err(err),
             };
+            response.outcome.ensure_completed()?;
 
             let (text, calls) = self.tool_dispatcher.parse_response(&response);
             if calls.is_empty() {
@@ -776,6 +777,7 @@ mod tests {
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: crate::providers::ChatOutcome::Completed,
                 });
             }
             Ok(guard.remove(0))
@@ -813,6 +815,7 @@ mod tests {
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: crate::providers::ChatOutcome::Completed,
                 });
             }
             Ok(guard.remove(0))
@@ -852,6 +855,7 @@ mod tests {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: crate::providers::ChatOutcome::Completed,
             }]),
         });
 
@@ -892,12 +896,14 @@ mod tests {
                     }],
                     usage: None,
                     reasoning_content: None,
+                    outcome: crate::providers::ChatOutcome::Completed,
                 },
                 crate::providers::ChatResponse {
                     text: Some("done".into()),
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: crate::providers::ChatOutcome::Completed,
                 },
             ]),
         });
@@ -939,6 +945,7 @@ mod tests {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: crate::providers::ChatOutcome::Completed,
             }]),
             seen_models: seen_models.clone(),
         });
@@ -0,0 +1,339 @@
+//! Delegate agents as direct request targets.
+//!
+//! Besides backing the `delegate` tool, an `[agents.<name>]` entry can answer
+//! requests itself: a webhook call with `"agent": "<name>"`, or every message
+//! on a channel mapped in `[channels_config.agents]`. Those requests use the
+//! agent's provider, model, system prompt and tool allowlist instead of the
+//! default persona. Providers are built on first use and cached per agent
+//! name; memory and conversation history are namespaced by agent name so
+//! agents never see each other's (or the default persona's) turns.
+
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::{Config, DelegateAgentConfig};
+use crate::providers::{self, Provider, ProviderRuntimeOptions};
+
+/// Health component prefix for instantiated agents (`agent:<name>`).
+pub const HEALTH_COMPONENT_PREFIX: &str = "agent:";
+
+/// Temperature used when an agent does not set one (same as the `delegate` tool).
+const DEFAULT_AGENT_TEMPERATURE: f64 = 0.7;
+
+/// Error text for an agent name that is not configured, listing the ones that are.
+pub fn unknown_agent_message<S: AsRef<str>>(name: &str, configured: &[S]) -> String {
+    if configured.is_empty() {
+        return format!("unknown agent `{name}`: no agents are configured under [agents]");
+    }
+    let names: Vec<&str> = configured.iter().map(AsRef::as_ref).collect();
+    format!(
+        "unknown agent `{name}`; configured agents: {}",
+        names.join(", ")
+    )
+}
+
+/// Daemon state older than this is treated as a stopped daemon.
+const DAEMON_STATE_STALE_SECS: i64 = 30;
+
+/// Agents a running daemon reports as instantiated, read from its
+/// `daemon_state.json` health snapshot. `None` when no fresh snapshot exists.
+pub fn daemon_instantiated_agents(config: &Config) -> Option<Vec<String>> {
+    let raw = std::fs::read_to_string(crate::daemon::state_file_path(config)).ok()?;
+    let snapshot: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    let updated_at = snapshot.get("updated_at")?.as_str()?;
+    let updated_at = chrono::DateTime::parse_from_rfc3339(updated_at).ok()?;
+    let age = chrono::Utc::now().signed_duration_since(updated_at.with_timezone(&chrono::Utc));
+    if age.num_seconds() > DAEMON_STATE_STALE_SECS {
+        return None;
+    }
+    Some(instantiated_in_snapshot(&snapshot))
+}
+
+fn instantiated_in_snapshot(snapshot: &serde_json::Value) -> Vec<String> {
+    let mut names: Vec<String> = snapshot
+        .get("components")
+        .and_then(serde_json::Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter(|(_, health)| {
+            health.get("status").and_then(serde_json::Value::as_str) == Some("ok")
+        })
+        .filter_map(|(component, _)| component.strip_prefix(HEALTH_COMPONENT_PREFIX))
+        .map(str::to_string)
+        .collect();
+    names.sort();
+    names
+}
+
+/// One resolved agent: its config plus its cached provider.
+#[derive(Clone)]
+pub struct AgentTarget {
+    pub name: String,
+    pub config: DelegateAgentConfig,
+    pub provider: Arc<dyn Provider>,
+}
+
+impl AgentTarget {
+    pub fn temperature(&self) -> f64 {
+        self.config.temperature.unwrap_or(DEFAULT_AGENT_TEMPERATURE)
+    }
+
+    /// Memory session that scopes what this agent stores and recalls.
+    pub fn memory_session(&self) -> String {
+        format!("{HEALTH_COMPONENT_PREFIX}{}", self.name)
+    }
+
+    /// Conversation-history key for this agent, distinct from the default
+    /// persona's and every other agent's key for the same sender.
+    pub fn history_key(&self, key: &str) -> String {
+        format!("{HEALTH_COMPONENT_PREFIX}{}:{key}", self.name)
+    }
+
+    /// Tools hidden from this agent: everything outside `allowed_tools` in
+    /// agentic mode, every tool otherwise. `delegate` is always hidden.
+    pub fn excluded_tools<'a>(&self, tool_names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let allowed: Vec<&str> = if self.config.agentic {
+            self.config
+                .allowed_tools
+                .iter()
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        tool_names
+            .into_iter()
+            .filter(|name| *name == "delegate" || !allowed.contains(name))
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Configured delegate agents plus the providers built for them so far.
+#[derive(Default)]
+pub struct DelegateAgentPool {
+    configs: HashMap<String, DelegateAgentConfig>,
+    /// Channel name → agent name, from `[channels_config.agents]`.
+    channel_routes: HashMap<String, String>,
+    fallback_credential: Option<String>,
+    provider_runtime_options: ProviderRuntimeOptions,
+    providers: Mutex<HashMap<String, Arc<dyn Provider>>>,
+}
+
+impl DelegateAgentPool {
+    pub fn new(
+        configs: HashMap<String, DelegateAgentConfig>,
+        fallback_credential: Option<String>,
+        provider_runtime_options: ProviderRuntimeOptions,
+    ) -> Self {
+        Self {
+            configs,
+            channel_routes: HashMap::new(),
+            fallback_credential,
+            provider_runtime_options,
+            providers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        let provider_runtime_options = ProviderRuntimeOptions {
+            auth_profile_override: None,
+            provider_api_url: config.api_url.clone(),
+            provider_transport: config.effective_provider_transport(),
+            zeroclaw_dir: config.config_path.parent().map(std::path::PathBuf::from),
+            secrets_encrypt: config.secrets.encrypt,
+            reasoning_enabled: config.runtime.reasoning_enabled,
+            reasoning_level: config.effective_provider_reasoning_level(),
+            custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
+            max_tokens_override: None,
+            model_support_vision: config.model_support_vision,
+            prompt_caching: config.provider.prompt_caching,
+            ollama: config.provider.ollama.clone(),
+        };
+        Self::new(
+            config.agents.clone(),
+            config.api_key.clone(),
+            provider_runtime_options,
+        )
+        .with_channel_routes(config.channels_config.agents.clone())
+    }
+
+    /// Answer the named channels with the given agents.
+    pub fn with_channel_routes(mut self, channel_routes: HashMap<String, String>) -> Self {
+        self.channel_routes = channel_routes
+            .into_iter()
+            .map(|(channel, agent)| (channel, agent.trim().to_string()))
+            .collect();
+        self
+    }
+
+    /// The agent that answers every message on `channel`, if one is mapped.
+    pub fn channel_agent(&self, channel: &str) -> Option<&str> {
+        self.channel_routes.get(channel).map(String::as_str)
+    }
+
+    /// Configured agent names, sorted.
+    pub fn configured(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.configs.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.configs.contains_key(name.trim())
+    }
+
+    /// Agents whose provider has been built, sorted.
+    pub fn instantiated(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.providers.lock().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Resolve `name`, building and caching its provider on first use.
+    pub fn target(&self, name: &str) -> Result<AgentTarget> {
+        let name = name.trim();
+        let config = self
+            .configs
+            .get(name)
+            .ok_or_else(|| anyhow!(unknown_agent_message(name, &self.configured())))?;
+
+        let mut providers = self.providers.lock();
+        let provider = if let Some(provider) = providers.get(name) {
+            Arc::clone(provider)
+        } else {
+            let credential = config
+                .api_key
+                .as_deref()
+                .or(self.fallback_credential.as_deref());
+            let component = format!("{HEALTH_COMPONENT_PREFIX}{name}");
+            let provider: Arc<dyn Provider> = match providers::create_provider_with_options(
+                &config.provider,
+                credential,
+                &self.provider_runtime_options,
+            ) {
+                Ok(provider) => Arc::from(provider),
+                Err(error) => {
+                    crate::health::mark_component_error(&component, &error);
+                    return Err(error.context(format!(
+                        "failed to create provider '{}' for agent '{name}'",
+                        config.provider
+                    )));
+                }
+            };
+            crate::health::mark_component_ok(&component);
+            providers.insert(name.to_string(), Arc::clone(&provider));
+            provider
+        };
+
+        Ok(AgentTarget {
+            name: name.to_string(),
+            config: config.clone(),
+            provider,
+        })
+    }
+
+    /// Use `provider` for `name` instead of building one from its config.
+    #[cfg(test)]
+    pub(crate) fn with_provider(self, name: &str, provider: Arc<dyn Provider>) -> Self {
+        self.providers.lock().insert(name.to_string(), provider);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(provider: &str, agentic: bool, allowed_tools: &[&str]) -> DelegateAgentConfig {
+        DelegateAgentConfig {
+            provider: provider.into(),
+            model: "test-model".into(),
+            system_prompt: Some("You triage support tickets.".into()),
+            api_key: None,
+            temperature: None,
+            max_depth: 3,
+            agentic,
+            allowed_tools: allowed_tools.iter().map(|t| (*t).to_string()).collect(),
+            max_iterations: 10,
+        }
+    }
+
+    #[test]
+    fn unknown_agent_lists_configured_names() {
+        let pool = DelegateAgentPool::new(
+            HashMap::from([
+                ("support-triager".to_string(), agent("ollama", false, &[])),
+                ("homelab".to_string(), agent("ollama", false, &[])),
+            ]),
+            None,
+            ProviderRuntimeOptions::default(),
+        );
+        let err = pool.target("ghost").err().unwrap().to_string();
+        assert!(err.contains("unknown agent `ghost`"));
+        assert!(err.contains("homelab, support-triager"));
+        assert!(pool.instantiated().is_empty());
+
+        let empty = DelegateAgentPool::default();
+        let err = empty.target("ghost").err().unwrap().to_string();
+        assert!(err.contains("no agents are configured"));
+    }
+
+    #[test]
+    fn providers_are_built_lazily_and_cached() {
+        let pool = DelegateAgentPool::new(
+            HashMap::from([("homelab".to_string(), agent("ollama", false, &[]))]),
+            None,
+            ProviderRuntimeOptions::default(),
+        );
+        assert!(pool.instantiated().is_empty());
+        let first = pool.target("homelab").unwrap();
+        let second = pool.target("homelab").unwrap();
+        assert!(Arc::ptr_eq(&first.provider, &second.provider));
+        assert_eq!(pool.instantiated(), ["homelab"]);
+        assert_eq!(first.memory_session(), "agent:homelab");
+        assert_eq!(
+            first.history_key("telegram_alice"),
+            "agent:homelab:telegram_alice"
+        );
+    }
+
+    #[test]
+    fn snapshot_reports_only_healthy_agent_components() {
+        let snapshot = serde_json::json!({
+            "components": {
+                "agent:homelab": { "status": "ok" },
+                "agent:broken": { "status": "error" },
+                "channels": { "status": "ok" },
+            }
+        });
+        assert_eq!(instantiated_in_snapshot(&snapshot), ["homelab"]);
+    }
+
+    #[test]
+    fn excluded_tools_follow_the_allowlist() {
+        let pool = DelegateAgentPool::new(
+            HashMap::from([
+                (
+                    "agentic".to_string(),
+                    agent("ollama", true, &["shell", "delegate"]),
+                ),
+                ("plain".to_string(), agent("ollama", false, &["shell"])),
+            ]),
+            None,
+            ProviderRuntimeOptions::default(),
+        );
+        let tools = ["shell", "file_read", "delegate"];
+        assert_eq!(
+            pool.target("agentic").unwrap().excluded_tools(tools),
+            ["file_read", "delegate"]
+        );
+        assert_eq!(
+            pool.target("plain").unwrap().excluded_tools(tools),
+            ["shell", "file_read", "delegate"]
+        );
+    }
+}
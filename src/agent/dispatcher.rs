@@ -252,6 +252,7 @@ impl ToolDispatcher for NativeToolDispatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::ChatOutcome;
 
     #[test]
     fn xml_dispatcher_parses_tool_calls() {
@@ -263,6 +264,7 @@ mod tests {
             tool_calls: vec![],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         };
         let dispatcher = XmlToolDispatcher;
         let (_, calls) = dispatcher.parse_response(&response);
@@ -281,6 +283,7 @@ mod tests {
             }],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         };
         let dispatcher = NativeToolDispatcher;
         let (_, calls) = dispatcher.parse_response(&response);
@@ -667,6 +667,7 @@ pub(crate) async fn run_tool_call_loop_with_reply_target(
 }
 
 /// Run the tool loop with optional non-CLI approval context scoped to this task.
+/// Streaming tools executed by the loop report progress to `progress_sink`.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_tool_call_loop_with_non_cli_approval_context(
     provider: &dyn Provider,
@@ -680,6 +681,7 @@ pub(crate) async fn run_tool_call_loop_with_non_cli_approval_context(
     approval: Option<&ApprovalManager>,
     channel_name: &str,
     non_cli_approval_context: Option<NonCliApprovalContext>,
+    progress_sink: Option<crate::tools::progress::ProgressSink>,
     multimodal_config: &crate::config::MultimodalConfig,
     max_tool_iterations: usize,
     cancellation_token: Option<CancellationToken>,
@@ -691,32 +693,31 @@ pub(crate) async fn run_tool_call_loop_with_non_cli_approval_context(
         .as_ref()
         .map(|ctx| ctx.reply_target.clone());
 
-    TOOL_LOOP_NON_CLI_APPROVAL_CONTEXT
-        .scope(
-            non_cli_approval_context,
-            TOOL_LOOP_REPLY_TARGET.scope(
-                reply_target,
-                run_tool_call_loop(
-                    provider,
-                    history,
-                    tools_registry,
-                    observer,
-                    provider_name,
-                    model,
-                    temperature,
-                    silent,
-                    approval,
-                    channel_name,
-                    multimodal_config,
-                    max_tool_iterations,
-                    cancellation_token,
-                    on_delta,
-                    hooks,
-                    excluded_tools,
-                ),
+    let tool_loop = TOOL_LOOP_NON_CLI_APPROVAL_CONTEXT.scope(
+        non_cli_approval_context,
+        TOOL_LOOP_REPLY_TARGET.scope(
+            reply_target,
+            run_tool_call_loop(
+                provider,
+                history,
+                tools_registry,
+                observer,
+                provider_name,
+                model,
+                temperature,
+                silent,
+                approval,
+                channel_name,
+                multimodal_config,
+                max_tool_iterations,
+                cancellation_token,
+                on_delta,
+                hooks,
+                excluded_tools,
             ),
-        )
-        .await
+        ),
+    );
+    crate::tools::progress::with_sink(progress_sink, tool_loop).await
 }
 
 // ── Agent Tool-Call Loop ──────────────────────────────────────────────────
@@ -921,6 +922,25 @@ pub(crate) async fn run_tool_call_loop(
                     output_tokens: resp_output_tokens,
                 });
 
+                // A refusal ends the turn; the caller answers with its
+                // refusal reply.
+                if let Err(refused) = resp.outcome.ensure_completed() {
+                    runtime_trace::record_event(
+                        "llm_refused",
+                        Some(channel_name),
+                        Some(provider_name),
+                        Some(model),
+                        Some(&turn_id),
+                        Some(false),
+                        Some(&refused.reason),
+                        serde_json::json!({
+                            "iteration": iteration + 1,
+                            "duration_ms": llm_started_at.elapsed().as_millis(),
+                        }),
+                    );
+                    return Err(refused.into());
+                }
+
                 let response_text = resp.text_or_empty().to_string();
                 // First try native structured tool calls (OpenAI-format).
                 // Fall back to text-based parsing (XML tags, markdown blocks,
@@ -1780,6 +1800,8 @@ pub async fn run(
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        prompt_caching: config.provider.prompt_caching,
+        ollama: config.provider.ollama.clone(),
     };
 
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
@@ -1818,7 +1840,7 @@ pub async fn run(
         .collect();
 
     // ── Build system prompt from workspace MD files (OpenClaw framework) ──
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         (
             "shell",
@@ -1863,6 +1885,14 @@ pub async fn run(
         "Force-run a cron job immediately and record a run history entry.",
     ));
     tool_descs.push(("cron_runs", "Show recent run history for a cron job."));
+    tool_descs.push((
+        "skill_list",
+        "List installed skills with version, source and tools.",
+    ));
+    tool_descs.push((
+        "skill_install",
+        "Install a skill (path, git/zip URL, clawhub:<slug>, namespace/name). Audited before install; confirm with the user and pass approved=true.",
+    ));
     tool_descs.push((
         "screenshot",
         "Capture a screenshot of the current screen. Returns file path and base64-encoded PNG. Use when: visual verification, UI inspection, debugging displays.",
@@ -1939,25 +1969,22 @@ pub async fn run(
             "Query connected hardware for reported GPIO pins and LED pin. Use when: user asks what pins are available.",
         ));
     }
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let prompt_profile = config.agent.prompt_profile();
     let native_tools = provider.supports_native_tools();
-    let mut system_prompt = crate::channels::build_system_prompt_with_mode(
+    let mut system_prompt = crate::channels::build_system_prompt_with_sections(
         &config.workspace_dir,
         model_name,
         &tool_descs,
         &skills,
         Some(&config.identity),
-        bootstrap_max_chars,
+        prompt_profile.bootstrap_max_chars,
         native_tools,
         config.skills.prompt_injection_mode,
+        &prompt_profile.sections,
     );
 
     // Append structured tool-use instructions with schemas (only for non-native providers)
-    if !native_tools {
+    if !native_tools && prompt_profile.sections.tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
@@ -1987,13 +2014,13 @@ pub async fn run(
         // Inject memory + hardware RAG context into user message
         let mem_context =
             build_context(mem.as_ref(), &msg, config.memory.min_relevance_score).await;
-        let rag_limit = if config.agent.compact_context { 2 } else { 5 };
+        let rag_limit = prompt_profile.rag_chunk_limit;
         let hw_context = hardware_rag
             .as_ref()
             .map(|r| build_hardware_context(r, &msg, &board_names, rag_limit))
             .unwrap_or_default();
         let context = format!("{mem_context}{hw_context}");
-        let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+        let now = crate::config::timezone::transcript_stamp_now();
         let enriched = if context.is_empty() {
             format!("[{now}] {msg}")
         } else {
@@ -2041,8 +2068,25 @@ pub async fn run(
         println!("Type /help for commands.\n");
         let cli = crate::channels::CliChannel::new();
 
+        // Channels linked with `zeroclaw link` share this conversation.
+        let linked = crate::links::LinkedConversation::resolve(
+            &config.workspace_dir,
+            crate::links::CLI_IDENTITY,
+        );
+        let shared_turns = linked
+            .as_ref()
+            .map(|link| link.turns(config.agent.max_history_messages))
+            .unwrap_or_default();
+
         // Persistent conversation history across turns
         let mut history = vec![ChatMessage::system(&system_prompt)];
+        if !shared_turns.is_empty() {
+            println!(
+                "🔗 Continuing the linked conversation ({} earlier turns).\n",
+                shared_turns.len()
+            );
+        }
+        history.extend(shared_turns);
         // Reusable readline editor for UTF-8 input support
         let mut rl = Editor::with_config(
             RlConfig::builder()
@@ -2093,9 +2137,13 @@ pub async fn run(
                     rl.clear_history()?;
                     history.clear();
                     history.push(ChatMessage::system(&system_prompt));
-                    // Clear conversation and daily memory
+                    // Clear conversation, ephemeral and daily memory
                     let mut cleared = 0;
-                    for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
+                    for category in [
+                        MemoryCategory::Conversation,
+                        MemoryCategory::Ephemeral,
+                        MemoryCategory::Daily,
+                    ] {
                         let entries = mem.list(Some(&category), None).await.unwrap_or_default();
                         for entry in entries {
                             if mem.forget(&entry.key).await.unwrap_or(false) {
@@ -2124,13 +2172,13 @@ pub async fn run(
             // Inject memory + hardware RAG context into user message
             let mem_context =
                 build_context(mem.as_ref(), &user_input, config.memory.min_relevance_score).await;
-            let rag_limit = if config.agent.compact_context { 2 } else { 5 };
+            let rag_limit = prompt_profile.rag_chunk_limit;
             let hw_context = hardware_rag
                 .as_ref()
                 .map(|r| build_hardware_context(r, &user_input, &board_names, rag_limit))
                 .unwrap_or_default();
             let context = format!("{mem_context}{hw_context}");
-            let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+            let now = crate::config::timezone::transcript_stamp_now();
             let enriched = if context.is_empty() {
                 format!("[{now}] {user_input}")
             } else {
@@ -2138,6 +2186,9 @@ pub async fn run(
             };
 
             history.push(ChatMessage::user(&enriched));
+            if let Some(link) = &linked {
+                link.record(&ChatMessage::user(format!("[{now}] {user_input}")));
+            }
 
             let ld_cfg = LoopDetectionConfig {
                 no_progress_threshold: config.agent.loop_detection_no_progress_threshold,
@@ -2193,6 +2244,9 @@ pub async fn run(
                     continue;
                 }
             };
+            if let Some(link) = &linked {
+                link.record(&ChatMessage::assistant(&response));
+            }
             final_output = response.clone();
             if let Err(e) = crate::channels::Channel::send(
                 &cli,
@@ -2296,6 +2350,8 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        prompt_caching: config.provider.prompt_caching,
+        ollama: config.provider.ollama.clone(),
     };
     let provider: Box<dyn Provider> = providers::create_routed_provider_with_options(
         provider_name,
@@ -2322,7 +2378,7 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
         .map(|b| b.board.clone())
         .collect();
 
-    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, &config);
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, &config);
     let mut tool_descs: Vec<(&str, &str)> = vec![
         ("shell", "Execute terminal commands."),
         ("file_read", "Read file contents."),
@@ -2379,35 +2435,32 @@ pub async fn process_message(config: Config, message: &str) -> Result<String> {
             "Query connected hardware for reported GPIO pins and LED pin. Use when user asks what pins are available.",
         ));
     }
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let prompt_profile = config.agent.prompt_profile();
     let native_tools = provider.supports_native_tools();
-    let mut system_prompt = crate::channels::build_system_prompt_with_mode(
+    let mut system_prompt = crate::channels::build_system_prompt_with_sections(
         &config.workspace_dir,
         &model_name,
         &tool_descs,
         &skills,
         Some(&config.identity),
-        bootstrap_max_chars,
+        prompt_profile.bootstrap_max_chars,
         native_tools,
         config.skills.prompt_injection_mode,
+        &prompt_profile.sections,
     );
-    if !native_tools {
+    if !native_tools && prompt_profile.sections.tools {
         system_prompt.push_str(&build_tool_instructions(&tools_registry));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
 
     let mem_context = build_context(mem.as_ref(), message, config.memory.min_relevance_score).await;
-    let rag_limit = if config.agent.compact_context { 2 } else { 5 };
+    let rag_limit = prompt_profile.rag_chunk_limit;
     let hw_context = hardware_rag
         .as_ref()
         .map(|r| build_hardware_context(r, message, &board_names, rag_limit))
         .unwrap_or_default();
     let context = format!("{mem_context}{hw_context}");
-    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+    let now = crate::config::timezone::transcript_stamp_now();
     let enriched = if context.is_empty() {
         format!("[{now}] {message}")
     } else {
@@ -2529,7 +2582,7 @@ mod tests {
     use crate::memory::{Memory, MemoryCategory, SqliteMemory};
     use crate::observability::NoopObserver;
     use crate::providers::traits::ProviderCapabilities;
-    use crate::providers::ChatResponse;
+    use crate::providers::{ChatOutcome, ChatResponse};
     use tempfile::TempDir;
 
     struct NonVisionProvider {
@@ -2595,6 +2648,7 @@ mod tests {
                 tool_calls: Vec::new(),
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             })
         }
     }
@@ -2613,6 +2667,7 @@ mod tests {
                     tool_calls: Vec::new(),
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 })
                 .collect();
             Self {
@@ -2867,6 +2922,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            max_dimension_px: None,
         };
 
         let err = run_tool_call_loop(
@@ -3198,6 +3254,7 @@ mod tests {
                 reply_target: "chat-approval".to_string(),
                 prompt_tx,
             }),
+            None,
             &crate::config::MultimodalConfig::default(),
             4,
             None,
@@ -1,6 +1,7 @@
 #[allow(clippy::module_inception)]
 pub mod agent;
 pub mod classifier;
+pub mod delegates;
 pub mod dispatcher;
 pub mod loop_;
 pub mod memory_loader;
@@ -3,7 +3,6 @@ use crate::identity;
 use crate::skills::Skill;
 use crate::tools::Tool;
 use anyhow::Result;
-use chrono::Local;
 use std::fmt::Write;
 use std::path::Path;
 
@@ -211,11 +210,9 @@ impl PromptSection for DateTimeSection {
     }
 
     fn build(&self, _ctx: &PromptContext<'_>) -> Result<String> {
-        let now = Local::now();
         Ok(format!(
-            "## Current Date & Time\n\n{} ({})",
-            now.format("%Y-%m-%d %H:%M:%S"),
-            now.format("%Z")
+            "## Current Date & Time\n\n{}",
+            crate::config::timezone::prompt_datetime_now()
         ))
     }
 }
@@ -493,9 +490,18 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: None,
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
 
         let ctx = PromptContext {
@@ -531,9 +537,18 @@ mod tests {
                 kind: "shell".into(),
                 command: "echo ok".into(),
                 args: std::collections::HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Run smoke tests before deploy.".into()],
             location: Some(Path::new("/tmp/workspace/skills/deploy/SKILL.md").to_path_buf()),
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
 
         let ctx = PromptContext {
@@ -591,9 +606,18 @@ mod tests {
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: std::collections::HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
         let ctx = PromptContext {
             workspace_dir: Path::new("/tmp/workspace"),
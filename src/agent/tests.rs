@@ -32,8 +32,8 @@ use crate::config::{AgentConfig, MemoryConfig};
 use crate::memory::{self, Memory};
 use crate::observability::{NoopObserver, Observer};
 use crate::providers::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ToolCall,
-    ToolResultMessage,
+    ChatMessage, ChatOutcome, ChatRequest, ChatResponse, ConversationMessage, Provider,
+    RefusedError, ToolCall, ToolResultMessage,
 };
 use crate::tools::{Tool, ToolResult};
 use anyhow::Result;
@@ -95,6 +95,7 @@ impl Provider for ScriptedProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
         Ok(guard.remove(0))
@@ -332,6 +333,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
         tool_calls: calls,
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -342,6 +344,7 @@ fn text_response(text: &str) -> ChatResponse {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -354,6 +357,7 @@ fn xml_tool_response(name: &str, args: &str) -> ChatResponse {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -744,6 +748,7 @@ async fn turn_handles_empty_text_response() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -759,6 +764,7 @@ async fn turn_handles_none_text_response() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }]));
 
     let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
@@ -768,6 +774,24 @@ async fn turn_handles_none_text_response() {
     assert!(response.is_empty());
 }
 
+#[tokio::test]
+async fn turn_ends_with_refused_error_on_refusal() {
+    let provider = Box::new(ScriptedProvider::new(vec![ChatResponse::refused(
+        ChatOutcome::Refused {
+            reason: "content_filter".into(),
+        },
+        None,
+    )]));
+
+    let mut agent = build_agent_with(provider, vec![], Box::new(NativeToolDispatcher));
+
+    let err = agent.turn("hi").await.unwrap_err();
+    assert_eq!(
+        RefusedError::find(&err).map(|refused| refused.reason.as_str()),
+        Some("content_filter")
+    );
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // 12. Mixed text + tool call responses
 // ═══════════════════════════════════════════════════════════════════════════
@@ -784,6 +808,7 @@ async fn turn_preserves_text_alongside_tool_calls() {
             }],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         },
         text_response("Here are the results"),
     ]));
@@ -1022,6 +1047,7 @@ async fn native_dispatcher_handles_stringified_arguments() {
         }],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     let (_, calls) = dispatcher.parse_response(&response);
@@ -1049,6 +1075,7 @@ fn xml_dispatcher_handles_nested_json() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1068,6 +1095,7 @@ fn xml_dispatcher_handles_empty_tool_call_tag() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     let dispatcher = XmlToolDispatcher;
@@ -1083,6 +1111,7 @@ fn xml_dispatcher_handles_unclosed_tool_call() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     let dispatcher = XmlToolDispatcher;
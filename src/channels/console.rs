@@ -0,0 +1,181 @@
+//! Console output of `zeroclaw channel start`.
+//!
+//! `--output pretty` (the default) prints the emoji lines meant for a human
+//! watching the terminal. `--output jsonl` prints one JSON object per event
+//! on stdout instead, so the server can be piped into `jq` or a log shipper;
+//! the startup banner and other decorative lines are suppressed so stdout
+//! carries nothing but events.
+//!
+//! Message bodies follow the same preview rules in both modes: a truncated,
+//! secret-scrubbed preview, or the full scrubbed body with `--debug`.
+//!
+//! `[observability] console_echo = false` silences both: events are logged
+//! through tracing instead, and decorative lines are dropped, so a systemd
+//! deployment's journal carries log lines only.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Format of the channel server's console output (`--output`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable lines with emoji markers.
+    #[default]
+    Pretty,
+    /// One JSON object per event, one event per line.
+    Jsonl,
+}
+
+static JSONL: AtomicBool = AtomicBool::new(false);
+static ECHO: AtomicBool = AtomicBool::new(true);
+
+pub(super) fn set_format(format: OutputFormat) {
+    JSONL.store(format == OutputFormat::Jsonl, Ordering::Relaxed);
+}
+
+/// `[observability] console_echo`.
+pub(super) fn set_echo(echo: bool) {
+    ECHO.store(echo, Ordering::Relaxed);
+}
+
+/// Whether stdout is reserved for JSONL events.
+pub(super) fn jsonl() -> bool {
+    JSONL.load(Ordering::Relaxed)
+}
+
+/// Whether decorative, human-only lines (progress notes) should be printed.
+pub(super) fn pretty() -> bool {
+    !jsonl() && ECHO.load(Ordering::Relaxed)
+}
+
+/// An event of a single inbound message's lifecycle.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub(super) enum Event<'a> {
+    MessageReceived {
+        channel: &'a str,
+        sender: &'a str,
+        message_id: &'a str,
+        preview: String,
+    },
+    ReplySent {
+        channel: &'a str,
+        sender: &'a str,
+        elapsed_ms: u128,
+        preview: String,
+    },
+    Error {
+        channel: &'a str,
+        sender: &'a str,
+        elapsed_ms: u128,
+        error_class: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        request_id: Option<&'a str>,
+        message: String,
+    },
+}
+
+impl Event<'_> {
+    fn pretty_line(&self) -> String {
+        match self {
+            Self::MessageReceived {
+                channel,
+                sender,
+                preview,
+                ..
+            } => format!("  💬 [{channel}] from {sender}: {preview}"),
+            Self::ReplySent {
+                elapsed_ms,
+                preview,
+                ..
+            } => format!("  🤖 Reply ({elapsed_ms}ms): {preview}"),
+            Self::Error {
+                elapsed_ms,
+                message,
+                ..
+            } => format!("  ❌ {message} (elapsed: {elapsed_ms}ms)"),
+        }
+    }
+
+    fn json_line(&self, at: DateTime<Utc>) -> String {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.insert(
+                "ts".into(),
+                at.to_rfc3339_opts(SecondsFormat::Millis, true).into(),
+            );
+        }
+        value.to_string()
+    }
+}
+
+/// Print `event` in the configured format. Pretty errors go to stderr;
+/// JSONL events all go to stdout. With echo off, the event is logged instead.
+pub(super) fn emit(event: &Event<'_>) {
+    let is_error = matches!(event, Event::Error { .. });
+    if !ECHO.load(Ordering::Relaxed) {
+        let fields = serde_json::to_string(event).unwrap_or_default();
+        if is_error {
+            tracing::warn!("channel event: {fields}");
+        } else {
+            tracing::info!("channel event: {fields}");
+        }
+    } else if jsonl() {
+        println!("{}", event.json_line(Utc::now()));
+    } else if is_error {
+        eprintln!("{}", event.pretty_line());
+    } else {
+        println!("{}", event.pretty_line());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn received_message_is_one_well_formed_json_line() {
+        let event = Event::MessageReceived {
+            channel: "telegram",
+            sender: "alice",
+            message_id: "msg-1",
+            preview: super::super::channel_log_body("use key sk-abcdefghijklmnopqrstuvwx", 80),
+        };
+        let at = Utc.with_ymd_and_hms(2026, 3, 8, 7, 5, 0).unwrap();
+        let line = event.json_line(at);
+        assert!(!line.contains('\n'));
+
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["event"], "message_received");
+        assert_eq!(parsed["ts"], "2026-03-08T07:05:00.000Z");
+        assert_eq!(parsed["channel"], "telegram");
+        assert_eq!(parsed["sender"], "alice");
+        assert_eq!(parsed["message_id"], "msg-1");
+        let preview = parsed["preview"].as_str().unwrap();
+        assert!(preview.starts_with("use key "), "{preview}");
+        assert!(!preview.contains("abcdefghijklmnopqrstuvwx"), "{preview}");
+    }
+
+    #[test]
+    fn error_events_keep_class_and_skip_missing_request_id() {
+        let event = Event::Error {
+            channel: "discord",
+            sender: "bob",
+            elapsed_ms: 1200,
+            error_class: "timeout",
+            request_id: None,
+            message: "LLM response timed out after 60s".into(),
+        };
+        let parsed: serde_json::Value = serde_json::from_str(&event.json_line(Utc::now())).unwrap();
+        assert_eq!(parsed["event"], "error");
+        assert_eq!(parsed["error_class"], "timeout");
+        assert_eq!(parsed["elapsed_ms"], 1200);
+        assert!(parsed.get("request_id").is_none());
+        assert_eq!(
+            event.pretty_line(),
+            "  ❌ LLM response timed out after 60s (elapsed: 1200ms)"
+        );
+    }
+}
@@ -1,11 +1,13 @@
+use super::slash_commands::{self, SlashCommand};
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::ReplyStyle;
 use anyhow::Context;
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
 use parking_lot::Mutex;
 use reqwest::multipart::{Form, Part};
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
@@ -19,9 +21,33 @@ pub struct DiscordChannel {
     mention_only: bool,
     group_reply_allowed_sender_ids: Vec<String>,
     workspace_dir: Option<PathBuf>,
+    slash_commands: Vec<SlashCommand>,
     typing_handles: Mutex<HashMap<String, tokio::task::JoinHandle<()>>>,
+    reply_style: ReplyStyle,
+    threads: Mutex<DiscordThreads>,
+    api_base: String,
 }
 
+const DISCORD_API_BASE: &str = "https://discord.com/api/v10";
+
+/// Threads that `thread-reply` opens on triggering messages. A thread started
+/// from a message takes the message's ID as its channel ID, so the message ID
+/// doubles as the thread ID.
+#[derive(Default)]
+struct DiscordThreads {
+    /// Messages awaiting their reply thread, with the thread name to use.
+    pending: HashMap<String, String>,
+    /// Threads opened by the bot.
+    started: HashSet<String>,
+}
+
+/// Pending thread names kept at most; messages that never get a reply would
+/// otherwise accumulate.
+const DISCORD_MAX_PENDING_THREADS: usize = 256;
+
+/// Discord's maximum thread name length.
+const DISCORD_MAX_THREAD_NAME_LENGTH: usize = 100;
+
 impl DiscordChannel {
     pub fn new(
         bot_token: String,
@@ -38,10 +64,26 @@ impl DiscordChannel {
             mention_only,
             group_reply_allowed_sender_ids: Vec::new(),
             workspace_dir: None,
+            slash_commands: Vec::new(),
             typing_handles: Mutex::new(HashMap::new()),
+            reply_style: ReplyStyle::TopLevel,
+            threads: Mutex::new(DiscordThreads::default()),
+            api_base: DISCORD_API_BASE.to_string(),
         }
     }
 
+    /// Configure how replies attach to the triggering message.
+    pub fn with_reply_style(mut self, reply_style: ReplyStyle) -> Self {
+        self.reply_style = reply_style;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
     /// Configure sender IDs that bypass mention gating in guild channels.
     pub fn with_group_reply_allowed_senders(mut self, sender_ids: Vec<String>) -> Self {
         self.group_reply_allowed_sender_ids = normalize_group_reply_allowed_sender_ids(sender_ids);
@@ -54,10 +96,107 @@ impl DiscordChannel {
         self
     }
 
+    /// Slash commands to register once the gateway session is ready.
+    pub fn with_slash_commands(mut self, commands: Vec<SlashCommand>) -> Self {
+        self.slash_commands = commands;
+        self
+    }
+
     fn http_client(&self) -> reqwest::Client {
         crate::config::build_runtime_proxy_client("channel.discord")
     }
 
+    /// Replace the application's commands with the configured set, in
+    /// `guild_id` when set (available at once) or globally.
+    async fn register_slash_commands(&self, application_id: &str) -> anyhow::Result<()> {
+        let url = match &self.guild_id {
+            Some(guild_id) => format!(
+                "https://discord.com/api/v10/applications/{application_id}/guilds/{guild_id}/commands"
+            ),
+            None => format!("https://discord.com/api/v10/applications/{application_id}/commands"),
+        };
+        let response = self
+            .http_client()
+            .put(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&slash_commands::discord_command_definitions(
+                &self.slash_commands,
+            ))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Discord command registration failed ({status}): {body}");
+        }
+        Ok(())
+    }
+
+    /// Answer an interaction within Discord's 3-second window, echoing the
+    /// command; the agent's reply follows as a normal channel message.
+    async fn acknowledge_interaction(
+        &self,
+        invocation: &slash_commands::DiscordInvocation,
+    ) -> anyhow::Result<()> {
+        let echo = format!("`/{}` {}", invocation.name, invocation.text);
+        let url = format!(
+            "https://discord.com/api/v10/interactions/{}/{}/callback",
+            invocation.interaction_id, invocation.token
+        );
+        let response = self
+            .http_client()
+            .post(&url)
+            .json(&json!({"type": 4, "data": {"content": echo.trim_end()}}))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "Discord interaction callback failed ({})",
+                response.status()
+            );
+        }
+        Ok(())
+    }
+
+    /// Turn an `INTERACTION_CREATE` payload into a channel message, after
+    /// the same sender and guild checks as ordinary messages.
+    async fn interaction_message(&self, d: &serde_json::Value) -> Option<ChannelMessage> {
+        let invocation = slash_commands::parse_discord_interaction(d)?;
+        if !self.is_user_allowed(&invocation.user_id) {
+            tracing::warn!(
+                "Discord: ignoring /{} from unauthorized user: {}",
+                invocation.name,
+                invocation.user_id
+            );
+            return None;
+        }
+        if let (Some(filter), Some(guild)) = (&self.guild_id, &invocation.guild_id) {
+            if filter != guild {
+                return None;
+            }
+        }
+        let content = slash_commands::invocation_content(
+            &self.slash_commands,
+            &invocation.name,
+            &invocation.text,
+        )?;
+        if let Err(err) = self.acknowledge_interaction(&invocation).await {
+            tracing::warn!("Discord: {err}");
+        }
+        Some(ChannelMessage {
+            id: format!("discord_interaction_{}", invocation.interaction_id),
+            sender: invocation.user_id,
+            reply_target: invocation.channel_id,
+            content,
+            channel: "discord".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+        })
+    }
+
     /// Check if a Discord user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -81,6 +220,93 @@ impl DiscordChannel {
         base64_decode(part)
     }
 
+    /// Thread of an inbound `MESSAGE_CREATE` payload. Messages inside a thread
+    /// (those carrying `position`, or sent in a thread the bot opened) belong
+    /// to it; with `thread-reply`, a guild message outside one gets a new
+    /// thread named after its first line.
+    fn inbound_thread_ts(&self, d: &serde_json::Value, content: &str) -> Option<String> {
+        let message_id = d.get("id").and_then(|i| i.as_str()).unwrap_or("");
+        let channel_id = d.get("channel_id").and_then(|c| c.as_str()).unwrap_or("");
+        let mut threads = self.threads.lock();
+        if !channel_id.is_empty()
+            && (d.get("position").is_some() || threads.started.contains(channel_id))
+        {
+            return Some(channel_id.to_string());
+        }
+        if self.reply_style != ReplyStyle::ThreadReply
+            || message_id.is_empty()
+            || d.get("guild_id").is_none()
+        {
+            return None;
+        }
+        if threads.pending.len() >= DISCORD_MAX_PENDING_THREADS {
+            threads.pending.clear();
+        }
+        threads
+            .pending
+            .insert(message_id.to_string(), discord_thread_name(content));
+        Some(message_id.to_string())
+    }
+
+    /// Channel to post `message` to, and the message it should quote.
+    /// Answers to a message awaiting its thread open the thread first; if
+    /// that fails they quote the message instead.
+    async fn reply_destination(&self, message: &SendMessage) -> (String, Option<String>) {
+        if let Some(thread) = message
+            .thread_ts
+            .as_deref()
+            .filter(|thread| *thread != message.recipient)
+        {
+            let pending = self.threads.lock().pending.remove(thread);
+            let Some(name) = pending else {
+                return (thread.to_string(), None);
+            };
+            match self.start_thread(&message.recipient, thread, &name).await {
+                Ok(()) => {
+                    self.threads.lock().started.insert(thread.to_string());
+                    return (thread.to_string(), None);
+                }
+                Err(err) => {
+                    tracing::warn!("Discord: could not start a reply thread: {err}");
+                    return (message.recipient.clone(), Some(thread.to_string()));
+                }
+            }
+        }
+        let reference = if self.reply_style == ReplyStyle::InlineReply {
+            message.reply_to.as_deref().and_then(discord_message_id)
+        } else {
+            None
+        };
+        (message.recipient.clone(), reference.map(str::to_string))
+    }
+
+    /// Open a public thread on `message_id` in `channel_id`.
+    async fn start_thread(
+        &self,
+        channel_id: &str,
+        message_id: &str,
+        name: &str,
+    ) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/channels/{channel_id}/messages/{message_id}/threads",
+            self.api_base
+        );
+        let resp = self
+            .http_client()
+            .post(&url)
+            .header("Authorization", format!("Bot {}", self.bot_token))
+            .json(&json!({ "name": name, "auto_archive_duration": 1440 }))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let err = resp.text().await.unwrap_or_default();
+            let sanitized = crate::providers::sanitize_api_error(&err);
+            anyhow::bail!("Discord start thread failed ({status}): {sanitized}");
+        }
+        Ok(())
+    }
+
     fn resolve_local_attachment_path(&self, target: &str) -> anyhow::Result<PathBuf> {
         let workspace = self.workspace_dir.as_ref().ok_or_else(|| {
             anyhow::anyhow!("workspace_dir is not configured; local file attachments are disabled")
@@ -118,6 +344,36 @@ impl DiscordChannel {
     }
 }
 
+/// Discord message ID of a [`ChannelMessage::id`] (`discord_<id>`). Slash
+/// command interactions have no message to reference.
+fn discord_message_id(id: &str) -> Option<&str> {
+    id.strip_prefix("discord_")
+        .filter(|raw| !raw.is_empty() && raw.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Thread name for a triggering message: its first non-empty line, cut to
+/// Discord's limit.
+fn discord_thread_name(content: &str) -> String {
+    let line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("Conversation");
+    line.chars().take(DISCORD_MAX_THREAD_NAME_LENGTH).collect()
+}
+
+/// Message body, quoting `reference` when set.
+fn discord_message_payload(content: &str, reference: Option<&str>) -> serde_json::Value {
+    let mut body = json!({ "content": content });
+    if let Some(message_id) = reference {
+        body["message_reference"] = json!({
+            "message_id": message_id,
+            "fail_if_not_exists": false,
+        });
+    }
+    body
+}
+
 fn normalize_group_reply_allowed_sender_ids(sender_ids: Vec<String>) -> Vec<String> {
     let mut normalized = sender_ids
         .into_iter()
@@ -303,12 +559,14 @@ fn with_inline_attachment_urls(
 
 async fn send_discord_message_json(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     recipient: &str,
     content: &str,
+    reference: Option<&str>,
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
-    let body = json!({ "content": content });
+    let url = format!("{api_base}/channels/{recipient}/messages");
+    let body = discord_message_payload(content, reference);
 
     let resp = client
         .post(&url)
@@ -332,14 +590,19 @@ async fn send_discord_message_json(
 
 async fn send_discord_message_with_files(
     client: &reqwest::Client,
+    api_base: &str,
     bot_token: &str,
     recipient: &str,
     content: &str,
+    reference: Option<&str>,
     files: &[PathBuf],
 ) -> anyhow::Result<()> {
-    let url = format!("https://discord.com/api/v10/channels/{recipient}/messages");
+    let url = format!("{api_base}/channels/{recipient}/messages");
 
-    let mut form = Form::new().text("payload_json", json!({ "content": content }).to_string());
+    let mut form = Form::new().text(
+        "payload_json",
+        discord_message_payload(content, reference).to_string(),
+    );
 
     for (idx, path) in files.iter().enumerate() {
         let bytes = tokio::fs::read(path).await.map_err(|error| {
@@ -606,20 +869,32 @@ impl Channel for DiscordChannel {
             with_inline_attachment_urls(&cleaned_content, &remote_urls, &unresolved_markers);
         let chunks = split_message_for_discord(&content);
         let client = self.http_client();
+        let (recipient, reference) = self.reply_destination(message).await;
 
         for (i, chunk) in chunks.iter().enumerate() {
+            // Only the first chunk quotes the triggering message.
+            let reference = reference.as_deref().filter(|_| i == 0);
             if i == 0 && !local_files.is_empty() {
                 send_discord_message_with_files(
                     &client,
+                    &self.api_base,
                     &self.bot_token,
-                    &message.recipient,
+                    &recipient,
                     chunk,
+                    reference,
                     &local_files,
                 )
                 .await?;
             } else {
-                send_discord_message_json(&client, &self.bot_token, &message.recipient, chunk)
-                    .await?;
+                send_discord_message_json(
+                    &client,
+                    &self.api_base,
+                    &self.bot_token,
+                    &recipient,
+                    chunk,
+                    reference,
+                )
+                .await?;
             }
 
             if i < chunks.len() - 1 {
@@ -754,8 +1029,34 @@ impl Channel for DiscordChannel {
                         _ => {}
                     }
 
-                    // Only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     let event_type = event.get("t").and_then(|t| t.as_str()).unwrap_or("");
+                    if event_type == "READY" && !self.slash_commands.is_empty() {
+                        let application_id = event
+                            .pointer("/d/application/id")
+                            .and_then(serde_json::Value::as_str)
+                            .unwrap_or_default();
+                        match self.register_slash_commands(application_id).await {
+                            Ok(()) => tracing::info!(
+                                "Discord: registered {} slash command(s)",
+                                self.slash_commands.len()
+                            ),
+                            Err(err) => tracing::warn!("Discord: {err}"),
+                        }
+                        continue;
+                    }
+                    if event_type == "INTERACTION_CREATE" {
+                        let Some(d) = event.get("d") else {
+                            continue;
+                        };
+                        if let Some(channel_msg) = self.interaction_message(d).await {
+                            if tx.send(channel_msg).await.is_err() {
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Otherwise only handle MESSAGE_CREATE (opcode 0, type "MESSAGE_CREATE")
                     if event_type != "MESSAGE_CREATE" {
                         continue;
                     }
@@ -803,6 +1104,7 @@ impl Channel for DiscordChannel {
                     else {
                         continue;
                     };
+                    let thread_ts = self.inbound_thread_ts(d, &clean_content);
 
                     let attachment_text = {
                         let atts = d
@@ -870,7 +1172,7 @@ impl Channel for DiscordChannel {
                             .duration_since(std::time::UNIX_EPOCH)
                             .unwrap_or_default()
                             .as_secs(),
-                        thread_ts: None,
+                        thread_ts,
                     };
 
                     if tx.send(channel_msg).await.is_err() {
@@ -1694,4 +1996,151 @@ mod tests {
         let escaped = channel.resolve_local_attachment_path(outside.to_string_lossy().as_ref());
         assert!(escaped.is_err(), "path outside workspace must be rejected");
     }
+
+    async fn discord_mock() -> wiremock::MockServer {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "id": "999" })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn inbound(channel: &DiscordChannel, d: &serde_json::Value) -> ChannelMessage {
+        ChannelMessage {
+            id: format!("discord_{}", d["id"].as_str().unwrap()),
+            sender: "42".into(),
+            reply_target: d["channel_id"].as_str().unwrap().into(),
+            content: d["content"].as_str().unwrap().into(),
+            channel: "discord".into(),
+            timestamp: 1,
+            thread_ts: channel.inbound_thread_ts(d, d["content"].as_str().unwrap()),
+        }
+    }
+
+    #[tokio::test]
+    async fn thread_reply_opens_a_thread_on_the_triggering_message() {
+        let server = discord_mock().await;
+        let channel = DiscordChannel::new("fake".into(), None, vec!["*".into()], false, false)
+            .with_reply_style(ReplyStyle::ThreadReply)
+            .with_api_base(server.uri());
+        let msg = inbound(
+            &channel,
+            &json!({
+                "id": "111",
+                "channel_id": "900",
+                "guild_id": "1",
+                "content": "How do I rotate keys?\nDetails follow",
+            }),
+        );
+        assert_eq!(msg.thread_ts.as_deref(), Some("111"));
+
+        channel
+            .send(&SendMessage::reply("first", &msg))
+            .await
+            .unwrap();
+        channel
+            .send(&SendMessage::reply("second", &msg))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let paths: Vec<&str> = requests.iter().map(|r| r.url.path()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/channels/900/messages/111/threads",
+                "/channels/111/messages",
+                "/channels/111/messages"
+            ]
+        );
+        let thread: serde_json::Value = requests[0].body_json().unwrap();
+        assert_eq!(thread["name"], "How do I rotate keys?");
+        let reply: serde_json::Value = requests[1].body_json().unwrap();
+        assert_eq!(reply["content"], "first");
+        assert!(reply.get("message_reference").is_none());
+
+        // Follow-ups inside the thread stay in it.
+        let follow_up = inbound(
+            &channel,
+            &json!({ "id": "112", "channel_id": "111", "guild_id": "1", "content": "thanks" }),
+        );
+        assert_eq!(follow_up.thread_ts.as_deref(), Some("111"));
+    }
+
+    #[tokio::test]
+    async fn inline_reply_quotes_the_triggering_message() {
+        let server = discord_mock().await;
+        let channel = DiscordChannel::new("fake".into(), None, vec!["*".into()], false, false)
+            .with_reply_style(ReplyStyle::InlineReply)
+            .with_api_base(server.uri());
+        let msg = inbound(
+            &channel,
+            &json!({ "id": "111", "channel_id": "900", "guild_id": "1", "content": "hi" }),
+        );
+        assert_eq!(msg.thread_ts, None);
+
+        channel
+            .send(&SendMessage::reply("hello", &msg))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].url.path(), "/channels/900/messages");
+        let body: serde_json::Value = requests[0].body_json().unwrap();
+        assert_eq!(body["message_reference"]["message_id"], "111");
+        assert_eq!(body["message_reference"]["fail_if_not_exists"], false);
+    }
+
+    #[tokio::test]
+    async fn top_level_replies_stay_in_existing_threads_only() {
+        let server = discord_mock().await;
+        let channel = DiscordChannel::new("fake".into(), None, vec!["*".into()], false, false)
+            .with_api_base(server.uri());
+        let top = inbound(
+            &channel,
+            &json!({ "id": "111", "channel_id": "900", "guild_id": "1", "content": "hi" }),
+        );
+        let threaded = inbound(
+            &channel,
+            &json!({
+                "id": "113",
+                "channel_id": "800",
+                "guild_id": "1",
+                "position": 3,
+                "content": "in a thread",
+            }),
+        );
+        assert_eq!(top.thread_ts, None);
+        assert_eq!(threaded.thread_ts.as_deref(), Some("800"));
+
+        channel.send(&SendMessage::reply("a", &top)).await.unwrap();
+        channel
+            .send(&SendMessage::reply("b", &threaded))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let paths: Vec<&str> = requests.iter().map(|r| r.url.path()).collect();
+        assert_eq!(paths, ["/channels/900/messages", "/channels/800/messages"]);
+        for request in &requests {
+            let body: serde_json::Value = request.body_json().unwrap();
+            assert!(body.get("message_reference").is_none());
+        }
+    }
+
+    #[test]
+    fn slash_command_interactions_have_no_message_to_quote() {
+        assert_eq!(discord_message_id("discord_111"), Some("111"));
+        assert_eq!(discord_message_id("discord_interaction_5"), None);
+        assert_eq!(discord_thread_name("\n  \n"), "Conversation");
+        assert_eq!(
+            discord_thread_name(&"x".repeat(150)).chars().count(),
+            DISCORD_MAX_THREAD_NAME_LENGTH
+        );
+    }
 }
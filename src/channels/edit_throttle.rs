@@ -0,0 +1,108 @@
+//! Scheduling for streamed-reply draft edits.
+//!
+//! Channels that can edit a sent message show a streamed reply as one
+//! placeholder that is edited as text arrives. Platforms rate-limit edits, so
+//! the draft updater pushes at most one edit per interval. Text that arrives in
+//! between is held back and flushed at [`EditThrottle::deadline`], so a pause in
+//! the stream (e.g. a tool call) never leaves a stale draft on screen.
+
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[derive(Debug)]
+pub struct EditThrottle {
+    interval: Duration,
+    last_edit: Option<Instant>,
+    pending: bool,
+}
+
+impl EditThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_edit: None,
+            pending: false,
+        }
+    }
+
+    /// Record that the draft text changed at `now`. Returns true when the edit
+    /// may be pushed right away; otherwise it is held until [`Self::deadline`].
+    pub fn offer(&mut self, now: Instant) -> bool {
+        self.pending = true;
+        self.last_edit
+            .is_none_or(|last| now >= last + self.interval)
+    }
+
+    /// When held-back text is due, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.last_edit
+            .filter(|_| self.pending)
+            .map(|last| last + self.interval)
+    }
+
+    /// Whether text changed since the last pushed edit.
+    pub fn has_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Record that an edit finished at `now`. Measuring from completion keeps
+    /// slow edits from being followed immediately by the next one.
+    pub fn edited(&mut self, now: Instant) {
+        self.last_edit = Some(now);
+        self.pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(1000);
+
+    #[test]
+    fn first_edit_is_immediate_and_later_ones_are_held() {
+        let start = Instant::now();
+        let mut throttle = EditThrottle::new(INTERVAL);
+        assert_eq!(throttle.deadline(), None);
+
+        assert!(throttle.offer(start));
+        throttle.edited(start);
+        assert!(!throttle.has_pending());
+        assert_eq!(throttle.deadline(), None);
+
+        // Two more deltas inside the interval collapse into one pending edit.
+        assert!(!throttle.offer(start + Duration::from_millis(200)));
+        assert!(!throttle.offer(start + Duration::from_millis(600)));
+        assert!(throttle.has_pending());
+        assert_eq!(throttle.deadline(), Some(start + INTERVAL));
+
+        throttle.edited(start + INTERVAL);
+        assert_eq!(throttle.deadline(), None);
+    }
+
+    #[test]
+    fn offer_after_the_interval_is_immediate() {
+        let start = Instant::now();
+        let mut throttle = EditThrottle::new(INTERVAL);
+        assert!(throttle.offer(start));
+        throttle.edited(start);
+
+        assert!(throttle.offer(start + INTERVAL));
+        assert!(throttle.offer(start + Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn interval_is_measured_from_edit_completion() {
+        let start = Instant::now();
+        let mut throttle = EditThrottle::new(INTERVAL);
+        assert!(throttle.offer(start));
+        // The edit request itself took 400ms.
+        throttle.edited(start + Duration::from_millis(400));
+
+        assert!(!throttle.offer(start + INTERVAL));
+        assert_eq!(
+            throttle.deadline(),
+            Some(start + Duration::from_millis(1400))
+        );
+    }
+}
@@ -0,0 +1,399 @@
+//! What channel users and webhook callers see when a request fails.
+//!
+//! Provider errors are raw ("OpenRouter API error (429 Too Many Requests):
+//! {...}") and can carry request IDs and internal URLs. Instead of relaying
+//! them, the failure is classified with the same [`ErrorClass`] the retry
+//! loop uses and answered with a short template for that class, plus a
+//! reference ID. The full error is logged under that ID and never sent.
+//!
+//! Built-in templates exist for `en` and `vi` (`[channels_config]
+//! error_language`); `[channels_config.error_templates.<lang>]` overrides
+//! them per class. A template may use `{capability}` (for `unsupported`) and
+//! `{request_id}`; without the latter a reference line is appended. Refusals
+//! (`refused`) are the model declining, not a failure: they are logged as a
+//! warning and their reply carries no reference line.
+
+use crate::config::ChannelsConfig;
+use crate::providers::reliable::{classify_error, ErrorClass};
+use crate::providers::{sanitize_api_error, ProviderCapabilityError};
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// A rendered error reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReply {
+    pub class: ErrorClass,
+    /// Short ID quoted to the user and attached to the logged error.
+    pub request_id: String,
+    pub text: String,
+}
+
+/// Templates for the configured language, with overrides applied.
+#[derive(Debug, Clone)]
+pub struct ErrorReplies {
+    language: String,
+    overrides: HashMap<ErrorClass, String>,
+}
+
+impl Default for ErrorReplies {
+    fn default() -> Self {
+        Self {
+            language: DEFAULT_LANGUAGE.into(),
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl ErrorReplies {
+    pub fn from_config(config: &ChannelsConfig) -> Result<Self> {
+        Self::compile(&config.error_language, &config.error_templates)
+    }
+
+    /// Validate every override (for every language, not just the active
+    /// one) and keep those for `language`.
+    pub fn compile(
+        language: &str,
+        templates: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<Self> {
+        let language = language.trim().to_ascii_lowercase();
+        let mut overrides = HashMap::new();
+        for (lang, by_class) in templates {
+            for (key, template) in by_class {
+                let Some(class) = ErrorClass::parse(key) else {
+                    let known: Vec<&str> = ErrorClass::ALL.iter().map(|c| c.as_str()).collect();
+                    bail!(
+                        "channels_config.error_templates.{lang}.{key} is not an error class (expected one of: {})",
+                        known.join(", ")
+                    );
+                };
+                if template.trim().is_empty() {
+                    bail!("channels_config.error_templates.{lang}.{key} is empty");
+                }
+                if lang.trim().eq_ignore_ascii_case(&language) {
+                    overrides.insert(class, template.trim().to_string());
+                }
+            }
+        }
+        Ok(Self {
+            language: if language.is_empty() {
+                DEFAULT_LANGUAGE.into()
+            } else {
+                language
+            },
+            overrides,
+        })
+    }
+
+    /// Classify `err`, log it in full under a fresh reference ID, and build
+    /// the reply for the user.
+    pub fn render(&self, err: &anyhow::Error) -> ErrorReply {
+        self.render_as(err, new_request_id())
+    }
+
+    /// [`Self::render`] under a reference ID the caller already handed out
+    /// (the gateway's `X-Request-Id`).
+    pub fn render_as(&self, err: &anyhow::Error, request_id: String) -> ErrorReply {
+        let class = classify(err);
+        if class == ErrorClass::Refused {
+            tracing::warn!(
+                request_id = %request_id,
+                class = class.as_str(),
+                "Request refused: {}",
+                sanitize_api_error(&format!("{err:#}"))
+            );
+        } else {
+            tracing::error!(
+                request_id = %request_id,
+                class = class.as_str(),
+                "Request failed: {}",
+                sanitize_api_error(&format!("{err:#}"))
+            );
+        }
+        let capability = err
+            .downcast_ref::<ProviderCapabilityError>()
+            .map(|e| e.capability.as_str());
+        let text = self.text(class, capability, &request_id);
+        ErrorReply {
+            class,
+            request_id,
+            text,
+        }
+    }
+
+    fn text(&self, class: ErrorClass, capability: Option<&str>, request_id: &str) -> String {
+        let template = self
+            .overrides
+            .get(&class)
+            .map_or_else(|| builtin(&self.language, class), String::as_str);
+        let mut text = template.replace("{capability}", capability.unwrap_or("this request"));
+        if text.contains("{request_id}") {
+            text = text.replace("{request_id}", request_id);
+        } else if class != ErrorClass::Refused {
+            text.push('\n');
+            text.push_str(&reference_line(&self.language, request_id));
+        }
+        text
+    }
+}
+
+/// Agent-loop stops first (they are not provider errors), then the
+/// provider classification shared with the retry loop.
+pub fn classify(err: &anyhow::Error) -> ErrorClass {
+    if crate::agent::loop_::is_loop_detection_error(err)
+        || crate::agent::loop_::is_tool_iteration_limit_error(err)
+    {
+        return ErrorClass::ToolFailure;
+    }
+    classify_error(err)
+}
+
+/// A short reference ID for one failed request.
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+fn reference_line(language: &str, request_id: &str) -> String {
+    match language {
+        "vi" => format!("Mã tham chiếu: {request_id}"),
+        _ => format!("Reference: {request_id}"),
+    }
+}
+
+fn builtin(language: &str, class: ErrorClass) -> &'static str {
+    match language {
+        "vi" => builtin_vi(class),
+        _ => builtin_en(class),
+    }
+}
+
+fn builtin_en(class: ErrorClass) -> &'static str {
+    match class {
+        ErrorClass::RateLimited => {
+            "⏳ I'm being rate limited right now — try again in about a minute."
+        }
+        ErrorClass::Auth => {
+            "🔑 My AI service rejected my credentials. Please let whoever runs this bot know."
+        }
+        ErrorClass::Network => {
+            "📡 I couldn't reach my AI service. Please try again in a moment."
+        }
+        ErrorClass::Overloaded => {
+            "🚧 My AI service is busy or having trouble. Please try again in a few minutes."
+        }
+        ErrorClass::BudgetExceeded => {
+            "💳 I've reached my usage limit for now. Please let whoever runs this bot know."
+        }
+        ErrorClass::ContextTooLong => {
+            "📚 This conversation has grown too long for me. Send /new to start fresh."
+        }
+        ErrorClass::Unsupported => {
+            "⚠️ The current model does not support {capability}. Try again without it, or pick another model with /models."
+        }
+        ErrorClass::Refused => "🙅 I can't help with that request.",
+        ErrorClass::ToolFailure => {
+            "🛠️ I got stuck working on that and stopped. Try rephrasing it or splitting it into smaller steps."
+        }
+        ErrorClass::Internal => "⚠️ Something went wrong on my side. Please try again.",
+    }
+}
+
+fn builtin_vi(class: ErrorClass) -> &'static str {
+    match class {
+        ErrorClass::RateLimited => {
+            "⏳ Mình đang bị giới hạn số lượt gọi — bạn thử lại sau khoảng một phút nhé."
+        }
+        ErrorClass::Auth => {
+            "🔑 Dịch vụ AI đã từ chối thông tin xác thực của mình. Bạn báo giúp người quản lý bot nhé."
+        }
+        ErrorClass::Network => {
+            "📡 Mình không kết nối được tới dịch vụ AI. Bạn thử lại sau ít phút nhé."
+        }
+        ErrorClass::Overloaded => {
+            "🚧 Dịch vụ AI đang quá tải hoặc gặp sự cố. Bạn thử lại sau vài phút nhé."
+        }
+        ErrorClass::BudgetExceeded => {
+            "💳 Mình đã dùng hết hạn mức sử dụng. Bạn báo giúp người quản lý bot nhé."
+        }
+        ErrorClass::ContextTooLong => {
+            "📚 Cuộc trò chuyện đã quá dài. Gửi /new để bắt đầu lại nhé."
+        }
+        ErrorClass::Unsupported => {
+            "⚠️ Mô hình hiện tại không hỗ trợ {capability}. Bạn thử lại mà không kèm nội dung đó, hoặc chọn mô hình khác bằng /models."
+        }
+        ErrorClass::Refused => "🙅 Mình không thể giúp với yêu cầu này.",
+        ErrorClass::ToolFailure => {
+            "🛠️ Mình bị kẹt khi xử lý yêu cầu này nên đã dừng lại. Bạn thử diễn đạt lại hoặc chia nhỏ yêu cầu nhé."
+        }
+        ErrorClass::Internal => "⚠️ Đã có lỗi xảy ra phía mình. Bạn thử lại nhé.",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::RefusedError;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn provider_errors_map_to_their_class() {
+        let cases = [
+            (
+                anyhow!("OpenRouter API error (429 Too Many Requests): {{\"error\":{{\"message\":\"Rate limit exceeded\"}}}}"),
+                ErrorClass::RateLimited,
+            ),
+            (
+                anyhow!("OpenAI API error (429 Too Many Requests): You exceeded your current quota (insufficient_quota)"),
+                ErrorClass::BudgetExceeded,
+            ),
+            (
+                anyhow!("Anthropic API error (401 Unauthorized): invalid x-api-key"),
+                ErrorClass::Auth,
+            ),
+            (
+                anyhow!("Anthropic API error (529 <unknown status code>): {{\"type\":\"overloaded_error\"}}"),
+                ErrorClass::Overloaded,
+            ),
+            (
+                anyhow!("Gemini API error (503 Service Unavailable): The model is overloaded."),
+                ErrorClass::Overloaded,
+            ),
+            (
+                anyhow!("error sending request for url (https://api.openai.com/v1/chat/completions)")
+                    .context("OpenAI request failed"),
+                ErrorClass::Network,
+            ),
+            (
+                anyhow!("Ollama API error (400 Bad Request): prompt is too long for this model"),
+                ErrorClass::ContextTooLong,
+            ),
+            (
+                anyhow::Error::new(ProviderCapabilityError {
+                    provider: "ollama".into(),
+                    capability: "vision".into(),
+                    message: "model does not accept images".into(),
+                }),
+                ErrorClass::Unsupported,
+            ),
+            (
+                anyhow::Error::new(RefusedError {
+                    reason: "content_filter".into(),
+                })
+                .context("channel turn failed"),
+                ErrorClass::Refused,
+            ),
+            (
+                anyhow!("Agent stopped early due to detected loop pattern (iteration 4/10): repeated shell calls"),
+                ErrorClass::ToolFailure,
+            ),
+            (
+                anyhow!("OpenRouter API error (404 Not Found): no endpoints found"),
+                ErrorClass::Internal,
+            ),
+        ];
+        for (err, expected) in cases {
+            assert_eq!(classify(&err), expected, "{err:#}");
+        }
+    }
+
+    #[test]
+    fn aggregated_retry_errors_keep_the_underlying_class() {
+        let err = anyhow!(
+            "All providers/models failed. Attempts:\n\
+             provider=openrouter model=x attempt 1/3: rate_limited; error=OpenRouter API error (429 Too Many Requests): slow down"
+        );
+        assert_eq!(classify(&err), ErrorClass::RateLimited);
+
+        // `fallback_on_refusal`: every provider refused.
+        let err = anyhow!(
+            "All providers/models failed. Attempts:\n\
+             provider=openai model=x attempt 1/3: refused; error=provider refused the request: content_filter"
+        );
+        assert_eq!(classify(&err), ErrorClass::Refused);
+
+        let err = Err::<(), _>(anyhow!("Anthropic API error (401 Unauthorized): bad key"))
+            .context("channel turn failed")
+            .unwrap_err();
+        assert_eq!(classify(&err), ErrorClass::Auth);
+    }
+
+    #[test]
+    fn replies_hide_details_and_carry_a_reference() {
+        let replies = ErrorReplies::default();
+        let reply = replies.render(&anyhow!(
+            "OpenRouter API error (429 Too Many Requests): request req_7f3a https://internal.example/trace"
+        ));
+        assert_eq!(reply.class, ErrorClass::RateLimited);
+        assert!(
+            reply.text.starts_with("⏳ I'm being rate limited"),
+            "{}",
+            reply.text
+        );
+        assert!(!reply.text.contains("internal.example"));
+        assert!(reply
+            .text
+            .ends_with(&format!("Reference: {}", reply.request_id)));
+        assert_eq!(reply.request_id.len(), 8);
+
+        let vision = replies.render(&anyhow::Error::new(ProviderCapabilityError {
+            provider: "dummy".into(),
+            capability: "vision".into(),
+            message: "no images".into(),
+        }));
+        assert!(
+            vision.text.contains("does not support vision"),
+            "{}",
+            vision.text
+        );
+
+        // A refusal is not a failure to look up: no reference line.
+        let refused = replies.render(&anyhow::Error::new(RefusedError {
+            reason: "I can't assist with that.".into(),
+        }));
+        assert_eq!(refused.class, ErrorClass::Refused);
+        assert_eq!(refused.text, "🙅 I can't help with that request.");
+    }
+
+    #[test]
+    fn language_and_overrides_pick_the_template() {
+        let vi = ErrorReplies::compile("vi", &HashMap::new()).unwrap();
+        let text = vi.text(ErrorClass::Overloaded, None, "abcd1234");
+        assert!(text.starts_with("🚧 Dịch vụ AI"), "{text}");
+        assert!(text.ends_with("Mã tham chiếu: abcd1234"));
+
+        let templates = HashMap::from([
+            (
+                "vi".to_string(),
+                HashMap::from([(
+                    "rate_limited".to_string(),
+                    "Chậm lại chút nhé ({request_id})".to_string(),
+                )]),
+            ),
+            (
+                "en".to_string(),
+                HashMap::from([("rate_limited".to_string(), "Slow down".to_string())]),
+            ),
+        ]);
+        let vi = ErrorReplies::compile("VI", &templates).unwrap();
+        assert_eq!(
+            vi.text(ErrorClass::RateLimited, None, "abcd1234"),
+            "Chậm lại chút nhé (abcd1234)"
+        );
+        // Unknown language: English built-ins.
+        let fr = ErrorReplies::compile("fr", &templates).unwrap();
+        assert!(fr
+            .text(ErrorClass::Internal, None, "abcd1234")
+            .starts_with("⚠️ Something went wrong"));
+
+        let bad = HashMap::from([(
+            "en".to_string(),
+            HashMap::from([("quota".to_string(), "x".to_string())]),
+        )]);
+        let err = ErrorReplies::compile("en", &bad).unwrap_err();
+        assert!(
+            err.to_string().contains("error_templates.en.quota"),
+            "{err}"
+        );
+    }
+}
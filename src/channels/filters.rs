@@ -0,0 +1,158 @@
+//! Inbound message filters (`[channels_config.filters]`).
+//!
+//! Cheap checks that run after runtime commands (`/new`, `/models`, approval
+//! commands, ...) and before the provider call. A message that matches a rule
+//! is not sent to the model and not auto-saved to memory; the rule either
+//! stays silent or replies with the acknowledgment configured for it.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::config::ChannelFiltersConfig;
+
+/// Rule key for `skip_bare_urls`.
+pub const BARE_URL_RULE: &str = "bare_url";
+/// Rule key for `skip_shorter_than`.
+pub const TOO_SHORT_RULE: &str = "too_short";
+
+/// Compiled `[channels_config.filters]` rules.
+#[derive(Debug, Default)]
+pub struct InboundFilter {
+    skip_bare_urls: bool,
+    skip_shorter_than: usize,
+    /// (pattern as configured, compiled regex)
+    patterns: Vec<(String, Regex)>,
+    acknowledgments: HashMap<String, String>,
+}
+
+/// The rule that skipped a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterMatch {
+    /// `bare_url`, `too_short`, or the matching pattern.
+    pub rule: String,
+    /// Reply to send, or `None` to stay silent.
+    pub acknowledgment: Option<String>,
+}
+
+impl InboundFilter {
+    /// Compile every rule, naming the offending entry on an invalid regex.
+    pub fn compile(config: &ChannelFiltersConfig) -> Result<Self> {
+        let patterns = config
+            .skip_patterns
+            .iter()
+            .enumerate()
+            .map(|(i, pattern)| {
+                let regex = Regex::new(pattern).with_context(|| {
+                    format!("channels_config.filters.skip_patterns[{i}] is not a valid regex")
+                })?;
+                Ok((pattern.clone(), regex))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            skip_bare_urls: config.skip_bare_urls,
+            skip_shorter_than: config.skip_shorter_than,
+            patterns,
+            acknowledgments: config
+                .acknowledgments
+                .iter()
+                .filter(|(_, reply)| !reply.trim().is_empty())
+                .map(|(rule, reply)| (rule.clone(), reply.clone()))
+                .collect(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.skip_bare_urls && self.skip_shorter_than == 0 && self.patterns.is_empty()
+    }
+
+    /// The first rule that matches `text`: bare URLs, then length, then
+    /// patterns in configured order.
+    pub fn check(&self, text: &str) -> Option<FilterMatch> {
+        let trimmed = text.trim();
+        let rule = if self.skip_bare_urls && is_bare_urls(trimmed) {
+            BARE_URL_RULE
+        } else if trimmed.chars().count() < self.skip_shorter_than {
+            TOO_SHORT_RULE
+        } else {
+            self.patterns
+                .iter()
+                .find(|(_, regex)| regex.is_match(trimmed))
+                .map(|(pattern, _)| pattern.as_str())?
+        };
+        Some(FilterMatch {
+            rule: rule.to_string(),
+            acknowledgment: self.acknowledgments.get(rule).cloned(),
+        })
+    }
+}
+
+/// Whether `text` is one or more whitespace-separated `http(s)` URLs and
+/// nothing else. Angle brackets (`<https://...>`) are ignored.
+fn is_bare_urls(text: &str) -> bool {
+    !text.is_empty()
+        && text.split_whitespace().all(|token| {
+            let token = token.trim_start_matches('<').trim_end_matches('>');
+            ["https://", "http://"].iter().any(|scheme| {
+                token
+                    .get(..scheme.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+                    && token.len() > scheme.len()
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(config: ChannelFiltersConfig) -> InboundFilter {
+        InboundFilter::compile(&config).unwrap()
+    }
+
+    #[test]
+    fn rules_match_in_order() {
+        let filter = filter(ChannelFiltersConfig {
+            skip_bare_urls: true,
+            skip_shorter_than: 2,
+            skip_patterns: vec!["^/start$".into()],
+            acknowledgments: HashMap::from([(BARE_URL_RULE.to_string(), "Saved.".to_string())]),
+        });
+
+        let url = filter
+            .check("https://example.com/a  <http://b.example>")
+            .unwrap();
+        assert_eq!(url.rule, BARE_URL_RULE);
+        assert_eq!(url.acknowledgment.as_deref(), Some("Saved."));
+
+        let short = filter.check(" 👍 ").unwrap();
+        assert_eq!(short.rule, TOO_SHORT_RULE);
+        assert_eq!(short.acknowledgment, None);
+
+        assert_eq!(filter.check("/start").unwrap().rule, "^/start$");
+        assert_eq!(filter.check("read https://example.com please"), None);
+        assert_eq!(filter.check("/start now"), None);
+        assert_eq!(filter.check("https://"), None);
+    }
+
+    #[test]
+    fn empty_config_matches_nothing() {
+        let filter = filter(ChannelFiltersConfig::default());
+        assert!(filter.is_empty());
+        assert_eq!(filter.check(""), None);
+        assert_eq!(filter.check("https://example.com"), None);
+    }
+
+    #[test]
+    fn invalid_pattern_names_the_entry() {
+        let err = InboundFilter::compile(&ChannelFiltersConfig {
+            skip_patterns: vec!["ok".into(), "(".into()],
+            ..ChannelFiltersConfig::default()
+        })
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("channels_config.filters.skip_patterns[1]"));
+    }
+}
@@ -5,6 +5,19 @@ use rusqlite::{Connection, OpenFlags};
 use std::path::Path;
 use tokio::sync::mpsc;
 
+/// Error for using the iMessage channel on a host other than macOS.
+pub const MACOS_ONLY_ERROR: &str =
+    "iMessage is macOS-only (it needs Messages.app, its chat.db and `osascript`)";
+
+/// Fail with [`MACOS_ONLY_ERROR`] unless running on macOS.
+pub fn ensure_supported() -> anyhow::Result<()> {
+    if cfg!(target_os = "macos") {
+        Ok(())
+    } else {
+        anyhow::bail!(MACOS_ONLY_ERROR)
+    }
+}
+
 /// iMessage channel using macOS `AppleScript` bridge.
 /// Polls the Messages database for new messages and sends replies via `osascript`.
 #[derive(Clone)]
@@ -21,6 +34,12 @@ impl IMessageChannel {
         }
     }
 
+    /// [`Self::new`], failing with [`MACOS_ONLY_ERROR`] on other hosts.
+    pub fn try_new(allowed_contacts: Vec<String>) -> anyhow::Result<Self> {
+        ensure_supported()?;
+        Ok(Self::new(allowed_contacts))
+    }
+
     fn is_contact_allowed(&self, sender: &str) -> bool {
         if self.allowed_contacts.iter().any(|u| u == "*") {
             return true;
@@ -96,6 +115,8 @@ impl Channel for IMessageChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+        ensure_supported()?;
+
         // Defense-in-depth: validate target format before any interpolation
         if !is_valid_imessage_target(&message.recipient) {
             anyhow::bail!(
@@ -131,6 +152,7 @@ end tell"#
     }
 
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        ensure_supported()?;
         tracing::info!("iMessage channel listening (AppleScript bridge)...");
 
         // Query the Messages SQLite database for new messages
@@ -317,6 +339,31 @@ async fn fetch_new_messages(
 mod tests {
     use super::*;
 
+    #[cfg(not(target_os = "macos"))]
+    #[tokio::test]
+    async fn non_macos_hosts_reject_the_channel() {
+        let err = IMessageChannel::try_new(vec!["*".into()]).unwrap_err();
+        assert_eq!(err.to_string(), MACOS_ONLY_ERROR);
+
+        let ch = IMessageChannel::new(vec!["*".into()]);
+        let err = ch
+            .send(&SendMessage::new("hi", "+1234567890"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), MACOS_ONLY_ERROR);
+        let (tx, _rx) = mpsc::channel(1);
+        let err = ch.listen(tx).await.unwrap_err();
+        assert_eq!(err.to_string(), MACOS_ONLY_ERROR);
+        assert!(!ch.health_check().await);
+    }
+
+    #[cfg(target_os = "macos")]
+    #[test]
+    fn macos_hosts_accept_the_channel() {
+        assert!(ensure_supported().is_ok());
+        assert!(IMessageChannel::try_new(vec!["*".into()]).is_ok());
+    }
+
     #[test]
     fn creates_with_contacts() {
         let ch = IMessageChannel::new(vec!["+1234567890".into()]);
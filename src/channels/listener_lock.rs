@@ -0,0 +1,457 @@
+//! Cross-process guard against two processes serving the same bot.
+//!
+//! Two `zeroclaw channel start` processes long-polling one Telegram token
+//! split the updates between them and both reply. On startup each process
+//! claims the channel identities it is about to serve (a hash of the token,
+//! room or guild) in `state/channel_listeners.json`, with its PID and a
+//! heartbeat timestamp. A claim held by another process whose heartbeat is
+//! fresh is refused; with `--takeover` it is overwritten, and the old holder
+//! sees on its next heartbeat that the entry is no longer its own and stops
+//! that listener. Entries whose heartbeat is older than [`STALE_AFTER_SECS`]
+//! belong to a crashed process and are reclaimed.
+//!
+//! Every read-modify-write of the registry happens under a `.lock` file
+//! created with `create_new`, so concurrent starts cannot both win.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::config::ChannelsConfig;
+
+/// How often a running process refreshes its claims.
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
+/// A claim whose heartbeat is older than this is treated as abandoned.
+pub const STALE_AFTER_SECS: i64 = 45;
+
+const REGISTRY_FILE: &str = "channel_listeners.json";
+const LOCK_WAIT: Duration = Duration::from_millis(20);
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// A `.lock` file this old was left by a process that died mid-update.
+const LOCK_STALE_AFTER: Duration = Duration::from_secs(10);
+
+/// One channel account this process wants to serve. `key` is a hash of the
+/// credentials and target, never the secret itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenerIdentity {
+    pub channel: &'static str,
+    pub key: String,
+}
+
+impl ListenerIdentity {
+    fn new(channel: &'static str, parts: &[&str]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(channel.as_bytes());
+        for part in parts {
+            hasher.update([0]);
+            hasher.update(part.as_bytes());
+        }
+        Self {
+            channel,
+            key: hex::encode(&hasher.finalize()[..16]),
+        }
+    }
+}
+
+/// Identities of the configured channels that hold an exclusive listener
+/// (long-poll, gateway or socket session). Keyed by the same display names
+/// as the channels `collect_configured_channels` builds.
+pub fn identities(channels: &ChannelsConfig) -> Vec<ListenerIdentity> {
+    let mut identities = Vec::new();
+    if let Some(tg) = &channels.telegram {
+        identities.push(ListenerIdentity::new("Telegram", &[&tg.bot_token]));
+    }
+    if let Some(dc) = &channels.discord {
+        identities.push(ListenerIdentity::new(
+            "Discord",
+            &[&dc.bot_token, dc.guild_id.as_deref().unwrap_or_default()],
+        ));
+    }
+    if let Some(sl) = &channels.slack {
+        identities.push(ListenerIdentity::new(
+            "Slack",
+            &[
+                &sl.bot_token,
+                sl.app_token.as_deref().unwrap_or_default(),
+                sl.channel_id.as_deref().unwrap_or_default(),
+            ],
+        ));
+    }
+    if let Some(mm) = &channels.mattermost {
+        identities.push(ListenerIdentity::new(
+            "Mattermost",
+            &[
+                &mm.url,
+                &mm.bot_token,
+                mm.channel_id.as_deref().unwrap_or_default(),
+            ],
+        ));
+    }
+    if let Some(mx) = &channels.matrix {
+        identities.push(ListenerIdentity::new(
+            "Matrix",
+            &[&mx.homeserver, &mx.access_token, &mx.room_id],
+        ));
+    }
+    if let Some(sig) = &channels.signal {
+        identities.push(ListenerIdentity::new(
+            "Signal",
+            &[&sig.http_url, &sig.account],
+        ));
+    }
+    if let Some(dt) = &channels.dingtalk {
+        identities.push(ListenerIdentity::new("DingTalk", &[&dt.client_id]));
+    }
+    if let Some(qq) = &channels.qq {
+        identities.push(ListenerIdentity::new("QQ", &[&qq.app_id]));
+    }
+    identities
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Registration {
+    channel: String,
+    pid: u32,
+    /// Unix seconds of the holder's last heartbeat.
+    heartbeat_at: i64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Registry {
+    #[serde(default)]
+    listeners: BTreeMap<String, Registration>,
+}
+
+/// A channel left unclaimed because another live process serves it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    pub channel: String,
+    pub pid: u32,
+    pub heartbeat_age_secs: i64,
+}
+
+/// A claim this process lost to `--takeover` from another process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TakenOver {
+    pub identity: ListenerIdentity,
+    pub by_pid: u32,
+}
+
+pub struct ListenerLock {
+    path: PathBuf,
+}
+
+impl ListenerLock {
+    /// The registry for `workspace_dir`.
+    pub fn new(workspace_dir: &Path) -> Self {
+        Self::at(workspace_dir.join("state").join(REGISTRY_FILE))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Claim `wanted` for `pid`. Identities held by another process with a
+    /// fresh heartbeat are returned as conflicts and left alone, unless
+    /// `takeover` is set. Own and stale entries are always reclaimed.
+    pub fn claim(
+        &self,
+        wanted: &[ListenerIdentity],
+        pid: u32,
+        now: i64,
+        takeover: bool,
+    ) -> Result<Vec<Conflict>> {
+        self.update(|registry| {
+            let mut conflicts = Vec::new();
+            for identity in wanted {
+                if let Some(holder) = registry.listeners.get(&identity.key) {
+                    let age = now - holder.heartbeat_at;
+                    if holder.pid != pid && age <= STALE_AFTER_SECS {
+                        if !takeover {
+                            conflicts.push(Conflict {
+                                channel: identity.channel.to_string(),
+                                pid: holder.pid,
+                                heartbeat_age_secs: age.max(0),
+                            });
+                            continue;
+                        }
+                        tracing::warn!(
+                            "Taking over {} from zeroclaw pid {}",
+                            identity.channel,
+                            holder.pid
+                        );
+                    } else if holder.pid != pid {
+                        tracing::info!(
+                            "Reclaiming {} from pid {} (no heartbeat for {age}s)",
+                            identity.channel,
+                            holder.pid
+                        );
+                    }
+                }
+                registry.listeners.insert(
+                    identity.key.clone(),
+                    Registration {
+                        channel: identity.channel.to_string(),
+                        pid,
+                        heartbeat_at: now,
+                    },
+                );
+            }
+            conflicts
+        })
+    }
+
+    /// Refresh `held` for `pid`. Returns the identities another process has
+    /// taken over since; those are no longer refreshed and the caller should
+    /// stop their listeners.
+    pub fn heartbeat(
+        &self,
+        held: &[ListenerIdentity],
+        pid: u32,
+        now: i64,
+    ) -> Result<Vec<TakenOver>> {
+        self.update(|registry| {
+            let mut lost = Vec::new();
+            for identity in held {
+                match registry.listeners.get_mut(&identity.key) {
+                    Some(entry) if entry.pid != pid => lost.push(TakenOver {
+                        identity: identity.clone(),
+                        by_pid: entry.pid,
+                    }),
+                    Some(entry) => entry.heartbeat_at = now,
+                    // Removed by hand: take it back rather than run unregistered.
+                    None => {
+                        registry.listeners.insert(
+                            identity.key.clone(),
+                            Registration {
+                                channel: identity.channel.to_string(),
+                                pid,
+                                heartbeat_at: now,
+                            },
+                        );
+                    }
+                }
+            }
+            lost
+        })
+    }
+
+    /// Drop `pid`'s claims on `held`, leaving entries another process took.
+    pub fn release(&self, held: &[ListenerIdentity], pid: u32) -> Result<()> {
+        self.update(|registry| {
+            for identity in held {
+                if registry
+                    .listeners
+                    .get(&identity.key)
+                    .is_some_and(|entry| entry.pid == pid)
+                {
+                    registry.listeners.remove(&identity.key);
+                }
+            }
+        })
+    }
+
+    fn update<T>(&self, change: impl FnOnce(&mut Registry) -> T) -> Result<T> {
+        let dir = self
+            .path
+            .parent()
+            .context("channel listener registry path has no parent directory")?;
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let _guard = FileLock::acquire(self.path.with_extension("lock"))?;
+
+        let mut registry = match fs::read_to_string(&self.path) {
+            Ok(raw) => serde_json::from_str(&raw).unwrap_or_else(|err| {
+                tracing::warn!(
+                    "Ignoring unreadable channel listener registry {}: {err}",
+                    self.path.display()
+                );
+                Registry::default()
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Registry::default(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read {}", self.path.display()))
+            }
+        };
+        let result = change(&mut registry);
+
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&registry)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        Ok(result)
+    }
+}
+
+/// Exclusive `.lock` file, removed on drop.
+struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    fn acquire(path: PathBuf) -> Result<Self> {
+        let started = SystemTime::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Self { path }),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let abandoned = fs::metadata(&path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > LOCK_STALE_AFTER);
+                    if abandoned {
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if started.elapsed().unwrap_or_default() > LOCK_TIMEOUT {
+                        anyhow::bail!(
+                            "Timed out waiting for channel listener lock at {}",
+                            path.display()
+                        );
+                    }
+                    std::thread::sleep(LOCK_WAIT);
+                }
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Failed to create {}", path.display()))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn telegram(token: &str) -> ListenerIdentity {
+        ListenerIdentity::new("Telegram", &[token])
+    }
+
+    #[test]
+    fn a_live_holder_blocks_the_second_process_until_takeover() {
+        let tmp = TempDir::new().unwrap();
+        let lock = ListenerLock::new(tmp.path());
+        let bot = [telegram("123:abc")];
+        let other_bot = [telegram("456:def")];
+
+        assert!(lock.claim(&bot, 100, 1_000, false).unwrap().is_empty());
+        // Same process again (daemon restarting its channels) is fine.
+        assert!(lock.claim(&bot, 100, 1_005, false).unwrap().is_empty());
+
+        let conflicts = lock.claim(&bot, 200, 1_010, false).unwrap();
+        assert_eq!(
+            conflicts,
+            vec![Conflict {
+                channel: "Telegram".into(),
+                pid: 100,
+                heartbeat_age_secs: 5,
+            }]
+        );
+        // A different token is not contended.
+        assert!(lock
+            .claim(&other_bot, 200, 1_010, false)
+            .unwrap()
+            .is_empty());
+        // The refused claim left the holder untouched.
+        assert!(lock.heartbeat(&bot, 100, 1_020).unwrap().is_empty());
+
+        assert!(lock.claim(&bot, 200, 1_030, true).unwrap().is_empty());
+        let lost = lock.heartbeat(&bot, 100, 1_035).unwrap();
+        assert_eq!(
+            lost,
+            vec![TakenOver {
+                identity: bot[0].clone(),
+                by_pid: 200,
+            }]
+        );
+        assert!(lock.heartbeat(&bot, 200, 1_040).unwrap().is_empty());
+
+        // The old holder shutting down must not drop the new claim.
+        lock.release(&bot, 100).unwrap();
+        assert_eq!(lock.claim(&bot, 300, 1_045, false).unwrap()[0].pid, 200);
+    }
+
+    #[test]
+    fn stale_claims_are_reclaimed_and_released_claims_are_free() {
+        let tmp = TempDir::new().unwrap();
+        let lock = ListenerLock::new(tmp.path());
+        let bot = [telegram("123:abc")];
+
+        lock.claim(&bot, 100, 1_000, false).unwrap();
+        let just_fresh = 1_000 + STALE_AFTER_SECS;
+        assert_eq!(lock.claim(&bot, 200, just_fresh, false).unwrap().len(), 1);
+        // pid 100 crashed: once its heartbeat is stale the claim goes to 200.
+        assert!(lock
+            .claim(&bot, 200, just_fresh + 1, false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            lock.heartbeat(&bot, 100, just_fresh + 2).unwrap()[0].by_pid,
+            200
+        );
+
+        lock.release(&bot, 200).unwrap();
+        assert!(lock
+            .claim(&bot, 300, just_fresh + 3, false)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn concurrent_claims_have_exactly_one_winner() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("state").join(REGISTRY_FILE);
+        let winners: usize = std::thread::scope(|scope| {
+            let handles: Vec<_> = (1..=8_u32)
+                .map(|pid| {
+                    let path = path.clone();
+                    scope.spawn(move || {
+                        ListenerLock::at(path)
+                            .claim(&[telegram("123:abc")], pid, 1_000, false)
+                            .unwrap()
+                            .is_empty()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| usize::from(handle.join().unwrap()))
+                .sum()
+        });
+        assert_eq!(winners, 1);
+        assert!(!path.with_extension("lock").exists());
+    }
+
+    #[test]
+    fn identities_hash_the_credentials() {
+        let mut channels = ChannelsConfig::default();
+        channels.telegram = Some(crate::config::TelegramConfig {
+            bot_token: "123:secret".into(),
+            allowed_users: vec![],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            group_reply: None,
+            base_url: None,
+        });
+        let ids = identities(&channels);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0].channel, "Telegram");
+        assert_eq!(ids[0], telegram("123:secret"));
+        assert_ne!(ids[0], telegram("123:other"));
+        assert!(!ids[0].key.contains("secret"));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::channels::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{ReplyStyle, StreamMode};
 use async_trait::async_trait;
 use matrix_sdk::{
     authentication::matrix::MatrixSession,
@@ -8,7 +9,7 @@ use matrix_sdk::{
             MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
         },
         events::Mentions,
-        OwnedRoomId, OwnedUserId,
+        EventId, OwnedRoomId, OwnedUserId,
     },
     Client as MatrixSdkClient, LoopCtrl, Room, RoomState, SessionMeta, SessionTokens,
 };
@@ -33,6 +34,9 @@ pub struct MatrixChannel {
     resolved_room_id_cache: Arc<RwLock<Option<String>>>,
     sdk_client: Arc<OnceCell<MatrixSdkClient>>,
     http_client: Client,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    reply_style: ReplyStyle,
 }
 
 impl std::fmt::Debug for MatrixChannel {
@@ -172,6 +176,9 @@ impl MatrixChannel {
             resolved_room_id_cache: Arc::new(RwLock::new(None)),
             sdk_client: Arc::new(OnceCell::new()),
             http_client: Client::new(),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            reply_style: ReplyStyle::TopLevel,
         }
     }
 
@@ -180,6 +187,93 @@ impl MatrixChannel {
         self
     }
 
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    /// Configure how replies attach to the triggering event.
+    pub fn with_reply_style(mut self, reply_style: ReplyStyle) -> Self {
+        self.reply_style = reply_style;
+        self
+    }
+
+    /// `content` with the relation `message` needs: an `m.thread` relation
+    /// when it goes into a thread (falling back to a reply to the event it
+    /// answers for clients without threads), or a rich reply
+    /// (`m.in_reply_to`) with `inline-reply`.
+    fn with_reply_relation(
+        mut content: serde_json::Value,
+        message: &SendMessage,
+        reply_style: ReplyStyle,
+    ) -> serde_json::Value {
+        let reply_to = message.reply_to.as_deref().filter(|id| id.starts_with('$'));
+        if let Some(root) = message.thread_ts.as_deref() {
+            content["m.relates_to"] = serde_json::json!({
+                "rel_type": "m.thread",
+                "event_id": root,
+                "is_falling_back": true,
+                "m.in_reply_to": { "event_id": reply_to.unwrap_or(root) },
+            });
+        } else if let (ReplyStyle::InlineReply, Some(event_id)) = (reply_style, reply_to) {
+            content["m.relates_to"] = serde_json::json!({
+                "m.in_reply_to": { "event_id": event_id },
+            });
+        }
+        content
+    }
+
+    /// Thread root of an inbound event: the thread it was sent in, or the
+    /// event itself when `thread-reply` starts a thread on it.
+    fn inbound_thread_ts(
+        event: &OriginalSyncRoomMessageEvent,
+        reply_style: ReplyStyle,
+    ) -> Option<String> {
+        if let Some(Relation::Thread(thread)) = event.content.relates_to.as_ref() {
+            return Some(thread.event_id.to_string());
+        }
+        (reply_style == ReplyStyle::ThreadReply).then(|| event.event_id.to_string())
+    }
+
+    /// Send `content` as an `m.room.message`, related as `message` needs.
+    async fn send_related(
+        &self,
+        content: RoomMessageEventContent,
+        message: &SendMessage,
+    ) -> anyhow::Result<String> {
+        let content =
+            Self::with_reply_relation(serde_json::to_value(content)?, message, self.reply_style);
+        let sent = self
+            .joined_room()
+            .await?
+            .send_raw("m.room.message", content)
+            .await?;
+        Ok(sent.response.event_id.to_string())
+    }
+
+    /// `m.replace` edit of `event_id`. The top-level body is the `* text`
+    /// fallback shown by clients without edit support.
+    fn replacement_content(event_id: &str, new_text: &str) -> serde_json::Value {
+        let mut content = serde_json::to_value(RoomMessageEventContent::text_markdown(format!(
+            "* {new_text}"
+        )))
+        .unwrap_or_default();
+        content["m.new_content"] =
+            serde_json::to_value(RoomMessageEventContent::text_markdown(new_text))
+                .unwrap_or_default();
+        content["m.relates_to"] = serde_json::json!({
+            "rel_type": "m.replace",
+            "event_id": event_id,
+        });
+        content
+    }
+
     fn encode_path_segment(value: &str) -> String {
         fn should_encode(byte: u8) -> bool {
             !matches!(
@@ -635,6 +729,29 @@ impl MatrixChannel {
         .to_string()
     }
 
+    /// The configured room, syncing once if the client has not seen it yet.
+    async fn joined_room(&self) -> anyhow::Result<Room> {
+        let client = self.matrix_client().await?;
+        let target_room_id = self.target_room_id().await?;
+        let target_room: OwnedRoomId = target_room_id.parse()?;
+
+        let mut room = client.get_room(&target_room);
+        if room.is_none() {
+            let _ = client.sync_once(SyncSettings::new()).await;
+            room = client.get_room(&target_room);
+        }
+
+        let Some(room) = room else {
+            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
+        };
+
+        if room.state() != RoomState::Joined {
+            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+        }
+
+        Ok(room)
+    }
+
     async fn log_e2ee_diagnostics(&self, client: &MatrixSdkClient) {
         match client.encryption().get_own_device().await {
             Ok(Some(device)) => {
@@ -674,27 +791,55 @@ impl Channel for MatrixChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let client = self.matrix_client().await?;
-        let target_room_id = self.target_room_id().await?;
-        let target_room: OwnedRoomId = target_room_id.parse()?;
+        self.send_related(
+            RoomMessageEventContent::text_markdown(&message.content),
+            message,
+        )
+        .await?;
 
-        let mut room = client.get_room(&target_room);
-        if room.is_none() {
-            let _ = client.sync_once(SyncSettings::new()).await;
-            room = client.get_room(&target_room);
-        }
+        Ok(())
+    }
 
-        let Some(room) = room else {
-            anyhow::bail!("Matrix room '{}' not found in joined rooms", target_room_id);
-        };
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
 
-        if room.state() != RoomState::Joined {
-            anyhow::bail!("Matrix room '{}' is not in joined state", target_room_id);
+    fn draft_update_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.draft_update_interval_ms)
+    }
+
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
         }
+        let text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let event_id = self
+            .send_related(RoomMessageEventContent::text_plain(text), message)
+            .await?;
+        Ok(Some(event_id))
+    }
 
-        room.send(RoomMessageEventContent::text_markdown(&message.content))
+    async fn edit(&self, _recipient: &str, message_id: &str, new_text: &str) -> anyhow::Result<()> {
+        self.joined_room()
+            .await?
+            .send_raw(
+                "m.room.message",
+                Self::replacement_content(message_id, new_text),
+            )
             .await?;
+        Ok(())
+    }
 
+    async fn cancel_draft(&self, _recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        let event_id = EventId::parse(message_id)?;
+        self.joined_room()
+            .await?
+            .redact(&event_id, None, None)
+            .await?;
         Ok(())
     }
 
@@ -745,6 +890,7 @@ impl Channel for MatrixChannel {
         let dedupe_for_handler = Arc::clone(&recent_event_cache);
         let bot_dedupe_for_handler = Arc::clone(&recent_bot_event_cache);
         let mention_only_for_handler = self.mention_only;
+        let reply_style_for_handler = self.reply_style;
 
         client.add_event_handler(move |event: OriginalSyncRoomMessageEvent, room: Room| {
             let tx = tx_handler.clone();
@@ -820,6 +966,7 @@ impl Channel for MatrixChannel {
                     }
                 }
 
+                let thread_ts = MatrixChannel::inbound_thread_ts(&event, reply_style_for_handler);
                 let msg = ChannelMessage {
                     id: event_id,
                     sender: sender.clone(),
@@ -830,7 +977,7 @@ impl Channel for MatrixChannel {
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_secs(),
-                    thread_ts: None,
+                    thread_ts,
                 };
 
                 let _ = tx.send(msg).await;
@@ -1162,6 +1309,123 @@ mod tests {
             .contains("<strong>hello</strong>"));
     }
 
+    #[test]
+    fn replacement_content_edits_the_draft_event() {
+        let value = MatrixChannel::replacement_content("$draft:matrix.org", "**done**");
+
+        assert_eq!(value["body"], "* **done**");
+        assert_eq!(value["m.new_content"]["body"], "**done**");
+        assert!(value["m.new_content"]["formatted_body"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("<strong>done</strong>"));
+        assert_eq!(value["m.relates_to"]["rel_type"], "m.replace");
+        assert_eq!(value["m.relates_to"]["event_id"], "$draft:matrix.org");
+    }
+
+    fn inbound_message(
+        event: &OriginalSyncRoomMessageEvent,
+        reply_style: ReplyStyle,
+    ) -> ChannelMessage {
+        ChannelMessage {
+            id: event.event_id.to_string(),
+            sender: event.sender.to_string(),
+            reply_target: event.sender.to_string(),
+            content: "question".into(),
+            channel: "matrix".into(),
+            timestamp: 1,
+            thread_ts: MatrixChannel::inbound_thread_ts(event, reply_style),
+        }
+    }
+
+    #[test]
+    fn thread_replies_relate_to_the_thread_root() {
+        let top_level = parse_sync_message_event(serde_json::json!({
+            "type": "m.room.message",
+            "event_id": "$question:matrix.org",
+            "sender": "@user:matrix.org",
+            "origin_server_ts": 1u64,
+            "content": { "msgtype": "m.text", "body": "question" }
+        }));
+        let in_thread = parse_sync_message_event(serde_json::json!({
+            "type": "m.room.message",
+            "event_id": "$followup:matrix.org",
+            "sender": "@user:matrix.org",
+            "origin_server_ts": 2u64,
+            "content": {
+                "msgtype": "m.text",
+                "body": "follow-up",
+                "m.relates_to": {
+                    "rel_type": "m.thread",
+                    "event_id": "$root:matrix.org",
+                    "is_falling_back": true,
+                    "m.in_reply_to": { "event_id": "$root:matrix.org" }
+                }
+            }
+        }));
+
+        let msg = inbound_message(&top_level, ReplyStyle::ThreadReply);
+        assert_eq!(msg.thread_ts.as_deref(), Some("$question:matrix.org"));
+        let value = MatrixChannel::with_reply_relation(
+            serde_json::to_value(RoomMessageEventContent::text_markdown("answer")).unwrap(),
+            &SendMessage::reply("answer", &msg),
+            ReplyStyle::ThreadReply,
+        );
+        assert_eq!(value["body"], "answer");
+        assert_eq!(value["m.relates_to"]["rel_type"], "m.thread");
+        assert_eq!(value["m.relates_to"]["event_id"], "$question:matrix.org");
+        assert_eq!(
+            value["m.relates_to"]["m.in_reply_to"]["event_id"],
+            "$question:matrix.org"
+        );
+
+        // Messages already in a thread are answered there whatever the style.
+        let msg = inbound_message(&in_thread, ReplyStyle::TopLevel);
+        assert_eq!(msg.thread_ts.as_deref(), Some("$root:matrix.org"));
+        let value = MatrixChannel::with_reply_relation(
+            serde_json::json!({ "msgtype": "m.text", "body": "answer" }),
+            &SendMessage::reply("answer", &msg),
+            ReplyStyle::TopLevel,
+        );
+        assert_eq!(value["m.relates_to"]["event_id"], "$root:matrix.org");
+        assert_eq!(
+            value["m.relates_to"]["m.in_reply_to"]["event_id"],
+            "$followup:matrix.org"
+        );
+    }
+
+    #[test]
+    fn inline_replies_are_rich_replies_and_top_level_has_no_relation() {
+        let event = parse_sync_message_event(serde_json::json!({
+            "type": "m.room.message",
+            "event_id": "$question:matrix.org",
+            "sender": "@user:matrix.org",
+            "origin_server_ts": 1u64,
+            "content": { "msgtype": "m.text", "body": "question" }
+        }));
+        let content = serde_json::json!({ "msgtype": "m.text", "body": "answer" });
+
+        let msg = inbound_message(&event, ReplyStyle::InlineReply);
+        assert_eq!(msg.thread_ts, None);
+        let value = MatrixChannel::with_reply_relation(
+            content.clone(),
+            &SendMessage::reply("answer", &msg),
+            ReplyStyle::InlineReply,
+        );
+        assert_eq!(
+            value["m.relates_to"],
+            serde_json::json!({ "m.in_reply_to": { "event_id": "$question:matrix.org" } })
+        );
+
+        let msg = inbound_message(&event, ReplyStyle::TopLevel);
+        let value = MatrixChannel::with_reply_relation(
+            content.clone(),
+            &SendMessage::reply("answer", &msg),
+            ReplyStyle::TopLevel,
+        );
+        assert_eq!(value, content);
+    }
+
     #[test]
     fn sync_filter_for_room_targets_requested_room() {
         let filter = MatrixChannel::sync_filter_for_room("!room:matrix.org", 0);
@@ -16,14 +16,19 @@
 
 pub mod clawdtalk;
 pub mod cli;
+pub mod console;
 pub mod dingtalk;
 pub mod discord;
+pub mod edit_throttle;
 pub mod email_channel;
+pub mod error_reply;
+pub mod filters;
 pub mod imessage;
 pub mod irc;
 #[cfg(feature = "channel-lark")]
 pub mod lark;
 pub mod linq;
+pub mod listener_lock;
 #[cfg(feature = "channel-matrix")]
 pub mod matrix;
 pub mod mattermost;
@@ -32,7 +37,9 @@ pub mod nostr;
 pub mod qq;
 pub mod signal;
 pub mod slack;
+pub mod slash_commands;
 pub mod telegram;
+pub mod tool_progress;
 pub mod traits;
 pub mod transcription;
 pub mod wati;
@@ -72,10 +79,10 @@ use crate::agent::loop_::{
     run_tool_call_loop_with_non_cli_approval_context, scrub_credentials, NonCliApprovalContext,
 };
 use crate::approval::{ApprovalManager, ApprovalResponse, PendingApprovalError};
-use crate::config::{Config, NonCliNaturalLanguageApprovalMode};
+use crate::config::{Config, NonCliNaturalLanguageApprovalMode, StartupBanner};
 use crate::identity;
 use crate::memory::{self, Memory};
-use crate::observability::{self, runtime_trace, Observer};
+use crate::observability::{self, banner, runtime_trace, Observer};
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::{LeakDetector, LeakResult, SecurityPolicy};
@@ -126,6 +133,8 @@ const CHANNEL_HISTORY_COMPACT_KEEP_MESSAGES: usize = 12;
 const CHANNEL_HISTORY_COMPACT_CONTENT_CHARS: usize = 600;
 /// Guardrail for hook-modified outbound channel content.
 const CHANNEL_HOOK_MAX_OUTBOUND_CHARS: usize = 20_000;
+/// How often messages queued during maintenance are checked for replay.
+const MAINTENANCE_DRAIN_INTERVAL: Duration = Duration::from_secs(5);
 
 type ProviderCacheMap = Arc<Mutex<HashMap<String, Arc<dyn Provider>>>>;
 type RouteSelectionMap = Arc<Mutex<HashMap<String, ChannelRouteSelection>>>;
@@ -152,6 +161,19 @@ fn clear_live_channels() {
         .clear();
 }
 
+/// When set (`channel start --debug`), console logs print full message bodies
+/// instead of truncated previews. Both are secret-scrubbed.
+static CHANNEL_DEBUG_BODIES: AtomicBool = AtomicBool::new(false);
+
+fn channel_log_body(content: &str, max_chars: usize) -> String {
+    let scrubbed = providers::scrub_secret_patterns(content);
+    if CHANNEL_DEBUG_BODIES.load(Ordering::Relaxed) {
+        scrubbed
+    } else {
+        truncate_with_ellipsis(&scrubbed, max_chars)
+    }
+}
+
 pub(crate) fn get_live_channel(name: &str) -> Option<Arc<dyn Channel>> {
     live_channels_registry()
         .lock()
@@ -230,6 +252,9 @@ struct ConfigFileStamp {
 struct RuntimeConfigState {
     defaults: ChannelRuntimeDefaults,
     perplexity_filter: crate::config::PerplexityFilterConfig,
+    inbound_filter: Arc<filters::InboundFilter>,
+    error_replies: Arc<error_reply::ErrorReplies>,
+    reset_command: String,
     last_applied_stamp: Option<ConfigFileStamp>,
 }
 
@@ -243,6 +268,10 @@ struct RuntimeAutonomyPolicy {
     non_cli_natural_language_approval_mode_by_channel:
         HashMap<String, NonCliNaturalLanguageApprovalMode>,
     perplexity_filter: crate::config::PerplexityFilterConfig,
+    channel_filters: crate::config::ChannelFiltersConfig,
+    channel_error_language: String,
+    channel_error_templates: HashMap<String, HashMap<String, String>>,
+    channel_reset_command: String,
 }
 
 fn runtime_config_store() -> &'static Mutex<HashMap<PathBuf, RuntimeConfigState>> {
@@ -267,6 +296,7 @@ struct ChannelRuntimeContext {
     model: Arc<String>,
     temperature: f64,
     auto_save_memory: bool,
+    memory_classification: crate::config::MemoryClassificationConfig,
     max_tool_iterations: usize,
     min_relevance_score: f64,
     conversation_histories: ConversationHistoryMap,
@@ -278,6 +308,8 @@ struct ChannelRuntimeContext {
     provider_runtime_options: providers::ProviderRuntimeOptions,
     workspace_dir: Arc<PathBuf>,
     message_timeout_secs: u64,
+    /// `[channels_config] tool_progress_interval_secs`; `0` is off.
+    tool_progress_interval_secs: u64,
     interrupt_on_new_message: bool,
     multimodal: crate::config::MultimodalConfig,
     hooks: Option<Arc<crate::hooks::HookRunner>>,
@@ -285,6 +317,8 @@ struct ChannelRuntimeContext {
     query_classification: crate::config::QueryClassificationConfig,
     model_routes: Vec<crate::config::ModelRouteConfig>,
     approval_manager: Arc<ApprovalManager>,
+    maintenance: Arc<crate::maintenance::MaintenanceMode>,
+    delegate_agents: Arc<crate::agent::delegates::DelegateAgentPool>,
 }
 
 #[derive(Clone)]
@@ -328,6 +362,40 @@ fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
     }
 }
 
+/// Auto-save an inbound message under the category picked by
+/// `[memory.classification]`. Short messages are skipped unless a marker rule
+/// matched ("note: ..."), since the user explicitly asked for those.
+async fn autosave_channel_message(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    provider: &dyn Provider,
+    model: &str,
+    session_id: Option<&str>,
+) {
+    let config = &ctx.memory_classification;
+    let classification = crate::memory::classify::classify(config, &msg.content);
+    if !classification.explicit && msg.content.chars().count() < AUTOSAVE_MIN_MESSAGE_CHARS {
+        return;
+    }
+    let mut category = classification.category;
+    if config.enabled && config.model_classifier && !classification.explicit {
+        if let Some(model_category) =
+            crate::memory::classify::classify_with_model(provider, model, &msg.content).await
+        {
+            category = model_category;
+        }
+    }
+    let _ = ctx
+        .memory
+        .store(
+            &conversation_memory_key(msg),
+            &msg.content,
+            category,
+            session_id,
+        )
+        .await;
+}
+
 fn conversation_history_key(msg: &traits::ChannelMessage) -> String {
     // Include thread_ts for per-topic session isolation in forum groups
     match &msg.thread_ts {
@@ -715,6 +783,33 @@ fn supports_runtime_model_switch(channel_name: &str) -> bool {
     matches!(channel_name, "telegram" | "discord")
 }
 
+/// Whether `content` is the configured `[channels_config].reset_command`.
+/// Slash commands may carry a `@botname` suffix, as in Telegram groups.
+fn is_reset_command(reset_command: &str, content: &str) -> bool {
+    let reset_command = reset_command.trim();
+    let Some(first) = content.split_whitespace().next() else {
+        return false;
+    };
+    let first = if first.starts_with('/') {
+        first.split('@').next().unwrap_or(first)
+    } else {
+        first
+    };
+    !reset_command.is_empty() && first.eq_ignore_ascii_case(reset_command)
+}
+
+/// Like [`parse_runtime_command`], also recognizing the configured reset command.
+fn parse_runtime_command_with_reset(
+    channel_name: &str,
+    content: &str,
+    reset_command: &str,
+) -> Option<ChannelRuntimeCommand> {
+    if is_reset_command(reset_command, content) {
+        return Some(ChannelRuntimeCommand::NewSession);
+    }
+    parse_runtime_command(channel_name, content)
+}
+
 fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRuntimeCommand> {
     let trimmed = content.trim();
     if !trimmed.starts_with('/') {
@@ -733,7 +828,7 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
 
     match base_command.as_str() {
         // History reset commands are safe for all channels.
-        "/new" | "/clear" => Some(ChannelRuntimeCommand::NewSession),
+        "/new" | "/clear" | "/reset" => Some(ChannelRuntimeCommand::NewSession),
         "/approve-all-once" => Some(ChannelRuntimeCommand::RequestAllToolsOnce),
         "/approve-request" => Some(ChannelRuntimeCommand::RequestToolApproval(tail)),
         "/approve-confirm" => Some(ChannelRuntimeCommand::ConfirmToolApproval(tail)),
@@ -743,7 +838,8 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
         "/approve" => Some(ChannelRuntimeCommand::ApproveTool(tail)),
         "/unapprove" => Some(ChannelRuntimeCommand::UnapproveTool(tail)),
         "/approvals" => Some(ChannelRuntimeCommand::ListApprovals),
-        // Provider/model switching remains limited to channels with session routing.
+        // Provider switching remains limited to channels with session routing;
+        // `/model` only changes the model of the current provider.
         "/models" if supports_runtime_model_switch(channel_name) => {
             if let Some(provider) = args.first() {
                 Some(ChannelRuntimeCommand::SetProvider(
@@ -753,7 +849,7 @@ fn parse_runtime_command(channel_name: &str, content: &str) -> Option<ChannelRun
                 Some(ChannelRuntimeCommand::ShowProviders)
             }
         }
-        "/model" if supports_runtime_model_switch(channel_name) => {
+        "/model" => {
             let model = tail;
             if model.is_empty() {
                 Some(ChannelRuntimeCommand::ShowModel)
@@ -955,6 +1051,10 @@ fn runtime_autonomy_policy_from_config(config: &Config) -> RuntimeAutonomyPolicy
             .non_cli_natural_language_approval_mode_by_channel
             .clone(),
         perplexity_filter: config.security.perplexity_filter.clone(),
+        channel_filters: config.channels_config.filters.clone(),
+        channel_error_language: config.channels_config.error_language.clone(),
+        channel_error_templates: config.channels_config.error_templates.clone(),
+        channel_reset_command: config.channels_config.reset_command.clone(),
     }
 }
 
@@ -985,6 +1085,56 @@ fn runtime_defaults_snapshot(ctx: &ChannelRuntimeContext) -> ChannelRuntimeDefau
     }
 }
 
+fn runtime_perplexity_filter_snapshot(
+    ctx: &ChannelRuntimeContext,
+) -> crate::config::PerplexityFilterConfig {
+    if let Some(config_path) = runtime_config_path(ctx) {
+        let store = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = store.get(&config_path) {
+            return state.perplexity_filter.clone();
+        }
+    }
+    crate::config::PerplexityFilterConfig::default()
+}
+
+fn runtime_inbound_filter_snapshot(ctx: &ChannelRuntimeContext) -> Arc<filters::InboundFilter> {
+    if let Some(config_path) = runtime_config_path(ctx) {
+        let store = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = store.get(&config_path) {
+            return Arc::clone(&state.inbound_filter);
+        }
+    }
+    Arc::new(filters::InboundFilter::default())
+}
+
+fn runtime_error_replies_snapshot(ctx: &ChannelRuntimeContext) -> Arc<error_reply::ErrorReplies> {
+    if let Some(config_path) = runtime_config_path(ctx) {
+        let store = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = store.get(&config_path) {
+            return Arc::clone(&state.error_replies);
+        }
+    }
+    Arc::new(error_reply::ErrorReplies::default())
+}
+
+fn runtime_reset_command_snapshot(ctx: &ChannelRuntimeContext) -> String {
+    if let Some(config_path) = runtime_config_path(ctx) {
+        let store = runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if let Some(state) = store.get(&config_path) {
+            return state.reset_command.clone();
+        }
+    }
+    "/reset".to_string()
+}
+
 fn snapshot_non_cli_excluded_tools(ctx: &ChannelRuntimeContext) -> Vec<String> {
     ctx.non_cli_excluded_tools
         .lock()
@@ -1476,6 +1626,13 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
 
     let (next_defaults, next_autonomy_policy) =
         load_runtime_defaults_from_config_file(&config_path).await?;
+    let next_inbound_filter = Arc::new(filters::InboundFilter::compile(
+        &next_autonomy_policy.channel_filters,
+    )?);
+    let next_error_replies = Arc::new(error_reply::ErrorReplies::compile(
+        &next_autonomy_policy.channel_error_language,
+        &next_autonomy_policy.channel_error_templates,
+    )?);
     let next_default_provider = providers::create_resilient_provider_with_options(
         &next_defaults.default_provider,
         next_defaults.api_key.as_deref(),
@@ -1510,6 +1667,9 @@ async fn maybe_apply_runtime_config_update(ctx: &ChannelRuntimeContext) -> Resul
             RuntimeConfigState {
                 defaults: next_defaults.clone(),
                 perplexity_filter: next_autonomy_policy.perplexity_filter.clone(),
+                inbound_filter: next_inbound_filter,
+                error_replies: next_error_replies,
+                reset_command: next_autonomy_policy.channel_reset_command.clone(),
                 last_applied_stamp: Some(stamp),
             },
         );
@@ -1740,6 +1900,48 @@ fn load_cached_model_preview(workspace_dir: &Path, provider_name: &str) -> Vec<S
         .unwrap_or_default()
 }
 
+/// Every cached model ID for `provider_name`, or `None` when the provider has
+/// no cache entry (so nothing can be checked).
+fn load_cached_models(workspace_dir: &Path, provider_name: &str) -> Option<Vec<String>> {
+    let cache_path = workspace_dir.join("state").join(MODEL_CACHE_FILE);
+    let raw = std::fs::read_to_string(cache_path).ok()?;
+    let state = serde_json::from_str::<ModelCacheState>(&raw).ok()?;
+    state
+        .entries
+        .into_iter()
+        .find(|entry| entry.provider == provider_name)
+        .map(|entry| entry.models)
+        .filter(|models| !models.is_empty())
+}
+
+fn is_model_id_char(ch: char) -> bool {
+    ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.' | ':' | '/' | '@' | '+')
+}
+
+/// Reason to refuse `/model <model>`, or `None` when the switch may proceed.
+/// Models are checked against the provider's model cache when there is one.
+fn validate_runtime_model(workspace_dir: &Path, provider: &str, model: &str) -> Option<String> {
+    if model.len() > 200 || !model.chars().all(is_model_id_char) {
+        return Some(format!(
+            "`{}` is not a valid model ID. Use `/model <model-id>`, e.g. `/model gpt-4o-mini`.",
+            truncate_with_ellipsis(model, 80)
+        ));
+    }
+    let cached = load_cached_models(workspace_dir, provider)?;
+    if cached.iter().any(|cached| cached == model) {
+        return None;
+    }
+    let preview = cached
+        .iter()
+        .take(MODEL_CACHE_PREVIEW_LIMIT)
+        .map(|cached| format!("`{cached}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Unknown model `{model}` for provider `{provider}`. Model unchanged.\nKnown models include: {preview}\nIf the list is out of date, ask the operator to run `zeroclaw models refresh --provider {provider}`."
+    ))
+}
+
 async fn get_or_create_provider(
     ctx: &ChannelRuntimeContext,
     provider_name: &str,
@@ -1894,7 +2096,10 @@ async fn handle_runtime_command_if_needed(
     target_channel: Option<&Arc<dyn Channel>>,
 ) -> bool {
     let is_slash_command = msg.content.trim_start().starts_with('/');
-    let Some(mut command) = parse_runtime_command(&msg.channel, &msg.content) else {
+    let reset_command = runtime_reset_command_snapshot(ctx);
+    let Some(mut command) =
+        parse_runtime_command_with_reset(&msg.channel, &msg.content, &reset_command)
+    else {
         return false;
     };
 
@@ -1944,10 +2149,7 @@ async fn handle_runtime_command_if_needed(
             }),
         );
 
-        if let Err(err) = channel
-            .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
-            .await
-        {
+        if let Err(err) = channel.send(&SendMessage::reply(response, msg)).await {
             tracing::warn!(
                 "Failed to send runtime command response on {}: {err}",
                 channel.name()
@@ -1977,13 +2179,7 @@ async fn handle_runtime_command_if_needed(
                         "mode": non_cli_natural_language_mode_label(mode),
                     }),
                 );
-                if let Err(err) = channel
-                    .send(
-                        &SendMessage::new(response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await
-                {
+                if let Err(err) = channel.send(&SendMessage::reply(response, msg)).await {
                     tracing::warn!(
                         "Failed to send runtime command response on {}: {err}",
                         channel.name()
@@ -2050,6 +2246,10 @@ async fn handle_runtime_command_if_needed(
             let model = raw_model.trim().trim_matches('`').to_string();
             if model.is_empty() {
                 "Model ID cannot be empty. Use `/model <model-id>`.".to_string()
+            } else if let Some(rejection) =
+                validate_runtime_model(ctx.workspace_dir.as_path(), &current.provider, &model)
+            {
+                rejection
             } else {
                 current.model = model.clone();
                 set_route_selection(ctx, &sender_key, current.clone());
@@ -2393,10 +2593,7 @@ async fn handle_runtime_command_if_needed(
         }
     };
 
-    if let Err(err) = channel
-        .send(&SendMessage::new(response, &msg.reply_target).in_thread(msg.thread_ts.clone()))
-        .await
-    {
+    if let Err(err) = channel.send(&SendMessage::reply(response, msg)).await {
         tracing::warn!(
             "Failed to send runtime command response on {}: {err}",
             channel.name()
@@ -2410,10 +2607,11 @@ async fn build_memory_context(
     mem: &dyn Memory,
     user_msg: &str,
     min_relevance_score: f64,
+    session_id: Option<&str>,
 ) -> String {
     let mut context = String::new();
 
-    if let Ok(entries) = mem.recall(user_msg, 5, None).await {
+    if let Ok(entries) = mem.recall(user_msg, 5, session_id).await {
         let mut included = 0usize;
         let mut used_chars = 0usize;
 
@@ -2726,6 +2924,55 @@ fn strip_isolated_tool_json_artifacts(message: &str, known_tool_names: &HashSet<
     result.trim().to_string()
 }
 
+/// Refresh this process's listener claims every
+/// [`listener_lock::HEARTBEAT_INTERVAL`]. A channel another process has taken
+/// over has its listener stopped; once all are gone the message bus closes
+/// and `start_channels` returns.
+fn spawn_listener_heartbeat(
+    lock: Arc<listener_lock::ListenerLock>,
+    mut held: Vec<listener_lock::ListenerIdentity>,
+    listeners: Vec<(&'static str, tokio::task::AbortHandle)>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let pid = std::process::id();
+        let mut ticks = tokio::time::interval(listener_lock::HEARTBEAT_INTERVAL);
+        ticks.tick().await;
+        while !held.is_empty() {
+            ticks.tick().await;
+            let lock = Arc::clone(&lock);
+            let refreshed = held.clone();
+            let result = tokio::task::spawn_blocking(move || {
+                lock.heartbeat(&refreshed, pid, chrono::Utc::now().timestamp())
+            })
+            .await;
+            let lost = match result {
+                Ok(Ok(lost)) => lost,
+                Ok(Err(e)) => {
+                    tracing::warn!("Channel listener heartbeat failed: {e:#}");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("Channel listener heartbeat task failed: {e}");
+                    continue;
+                }
+            };
+            for taken in lost {
+                tracing::warn!(
+                    "{} was taken over by zeroclaw pid {}; stopping its listener here",
+                    taken.identity.channel,
+                    taken.by_pid
+                );
+                held.retain(|identity| identity.key != taken.identity.key);
+                for (name, listener) in &listeners {
+                    if *name == taken.identity.channel {
+                        listener.abort();
+                    }
+                }
+            }
+        }
+    })
+}
+
 fn spawn_supervised_listener(
     ch: Arc<dyn Channel>,
     tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
@@ -2802,6 +3049,84 @@ fn spawn_supervised_listener_with_health_interval(
     })
 }
 
+/// Replay messages queued during maintenance once it ends. Holds only a weak
+/// sender so the bus still closes when every channel listener stops.
+fn spawn_maintenance_drain(
+    maintenance: Arc<crate::maintenance::MaintenanceMode>,
+    bus: tokio::sync::mpsc::WeakSender<traits::ChannelMessage>,
+) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(MAINTENANCE_DRAIN_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Some(tx) = bus.upgrade() else {
+                return;
+            };
+            let queued = maintenance.take_queued();
+            if queued.is_empty() {
+                continue;
+            }
+            tracing::info!(
+                count = queued.len(),
+                "Maintenance ended; processing queued messages"
+            );
+            for msg in queued {
+                if tx.send(msg).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Send provider failover and recovery notices to `[reliability]
+/// notify_channel`. Returns `None` when no notify target is configured or the
+/// channel is not running.
+fn spawn_failover_notifier(
+    reliability: &crate::config::ReliabilityConfig,
+    channels_by_name: &HashMap<String, Arc<dyn Channel>>,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let channel_name = reliability
+        .notify_channel
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())?;
+    let Some(recipient) = reliability
+        .notify_recipient
+        .as_deref()
+        .map(str::trim)
+        .filter(|recipient| !recipient.is_empty())
+        .map(str::to_string)
+    else {
+        tracing::warn!(
+            "reliability.notify_channel is set without notify_recipient; failover notices are off"
+        );
+        return None;
+    };
+    let Some(channel) = channels_by_name.get(channel_name).cloned() else {
+        tracing::warn!(
+            "reliability.notify_channel `{channel_name}` is not a running channel; failover notices are off"
+        );
+        return None;
+    };
+    let events = providers::failover::subscribe();
+    Some(tokio::spawn(providers::failover::forward_notifications(
+        events,
+        move |text| {
+            let channel = Arc::clone(&channel);
+            let message = SendMessage::new(text, &recipient);
+            async move {
+                if let Err(e) = channel.send(&message).await {
+                    tracing::warn!(
+                        channel = %channel.name(),
+                        "Failed to send failover notice: {e}"
+                    );
+                }
+            }
+        },
+    )))
+}
+
 fn compute_max_in_flight_messages(channel_count: usize) -> usize {
     channel_count
         .saturating_mul(CHANNEL_PARALLELISM_PER_CHANNEL)
@@ -2847,6 +3172,123 @@ fn spawn_scoped_typing_task(
     handle
 }
 
+/// Acknowledge `msg` with the maintenance reply (queueing it for later if
+/// enabled) without touching the provider.
+async fn reply_during_maintenance(
+    ctx: &ChannelRuntimeContext,
+    msg: traits::ChannelMessage,
+    channel: Option<&Arc<dyn Channel>>,
+    mut reply: String,
+) {
+    let reply_target = msg.reply_target.clone();
+    let thread_ts = msg.thread_ts.clone();
+    let channel_name = msg.channel.clone();
+    if ctx.maintenance.enqueue(msg) {
+        reply.push_str(" Your message is queued and will be answered once I'm back.");
+    }
+    runtime_trace::record_event(
+        "channel_message_maintenance_reply",
+        Some(channel_name.as_str()),
+        None,
+        None,
+        None,
+        Some(true),
+        None,
+        serde_json::json!({ "reply_target": reply_target }),
+    );
+    if let Some(channel) = channel {
+        if let Err(err) = channel
+            .send(&SendMessage::new(reply, &reply_target).in_thread(thread_ts))
+            .await
+        {
+            tracing::warn!(channel = %channel_name, "Failed to send maintenance reply: {err}");
+        }
+    }
+}
+
+/// Answer a `zeroclaw link create` code: link the sender to the CLI identity
+/// and confirm. The code never reaches the provider, memory or history.
+async fn redeem_link_code(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    channel: Option<&Arc<dyn Channel>>,
+) {
+    let identity = crate::links::identity(&msg.channel, &msg.sender);
+    let redeemed =
+        crate::links::LinkStore::open_existing(ctx.workspace_dir.as_path()).and_then(|store| {
+            match store {
+                Some(store) => {
+                    store.redeem(&msg.content, &identity, chrono::Utc::now().timestamp())
+                }
+                None => Ok(None),
+            }
+        });
+    let reply = match redeemed {
+        Ok(Some(mode)) => {
+            if console::pretty() {
+                println!("  🔗 Linked {identity} to the CLI ({})", mode.as_str());
+            }
+            format!(
+                "🔗 Linked. This chat now shares its conversation with the ZeroClaw CLI ({}).",
+                mode.as_str()
+            )
+        }
+        Ok(None) => "That link code is invalid, already used or expired. Run `zeroclaw link create` for a new one.".to_string(),
+        Err(err) => {
+            tracing::warn!("Failed to redeem link code: {err:#}");
+            "⚠️ Could not link this chat right now. Please try again.".to_string()
+        }
+    };
+    if let Some(channel) = channel {
+        let _ = channel.send(&SendMessage::reply(reply, msg)).await;
+    }
+}
+
+/// Skip a message matched by `[channels_config.filters]`: no provider call,
+/// no memory auto-save, and at most the rule's acknowledgment as a reply.
+async fn skip_filtered_message(
+    ctx: &ChannelRuntimeContext,
+    msg: &traits::ChannelMessage,
+    channel: Option<&Arc<dyn Channel>>,
+    matched: filters::FilterMatch,
+) {
+    if console::pretty() {
+        println!("  🚫 Skipped by channel filter `{}`", matched.rule);
+    }
+    runtime_trace::record_event(
+        "channel_message_filtered",
+        Some(msg.channel.as_str()),
+        None,
+        None,
+        None,
+        Some(true),
+        Some("skipped by [channels_config.filters]"),
+        serde_json::json!({
+            "sender": msg.sender,
+            "message_id": msg.id,
+            "rule": matched.rule,
+            "acknowledged": matched.acknowledgment.is_some(),
+        }),
+    );
+    ctx.observer
+        .record_event(&crate::observability::ObserverEvent::ChannelMessage {
+            channel: msg.channel.clone(),
+            direction: "filtered".into(),
+        });
+    let (Some(channel), Some(reply)) = (channel, matched.acknowledgment) else {
+        return;
+    };
+    if let Err(err) = channel.send(&SendMessage::reply(reply, msg)).await {
+        tracing::warn!(channel = %msg.channel, "Failed to send filter acknowledgment: {err}");
+    }
+}
+
+async fn push_draft_update(channel: &dyn Channel, reply_target: &str, draft_id: &str, text: &str) {
+    if let Err(e) = channel.update_draft(reply_target, draft_id, text).await {
+        tracing::debug!("Draft update failed: {e}");
+    }
+}
+
 async fn process_channel_message(
     ctx: Arc<ChannelRuntimeContext>,
     msg: traits::ChannelMessage,
@@ -2856,12 +3298,12 @@ async fn process_channel_message(
         return;
     }
 
-    println!(
-        "  💬 [{}] from {}: {}",
-        msg.channel,
-        msg.sender,
-        truncate_with_ellipsis(&msg.content, 80)
-    );
+    console::emit(&console::Event::MessageReceived {
+        channel: &msg.channel,
+        sender: &msg.sender,
+        message_id: &msg.id,
+        preview: channel_log_body(&msg.content, 80),
+    });
     runtime_trace::record_event(
         "channel_message_inbound",
         Some(msg.channel.as_str()),
@@ -2878,6 +3320,21 @@ async fn process_channel_message(
         }),
     );
 
+    // ── Maintenance: canned reply, no provider call ──────
+    // Checked before hooks so queued messages run through them on replay.
+    if let Some(reply) = ctx.maintenance.canned_reply() {
+        let channel = ctx.channels_by_name.get(&msg.channel).cloned();
+        reply_during_maintenance(ctx.as_ref(), msg, channel.as_ref(), reply).await;
+        return;
+    }
+
+    // ── Identity link handshake: answered here, kept out of history ──
+    if crate::links::looks_like_code(&msg.content) {
+        let channel = ctx.channels_by_name.get(&msg.channel).cloned();
+        redeem_link_code(ctx.as_ref(), &msg, channel.as_ref()).await;
+        return;
+    }
+
     // ── Hook: on_message_received (modifying) ────────────
     let msg = if let Some(hooks) = &ctx.hooks {
         match hooks.run_on_message_received(msg).await {
@@ -2898,6 +3355,11 @@ async fn process_channel_message(
     if handle_runtime_command_if_needed(ctx.as_ref(), &msg, target_channel.as_ref()).await {
         return;
     }
+    // Runtime commands above always win over the inbound filters.
+    if let Some(matched) = runtime_inbound_filter_snapshot(ctx.as_ref()).check(&msg.content) {
+        skip_filtered_message(ctx.as_ref(), &msg, target_channel.as_ref(), matched).await;
+        return;
+    }
     if !msg.content.trim_start().starts_with('/') {
         let perplexity_cfg = runtime_perplexity_filter_snapshot(ctx.as_ref());
         if let Some(assessment) =
@@ -2933,67 +3395,111 @@ or tune thresholds in config.",
                     perplexity_cfg.symbol_ratio_threshold,
                     assessment.suspicious_token_count
                 );
-                let _ = channel
-                    .send(
-                        &SendMessage::new(warning, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await;
+                let _ = channel.send(&SendMessage::reply(warning, &msg)).await;
             }
             return;
         }
     }
 
-    let history_key = conversation_history_key(&msg);
+    // ── Delegate agent: channels mapped in [channels_config.agents] ──
+    let agent = match ctx.delegate_agents.channel_agent(&msg.channel) {
+        None => None,
+        Some(name) => match ctx.delegate_agents.target(name) {
+            Ok(target) => Some(target),
+            Err(err) => {
+                let safe_err = providers::sanitize_api_error(&format!("{err:#}"));
+                let message =
+                    format!("⚠️ Failed to initialize agent `{name}`.\nDetails: {safe_err}");
+                if let Some(channel) = target_channel.as_ref() {
+                    let _ = channel.send(&SendMessage::reply(message, &msg)).await;
+                }
+                return;
+            }
+        },
+    };
+    let memory_session = agent
+        .as_ref()
+        .map(crate::agent::delegates::AgentTarget::memory_session);
+
+    let history_key = match &agent {
+        Some(target) => target.history_key(&conversation_history_key(&msg)),
+        None => conversation_history_key(&msg),
+    };
     // Try classification first, fall back to sender/default route
-    let route = classify_message_route(ctx.as_ref(), &msg.content)
-        .unwrap_or_else(|| get_route_selection(ctx.as_ref(), &history_key));
-    let runtime_defaults = runtime_defaults_snapshot(ctx.as_ref());
-    let active_provider = match get_or_create_provider(ctx.as_ref(), &route.provider).await {
-        Ok(provider) => provider,
-        Err(err) => {
-            let safe_err = providers::sanitize_api_error(&err.to_string());
-            let message = format!(
-                "⚠️ Failed to initialize provider `{}`. Please run `/models` to choose another provider.\nDetails: {safe_err}",
-                route.provider
-            );
-            if let Some(channel) = target_channel.as_ref() {
-                let _ = channel
-                    .send(
-                        &SendMessage::new(message, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
-                    .await;
+    let route = match &agent {
+        Some(target) => ChannelRouteSelection {
+            provider: target.config.provider.clone(),
+            model: target.config.model.clone(),
+        },
+        None => classify_message_route(ctx.as_ref(), &msg.content)
+            .unwrap_or_else(|| get_route_selection(ctx.as_ref(), &history_key)),
+    };
+    let (temperature, max_tool_iterations) = match &agent {
+        Some(target) => (target.temperature(), target.config.max_iterations),
+        None => (
+            runtime_defaults_snapshot(ctx.as_ref()).temperature,
+            ctx.max_tool_iterations,
+        ),
+    };
+    let active_provider = if let Some(target) = &agent {
+        Arc::clone(&target.provider)
+    } else {
+        match get_or_create_provider(ctx.as_ref(), &route.provider).await {
+            Ok(provider) => provider,
+            Err(err) => {
+                let safe_err = providers::sanitize_api_error(&err.to_string());
+                let message = format!(
+                    "⚠️ Failed to initialize provider `{}`. Please run `/models` to choose another provider.\nDetails: {safe_err}",
+                    route.provider
+                );
+                if let Some(channel) = target_channel.as_ref() {
+                    let _ = channel.send(&SendMessage::reply(message, &msg)).await;
+                }
+                return;
             }
-            return;
         }
     };
-    if ctx.auto_save_memory && msg.content.chars().count() >= AUTOSAVE_MIN_MESSAGE_CHARS {
-        let autosave_key = conversation_memory_key(&msg);
-        let _ = ctx
-            .memory
-            .store(
-                &autosave_key,
-                &msg.content,
-                crate::memory::MemoryCategory::Conversation,
-                None,
-            )
-            .await;
+    if ctx.auto_save_memory {
+        autosave_channel_message(
+            ctx.as_ref(),
+            &msg,
+            active_provider.as_ref(),
+            &route.model,
+            memory_session.as_deref(),
+        )
+        .await;
     }
 
-    println!("  ⏳ Processing message...");
+    if console::pretty() {
+        println!("  ⏳ Processing message...");
+    }
     let started_at = Instant::now();
 
-    let had_prior_history = ctx
-        .conversation_histories
-        .lock()
-        .unwrap_or_else(|e| e.into_inner())
-        .get(&history_key)
-        .is_some_and(|turns| !turns.is_empty());
+    // Senders linked with `zeroclaw link` share the CLI's conversation.
+    let linked = if agent.is_none() {
+        crate::links::LinkedConversation::resolve(
+            ctx.workspace_dir.as_path(),
+            &crate::links::identity(&msg.channel, &msg.sender),
+        )
+    } else {
+        None
+    };
+    let shared_turns = linked
+        .as_ref()
+        .map(|link| link.turns(MAX_CHANNEL_HISTORY))
+        .unwrap_or_default();
+
+    let had_prior_history = !shared_turns.is_empty()
+        || ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&history_key)
+            .is_some_and(|turns| !turns.is_empty());
 
     // Inject per-message timestamp so the LLM always knows the current time,
     // even in multi-turn conversations where the system prompt may be stale.
-    let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S %Z");
+    let now = crate::config::timezone::transcript_stamp_now();
     let timestamped_content = format!("[{now}] {}", msg.content);
 
     // Preserve user turn before the LLM call so interrupted requests keep context.
@@ -3003,22 +3509,44 @@ or tune thresholds in config.",
         ChatMessage::user(&timestamped_content),
     );
 
-    // Build history from per-sender conversation cache.
-    let prior_turns_raw = ctx
+    // Build history from per-sender conversation cache. A merged link reads
+    // the shared log instead; a mirrored one reads it ahead of its own turns.
+    let own_turns = ctx
         .conversation_histories
         .lock()
         .unwrap_or_else(|e| e.into_inner())
         .get(&history_key)
         .cloned()
         .unwrap_or_default();
+    let prior_turns_raw = match linked.as_ref().map(crate::links::LinkedConversation::mode) {
+        Some(crate::links::LinkMode::Merged) => {
+            let mut turns = shared_turns;
+            turns.push(ChatMessage::user(&timestamped_content));
+            turns
+        }
+        Some(crate::links::LinkMode::Mirrored) => {
+            let mut turns = shared_turns;
+            turns.extend(own_turns);
+            turns
+        }
+        None => own_turns,
+    };
+    if let Some(link) = &linked {
+        link.record(&ChatMessage::user(&timestamped_content));
+    }
     let mut prior_turns = normalize_cached_channel_turns(prior_turns_raw);
 
     // Only enrich with memory context when there is no prior conversation
     // history. Follow-up turns already include context from previous messages.
     if !had_prior_history {
-        let memory_context =
-            build_memory_context(ctx.memory.as_ref(), &msg.content, ctx.min_relevance_score).await;
-        if let Some(last_turn) = prior_turns.last_mut() {
+        let memory_context = build_memory_context(
+            ctx.memory.as_ref(),
+            &msg.content,
+            ctx.min_relevance_score,
+            memory_session.as_deref(),
+        )
+        .await;
+        if let Some(last_turn) = prior_turns.last_mut() {
             if last_turn.role == "user" && !memory_context.is_empty() {
                 last_turn.content = format!("{memory_context}{timestamped_content}");
             }
@@ -3027,13 +3555,21 @@ or tune thresholds in config.",
 
     let expose_internal_tool_details =
         msg.channel == "cli" || should_expose_internal_tool_details(&msg.content);
-    let excluded_tools_snapshot = if msg.channel == "cli" {
+    let mut excluded_tools_snapshot = if msg.channel == "cli" {
         Vec::new()
     } else {
         snapshot_non_cli_excluded_tools(ctx.as_ref())
     };
+    if let Some(target) = &agent {
+        excluded_tools_snapshot
+            .extend(target.excluded_tools(ctx.tools_registry.iter().map(|tool| tool.name())));
+    }
+    let base_system_prompt = match &agent {
+        Some(target) => target.config.system_prompt.as_deref().unwrap_or_default(),
+        None => ctx.system_prompt.as_str(),
+    };
     let mut system_prompt = build_channel_system_prompt(
-        ctx.system_prompt.as_str(),
+        base_system_prompt,
         &msg.channel,
         &msg.reply_target,
         expose_internal_tool_details,
@@ -3045,9 +3581,14 @@ or tune thresholds in config.",
     ));
     let mut history = vec![ChatMessage::system(system_prompt)];
     history.extend(prior_turns);
+    // Outbound filters only see the complete reply, so drafts stay off.
     let use_streaming = target_channel
         .as_ref()
-        .is_some_and(|ch| ch.supports_draft_updates());
+        .is_some_and(|ch| ch.supports_draft_updates())
+        && !ctx
+            .hooks
+            .as_ref()
+            .is_some_and(|hooks| hooks.filters_outbound());
 
     tracing::debug!(
         channel = %msg.channel,
@@ -3066,12 +3607,7 @@ or tune thresholds in config.",
 
     let draft_message_id = if use_streaming {
         if let Some(channel) = target_channel.as_ref() {
-            match channel
-                .send_draft(
-                    &SendMessage::new("...", &msg.reply_target).in_thread(msg.thread_ts.clone()),
-                )
-                .await
-            {
+            match channel.send_draft(&SendMessage::reply("...", &msg)).await {
                 Ok(id) => id,
                 Err(e) => {
                     tracing::debug!("Failed to send draft on {}: {e}", channel.name());
@@ -3096,8 +3632,34 @@ or tune thresholds in config.",
         let suppress_internal_progress = !expose_internal_tool_details;
         Some(tokio::spawn(async move {
             let mut accumulated = String::new();
-            while let Some(delta) = rx.recv().await {
+            let mut throttle = edit_throttle::EditThrottle::new(channel.draft_update_interval());
+            loop {
+                let delta = if let Some(deadline) = throttle.deadline() {
+                    tokio::select! {
+                        delta = rx.recv() => delta,
+                        () = tokio::time::sleep_until(deadline) => {
+                            push_draft_update(channel.as_ref(), &reply_target, &draft_id, &accumulated)
+                                .await;
+                            throttle.edited(tokio::time::Instant::now());
+                            continue;
+                        }
+                    }
+                } else {
+                    rx.recv().await
+                };
+                // On close the final reply replaces the draft, so nothing held
+                // back needs flushing.
+                let Some(delta) = delta else {
+                    break;
+                };
+
                 if delta == crate::agent::loop_::DRAFT_CLEAR_SENTINEL {
+                    // Show held-back progress before it is wiped.
+                    if throttle.has_pending() {
+                        push_draft_update(channel.as_ref(), &reply_target, &draft_id, &accumulated)
+                            .await;
+                        throttle.edited(tokio::time::Instant::now());
+                    }
                     accumulated.clear();
                     continue;
                 }
@@ -3107,11 +3669,10 @@ or tune thresholds in config.",
                 }
 
                 accumulated.push_str(visible_delta);
-                if let Err(e) = channel
-                    .update_draft(&reply_target, &draft_id, &accumulated)
-                    .await
-                {
-                    tracing::debug!("Draft update failed: {e}");
+                if throttle.offer(tokio::time::Instant::now()) {
+                    push_draft_update(channel.as_ref(), &reply_target, &draft_id, &accumulated)
+                        .await;
+                    throttle.edited(tokio::time::Instant::now());
                 }
             }
         }))
@@ -3148,7 +3709,7 @@ or tune thresholds in config.",
     }
 
     let timeout_budget_secs =
-        channel_message_timeout_budget_secs(ctx.message_timeout_secs, ctx.max_tool_iterations);
+        channel_message_timeout_budget_secs(ctx.message_timeout_secs, max_tool_iterations);
     let (approval_prompt_tx, mut approval_prompt_rx) =
         tokio::sync::mpsc::unbounded_channel::<crate::agent::loop_::NonCliApprovalPrompt>();
     let approval_prompt_task = if msg.channel == "cli" {
@@ -3189,6 +3750,20 @@ or tune thresholds in config.",
             prompt_tx: approval_prompt_tx.clone(),
         })
     };
+    let (progress_sink, tool_progress_task) = match target_channel.as_ref() {
+        Some(channel) if msg.channel != "cli" && ctx.tool_progress_interval_secs > 0 => {
+            let (sink, rx) = crate::tools::progress::ProgressSink::channel();
+            let task = tokio::spawn(tool_progress::forward_tool_progress(
+                Arc::clone(channel),
+                msg.reply_target.clone(),
+                msg.thread_ts.clone(),
+                rx,
+                Duration::from_secs(ctx.tool_progress_interval_secs),
+            ));
+            (Some(sink), Some(task))
+        }
+        _ => (None, None),
+    };
 
     let llm_result = tokio::select! {
         () = cancellation_token.cancelled() => LlmExecutionResult::Cancelled,
@@ -3201,13 +3776,14 @@ or tune thresholds in config.",
                 ctx.observer.as_ref(),
                 route.provider.as_str(),
                 route.model.as_str(),
-                runtime_defaults.temperature,
+                temperature,
                 true,
                 Some(ctx.approval_manager.as_ref()),
                 msg.channel.as_str(),
                 non_cli_approval_context,
+                progress_sink,
                 &ctx.multimodal,
-                ctx.max_tool_iterations,
+                max_tool_iterations,
                 Some(cancellation_token.clone()),
                 delta_tx,
                 ctx.hooks.as_deref(),
@@ -3224,6 +3800,11 @@ or tune thresholds in config.",
     if let Some(handle) = draft_updater {
         let _ = handle.await;
     }
+    if let Some(handle) = tool_progress_task {
+        // A cancelled turn can leave a tool running on a blocking thread that
+        // still holds the sink, so stop showing progress instead of waiting.
+        handle.abort();
+    }
 
     if let Some(token) = typing_cancellation.as_ref() {
         token.cancel();
@@ -3363,11 +3944,15 @@ or tune thresholds in config.",
                 &history_key,
                 ChatMessage::assistant(&history_response),
             );
-            println!(
-                "  🤖 Reply ({}ms): {}",
-                started_at.elapsed().as_millis(),
-                truncate_with_ellipsis(&delivered_response, 80)
-            );
+            if let Some(link) = &linked {
+                link.record(&ChatMessage::assistant(&history_response));
+            }
+            console::emit(&console::Event::ReplySent {
+                channel: &msg.channel,
+                sender: &msg.sender,
+                elapsed_ms: started_at.elapsed().as_millis(),
+                preview: channel_log_body(&delivered_response, 80),
+            });
             if let Some(channel) = target_channel.as_ref() {
                 if let Some(ref draft_id) = draft_message_id {
                     if let Err(e) = channel
@@ -3376,20 +3961,24 @@ or tune thresholds in config.",
                     {
                         tracing::warn!("Failed to finalize draft: {e}; sending as new message");
                         let _ = channel
-                            .send(
-                                &SendMessage::new(&delivered_response, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
+                            .send(&SendMessage::reply(&delivered_response, &msg))
                             .await;
                     }
                 } else if let Err(e) = channel
-                    .send(
-                        &SendMessage::new(delivered_response, &msg.reply_target)
-                            .in_thread(msg.thread_ts.clone()),
-                    )
+                    .send(&SendMessage::reply(delivered_response, &msg))
                     .await
                 {
-                    eprintln!("  ❌ Failed to reply on {}: {e}", channel.name());
+                    console::emit(&console::Event::Error {
+                        channel: &msg.channel,
+                        sender: &msg.sender,
+                        elapsed_ms: started_at.elapsed().as_millis(),
+                        error_class: "delivery",
+                        request_id: None,
+                        message: providers::sanitize_api_error(&format!(
+                            "Failed to reply on {}: {e}",
+                            channel.name()
+                        )),
+                    });
                 }
             }
         }
@@ -3428,11 +4017,16 @@ or tune thresholds in config.",
                 } else {
                     "⚠️ Context window exceeded for this conversation. Please resend your last message."
                 };
-                eprintln!(
-                    "  ⚠️ Context window exceeded after {}ms; sender history compacted={}",
-                    started_at.elapsed().as_millis(),
-                    compacted
-                );
+                console::emit(&console::Event::Error {
+                    channel: &msg.channel,
+                    sender: &msg.sender,
+                    elapsed_ms: started_at.elapsed().as_millis(),
+                    error_class: providers::reliable::ErrorClass::ContextTooLong.as_str(),
+                    request_id: None,
+                    message: format!(
+                        "Context window exceeded; sender history compacted={compacted}"
+                    ),
+                });
                 runtime_trace::record_event(
                     "channel_message_error",
                     Some(msg.channel.as_str()),
@@ -3453,16 +4047,11 @@ or tune thresholds in config.",
                             .finalize_draft(&msg.reply_target, draft_id, error_text)
                             .await;
                     } else {
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(error_text, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = channel.send(&SendMessage::reply(error_text, &msg)).await;
                     }
                 }
             } else if is_tool_iteration_limit_error(&e) {
-                let limit = ctx.max_tool_iterations.max(1);
+                let limit = max_tool_iterations.max(1);
                 let pause_text = format!(
                     "⚠️ Reached tool-iteration limit ({limit}) for this turn. Context and progress were preserved. Reply \"continue\" to resume, or increase `agent.max_tool_iterations`."
                 );
@@ -3493,22 +4082,31 @@ or tune thresholds in config.",
                             .finalize_draft(&msg.reply_target, draft_id, &pause_text)
                             .await;
                     } else {
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(pause_text, &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = channel.send(&SendMessage::reply(pause_text, &msg)).await;
                     }
                 }
             } else {
-                eprintln!(
-                    "  ❌ LLM error after {}ms: {e}",
-                    started_at.elapsed().as_millis()
-                );
                 let safe_error = providers::sanitize_api_error(&e.to_string());
+                let reply = runtime_error_replies_snapshot(ctx.as_ref()).render(&e);
+                let refused = reply.class == providers::reliable::ErrorClass::Refused;
+                console::emit(&console::Event::Error {
+                    channel: &msg.channel,
+                    sender: &msg.sender,
+                    elapsed_ms: started_at.elapsed().as_millis(),
+                    error_class: reply.class.as_str(),
+                    request_id: Some(&reply.request_id),
+                    message: if refused {
+                        format!("LLM refused: {safe_error}")
+                    } else {
+                        format!("LLM error: {safe_error}")
+                    },
+                });
                 runtime_trace::record_event(
-                    "channel_message_error",
+                    if refused {
+                        "channel_message_refused"
+                    } else {
+                        "channel_message_error"
+                    },
                     Some(msg.channel.as_str()),
                     Some(route.provider.as_str()),
                     Some(route.model.as_str()),
@@ -3532,21 +4130,20 @@ or tune thresholds in config.",
                     append_sender_turn(
                         ctx.as_ref(),
                         &history_key,
-                        ChatMessage::assistant("[Task failed — not continuing this request]"),
+                        ChatMessage::assistant(if refused {
+                            "[Request refused by the model — not continuing this request]"
+                        } else {
+                            "[Task failed — not continuing this request]"
+                        }),
                     );
                 }
                 if let Some(channel) = target_channel.as_ref() {
                     if let Some(ref draft_id) = draft_message_id {
                         let _ = channel
-                            .finalize_draft(&msg.reply_target, draft_id, &format!("⚠️ Error: {e}"))
+                            .finalize_draft(&msg.reply_target, draft_id, &reply.text)
                             .await;
                     } else {
-                        let _ = channel
-                            .send(
-                                &SendMessage::new(format!("⚠️ Error: {e}"), &msg.reply_target)
-                                    .in_thread(msg.thread_ts.clone()),
-                            )
-                            .await;
+                        let _ = channel.send(&SendMessage::reply(reply.text, &msg)).await;
                     }
                 }
             }
@@ -3554,7 +4151,7 @@ or tune thresholds in config.",
         LlmExecutionResult::Completed(Err(_)) => {
             let timeout_msg = format!(
                 "LLM response timed out after {}s (base={}s, max_tool_iterations={})",
-                timeout_budget_secs, ctx.message_timeout_secs, ctx.max_tool_iterations
+                timeout_budget_secs, ctx.message_timeout_secs, max_tool_iterations
             );
             runtime_trace::record_event(
                 "channel_message_timeout",
@@ -3569,11 +4166,14 @@ or tune thresholds in config.",
                     "elapsed_ms": started_at.elapsed().as_millis(),
                 }),
             );
-            eprintln!(
-                "  ❌ {} (elapsed: {}ms)",
-                timeout_msg,
-                started_at.elapsed().as_millis()
-            );
+            console::emit(&console::Event::Error {
+                channel: &msg.channel,
+                sender: &msg.sender,
+                elapsed_ms: started_at.elapsed().as_millis(),
+                error_class: "timeout",
+                request_id: None,
+                message: timeout_msg,
+            });
             // Close the orphan user turn so subsequent messages don't
             // inherit this timed-out request as unfinished context.
             append_sender_turn(
@@ -3589,12 +4189,7 @@ or tune thresholds in config.",
                         .finalize_draft(&msg.reply_target, draft_id, error_text)
                         .await;
                 } else {
-                    let _ = channel
-                        .send(
-                            &SendMessage::new(error_text, &msg.reply_target)
-                                .in_thread(msg.thread_ts.clone()),
-                        )
-                        .await;
+                    let _ = channel.send(&SendMessage::reply(error_text, &msg)).await;
                 }
             }
         }
@@ -3692,11 +4287,14 @@ async fn run_message_dispatch_loop(
 }
 
 /// Load OpenClaw format bootstrap files into the prompt.
+///
+/// Files toggled off in `sections` are skipped without a missing-file marker.
 fn load_openclaw_bootstrap_files(
     prompt: &mut String,
     workspace_dir: &std::path::Path,
     max_chars_per_file: usize,
     identity_config: Option<&crate::config::IdentityConfig>,
+    sections: &crate::config::PromptSectionsConfig,
 ) {
     prompt.push_str(
         "The following workspace files define your identity, behavior, and context. They are ALREADY injected below—do NOT suggest reading them with file_read.\n\n",
@@ -3705,18 +4303,20 @@ fn load_openclaw_bootstrap_files(
     let bootstrap_files = ["AGENTS.md", "SOUL.md", "TOOLS.md", "IDENTITY.md", "USER.md"];
 
     for filename in &bootstrap_files {
-        inject_workspace_file(prompt, workspace_dir, filename, max_chars_per_file);
+        if sections.bootstrap_file_enabled(filename) {
+            inject_workspace_file(prompt, workspace_dir, filename, max_chars_per_file);
+        }
     }
 
     // BOOTSTRAP.md — only if it exists (first-run ritual)
     let bootstrap_path = workspace_dir.join("BOOTSTRAP.md");
-    if bootstrap_path.exists() {
+    if bootstrap_path.exists() && sections.bootstrap_file_enabled("BOOTSTRAP.md") {
         inject_workspace_file(prompt, workspace_dir, "BOOTSTRAP.md", max_chars_per_file);
     }
 
     // MEMORY.md — curated long-term memory (main session only, when present)
     let memory_path = workspace_dir.join("MEMORY.md");
-    if memory_path.exists() {
+    if memory_path.exists() && sections.bootstrap_file_enabled("MEMORY.md") {
         inject_workspace_file(prompt, workspace_dir, "MEMORY.md", max_chars_per_file);
     }
 
@@ -3724,7 +4324,9 @@ fn load_openclaw_bootstrap_files(
     for file in extra_files {
         match normalize_openclaw_identity_extra_file(file) {
             Some(safe_relative) => {
-                inject_workspace_file(prompt, workspace_dir, safe_relative, max_chars_per_file);
+                if sections.bootstrap_file_enabled(safe_relative) {
+                    inject_workspace_file(prompt, workspace_dir, safe_relative, max_chars_per_file);
+                }
             }
             None => {
                 tracing::warn!(
@@ -3767,7 +4369,7 @@ fn normalize_openclaw_identity_extra_file(raw: &str) -> Option<&str> {
 /// 3. Skills — full skill instructions and tool metadata
 /// 4. Workspace — working directory
 /// 5. Bootstrap files — AGENTS, SOUL, TOOLS, IDENTITY, USER, BOOTSTRAP, MEMORY (when present)
-/// 6. Date & Time — timezone for cache stability
+/// 6. Date & Time — weekday, time, UTC offset and IANA zone (`[agent] timezone`)
 /// 7. Runtime — host, OS, model
 ///
 /// When `identity_config` is set to AIEOS format, the bootstrap files section
@@ -3775,6 +4377,9 @@ fn normalize_openclaw_identity_extra_file(raw: &str) -> Option<&str> {
 ///
 /// Daily memory files (`memory/*.md`) are NOT injected — they are accessed
 /// on-demand via `memory_recall` / `memory_search` tools.
+///
+/// Sections can be switched off with `[agent.prompt_sections]`; see
+/// [`build_system_prompt_with_sections`].
 pub fn build_system_prompt(
     workspace_dir: &std::path::Path,
     model_name: &str,
@@ -3804,11 +4409,38 @@ pub fn build_system_prompt_with_mode(
     bootstrap_max_chars: Option<usize>,
     native_tools: bool,
     skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+) -> String {
+    build_system_prompt_with_sections(
+        workspace_dir,
+        model_name,
+        tools,
+        skills,
+        identity_config,
+        bootstrap_max_chars,
+        native_tools,
+        skills_prompt_mode,
+        &crate::config::PromptSectionsConfig::default(),
+    )
+}
+
+/// [`build_system_prompt_with_mode`] with only the sections enabled in
+/// `sections`. Safety stays on unless it is off and acknowledged.
+pub fn build_system_prompt_with_sections(
+    workspace_dir: &std::path::Path,
+    model_name: &str,
+    tools: &[(&str, &str)],
+    skills: &[crate::skills::Skill],
+    identity_config: Option<&crate::config::IdentityConfig>,
+    bootstrap_max_chars: Option<usize>,
+    native_tools: bool,
+    skills_prompt_mode: crate::config::SkillsPromptInjectionMode,
+    sections: &crate::config::PromptSectionsConfig,
 ) -> String {
     use std::fmt::Write;
     let mut prompt = String::with_capacity(8192);
 
     // ── 1. Tooling ──────────────────────────────────────────────
+    let tools: &[(&str, &str)] = if sections.tools { tools } else { &[] };
     if !tools.is_empty() {
         prompt.push_str("## Tools\n\n");
         prompt.push_str("You have access to the following tools:\n\n");
@@ -3857,17 +4489,19 @@ pub fn build_system_prompt_with_mode(
     }
 
     // ── 2. Safety ───────────────────────────────────────────────
-    prompt.push_str("## Safety\n\n");
-    prompt.push_str(
-        "- Do not exfiltrate private data.\n\
-         - Do not run destructive commands without asking.\n\
-         - Do not bypass oversight or approval mechanisms.\n\
-         - Prefer `trash` over `rm` (recoverable beats gone forever).\n\
-         - When in doubt, ask before acting externally.\n\n",
-    );
+    if sections.safety_enabled() {
+        prompt.push_str("## Safety\n\n");
+        prompt.push_str(
+            "- Do not exfiltrate private data.\n\
+             - Do not run destructive commands without asking.\n\
+             - Do not bypass oversight or approval mechanisms.\n\
+             - Prefer `trash` over `rm` (recoverable beats gone forever).\n\
+             - When in doubt, ask before acting externally.\n\n",
+        );
+    }
 
     // ── 3. Skills (full or compact, based on config) ─────────────
-    if !skills.is_empty() {
+    if sections.skills && !skills.is_empty() {
         prompt.push_str(&crate::skills::skills_to_prompt_with_mode(
             skills,
             workspace_dir,
@@ -3877,80 +4511,100 @@ pub fn build_system_prompt_with_mode(
     }
 
     // ── 4. Workspace ────────────────────────────────────────────
-    let _ = writeln!(
-        prompt,
-        "## Workspace\n\nWorking directory: `{}`\n",
-        workspace_dir.display()
-    );
+    if sections.workspace {
+        let _ = writeln!(
+            prompt,
+            "## Workspace\n\nWorking directory: `{}`\n",
+            workspace_dir.display()
+        );
+    }
 
     // ── 5. Bootstrap files (injected into context) ──────────────
-    prompt.push_str("## Project Context\n\n");
-
-    // Check if AIEOS identity is configured
-    if let Some(config) = identity_config {
-        if identity::is_aieos_configured(config) {
-            // Load AIEOS identity
-            match identity::load_aieos_identity(config, workspace_dir) {
-                Ok(Some(aieos_identity)) => {
-                    let aieos_prompt = identity::aieos_to_system_prompt(&aieos_identity);
-                    if !aieos_prompt.is_empty() {
-                        prompt.push_str(&aieos_prompt);
-                        prompt.push_str("\n\n");
+    if sections.bootstrap {
+        prompt.push_str("## Project Context\n\n");
+
+        // Check if AIEOS identity is configured
+        if let Some(config) = identity_config {
+            if identity::is_aieos_configured(config) {
+                // Load AIEOS identity
+                match identity::load_aieos_identity(config, workspace_dir) {
+                    Ok(Some(aieos_identity)) => {
+                        let aieos_prompt = identity::aieos_to_system_prompt(&aieos_identity);
+                        if !aieos_prompt.is_empty() {
+                            prompt.push_str(&aieos_prompt);
+                            prompt.push_str("\n\n");
+                        }
+                    }
+                    Ok(None) => {
+                        // No AIEOS identity loaded (shouldn't happen if is_aieos_configured returned true)
+                        // Fall back to OpenClaw bootstrap files
+                        let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
+                        load_openclaw_bootstrap_files(
+                            &mut prompt,
+                            workspace_dir,
+                            max_chars,
+                            identity_config,
+                            sections,
+                        );
+                    }
+                    Err(e) => {
+                        // Log error but don't fail - fall back to OpenClaw
+                        eprintln!(
+                            "Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format."
+                        );
+                        let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
+                        load_openclaw_bootstrap_files(
+                            &mut prompt,
+                            workspace_dir,
+                            max_chars,
+                            identity_config,
+                            sections,
+                        );
                     }
                 }
-                Ok(None) => {
-                    // No AIEOS identity loaded (shouldn't happen if is_aieos_configured returned true)
-                    // Fall back to OpenClaw bootstrap files
-                    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(
-                        &mut prompt,
-                        workspace_dir,
-                        max_chars,
-                        identity_config,
-                    );
-                }
-                Err(e) => {
-                    // Log error but don't fail - fall back to OpenClaw
-                    eprintln!(
-                        "Warning: Failed to load AIEOS identity: {e}. Using OpenClaw format."
-                    );
-                    let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-                    load_openclaw_bootstrap_files(
-                        &mut prompt,
-                        workspace_dir,
-                        max_chars,
-                        identity_config,
-                    );
-                }
+            } else {
+                // OpenClaw format
+                let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
+                load_openclaw_bootstrap_files(
+                    &mut prompt,
+                    workspace_dir,
+                    max_chars,
+                    identity_config,
+                    sections,
+                );
             }
         } else {
-            // OpenClaw format
+            // No identity config - use OpenClaw format
             let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-            load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars, identity_config);
+            load_openclaw_bootstrap_files(
+                &mut prompt,
+                workspace_dir,
+                max_chars,
+                identity_config,
+                sections,
+            );
         }
-    } else {
-        // No identity config - use OpenClaw format
-        let max_chars = bootstrap_max_chars.unwrap_or(BOOTSTRAP_MAX_CHARS);
-        load_openclaw_bootstrap_files(&mut prompt, workspace_dir, max_chars, identity_config);
     }
 
     // ── 6. Date & Time ──────────────────────────────────────────
-    let now = chrono::Local::now();
-    let _ = writeln!(
-        prompt,
-        "## Current Date & Time\n\n{} ({})\n",
-        now.format("%Y-%m-%d %H:%M:%S"),
-        now.format("%Z")
-    );
+    if sections.datetime {
+        let _ = writeln!(
+            prompt,
+            "## Current Date & Time\n\n{}\n",
+            crate::config::timezone::prompt_datetime_now()
+        );
+    }
 
     // ── 7. Runtime ──────────────────────────────────────────────
-    let host =
-        hostname::get().map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().to_string());
-    let _ = writeln!(
-        prompt,
-        "## Runtime\n\nHost: {host} | OS: {} | Model: {model_name}\n",
-        std::env::consts::OS,
-    );
+    if sections.runtime {
+        let host =
+            hostname::get().map_or_else(|_| "unknown".into(), |h| h.to_string_lossy().to_string());
+        let _ = writeln!(
+            prompt,
+            "## Runtime\n\nHost: {host} | OS: {} | Model: {model_name}\n",
+            std::env::consts::OS,
+        );
+    }
 
     // ── 8. Channel Capabilities ─────────────────────────────────────
     prompt.push_str("## Channel Capabilities\n\n");
@@ -4165,7 +4819,7 @@ fn maybe_restart_managed_daemon_service() -> Result<bool> {
 
 pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Config) -> Result<()> {
     match command {
-        crate::ChannelCommands::Start => {
+        crate::ChannelCommands::Start { .. } => {
             anyhow::bail!("Start must be handled in main.rs (requires async runtime)")
         }
         crate::ChannelCommands::Doctor => {
@@ -4210,6 +4864,35 @@ pub(crate) async fn handle_command(command: crate::ChannelCommands, config: &Con
         crate::ChannelCommands::BindTelegram { identity } => {
             bind_telegram_identity(config, &identity).await
         }
+        crate::ChannelCommands::TestFilter { text, channel } => {
+            let filter = filters::InboundFilter::compile(&config.channels_config.filters)?;
+            if filter.is_empty() {
+                println!(
+                    "No [channels_config.filters] rules configured; every message is processed."
+                );
+            }
+            if parse_runtime_command_with_reset(
+                &channel,
+                &text,
+                &config.channels_config.reset_command,
+            )
+            .is_some()
+            {
+                println!("Runtime command on {channel}: handled before filters, never skipped.");
+                return Ok(());
+            }
+            match filter.check(&text) {
+                Some(matched) => {
+                    println!("Matched: {}", matched.rule);
+                    match matched.acknowledgment {
+                        Some(reply) => println!("Reply:   {reply}"),
+                        None => println!("Reply:   (silent)"),
+                    }
+                }
+                None => println!("No rule matched; the message goes to the provider."),
+            }
+            Ok(())
+        }
     }
 }
 
@@ -4230,6 +4913,208 @@ fn classify_health_result(
     }
 }
 
+/// Config keys (`[channels_config.<key>]`) of channels that run as listeners,
+/// paired with the module that implements each one.
+const LISTENER_CHANNEL_KEYS: &[(&str, &str)] = &[
+    ("telegram", "telegram"),
+    ("discord", "discord"),
+    ("slack", "slack"),
+    ("mattermost", "mattermost"),
+    ("imessage", "imessage"),
+    ("matrix", "matrix"),
+    ("signal", "signal"),
+    ("whatsapp", "whatsapp"),
+    ("linq", "linq"),
+    ("wati", "wati"),
+    ("nextcloud_talk", "nextcloud_talk"),
+    ("email", "email_channel"),
+    ("irc", "irc"),
+    ("lark", "lark"),
+    ("feishu", "lark"),
+    ("dingtalk", "dingtalk"),
+    ("qq", "qq"),
+    ("nostr", "nostr"),
+    ("clawdtalk", "clawdtalk"),
+];
+
+fn configured_listener_keys(channels: &crate::config::ChannelsConfig) -> Vec<&'static str> {
+    let configured = [
+        channels.telegram.is_some(),
+        channels.discord.is_some(),
+        channels.slack.is_some(),
+        channels.mattermost.is_some(),
+        channels.imessage.is_some(),
+        channels.matrix.is_some(),
+        channels.signal.is_some(),
+        channels.whatsapp.is_some(),
+        channels.linq.is_some(),
+        channels.wati.is_some(),
+        channels.nextcloud_talk.is_some(),
+        channels.email.is_some(),
+        channels.irc.is_some(),
+        channels.lark.is_some(),
+        channels.feishu.is_some(),
+        channels.dingtalk.is_some(),
+        channels.qq.is_some(),
+        channels.nostr.is_some(),
+        channels.clawdtalk.is_some(),
+    ];
+    LISTENER_CHANNEL_KEYS
+        .iter()
+        .zip(configured)
+        .filter_map(|((key, _), is_configured)| is_configured.then_some(*key))
+        .collect()
+}
+
+/// Which configured channels to construct (`channel start --only/--except`).
+///
+/// An empty selection keeps every configured channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChannelSelection {
+    pub only: Vec<String>,
+    pub except: Vec<String>,
+}
+
+impl ChannelSelection {
+    fn normalize(name: &str) -> String {
+        name.trim().to_ascii_lowercase().replace('-', "_")
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.only.is_empty() && self.except.is_empty()
+    }
+
+    pub fn allows(&self, key: &str) -> bool {
+        let matches = |names: &[String]| names.iter().any(|name| Self::normalize(name) == key);
+        (self.only.is_empty() || matches(&self.only)) && !matches(&self.except)
+    }
+
+    /// Reject unknown names and `--only` names that are not configured.
+    pub fn validate(&self, channels: &crate::config::ChannelsConfig) -> Result<()> {
+        let configured = configured_listener_keys(channels);
+        for name in self.only.iter().chain(&self.except) {
+            let key = Self::normalize(name);
+            if !LISTENER_CHANNEL_KEYS.iter().any(|(known, _)| *known == key) {
+                anyhow::bail!(
+                    "Unknown channel '{name}'. Known channels: {}",
+                    LISTENER_CHANNEL_KEYS
+                        .iter()
+                        .map(|(known, _)| *known)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+        for name in &self.only {
+            let key = Self::normalize(name);
+            if !configured.contains(&key.as_str()) {
+                anyhow::bail!(
+                    "Channel '{name}' is not configured. Configured channels: {}",
+                    if configured.is_empty() {
+                        "(none)".to_string()
+                    } else {
+                        configured.join(", ")
+                    }
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// `tracing` directives that raise the selected channels' modules to debug
+    /// and keep unselected ones at info.
+    pub fn debug_log_directives(&self) -> Vec<String> {
+        let mut directives = vec!["zeroclaw::channels=debug".to_string()];
+        for (_, module) in LISTENER_CHANNEL_KEYS {
+            let selected = LISTENER_CHANNEL_KEYS
+                .iter()
+                .any(|(key, m)| m == module && self.allows(key));
+            let level = if selected { "debug" } else { "info" };
+            let directive = format!("zeroclaw::channels::{module}={level}");
+            if !directives.contains(&directive) {
+                directives.push(directive);
+            }
+        }
+        directives
+    }
+
+    fn apply(&self, channels: &mut crate::config::ChannelsConfig) {
+        if !self.allows("telegram") {
+            channels.telegram = None;
+        }
+        if !self.allows("discord") {
+            channels.discord = None;
+        }
+        if !self.allows("slack") {
+            channels.slack = None;
+        }
+        if !self.allows("mattermost") {
+            channels.mattermost = None;
+        }
+        if !self.allows("imessage") {
+            channels.imessage = None;
+        }
+        if !self.allows("matrix") {
+            channels.matrix = None;
+        }
+        if !self.allows("signal") {
+            channels.signal = None;
+        }
+        if !self.allows("whatsapp") {
+            channels.whatsapp = None;
+        }
+        if !self.allows("linq") {
+            channels.linq = None;
+        }
+        if !self.allows("wati") {
+            channels.wati = None;
+        }
+        if !self.allows("nextcloud_talk") {
+            channels.nextcloud_talk = None;
+        }
+        if !self.allows("email") {
+            channels.email = None;
+        }
+        if !self.allows("irc") {
+            channels.irc = None;
+        }
+        if !self.allows("lark") {
+            channels.lark = None;
+        }
+        if !self.allows("feishu") {
+            channels.feishu = None;
+        }
+        if !self.allows("dingtalk") {
+            channels.dingtalk = None;
+        }
+        if !self.allows("qq") {
+            channels.qq = None;
+        }
+        if !self.allows("nostr") {
+            channels.nostr = None;
+        }
+        if !self.allows("clawdtalk") {
+            channels.clawdtalk = None;
+        }
+    }
+}
+
+/// Options for [`start_channels_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ChannelStartOptions {
+    pub selection: ChannelSelection,
+    /// Print full (secret-scrubbed) message bodies instead of truncated previews.
+    pub debug: bool,
+    /// Console output format for message events.
+    pub output: console::OutputFormat,
+    /// Run the `[heartbeat]` loop alongside the channels. Off for the
+    /// daemon, which supervises its own heartbeat.
+    pub heartbeat: bool,
+    /// Take over channels another live process is serving instead of
+    /// refusing to start them (see [`listener_lock`]).
+    pub takeover: bool,
+}
+
 struct ConfiguredChannel {
     display_name: &'static str,
     channel: Arc<dyn Channel>,
@@ -4237,11 +5122,19 @@ struct ConfiguredChannel {
 
 fn collect_configured_channels(
     config: &Config,
-    matrix_skip_context: &str,
-) -> Vec<ConfiguredChannel> {
-    // Keep this symbol used even when Matrix support is compiled in and
-    // `#[cfg(not(feature = "channel-matrix"))]` blocks are removed.
-    let _ = matrix_skip_context;
+    skip_context: &str,
+    selection: &ChannelSelection,
+) -> Result<Vec<ConfiguredChannel>> {
+    selection.validate(&config.channels_config)?;
+    let selected_config;
+    let config = if selection.is_empty() {
+        config
+    } else {
+        let mut filtered = config.clone();
+        selection.apply(&mut filtered.channels_config);
+        selected_config = filtered;
+        &selected_config
+    };
     let mut channels = Vec::new();
 
     if let Some(ref tg) = config.channels_config.telegram {
@@ -4277,7 +5170,11 @@ fn collect_configured_channels(
                     dc.effective_group_reply_mode().requires_mention(),
                 )
                 .with_group_reply_allowed_senders(dc.group_reply_allowed_sender_ids())
-                .with_workspace_dir(config.workspace_dir.clone()),
+                .with_workspace_dir(config.workspace_dir.clone())
+                .with_slash_commands(slash_commands::SlashCommand::parse_list(
+                    &dc.slash_commands,
+                )?)
+                .with_reply_style(dc.effective_reply_style()),
             ),
         });
     }
@@ -4295,7 +5192,12 @@ fn collect_configured_channels(
                 .with_group_reply_policy(
                     sl.effective_group_reply_mode().requires_mention(),
                     sl.group_reply_allowed_sender_ids(),
-                ),
+                )
+                .with_streaming(sl.stream_mode, sl.draft_update_interval_ms)
+                .with_slash_commands(slash_commands::SlashCommand::parse_list(
+                    &sl.slash_commands,
+                )?)
+                .with_reply_style(sl.effective_reply_style()),
             ),
         });
     }
@@ -4318,10 +5220,15 @@ fn collect_configured_channels(
     }
 
     if let Some(ref im) = config.channels_config.imessage {
-        channels.push(ConfiguredChannel {
-            display_name: "iMessage",
-            channel: Arc::new(IMessageChannel::new(im.allowed_contacts.clone())),
-        });
+        match IMessageChannel::try_new(im.allowed_contacts.clone()) {
+            Ok(channel) => channels.push(ConfiguredChannel {
+                display_name: "iMessage",
+                channel: Arc::new(channel),
+            }),
+            Err(err) => tracing::warn!(
+                "iMessage channel is configured but {err}; skipping iMessage {skip_context}."
+            ),
+        }
     }
 
     #[cfg(feature = "channel-matrix")]
@@ -4338,7 +5245,9 @@ fn collect_configured_channels(
                     mx.device_id.clone(),
                     config.config_path.parent().map(|path| path.to_path_buf()),
                 )
-                .with_mention_only(mx.mention_only),
+                .with_mention_only(mx.mention_only)
+                .with_streaming(mx.stream_mode, mx.draft_update_interval_ms)
+                .with_reply_style(mx.effective_reply_style()),
             ),
         });
     }
@@ -4347,7 +5256,7 @@ fn collect_configured_channels(
     if config.channels_config.matrix.is_some() {
         tracing::warn!(
             "Matrix channel is configured but this build was compiled without `channel-matrix`; skipping Matrix {}.",
-            matrix_skip_context
+            skip_context
         );
     }
 
@@ -4550,14 +5459,18 @@ fn collect_configured_channels(
         });
     }
 
-    channels
+    Ok(channels)
 }
 
 async fn append_nostr_channel_if_available(
     config: &Config,
     channels: &mut Vec<ConfiguredChannel>,
     startup_context: &str,
+    selection: &ChannelSelection,
 ) -> Option<String> {
+    if !selection.allows("nostr") {
+        return None;
+    }
     let ns = config.channels_config.nostr.as_ref()?;
     match NostrChannel::new(&ns.private_key, ns.relays.clone(), &ns.allowed_pubkeys).await {
         Ok(channel) => {
@@ -4577,11 +5490,12 @@ async fn append_nostr_channel_if_available(
 
 /// Run health checks for configured channels.
 pub async fn doctor_channels(config: Config) -> Result<()> {
-    let mut channels = collect_configured_channels(&config, "health check");
+    let selection = ChannelSelection::default();
+    let mut channels = collect_configured_channels(&config, "health check", &selection)?;
     let mut init_failures = Vec::new();
 
     if let Some(reason) =
-        append_nostr_channel_if_available(&config, &mut channels, "health check").await
+        append_nostr_channel_if_available(&config, &mut channels, "health check", &selection).await
     {
         init_failures.push(reason);
     }
@@ -4642,8 +5556,22 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 }
 
 /// Start all configured channels and route messages to the agent
-#[allow(clippy::too_many_lines)]
 pub async fn start_channels(config: Config) -> Result<()> {
+    start_channels_with_options(config, ChannelStartOptions::default()).await
+}
+
+/// Start the selected configured channels and route messages to the agent.
+#[allow(clippy::too_many_lines)]
+pub async fn start_channels_with_options(
+    config: Config,
+    options: ChannelStartOptions,
+) -> Result<()> {
+    // Fail fast on bad `--only`/`--except` names before any provider or listener starts.
+    options.selection.validate(&config.channels_config)?;
+    CHANNEL_DEBUG_BODIES.store(options.debug, Ordering::Relaxed);
+    console::set_format(options.output);
+    console::set_echo(config.observability.console_echo);
+
     // Ensure stale channel handles are never reused across restarts.
     clear_live_channels();
 
@@ -4659,6 +5587,8 @@ pub async fn start_channels(config: Config) -> Result<()> {
         custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
         max_tokens_override: None,
         model_support_vision: config.model_support_vision,
+        prompt_caching: config.provider.prompt_caching,
+        ollama: config.provider.ollama.clone(),
     };
     let provider: Arc<dyn Provider> = Arc::from(
         create_resilient_provider_nonblocking(
@@ -4687,6 +5617,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
             RuntimeConfigState {
                 defaults: runtime_defaults_from_config(&config),
                 perplexity_filter: config.security.perplexity_filter.clone(),
+                inbound_filter: Arc::new(filters::InboundFilter::compile(
+                    &config.channels_config.filters,
+                )?),
+                error_replies: Arc::new(error_reply::ErrorReplies::from_config(
+                    &config.channels_config,
+                )?),
+                reset_command: config.channels_config.reset_command.clone(),
                 last_applied_stamp: initial_stamp,
             },
         );
@@ -4771,78 +5708,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
     let tools_registry = Arc::new(built_tools);
 
-    let skills = crate::skills::load_skills_with_config(&workspace, &config);
-
-    // Collect tool descriptions for the prompt
-    let mut tool_descs: Vec<(&str, &str)> = vec![
-        (
-            "shell",
-            "Execute terminal commands. Use when: running local checks, build/test commands, diagnostics. Don't use when: a safer dedicated tool exists, or command is destructive without approval.",
-        ),
-        (
-            "file_read",
-            "Read file contents. Use when: inspecting project files, configs, logs. Don't use when: a targeted search is enough.",
-        ),
-        (
-            "file_write",
-            "Write file contents. Use when: applying focused edits, scaffolding files, updating docs/code. Don't use when: side effects are unclear or file ownership is uncertain.",
-        ),
-        (
-            "memory_store",
-            "Save to memory. Use when: preserving durable preferences, decisions, key context. Don't use when: information is transient/noisy/sensitive without need.",
-        ),
-        (
-            "memory_recall",
-            "Search memory. Use when: retrieving prior decisions, user preferences, historical context. Don't use when: answer is already in current context.",
-        ),
-        (
-            "memory_forget",
-            "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
-        ),
-    ];
+    let skills = crate::skills::load_prompt_skills(&workspace, &config);
 
-    if config.browser.enabled {
-        tool_descs.push((
-            "browser_open",
-            "Open approved HTTPS URLs in system browser (allowlist-only, no scraping)",
-        ));
-        tool_descs.push((
-            "browser",
-            "Automate browser actions (open/click/type/scroll/screenshot) with backend-aware safety checks.",
-        ));
-    }
-    if config.composio.enabled {
-        tool_descs.push((
-            "composio",
-            "Execute actions on 1000+ apps via Composio (Gmail, Notion, GitHub, Slack, etc.). Use action='list' to discover actions, 'list_accounts' to retrieve connected account IDs, 'execute' to run (optionally with connected_account_id), and 'connect' for OAuth.",
-        ));
-    }
-    tool_descs.push((
-        "schedule",
-        "Manage scheduled tasks (create/list/get/cancel/pause/resume). Supports recurring cron and one-shot delays.",
-    ));
-    tool_descs.push((
-        "pushover",
-        "Send a Pushover notification to your device. Requires PUSHOVER_TOKEN and PUSHOVER_USER_KEY in .env file.",
-    ));
-    if !config.agents.is_empty() {
-        tool_descs.push((
-            "delegate",
-            "Delegate a subtask to a specialized agent. Use when: a task benefits from a different model (e.g. fast summarization, deep reasoning, code generation). The sub-agent runs a single prompt and returns its response.",
-        ));
-        tool_descs.push((
-            "subagent_spawn",
-            "Spawn a delegate agent in the background. Returns immediately with a session_id. Use for long-running tasks that should not block.",
-        ));
-        tool_descs.push((
-            "subagent_list",
-            "List running and completed background sub-agents. Filter by status: running, completed, failed, killed, or all.",
-        ));
-        tool_descs.push((
-            "subagent_manage",
-            "Manage a background sub-agent: 'status' to check progress/output, 'kill' to cancel a running session.",
-        ));
-    }
+    // Collect tool descriptions for the prompt from the registered tools.
+    let mut tool_descs = tools::available_tools(tools_registry.as_ref());
 
     // Filter out tools excluded for non-CLI channels so the system prompt
     // does not advertise them for channel-driven runs.
@@ -4851,45 +5720,36 @@ pub async fn start_channels(config: Config) -> Result<()> {
         tool_descs.retain(|(name, _)| !excluded.iter().any(|ex| ex == name));
     }
 
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let prompt_profile = config.agent.prompt_profile();
     let native_tools = provider.supports_native_tools();
-    let mut system_prompt = build_system_prompt_with_mode(
+    let mut system_prompt = build_system_prompt_with_sections(
         &workspace,
         &model,
         &tool_descs,
         &skills,
         Some(&config.identity),
-        bootstrap_max_chars,
+        prompt_profile.bootstrap_max_chars,
         native_tools,
         config.skills.prompt_injection_mode,
+        &prompt_profile.sections,
     );
-    if !native_tools {
+    if !native_tools && prompt_profile.sections.tools {
         let filtered_specs = filtered_tool_specs_for_runtime(tools_registry.as_ref(), excluded);
         system_prompt.push_str(&build_tool_instructions_from_specs(&filtered_specs));
     }
     system_prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
 
-    if !skills.is_empty() {
-        println!(
-            "  🧩 Skills:   {}",
-            skills
-                .iter()
-                .map(|s| s.name.as_str())
-                .collect::<Vec<_>>()
-                .join(", ")
-        );
-    }
-
     // Collect active channels from a shared builder to keep startup and doctor parity.
-    let mut configured_channels = collect_configured_channels(&config, "runtime startup");
+    let mut configured_channels =
+        collect_configured_channels(&config, "runtime startup", &options.selection)?;
     let mut init_failures = Vec::new();
-    if let Some(reason) =
-        append_nostr_channel_if_available(&config, &mut configured_channels, "runtime startup")
-            .await
+    if let Some(reason) = append_nostr_channel_if_available(
+        &config,
+        &mut configured_channels,
+        "runtime startup",
+        &options.selection,
+    )
+    .await
     {
         init_failures.push(reason);
     }
@@ -4908,38 +5768,109 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
     if !init_failures.is_empty() {
         for failure in &init_failures {
-            println!("  ⚠️  {failure}");
+            eprintln!("  ⚠️  {failure}");
         }
-        println!();
+        eprintln!();
     }
 
-    let channels: Vec<Arc<dyn Channel>> = configured_channels
-        .into_iter()
-        .map(|configured| configured.channel)
+    // Refuse bots another live zeroclaw process is already serving.
+    let listener_registry = Arc::new(listener_lock::ListenerLock::new(&config.workspace_dir));
+    let own_pid = std::process::id();
+    let mut held_listeners: Vec<listener_lock::ListenerIdentity> =
+        listener_lock::identities(&config.channels_config)
+            .into_iter()
+            .filter(|identity| {
+                configured_channels
+                    .iter()
+                    .any(|configured| configured.display_name == identity.channel)
+            })
+            .collect();
+    let conflicts = listener_registry.claim(
+        &held_listeners,
+        own_pid,
+        chrono::Utc::now().timestamp(),
+        options.takeover,
+    )?;
+    if !conflicts.is_empty() {
+        for conflict in &conflicts {
+            eprintln!(
+                "  ❌ {} is already served by zeroclaw pid {} (heartbeat {}s ago); not starting it here.",
+                conflict.channel, conflict.pid, conflict.heartbeat_age_secs
+            );
+        }
+        eprintln!(
+            "     Stop the other process, or re-run with `zeroclaw channel start --takeover` to move it here."
+        );
+        eprintln!();
+        held_listeners.retain(|identity| {
+            !conflicts
+                .iter()
+                .any(|conflict| conflict.channel == identity.channel)
+        });
+        configured_channels.retain(|configured| {
+            !conflicts
+                .iter()
+                .any(|conflict| conflict.channel == configured.display_name)
+        });
+        if configured_channels.is_empty() {
+            anyhow::bail!(
+                "Every selected channel is already served by another zeroclaw process; \
+                 use --takeover to move them to this one."
+            );
+        }
+    }
+
+    let channel_display_names: Vec<&'static str> = configured_channels
+        .iter()
+        .map(|configured| configured.display_name)
+        .collect();
+    let channels: Vec<Arc<dyn Channel>> = configured_channels
+        .into_iter()
+        .map(|configured| configured.channel)
         .collect();
 
-    println!("🦀 ZeroClaw Channel Server");
-    println!("  🤖 Model:    {model}");
+    // JSONL output keeps stdout for events, so the banner goes to tracing.
+    let banner_variant = if console::jsonl() {
+        StartupBanner::Off
+    } else {
+        banner::effective(config.observability.startup_banner)
+    };
     let effective_backend = memory::effective_memory_backend_name(
         &config.memory.backend,
         Some(&config.storage.provider.config),
     );
-    println!(
-        "  🧠 Memory:   {} (auto-save: {})",
-        effective_backend,
-        if config.memory.auto_save { "on" } else { "off" }
-    );
-    println!(
-        "  📡 Channels: {}",
-        channels
-            .iter()
-            .map(|c| c.name())
-            .collect::<Vec<_>>()
-            .join(", ")
+    let mut banner_details = Vec::new();
+    if !skills.is_empty() {
+        banner_details.push(format!(
+            "  🧩 Skills:    {}",
+            skills
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    banner_details.extend([
+        String::new(),
+        "  Listening for messages... (Ctrl+C to stop)".into(),
+        String::new(),
+    ]);
+    banner::print(
+        banner_variant,
+        &banner::BannerInfo {
+            title: "ZeroClaw Channel Server",
+            model: Some(&model),
+            memory: Some(format!(
+                "{effective_backend} (auto-save: {})",
+                if config.memory.auto_save { "on" } else { "off" }
+            )),
+            workspace: Some(&config.workspace_dir),
+            config_path: Some(&config.config_path),
+            channels: channels.iter().map(|c| c.name()).collect(),
+            details: banner_details,
+            ..banner::BannerInfo::default()
+        },
     );
-    println!();
-    println!("  Listening for messages... (Ctrl+C to stop)");
-    println!();
 
     crate::health::mark_component_ok("channels");
 
@@ -4965,6 +5896,30 @@ pub async fn start_channels(config: Config) -> Result<()> {
             max_backoff_secs,
         ));
     }
+    let listener_heartbeat = spawn_listener_heartbeat(
+        Arc::clone(&listener_registry),
+        held_listeners.clone(),
+        channel_display_names
+            .iter()
+            .copied()
+            .zip(handles.iter().map(tokio::task::JoinHandle::abort_handle))
+            .collect(),
+    );
+    let maintenance = Arc::new(crate::maintenance::MaintenanceMode::from_config(&config));
+    spawn_maintenance_drain(Arc::clone(&maintenance), tx.downgrade());
+    let heartbeat = if options.heartbeat && config.heartbeat.enabled {
+        let _ =
+            crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
+                .await;
+        let runner = crate::heartbeat::runner::HeartbeatRunner::from_config(&config).await?;
+        Some(tokio::spawn(async move {
+            if let Err(e) = Arc::new(runner).run_forever().await {
+                tracing::error!("Heartbeat stopped: {e:#}");
+            }
+        }))
+    } else {
+        None
+    };
     drop(tx); // Drop our copy so rx closes when all channels stop
 
     let channels_by_name = Arc::new(
@@ -4974,9 +5929,12 @@ pub async fn start_channels(config: Config) -> Result<()> {
             .collect::<HashMap<_, _>>(),
     );
     register_live_channels(channels_by_name.as_ref());
+    let failover_notifier = spawn_failover_notifier(&config.reliability, channels_by_name.as_ref());
     let max_in_flight_messages = compute_max_in_flight_messages(channels.len());
 
-    println!("  🚦 In-flight message limit: {max_in_flight_messages}");
+    if banner_variant == StartupBanner::Full {
+        println!("  🚦 In-flight message limit: {max_in_flight_messages}");
+    }
 
     let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
     provider_cache_seed.insert(provider_name.clone(), Arc::clone(&provider));
@@ -4999,6 +5957,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
         model: Arc::new(model.clone()),
         temperature,
         auto_save_memory: config.memory.auto_save,
+        memory_classification: config.memory.classification.clone(),
         max_tool_iterations: config.agent.max_tool_iterations,
         min_relevance_score: config.memory.min_relevance_score,
         conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -5010,14 +5969,13 @@ pub async fn start_channels(config: Config) -> Result<()> {
         provider_runtime_options,
         workspace_dir: Arc::new(config.workspace_dir.clone()),
         message_timeout_secs,
+        tool_progress_interval_secs: config.channels_config.tool_progress_interval_secs,
         interrupt_on_new_message,
         multimodal: config.multimodal.clone(),
         hooks: if config.hooks.enabled {
-            let mut runner = crate::hooks::HookRunner::new();
-            if config.hooks.builtin.command_logger {
-                runner.register(Box::new(crate::hooks::builtin::CommandLoggerHook::new()));
-            }
-            Some(Arc::new(runner))
+            Some(Arc::new(crate::hooks::HookRunner::from_config(
+                &config.hooks,
+            )?))
         } else {
             None
         },
@@ -5026,6 +5984,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         )),
         query_classification: config.query_classification.clone(),
         model_routes: config.model_routes.clone(),
+        maintenance: Arc::clone(&maintenance),
+        delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::from_config(
+            &config,
+        )),
         // WASM skill tools are sandboxed by the WASM engine and cannot access the
         // host filesystem, network, or shell. Pre-approve them so they are not
         // denied on non-CLI channels (which have no interactive stdin to prompt).
@@ -5042,11 +6004,21 @@ pub async fn start_channels(config: Config) -> Result<()> {
     });
 
     run_message_dispatch_loop(rx, runtime_ctx, max_in_flight_messages).await;
+    if let Some(heartbeat) = heartbeat {
+        heartbeat.abort();
+    }
+    if let Some(notifier) = failover_notifier {
+        notifier.abort();
+    }
 
     // Wait for all channel tasks
     for h in handles {
         let _ = h.await;
     }
+    listener_heartbeat.abort();
+    if let Err(e) = listener_registry.release(&held_listeners, own_pid) {
+        tracing::warn!("Failed to release channel listener claims: {e:#}");
+    }
 
     clear_live_channels();
 
@@ -5347,6 +6319,7 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
@@ -5361,9 +6334,12 @@ mod tests {
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -5401,6 +6377,7 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -5415,9 +6392,12 @@ mod tests {
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -5458,6 +6438,7 @@ mod tests {
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
@@ -5472,9 +6453,12 @@ mod tests {
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6056,6 +7040,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6067,12 +7052,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6133,6 +7121,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6144,10 +7133,13 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6178,6 +7170,94 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(!sent_messages[0].contains("mock_price"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_replies_and_queues_during_maintenance() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let maintenance = Arc::new(crate::maintenance::MaintenanceMode::new(
+            temp.path().join(crate::maintenance::STATE_FILE),
+            false,
+            "Down for maintenance. {message}",
+            true,
+        ));
+        maintenance
+            .set(true, Some("back at 5pm".into()))
+            .expect("enable maintenance");
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
+            max_tool_iterations: 10,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
+            interrupt_on_new_message: false,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            maintenance: Arc::clone(&maintenance),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+        });
+
+        let inbound = traits::ChannelMessage {
+            id: "msg-1".to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-42".to_string(),
+            content: "Are you there?".to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: None,
+        };
+        process_channel_message(runtime_ctx.clone(), inbound, CancellationToken::new()).await;
+
+        {
+            let sent = channel_impl.sent_messages.lock().await;
+            assert_eq!(sent.len(), 1);
+            assert!(sent[0].starts_with("chat-42:Down for maintenance. back at 5pm"));
+            assert!(sent[0].contains("queued"));
+        }
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert!(maintenance.take_queued().is_empty(), "held while active");
+
+        maintenance.set(false, None).expect("disable maintenance");
+        for queued in maintenance.take_queued() {
+            process_channel_message(runtime_ctx.clone(), queued, CancellationToken::new()).await;
+        }
+
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 1);
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 2);
+        assert!(sent[1].starts_with("chat-42:"));
+        assert!(!sent[1].contains("maintenance"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_telegram_does_not_persist_tool_summary_prefix() {
         let channel_impl = Arc::new(TelegramRecordingChannel::default());
@@ -6197,6 +7277,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6208,10 +7289,13 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6275,6 +7359,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6286,8 +7371,11 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6352,6 +7440,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6363,8 +7452,11 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6421,6 +7513,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6432,12 +7525,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6485,6 +7581,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6496,12 +7593,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -6531,25 +7631,269 @@ BTC is currently around $65,000 based on latest tool output."#
     }
 
     #[tokio::test]
-    async fn process_channel_message_handles_models_command_without_llm_call() {
-        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+    async fn process_channel_message_handles_models_command_without_llm_call() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let default_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let default_provider: Arc<dyn Provider> = default_provider_impl.clone();
+        let fallback_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let fallback_provider: Arc<dyn Provider> = fallback_provider_impl.clone();
+
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
+        provider_cache_seed.insert("openrouter".to_string(), fallback_provider);
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&default_provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+        });
+
+        process_channel_message(
+            runtime_ctx.clone(),
+            traits::ChannelMessage {
+                id: "msg-cmd-1".to_string(),
+                sender: "alice".to_string(),
+                reply_target: "chat-1".to_string(),
+                content: "/models openrouter".to_string(),
+                channel: "telegram".to_string(),
+                timestamp: 1,
+                thread_ts: None,
+            },
+            CancellationToken::new(),
+        )
+        .await;
+
+        let sent = channel_impl.sent_messages.lock().await;
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].contains("Provider switched to `openrouter`"));
+
+        let route_key = "telegram_alice";
+        let route = runtime_ctx
+            .route_overrides
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(route_key)
+            .cloned()
+            .expect("route should be stored for sender");
+        assert_eq!(route.provider, "openrouter");
+        assert_eq!(route.model, "default-model");
+
+        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_applies_inbound_filters_after_runtime_commands() {
+        let channel_impl = Arc::new(TelegramRecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let default_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let default_provider: Arc<dyn Provider> = default_provider_impl.clone();
+        let fallback_provider_impl = Arc::new(ModelCaptureProvider::default());
+        let fallback_provider: Arc<dyn Provider> = fallback_provider_impl.clone();
+
+        let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
+        provider_cache_seed.insert("openrouter".to_string(), fallback_provider);
+
+        // Filters live in the runtime config store, keyed by config path.
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let filter_config = crate::config::ChannelFiltersConfig {
+            skip_bare_urls: true,
+            skip_shorter_than: 0,
+            skip_patterns: vec!["^/".to_string()],
+            acknowledgments: HashMap::from([(
+                filters::BARE_URL_RULE.to_string(),
+                "Link noted.".to_string(),
+            )]),
+        };
+        runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                temp.path().join("config.toml"),
+                RuntimeConfigState {
+                    defaults: ChannelRuntimeDefaults {
+                        default_provider: "test-provider".to_string(),
+                        model: "default-model".to_string(),
+                        temperature: 0.0,
+                        api_key: None,
+                        api_url: None,
+                        reliability: crate::config::ReliabilityConfig::default(),
+                    },
+                    perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+                    inbound_filter: Arc::new(
+                        filters::InboundFilter::compile(&filter_config).unwrap(),
+                    ),
+                    error_replies: Arc::new(error_reply::ErrorReplies::default()),
+                    reset_command: "/reset".to_string(),
+                    last_applied_stamp: None,
+                },
+            );
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: Arc::clone(&default_provider),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("default-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(provider_cache_seed)),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions {
+                zeroclaw_dir: Some(temp.path().to_path_buf()),
+                ..providers::ProviderRuntimeOptions::default()
+            },
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+        });
+
+        let send = |id: &str, content: &str| {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: content.to_string(),
+                    channel: "telegram".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                },
+                CancellationToken::new(),
+            )
+        };
+
+        // Silent rule: nothing sent, no provider call.
+        send("msg-1", "/start").await;
+        assert!(channel_impl.sent_messages.lock().await.is_empty());
+
+        // Acknowledging rule: canned reply only.
+        send("msg-2", "https://example.com/article").await;
+        assert_eq!(
+            channel_impl.sent_messages.lock().await.as_slice(),
+            ["chat-1:Link noted."]
+        );
+
+        // A runtime command still runs even though `^/` would match it.
+        send("msg-3", "/models openrouter").await;
+        {
+            let sent = channel_impl.sent_messages.lock().await;
+            assert_eq!(sent.len(), 2);
+            assert!(sent[1].contains("Provider switched to `openrouter`"));
+        }
+
+        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn process_channel_message_reset_and_model_commands_skip_the_provider() {
+        let channel_impl = Arc::new(RecordingChannel::default());
         let channel: Arc<dyn Channel> = channel_impl.clone();
-
         let mut channels_by_name = HashMap::new();
         channels_by_name.insert(channel.name().to_string(), channel);
 
-        let default_provider_impl = Arc::new(ModelCaptureProvider::default());
-        let default_provider: Arc<dyn Provider> = default_provider_impl.clone();
-        let fallback_provider_impl = Arc::new(ModelCaptureProvider::default());
-        let fallback_provider: Arc<dyn Provider> = fallback_provider_impl.clone();
-
+        let provider_impl = Arc::new(ModelCaptureProvider::default());
+        let provider: Arc<dyn Provider> = provider_impl.clone();
         let mut provider_cache_seed: HashMap<String, Arc<dyn Provider>> = HashMap::new();
-        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&default_provider));
-        provider_cache_seed.insert("openrouter".to_string(), fallback_provider);
+        provider_cache_seed.insert("test-provider".to_string(), Arc::clone(&provider));
+
+        let temp = tempfile::TempDir::new().expect("temp dir");
+        let workspace_dir = temp.path().join("workspace");
+        std::fs::create_dir_all(workspace_dir.join("state")).expect("state dir");
+        std::fs::write(
+            workspace_dir.join("state").join(MODEL_CACHE_FILE),
+            r#"{"entries":[{"provider":"test-provider","models":["default-model","fast-model"]}]}"#,
+        )
+        .expect("model cache");
+        runtime_config_store()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(
+                temp.path().join("config.toml"),
+                RuntimeConfigState {
+                    defaults: ChannelRuntimeDefaults {
+                        default_provider: "test-provider".to_string(),
+                        model: "default-model".to_string(),
+                        temperature: 0.0,
+                        api_key: None,
+                        api_url: None,
+                        reliability: crate::config::ReliabilityConfig::default(),
+                    },
+                    perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+                    inbound_filter: Arc::new(filters::InboundFilter::default()),
+                    error_replies: Arc::new(error_reply::ErrorReplies::default()),
+                    reset_command: "!forget".to_string(),
+                    last_applied_stamp: None,
+                },
+            );
 
         let runtime_ctx = Arc::new(ChannelRuntimeContext {
             channels_by_name: Arc::new(channels_by_name),
-            provider: Arc::clone(&default_provider),
+            provider: Arc::clone(&provider),
             default_provider: Arc::new("test-provider".to_string()),
             memory: Arc::new(NoopMemory),
             tools_registry: Arc::new(vec![]),
@@ -6558,6 +7902,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6566,52 +7911,97 @@ BTC is currently around $65,000 based on latest tool output."#
             api_key: None,
             api_url: None,
             reliability: Arc::new(crate::config::ReliabilityConfig::default()),
-            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
-            workspace_dir: Arc::new(std::env::temp_dir()),
+            provider_runtime_options: providers::ProviderRuntimeOptions {
+                zeroclaw_dir: Some(temp.path().to_path_buf()),
+                ..providers::ProviderRuntimeOptions::default()
+            },
+            workspace_dir: Arc::new(workspace_dir),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
         });
 
-        process_channel_message(
-            runtime_ctx.clone(),
-            traits::ChannelMessage {
-                id: "msg-cmd-1".to_string(),
-                sender: "alice".to_string(),
-                reply_target: "chat-1".to_string(),
-                content: "/models openrouter".to_string(),
-                channel: "telegram".to_string(),
-                timestamp: 1,
-                thread_ts: None,
-            },
-            CancellationToken::new(),
-        )
-        .await;
+        let send = |id: &str, content: &str| {
+            process_channel_message(
+                runtime_ctx.clone(),
+                traits::ChannelMessage {
+                    id: id.to_string(),
+                    sender: "alice".to_string(),
+                    reply_target: "chat-1".to_string(),
+                    content: content.to_string(),
+                    channel: "test-channel".to_string(),
+                    timestamp: 1,
+                    thread_ts: None,
+                },
+                CancellationToken::new(),
+            )
+        };
+        let history_len = || {
+            runtime_ctx
+                .conversation_histories
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .get("test-channel_alice")
+                .map_or(0, Vec::len)
+        };
 
-        let sent = channel_impl.sent_messages.lock().await;
-        assert_eq!(sent.len(), 1);
-        assert!(sent[0].contains("Provider switched to `openrouter`"));
+        send("msg-1", "hello").await;
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 1);
+        assert!(history_len() > 0);
 
-        let route_key = "telegram_alice";
-        let route = runtime_ctx
-            .route_overrides
-            .lock()
-            .unwrap_or_else(|e| e.into_inner())
-            .get(route_key)
-            .cloned()
-            .expect("route should be stored for sender");
-        assert_eq!(route.provider, "openrouter");
-        assert_eq!(route.model, "default-model");
+        // The configured reset command clears history without a provider call.
+        send("msg-2", "!forget").await;
+        assert_eq!(history_len(), 0);
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 1);
+        assert!(channel_impl.sent_messages.lock().await[1]
+            .ends_with("Conversation history cleared. Starting fresh."));
 
-        assert_eq!(default_provider_impl.call_count.load(Ordering::SeqCst), 0);
-        assert_eq!(fallback_provider_impl.call_count.load(Ordering::SeqCst), 0);
+        // `/model` works on any channel, but only for models the provider has.
+        send("msg-3", "/model no-such-model").await;
+        send("msg-4", "/model fast-model").await;
+        {
+            let sent = channel_impl.sent_messages.lock().await;
+            assert!(sent[2].contains("Unknown model `no-such-model`"));
+            assert!(sent[3].contains("Model switched to `fast-model`"));
+        }
+        assert_eq!(provider_impl.call_count.load(Ordering::SeqCst), 1);
+
+        send("msg-5", "hello again").await;
+        assert_eq!(
+            provider_impl
+                .models
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .as_slice(),
+            ["default-model", "fast-model"]
+        );
+    }
+
+    #[test]
+    fn reset_command_matches_configured_word_only() {
+        assert!(is_reset_command("/reset", "/reset"));
+        assert!(is_reset_command("/reset", "  /RESET@zeroclaw_bot please"));
+        assert!(is_reset_command("!forget", "!forget"));
+        assert!(!is_reset_command("!forget", "please !forget"));
+        assert!(!is_reset_command("/reset", "/resetting"));
+        assert_eq!(
+            parse_runtime_command_with_reset("slack", "!forget", "!forget"),
+            Some(ChannelRuntimeCommand::NewSession)
+        );
+        assert_eq!(
+            parse_runtime_command("slack", "/model gpt-4o-mini"),
+            Some(ChannelRuntimeCommand::SetModel("gpt-4o-mini".to_string()))
+        );
     }
 
     #[tokio::test]
@@ -6659,6 +8049,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6673,12 +8064,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
         assert_eq!(
@@ -6809,6 +8203,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6823,12 +8218,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
         assert_eq!(
@@ -6919,6 +8317,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -6933,12 +8332,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager,
         });
 
@@ -7024,6 +8426,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7038,12 +8441,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["shell".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager,
         });
 
@@ -7120,6 +8526,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7134,12 +8541,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(vec!["mock_price".to_string()])),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7268,6 +8678,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7282,12 +8693,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7413,6 +8827,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7427,12 +8842,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7528,6 +8946,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7542,12 +8961,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7623,6 +9045,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7637,12 +9060,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7740,6 +9166,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7751,12 +9178,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(&autonomy_cfg)),
         });
 
@@ -7855,6 +9285,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7866,12 +9297,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -7931,6 +9365,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("default-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -7942,12 +9377,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8004,6 +9442,9 @@ BTC is currently around $65,000 based on latest tool output."#
                         reliability: crate::config::ReliabilityConfig::default(),
                     },
                     perplexity_filter: crate::config::PerplexityFilterConfig::default(),
+                    inbound_filter: Arc::new(filters::InboundFilter::default()),
+                    error_replies: Arc::new(error_reply::ErrorReplies::default()),
+                    reset_command: "/reset".to_string(),
                     last_applied_stamp: None,
                 },
             );
@@ -8020,6 +9461,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("startup-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8034,12 +9476,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8165,6 +9610,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("llama3.2".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8179,12 +9625,15 @@ BTC is currently around $65,000 based on latest tool output."#
             },
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8271,6 +9720,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 12,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8282,12 +9732,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8336,6 +9789,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 3,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8347,12 +9801,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8513,6 +9970,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8524,12 +9982,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8598,6 +10059,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8609,12 +10071,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8695,6 +10160,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8706,12 +10172,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: true,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8774,6 +10243,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8785,12 +10255,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8838,6 +10311,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 10,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -8849,12 +10323,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -8914,6 +10391,92 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(prompt.contains("## Runtime"), "missing Runtime section");
     }
 
+    fn prompt_with_sections(
+        workspace_dir: &std::path::Path,
+        sections: &crate::config::PromptSectionsConfig,
+    ) -> String {
+        let tools = vec![("shell", "Run commands")];
+        build_system_prompt_with_sections(
+            workspace_dir,
+            "test-model",
+            &tools,
+            &[],
+            None,
+            None,
+            false,
+            crate::config::SkillsPromptInjectionMode::Full,
+            sections,
+        )
+    }
+
+    #[test]
+    fn prompt_section_toggles_remove_each_section() {
+        use crate::config::PromptSectionsConfig;
+
+        let ws = make_workspace();
+        let toggles: [(&str, fn(&mut PromptSectionsConfig)); 5] = [
+            ("## Tools", |s| s.tools = false),
+            ("## Workspace", |s| s.workspace = false),
+            ("## Project Context", |s| s.bootstrap = false),
+            ("## Current Date & Time", |s| s.datetime = false),
+            ("## Runtime", |s| s.runtime = false),
+        ];
+        for (heading, disable) in toggles {
+            let mut sections = PromptSectionsConfig::default();
+            assert!(prompt_with_sections(ws.path(), &sections).contains(heading));
+            disable(&mut sections);
+            let prompt = prompt_with_sections(ws.path(), &sections);
+            assert!(!prompt.contains(heading), "{heading} should be toggled off");
+            assert!(prompt.contains("## Safety"));
+        }
+
+        let no_bootstrap = prompt_with_sections(
+            ws.path(),
+            &PromptSectionsConfig {
+                bootstrap: false,
+                ..PromptSectionsConfig::default()
+            },
+        );
+        assert!(!no_bootstrap.contains("### SOUL.md"));
+        assert!(!no_bootstrap.contains("[File not found"));
+    }
+
+    #[test]
+    fn prompt_safety_section_requires_acknowledgment_to_disable() {
+        use crate::config::PromptSectionsConfig;
+
+        let ws = make_workspace();
+        let unacknowledged = PromptSectionsConfig {
+            safety: false,
+            ..PromptSectionsConfig::default()
+        };
+        assert!(prompt_with_sections(ws.path(), &unacknowledged).contains("## Safety"));
+
+        let acknowledged = PromptSectionsConfig {
+            i_understand_no_safety: true,
+            ..unacknowledged
+        };
+        assert!(!prompt_with_sections(ws.path(), &acknowledged).contains("## Safety"));
+    }
+
+    #[test]
+    fn prompt_bootstrap_file_list_skips_markers_for_excluded_files() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join("MEMORY.md"), "# Memory\nUser likes Rust.").unwrap();
+        let sections = crate::config::PromptSectionsConfig {
+            bootstrap_files: Some(vec!["IDENTITY.md".into()]),
+            ..crate::config::PromptSectionsConfig::default()
+        };
+        let prompt = prompt_with_sections(tmp.path(), &sections);
+
+        // Toggled on but missing: marker stays.
+        assert!(prompt.contains("[File not found: IDENTITY.md]"));
+        // Toggled off: neither content nor marker.
+        assert!(!prompt.contains("SOUL.md"));
+        assert!(!prompt.contains("AGENTS.md"));
+        assert!(!prompt.contains("### MEMORY.md"));
+    }
+
     #[test]
     fn prompt_injects_tools() {
         let ws = make_workspace();
@@ -9065,9 +10628,18 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
 
         let prompt = build_system_prompt(ws.path(), "model", &[], &skills, None, None);
@@ -9100,9 +10672,18 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Always run cargo test before final response.".into()],
             location: None,
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
 
         let prompt = build_system_prompt_with_mode(
@@ -9141,9 +10722,18 @@ BTC is currently around $65,000 based on latest tool output."#
                 kind: "shell&exec".into(),
                 command: "cargo clippy".into(),
                 args: HashMap::new(),
+                timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
             }],
             prompts: vec!["Use <tool_call> and & keep output \"safe\"".into()],
             location: None,
+            id: String::new(),
+            source: crate::skills::SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
         }];
 
         let prompt = build_system_prompt(ws.path(), "model", &[], &skills, None, None);
@@ -9333,7 +10923,7 @@ BTC is currently around $65,000 based on latest tool output."#
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age", 0.0).await;
+        let context = build_memory_context(&mem, "age", 0.0, None).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
@@ -9359,6 +10949,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -9370,12 +10961,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -9429,6 +11023,104 @@ BTC is currently around $65,000 based on latest tool output."#
         assert!(calls[1][3].1.contains("follow up"));
     }
 
+    #[tokio::test]
+    async fn process_channel_message_keeps_one_history_per_thread() {
+        let channel_impl = Arc::new(RecordingChannel::default());
+        let channel: Arc<dyn Channel> = channel_impl.clone();
+
+        let mut channels_by_name = HashMap::new();
+        channels_by_name.insert(channel.name().to_string(), channel);
+
+        let provider_impl = Arc::new(HistoryCaptureProvider::default());
+
+        let runtime_ctx = Arc::new(ChannelRuntimeContext {
+            channels_by_name: Arc::new(channels_by_name),
+            provider: provider_impl.clone(),
+            default_provider: Arc::new("test-provider".to_string()),
+            memory: Arc::new(NoopMemory),
+            tools_registry: Arc::new(vec![]),
+            observer: Arc::new(NoopObserver),
+            system_prompt: Arc::new("test-system-prompt".to_string()),
+            model: Arc::new("test-model".to_string()),
+            temperature: 0.0,
+            auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
+            max_tool_iterations: 5,
+            min_relevance_score: 0.0,
+            conversation_histories: Arc::new(Mutex::new(HashMap::new())),
+            provider_cache: Arc::new(Mutex::new(HashMap::new())),
+            route_overrides: Arc::new(Mutex::new(HashMap::new())),
+            api_key: None,
+            api_url: None,
+            reliability: Arc::new(crate::config::ReliabilityConfig::default()),
+            provider_runtime_options: providers::ProviderRuntimeOptions::default(),
+            workspace_dir: Arc::new(std::env::temp_dir()),
+            message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
+            interrupt_on_new_message: false,
+            multimodal: crate::config::MultimodalConfig::default(),
+            hooks: None,
+            non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
+            query_classification: crate::config::QueryClassificationConfig::default(),
+            model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
+            approval_manager: Arc::new(ApprovalManager::from_config(
+                &crate::config::AutonomyConfig::default(),
+            )),
+        });
+
+        let in_thread = |id: &str, content: &str, thread: &str| traits::ChannelMessage {
+            id: id.to_string(),
+            sender: "alice".to_string(),
+            reply_target: "chat-1".to_string(),
+            content: content.to_string(),
+            channel: "test-channel".to_string(),
+            timestamp: 1,
+            thread_ts: Some(thread.to_string()),
+        };
+
+        // Two threads started by the same sender, handled concurrently.
+        tokio::join!(
+            process_channel_message(
+                runtime_ctx.clone(),
+                in_thread("msg-a", "about cats", "t1"),
+                CancellationToken::new(),
+            ),
+            process_channel_message(
+                runtime_ctx.clone(),
+                in_thread("msg-b", "about dogs", "t2"),
+                CancellationToken::new(),
+            ),
+        );
+        process_channel_message(
+            runtime_ctx.clone(),
+            in_thread("msg-c", "more cats", "t1"),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let calls = provider_impl
+            .calls
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let follow_up = calls.last().unwrap();
+        assert_eq!(follow_up.len(), 4);
+        assert!(follow_up[1].1.contains("about cats"));
+        assert!(follow_up[3].1.contains("more cats"));
+        assert!(!follow_up
+            .iter()
+            .any(|(_, text)| text.contains("about dogs")));
+
+        let histories = runtime_ctx
+            .conversation_histories
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        assert_eq!(histories["test-channel_t1_alice"].len(), 4);
+        assert_eq!(histories["test-channel_t2_alice"].len(), 2);
+        assert!(!histories.contains_key("test-channel_alice"));
+    }
+
     #[tokio::test]
     async fn process_channel_message_enriches_current_turn_without_persisting_context() {
         let channel_impl = Arc::new(RecordingChannel::default());
@@ -9449,6 +11141,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -9460,12 +11153,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -9539,6 +11235,7 @@ BTC is currently around $65,000 based on latest tool output."#
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(histories)),
@@ -9550,12 +11247,15 @@ BTC is currently around $65,000 based on latest tool output."#
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -10026,7 +11726,8 @@ BTC is currently around $65,000 based on latest tool output."#;
             group_reply: None,
         });
 
-        let channels = collect_configured_channels(&config, "test");
+        let channels =
+            collect_configured_channels(&config, "test", &ChannelSelection::default()).unwrap();
 
         assert!(channels
             .iter()
@@ -10036,6 +11737,24 @@ BTC is currently around $65,000 based on latest tool output."#;
             .any(|entry| entry.channel.name() == "mattermost"));
     }
 
+    #[test]
+    fn collect_configured_channels_skips_imessage_off_macos() {
+        let mut config = Config::default();
+        config.channels_config.imessage = Some(crate::config::schema::IMessageConfig {
+            allowed_contacts: vec!["*".to_string()],
+        });
+
+        let channels =
+            collect_configured_channels(&config, "test", &ChannelSelection::default()).unwrap();
+
+        assert_eq!(
+            channels
+                .iter()
+                .any(|entry| entry.channel.name() == "imessage"),
+            cfg!(target_os = "macos")
+        );
+    }
+
     #[test]
     fn collect_configured_channels_includes_dingtalk_when_configured() {
         let mut config = Config::default();
@@ -10045,7 +11764,8 @@ BTC is currently around $65,000 based on latest tool output."#;
             allowed_users: vec!["*".to_string()],
         });
 
-        let channels = collect_configured_channels(&config, "test");
+        let channels =
+            collect_configured_channels(&config, "test", &ChannelSelection::default()).unwrap();
 
         assert!(channels
             .iter()
@@ -10055,6 +11775,94 @@ BTC is currently around $65,000 based on latest tool output."#;
             .any(|entry| entry.channel.name() == "dingtalk"));
     }
 
+    fn config_with_mattermost_and_dingtalk() -> Config {
+        let mut config = Config::default();
+        config.channels_config.mattermost = Some(crate::config::schema::MattermostConfig {
+            url: "https://mattermost.example.com".to_string(),
+            bot_token: "test-token".to_string(),
+            channel_id: Some("channel-1".to_string()),
+            allowed_users: vec![],
+            thread_replies: Some(true),
+            mention_only: Some(false),
+            group_reply: None,
+        });
+        config.channels_config.dingtalk = Some(crate::config::schema::DingTalkConfig {
+            client_id: "ding-app-key".to_string(),
+            client_secret: "ding-app-secret".to_string(),
+            allowed_users: vec!["*".to_string()],
+        });
+        config
+    }
+
+    #[test]
+    fn collect_configured_channels_honors_only_selection() {
+        let config = config_with_mattermost_and_dingtalk();
+        let selection = ChannelSelection {
+            only: vec!["DingTalk".to_string()],
+            except: Vec::new(),
+        };
+
+        let channels = collect_configured_channels(&config, "test", &selection).unwrap();
+
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].display_name, "DingTalk");
+    }
+
+    #[test]
+    fn collect_configured_channels_honors_except_selection() {
+        let config = config_with_mattermost_and_dingtalk();
+        let selection = ChannelSelection {
+            only: Vec::new(),
+            except: vec!["dingtalk".to_string()],
+        };
+
+        let channels = collect_configured_channels(&config, "test", &selection).unwrap();
+
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].display_name, "Mattermost");
+    }
+
+    #[test]
+    fn collect_configured_channels_rejects_unconfigured_only_name() {
+        let config = config_with_mattermost_and_dingtalk();
+        let selection = ChannelSelection {
+            only: vec!["matrix".to_string()],
+            except: Vec::new(),
+        };
+
+        let err = collect_configured_channels(&config, "test", &selection)
+            .err()
+            .expect("unconfigured channel must be rejected");
+        let message = err.to_string();
+        assert!(message.contains("not configured"));
+        assert!(message.contains("mattermost, dingtalk"));
+        assert!(get_live_channel("mattermost").is_none());
+    }
+
+    #[test]
+    fn channel_selection_rejects_unknown_names() {
+        let selection = ChannelSelection {
+            only: Vec::new(),
+            except: vec!["carrier-pigeon".to_string()],
+        };
+        let err = selection
+            .validate(&crate::config::ChannelsConfig::default())
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown channel"));
+    }
+
+    #[test]
+    fn channel_selection_debug_directives_target_selected_modules() {
+        let selection = ChannelSelection {
+            only: vec!["email".to_string()],
+            except: Vec::new(),
+        };
+        let directives = selection.debug_log_directives();
+        assert!(directives.contains(&"zeroclaw::channels::email_channel=debug".to_string()));
+        assert!(directives.contains(&"zeroclaw::channels::telegram=info".to_string()));
+        assert!(!directives.contains(&"zeroclaw::channels::telegram=debug".to_string()));
+    }
+
     struct AlwaysFailChannel {
         name: &'static str,
         calls: Arc<AtomicUsize>,
@@ -10240,7 +12048,7 @@ BTC is currently around $65,000 based on latest tool output."#;
 
     /// End-to-end test: a photo attachment message (containing `[IMAGE:]`
     /// marker) sent through `process_channel_message` with a non-vision
-    /// provider must produce a `"⚠️ Error: …does not support vision"` reply
+    /// provider must produce a friendly "…does not support vision" reply
     /// on the recording channel — no real Telegram or LLM API required.
     #[tokio::test]
     async fn e2e_photo_attachment_rejected_by_non_vision_provider() {
@@ -10262,6 +12070,7 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -10273,12 +12082,15 @@ BTC is currently around $65,000 based on latest tool output."#;
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
@@ -10308,8 +12120,8 @@ BTC is currently around $65,000 based on latest tool output."#;
             sent[0]
         );
         assert!(
-            sent[0].contains("⚠️ Error"),
-            "reply must start with error prefix, got: {}",
+            sent[0].contains("Reference: ") && !sent[0].contains("provider_capability_error"),
+            "reply must carry a reference instead of the raw error, got: {}",
             sent[0]
         );
     }
@@ -10333,6 +12145,7 @@ BTC is currently around $65,000 based on latest tool output."#;
             model: Arc::new("test-model".to_string()),
             temperature: 0.0,
             auto_save_memory: false,
+            memory_classification: crate::config::MemoryClassificationConfig::default(),
             max_tool_iterations: 5,
             min_relevance_score: 0.0,
             conversation_histories: Arc::new(Mutex::new(HashMap::new())),
@@ -10344,12 +12157,15 @@ BTC is currently around $65,000 based on latest tool output."#;
             provider_runtime_options: providers::ProviderRuntimeOptions::default(),
             workspace_dir: Arc::new(std::env::temp_dir()),
             message_timeout_secs: CHANNEL_MESSAGE_TIMEOUT_SECS,
+            tool_progress_interval_secs: 0,
             interrupt_on_new_message: false,
             multimodal: crate::config::MultimodalConfig::default(),
             hooks: None,
             non_cli_excluded_tools: Arc::new(Mutex::new(Vec::new())),
             query_classification: crate::config::QueryClassificationConfig::default(),
             model_routes: Vec::new(),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             approval_manager: Arc::new(ApprovalManager::from_config(
                 &crate::config::AutonomyConfig::default(),
             )),
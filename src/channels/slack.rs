@@ -1,4 +1,6 @@
+use super::slash_commands::{self, SlashCommand};
 use super::traits::{Channel, ChannelMessage, SendMessage};
+use crate::config::{ReplyStyle, StreamMode};
 use async_trait::async_trait;
 use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
@@ -15,8 +17,14 @@ pub struct SlackChannel {
     allowed_users: Vec<String>,
     mention_only: bool,
     group_reply_allowed_sender_ids: Vec<String>,
+    stream_mode: StreamMode,
+    draft_update_interval_ms: u64,
+    slash_commands: Vec<SlashCommand>,
+    reply_style: ReplyStyle,
+    api_base: String,
 }
 
+const SLACK_API_BASE: &str = "https://slack.com/api";
 const SLACK_HISTORY_MAX_RETRIES: u32 = 3;
 const SLACK_HISTORY_DEFAULT_RETRY_AFTER_SECS: u64 = 1;
 const SLACK_HISTORY_MAX_BACKOFF_SECS: u64 = 120;
@@ -36,9 +44,139 @@ impl SlackChannel {
             allowed_users,
             mention_only: false,
             group_reply_allowed_sender_ids: Vec::new(),
+            stream_mode: StreamMode::Off,
+            draft_update_interval_ms: 1000,
+            slash_commands: Vec::new(),
+            reply_style: ReplyStyle::ThreadReply,
+            api_base: SLACK_API_BASE.to_string(),
         }
     }
 
+    /// Configure how replies attach to the triggering message. Slack has no
+    /// quoted replies, so [`ReplyStyle::InlineReply`] answers in a thread.
+    pub fn with_reply_style(mut self, reply_style: ReplyStyle) -> Self {
+        self.reply_style = reply_style;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_api_base(mut self, api_base: String) -> Self {
+        self.api_base = api_base;
+        self
+    }
+
+    /// Slash commands to accept over Socket Mode. Slack has no bot-token API
+    /// to create them, so they must also exist in the app settings.
+    pub fn with_slash_commands(mut self, commands: Vec<SlashCommand>) -> Self {
+        self.slash_commands = commands;
+        self
+    }
+
+    /// Turn a Socket Mode `slash_commands` payload into a channel message,
+    /// after the same sender and channel checks as ordinary messages.
+    fn slash_command_message(
+        &self,
+        payload: &serde_json::Value,
+        scoped_channel: Option<&str>,
+    ) -> Option<ChannelMessage> {
+        let invocation = slash_commands::parse_slack_command(payload)?;
+        if scoped_channel.is_some_and(|scoped| scoped != invocation.channel_id) {
+            return None;
+        }
+        if !self.is_user_allowed(&invocation.user_id) {
+            tracing::warn!(
+                "Slack: ignoring {} from unauthorized user: {}",
+                invocation.name,
+                invocation.user_id
+            );
+            return None;
+        }
+        let content = slash_commands::invocation_content(
+            &self.slash_commands,
+            &invocation.name,
+            &invocation.text,
+        )?;
+        Some(ChannelMessage {
+            id: format!("slack_command_{}", invocation.trigger_id),
+            sender: invocation.user_id,
+            reply_target: invocation.channel_id,
+            content,
+            channel: "slack".to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            thread_ts: None,
+        })
+    }
+
+    /// Configure streaming mode for progressive draft updates.
+    pub fn with_streaming(
+        mut self,
+        stream_mode: StreamMode,
+        draft_update_interval_ms: u64,
+    ) -> Self {
+        self.stream_mode = stream_mode;
+        self.draft_update_interval_ms = draft_update_interval_ms;
+        self
+    }
+
+    async fn post_message(
+        &self,
+        channel: &str,
+        text: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let mut body = serde_json::json!({
+            "channel": channel,
+            "text": text
+        });
+
+        if let Some(ts) = thread_ts {
+            body["thread_ts"] = serde_json::json!(ts);
+        }
+
+        self.web_api_call("chat.postMessage", &body).await
+    }
+
+    /// POST a Web API method and return the parsed response.
+    async fn web_api_call(
+        &self,
+        method: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<serde_json::Value> {
+        let resp = self
+            .http_client()
+            .post(format!("{}/{method}", self.api_base))
+            .bearer_auth(&self.bot_token)
+            .json(body)
+            .send()
+            .await?;
+
+        let status = resp.status();
+        let body = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+
+        if !status.is_success() {
+            let sanitized = crate::providers::sanitize_api_error(&body);
+            anyhow::bail!("Slack {method} failed ({status}): {sanitized}");
+        }
+
+        // Slack returns 200 for most app-level errors; check JSON "ok" field
+        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
+            let err = parsed
+                .get("error")
+                .and_then(|e| e.as_str())
+                .unwrap_or("unknown");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+
+        Ok(parsed)
+    }
+
     /// Configure group-chat trigger policy.
     pub fn with_group_reply_policy(
         mut self,
@@ -92,11 +230,17 @@ impl SlackChannel {
     }
 
     /// Resolve the thread identifier for inbound Slack messages.
-    /// Replies carry `thread_ts` (root thread id); top-level messages only have `ts`.
-    fn inbound_thread_ts(msg: &serde_json::Value, ts: &str) -> Option<String> {
+    /// Replies carry `thread_ts` (root thread id); top-level messages only have
+    /// `ts`, which starts a thread unless replies go to the top level.
+    fn inbound_thread_ts(
+        msg: &serde_json::Value,
+        ts: &str,
+        reply_style: ReplyStyle,
+    ) -> Option<String> {
+        let starts_thread = !ts.is_empty() && reply_style != ReplyStyle::TopLevel;
         msg.get("thread_ts")
             .and_then(|t| t.as_str())
-            .or(if ts.is_empty() { None } else { Some(ts) })
+            .or(if starts_thread { Some(ts) } else { None })
             .map(str::to_string)
     }
 
@@ -398,6 +542,17 @@ impl SlackChannel {
                     tracing::warn!("Slack Socket Mode: received disconnect event");
                     break;
                 }
+                if envelope_type == "slash_commands" {
+                    let Some(channel_msg) = envelope.get("payload").and_then(|payload| {
+                        self.slash_command_message(payload, scoped_channel.as_deref())
+                    }) else {
+                        continue;
+                    };
+                    if tx.send(channel_msg).await.is_err() {
+                        return Ok(());
+                    }
+                    continue;
+                }
                 if envelope_type != "events_api" {
                     continue;
                 }
@@ -487,7 +642,7 @@ impl SlackChannel {
                         .duration_since(std::time::UNIX_EPOCH)
                         .unwrap_or_default()
                         .as_secs(),
-                    thread_ts: Self::inbound_thread_ts(event, ts),
+                    thread_ts: Self::inbound_thread_ts(event, ts, self.reply_style),
                 };
 
                 if tx.send(channel_msg).await.is_err() {
@@ -652,44 +807,63 @@ impl Channel for SlackChannel {
     }
 
     async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
-        let mut body = serde_json::json!({
-            "channel": message.recipient,
-            "text": message.content
-        });
-
-        if let Some(ref ts) = message.thread_ts {
-            body["thread_ts"] = serde_json::json!(ts);
-        }
-
-        let resp = self
-            .http_client()
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
+        self.post_message(
+            &message.recipient,
+            &message.content,
+            message.thread_ts.as_deref(),
+        )
+        .await?;
+        Ok(())
+    }
 
-        let status = resp.status();
-        let body = resp
-            .text()
-            .await
-            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+    fn supports_draft_updates(&self) -> bool {
+        self.stream_mode != StreamMode::Off
+    }
 
-        if !status.is_success() {
-            let sanitized = crate::providers::sanitize_api_error(&body);
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {sanitized}");
-        }
+    fn draft_update_interval(&self) -> Duration {
+        Duration::from_millis(self.draft_update_interval_ms)
+    }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
-        let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
-        if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
-            let err = parsed
-                .get("error")
-                .and_then(|e| e.as_str())
-                .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+    async fn send_draft(&self, message: &SendMessage) -> anyhow::Result<Option<String>> {
+        if self.stream_mode == StreamMode::Off {
+            return Ok(None);
         }
+        let text = if message.content.is_empty() {
+            "..."
+        } else {
+            message.content.as_str()
+        };
+        let posted = self
+            .post_message(&message.recipient, text, message.thread_ts.as_deref())
+            .await?;
+        Ok(posted
+            .get("ts")
+            .and_then(|ts| ts.as_str())
+            .map(str::to_string))
+    }
+
+    async fn edit(&self, recipient: &str, message_id: &str, new_text: &str) -> anyhow::Result<()> {
+        self.web_api_call(
+            "chat.update",
+            &serde_json::json!({
+                "channel": recipient,
+                "ts": message_id,
+                "text": new_text,
+            }),
+        )
+        .await?;
+        Ok(())
+    }
 
+    async fn cancel_draft(&self, recipient: &str, message_id: &str) -> anyhow::Result<()> {
+        self.web_api_call(
+            "chat.delete",
+            &serde_json::json!({
+                "channel": recipient,
+                "ts": message_id,
+            }),
+        )
+        .await?;
         Ok(())
     }
 
@@ -698,11 +872,26 @@ impl Channel for SlackChannel {
         let scoped_channel = self.configured_channel_id();
         if self.configured_app_token().is_some() {
             tracing::info!("Slack channel listening in Socket Mode");
+            if !self.slash_commands.is_empty() {
+                tracing::info!(
+                    "Slack: handling slash commands {}; add them under Slash Commands in the app settings",
+                    self.slash_commands
+                        .iter()
+                        .map(|command| format!("/{}", command.name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
             return self
                 .listen_socket_mode(tx, &bot_user_id, scoped_channel)
                 .await;
         }
 
+        if !self.slash_commands.is_empty() {
+            tracing::warn!(
+                "Slack: slash_commands need Socket Mode (app_token); ignoring them while polling"
+            );
+        }
         let mut discovered_channels: Vec<String> = Vec::new();
         let mut last_discovery = Instant::now();
         let mut last_ts_by_channel: HashMap<String, String> = HashMap::new();
@@ -831,7 +1020,7 @@ impl Channel for SlackChannel {
                                 .duration_since(std::time::UNIX_EPOCH)
                                 .unwrap_or_default()
                                 .as_secs(),
-                            thread_ts: Self::inbound_thread_ts(msg, ts),
+                            thread_ts: Self::inbound_thread_ts(msg, ts, self.reply_style),
                         };
 
                         if tx.send(channel_msg).await.is_err() {
@@ -864,6 +1053,35 @@ mod tests {
         assert_eq!(ch.name(), "slack");
     }
 
+    #[test]
+    fn slash_commands_respect_allowlist_scope_and_config() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec!["U1".into()])
+            .with_slash_commands(vec![SlashCommand::Reset]);
+        let payload = |command: &str, user: &str, channel: &str| {
+            serde_json::json!({
+                "command": command, "text": "", "user_id": user,
+                "channel_id": channel, "trigger_id": "t.1"
+            })
+        };
+
+        let msg = ch
+            .slash_command_message(&payload("/reset", "U1", "C1"), None)
+            .unwrap();
+        assert_eq!(msg.content, "/reset");
+        assert_eq!(msg.sender, "U1");
+        assert_eq!(msg.reply_target, "C1");
+
+        assert!(ch
+            .slash_command_message(&payload("/reset", "U2", "C1"), None)
+            .is_none());
+        assert!(ch
+            .slash_command_message(&payload("/reset", "U1", "C1"), Some("C9"))
+            .is_none());
+        assert!(ch
+            .slash_command_message(&payload("/skills", "U1", "C1"), None)
+            .is_none());
+    }
+
     #[test]
     fn slack_channel_with_channel_id() {
         let ch = SlackChannel::new("xoxb-fake".into(), None, Some("C12345".into()), vec![]);
@@ -1070,7 +1288,7 @@ mod tests {
             "thread_ts": "123.001"
         });
 
-        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "123.002");
+        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "123.002", ReplyStyle::ThreadReply);
         assert_eq!(thread_ts.as_deref(), Some("123.001"));
     }
 
@@ -1080,7 +1298,7 @@ mod tests {
             "ts": "123.001"
         });
 
-        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "123.001");
+        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "123.001", ReplyStyle::ThreadReply);
         assert_eq!(thread_ts.as_deref(), Some("123.001"));
     }
 
@@ -1088,10 +1306,69 @@ mod tests {
     fn inbound_thread_ts_none_when_ts_missing() {
         let msg = serde_json::json!({});
 
-        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "");
+        let thread_ts = SlackChannel::inbound_thread_ts(&msg, "", ReplyStyle::ThreadReply);
         assert_eq!(thread_ts, None);
     }
 
+    #[test]
+    fn inbound_thread_ts_top_level_only_keeps_existing_threads() {
+        let top_level = serde_json::json!({ "ts": "123.001" });
+        let threaded = serde_json::json!({ "ts": "123.002", "thread_ts": "123.001" });
+
+        assert_eq!(
+            SlackChannel::inbound_thread_ts(&top_level, "123.001", ReplyStyle::TopLevel),
+            None
+        );
+        assert_eq!(
+            SlackChannel::inbound_thread_ts(&threaded, "123.002", ReplyStyle::TopLevel).as_deref(),
+            Some("123.001")
+        );
+        assert_eq!(
+            SlackChannel::inbound_thread_ts(&top_level, "123.001", ReplyStyle::InlineReply)
+                .as_deref(),
+            Some("123.001")
+        );
+    }
+
+    #[tokio::test]
+    async fn thread_replies_post_into_the_triggering_thread() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/chat.postMessage"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "ok": true, "ts": "123.009" })),
+            )
+            .mount(&server)
+            .await;
+        let channel = SlackChannel::new("xoxb-test".into(), None, None, vec!["*".into()])
+            .with_api_base(server.uri());
+        let event = serde_json::json!({ "ts": "123.001" });
+        let msg = ChannelMessage {
+            id: "slack_C123_123.001".into(),
+            sender: "U1".into(),
+            reply_target: "C123".into(),
+            content: "hello".into(),
+            channel: "slack".into(),
+            timestamp: 1,
+            thread_ts: SlackChannel::inbound_thread_ts(&event, "123.001", ReplyStyle::ThreadReply),
+        };
+
+        channel
+            .send(&SendMessage::reply("hi there", &msg))
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = requests[0].body_json().unwrap();
+        assert_eq!(body["channel"], "C123");
+        assert_eq!(body["thread_ts"], "123.001");
+        assert_eq!(body["text"], "hi there");
+    }
+
     #[test]
     fn ensure_poll_cursor_bootstraps_new_channel() {
         let mut cursors = HashMap::new();
@@ -1142,4 +1419,25 @@ mod tests {
         let delay = SlackChannel::compute_retry_delay(30, 3, 250);
         assert_eq!(delay, Duration::from_secs(120) + Duration::from_millis(250));
     }
+
+    #[test]
+    fn supports_draft_updates_respects_stream_mode() {
+        let off = SlackChannel::new("xoxb-fake".into(), None, None, vec![]);
+        assert!(!off.supports_draft_updates());
+
+        let partial = SlackChannel::new("xoxb-fake".into(), None, None, vec![])
+            .with_streaming(StreamMode::Partial, 1500);
+        assert!(partial.supports_draft_updates());
+        assert_eq!(partial.draft_update_interval(), Duration::from_millis(1500));
+    }
+
+    #[tokio::test]
+    async fn send_draft_returns_none_when_stream_mode_off() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, None, vec![]);
+        let id = ch
+            .send_draft(&SendMessage::new("draft", "C12345"))
+            .await
+            .unwrap();
+        assert!(id.is_none());
+    }
 }
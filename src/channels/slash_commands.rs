@@ -0,0 +1,283 @@
+//! Native slash commands for Discord and Slack (`slash_commands = [...]`).
+//!
+//! Discord commands are registered on startup with the application command
+//! API (per guild when `guild_id` is set, otherwise globally). Slack has no
+//! bot-token API for that, so Slack commands must be added in the app's
+//! settings; Socket Mode delivers them without a request URL. Invocations
+//! from either platform become ordinary [`super::traits::ChannelMessage`]s:
+//! `/ask` carries the question, `/reset` becomes the `/reset` runtime
+//! command that clears the sender's history, and `/skills` asks the agent
+//! to list its skills.
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+/// Message sent to the agent for `/skills`.
+const SKILLS_PROMPT: &str =
+    "List the skills you have available, with one line each on what they do.";
+
+/// A slash command zeroclaw knows how to handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlashCommand {
+    Ask,
+    Reset,
+    Skills,
+}
+
+impl SlashCommand {
+    pub const ALL: [Self; 3] = [Self::Ask, Self::Reset, Self::Skills];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ask => "ask",
+            Self::Reset => "reset",
+            Self::Skills => "skills",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Ask => "Ask the assistant a question",
+            Self::Reset => "Clear your conversation history with the assistant",
+            Self::Skills => "List the assistant's skills",
+        }
+    }
+
+    /// Whether the command takes a free-text argument.
+    fn takes_text(self) -> bool {
+        matches!(self, Self::Ask)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().trim_start_matches('/');
+        Self::ALL
+            .into_iter()
+            .find(|command| command.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a `slash_commands` config list, rejecting unknown names.
+    pub fn parse_list(names: &[String]) -> Result<Vec<Self>> {
+        let mut commands = Vec::new();
+        for name in names {
+            let Some(command) = Self::from_name(name) else {
+                bail!(
+                    "unknown slash command `{name}`; supported: {}",
+                    Self::ALL.map(Self::name).join(", ")
+                );
+            };
+            if !commands.contains(&command) {
+                commands.push(command);
+            }
+        }
+        Ok(commands)
+    }
+}
+
+/// The message content an invocation of `name` with `text` routes to, or
+/// `None` when the command is not enabled or `/ask` has no question.
+pub fn invocation_content(enabled: &[SlashCommand], name: &str, text: &str) -> Option<String> {
+    let command = SlashCommand::from_name(name).filter(|command| enabled.contains(command))?;
+    let text = text.trim();
+    match command {
+        SlashCommand::Ask if text.is_empty() => None,
+        SlashCommand::Ask => Some(text.to_string()),
+        SlashCommand::Reset => Some("/reset".to_string()),
+        SlashCommand::Skills => Some(SKILLS_PROMPT.to_string()),
+    }
+}
+
+/// Body for Discord's bulk-overwrite application commands endpoint.
+pub fn discord_command_definitions(enabled: &[SlashCommand]) -> Value {
+    Value::Array(
+        enabled
+            .iter()
+            .map(|command| {
+                let mut definition = json!({
+                    "name": command.name(),
+                    "description": command.description(),
+                    "type": 1,
+                });
+                if command.takes_text() {
+                    definition["options"] = json!([{
+                        "type": 3,
+                        "name": "text",
+                        "description": "Your message",
+                        "required": true,
+                    }]);
+                }
+                definition
+            })
+            .collect(),
+    )
+}
+
+/// A slash command invocation read from a Discord `INTERACTION_CREATE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscordInvocation {
+    pub interaction_id: String,
+    pub token: String,
+    pub name: String,
+    pub text: String,
+    pub user_id: String,
+    pub channel_id: String,
+    pub guild_id: Option<String>,
+}
+
+/// Parse the `d` payload of an `INTERACTION_CREATE` event. Only application
+/// commands (interaction type 2) are returned.
+pub fn parse_discord_interaction(d: &Value) -> Option<DiscordInvocation> {
+    if d.get("type").and_then(Value::as_u64) != Some(2) {
+        return None;
+    }
+    let str_at = |value: &Value, pointer: &str| {
+        value
+            .pointer(pointer)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let data = d.get("data")?;
+    let text = data
+        .get("options")
+        .and_then(Value::as_array)
+        .map(|options| {
+            options
+                .iter()
+                .filter_map(|option| option.get("value").and_then(Value::as_str))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+    Some(DiscordInvocation {
+        interaction_id: str_at(d, "/id")?,
+        token: str_at(d, "/token")?,
+        name: str_at(data, "/name")?,
+        text,
+        // Guild invocations carry `member.user`, DMs carry `user`.
+        user_id: str_at(d, "/member/user/id").or_else(|| str_at(d, "/user/id"))?,
+        channel_id: str_at(d, "/channel_id").or_else(|| str_at(d, "/channel/id"))?,
+        guild_id: str_at(d, "/guild_id"),
+    })
+}
+
+/// A slash command invocation read from a Slack Socket Mode
+/// `slash_commands` envelope payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlackInvocation {
+    pub name: String,
+    pub text: String,
+    pub user_id: String,
+    pub channel_id: String,
+    pub trigger_id: String,
+}
+
+pub fn parse_slack_command(payload: &Value) -> Option<SlackInvocation> {
+    let field = |key: &str| payload.get(key).and_then(Value::as_str).map(str::to_string);
+    Some(SlackInvocation {
+        name: field("command")?,
+        text: field("text").unwrap_or_default(),
+        user_id: field("user_id")?,
+        channel_id: field("channel_id")?,
+        trigger_id: field("trigger_id").unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [SlashCommand; 3] = SlashCommand::ALL;
+
+    #[test]
+    fn config_names_are_validated_and_deduplicated() {
+        let names = vec!["ask".to_string(), "/Reset".into(), "ask".into()];
+        assert_eq!(
+            SlashCommand::parse_list(&names).unwrap(),
+            vec![SlashCommand::Ask, SlashCommand::Reset]
+        );
+        let err = SlashCommand::parse_list(&["deploy".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("supported: ask, reset, skills"));
+    }
+
+    #[test]
+    fn invocations_map_to_message_content() {
+        assert_eq!(
+            invocation_content(&ALL, "/ask", "  what's the weather? ").as_deref(),
+            Some("what's the weather?")
+        );
+        assert_eq!(invocation_content(&ALL, "ask", "   "), None);
+        assert_eq!(
+            invocation_content(&ALL, "/RESET", "ignored").as_deref(),
+            Some("/reset")
+        );
+        assert_eq!(
+            invocation_content(&ALL, "skills", "").as_deref(),
+            Some(SKILLS_PROMPT)
+        );
+        // Not enabled in config, or not a zeroclaw command at all.
+        assert_eq!(invocation_content(&[SlashCommand::Ask], "/reset", ""), None);
+        assert_eq!(invocation_content(&ALL, "/deploy", "prod"), None);
+    }
+
+    #[test]
+    fn discord_interactions_are_parsed() {
+        let guild = json!({
+            "id": "111", "token": "tok", "type": 2,
+            "guild_id": "g1", "channel_id": "c1",
+            "member": {"user": {"id": "u1"}},
+            "data": {"name": "ask", "options": [{"name": "text", "type": 3, "value": "hi there"}]}
+        });
+        assert_eq!(
+            parse_discord_interaction(&guild),
+            Some(DiscordInvocation {
+                interaction_id: "111".into(),
+                token: "tok".into(),
+                name: "ask".into(),
+                text: "hi there".into(),
+                user_id: "u1".into(),
+                channel_id: "c1".into(),
+                guild_id: Some("g1".into()),
+            })
+        );
+
+        let dm = json!({
+            "id": "112", "token": "tok", "type": 2,
+            "channel": {"id": "dm1"}, "user": {"id": "u2"},
+            "data": {"name": "reset"}
+        });
+        let parsed = parse_discord_interaction(&dm).unwrap();
+        assert_eq!(
+            (parsed.user_id.as_str(), parsed.channel_id.as_str()),
+            ("u2", "dm1")
+        );
+        assert_eq!(parsed.text, "");
+        assert_eq!(parsed.guild_id, None);
+
+        // Button clicks and autocomplete are not slash commands.
+        let component = json!({"id": "113", "token": "tok", "type": 3, "data": {"custom_id": "x"}});
+        assert_eq!(parse_discord_interaction(&component), None);
+    }
+
+    #[test]
+    fn discord_definitions_give_ask_a_required_text_option() {
+        let definitions = discord_command_definitions(&ALL);
+        assert_eq!(definitions.as_array().unwrap().len(), 3);
+        assert_eq!(definitions[0]["name"], "ask");
+        assert_eq!(definitions[0]["options"][0]["required"], true);
+        assert!(definitions[1].get("options").is_none());
+    }
+
+    #[test]
+    fn slack_commands_are_parsed() {
+        let payload = json!({
+            "command": "/ask", "text": "summarize #general",
+            "user_id": "U1", "channel_id": "C1", "trigger_id": "t.1"
+        });
+        let parsed = parse_slack_command(&payload).unwrap();
+        assert_eq!(parsed.name, "/ask");
+        assert_eq!(
+            invocation_content(&ALL, &parsed.name, &parsed.text).as_deref(),
+            Some("summarize #general")
+        );
+        assert_eq!(parse_slack_command(&json!({"command": "/ask"})), None);
+    }
+}
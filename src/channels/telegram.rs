@@ -539,6 +539,46 @@ impl TelegramChannel {
         self
     }
 
+    /// Plain-text `sendMessage` that returns the new message's id, for
+    /// messages edited later (drafts, tool status). `purpose` only labels
+    /// errors.
+    async fn send_plain_message(
+        &self,
+        recipient: &str,
+        text: &str,
+        purpose: &str,
+    ) -> anyhow::Result<Option<String>> {
+        let (chat_id, thread_id) = Self::parse_reply_target(recipient);
+        let end = crate::util::floor_utf8_char_boundary(text, TELEGRAM_MAX_MESSAGE_LENGTH);
+        let mut body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": &text[..end],
+        });
+        if let Some(tid) = thread_id {
+            body["message_thread_id"] = serde_json::Value::String(tid);
+        }
+
+        let resp = self
+            .client
+            .post(self.api_url("sendMessage"))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err = resp.text().await.unwrap_or_default();
+            let sanitized = Self::sanitize_telegram_error(&err);
+            anyhow::bail!("Telegram sendMessage ({purpose}) failed: {sanitized}");
+        }
+
+        let resp_json: serde_json::Value = resp.json().await?;
+        Ok(resp_json
+            .get("result")
+            .and_then(|r| r.get("message_id"))
+            .and_then(|id| id.as_i64())
+            .map(|id| id.to_string()))
+    }
+
     /// Parse reply_target into (chat_id, optional thread_id).
     fn parse_reply_target(reply_target: &str) -> (String, Option<String>) {
         if let Some((chat_id, thread_id)) = reply_target.split_once(':') {
@@ -2682,46 +2722,72 @@ impl Channel for TelegramChannel {
             return Ok(None);
         }
 
-        let (chat_id, thread_id) = Self::parse_reply_target(&message.recipient);
         let initial_text = if message.content.is_empty() {
-            "...".to_string()
+            "..."
         } else {
-            message.content.clone()
+            message.content.as_str()
         };
+        let message_id = self
+            .send_plain_message(&message.recipient, initial_text, "draft")
+            .await?;
 
-        let mut body = serde_json::json!({
+        let (chat_id, _) = Self::parse_reply_target(&message.recipient);
+        self.last_draft_edit
+            .lock()
+            .insert(chat_id, std::time::Instant::now());
+
+        Ok(message_id)
+    }
+
+    /// Edits the first status in place, so a running tool's progress stays
+    /// one message.
+    async fn send_or_edit_status(
+        &self,
+        message: &SendMessage,
+        status_id: Option<&str>,
+    ) -> anyhow::Result<Option<String>> {
+        if let Some(status_id) = status_id {
+            self.edit(&message.recipient, status_id, &message.content)
+                .await?;
+            return Ok(Some(status_id.to_string()));
+        }
+        self.send_plain_message(&message.recipient, &message.content, "status")
+            .await
+    }
+
+    fn draft_update_interval(&self) -> Duration {
+        Duration::from_millis(self.draft_update_interval_ms)
+    }
+
+    /// Plain-text `editMessageText`, truncated to the Telegram message limit.
+    async fn edit(&self, recipient: &str, message_id: &str, new_text: &str) -> anyhow::Result<()> {
+        let (chat_id, _) = Self::parse_reply_target(recipient);
+        // UTF-8 safe truncation for mid-stream edits
+        let end = crate::util::floor_utf8_char_boundary(new_text, TELEGRAM_MAX_MESSAGE_LENGTH);
+        let message_id: i64 = message_id
+            .parse()
+            .with_context(|| format!("Invalid Telegram message_id '{message_id}'"))?;
+
+        let body = serde_json::json!({
             "chat_id": chat_id,
-            "text": initial_text,
+            "message_id": message_id,
+            "text": &new_text[..end],
         });
-        if let Some(tid) = thread_id {
-            body["message_thread_id"] = serde_json::Value::String(tid.to_string());
-        }
 
         let resp = self
             .client
-            .post(self.api_url("sendMessage"))
+            .post(self.api_url("editMessageText"))
             .json(&body)
             .send()
             .await?;
 
         if !resp.status().is_success() {
+            let status = resp.status();
             let err = resp.text().await.unwrap_or_default();
             let sanitized = Self::sanitize_telegram_error(&err);
-            anyhow::bail!("Telegram sendMessage (draft) failed: {sanitized}");
+            anyhow::bail!("Telegram editMessageText failed ({status}): {sanitized}");
         }
-
-        let resp_json: serde_json::Value = resp.json().await?;
-        let message_id = resp_json
-            .get("result")
-            .and_then(|r| r.get("message_id"))
-            .and_then(|id| id.as_i64())
-            .map(|id| id.to_string());
-
-        self.last_draft_edit
-            .lock()
-            .insert(chat_id.to_string(), std::time::Instant::now());
-
-        Ok(message_id)
+        Ok(())
     }
 
     async fn update_draft(
@@ -2743,51 +2809,13 @@ impl Channel for TelegramChannel {
             }
         }
 
-        // Truncate to Telegram limit for mid-stream edits (UTF-8 safe)
-        let display_text = if text.len() > TELEGRAM_MAX_MESSAGE_LENGTH {
-            let mut end = 0;
-            for (idx, ch) in text.char_indices() {
-                let next = idx + ch.len_utf8();
-                if next > TELEGRAM_MAX_MESSAGE_LENGTH {
-                    break;
-                }
-                end = next;
-            }
-            &text[..end]
-        } else {
-            text
-        };
-
-        let message_id_parsed = match message_id.parse::<i64>() {
-            Ok(id) => id,
-            Err(e) => {
-                tracing::warn!("Invalid Telegram message_id '{message_id}': {e}");
-                return Ok(None);
+        match self.edit(recipient, message_id, text).await {
+            Ok(()) => {
+                self.last_draft_edit
+                    .lock()
+                    .insert(chat_id, std::time::Instant::now());
             }
-        };
-
-        let body = serde_json::json!({
-            "chat_id": chat_id,
-            "message_id": message_id_parsed,
-            "text": display_text,
-        });
-
-        let resp = self
-            .client
-            .post(self.api_url("editMessageText"))
-            .json(&body)
-            .send()
-            .await?;
-
-        if resp.status().is_success() {
-            self.last_draft_edit
-                .lock()
-                .insert(chat_id.clone(), std::time::Instant::now());
-        } else {
-            let status = resp.status();
-            let err = resp.text().await.unwrap_or_default();
-            let sanitized = Self::sanitize_telegram_error(&err);
-            tracing::debug!("Telegram editMessageText failed ({status}): {sanitized}");
+            Err(e) => tracing::debug!("{e}"),
         }
 
         Ok(None)
@@ -0,0 +1,268 @@
+//! Status messages for long-running tools
+//! (`[channels_config] tool_progress_interval_secs`).
+//!
+//! While the tool loop runs, progress lines reported by streaming tools (see
+//! [`crate::tools::progress`]) are shown in the conversation at most once per
+//! interval. Channels that can edit messages keep them in one status message
+//! that is edited in place; others get a new message per update, capped at
+//! [`MAX_STATUS_MESSAGES`] per turn so a chatty tool cannot flood the chat.
+
+use super::edit_throttle::EditThrottle;
+use super::traits::{Channel, SendMessage};
+use crate::tools::progress::ToolProgress;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+/// Status messages sent per turn by channels that cannot edit them.
+pub const MAX_STATUS_MESSAGES: usize = 3;
+
+struct StatusLine {
+    channel: Arc<dyn Channel>,
+    reply_target: String,
+    thread_ts: Option<String>,
+    status_id: Option<String>,
+    sent: usize,
+}
+
+impl StatusLine {
+    async fn show(&mut self, progress: &ToolProgress) {
+        if self.status_id.is_none() && self.sent >= MAX_STATUS_MESSAGES {
+            return;
+        }
+        let message = SendMessage::new(
+            format!("\u{23F3} {}: {}", progress.tool, progress.message),
+            &self.reply_target,
+        )
+        .in_thread(self.thread_ts.clone());
+        match self
+            .channel
+            .send_or_edit_status(&message, self.status_id.as_deref())
+            .await
+        {
+            Ok(Some(id)) => self.status_id = Some(id),
+            Ok(None) => self.sent += 1,
+            Err(err) => {
+                // Count failures too, so a broken channel is not retried for
+                // every line.
+                self.sent += 1;
+                tracing::debug!(
+                    channel = %self.channel.name(),
+                    "Failed to show tool progress: {err}"
+                );
+            }
+        }
+    }
+}
+
+/// Show progress from `rx` until every sender is gone (the tool loop ended).
+/// Updates arriving within `interval` of the last one shown are held back and
+/// only the latest is shown when the interval has passed.
+pub async fn forward_tool_progress(
+    channel: Arc<dyn Channel>,
+    reply_target: String,
+    thread_ts: Option<String>,
+    mut rx: mpsc::UnboundedReceiver<ToolProgress>,
+    interval: Duration,
+) {
+    let mut status = StatusLine {
+        channel,
+        reply_target,
+        thread_ts,
+        status_id: None,
+        sent: 0,
+    };
+    let mut throttle = EditThrottle::new(interval);
+    let mut latest: Option<ToolProgress> = None;
+    loop {
+        let next = if let Some(deadline) = throttle.deadline() {
+            tokio::select! {
+                progress = rx.recv() => progress,
+                () = tokio::time::sleep_until(deadline) => {
+                    if let Some(progress) = &latest {
+                        status.show(progress).await;
+                    }
+                    throttle.edited(Instant::now());
+                    continue;
+                }
+            }
+        } else {
+            rx.recv().await
+        };
+        // The reply follows right after, so held-back progress is dropped.
+        let Some(progress) = next else {
+            break;
+        };
+        let progress = latest.insert(progress);
+        if throttle.offer(Instant::now()) {
+            status.show(progress).await;
+            throttle.edited(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::traits::ChannelMessage;
+    use crate::skills::protocol::ToolEnvelope;
+    use crate::tools::progress::{ProgressLines, ProgressSink};
+    use async_trait::async_trait;
+    use tokio::io::AsyncReadExt;
+
+    /// Records what it shows; edits in place when `editable`.
+    #[derive(Default)]
+    struct StatusChannel {
+        editable: bool,
+        shown: parking_lot::Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl Channel for StatusChannel {
+        fn name(&self) -> &str {
+            "status"
+        }
+
+        async fn send(&self, message: &SendMessage) -> anyhow::Result<()> {
+            self.shown.lock().push(message.content.clone());
+            Ok(())
+        }
+
+        async fn listen(&self, _tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+            Ok(())
+        }
+
+        async fn send_or_edit_status(
+            &self,
+            message: &SendMessage,
+            status_id: Option<&str>,
+        ) -> anyhow::Result<Option<String>> {
+            if !self.editable {
+                self.send(message).await?;
+                return Ok(None);
+            }
+            let id = status_id.unwrap_or("status-1");
+            self.shown.lock().push(format!("{id}: {}", message.content));
+            Ok(Some(id.to_string()))
+        }
+    }
+
+    /// Runs a scripted tool that prints progress lines and then its envelope,
+    /// feeding stdout through [`ProgressLines`] as it arrives. Returns the
+    /// complete stdout.
+    async fn run_fixture(script: &str, sink: ProgressSink) -> Vec<u8> {
+        let mut child = tokio::process::Command::new("sh")
+            .arg("-c")
+            .arg(script)
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .expect("sh is available");
+        let mut stdout = child.stdout.take().unwrap();
+        let mut lines = ProgressLines::new("crawl", sink);
+        let mut output = Vec::new();
+        let mut chunk = [0u8; 64];
+        loop {
+            let read = stdout.read(&mut chunk).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            lines.push(&chunk[..read]);
+            output.extend_from_slice(&chunk[..read]);
+        }
+        assert!(child.wait().await.unwrap().success());
+        output
+    }
+
+    #[tokio::test]
+    async fn scripted_tool_progress_is_throttled_and_envelope_still_parses() {
+        let script = r#"
+            for page in 1 2 3 4 5; do
+                echo "@progress: fetched page $page/5"
+                sleep 0.05
+            done
+            printf '{"success":true,"output":"5 pages crawled"}\n'
+        "#;
+        let channel = Arc::new(StatusChannel {
+            editable: true,
+            ..StatusChannel::default()
+        });
+        let (sink, rx) = ProgressSink::channel();
+        let forwarder = tokio::spawn(forward_tool_progress(
+            channel.clone(),
+            "chat".into(),
+            None,
+            rx,
+            Duration::from_secs(60),
+        ));
+
+        let output = run_fixture(script, sink).await;
+        forwarder.await.unwrap();
+
+        // Five lines inside one interval: only the first is shown.
+        assert_eq!(
+            *channel.shown.lock(),
+            ["status-1: \u{23F3} crawl: fetched page 1/5"]
+        );
+        assert_eq!(
+            ToolEnvelope::parse_streamed(&output).unwrap(),
+            ToolEnvelope::ok("5 pages crawled")
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn held_back_progress_is_shown_after_the_interval() {
+        let channel = Arc::new(StatusChannel {
+            editable: true,
+            ..StatusChannel::default()
+        });
+        let (sink, rx) = ProgressSink::channel();
+        let forwarder = tokio::spawn(forward_tool_progress(
+            channel.clone(),
+            "chat".into(),
+            None,
+            rx,
+            Duration::from_secs(5),
+        ));
+
+        for message in ["step 1", "step 2", "step 3"] {
+            sink.report("crawl", message);
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        drop(sink);
+        forwarder.await.unwrap();
+
+        assert_eq!(
+            *channel.shown.lock(),
+            [
+                "status-1: \u{23F3} crawl: step 1",
+                "status-1: \u{23F3} crawl: step 3"
+            ]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn channels_without_edits_get_a_few_messages_at_most() {
+        let channel = Arc::new(StatusChannel::default());
+        let (sink, rx) = ProgressSink::channel();
+        let forwarder = tokio::spawn(forward_tool_progress(
+            channel.clone(),
+            "chat".into(),
+            None,
+            rx,
+            Duration::from_secs(1),
+        ));
+
+        for page in 1..=10 {
+            sink.report("crawl", &format!("page {page}"));
+            tokio::time::sleep(Duration::from_secs(2)).await;
+        }
+        drop(sink);
+        forwarder.await.unwrap();
+
+        let shown = channel.shown.lock();
+        assert_eq!(shown.len(), MAX_STATUS_MESSAGES);
+        assert_eq!(shown[0], "\u{23F3} crawl: page 1");
+    }
+}
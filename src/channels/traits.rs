@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 
 /// A message received from or sent to a channel
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ChannelMessage {
     pub id: String,
     pub sender: String,
@@ -22,6 +22,9 @@ pub struct SendMessage {
     pub subject: Option<String>,
     /// Platform thread identifier for threaded replies (e.g. Slack `thread_ts`).
     pub thread_ts: Option<String>,
+    /// [`ChannelMessage::id`] of the message this answers. Channels configured
+    /// for inline replies (`reply_style = "inline-reply"`) quote it.
+    pub reply_to: Option<String>,
 }
 
 impl SendMessage {
@@ -32,6 +35,19 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: None,
             thread_ts: None,
+            reply_to: None,
+        }
+    }
+
+    /// A reply to `msg`: sent to its reply target, in its thread, and
+    /// referencing it for channels that quote the message they answer.
+    pub fn reply(content: impl Into<String>, msg: &ChannelMessage) -> Self {
+        Self {
+            content: content.into(),
+            recipient: msg.reply_target.clone(),
+            subject: None,
+            thread_ts: msg.thread_ts.clone(),
+            reply_to: Some(msg.id.clone()),
         }
     }
 
@@ -46,6 +62,7 @@ impl SendMessage {
             recipient: recipient.into(),
             subject: Some(subject.into()),
             thread_ts: None,
+            reply_to: None,
         }
     }
 
@@ -89,33 +106,58 @@ pub trait Channel: Send + Sync {
         false
     }
 
+    /// Minimum gap between two draft edits pushed by the streaming reply path.
+    /// Text that arrives sooner is held back and flushed once the gap has passed.
+    fn draft_update_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(1000)
+    }
+
     /// Send an initial draft message. Returns a platform-specific message ID for later edits.
     async fn send_draft(&self, _message: &SendMessage) -> anyhow::Result<Option<String>> {
         Ok(None)
     }
 
+    /// Replace the text of a message this channel sent earlier.
+    ///
+    /// Channels that can edit messages implement this together with
+    /// `send_draft`; the default draft update and finalize steps then edit the
+    /// placeholder in place. Default: no-op.
+    async fn edit(
+        &self,
+        _recipient: &str,
+        _message_id: &str,
+        _new_text: &str,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Update a previously sent draft message with new accumulated content.
     ///
     /// Returns `Ok(None)` to keep the current draft message ID, or
     /// `Ok(Some(new_id))` when a continuation message was created
     /// (e.g. after hitting a platform edit-count cap).
+    ///
+    /// Default: [`Channel::edit`] the draft with `text`.
     async fn update_draft(
         &self,
-        _recipient: &str,
-        _message_id: &str,
-        _text: &str,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
     ) -> anyhow::Result<Option<String>> {
+        self.edit(recipient, message_id, text).await?;
         Ok(None)
     }
 
     /// Finalize a draft with the complete response (e.g. apply Markdown formatting).
+    ///
+    /// Default: [`Channel::edit`] the draft with `text`.
     async fn finalize_draft(
         &self,
-        _recipient: &str,
-        _message_id: &str,
-        _text: &str,
+        recipient: &str,
+        message_id: &str,
+        text: &str,
     ) -> anyhow::Result<()> {
-        Ok(())
+        self.edit(recipient, message_id, text).await
     }
 
     /// Cancel and remove a previously sent draft message if the channel supports it.
@@ -123,6 +165,21 @@ pub trait Channel: Send + Sync {
         Ok(())
     }
 
+    /// Show a status line from a running tool.
+    ///
+    /// `status_id` is the id this method returned for an earlier status in
+    /// the same turn. Channels that can edit messages replace that status in
+    /// place and return its id; the default sends `message` as a new message
+    /// and returns `Ok(None)`, so callers can cap how many they send.
+    async fn send_or_edit_status(
+        &self,
+        message: &SendMessage,
+        _status_id: Option<&str>,
+    ) -> anyhow::Result<Option<String>> {
+        self.send(message).await?;
+        Ok(None)
+    }
+
     /// Send an interactive approval prompt, if supported by the channel.
     ///
     /// Default behavior sends a plain-text fallback with slash-command actions.
@@ -271,6 +328,70 @@ mod tests {
             .await
             .is_ok());
         assert!(channel.cancel_draft("bob", "msg_1").await.is_ok());
+        assert!(channel.edit("bob", "msg_1", "edited").await.is_ok());
+        assert_eq!(
+            channel
+                .send_or_edit_status(&SendMessage::new("working", "bob"), None)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn default_draft_steps_route_through_edit() {
+        #[derive(Default)]
+        struct EditingChannel {
+            edits: std::sync::Mutex<Vec<String>>,
+        }
+
+        #[async_trait]
+        impl Channel for EditingChannel {
+            fn name(&self) -> &str {
+                "editing"
+            }
+
+            async fn send(&self, _message: &SendMessage) -> anyhow::Result<()> {
+                Ok(())
+            }
+
+            async fn listen(
+                &self,
+                _tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+            ) -> anyhow::Result<()> {
+                Ok(())
+            }
+
+            async fn edit(
+                &self,
+                recipient: &str,
+                message_id: &str,
+                new_text: &str,
+            ) -> anyhow::Result<()> {
+                self.edits
+                    .lock()
+                    .unwrap()
+                    .push(format!("{recipient}/{message_id}:{new_text}"));
+                Ok(())
+            }
+        }
+
+        let channel = EditingChannel::default();
+        assert_eq!(
+            channel
+                .update_draft("bob", "msg_1", "partial")
+                .await
+                .unwrap(),
+            None
+        );
+        channel
+            .finalize_draft("bob", "msg_1", "final text")
+            .await
+            .unwrap();
+        assert_eq!(
+            *channel.edits.lock().unwrap(),
+            ["bob/msg_1:partial", "bob/msg_1:final text"]
+        );
     }
 
     #[tokio::test]
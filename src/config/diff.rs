@@ -0,0 +1,191 @@
+//! `zeroclaw config diff`: show only the settings that differ from defaults.
+
+use super::Config;
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// Placeholder used for sensitive values, matching the `Config` `Debug` impl.
+pub const REDACTED: &str = "***REDACTED***";
+
+/// One differing leaf, addressed by its dotted TOML path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigDiffEntry {
+    pub path: String,
+    /// Default value, or `None` when the key has no default (e.g. a map entry).
+    pub default: Option<Value>,
+    /// Current value, or `None` when a default key is absent from the config.
+    pub current: Option<Value>,
+}
+
+/// Compare `config` against `Config::default()`, redacting sensitive values.
+pub fn diff_against_defaults(config: &Config) -> Result<Vec<ConfigDiffEntry>> {
+    let current = serde_json::to_value(config).context("failed to serialize current config")?;
+    let defaults =
+        serde_json::to_value(Config::default()).context("failed to serialize default config")?;
+
+    let mut entries = Vec::new();
+    diff_values("", Some(&defaults), Some(&current), &mut entries);
+    Ok(entries)
+}
+
+fn diff_values(
+    path: &str,
+    default: Option<&Value>,
+    current: Option<&Value>,
+    out: &mut Vec<ConfigDiffEntry>,
+) {
+    if default == current {
+        return;
+    }
+
+    if let (Some(Value::Object(default_map)), Some(Value::Object(current_map))) = (default, current)
+    {
+        let mut keys: Vec<&String> = default_map.keys().chain(current_map.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        for key in keys {
+            let child = if path.is_empty() {
+                key.clone()
+            } else {
+                format!("{path}.{key}")
+            };
+            diff_values(&child, default_map.get(key), current_map.get(key), out);
+        }
+        return;
+    }
+
+    let sensitive = is_sensitive_path(path);
+    out.push(ConfigDiffEntry {
+        path: path.to_string(),
+        default: default.map(|value| redact_value(value, sensitive)),
+        current: current.map(|value| redact_value(value, sensitive)),
+    });
+}
+
+fn is_sensitive_path(path: &str) -> bool {
+    path.split('.').any(is_sensitive_key)
+}
+
+/// Credential-bearing keys: `*_secret`, `*_password`, `*_token(s)`,
+/// `*_key(s)` (but not `max_tokens`-style limits), and database URLs.
+fn is_sensitive_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    if key == "db_url" {
+        return true;
+    }
+    let words: Vec<&str> = key.split('_').collect();
+    if words
+        .iter()
+        .any(|word| matches!(*word, "secret" | "password" | "credential" | "credentials"))
+    {
+        return true;
+    }
+    !words.contains(&"max") && matches!(words.last(), Some(&("token" | "tokens" | "key" | "keys")))
+}
+
+/// Replace sensitive leaves with [`REDACTED`]; nested objects are redacted
+/// key-by-key so non-secret siblings (e.g. a new channel's allowlist) stay
+/// visible.
+fn redact_value(value: &Value, sensitive: bool) -> Value {
+    match value {
+        Value::Null => Value::Null,
+        Value::Object(map) if !sensitive => Value::Object(
+            map.iter()
+                .map(|(key, child)| (key.clone(), redact_value(child, is_sensitive_key(key))))
+                .collect(),
+        ),
+        Value::Array(items) if !sensitive => {
+            Value::Array(items.iter().map(|item| redact_value(item, false)).collect())
+        }
+        _ if sensitive => Value::String(REDACTED.to_string()),
+        other => other.clone(),
+    }
+}
+
+/// Render entries as `path: default -> current` lines.
+pub fn render_diff(entries: &[ConfigDiffEntry]) -> String {
+    let render = |value: &Option<Value>| match value {
+        Some(value) => value.to_string(),
+        None => "(unset)".to_string(),
+    };
+
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&format!(
+            "{}: {} -> {}\n",
+            entry.path,
+            render(&entry.default),
+            render(&entry.current)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_reports_only_changed_fields() {
+        let mut config = Config::default();
+        config.default_provider = Some("anthropic".into());
+        config.default_model = Some("claude-sonnet-4-6".into());
+
+        let entries = diff_against_defaults(&config).unwrap();
+        let paths: Vec<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+
+        assert!(paths.contains(&"default_provider"));
+        assert!(paths.contains(&"default_model"));
+        assert!(!paths.iter().any(|p| p.starts_with("gateway")));
+        assert!(!paths.iter().any(|p| p.starts_with("memory")));
+
+        let provider = entries
+            .iter()
+            .find(|e| e.path == "default_provider")
+            .unwrap();
+        assert_eq!(provider.current, Some(Value::String("anthropic".into())));
+    }
+
+    #[test]
+    fn sensitive_keys_exclude_limits() {
+        for key in [
+            "api_key",
+            "bot_token",
+            "paired_tokens",
+            "app_secret",
+            "db_url",
+        ] {
+            assert!(is_sensitive_key(key), "{key}");
+        }
+        for key in [
+            "max_tokens",
+            "rate_limit_max_keys",
+            "token_ttl_secs",
+            "allowed_pubkeys",
+        ] {
+            assert!(!is_sensitive_key(key), "{key}");
+        }
+    }
+
+    #[test]
+    fn diff_of_defaults_is_empty() {
+        assert!(diff_against_defaults(&Config::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn diff_redacts_secrets() {
+        let mut config = Config::default();
+        config.api_key = Some("sk-live-credential".into());
+        config.gateway.paired_tokens = vec!["zc_0123456789abcdef".into()];
+
+        let entries = diff_against_defaults(&config).unwrap();
+        let rendered = render_diff(&entries);
+
+        assert!(rendered.contains("api_key"));
+        assert!(rendered.contains(REDACTED));
+        assert!(!rendered.contains("sk-live-credential"));
+        assert!(!rendered.contains("zc_0123456789abcdef"));
+    }
+}
@@ -0,0 +1,496 @@
+//! JSON Schema export for `config.toml` and schema-driven hints for config
+//! errors.
+//!
+//! `zeroclaw config schema [--path <a.b>]` prints [`config_schema`] or the
+//! section at a dotted path, with the `$defs` it references, so the output
+//! can be handed to taplo / Even Better TOML on its own. When `config.toml`
+//! fails to load, [`parse_error_hint`] turns the failing TOML position into
+//! a schema path and lists what that section expects; [`suggest_key`] offers
+//! the closest known key for typos.
+
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+use super::Config;
+
+/// Most fields listed in an error hint before pointing at `config schema`.
+const MAX_HINT_FIELDS: usize = 25;
+
+/// The full schema for [`Config`] as JSON.
+pub fn config_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(Config)).unwrap_or(Value::Null)
+}
+
+/// The schema of the section at a dotted `path` (`skills`,
+/// `channels_config.telegram`, `mcp.servers.0`), as a standalone schema that
+/// carries the `$defs` it references. An empty path is the full schema.
+pub fn section_schema(root: &Value, path: &str) -> Result<Value> {
+    let segments: Vec<&str> = path.split('.').filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        return Ok(root.clone());
+    }
+    let Some(section) = navigate(root, &segments) else {
+        let owned: Vec<String> = segments.iter().map(|s| (*s).to_string()).collect();
+        match suggest_key(root, &owned) {
+            Some(close) => bail!("No config section at `{path}` (did you mean `{close}`?)"),
+            None => bail!("No config section at `{path}`"),
+        }
+    };
+
+    let mut standalone = match section {
+        Value::Object(map) => map.clone(),
+        other => {
+            let mut map = Map::new();
+            map.insert("const".into(), other.clone());
+            map
+        }
+    };
+    if let Some(dialect) = root.get("$schema") {
+        standalone.insert("$schema".into(), dialect.clone());
+    }
+    let defs = referenced_defs(root, section);
+    if !defs.is_empty() {
+        standalone.insert("$defs".into(), Value::Object(defs));
+    }
+    Ok(Value::Object(standalone))
+}
+
+/// The closest known key to the last segment of `path`, looked up among the
+/// properties of its parent section. `None` when nothing is close enough.
+pub fn suggest_key(root: &Value, path: &[String]) -> Option<String> {
+    let (key, parent) = path.split_last()?;
+    let parent: Vec<&str> = parent.iter().map(String::as_str).collect();
+    let section = navigate(root, &parent)?;
+    closest(key, &property_names(section))
+}
+
+/// Text listing what the section at `path` expects: one line per field with
+/// its type, whether it is required, its default, and the first line of its
+/// doc comment.
+pub fn describe_section(root: &Value, path: &[String]) -> Option<String> {
+    let segments: Vec<&str> = path.iter().map(String::as_str).collect();
+    let mut section = navigate(root, &segments)?;
+    // `[[table]]` sections describe the array's elements.
+    if section.get("properties").is_none() {
+        section = resolve(root, section.get("items")?);
+    }
+    let properties = section.get("properties")?.as_object()?;
+    let required: BTreeSet<&str> = section
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let label = if path.is_empty() {
+        "config.toml".to_string()
+    } else {
+        format!("[{}]", path.join("."))
+    };
+    let mut text = format!("{label} expects:");
+    for (name, field) in properties.iter().take(MAX_HINT_FIELDS) {
+        let mut line = format!("\n  {name}: {}", type_label(root, field));
+        if required.contains(name.as_str()) {
+            line.push_str(", required");
+        } else if let Some(default) = field.get("default") {
+            line.push_str(&format!(", default {default}"));
+        }
+        if let Some(doc) = field
+            .get("description")
+            .and_then(Value::as_str)
+            .and_then(|doc| doc.lines().next())
+        {
+            line.push_str(&format!(" — {doc}"));
+        }
+        text.push_str(&line);
+    }
+    if properties.len() > MAX_HINT_FIELDS {
+        text.push_str(&format!(
+            "\n  … {} more (see `zeroclaw config schema --path {}`)",
+            properties.len() - MAX_HINT_FIELDS,
+            path.join(".")
+        ));
+    }
+    Some(text)
+}
+
+/// Hint to print under a config parse error: what the failing section
+/// expects, and a "did you mean" for a misspelled key. `offset` is the byte
+/// position the TOML error points at. Empty when there is nothing to add.
+pub fn parse_error_hint(root: &Value, contents: &str, message: &str, offset: usize) -> String {
+    let (table, key) = toml_path_at(contents, offset);
+    let mut hint = String::new();
+
+    // `unknown field `x`, expected ...` names the key itself.
+    let misspelled = backticked_after(message, "unknown field").or_else(|| key.clone());
+    if let Some(misspelled) = misspelled {
+        let mut path = table.clone();
+        path.push(misspelled.clone());
+        if let Some(close) = suggest_key(root, &path) {
+            if close != misspelled {
+                hint.push_str(&format!(
+                    "did you mean `{close}` instead of `{misspelled}`?\n"
+                ));
+            }
+        }
+    }
+
+    // Describe the innermost section of the failing path the schema knows.
+    let mut section = table;
+    if let Some(key) = key {
+        section.push(key);
+    }
+    while !section.is_empty() {
+        if let Some(text) = describe_section(root, &section) {
+            hint.push_str(&text);
+            break;
+        }
+        section.pop();
+    }
+    hint
+}
+
+/// The `[table]` header in force at `offset` and the key on that line, if
+/// the line is a `key = value` pair.
+fn toml_path_at(contents: &str, offset: usize) -> (Vec<String>, Option<String>) {
+    let offset = offset.min(contents.len());
+    let mut table = Vec::new();
+    let mut key = None;
+    let mut start = 0;
+    for line in contents.split_inclusive('\n') {
+        let end = start + line.len();
+        let trimmed = line.trim();
+        if let Some(header) = trimmed
+            .strip_prefix("[[")
+            .and_then(|rest| rest.split("]]").next())
+            .or_else(|| {
+                trimmed
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.split(']').next())
+            })
+        {
+            table = split_dotted(header);
+            key = None;
+        } else if offset < end {
+            key = trimmed
+                .split_once('=')
+                .map(|(name, _)| split_dotted(name))
+                .and_then(|mut dotted| {
+                    let last = dotted.pop()?;
+                    table.extend(dotted);
+                    Some(last)
+                });
+        }
+        if offset < end {
+            break;
+        }
+        start = end;
+    }
+    (table, key)
+}
+
+fn split_dotted(path: &str) -> Vec<String> {
+    path.split('.')
+        .map(|part| part.trim().trim_matches('"').trim_matches('\'').to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn backticked_after(message: &str, marker: &str) -> Option<String> {
+    let rest = &message[message.find(marker)? + marker.len()..];
+    let rest = &rest[rest.find('`')? + 1..];
+    Some(rest[..rest.find('`')?].to_string())
+}
+
+/// Walk `segments` down from the root, through `$ref`s, `Option` wrappers,
+/// map values and array items.
+fn navigate<'a>(root: &'a Value, segments: &[&str]) -> Option<&'a Value> {
+    let mut node = resolve(root, root);
+    for segment in segments {
+        if segment.parse::<usize>().is_ok() {
+            node = resolve(root, node.get("items")?);
+            continue;
+        }
+        // A key under a `[[table]]` path names a field of its elements.
+        if node.get("properties").is_none() {
+            if let Some(items) = node.get("items") {
+                node = resolve(root, items);
+            }
+        }
+        let child = node
+            .get("properties")
+            .and_then(|properties| properties.get(*segment))
+            .or_else(|| {
+                node.get("additionalProperties")
+                    .filter(|value| value.is_object())
+            })?;
+        node = resolve(root, child);
+    }
+    Some(node)
+}
+
+/// Follow `$ref` and unwrap `anyOf`/`oneOf`/`allOf` down to the first
+/// non-null variant.
+fn resolve<'a>(root: &'a Value, mut node: &'a Value) -> &'a Value {
+    for _ in 0..32 {
+        if let Some(name) = node
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        {
+            match root.get("$defs").and_then(|defs| defs.get(name)) {
+                Some(target) => {
+                    node = target;
+                    continue;
+                }
+                None => return node,
+            }
+        }
+        let variant = ["anyOf", "oneOf", "allOf"]
+            .iter()
+            .filter_map(|keyword| node.get(*keyword).and_then(Value::as_array))
+            .flatten()
+            .find(|variant| variant.get("type").and_then(Value::as_str) != Some("null"));
+        match variant {
+            Some(variant) if node.get("properties").is_none() => node = variant,
+            _ => return node,
+        }
+    }
+    node
+}
+
+fn property_names(section: &Value) -> Vec<String> {
+    section
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+fn type_label(root: &Value, field: &Value) -> String {
+    if let Some(name) = field
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+    {
+        return match resolve(root, field).get("enum").and_then(Value::as_array) {
+            Some(values) => values
+                .iter()
+                .map(Value::to_string)
+                .collect::<Vec<_>>()
+                .join(" | "),
+            None => name.to_string(),
+        };
+    }
+    if let Some(variants) = ["anyOf", "oneOf"]
+        .iter()
+        .find_map(|keyword| field.get(*keyword).and_then(Value::as_array))
+    {
+        return variants
+            .iter()
+            .map(|variant| type_label(root, variant))
+            .collect::<Vec<_>>()
+            .join(" | ");
+    }
+    match field.get("type") {
+        Some(Value::String(kind)) if kind == "array" => match field.get("items") {
+            Some(items) => format!("array of {}", type_label(root, items)),
+            None => "array".into(),
+        },
+        Some(Value::String(kind)) => kind.clone(),
+        Some(Value::Array(kinds)) => kinds
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        _ => "any".into(),
+    }
+}
+
+/// Every `$defs` entry `node` references, transitively.
+fn referenced_defs(root: &Value, node: &Value) -> Map<String, Value> {
+    let Some(all) = root.get("$defs").and_then(Value::as_object) else {
+        return Map::new();
+    };
+    let mut found = Map::new();
+    let mut pending = Vec::new();
+    collect_refs(node, &mut pending);
+    while let Some(name) = pending.pop() {
+        if found.contains_key(&name) {
+            continue;
+        }
+        if let Some(def) = all.get(&name) {
+            collect_refs(def, &mut pending);
+            found.insert(name, def.clone());
+        }
+    }
+    found
+}
+
+fn collect_refs(node: &Value, out: &mut Vec<String>) {
+    match node {
+        Value::Object(map) => {
+            if let Some(name) = map
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| reference.strip_prefix("#/$defs/"))
+            {
+                out.push(name.to_string());
+            }
+            map.values().for_each(|value| collect_refs(value, out));
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_refs(value, out)),
+        _ => {}
+    }
+}
+
+/// The candidate within edit distance of `key` (a third of its length, at
+/// least 1), nearest first.
+fn closest(key: &str, candidates: &[String]) -> Option<String> {
+    let limit = (key.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// Edit distance over chars, counting an adjacent swap (`prot` → `port`)
+/// as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let mut best = (previous[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(previous[j] + usize::from(ca != cb));
+            if i > 0 && j > 0 && *ca == b[j - 1] && a[i - 1] == *cb {
+                best = best.min(before_previous[j - 1] + 1);
+            }
+            current[j + 1] = best;
+        }
+        before_previous = std::mem::replace(&mut previous, current);
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(dotted: &str) -> Vec<String> {
+        split_dotted(dotted)
+    }
+
+    #[test]
+    fn schema_generation_is_deterministic() {
+        let first = serde_json::to_string_pretty(&config_schema()).unwrap();
+        let second = serde_json::to_string_pretty(&config_schema()).unwrap();
+        assert_eq!(first, second);
+        assert!(first.contains("\"skills\""));
+    }
+
+    #[test]
+    fn section_paths_resolve_nested_definitions() {
+        let root = config_schema();
+
+        let skills = section_schema(&root, "skills").unwrap();
+        assert!(
+            skills["properties"]["allow_scripts"].is_object(),
+            "{skills}"
+        );
+
+        // Option<TelegramConfig> under channels_config resolves to the struct,
+        // and the standalone schema carries the $defs it still references.
+        let telegram = section_schema(&root, "channels_config.telegram").unwrap();
+        assert!(
+            telegram["properties"]["bot_token"].is_object(),
+            "{telegram}"
+        );
+        assert!(telegram["required"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::from("bot_token")));
+        assert_eq!(telegram["$schema"], root["$schema"]);
+        let mut refs = Vec::new();
+        collect_refs(&telegram, &mut refs);
+        for name in refs {
+            assert!(
+                telegram["$defs"].get(&name).is_some(),
+                "missing $defs/{name}"
+            );
+        }
+
+        let err = section_schema(&root, "channels_config.telegarm").unwrap_err();
+        assert!(
+            err.to_string().contains("did you mean `telegram`?"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn typoed_keys_get_a_suggestion() {
+        let root = config_schema();
+        assert_eq!(
+            suggest_key(&root, &path("gateway.prot")).as_deref(),
+            Some("port")
+        );
+        assert_eq!(
+            suggest_key(&root, &path("default_provder")).as_deref(),
+            Some("default_provider")
+        );
+        assert_eq!(
+            suggest_key(&root, &path("gateway.completely_unrelated")),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_errors_get_the_section_fragment() {
+        let root = config_schema();
+        let contents =
+            "default_temperature = 0.7\n\n[channels_config.telegram]\nallowed_users = []\n";
+        let offset = contents.find("[channels_config").unwrap();
+        let hint = parse_error_hint(&root, contents, "missing field `bot_token`", offset);
+        assert!(
+            hint.starts_with("[channels_config.telegram] expects:"),
+            "{hint}"
+        );
+        assert!(hint.contains("\n  bot_token: string, required"), "{hint}");
+
+        let contents = "[gateway]\nport = \"x\"\n";
+        let hint = parse_error_hint(
+            &root,
+            contents,
+            "invalid type: string \"x\", expected u16",
+            contents.find("\"x\"").unwrap(),
+        );
+        assert!(hint.starts_with("[gateway] expects:"), "{hint}");
+        assert!(hint.contains("\n  port: integer"), "{hint}");
+
+        let contents = "[autonomy]\nlevel = \"full\"\nworkspace_olny = true\n";
+        let hint = parse_error_hint(
+            &root,
+            contents,
+            "unknown field `workspace_olny`",
+            contents.find("workspace_olny").unwrap(),
+        );
+        assert!(
+            hint.starts_with("did you mean `workspace_only` instead of `workspace_olny`?"),
+            "{hint}"
+        );
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("port", "port"), 0);
+        assert_eq!(edit_distance("prot", "port"), 1);
+        assert_eq!(edit_distance("comand", "command"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
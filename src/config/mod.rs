@@ -1,28 +1,38 @@
+pub mod diff;
+pub mod json_schema;
 pub mod schema;
+pub mod secrets;
+pub mod timezone;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use schema::{
     apply_runtime_proxy_to_builder, build_runtime_proxy_client,
-    build_runtime_proxy_client_with_timeouts, runtime_proxy_config, set_runtime_proxy_config,
-    AgentConfig, AgentsIpcConfig, AuditConfig, AutonomyConfig, BrowserComputerUseConfig,
-    BrowserConfig, BuiltinHooksConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config,
+    build_runtime_proxy_client_with_timeouts, parse_cidr_notation, runtime_proxy_config,
+    set_runtime_proxy_config,
+    AgentConfig, AgentsIpcConfig, AuditConfig, AutonomyConfig, BannedPhraseAction,
+    BannedPhraseRule, BrowserComputerUseConfig,
+    BrowserConfig, BuiltinHooksConfig, ChannelFiltersConfig, ChannelsConfig, ClassificationRule, ComposioConfig, Config,
     CoordinationConfig, CostConfig, CronConfig, DelegateAgentConfig, DiscordConfig,
     EconomicConfig, EconomicTokenPricing,
     DockerRuntimeConfig, EmbeddingRouteConfig, EstopConfig, FeishuConfig, GatewayConfig,
+    GatewayErrorFormat,
     GroupReplyConfig, GroupReplyMode, HardwareConfig, HardwareTransport, HeartbeatConfig,
     HooksConfig, HttpRequestConfig, IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig,
-    MemoryConfig, ModelRouteConfig, MultimodalConfig, NextcloudTalkConfig,
-    NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OtpChallengeDelivery, OtpConfig,
-    OtpMethod, PeripheralBoardConfig, PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig,
-    PluginsConfig, ProviderConfig, ProxyConfig, ProxyScope, QdrantConfig,
-    QueryClassificationConfig, ReliabilityConfig, ResearchPhaseConfig, ResearchTrigger,
+    MemoryClassificationConfig, MemoryClassificationRule, MemoryConfig, ModelRouteConfig,
+    MultimodalConfig, NextcloudTalkConfig,
+    NonCliNaturalLanguageApprovalMode, ObservabilityConfig, OllamaProviderConfig,
+    OtpChallengeDelivery, OtpConfig,
+    OtpMethod, OutboundHooksConfig, OutboundRedactionRule, PeripheralBoardConfig,
+    PeripheralsConfig, PerplexityFilterConfig, PluginEntryConfig,
+    PluginsConfig, PromptProfile, PromptSectionsConfig, ProviderConfig, ProxyConfig, ProxyScope, QdrantConfig,
+    QueryClassificationConfig, ReliabilityConfig, ReplyStyle, ResearchPhaseConfig, ResearchTrigger,
     ResourceLimitsConfig, RuntimeConfig, SandboxBackend, SandboxConfig, SchedulerConfig,
-    SecretsConfig, SecurityConfig, SecurityRoleConfig, SkillsConfig, SkillsPromptInjectionMode,
-    SlackConfig, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
+    SecretsConfig, SecurityConfig, SecurityRoleConfig, SkillsConfig, SkillsIncludeDeprecated, SkillsPromptInjectionMode,
+    SlackConfig, StartupBanner, StorageConfig, StorageProviderConfig, StorageProviderSection, StreamMode,
     SyscallAnomalyConfig, TelegramConfig, TranscriptionConfig, TunnelConfig, UrlAccessConfig,
     WasmCapabilityEscalationMode, WasmConfig, WasmModuleHashPolicy, WasmRuntimeConfig,
-    WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
+    WasmSchedulePriority, WasmSecurityConfig, WebFetchConfig, WebSearchConfig, WebhookConfig,
 };
 
 pub fn name_and_presence<T: traits::ChannelConfig>(channel: Option<&T>) -> (&'static str, bool) {
@@ -62,6 +72,8 @@ mod tests {
             listen_to_bots: false,
             mention_only: false,
             group_reply: None,
+            slash_commands: vec![],
+            reply_style: None,
         };
 
         let lark = LarkConfig {
@@ -43,6 +43,8 @@ const SUPPORTED_PROXY_SERVICE_KEYS: &[&str] = &[
     "memory.embeddings",
     "tunnel.custom",
     "transcription.groq",
+    "skills.git",
+    "skills.download",
 ];
 
 const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
@@ -52,8 +54,12 @@ const SUPPORTED_PROXY_SERVICE_SELECTORS: &[&str] = &[
     "memory.*",
     "tunnel.*",
     "transcription.*",
+    "skills.*",
 ];
 
+/// Proxy variables set on (or stripped from) spawned subprocesses.
+const PROXY_ENV_KEYS: &[&str] = &["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY", "NO_PROXY"];
+
 static RUNTIME_PROXY_CONFIG: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
 static RUNTIME_PROXY_CLIENT_CACHE: OnceLock<RwLock<HashMap<String, reqwest::Client>>> =
     OnceLock::new();
@@ -308,7 +314,7 @@ pub struct ModelProviderConfig {
 }
 
 /// Provider behavior overrides (`[provider]` section).
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderConfig {
     /// Optional reasoning level override for providers that support explicit levels
     /// (e.g. OpenAI Codex `/responses` reasoning effort).
@@ -328,6 +334,49 @@ pub struct ProviderConfig {
     /// Existing configs that omit `provider.transport` remain valid and fall back to defaults.
     #[serde(default)]
     pub transport: Option<String>,
+    /// Emit prompt-caching breakpoints for providers that support them (Anthropic
+    /// `cache_control`). The stable part of the system prompt (identity, tools,
+    /// skills, workspace files) is cached; the per-turn date/runtime tail is not.
+    /// Default: `true`.
+    #[serde(default = "default_true")]
+    pub prompt_caching: bool,
+    /// Ollama-only request options (`[provider.ollama]`).
+    #[serde(default)]
+    pub ollama: OllamaProviderConfig,
+}
+
+impl Default for ProviderConfig {
+    fn default() -> Self {
+        Self {
+            reasoning_level: None,
+            transport: None,
+            prompt_caching: true,
+            ollama: OllamaProviderConfig::default(),
+        }
+    }
+}
+
+/// Ollama request options (`[provider.ollama]`).
+///
+/// Only the `ollama` provider reads these. Unset fields are left out of the
+/// request entirely, so older Ollama versions see the same payload as before.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct OllamaProviderConfig {
+    /// How long Ollama keeps the model loaded after a request (e.g. `"10m"`,
+    /// `"-1"` to keep it loaded). Avoids a cold start on every message.
+    #[serde(default)]
+    pub keep_alive: Option<String>,
+    /// Context window in tokens (`options.num_ctx`). Ollama falls back to a
+    /// small default (2048 on older versions), which truncates long prompts.
+    #[serde(default)]
+    pub num_ctx: Option<u32>,
+    /// Maximum tokens to generate (`options.num_predict`; `-1` = no limit).
+    #[serde(default)]
+    pub num_predict: Option<i32>,
+    /// Raise `num_ctx` for a request, up to the model maximum reported by
+    /// `/api/show`, when the prompt likely would not fit. Default: `false`.
+    #[serde(default)]
+    pub auto_num_ctx: bool,
 }
 
 // ── Delegate Agents ──────────────────────────────────────────────
@@ -432,7 +481,7 @@ impl std::fmt::Debug for Config {
             .field("delegate_agents", &delegate_agent_ids)
             .field("cli_channel_enabled", &self.channels_config.cli)
             .field("enabled_channels_count", &enabled_channel_count)
-            .field("sensitive_sections", &"***REDACTED***")
+            .field("sensitive_sections", &super::diff::REDACTED)
             .finish_non_exhaustive()
     }
 }
@@ -718,8 +767,12 @@ impl Default for CoordinationConfig {
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AgentConfig {
     /// When true: bootstrap_max_chars=6000, rag_chunk_limit=2. Use for 13B or smaller models.
+    /// Which sections are injected is controlled by `prompt_sections`.
     #[serde(default)]
     pub compact_context: bool,
+    /// System prompt section toggles (`[agent.prompt_sections]`).
+    #[serde(default)]
+    pub prompt_sections: PromptSectionsConfig,
     /// Maximum tool-call loop turns per user message. Default: `20`.
     /// Setting to `0` falls back to the safe default of `20`.
     #[serde(default = "default_agent_max_tool_iterations")]
@@ -748,6 +801,24 @@ pub struct AgentConfig {
     /// Set to `0` to disable. Default: `3`.
     #[serde(default = "default_loop_detection_failure_streak")]
     pub loop_detection_failure_streak: usize,
+    /// Maintenance mode: answer inbound channel and gateway messages with
+    /// `maintenance_reply` and make no provider calls. Can also be toggled at
+    /// runtime with `zeroclaw maintenance on|off`. Default: `false`.
+    #[serde(default)]
+    pub maintenance: bool,
+    /// Canned maintenance reply. `{message}` is replaced by the operator note
+    /// given to `zeroclaw maintenance on --message` (or removed).
+    #[serde(default = "default_agent_maintenance_reply")]
+    pub maintenance_reply: String,
+    /// Queue channel messages received during maintenance and process them
+    /// once it ends. Default: `false`.
+    #[serde(default)]
+    pub queue_during_maintenance: bool,
+    /// IANA timezone (e.g. `"Asia/Ho_Chi_Minh"`) for the prompt's Date &
+    /// Time section, turn timestamps, skill `{{now}}` and cron expressions
+    /// without their own `tz`. Default: the host's zone.
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 fn default_agent_max_tool_iterations() -> usize {
@@ -774,10 +845,15 @@ fn default_loop_detection_failure_streak() -> usize {
     3
 }
 
+fn default_agent_maintenance_reply() -> String {
+    "I'm down for maintenance right now and can't answer yet. {message}".into()
+}
+
 impl Default for AgentConfig {
     fn default() -> Self {
         Self {
             compact_context: true,
+            prompt_sections: PromptSectionsConfig::default(),
             max_tool_iterations: default_agent_max_tool_iterations(),
             max_history_messages: default_agent_max_history_messages(),
             parallel_tools: false,
@@ -785,10 +861,104 @@ impl Default for AgentConfig {
             loop_detection_no_progress_threshold: default_loop_detection_no_progress_threshold(),
             loop_detection_ping_pong_cycles: default_loop_detection_ping_pong_cycles(),
             loop_detection_failure_streak: default_loop_detection_failure_streak(),
+            maintenance: false,
+            maintenance_reply: default_agent_maintenance_reply(),
+            queue_during_maintenance: false,
+            timezone: None,
+        }
+    }
+}
+
+impl AgentConfig {
+    /// Prompt sizing for the configured context mode: `compact_context` keeps
+    /// the section toggles as configured and tightens the char limits.
+    pub fn prompt_profile(&self) -> PromptProfile {
+        PromptProfile {
+            sections: self.prompt_sections.clone(),
+            bootstrap_max_chars: self.compact_context.then_some(6000),
+            rag_chunk_limit: if self.compact_context { 2 } else { 5 },
+        }
+    }
+}
+
+/// Resolved prompt settings from [`AgentConfig::prompt_profile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptProfile {
+    pub sections: PromptSectionsConfig,
+    /// Per-file bootstrap limit; `None` uses the builder default.
+    pub bootstrap_max_chars: Option<usize>,
+    /// Memory chunks recalled into context per turn.
+    pub rag_chunk_limit: usize,
+}
+
+/// System prompt section toggles (`[agent.prompt_sections]`).
+///
+/// Every section is on by default. Turning `safety` off also requires
+/// `i_understand_no_safety = true`; without it the section stays on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct PromptSectionsConfig {
+    /// Tool list (and the hardware note when hardware tools are present).
+    #[serde(default = "default_true")]
+    pub tools: bool,
+    /// Safety guardrails.
+    #[serde(default = "default_true")]
+    pub safety: bool,
+    /// Acknowledgment required for `safety = false` to take effect.
+    #[serde(default)]
+    pub i_understand_no_safety: bool,
+    /// Skill instructions.
+    #[serde(default = "default_true")]
+    pub skills: bool,
+    /// Working directory line.
+    #[serde(default = "default_true")]
+    pub workspace: bool,
+    /// Bootstrap files (AGENTS.md, SOUL.md, ...) or the AIEOS identity.
+    #[serde(default = "default_true")]
+    pub bootstrap: bool,
+    /// Inject only these bootstrap files (e.g. `["IDENTITY.md"]`). Files left
+    /// out get no missing-file marker. Omit to inject all of them.
+    #[serde(default)]
+    pub bootstrap_files: Option<Vec<String>>,
+    /// Current date and time.
+    #[serde(default = "default_true")]
+    pub datetime: bool,
+    /// Host, OS and model line.
+    #[serde(default = "default_true")]
+    pub runtime: bool,
+}
+
+impl Default for PromptSectionsConfig {
+    fn default() -> Self {
+        Self {
+            tools: true,
+            safety: true,
+            i_understand_no_safety: false,
+            skills: true,
+            workspace: true,
+            bootstrap: true,
+            bootstrap_files: None,
+            datetime: true,
+            runtime: true,
         }
     }
 }
 
+impl PromptSectionsConfig {
+    /// Safety is injected unless it is off *and* the acknowledgment is set.
+    pub fn safety_enabled(&self) -> bool {
+        self.safety || !self.i_understand_no_safety
+    }
+
+    /// Whether bootstrap file `filename` is injected.
+    pub fn bootstrap_file_enabled(&self, filename: &str) -> bool {
+        self.bootstrap
+            && self
+                .bootstrap_files
+                .as_ref()
+                .is_none_or(|files| files.iter().any(|file| file.trim() == filename))
+    }
+}
+
 /// Skills loading configuration (`[skills]` section).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
@@ -800,6 +970,19 @@ pub enum SkillsPromptInjectionMode {
     Compact,
 }
 
+/// How deprecated skills appear in the system prompt (`[skills] include_deprecated`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SkillsIncludeDeprecated {
+    /// Leave deprecated skills out of the prompt.
+    Exclude,
+    /// Include them with a short deprecation note naming the replacement.
+    #[default]
+    Note,
+    /// Include them as if they were not deprecated.
+    Full,
+}
+
 fn parse_skills_prompt_injection_mode(raw: &str) -> Option<SkillsPromptInjectionMode> {
     match raw.trim().to_ascii_lowercase().as_str() {
         "full" => Some(SkillsPromptInjectionMode::Full),
@@ -809,7 +992,7 @@ fn parse_skills_prompt_injection_mode(raw: &str) -> Option<SkillsPromptInjection
 }
 
 /// Skills loading configuration (`[skills]` section).
-#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SkillsConfig {
     /// Enable loading and syncing the community open-skills repository.
     /// Default: `false` (opt-in).
@@ -823,15 +1006,69 @@ pub struct SkillsConfig {
     /// Default: `false` (secure by default).
     #[serde(default)]
     pub allow_scripts: bool,
+    /// Lowest audit finding severity (`low`, `medium`, `high`, `critical`) that
+    /// rejects a skill at install and load time; findings below it only warn.
+    /// Default: `low` (any finding blocks). Path-traversal and symlink
+    /// findings block at every threshold. Also accepted as `audit_fail_threshold`.
+    #[serde(default, alias = "audit_fail_threshold")]
+    pub block_at_severity: crate::skills::AuditSeverity,
+    /// Refuse to install a skill that declares `[skill.capabilities]` until
+    /// `skill install --accept-capabilities` is given. Default: `false` (the
+    /// requested capabilities are printed and recorded).
+    #[serde(default)]
+    pub require_capability_consent: bool,
     /// Controls how skills are injected into the system prompt.
     /// `full` preserves legacy behavior. `compact` keeps context small and loads skills on demand.
     #[serde(default)]
     pub prompt_injection_mode: SkillsPromptInjectionMode,
+    /// How skills marked `deprecated = true` (in `SKILL.toml` or by the
+    /// registry) are injected: `exclude`, `note` or `full`. Default: `note`.
+    #[serde(default)]
+    pub include_deprecated: SkillsIncludeDeprecated,
+    /// Bare tool name → skill that answers to it when several loaded skills
+    /// declare a tool with that name (`[skills.tool_aliases]`). Unpinned
+    /// colliding names are only reachable as `<skill>.<tool>`.
+    #[serde(default)]
+    pub tool_aliases: HashMap<String, String>,
+    /// Environment for each skill's tools (`[skills.env.<skill>]`): variable
+    /// name → value. A skill's tools only get the variables its `SKILL.toml`
+    /// lists in `required_env`, and only from its own table; values never
+    /// reach the prompt. Stored encrypted when `secrets.encrypt = true`.
+    #[serde(default)]
+    pub env: HashMap<String, HashMap<String, String>>,
     /// Optional ClawhHub API token for authenticated skill downloads.
     /// Obtain from https://clawhub.ai after signing in.
     /// Set via config: `clawhub_token = "..."` under `[skills]`.
+    /// Stored encrypted when `secrets.encrypt = true`.
     #[serde(default)]
     pub clawhub_token: Option<String>,
+    /// Redirects a skill download may follow (0..=10). Every hop must stay on
+    /// HTTPS, and where the download ends up is checked against the same
+    /// host rules as the original URL. Default: `3`.
+    #[serde(default = "default_skills_download_max_redirects")]
+    pub download_max_redirects: u32,
+}
+
+fn default_skills_download_max_redirects() -> u32 {
+    crate::skills::DEFAULT_DOWNLOAD_MAX_REDIRECTS
+}
+
+impl Default for SkillsConfig {
+    fn default() -> Self {
+        Self {
+            open_skills_enabled: false,
+            open_skills_dir: None,
+            allow_scripts: false,
+            block_at_severity: crate::skills::AuditSeverity::default(),
+            require_capability_consent: false,
+            prompt_injection_mode: SkillsPromptInjectionMode::default(),
+            include_deprecated: SkillsIncludeDeprecated::default(),
+            tool_aliases: HashMap::new(),
+            env: HashMap::new(),
+            clawhub_token: None,
+            download_max_redirects: default_skills_download_max_redirects(),
+        }
+    }
 }
 
 /// WASM plugin engine configuration (`[wasm]` section).
@@ -856,12 +1093,43 @@ pub struct WasmConfig {
     /// Default: the public ZeroMarket registry.
     #[serde(default = "default_registry_url")]
     pub registry_url: String,
+    /// Maximum WASM executions running at once, across skill tools, the
+    /// `wasm_module` tool and the `wasmtime` CLI. Further executions queue.
+    /// Default: `4`.
+    #[serde(default = "default_wasm_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Seconds a queued execution waits for a free slot before failing with
+    /// "tool execution queue full". Default: `30`.
+    #[serde(default = "default_wasm_queue_timeout_secs")]
+    pub queue_timeout_secs: u64,
+    /// Queue priority of executions started by cron jobs: `low` waits until
+    /// no interactive execution is queued, `normal` keeps plain arrival
+    /// order. Default: `low`.
+    #[serde(default)]
+    pub scheduled_priority: WasmSchedulePriority,
+}
+
+/// Queue priority of scheduler-triggered WASM executions (`[wasm].scheduled_priority`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum WasmSchedulePriority {
+    #[default]
+    Low,
+    Normal,
 }
 
 fn default_wasm_memory_limit_mb() -> u64 {
     64
 }
 
+fn default_wasm_max_concurrent() -> usize {
+    4
+}
+
+fn default_wasm_queue_timeout_secs() -> u64 {
+    30
+}
+
 fn default_wasm_fuel_limit() -> u64 {
     1_000_000_000
 }
@@ -877,6 +1145,9 @@ impl Default for WasmConfig {
             memory_limit_mb: default_wasm_memory_limit_mb(),
             fuel_limit: default_wasm_fuel_limit(),
             registry_url: default_registry_url(),
+            max_concurrent: default_wasm_max_concurrent(),
+            queue_timeout_secs: default_wasm_queue_timeout_secs(),
+            scheduled_priority: WasmSchedulePriority::default(),
         }
     }
 }
@@ -890,9 +1161,15 @@ pub struct MultimodalConfig {
     /// Maximum image payload size in MiB before base64 encoding.
     #[serde(default = "default_multimodal_max_image_size_mb")]
     pub max_image_size_mb: usize,
-    /// Allow fetching remote image URLs (http/https). Disabled by default.
+    /// Allow fetching remote `https://` image URLs. Private, loopback and
+    /// link-local destinations are always refused. Disabled by default.
     #[serde(default)]
     pub allow_remote_fetch: bool,
+    /// Downscale images whose width or height exceeds this many pixels
+    /// (aspect ratio preserved) before base64 encoding. Unset uses the
+    /// built-in 512px prompt budget.
+    #[serde(default)]
+    pub max_dimension_px: Option<u32>,
 }
 
 fn default_multimodal_max_images() -> usize {
@@ -910,6 +1187,11 @@ impl MultimodalConfig {
         let max_image_size_mb = self.max_image_size_mb.clamp(1, 20);
         (max_images, max_image_size_mb)
     }
+
+    /// Clamp the configured downscale bound to safe runtime bounds.
+    pub fn effective_max_dimension_px(&self) -> Option<u32> {
+        self.max_dimension_px.map(|px| px.clamp(64, 4096))
+    }
 }
 
 impl Default for MultimodalConfig {
@@ -918,6 +1200,7 @@ impl Default for MultimodalConfig {
             max_images: default_multimodal_max_images(),
             max_image_size_mb: default_multimodal_max_image_size_mb(),
             allow_remote_fetch: false,
+            max_dimension_px: None,
         }
     }
 }
@@ -1247,6 +1530,15 @@ pub struct GatewayConfig {
     /// Gateway host (default: 127.0.0.1)
     #[serde(default = "default_gateway_host")]
     pub host: String,
+
+    /// Listen on a Unix domain socket instead of `host`/`port`, as
+    /// `"unix:/run/zeroclaw/gateway.sock"` (Unix only). `--host` overrides it.
+    #[serde(default)]
+    pub listen: Option<String>,
+
+    /// Octal permissions of the `listen` socket file (default: `"0600"`).
+    #[serde(default = "default_gateway_socket_permissions")]
+    pub socket_permissions: String,
     /// Require pairing before accepting requests (default: true)
     #[serde(default = "default_true")]
     pub require_pairing: bool,
@@ -1257,6 +1549,18 @@ pub struct GatewayConfig {
     #[serde(default)]
     pub paired_tokens: Vec<String>,
 
+    /// Admin token for `GET /pair/tokens` and `DELETE /pair/tokens/{fingerprint}`,
+    /// sent as `X-Admin-Token`. Paired bearer tokens are never accepted there;
+    /// unset (default) disables both endpoints.
+    #[serde(default)]
+    pub admin_token: Option<String>,
+
+    /// Advertise the gateway as `_zeroclaw._tcp` over mDNS so LAN companion
+    /// apps can find it. Only takes effect on a non-loopback bind with
+    /// pairing required (default: false).
+    #[serde(default)]
+    pub advertise_mdns: bool,
+
     /// Max `/pair` requests per minute per client key.
     #[serde(default = "default_pair_rate_limit")]
     pub pair_rate_limit_per_minute: u32,
@@ -1282,11 +1586,60 @@ pub struct GatewayConfig {
     #[serde(default = "default_gateway_idempotency_max_keys")]
     pub idempotency_max_keys: usize,
 
+    /// Browser origins allowed to call the gateway cross-origin (exact
+    /// scheme+host+port match, e.g. `"https://dashboard.internal"`).
+    /// Empty (default) disables CORS; `"*"` allows any origin and must be set explicitly.
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// Seconds a client has to send request headers, and a handler has to
+    /// read the body and respond, before the request is aborted (default: 30).
+    #[serde(default = "default_gateway_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+
+    /// Maximum concurrent connections; extra connections get `503` (default: 256).
+    #[serde(default = "default_gateway_max_connections")]
+    pub max_connections: usize,
+
+    /// Maximum requests handled at once across all connections; extra
+    /// requests get `503` with `Retry-After` (default: 64).
+    #[serde(default = "default_gateway_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+
+    /// Hard cap on bytes read for one request (headers + body) before the
+    /// connection is closed with `413` (default: 2 MiB).
+    #[serde(default = "default_gateway_max_request_bytes")]
+    pub max_request_bytes: usize,
+
+    /// Hard cap on one `POST /webhook` agent-mode run before the tool calls
+    /// finished so far are returned with `504`. Kept at least a second below
+    /// `request_timeout_secs` (default: 25).
+    #[serde(default = "default_webhook_agent_timeout_secs")]
+    pub webhook_agent_timeout_secs: u64,
+
+    /// Shape of error bodies: `legacy` (default) keeps `"error"` as the
+    /// message string with `code` beside it; `structured` nests
+    /// `{"code", "message", "field"}` under `"error"`. `legacy` is kept for
+    /// one deprecation window and will then be dropped.
+    #[serde(default)]
+    pub error_format: GatewayErrorFormat,
+
     /// Node-control protocol scaffold (`[gateway.node_control]`).
     #[serde(default)]
     pub node_control: NodeControlConfig,
 }
 
+/// Shape of gateway error bodies (`[gateway] error_format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum GatewayErrorFormat {
+    /// `{"error": "<message>", "code": "...", "request_id": "..."}`.
+    #[default]
+    Legacy,
+    /// `{"error": {"code": "...", "message": "..."}, "request_id": "..."}`.
+    Structured,
+}
+
 /// Node-control scaffold settings under `[gateway.node_control]`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 pub struct NodeControlConfig {
@@ -1313,6 +1666,10 @@ fn default_gateway_host() -> String {
     "127.0.0.1".into()
 }
 
+fn default_gateway_socket_permissions() -> String {
+    "0600".into()
+}
+
 fn default_pair_rate_limit() -> u32 {
     10
 }
@@ -1333,6 +1690,26 @@ fn default_gateway_idempotency_max_keys() -> usize {
     10_000
 }
 
+fn default_gateway_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_webhook_agent_timeout_secs() -> u64 {
+    25
+}
+
+fn default_gateway_max_connections() -> usize {
+    256
+}
+
+fn default_gateway_max_concurrent_requests() -> usize {
+    64
+}
+
+fn default_gateway_max_request_bytes() -> usize {
+    2 * 1024 * 1024
+}
+
 fn default_true() -> bool {
     true
 }
@@ -1342,15 +1719,26 @@ impl Default for GatewayConfig {
         Self {
             port: default_gateway_port(),
             host: default_gateway_host(),
+            listen: None,
+            socket_permissions: default_gateway_socket_permissions(),
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: Vec::new(),
+            admin_token: None,
+            advertise_mdns: false,
             pair_rate_limit_per_minute: default_pair_rate_limit(),
             webhook_rate_limit_per_minute: default_webhook_rate_limit(),
             trust_forwarded_headers: false,
             rate_limit_max_keys: default_gateway_rate_limit_max_keys(),
             idempotency_ttl_secs: default_idempotency_ttl_secs(),
             idempotency_max_keys: default_gateway_idempotency_max_keys(),
+            cors_allowed_origins: Vec::new(),
+            request_timeout_secs: default_gateway_request_timeout_secs(),
+            max_connections: default_gateway_max_connections(),
+            max_concurrent_requests: default_gateway_max_concurrent_requests(),
+            max_request_bytes: default_gateway_max_request_bytes(),
+            webhook_agent_timeout_secs: default_webhook_agent_timeout_secs(),
+            error_format: GatewayErrorFormat::default(),
             node_control: NodeControlConfig::default(),
         }
     }
@@ -2062,6 +2450,61 @@ impl ProxyConfig {
         set_proxy_env_pair("NO_PROXY", no_proxy_joined.as_deref());
     }
 
+    /// Apply this proxy to a spawned subprocess (`git`, `curl`, ...).
+    ///
+    /// When the proxy applies to `service_key`, the configured URLs are set
+    /// explicitly on the child (upper- and lowercase, since curl only reads
+    /// lowercase `http_proxy`) and unset ones are removed. When
+    /// `scope = "services"` excludes the service, all proxy vars are removed so
+    /// a proxy inherited from the parent environment is not used. Otherwise
+    /// (disabled, or `scope = "environment"`) the child inherits the parent env.
+    pub fn apply_to_command(&self, cmd: &mut std::process::Command, service_key: &str) {
+        if self.should_apply_to_service(service_key) {
+            let no_proxy = {
+                let list = self.normalized_no_proxy();
+                (!list.is_empty()).then(|| list.join(","))
+            };
+            let values = [
+                normalize_proxy_url_option(self.http_proxy.as_deref()),
+                normalize_proxy_url_option(self.https_proxy.as_deref()),
+                normalize_proxy_url_option(self.all_proxy.as_deref()),
+                no_proxy,
+            ];
+            for (key, value) in PROXY_ENV_KEYS.iter().zip(values) {
+                let lowercase_key = key.to_ascii_lowercase();
+                match value {
+                    Some(value) => {
+                        cmd.env(key, &value);
+                        cmd.env(lowercase_key, value);
+                    }
+                    None => {
+                        cmd.env_remove(key);
+                        cmd.env_remove(lowercase_key);
+                    }
+                }
+            }
+        } else if self.enabled && self.scope == ProxyScope::Services {
+            for key in PROXY_ENV_KEYS {
+                cmd.env_remove(key);
+                cmd.env_remove(key.to_ascii_lowercase());
+            }
+        }
+    }
+
+    /// Value for git's `-c http.proxy=...` when spawning git for `service_key`.
+    ///
+    /// `Some(url)` forces the configured proxy, `Some("")` disables any proxy
+    /// from the user's gitconfig when the service is excluded, and `None`
+    /// leaves git's own configuration alone.
+    pub fn git_http_proxy(&self, service_key: &str) -> Option<String> {
+        if self.should_apply_to_service(service_key) {
+            return normalize_proxy_url_option(self.https_proxy.as_deref())
+                .or_else(|| normalize_proxy_url_option(self.all_proxy.as_deref()))
+                .or_else(|| normalize_proxy_url_option(self.http_proxy.as_deref()));
+        }
+        (self.enabled && self.scope == ProxyScope::Services).then(String::new)
+    }
+
     pub fn clear_process_env() {
         clear_proxy_env_pair("HTTP_PROXY");
         clear_proxy_env_pair("HTTPS_PROXY");
@@ -2165,7 +2608,8 @@ fn validate_proxy_url(field: &str, url: &str) -> Result<()> {
     Ok(())
 }
 
-fn parse_cidr_notation(raw: &str) -> Result<(IpAddr, u8)> {
+/// Parse `ip/prefix` CIDR notation, validating the prefix length for the family.
+pub fn parse_cidr_notation(raw: &str) -> Result<(IpAddr, u8)> {
     let (ip_raw, prefix_raw) = raw
         .trim()
         .split_once('/')
@@ -2455,6 +2899,88 @@ impl Default for QdrantConfig {
     }
 }
 
+/// Auto-save categorization (`[memory.classification]`).
+///
+/// Before a channel or gateway message is auto-saved, the rules below are
+/// checked in order and the first marker found picks the category. Messages no
+/// rule matches are stored as `conversation`, or `ephemeral` when shorter than
+/// `ephemeral_max_chars`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryClassificationConfig {
+    /// Categorize auto-saved messages. When false everything is `conversation`. Default: `true`.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Unmatched messages shorter than this many characters are `ephemeral`. Default: `60`.
+    #[serde(default = "default_ephemeral_max_chars")]
+    pub ephemeral_max_chars: usize,
+    /// Marker rules, checked in order. Setting this replaces the built-in table.
+    #[serde(default = "default_memory_classification_rules")]
+    pub rules: Vec<MemoryClassificationRule>,
+    /// Ask the channel's model to categorize messages no rule matched. Costs
+    /// one extra provider call per auto-saved message. Default: `false`.
+    #[serde(default)]
+    pub model_classifier: bool,
+}
+
+/// One `[[memory.classification.rules]]` entry.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct MemoryClassificationRule {
+    /// Target category: `fact`, `preference`, `task`, or any custom name.
+    pub category: String,
+    /// Case-insensitive phrases that mark a message, e.g. `"remember that"`.
+    #[serde(default)]
+    pub markers: Vec<String>,
+}
+
+fn default_ephemeral_max_chars() -> usize {
+    60
+}
+
+fn default_memory_classification_rules() -> Vec<MemoryClassificationRule> {
+    let rule = |category: &str, markers: &[&str]| MemoryClassificationRule {
+        category: category.into(),
+        markers: markers.iter().map(|m| (*m).to_string()).collect(),
+    };
+    vec![
+        rule(
+            "preference",
+            &[
+                "i prefer",
+                "my preference is",
+                "i'd rather",
+                "i would rather",
+                "please always",
+                "please never",
+            ],
+        ),
+        rule(
+            "fact",
+            &[
+                "remember that",
+                "remember:",
+                "note:",
+                "note that",
+                "for the record",
+            ],
+        ),
+        rule(
+            "task",
+            &["todo:", "to-do:", "remind me to", "don't let me forget"],
+        ),
+    ]
+}
+
+impl Default for MemoryClassificationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ephemeral_max_chars: default_ephemeral_max_chars(),
+            rules: default_memory_classification_rules(),
+            model_classifier: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct MemoryConfig {
@@ -2477,6 +3003,15 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
+    /// For sqlite backend: per-category retention in days, e.g.
+    /// `{ ephemeral = 2, task = 90 }`. Overrides `conversation_retention_days`
+    /// for `conversation`; categories not listed are never pruned.
+    /// Default: `{ ephemeral = 2 }`.
+    #[serde(default = "default_category_retention_days")]
+    pub category_retention_days: BTreeMap<String, u32>,
+    /// Auto-save categorization rules
+    #[serde(default)]
+    pub classification: MemoryClassificationConfig,
     /// Embedding provider: "none" | "openai" | "custom:URL"
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
@@ -2532,6 +3067,19 @@ pub struct MemoryConfig {
     #[serde(default)]
     pub sqlite_open_timeout_secs: Option<u64>,
 
+    // ── Forgetting ─────────────────────────────────────────────
+    /// Let the `memory_forget` tool delete by key prefix or search query
+    /// (two-step, token-confirmed). When false the agent can only forget a
+    /// single exact key.
+    #[serde(default)]
+    pub allow_bulk_forget: bool,
+    /// Max entries one confirmed bulk `memory_forget` call may delete (default: 100)
+    #[serde(default = "default_bulk_forget_max_entries")]
+    pub bulk_forget_max_entries: usize,
+    /// Days forgotten memories stay restorable via `zeroclaw memory restore` (default: 7)
+    #[serde(default = "default_undo_retention_days")]
+    pub undo_retention_days: u32,
+
     // ── Qdrant backend options ─────────────────────────────────
     /// Configuration for Qdrant vector database backend.
     /// Used when `backend = "qdrant"` or `backend = "sqlite_qdrant_hybrid"`.
@@ -2554,6 +3102,9 @@ fn default_purge_after_days() -> u32 {
 fn default_conversation_retention_days() -> u32 {
     30
 }
+fn default_category_retention_days() -> BTreeMap<String, u32> {
+    BTreeMap::from([("ephemeral".to_string(), 2)])
+}
 fn default_embedding_model() -> String {
     "text-embedding-3-small".into()
 }
@@ -2581,6 +3132,12 @@ fn default_response_cache_ttl() -> u32 {
 fn default_response_cache_max() -> usize {
     5_000
 }
+fn default_bulk_forget_max_entries() -> usize {
+    100
+}
+fn default_undo_retention_days() -> u32 {
+    7
+}
 
 impl Default for MemoryConfig {
     fn default() -> Self {
@@ -2591,6 +3148,8 @@ impl Default for MemoryConfig {
             archive_after_days: default_archive_after_days(),
             purge_after_days: default_purge_after_days(),
             conversation_retention_days: default_conversation_retention_days(),
+            category_retention_days: default_category_retention_days(),
+            classification: MemoryClassificationConfig::default(),
             embedding_provider: default_embedding_provider(),
             embedding_model: default_embedding_model(),
             embedding_dimensions: default_embedding_dims(),
@@ -2606,6 +3165,9 @@ impl Default for MemoryConfig {
             snapshot_on_hygiene: false,
             auto_hydrate: true,
             sqlite_open_timeout_secs: None,
+            allow_bulk_forget: false,
+            bulk_forget_max_entries: default_bulk_forget_max_entries(),
+            undo_retention_days: default_undo_retention_days(),
             qdrant: QdrantConfig::default(),
         }
     }
@@ -2639,6 +3201,30 @@ pub struct ObservabilityConfig {
     /// Maximum entries retained when runtime_trace_mode = "rolling".
     #[serde(default = "default_runtime_trace_max_entries")]
     pub runtime_trace_max_entries: usize,
+
+    /// What `channel start` and `gateway` print at startup: "full" | "minimal" | "off".
+    /// The global `--quiet` flag forces "off".
+    #[serde(default)]
+    pub startup_banner: StartupBanner,
+
+    /// Print the per-message console lines of the channel server (received
+    /// message, reply, processing notes). Turn off when running under systemd
+    /// and rely on tracing alone. Default: `true`.
+    #[serde(default = "default_true")]
+    pub console_echo: bool,
+}
+
+/// Startup banner variant (`[observability] startup_banner`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StartupBanner {
+    /// Multi-line banner with model, memory, workspace, config and channels.
+    #[default]
+    Full,
+    /// One line with the version and listening address.
+    Minimal,
+    /// Nothing on stdout; the banner is logged through tracing at info.
+    Off,
 }
 
 impl Default for ObservabilityConfig {
@@ -2650,6 +3236,8 @@ impl Default for ObservabilityConfig {
             runtime_trace_mode: default_runtime_trace_mode(),
             runtime_trace_path: default_runtime_trace_path(),
             runtime_trace_max_entries: default_runtime_trace_max_entries(),
+            startup_banner: StartupBanner::default(),
+            console_echo: true,
         }
     }
 }
@@ -2677,6 +3265,9 @@ pub struct HooksConfig {
     pub enabled: bool,
     #[serde(default)]
     pub builtin: BuiltinHooksConfig,
+    /// Redaction and banned-phrase rules applied to every outbound reply.
+    #[serde(default)]
+    pub outbound: OutboundHooksConfig,
 }
 
 impl Default for HooksConfig {
@@ -2684,6 +3275,7 @@ impl Default for HooksConfig {
         Self {
             enabled: true,
             builtin: BuiltinHooksConfig::default(),
+            outbound: OutboundHooksConfig::default(),
         }
     }
 }
@@ -2694,6 +3286,80 @@ pub struct BuiltinHooksConfig {
     pub command_logger: bool,
 }
 
+/// Outbound reply filtering (`[hooks.outbound]`).
+///
+/// Applied to the complete reply before channel chunking, on both channel
+/// and gateway replies. Requires `[hooks] enabled = true`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OutboundHooksConfig {
+    /// Regex → replacement rules (`$1`-style capture references allowed).
+    #[serde(default)]
+    pub redactions: Vec<OutboundRedactionRule>,
+    /// Phrases that redact, block, or flag a reply when they appear.
+    #[serde(default)]
+    pub banned_phrases: Vec<BannedPhraseRule>,
+    /// Reply sent in place of a blocked one.
+    #[serde(default = "default_outbound_block_notice")]
+    pub block_notice: String,
+}
+
+fn default_outbound_block_notice() -> String {
+    "This reply was withheld by the outbound content policy.".into()
+}
+
+impl Default for OutboundHooksConfig {
+    fn default() -> Self {
+        Self {
+            redactions: Vec::new(),
+            banned_phrases: Vec::new(),
+            block_notice: default_outbound_block_notice(),
+        }
+    }
+}
+
+impl OutboundHooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.redactions.is_empty() && self.banned_phrases.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OutboundRedactionRule {
+    /// Regex matched against the outbound reply.
+    pub pattern: String,
+    /// Replacement text. Default: `[REDACTED]`.
+    #[serde(default = "default_outbound_redaction_replacement")]
+    pub replacement: String,
+}
+
+fn default_outbound_redaction_replacement() -> String {
+    "[REDACTED]".into()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct BannedPhraseRule {
+    /// Phrase to look for; matched case-insensitively unless `regex = true`.
+    pub phrase: String,
+    /// Treat `phrase` as a regex.
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub action: BannedPhraseAction,
+}
+
+/// What a matching banned phrase does to the reply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BannedPhraseAction {
+    /// Replace the phrase with `[REDACTED]`.
+    #[default]
+    Redact,
+    /// Send `block_notice` instead of the reply and log it (redacted) as blocked.
+    Block,
+    /// Send the reply unchanged but log it as flagged.
+    Flag,
+}
+
 // ── Plugin system ─────────────────────────────────────────────────────────────
 
 /// Plugin system configuration (`[plugins]` section).
@@ -2909,6 +3575,7 @@ fn default_non_cli_excluded_tools() -> Vec<String> {
         "cron_remove",
         "cron_update",
         "cron_run",
+        "skill_install",
         "memory_store",
         "memory_forget",
         "proxy_config",
@@ -3381,6 +4048,18 @@ pub struct ReliabilityConfig {
     /// Max retries for cron job execution attempts.
     #[serde(default = "default_scheduler_retries")]
     pub scheduler_retries: u32,
+    /// Channel that gets provider failover and recovery notices (e.g. `"telegram"`).
+    #[serde(default)]
+    pub notify_channel: Option<String>,
+    /// Recipient on `notify_channel` (chat ID, channel ID, or user, as the
+    /// channel expects).
+    #[serde(default)]
+    pub notify_recipient: Option<String>,
+    /// Try fallback providers and models when a provider refuses a request
+    /// on content-policy grounds. Off by default: refusals are never retried,
+    /// and the next provider usually refuses too.
+    #[serde(default)]
+    pub fallback_on_refusal: bool,
 }
 
 fn default_provider_retries() -> u32 {
@@ -3419,6 +4098,9 @@ impl Default for ReliabilityConfig {
             channel_max_backoff_secs: default_channel_backoff_max_secs(),
             scheduler_poll_secs: default_scheduler_poll_secs(),
             scheduler_retries: default_scheduler_retries(),
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         }
     }
 }
@@ -3475,6 +4157,7 @@ impl Default for SchedulerConfig {
 /// hint = "fast"
 /// provider = "groq"
 /// model = "llama-3.3-70b-versatile"
+/// fallback = ["hint:reasoning"]
 /// ```
 ///
 /// Usage: pass `hint:reasoning` as the model parameter to route the request.
@@ -3501,6 +4184,10 @@ pub struct ModelRouteConfig {
     /// Existing configs without this field remain valid.
     #[serde(default)]
     pub transport: Option<String>,
+    /// Routes to try, in order, when this route's provider fails
+    /// (e.g. `["hint:fast"]`). Each must name another route's hint.
+    #[serde(default)]
+    pub fallback: Vec<String>,
 }
 
 // ── Embedding routing ───────────────────────────────────────────
@@ -3587,6 +4274,17 @@ pub struct HeartbeatConfig {
     /// Optional delivery recipient/chat identifier (required when `target` is set).
     #[serde(default, alias = "recipient")]
     pub to: Option<String>,
+    /// Model for heartbeat runs, e.g. `hint:cheap` to route through
+    /// `[[model_routes]]`. Default: the default model.
+    #[serde(default)]
+    pub heartbeat_model: Option<String>,
+    /// A heartbeat run is cut off after this many seconds. Default: `120`.
+    #[serde(default = "default_heartbeat_max_runtime_secs")]
+    pub max_runtime_secs: u64,
+}
+
+fn default_heartbeat_max_runtime_secs() -> u64 {
+    120
 }
 
 impl Default for HeartbeatConfig {
@@ -3597,6 +4295,8 @@ impl Default for HeartbeatConfig {
             message: None,
             target: None,
             to: None,
+            heartbeat_model: None,
+            max_runtime_secs: default_heartbeat_max_runtime_secs(),
         }
     }
 }
@@ -3807,6 +4507,59 @@ pub struct ChannelsConfig {
     /// Default: 300s for on-device LLMs (Ollama) which are slower than cloud APIs.
     #[serde(default = "default_channel_message_timeout_secs")]
     pub message_timeout_secs: u64,
+    /// Channels answered by a named delegate agent instead of the default
+    /// persona, keyed by channel name (e.g. `telegram = "homelab"`). Each value
+    /// must name an `[agents.<name>]` entry.
+    #[serde(default)]
+    pub agents: HashMap<String, String>,
+    /// Inbound message filters (`[channels_config.filters]`).
+    #[serde(default)]
+    pub filters: ChannelFiltersConfig,
+    /// Language of the built-in error replies sent to channel users and
+    /// webhook callers (`en`, `vi`). Unknown languages fall back to English
+    /// unless `error_templates` covers them. Default: `en`.
+    #[serde(default = "default_channel_error_language")]
+    pub error_language: String,
+    /// Error reply overrides, keyed by language and then by error class
+    /// (`rate_limited`, `auth`, `network`, `overloaded`, `budget_exceeded`,
+    /// `context_too_long`, `unsupported`, `tool_failure`, `internal`).
+    #[serde(default)]
+    pub error_templates: HashMap<String, HashMap<String, String>>,
+    /// Message that clears the sender's conversation history on any channel,
+    /// answered without calling the provider. `/new` and `/clear` always work
+    /// as well. Default: `/reset`.
+    #[serde(default = "default_channel_reset_command")]
+    pub reset_command: String,
+    /// Minimum seconds between status updates from streaming tools (WASM
+    /// tools with `"streaming": true`) while they run. Telegram edits one
+    /// status message; other channels send at most a few. `0` turns tool
+    /// progress off. Default: `5`.
+    #[serde(default = "default_tool_progress_interval_secs")]
+    pub tool_progress_interval_secs: u64,
+}
+
+/// Inbound filters for channel messages (`[channels_config.filters]`).
+///
+/// A message matching a rule skips the provider call and memory auto-save.
+/// Runtime commands (`/new`, `/models`, approval commands, ...) are handled
+/// before the filters and are never skipped.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct ChannelFiltersConfig {
+    /// Skip messages that are nothing but one or more URLs.
+    #[serde(default)]
+    pub skip_bare_urls: bool,
+    /// Skip messages with fewer than this many characters after trimming
+    /// (`0` disables the rule).
+    #[serde(default)]
+    pub skip_shorter_than: usize,
+    /// Skip messages matching any of these regular expressions.
+    #[serde(default)]
+    pub skip_patterns: Vec<String>,
+    /// Acknowledgment sent when a rule skips a message, keyed by rule:
+    /// `bare_url`, `too_short`, or the pattern string itself. Rules without an
+    /// entry stay silent.
+    #[serde(default)]
+    pub acknowledgments: HashMap<String, String>,
 }
 
 impl ChannelsConfig {
@@ -3909,6 +4662,18 @@ fn default_channel_message_timeout_secs() -> u64 {
     300
 }
 
+fn default_tool_progress_interval_secs() -> u64 {
+    5
+}
+
+fn default_channel_error_language() -> String {
+    "en".into()
+}
+
+fn default_channel_reset_command() -> String {
+    "/reset".into()
+}
+
 impl Default for ChannelsConfig {
     fn default() -> Self {
         Self {
@@ -3934,6 +4699,12 @@ impl Default for ChannelsConfig {
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: default_channel_message_timeout_secs(),
+            agents: HashMap::new(),
+            filters: ChannelFiltersConfig::default(),
+            error_language: default_channel_error_language(),
+            error_templates: HashMap::new(),
+            reset_command: default_channel_reset_command(),
+            tool_progress_interval_secs: default_tool_progress_interval_secs(),
         }
     }
 }
@@ -3953,6 +4724,20 @@ fn default_draft_update_interval_ms() -> u64 {
     1000
 }
 
+/// Where a chat channel posts its answer to a message (`reply_style`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReplyStyle {
+    /// Answer in a thread on the triggering message, starting one if needed.
+    /// Each thread keeps its own conversation history.
+    ThreadReply,
+    /// Answer in the conversation, quoting the triggering message.
+    InlineReply,
+    /// Answer with a plain message in the conversation. Messages sent inside
+    /// an existing thread are still answered in that thread.
+    TopLevel,
+}
+
 /// Group-chat reply trigger mode for channels that support mention gating.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -4087,6 +4872,13 @@ pub struct DiscordConfig {
     /// Group-chat trigger controls.
     #[serde(default)]
     pub group_reply: Option<GroupReplyConfig>,
+    /// Slash commands to register on startup: `ask`, `reset`, `skills`.
+    /// Registered for `guild_id` when set, otherwise globally. Empty = none.
+    #[serde(default)]
+    pub slash_commands: Vec<String>,
+    /// How replies attach to the triggering message. Default: `top-level`.
+    #[serde(default)]
+    pub reply_style: Option<ReplyStyle>,
 }
 
 impl ChannelConfig for DiscordConfig {
@@ -4108,6 +4900,11 @@ impl DiscordConfig {
         )
     }
 
+    #[must_use]
+    pub fn effective_reply_style(&self) -> ReplyStyle {
+        self.reply_style.unwrap_or(ReplyStyle::TopLevel)
+    }
+
     #[must_use]
     pub fn group_reply_allowed_sender_ids(&self) -> Vec<String> {
         clone_group_reply_allowed_sender_ids(self.group_reply.as_ref())
@@ -4130,6 +4927,20 @@ pub struct SlackConfig {
     /// Group-chat trigger controls.
     #[serde(default)]
     pub group_reply: Option<GroupReplyConfig>,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+    /// Slash commands to handle: `ask`, `reset`, `skills`. They must also be
+    /// added in the Slack app settings, and need Socket Mode (`app_token`).
+    #[serde(default)]
+    pub slash_commands: Vec<String>,
+    /// How replies attach to the triggering message. Default: `thread-reply`.
+    /// Slack has no quoted replies, so `inline-reply` also answers in a thread.
+    #[serde(default)]
+    pub reply_style: Option<ReplyStyle>,
 }
 
 impl ChannelConfig for SlackConfig {
@@ -4147,6 +4958,11 @@ impl SlackConfig {
         resolve_group_reply_mode(self.group_reply.as_ref(), None, GroupReplyMode::AllMessages)
     }
 
+    #[must_use]
+    pub fn effective_reply_style(&self) -> ReplyStyle {
+        self.reply_style.unwrap_or(ReplyStyle::ThreadReply)
+    }
+
     #[must_use]
     pub fn group_reply_allowed_sender_ids(&self) -> Vec<String> {
         clone_group_reply_allowed_sender_ids(self.group_reply.as_ref())
@@ -4208,8 +5024,12 @@ impl MattermostConfig {
 pub struct WebhookConfig {
     /// Port to listen on for incoming webhooks.
     pub port: u16,
-    /// Optional shared secret for webhook signature verification.
+    /// Optional shared secret, sent by callers in `X-Webhook-Secret`.
     pub secret: Option<String>,
+    /// Optional HMAC-SHA256 key. When set, callers must sign the raw request
+    /// body and send `X-Hub-Signature-256: sha256=<hex>` (GitHub style).
+    #[serde(default)]
+    pub hmac_secret: Option<String>,
 }
 
 impl ChannelConfig for WebhookConfig {
@@ -4257,6 +5077,15 @@ pub struct MatrixConfig {
     /// When true, only respond to direct rooms, explicit @-mentions, or replies to bot messages.
     #[serde(default)]
     pub mention_only: bool,
+    /// Streaming mode for progressive response delivery via message edits.
+    #[serde(default)]
+    pub stream_mode: StreamMode,
+    /// Minimum interval (ms) between draft message edits to avoid rate limits.
+    #[serde(default = "default_draft_update_interval_ms")]
+    pub draft_update_interval_ms: u64,
+    /// How replies attach to the triggering message. Default: `top-level`.
+    #[serde(default)]
+    pub reply_style: Option<ReplyStyle>,
 }
 
 impl ChannelConfig for MatrixConfig {
@@ -4268,6 +5097,13 @@ impl ChannelConfig for MatrixConfig {
     }
 }
 
+impl MatrixConfig {
+    #[must_use]
+    pub fn effective_reply_style(&self) -> ReplyStyle {
+        self.reply_style.unwrap_or(ReplyStyle::TopLevel)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SignalConfig {
     /// Base URL for the signal-cli HTTP daemon (e.g. "http://127.0.0.1:8686").
@@ -5802,6 +6638,11 @@ fn decrypt_channel_secrets(
             &mut webhook.secret,
             "config.channels_config.webhook.secret",
         )?;
+        decrypt_optional_secret(
+            store,
+            &mut webhook.hmac_secret,
+            "config.channels_config.webhook.hmac_secret",
+        )?;
     }
     if let Some(ref mut matrix) = channels.matrix {
         decrypt_secret(
@@ -5918,6 +6759,37 @@ fn decrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut wati) = channels.wati {
+        decrypt_secret(
+            store,
+            &mut wati.api_token,
+            "config.channels_config.wati.api_token",
+        )?;
+    }
+    if let Some(ref mut email) = channels.email {
+        decrypt_secret(
+            store,
+            &mut email.password,
+            "config.channels_config.email.password",
+        )?;
+    }
+    if let Some(ref mut feishu) = channels.feishu {
+        decrypt_secret(
+            store,
+            &mut feishu.app_secret,
+            "config.channels_config.feishu.app_secret",
+        )?;
+        decrypt_optional_secret(
+            store,
+            &mut feishu.encrypt_key,
+            "config.channels_config.feishu.encrypt_key",
+        )?;
+        decrypt_optional_secret(
+            store,
+            &mut feishu.verification_token,
+            "config.channels_config.feishu.verification_token",
+        )?;
+    }
     Ok(())
 }
 
@@ -5964,6 +6836,11 @@ fn encrypt_channel_secrets(
             &mut webhook.secret,
             "config.channels_config.webhook.secret",
         )?;
+        encrypt_optional_secret(
+            store,
+            &mut webhook.hmac_secret,
+            "config.channels_config.webhook.hmac_secret",
+        )?;
     }
     if let Some(ref mut matrix) = channels.matrix {
         encrypt_secret(
@@ -6080,6 +6957,37 @@ fn encrypt_channel_secrets(
             "config.channels_config.clawdtalk.webhook_secret",
         )?;
     }
+    if let Some(ref mut wati) = channels.wati {
+        encrypt_secret(
+            store,
+            &mut wati.api_token,
+            "config.channels_config.wati.api_token",
+        )?;
+    }
+    if let Some(ref mut email) = channels.email {
+        encrypt_secret(
+            store,
+            &mut email.password,
+            "config.channels_config.email.password",
+        )?;
+    }
+    if let Some(ref mut feishu) = channels.feishu {
+        encrypt_secret(
+            store,
+            &mut feishu.app_secret,
+            "config.channels_config.feishu.app_secret",
+        )?;
+        encrypt_optional_secret(
+            store,
+            &mut feishu.encrypt_key,
+            "config.channels_config.feishu.encrypt_key",
+        )?;
+        encrypt_optional_secret(
+            store,
+            &mut feishu.verification_token,
+            "config.channels_config.feishu.verification_token",
+        )?;
+    }
     Ok(())
 }
 
@@ -6243,20 +7151,46 @@ impl Config {
             // Track ignored/unknown config keys to warn users about silent misconfigurations
             // (e.g., using [providers.ollama] which doesn't exist instead of top-level api_url)
             let mut ignored_paths: Vec<String> = Vec::new();
-            let mut config: Config = serde_ignored::deserialize(
+            let parsed: Result<Config, toml::de::Error> = serde_ignored::deserialize(
                 toml::de::Deserializer::parse(&contents).context("Failed to parse config file")?,
                 |path| {
                     ignored_paths.push(path.to_string());
                 },
-            )
-            .context("Failed to deserialize config file")?;
+            );
+            let mut config = match parsed {
+                Ok(config) => config,
+                Err(err) => {
+                    // Point at what the failing section expects, from the schema.
+                    let offset = err.span().map_or(0, |span| span.start);
+                    let hint = super::json_schema::parse_error_hint(
+                        &super::json_schema::config_schema(),
+                        &contents,
+                        err.message(),
+                        offset,
+                    );
+                    let err = if hint.is_empty() {
+                        anyhow::Error::new(err)
+                    } else {
+                        anyhow::anyhow!("{err}\n{hint}")
+                    };
+                    return Err(err.context("Failed to deserialize config file"));
+                }
+            };
 
             // Warn about each unknown config key
-            for path in ignored_paths {
-                tracing::warn!(
-                    "Unknown config key ignored: \"{}\". Check config.toml for typos or deprecated options.",
-                    path
-                );
+            if !ignored_paths.is_empty() {
+                let schema = super::json_schema::config_schema();
+                for path in ignored_paths {
+                    let segments: Vec<String> = path.split('.').map(str::to_string).collect();
+                    let suggestion = super::json_schema::suggest_key(&schema, &segments)
+                        .map(|close| format!(" Did you mean \"{close}\"?"))
+                        .unwrap_or_default();
+                    tracing::warn!(
+                        "Unknown config key ignored: \"{}\".{} Check config.toml for typos or deprecated options.",
+                        path,
+                        suggestion
+                    );
+                }
             }
             // Set computed paths that are skipped during serialization
             config.config_path = config_path.clone();
@@ -6315,6 +7249,16 @@ impl Config {
                 &mut config.web_search.jina_api_key,
                 "config.web_search.jina_api_key",
             )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.skills.clawhub_token,
+                "config.skills.clawhub_token",
+            )?;
+            for (skill, vars) in &mut config.skills.env {
+                for (name, value) in vars.iter_mut() {
+                    decrypt_secret(&store, value, &format!("config.skills.env.{skill}.{name}"))?;
+                }
+            }
 
             decrypt_optional_secret(
                 &store,
@@ -6331,6 +7275,60 @@ impl Config {
                 &mut config.gateway.paired_tokens,
                 "config.gateway.paired_tokens",
             )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.gateway.admin_token,
+                "config.gateway.admin_token",
+            )?;
+
+            decrypt_optional_secret(
+                &store,
+                &mut config.web_search.api_key,
+                "config.web_search.api_key",
+            )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.web_fetch.api_key,
+                "config.web_fetch.api_key",
+            )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.memory.qdrant.api_key,
+                "config.memory.qdrant.api_key",
+            )?;
+            decrypt_optional_secret(
+                &store,
+                &mut config.gateway.node_control.auth_token,
+                "config.gateway.node_control.auth_token",
+            )?;
+            if let Some(ref mut cloudflare) = config.tunnel.cloudflare {
+                decrypt_secret(
+                    &store,
+                    &mut cloudflare.token,
+                    "config.tunnel.cloudflare.token",
+                )?;
+            }
+            if let Some(ref mut ngrok) = config.tunnel.ngrok {
+                decrypt_secret(
+                    &store,
+                    &mut ngrok.auth_token,
+                    "config.tunnel.ngrok.auth_token",
+                )?;
+            }
+            for route in &mut config.model_routes {
+                decrypt_optional_secret(
+                    &store,
+                    &mut route.api_key,
+                    "config.model_routes[].api_key",
+                )?;
+            }
+            for route in &mut config.embedding_routes {
+                decrypt_optional_secret(
+                    &store,
+                    &mut route.api_key,
+                    "config.embedding_routes[].api_key",
+                )?;
+            }
 
             for agent in config.agents.values_mut() {
                 decrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
@@ -6561,6 +7559,19 @@ impl Config {
         if self.gateway.host.trim().is_empty() {
             anyhow::bail!("gateway.host must not be empty");
         }
+        if let Some(listen) = &self.gateway.listen {
+            crate::gateway::listen::validate(listen, &self.gateway.socket_permissions)?;
+        }
+
+        // Channel slash commands
+        if let Some(discord) = &self.channels_config.discord {
+            crate::channels::slash_commands::SlashCommand::parse_list(&discord.slash_commands)
+                .context("channels_config.discord.slash_commands")?;
+        }
+        if let Some(slack) = &self.channels_config.slack {
+            crate::channels::slash_commands::SlashCommand::parse_list(&slack.slash_commands)
+                .context("channels_config.slack.slash_commands")?;
+        }
 
         // Autonomy
         if self.autonomy.max_actions_per_hour == 0 {
@@ -6594,6 +7605,25 @@ impl Config {
             }
         }
 
+        if let Some(timezone) = &self.agent.timezone {
+            super::timezone::parse(timezone).context("agent.timezone is invalid")?;
+        }
+
+        // Agent prompt sections
+        let sections = &self.agent.prompt_sections;
+        if !sections.safety && !sections.i_understand_no_safety {
+            tracing::warn!(
+                "agent.prompt_sections.safety = false is ignored without i_understand_no_safety = true"
+            );
+        }
+        if let Some(files) = &sections.bootstrap_files {
+            for (i, file) in files.iter().enumerate() {
+                if file.trim().is_empty() {
+                    anyhow::bail!("agent.prompt_sections.bootstrap_files[{i}] must not be empty");
+                }
+            }
+        }
+
         // Security OTP / estop
         if self.security.otp.token_ttl_secs == 0 {
             anyhow::bail!("security.otp.token_ttl_secs must be greater than 0");
@@ -6940,6 +7970,20 @@ impl Config {
             {
                 anyhow::bail!("model_routes[{i}].transport must be one of: auto, websocket, sse");
             }
+            for fallback in &route.fallback {
+                let fallback_hint = fallback.trim();
+                let fallback_hint = fallback_hint.strip_prefix("hint:").unwrap_or(fallback_hint);
+                if fallback_hint == route.hint
+                    || !self
+                        .model_routes
+                        .iter()
+                        .any(|other| other.hint == fallback_hint)
+                {
+                    anyhow::bail!(
+                        "model_routes[{i}].fallback entry `{fallback}` must name another route's hint"
+                    );
+                }
+            }
         }
 
         if self
@@ -7056,6 +8100,21 @@ impl Config {
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
+        // Channels routed to delegate agents must name configured agents.
+        let mut channel_agents: Vec<(&String, &String)> =
+            self.channels_config.agents.iter().collect();
+        channel_agents.sort();
+        for (channel, agent) in channel_agents {
+            if !self.agents.contains_key(agent.trim()) {
+                let mut configured: Vec<&str> = self.agents.keys().map(String::as_str).collect();
+                configured.sort_unstable();
+                anyhow::bail!(
+                    "channels_config.agents.{channel}: {}",
+                    crate::agent::delegates::unknown_agent_message(agent, &configured)
+                );
+            }
+        }
+
         // Delegate coordination runtime safety bounds.
         if self.coordination.enabled && self.coordination.lead_agent.trim().is_empty() {
             anyhow::bail!("coordination.lead_agent must not be empty when coordination is enabled");
@@ -7083,6 +8142,30 @@ impl Config {
         if self.wasm.fuel_limit == 0 {
             anyhow::bail!("wasm.fuel_limit must be greater than 0");
         }
+        if self.wasm.max_concurrent == 0 {
+            anyhow::bail!("wasm.max_concurrent must be at least 1");
+        }
+        if self.skills.download_max_redirects > crate::skills::DOWNLOAD_MAX_REDIRECTS_LIMIT {
+            anyhow::bail!(
+                "skills.download_max_redirects must be at most {}, got {}",
+                crate::skills::DOWNLOAD_MAX_REDIRECTS_LIMIT,
+                self.skills.download_max_redirects
+            );
+        }
+
+        // Outbound reply filter and inbound channel filters: report invalid regexes up front.
+        crate::hooks::OutboundFilter::compile(&self.hooks.outbound)?;
+        crate::channels::filters::InboundFilter::compile(&self.channels_config.filters)?;
+        crate::channels::error_reply::ErrorReplies::from_config(&self.channels_config)?;
+        {
+            let reset = self.channels_config.reset_command.trim();
+            if reset.is_empty() || reset.contains(char::is_whitespace) {
+                anyhow::bail!(
+                    "channels_config.reset_command must be a single word such as `/reset`, got {:?}",
+                    self.channels_config.reset_command
+                );
+            }
+        }
         {
             let url = &self.wasm.registry_url;
             // Extract what comes after "https://" and check that the host part
@@ -7719,6 +8802,12 @@ impl Config {
         }
 
         set_runtime_proxy_config(self.proxy.clone());
+        super::timezone::set_configured(
+            self.agent
+                .timezone
+                .as_deref()
+                .and_then(|name| super::timezone::parse(name).ok()),
+        );
     }
 
     pub async fn save(&self) -> Result<()> {
@@ -7783,6 +8872,16 @@ impl Config {
             &mut config_to_save.web_search.jina_api_key,
             "config.web_search.jina_api_key",
         )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.skills.clawhub_token,
+            "config.skills.clawhub_token",
+        )?;
+        for (skill, vars) in &mut config_to_save.skills.env {
+            for (name, value) in vars.iter_mut() {
+                encrypt_secret(&store, value, &format!("config.skills.env.{skill}.{name}"))?;
+            }
+        }
 
         encrypt_optional_secret(
             &store,
@@ -7799,6 +8898,56 @@ impl Config {
             &mut config_to_save.gateway.paired_tokens,
             "config.gateway.paired_tokens",
         )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.gateway.admin_token,
+            "config.gateway.admin_token",
+        )?;
+
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.web_search.api_key,
+            "config.web_search.api_key",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.web_fetch.api_key,
+            "config.web_fetch.api_key",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.memory.qdrant.api_key,
+            "config.memory.qdrant.api_key",
+        )?;
+        encrypt_optional_secret(
+            &store,
+            &mut config_to_save.gateway.node_control.auth_token,
+            "config.gateway.node_control.auth_token",
+        )?;
+        if let Some(ref mut cloudflare) = config_to_save.tunnel.cloudflare {
+            encrypt_secret(
+                &store,
+                &mut cloudflare.token,
+                "config.tunnel.cloudflare.token",
+            )?;
+        }
+        if let Some(ref mut ngrok) = config_to_save.tunnel.ngrok {
+            encrypt_secret(
+                &store,
+                &mut ngrok.auth_token,
+                "config.tunnel.ngrok.auth_token",
+            )?;
+        }
+        for route in &mut config_to_save.model_routes {
+            encrypt_optional_secret(&store, &mut route.api_key, "config.model_routes[].api_key")?;
+        }
+        for route in &mut config_to_save.embedding_routes {
+            encrypt_optional_secret(
+                &store,
+                &mut route.api_key,
+                "config.embedding_routes[].api_key",
+            )?;
+        }
 
         for agent in config_to_save.agents.values_mut() {
             encrypt_optional_secret(&store, &mut agent.api_key, "config.agents.*.api_key")?;
@@ -8378,6 +9527,7 @@ default_temperature = 0.7
                 message: Some("Check London time".into()),
                 target: Some("telegram".into()),
                 to: Some("123456".into()),
+                ..HeartbeatConfig::default()
             },
             cron: CronConfig::default(),
             goal_loop: GoalLoopConfig::default(),
@@ -8413,6 +9563,12 @@ default_temperature = 0.7
                 nostr: None,
                 clawdtalk: None,
                 message_timeout_secs: 300,
+                agents: HashMap::new(),
+                filters: ChannelFiltersConfig::default(),
+                error_language: default_channel_error_language(),
+                error_templates: HashMap::new(),
+                reset_command: default_channel_reset_command(),
+                tool_progress_interval_secs: default_tool_progress_interval_secs(),
             },
             memory: MemoryConfig::default(),
             storage: StorageConfig::default(),
@@ -8673,6 +9829,21 @@ reasoning_level = "high"
         );
     }
 
+    #[test]
+    async fn provider_prompt_caching_defaults_on_and_can_be_disabled() {
+        let parsed: Config = toml::from_str("default_temperature = 0.7\n").unwrap();
+        assert!(parsed.provider.prompt_caching);
+
+        let raw = r#"
+default_temperature = 0.7
+
+[provider]
+prompt_caching = false
+"#;
+        let parsed: Config = toml::from_str(raw).unwrap();
+        assert!(!parsed.provider.prompt_caching);
+    }
+
     #[test]
     async fn runtime_reasoning_level_alias_deserializes() {
         let raw = r#"
@@ -8738,6 +9909,50 @@ tool_dispatcher = "xml"
         assert_eq!(parsed.agent.tool_dispatcher, "xml");
     }
 
+    #[test]
+    async fn agent_prompt_sections_deserialize_with_profile() {
+        let raw = r#"
+default_temperature = 0.7
+[agent]
+compact_context = true
+
+[agent.prompt_sections]
+tools = false
+skills = false
+bootstrap_files = ["IDENTITY.md"]
+"#;
+        let parsed: Config = toml::from_str(raw).unwrap();
+        let profile = parsed.agent.prompt_profile();
+        assert!(!profile.sections.tools);
+        assert!(!profile.sections.skills);
+        assert!(profile.sections.runtime);
+        assert!(profile.sections.safety_enabled());
+        assert!(profile.sections.bootstrap_file_enabled("IDENTITY.md"));
+        assert!(!profile.sections.bootstrap_file_enabled("SOUL.md"));
+        assert_eq!(profile.bootstrap_max_chars, Some(6000));
+        assert_eq!(profile.rag_chunk_limit, 2);
+
+        let full = AgentConfig {
+            compact_context: false,
+            ..AgentConfig::default()
+        }
+        .prompt_profile();
+        assert_eq!(full.sections, PromptSectionsConfig::default());
+        assert_eq!(full.bootstrap_max_chars, None);
+        assert_eq!(full.rag_chunk_limit, 5);
+    }
+
+    #[test]
+    async fn agent_timezone_is_validated_at_load() {
+        let mut config = Config::default();
+        config.agent.timezone = Some("Asia/Ho_Chi_Minh".into());
+        assert!(config.validate().is_ok());
+
+        config.agent.timezone = Some("Asia/Saigon City".into());
+        let err = config.validate().unwrap_err();
+        assert!(format!("{err:#}").contains("agent.timezone"), "{err:#}");
+    }
+
     #[tokio::test]
     async fn sync_directory_handles_existing_directory() {
         let dir = std::env::temp_dir().join(format!(
@@ -8853,6 +10068,11 @@ tool_dispatcher = "xml"
         config.web_search.perplexity_api_key = Some("perplexity-credential".into());
         config.web_search.exa_api_key = Some("exa-credential".into());
         config.web_search.jina_api_key = Some("jina-credential".into());
+        config.skills.clawhub_token = Some("clawhub-credential".into());
+        config.skills.env.insert(
+            "weather".into(),
+            HashMap::from([("WEATHER_API_KEY".into(), "weather-credential".into())]),
+        );
         config.storage.provider.config.db_url = Some("postgres://user:pw@host/db".into());
         config.reliability.api_keys = vec!["backup-credential".into()];
         config.gateway.paired_tokens = vec!["zc_0123456789abcdef".into()];
@@ -8969,6 +10189,23 @@ tool_dispatcher = "xml"
         assert!(crate::security::SecretStore::is_encrypted(jina_encrypted));
         assert_eq!(store.decrypt(jina_encrypted).unwrap(), "jina-credential");
 
+        let clawhub_encrypted = stored.skills.clawhub_token.as_deref().unwrap();
+        assert!(crate::security::SecretStore::is_encrypted(
+            clawhub_encrypted
+        ));
+        assert_eq!(
+            store.decrypt(clawhub_encrypted).unwrap(),
+            "clawhub-credential"
+        );
+        let weather_encrypted = &stored.skills.env["weather"]["WEATHER_API_KEY"];
+        assert!(crate::security::SecretStore::is_encrypted(
+            weather_encrypted
+        ));
+        assert_eq!(
+            store.decrypt(weather_encrypted).unwrap(),
+            "weather-credential"
+        );
+
         let worker = stored.agents.get("worker").unwrap();
         let worker_encrypted = worker.api_key.as_deref().unwrap();
         assert!(crate::security::SecretStore::is_encrypted(worker_encrypted));
@@ -9005,6 +10242,33 @@ tool_dispatcher = "xml"
         let _ = fs::remove_dir_all(&dir).await;
     }
 
+    #[test]
+    async fn decrypt_secrets_accepts_mixed_plaintext_and_encrypted_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = crate::security::SecretStore::new(dir.path(), true);
+
+        let mut encrypted = Some(store.encrypt("clawhub-credential").unwrap());
+        let mut plaintext = Some("sk-plaintext".to_string());
+        let mut tokens = vec![
+            store.encrypt("zc_encrypted").unwrap(),
+            "zc_plaintext".to_string(),
+        ];
+
+        decrypt_optional_secret(&store, &mut encrypted, "encrypted").unwrap();
+        decrypt_optional_secret(&store, &mut plaintext, "plaintext").unwrap();
+        decrypt_vec_secrets(&store, &mut tokens, "tokens").unwrap();
+
+        assert_eq!(encrypted.as_deref(), Some("clawhub-credential"));
+        assert_eq!(plaintext.as_deref(), Some("sk-plaintext"));
+        assert_eq!(tokens, ["zc_encrypted", "zc_plaintext"]);
+
+        // Re-saving migrates the plaintext values; encrypted ones are left alone.
+        encrypt_optional_secret(&store, &mut plaintext, "plaintext").unwrap();
+        assert!(crate::security::SecretStore::is_encrypted(
+            plaintext.as_deref().unwrap()
+        ));
+    }
+
     #[tokio::test]
     async fn config_save_atomic_cleanup() {
         let dir =
@@ -9112,6 +10376,8 @@ tool_dispatcher = "xml"
             listen_to_bots: false,
             mention_only: false,
             group_reply: None,
+            slash_commands: vec![],
+            reply_style: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -9128,6 +10394,8 @@ tool_dispatcher = "xml"
             listen_to_bots: false,
             mention_only: false,
             group_reply: None,
+            slash_commands: vec![],
+            reply_style: None,
         };
         let json = serde_json::to_string(&dc).unwrap();
         let parsed: DiscordConfig = serde_json::from_str(&json).unwrap();
@@ -9212,6 +10480,9 @@ tool_dispatcher = "xml"
             room_id: "!room123:matrix.org".into(),
             allowed_users: vec!["@user:matrix.org".into()],
             mention_only: false,
+            stream_mode: StreamMode::Partial,
+            draft_update_interval_ms: 1500,
+            reply_style: None,
         };
         let json = serde_json::to_string(&mc).unwrap();
         let parsed: MatrixConfig = serde_json::from_str(&json).unwrap();
@@ -9221,6 +10492,8 @@ tool_dispatcher = "xml"
         assert_eq!(parsed.device_id.as_deref(), Some("DEVICE123"));
         assert_eq!(parsed.room_id, "!room123:matrix.org");
         assert_eq!(parsed.allowed_users.len(), 1);
+        assert_eq!(parsed.stream_mode, StreamMode::Partial);
+        assert_eq!(parsed.draft_update_interval_ms, 1500);
     }
 
     #[test]
@@ -9233,6 +10506,9 @@ tool_dispatcher = "xml"
             room_id: "!abc:synapse.local".into(),
             allowed_users: vec!["@admin:synapse.local".into(), "*".into()],
             mention_only: true,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: default_draft_update_interval_ms(),
+            reply_style: None,
         };
         let toml_str = toml::to_string(&mc).unwrap();
         let parsed: MatrixConfig = toml::from_str(&toml_str).unwrap();
@@ -9324,6 +10600,9 @@ allowed_users = ["@ops:matrix.org"]
                 room_id: "!r:m".into(),
                 allowed_users: vec!["@u:m".into()],
                 mention_only: false,
+                stream_mode: StreamMode::default(),
+                draft_update_interval_ms: 1000,
+                reply_style: None,
             }),
             signal: None,
             whatsapp: None,
@@ -9339,6 +10618,12 @@ allowed_users = ["@ops:matrix.org"]
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            agents: HashMap::new(),
+            filters: ChannelFiltersConfig::default(),
+            error_language: default_channel_error_language(),
+            error_templates: HashMap::new(),
+            reset_command: default_channel_reset_command(),
+            tool_progress_interval_secs: default_tool_progress_interval_secs(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -9416,6 +10701,30 @@ channel_id = "C123"
         );
     }
 
+    #[test]
+    async fn reply_style_defaults_keep_each_channels_behavior() {
+        let slack: SlackConfig = toml::from_str(r#"bot_token = "xoxb-tok""#).unwrap();
+        assert_eq!(slack.effective_reply_style(), ReplyStyle::ThreadReply);
+        let discord: DiscordConfig = toml::from_str(r#"bot_token = "tok""#).unwrap();
+        assert_eq!(discord.effective_reply_style(), ReplyStyle::TopLevel);
+
+        let discord: DiscordConfig = toml::from_str(
+            r#"
+bot_token = "tok"
+reply_style = "inline-reply"
+"#,
+        )
+        .unwrap();
+        assert_eq!(discord.effective_reply_style(), ReplyStyle::InlineReply);
+        assert!(toml::from_str::<DiscordConfig>(
+            r#"
+bot_token = "tok"
+reply_style = "thread"
+"#
+        )
+        .is_err());
+    }
+
     #[test]
     async fn slack_group_reply_config_supports_sender_overrides() {
         let json = r#"{
@@ -9484,9 +10793,18 @@ channel_id = "C123"
         let json = r#"{"port":8080}"#;
         let parsed: WebhookConfig = serde_json::from_str(json).unwrap();
         assert!(parsed.secret.is_none());
+        assert!(parsed.hmac_secret.is_none());
         assert_eq!(parsed.port, 8080);
     }
 
+    #[test]
+    async fn webhook_config_with_hmac_secret() {
+        let json = r#"{"port":8080,"hmac_secret":"signing-key"}"#;
+        let parsed: WebhookConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.hmac_secret.as_deref(), Some("signing-key"));
+        assert!(parsed.secret.is_none());
+    }
+
     // ── WhatsApp config ──────────────────────────────────────
 
     #[test]
@@ -9617,6 +10935,12 @@ channel_id = "C123"
             nostr: None,
             clawdtalk: None,
             message_timeout_secs: 300,
+            agents: HashMap::new(),
+            filters: ChannelFiltersConfig::default(),
+            error_language: default_channel_error_language(),
+            error_templates: HashMap::new(),
+            reset_command: default_channel_reset_command(),
+            tool_progress_interval_secs: default_tool_progress_interval_secs(),
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -9695,15 +11019,26 @@ channel_id = "C123"
         let g = GatewayConfig {
             port: 42617,
             host: "127.0.0.1".into(),
+            listen: Some("unix:/run/zeroclaw/gateway.sock".into()),
+            socket_permissions: "0660".into(),
             require_pairing: true,
             allow_public_bind: false,
             paired_tokens: vec!["zc_test_token".into()],
+            admin_token: Some("admin-token".into()),
+            advertise_mdns: true,
             pair_rate_limit_per_minute: 12,
             webhook_rate_limit_per_minute: 80,
             trust_forwarded_headers: true,
             rate_limit_max_keys: 2048,
             idempotency_ttl_secs: 600,
             idempotency_max_keys: 4096,
+            cors_allowed_origins: vec!["https://dashboard.internal".into()],
+            request_timeout_secs: 45,
+            max_connections: 64,
+            max_concurrent_requests: 16,
+            max_request_bytes: 1_048_576,
+            webhook_agent_timeout_secs: 40,
+            error_format: GatewayErrorFormat::Structured,
             node_control: NodeControlConfig {
                 enabled: true,
                 auth_token: Some("node-token".into()),
@@ -9715,12 +11050,24 @@ channel_id = "C123"
         assert!(parsed.require_pairing);
         assert!(!parsed.allow_public_bind);
         assert_eq!(parsed.paired_tokens, vec!["zc_test_token"]);
+        assert_eq!(parsed.admin_token.as_deref(), Some("admin-token"));
+        assert!(parsed.advertise_mdns);
         assert_eq!(parsed.pair_rate_limit_per_minute, 12);
         assert_eq!(parsed.webhook_rate_limit_per_minute, 80);
         assert!(parsed.trust_forwarded_headers);
         assert_eq!(parsed.rate_limit_max_keys, 2048);
         assert_eq!(parsed.idempotency_ttl_secs, 600);
         assert_eq!(parsed.idempotency_max_keys, 4096);
+        assert_eq!(parsed.request_timeout_secs, 45);
+        assert_eq!(parsed.max_connections, 64);
+        assert_eq!(parsed.max_concurrent_requests, 16);
+        assert_eq!(parsed.max_request_bytes, 1_048_576);
+        assert_eq!(parsed.error_format, GatewayErrorFormat::Structured);
+        assert_eq!(
+            parsed.listen.as_deref(),
+            Some("unix:/run/zeroclaw/gateway.sock")
+        );
+        assert_eq!(parsed.socket_permissions, "0660");
         assert!(parsed.node_control.enabled);
         assert_eq!(
             parsed.node_control.auth_token.as_deref(),
@@ -10342,6 +11689,7 @@ provider_api = "not-a-real-mode"
             max_tokens: Some(0),
             api_key: None,
             transport: None,
+            fallback: Vec::new(),
         }];
 
         let err = config
@@ -10384,6 +11732,7 @@ provider_api = "not-a-real-mode"
             max_tokens: None,
             api_key: None,
             transport: Some("udp".to_string()),
+            fallback: Vec::new(),
         }];
 
         let err = config
@@ -10394,6 +11743,31 @@ provider_api = "not-a-real-mode"
             .contains("model_routes[0].transport must be one of: auto, websocket, sse"));
     }
 
+    #[test]
+    async fn model_route_fallback_must_name_another_route() {
+        let route = |hint: &str, fallback: &[&str]| ModelRouteConfig {
+            hint: hint.to_string(),
+            provider: "openrouter".to_string(),
+            model: "anthropic/claude-sonnet-4.6".to_string(),
+            max_tokens: None,
+            api_key: None,
+            transport: None,
+            fallback: fallback.iter().map(ToString::to_string).collect(),
+        };
+        let mut config = Config::default();
+        config.model_routes = vec![route("reasoning", &["hint:fast"]), route("fast", &[])];
+        assert!(config.validate().is_ok());
+
+        config.model_routes = vec![route("reasoning", &["hint:reasoning"])];
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains(
+            "model_routes[0].fallback entry `hint:reasoning` must name another route's hint"
+        ));
+
+        config.model_routes = vec![route("reasoning", &["hint:missing"])];
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     async fn env_override_glm_api_key_for_regional_aliases() {
         let _env_guard = env_override_lock().await;
@@ -10549,6 +11923,39 @@ provider_api = "not-a-real-mode"
             .contains("wire_api must be one of: responses, chat_completions"));
     }
 
+    #[test]
+    async fn validate_rejects_channel_routed_to_unknown_agent() {
+        let mut config = Config::default();
+        config.agents.insert(
+            "homelab".into(),
+            DelegateAgentConfig {
+                provider: "ollama".into(),
+                model: "llama3".into(),
+                system_prompt: None,
+                api_key: None,
+                temperature: None,
+                max_depth: 3,
+                agentic: false,
+                allowed_tools: Vec::new(),
+                max_iterations: 10,
+            },
+        );
+        config
+            .channels_config
+            .agents
+            .insert("telegram".into(), "homelab".into());
+        assert!(config.validate().is_ok());
+
+        config
+            .channels_config
+            .agents
+            .insert("discord".into(), "ghost".into());
+        let error = config.validate().expect_err("expected validation failure");
+        let message = error.to_string();
+        assert!(message.contains("channels_config.agents.discord"));
+        assert!(message.contains("configured agents: homelab"));
+    }
+
     #[test]
     async fn env_override_model_fallback() {
         let _env_guard = env_override_lock().await;
@@ -11376,6 +12783,89 @@ default_model = "legacy-model"
         clear_proxy_env_test_vars();
     }
 
+    fn command_env(cmd: &std::process::Command, key: &str) -> Option<Option<String>> {
+        cmd.get_envs()
+            .find(|(name, _)| *name == std::ffi::OsStr::new(key))
+            .map(|(_, value)| value.map(|v| v.to_string_lossy().into_owned()))
+    }
+
+    #[test]
+    async fn proxy_apply_to_command_follows_scope() {
+        let base = ProxyConfig {
+            enabled: true,
+            http_proxy: None,
+            https_proxy: Some("http://proxy.corp:3128".into()),
+            all_proxy: None,
+            no_proxy: vec!["localhost".into()],
+            scope: ProxyScope::Zeroclaw,
+            services: Vec::new(),
+        };
+
+        // scope = zeroclaw: explicit vars, unset ones removed.
+        let mut cmd = std::process::Command::new("git");
+        base.apply_to_command(&mut cmd, "skills.git");
+        assert_eq!(
+            command_env(&cmd, "HTTPS_PROXY"),
+            Some(Some("http://proxy.corp:3128".into()))
+        );
+        assert_eq!(
+            command_env(&cmd, "https_proxy"),
+            Some(Some("http://proxy.corp:3128".into()))
+        );
+        assert_eq!(
+            command_env(&cmd, "NO_PROXY"),
+            Some(Some("localhost".into()))
+        );
+        assert_eq!(command_env(&cmd, "HTTP_PROXY"), Some(None));
+        assert_eq!(
+            base.git_http_proxy("skills.git").as_deref(),
+            Some("http://proxy.corp:3128")
+        );
+
+        // scope = services with a matching selector behaves like zeroclaw.
+        let matching = ProxyConfig {
+            scope: ProxyScope::Services,
+            services: vec!["skills.*".into()],
+            ..base.clone()
+        };
+        let mut cmd = std::process::Command::new("curl");
+        matching.apply_to_command(&mut cmd, "skills.download");
+        assert_eq!(
+            command_env(&cmd, "HTTPS_PROXY"),
+            Some(Some("http://proxy.corp:3128".into()))
+        );
+
+        // scope = services without a matching selector strips inherited vars.
+        let excluded = ProxyConfig {
+            scope: ProxyScope::Services,
+            services: vec!["provider.openai".into()],
+            ..base.clone()
+        };
+        let mut cmd = std::process::Command::new("git");
+        excluded.apply_to_command(&mut cmd, "skills.git");
+        for key in ["HTTP_PROXY", "https_proxy", "ALL_PROXY", "no_proxy"] {
+            assert_eq!(command_env(&cmd, key), Some(None), "{key}");
+        }
+        assert_eq!(excluded.git_http_proxy("skills.git").as_deref(), Some(""));
+
+        // scope = environment / disabled: child inherits, nothing touched.
+        for proxy in [
+            ProxyConfig {
+                scope: ProxyScope::Environment,
+                ..base.clone()
+            },
+            ProxyConfig {
+                enabled: false,
+                ..base.clone()
+            },
+        ] {
+            let mut cmd = std::process::Command::new("git");
+            proxy.apply_to_command(&mut cmd, "skills.git");
+            assert_eq!(cmd.get_envs().count(), 0);
+            assert_eq!(proxy.git_http_proxy("skills.git"), None);
+        }
+    }
+
     fn runtime_proxy_cache_contains(cache_key: &str) -> bool {
         match runtime_proxy_client_cache().read() {
             Ok(guard) => guard.contains_key(cache_key),
@@ -0,0 +1,591 @@
+//! `zeroclaw secrets migrate|rotate`: rewrite config secrets in place.
+//!
+//! Both commands edit `config.toml` with `toml_edit`, so comments, ordering
+//! and formatting survive; only the registered secret values change. Files
+//! are replaced atomically through a temp file in the same directory.
+
+use crate::security::SecretStore;
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, TableLike, Value};
+
+/// Every config value encrypted at rest, as a dotted TOML path. `*` matches
+/// any table key and `[]` every array element. Must cover every field that
+/// `Config::save` encrypts (including serde aliases), so `secrets migrate`
+/// and `secrets rotate` never skip one.
+pub const SECRET_FIELDS: &[&str] = &[
+    "api_key",
+    "transcription.api_key",
+    "composio.api_key",
+    "proxy.http_proxy",
+    "proxy.https_proxy",
+    "proxy.all_proxy",
+    "browser.computer_use.api_key",
+    "web_search.api_key",
+    "web_search.brave_api_key",
+    "web_search.perplexity_api_key",
+    "web_search.exa_api_key",
+    "web_search.jina_api_key",
+    "web_fetch.api_key",
+    "skills.clawhub_token",
+    "storage.provider.config.db_url",
+    "storage.provider.config.dbURL",
+    "storage.provider.config.database_url",
+    "storage.provider.config.databaseUrl",
+    "memory.qdrant.api_key",
+    "reliability.api_keys[]",
+    "gateway.paired_tokens[]",
+    "gateway.admin_token",
+    "gateway.node_control.auth_token",
+    "tunnel.cloudflare.token",
+    "tunnel.ngrok.auth_token",
+    "model_routes[].api_key",
+    "embedding_routes[].api_key",
+    "agents.*.api_key",
+    "channels_config.telegram.bot_token",
+    "channels_config.discord.bot_token",
+    "channels_config.slack.bot_token",
+    "channels_config.slack.app_token",
+    "channels_config.mattermost.bot_token",
+    "channels_config.webhook.secret",
+    "channels_config.webhook.hmac_secret",
+    "channels_config.matrix.access_token",
+    "channels_config.whatsapp.access_token",
+    "channels_config.whatsapp.app_secret",
+    "channels_config.whatsapp.verify_token",
+    "channels_config.linq.api_token",
+    "channels_config.linq.signing_secret",
+    "channels_config.nextcloud_talk.app_token",
+    "channels_config.nextcloud_talk.webhook_secret",
+    "channels_config.irc.server_password",
+    "channels_config.irc.nickserv_password",
+    "channels_config.irc.sasl_password",
+    "channels_config.lark.app_secret",
+    "channels_config.lark.encrypt_key",
+    "channels_config.lark.verification_token",
+    "channels_config.dingtalk.client_secret",
+    "channels_config.qq.app_secret",
+    "channels_config.nostr.private_key",
+    "channels_config.clawdtalk.api_key",
+    "channels_config.clawdtalk.webhook_secret",
+    "channels_config.wati.api_token",
+    "channels_config.email.password",
+    "channels_config.feishu.app_secret",
+    "channels_config.feishu.encrypt_key",
+    "channels_config.feishu.verification_token",
+];
+
+/// Outcome of `zeroclaw secrets rotate`.
+#[derive(Debug, Default)]
+pub struct RotationSummary {
+    /// Secret values re-encrypted in `config.toml`.
+    pub config_values: usize,
+    /// Other files under the config directory that were re-encrypted
+    /// (auth profiles, pending OAuth logins, the OTP secret).
+    pub other_files: Vec<PathBuf>,
+}
+
+/// Encrypt every plaintext secret in `config_path` and upgrade legacy `enc:`
+/// values to `enc2:`. Returns how many values changed; the file is left
+/// untouched when nothing needed encrypting.
+pub fn migrate_config_file(config_path: &Path) -> Result<usize> {
+    let (mut doc, store) = open_config(config_path)?;
+
+    let mut changed = 0;
+    visit_secrets(&mut doc, &mut |field, raw| {
+        if raw.is_empty() || SecretStore::is_secure_encrypted(raw) {
+            return Ok(None);
+        }
+        let (plaintext, migrated) = store
+            .decrypt_and_migrate(raw)
+            .with_context(|| format!("Failed to decrypt config.{field}"))?;
+        let encrypted = match migrated {
+            Some(encrypted) => encrypted,
+            None => store
+                .encrypt(&plaintext)
+                .with_context(|| format!("Failed to encrypt config.{field}"))?,
+        };
+        changed += 1;
+        Ok(Some(encrypted))
+    })?;
+
+    if changed > 0 {
+        write_atomically(config_path, doc.to_string().as_bytes())?;
+    }
+    Ok(changed)
+}
+
+/// Re-encrypt every secret under a freshly generated key, then replace the
+/// old key. Plaintext values are encrypted along the way.
+///
+/// Everything is decrypted and re-encrypted in memory before any file is
+/// written, so a wrong key or corrupt value aborts with nothing changed.
+pub fn rotate_secrets(config_path: &Path) -> Result<RotationSummary> {
+    let zeroclaw_dir = config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    let (doc, store) = open_config(config_path)?;
+    let staged = store.stage_rotation()?;
+    let prepared = reencrypt_all(config_path, zeroclaw_dir, doc, &store, &staged);
+    let (mut summary, rewrites) = match prepared {
+        Ok(prepared) => prepared,
+        Err(err) => {
+            if let Err(discard_err) = staged.discard_rotation() {
+                tracing::warn!("{discard_err:#}");
+            }
+            return Err(err);
+        }
+    };
+
+    for (path, contents) in &rewrites {
+        write_atomically(path, contents)?;
+        if path != config_path {
+            summary.other_files.push(path.clone());
+        }
+    }
+    staged.commit_rotation()?;
+    Ok(summary)
+}
+
+/// Decrypt everything under `store` and encrypt it again under `staged`,
+/// returning the new file contents without writing them.
+fn reencrypt_all(
+    config_path: &Path,
+    zeroclaw_dir: &Path,
+    mut doc: DocumentMut,
+    store: &SecretStore,
+    staged: &SecretStore,
+) -> Result<(RotationSummary, Vec<(PathBuf, Vec<u8>)>)> {
+    let reencrypt = |label: &str, raw: &str| -> Result<String> {
+        let plaintext = store
+            .decrypt(raw)
+            .with_context(|| format!("Failed to decrypt {label}"))?;
+        staged
+            .encrypt(&plaintext)
+            .with_context(|| format!("Failed to encrypt {label}"))
+    };
+
+    let mut summary = RotationSummary::default();
+    visit_secrets(&mut doc, &mut |field, raw| {
+        if raw.is_empty() {
+            return Ok(None);
+        }
+        summary.config_values += 1;
+        reencrypt(&format!("config.{field}"), raw).map(Some)
+    })?;
+    let mut rewrites = vec![(config_path.to_path_buf(), doc.to_string().into_bytes())];
+
+    let otp_path = crate::security::otp::secret_file_path(zeroclaw_dir);
+    if otp_path.exists() {
+        let raw = std::fs::read_to_string(&otp_path)
+            .with_context(|| format!("Failed to read {}", otp_path.display()))?;
+        let raw = raw.trim();
+        if SecretStore::is_encrypted(raw) {
+            let label = otp_path.display().to_string();
+            rewrites.push((otp_path, reencrypt(&label, raw)?.into_bytes()));
+        }
+    }
+
+    for path in auth_state_files(zeroclaw_dir)? {
+        let raw =
+            std::fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut json: serde_json::Value = serde_json::from_slice(&raw)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        let label = path.display().to_string();
+        let mut changed = false;
+        reencrypt_json_strings(&mut json, &mut |raw| {
+            changed = true;
+            reencrypt(&label, raw)
+        })?;
+        if changed {
+            let json = serde_json::to_vec_pretty(&json)
+                .with_context(|| format!("Failed to serialize {}", path.display()))?;
+            rewrites.push((path, json));
+        }
+    }
+
+    Ok((summary, rewrites))
+}
+
+fn open_config(config_path: &Path) -> Result<(DocumentMut, SecretStore)> {
+    let zeroclaw_dir = config_path
+        .parent()
+        .context("Config path must have a parent directory")?;
+    let contents = std::fs::read_to_string(config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    let doc: DocumentMut = contents
+        .parse()
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    let encrypt = doc
+        .get("secrets")
+        .and_then(|secrets| secrets.get("encrypt"))
+        .and_then(Item::as_bool)
+        .unwrap_or(true);
+    anyhow::ensure!(
+        encrypt,
+        "secrets.encrypt is false in {}; set it to true to store secrets encrypted",
+        config_path.display()
+    );
+    Ok((doc, SecretStore::new(zeroclaw_dir, true)))
+}
+
+type SecretVisitor<'a> = dyn FnMut(&str, &str) -> Result<Option<String>> + 'a;
+
+/// Call `visit(field, value)` for every string at a [`SECRET_FIELDS`] path and
+/// store the returned replacement, keeping the value's surrounding whitespace
+/// and comments.
+fn visit_secrets(doc: &mut DocumentMut, visit: &mut SecretVisitor<'_>) -> Result<()> {
+    for field in SECRET_FIELDS {
+        let path = field_segments(field);
+        visit_table(doc.as_table_mut(), &path, field, visit)?;
+    }
+    Ok(())
+}
+
+/// `model_routes[].api_key` → `["model_routes", "[]", "api_key"]`.
+fn field_segments(field: &str) -> Vec<&str> {
+    field
+        .split('.')
+        .flat_map(|segment| match segment.strip_suffix("[]") {
+            Some(name) => vec![name, "[]"],
+            None => vec![segment],
+        })
+        .collect()
+}
+
+fn visit_table(
+    table: &mut dyn TableLike,
+    path: &[&str],
+    field: &str,
+    visit: &mut SecretVisitor<'_>,
+) -> Result<()> {
+    let Some((segment, rest)) = path.split_first() else {
+        return Ok(());
+    };
+    if *segment == "*" {
+        for (_, item) in table.iter_mut() {
+            visit_item(item, rest, field, visit)?;
+        }
+    } else if let Some(item) = table.get_mut(segment) {
+        visit_item(item, rest, field, visit)?;
+    }
+    Ok(())
+}
+
+fn visit_item(
+    item: &mut Item,
+    path: &[&str],
+    field: &str,
+    visit: &mut SecretVisitor<'_>,
+) -> Result<()> {
+    match item {
+        Item::Value(value) => visit_value(value, path, field, visit),
+        Item::Table(table) => visit_table(table, path, field, visit),
+        Item::ArrayOfTables(tables) if path.first() == Some(&"[]") => {
+            for table in tables.iter_mut() {
+                visit_table(table, &path[1..], field, visit)?;
+            }
+            Ok(())
+        }
+        Item::ArrayOfTables(_) | Item::None => Ok(()),
+    }
+}
+
+fn visit_value(
+    value: &mut Value,
+    path: &[&str],
+    field: &str,
+    visit: &mut SecretVisitor<'_>,
+) -> Result<()> {
+    match path.split_first() {
+        None => {
+            let Some(raw) = value.as_str() else {
+                return Ok(());
+            };
+            if let Some(replacement) = visit(field, raw)? {
+                let decor = value.decor().clone();
+                *value = Value::from(replacement);
+                *value.decor_mut() = decor;
+            }
+            Ok(())
+        }
+        Some((&"[]", rest)) => {
+            if let Some(array) = value.as_array_mut() {
+                for element in array.iter_mut() {
+                    visit_value(element, rest, field, visit)?;
+                }
+            }
+            Ok(())
+        }
+        Some(_) => match value.as_inline_table_mut() {
+            Some(table) => visit_table(table, path, field, visit),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Auth profiles and pending OAuth logins (`auth-*.json`) share the config
+/// directory's key.
+fn auth_state_files(zeroclaw_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(zeroclaw_dir)
+        .with_context(|| format!("Failed to read {}", zeroclaw_dir.display()))?
+    {
+        let path = entry?.path();
+        let is_auth_state = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("auth-") && name.ends_with(".json"));
+        if is_auth_state && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn reencrypt_json_strings(
+    value: &mut serde_json::Value,
+    reencrypt: &mut dyn FnMut(&str) -> Result<String>,
+) -> Result<()> {
+    match value {
+        serde_json::Value::String(raw) if SecretStore::is_encrypted(raw) => {
+            *raw = reencrypt(raw)?;
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                reencrypt_json_strings(value, reencrypt)?;
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for value in map.values_mut() {
+                reencrypt_json_strings(value, reencrypt)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace `path` through a temp file in the same directory. The temp file is
+/// created owner-only (0600), which the renamed file keeps.
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+
+    let dir = path
+        .parent()
+        .context("Secret file path must have a parent directory")?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("Failed to create a temp file in {}", dir.display()))?;
+    tmp.write_all(contents)
+        .and_then(|()| tmp.as_file().sync_all())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    tmp.persist(path)
+        .map_err(|err| err.error)
+        .with_context(|| format!("Failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    const PLAINTEXT_CONFIG: &str = r#"# my zeroclaw setup
+default_provider = "openrouter"
+default_temperature = 0.7
+api_key = "sk-root-plaintext" # rotated 2026-09
+
+[skills]
+clawhub_token = "clawhub-plaintext"
+
+[gateway]
+paired_tokens = ["zc_first", "zc_second"]
+
+[[model_routes]]
+hint = "fast"
+provider = "groq"
+model = "llama"
+api_key = "groq-plaintext"
+
+[agents.worker]
+provider = "openrouter"
+model = "model-test"
+api_key = "agent-plaintext"
+
+# Channel credentials
+[channels_config.webhook]
+port = 8080
+secret = "webhook-plaintext"
+
+[channels_config.email]
+imap_host = "imap.example.com"
+smtp_host = "smtp.example.com"
+username = "bot@example.com"
+password = "email-plaintext"
+from_address = "bot@example.com"
+"#;
+
+    const PLAINTEXTS: &[&str] = &[
+        "sk-root-plaintext",
+        "clawhub-plaintext",
+        "zc_first",
+        "zc_second",
+        "groq-plaintext",
+        "agent-plaintext",
+        "webhook-plaintext",
+        "email-plaintext",
+    ];
+
+    fn write_config(dir: &Path, contents: &str) -> PathBuf {
+        let path = dir.join("config.toml");
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn migrate_encrypts_plaintext_and_keeps_comments() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(dir.path(), PLAINTEXT_CONFIG);
+
+        assert_eq!(migrate_config_file(&path).unwrap(), PLAINTEXTS.len());
+        let migrated = std::fs::read_to_string(&path).unwrap();
+        for plaintext in PLAINTEXTS {
+            assert!(!migrated.contains(plaintext), "{plaintext} left in config");
+        }
+        assert!(migrated.starts_with("# my zeroclaw setup\n"));
+        assert!(migrated.contains("# rotated 2026-09"));
+        assert!(migrated.contains("# Channel credentials"));
+        assert!(migrated.contains("default_provider = \"openrouter\""));
+
+        let store = SecretStore::with_passphrase(dir.path(), true, None);
+        let config: Config = toml::from_str(&migrated).unwrap();
+        assert_eq!(
+            store.decrypt(config.api_key.as_deref().unwrap()).unwrap(),
+            "sk-root-plaintext"
+        );
+        assert_eq!(
+            store
+                .decrypt(config.model_routes[0].api_key.as_deref().unwrap())
+                .unwrap(),
+            "groq-plaintext"
+        );
+        assert_eq!(
+            store
+                .decrypt(&config.channels_config.email.unwrap().password)
+                .unwrap(),
+            "email-plaintext"
+        );
+
+        // A second run finds nothing left to do and leaves the file alone.
+        assert_eq!(migrate_config_file(&path).unwrap(), 0);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), migrated);
+    }
+
+    #[test]
+    fn migrate_refuses_when_encryption_is_disabled() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(
+            dir.path(),
+            "api_key = \"sk-plain\"\n\n[secrets]\nencrypt = false\n",
+        );
+        let err = migrate_config_file(&path).unwrap_err().to_string();
+        assert!(err.contains("secrets.encrypt is false"), "{err}");
+        assert!(std::fs::read_to_string(&path).unwrap().contains("sk-plain"));
+    }
+
+    #[test]
+    fn rotate_reencrypts_config_and_auth_state_under_a_new_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(dir.path(), PLAINTEXT_CONFIG);
+        migrate_config_file(&path).unwrap();
+        let store = SecretStore::with_passphrase(dir.path(), true, None);
+        let old_key = std::fs::read_to_string(store.key_path()).unwrap();
+        let profile_token = store.encrypt("oauth-refresh-token").unwrap();
+        std::fs::write(
+            dir.path().join("auth-profiles.json"),
+            serde_json::json!({ "profiles": { "default": { "token": profile_token } } })
+                .to_string(),
+        )
+        .unwrap();
+
+        let summary = rotate_secrets(&path).unwrap();
+        assert_eq!(summary.config_values, PLAINTEXTS.len());
+        assert_eq!(summary.other_files, [dir.path().join("auth-profiles.json")]);
+        assert_ne!(std::fs::read_to_string(store.key_path()).unwrap(), old_key);
+
+        let rotated = std::fs::read_to_string(&path).unwrap();
+        assert!(rotated.contains("# Channel credentials"));
+        let reopened = SecretStore::with_passphrase(dir.path(), true, None);
+        let config: Config = toml::from_str(&rotated).unwrap();
+        assert_eq!(
+            reopened
+                .decrypt(config.skills.clawhub_token.as_deref().unwrap())
+                .unwrap(),
+            "clawhub-plaintext"
+        );
+        let profiles: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(dir.path().join("auth-profiles.json")).unwrap())
+                .unwrap();
+        let token = profiles["profiles"]["default"]["token"].as_str().unwrap();
+        assert_eq!(reopened.decrypt(token).unwrap(), "oauth-refresh-token");
+        assert!(store.decrypt(&profile_token).is_err());
+    }
+
+    #[test]
+    fn rotate_with_wrong_key_changes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let foreign = SecretStore::with_passphrase(other.path(), true, None);
+        let encrypted = foreign.encrypt("sk-elsewhere").unwrap();
+        let contents = format!("api_key = \"{encrypted}\"\n");
+        let path = write_config(dir.path(), &contents);
+
+        let err = format!("{:#}", rotate_secrets(&path).unwrap_err());
+        assert!(err.contains("config.api_key"), "{err}");
+        assert!(err.contains(".secret_key"), "{err}");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        // The staged key is dropped, so the next attempt starts clean.
+        assert!(!dir.path().join(".secret_key.rotating").exists());
+    }
+
+    #[tokio::test]
+    async fn registry_covers_every_field_save_encrypts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let mut config: Config = toml::from_str(PLAINTEXT_CONFIG).unwrap();
+        config.config_path = path.clone();
+        config.workspace_dir = dir.path().join("workspace");
+        config.web_search.api_key = Some("search-plaintext".into());
+        config.memory.qdrant.api_key = Some("qdrant-plaintext".into());
+        config.gateway.admin_token = Some("admin-plaintext".into());
+        config.save().await.unwrap();
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        let mut doc: DocumentMut = saved.parse().unwrap();
+        let mut registered = 0;
+        visit_secrets(&mut doc, &mut |_, raw| {
+            if SecretStore::is_encrypted(raw) {
+                registered += 1;
+            }
+            Ok(None)
+        })
+        .unwrap();
+        assert_eq!(registered, saved.matches("\"enc2:").count());
+        assert_eq!(registered, PLAINTEXTS.len() + 3);
+    }
+
+    #[test]
+    fn debug_and_diff_output_never_show_secrets() {
+        let config: Config = toml::from_str(PLAINTEXT_CONFIG).unwrap();
+        let debug = format!("{config:?}");
+        let diff = crate::config::diff::render_diff(
+            &crate::config::diff::diff_against_defaults(&config).unwrap(),
+        );
+        for plaintext in PLAINTEXTS {
+            assert!(!debug.contains(plaintext), "{plaintext} in Debug output");
+            assert!(!diff.contains(plaintext), "{plaintext} in config diff");
+        }
+    }
+}
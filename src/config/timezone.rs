@@ -0,0 +1,188 @@
+//! Timezone used when showing time to the model and the user.
+//!
+//! `[agent] timezone` names an IANA zone (`"Asia/Ho_Chi_Minh"`); when it is
+//! unset the host's zone is used. Timestamps are still stored in UTC (or as
+//! RFC 3339 with an explicit offset); this module only decides how "now" is
+//! rendered: the system prompt's Date & Time section, the `[...]` stamp on
+//! each user turn, `{{now}}` in skill instructions, and the default zone
+//! for cron expressions without their own `tz`.
+//!
+//! The configured zone is process-wide, set from config load alongside the
+//! runtime proxy settings.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+use std::borrow::Cow;
+use std::sync::{OnceLock, RwLock};
+
+static CONFIGURED: RwLock<Option<Tz>> = RwLock::new(None);
+
+/// Parse an IANA zone name such as `Europe/Berlin`.
+pub fn parse(name: &str) -> Result<Tz> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| anyhow!("unknown IANA timezone: {name}"))
+}
+
+/// Record the zone from `[agent] timezone` (`None` = host zone).
+pub fn set_configured(zone: Option<Tz>) {
+    match CONFIGURED.write() {
+        Ok(mut guard) => *guard = zone,
+        Err(poisoned) => *poisoned.into_inner() = zone,
+    }
+}
+
+/// The zone explicitly set in config, if any.
+pub fn configured() -> Option<Tz> {
+    match CONFIGURED.read() {
+        Ok(guard) => *guard,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Configured zone, else the host's zone when it can be named. `None`
+/// means "host local time" with an offset but no IANA name.
+pub fn effective() -> Option<Tz> {
+    configured().or_else(host_zone)
+}
+
+/// Best-effort IANA name of the host zone: `TZ`, then the
+/// `/etc/localtime` symlink, then `/etc/timezone`.
+fn host_zone() -> Option<Tz> {
+    static HOST: OnceLock<Option<Tz>> = OnceLock::new();
+    *HOST.get_or_init(|| {
+        let from_env = std::env::var("TZ")
+            .ok()
+            .and_then(|tz| parse(tz.trim_start_matches(':')).ok());
+        from_env
+            .or_else(|| {
+                let target = std::fs::read_link("/etc/localtime").ok()?;
+                let target = target.to_string_lossy();
+                let (_, name) = target.split_once("zoneinfo/")?;
+                parse(name).ok()
+            })
+            .or_else(|| parse(&std::fs::read_to_string("/etc/timezone").ok()?).ok())
+    })
+}
+
+/// `now` in `zone` (host local time when `None`) and the zone's label.
+fn localize(now: DateTime<Utc>, zone: Option<Tz>) -> (DateTime<FixedOffset>, &'static str) {
+    match zone {
+        Some(tz) => (now.with_timezone(&tz).fixed_offset(), tz.name()),
+        None => (now.with_timezone(&Local).fixed_offset(), "host local time"),
+    }
+}
+
+/// Body of the system prompt's Date & Time section, e.g.
+/// `Sunday, 2026-03-08 14:05:00 (UTC+07:00, Asia/Ho_Chi_Minh)`.
+pub fn prompt_datetime(now: DateTime<Utc>, zone: Option<Tz>) -> String {
+    let (local, name) = localize(now, zone);
+    format!(
+        "{} (UTC{}, {name})",
+        local.format("%A, %Y-%m-%d %H:%M:%S"),
+        local.format("%:z")
+    )
+}
+
+/// Stamp prefixed to user turns, e.g. `2026-03-08 14:05:00 +07:00`.
+pub fn transcript_stamp(now: DateTime<Utc>, zone: Option<Tz>) -> String {
+    let (local, _) = localize(now, zone);
+    local.format("%Y-%m-%d %H:%M:%S %:z").to_string()
+}
+
+/// Calendar date of `now` in `zone`.
+pub fn local_date(now: DateTime<Utc>, zone: Option<Tz>) -> NaiveDate {
+    localize(now, zone).0.date_naive()
+}
+
+/// Replace `{{now}}`, `{{today}}` and `{{timezone}}` in skill text.
+pub fn interpolate(text: &str, now: DateTime<Utc>, zone: Option<Tz>) -> Cow<'_, str> {
+    if !text.contains("{{") {
+        return Cow::Borrowed(text);
+    }
+    let (local, name) = localize(now, zone);
+    Cow::Owned(
+        text.replace("{{now}}", &transcript_stamp(now, zone))
+            .replace("{{today}}", &local.format("%Y-%m-%d").to_string())
+            .replace("{{timezone}}", name),
+    )
+}
+
+/// Render a stored UTC instant for display in the effective zone.
+pub fn display(at: DateTime<Utc>) -> String {
+    transcript_stamp(at, effective())
+}
+
+/// [`display`] for an RFC 3339 string; anything else is shown as stored.
+pub fn display_stored(stored: &str) -> String {
+    DateTime::parse_from_rfc3339(stored)
+        .map_or_else(|_| stored.to_string(), |at| display(at.with_timezone(&Utc)))
+}
+
+/// [`prompt_datetime`] for the current time in the effective zone.
+pub fn prompt_datetime_now() -> String {
+    prompt_datetime(Utc::now(), effective())
+}
+
+/// [`transcript_stamp`] for the current time in the effective zone.
+pub fn transcript_stamp_now() -> String {
+    transcript_stamp(Utc::now(), effective())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn fixed_clock() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 8, 7, 5, 0).unwrap()
+    }
+
+    #[test]
+    fn prompt_datetime_renders_in_the_given_zone() {
+        let zone = parse("Asia/Ho_Chi_Minh").unwrap();
+        assert_eq!(
+            prompt_datetime(fixed_clock(), Some(zone)),
+            "Sunday, 2026-03-08 14:05:00 (UTC+07:00, Asia/Ho_Chi_Minh)"
+        );
+        assert_eq!(
+            transcript_stamp(fixed_clock(), Some(zone)),
+            "2026-03-08 14:05:00 +07:00"
+        );
+
+        // Same instant is still Saturday evening in Los Angeles.
+        let zone = parse("America/Los_Angeles").unwrap();
+        assert_eq!(
+            prompt_datetime(fixed_clock(), Some(zone)),
+            "Saturday, 2026-03-07 23:05:00 (UTC-08:00, America/Los_Angeles)"
+        );
+        assert_eq!(
+            local_date(fixed_clock(), Some(zone)),
+            NaiveDate::from_ymd_opt(2026, 3, 7).unwrap()
+        );
+    }
+
+    #[test]
+    fn interpolate_fills_known_variables_only() {
+        let zone = parse("Europe/Berlin").ok();
+        assert_eq!(
+            interpolate(
+                "Today is {{today}} ({{timezone}}); now {{now}}. Keep {{other}}.",
+                fixed_clock(),
+                zone
+            ),
+            "Today is 2026-03-08 (Europe/Berlin); now 2026-03-08 08:05:00 +01:00. Keep {{other}}."
+        );
+        assert!(matches!(
+            interpolate("no variables", fixed_clock(), zone),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn parse_rejects_unknown_zones() {
+        assert!(parse("Mars/Olympus_Mons").is_err());
+        assert!(parse(" UTC ").is_ok());
+    }
+}
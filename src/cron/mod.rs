@@ -36,13 +36,13 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             for job in jobs {
                 let last_run = job
                     .last_run
-                    .map_or_else(|| "never".into(), |d| d.to_rfc3339());
+                    .map_or_else(|| "never".into(), crate::config::timezone::display);
                 let last_status = job.last_status.unwrap_or_else(|| "n/a".into());
                 println!(
                     "- {} | {:?} | next={} | last={} ({})",
                     job.id,
                     job.schedule,
-                    job.next_run.to_rfc3339(),
+                    crate::config::timezone::display(job.next_run),
                     last_run,
                     last_status,
                 );
@@ -67,7 +67,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added cron job {}", job.id);
             println!("  Expr: {}", job.expression);
-            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Next: {}", crate::config::timezone::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
@@ -78,7 +78,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let schedule = Schedule::At { at };
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
-            println!("  At  : {}", job.next_run.to_rfc3339());
+            println!("  At  : {}", crate::config::timezone::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
@@ -87,14 +87,17 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = add_shell_job(config, None, schedule, &command)?;
             println!("✅ Added interval cron job {}", job.id);
             println!("  Every(ms): {every_ms}");
-            println!("  Next     : {}", job.next_run.to_rfc3339());
+            println!(
+                "  Next     : {}",
+                crate::config::timezone::display(job.next_run)
+            );
             println!("  Cmd      : {}", job.command);
             Ok(())
         }
         crate::CronCommands::Once { delay, command } => {
             let job = add_once(config, &delay, &command)?;
             println!("✅ Added one-shot cron job {}", job.id);
-            println!("  At  : {}", job.next_run.to_rfc3339());
+            println!("  At  : {}", crate::config::timezone::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
@@ -146,7 +149,7 @@ pub fn handle_command(command: crate::CronCommands, config: &Config) -> Result<(
             let job = update_job(config, &id, patch)?;
             println!("\u{2705} Updated cron job {}", job.id);
             println!("  Expr: {}", job.expression);
-            println!("  Next: {}", job.next_run.to_rfc3339());
+            println!("  Next: {}", crate::config::timezone::display(job.next_run));
             println!("  Cmd : {}", job.command);
             Ok(())
         }
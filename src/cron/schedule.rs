@@ -4,16 +4,31 @@ use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use cron::Schedule as CronExprSchedule;
 use std::str::FromStr;
 
+/// Cron expressions without their own `tz` follow `[agent] timezone` when it
+/// is set, and UTC otherwise.
 pub fn next_run_for_schedule(schedule: &Schedule, from: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    next_run_in_zone(schedule, from, crate::config::timezone::configured())
+}
+
+fn next_run_in_zone(
+    schedule: &Schedule,
+    from: DateTime<Utc>,
+    default_zone: Option<chrono_tz::Tz>,
+) -> Result<DateTime<Utc>> {
     match schedule {
         Schedule::Cron { expr, tz } => {
             let normalized = normalize_expression(expr)?;
             let cron = CronExprSchedule::from_str(&normalized)
                 .with_context(|| format!("Invalid cron expression: {expr}"))?;
 
-            if let Some(tz_name) = tz {
-                let timezone = chrono_tz::Tz::from_str(tz_name)
-                    .with_context(|| format!("Invalid IANA timezone: {tz_name}"))?;
+            let timezone = match tz {
+                Some(tz_name) => Some(
+                    chrono_tz::Tz::from_str(tz_name)
+                        .with_context(|| format!("Invalid IANA timezone: {tz_name}"))?,
+                ),
+                None => default_zone,
+            };
+            if let Some(timezone) = timezone {
                 let localized_from = from.with_timezone(&timezone);
                 let next_local = cron.after(&localized_from).next().ok_or_else(|| {
                     anyhow::anyhow!("No future occurrence for expression: {expr}")
@@ -111,4 +126,35 @@ mod tests {
         let next = next_run_for_schedule(&schedule, from).unwrap();
         assert_eq!(next, Utc.with_ymd_and_hms(2026, 2, 16, 17, 0, 0).unwrap());
     }
+
+    #[test]
+    fn cron_without_tz_follows_the_agent_timezone_across_dst() {
+        let new_york = crate::config::timezone::parse("America/New_York").unwrap();
+        let schedule = Schedule::Cron {
+            expr: "0 9 * * *".into(),
+            tz: None,
+        };
+
+        // Saturday 10:00 EST; the next 09:00 is Sunday, after clocks spring
+        // forward, so it lands at 13:00 UTC instead of 14:00.
+        let from = Utc.with_ymd_and_hms(2026, 3, 7, 15, 0, 0).unwrap();
+        let next = next_run_in_zone(&schedule, from, Some(new_york)).unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 3, 8, 13, 0, 0).unwrap());
+        assert_eq!(
+            crate::config::timezone::transcript_stamp(next, Some(new_york)),
+            "2026-03-08 09:00:00 -04:00"
+        );
+
+        // An explicit tz still wins, and no zone at all means UTC.
+        let pinned = Schedule::Cron {
+            expr: "0 9 * * *".into(),
+            tz: Some("UTC".into()),
+        };
+        let utc_next = Utc.with_ymd_and_hms(2026, 3, 8, 9, 0, 0).unwrap();
+        assert_eq!(
+            next_run_in_zone(&pinned, from, Some(new_york)).unwrap(),
+            utc_next
+        );
+        assert_eq!(next_run_in_zone(&schedule, from, None).unwrap(), utc_next);
+    }
 }
@@ -173,8 +173,9 @@ async fn run_agent_job(
     let model_override = job.model.clone();
 
     let run_result = match job.session_target {
+        // WASM tools called by scheduled jobs queue behind interactive ones.
         SessionTarget::Main | SessionTarget::Isolated => {
-            crate::agent::run(
+            crate::tools::wasm_queue::scheduled(crate::agent::run(
                 config.clone(),
                 Some(prefixed_prompt),
                 None,
@@ -182,7 +183,7 @@ async fn run_agent_job(
                 config.default_temperature,
                 vec![],
                 false,
-            )
+            ))
             .await
         }
     };
@@ -1,16 +1,30 @@
 use crate::config::Config;
 use anyhow::{bail, Result};
 use chrono::Utc;
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::path::PathBuf;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
 const STATUS_FLUSH_SECONDS: u64 = 5;
+/// A state file older than this no longer describes a running daemon.
+const STATE_STALE_SECONDS: i64 = 30;
 
 pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
-    // Pre-flight: check if port is already in use by another zeroclaw daemon
-    if let Err(_e) = check_port_available(&host, port).await {
+    // Pre-flight: check if the socket or port is already in use by another zeroclaw daemon
+    if let Some(path) = crate::gateway::listen::unix_socket_path(&host) {
+        if crate::gateway::listen::get_health(path)
+            .await
+            .is_some_and(|json| is_zeroclaw_health(&json))
+        {
+            tracing::info!("ZeroClaw daemon already running on {host}");
+            println!("✓ ZeroClaw daemon already running on {host}");
+            println!("  Use 'zeroclaw restart' to restart, or 'zeroclaw status' to check health.");
+            return Ok(());
+        }
+        // Anything else on the socket is refused when the gateway binds it.
+    } else if let Err(_e) = check_port_available(&host, port).await {
         // Port is in use - check if it's our daemon
         if is_zeroclaw_daemon_running(&host, port).await {
             tracing::info!("ZeroClaw daemon already running on {host}:{port}");
@@ -33,6 +47,10 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     crate::health::mark_component_ok("daemon");
 
+    if let Err(error) = crate::providers::validate_configured_providers(&config) {
+        tracing::warn!("Provider configuration problems (run `zeroclaw config validate`): {error}");
+    }
+
     if config.heartbeat.enabled {
         let _ =
             crate::heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
@@ -129,6 +147,34 @@ pub fn state_file_path(config: &Config) -> PathBuf {
         .join("daemon_state.json")
 }
 
+/// Runtime metrics (see [`crate::health::set_metric`]) from the state file
+/// of a running daemon, or `None` when no daemon wrote it recently.
+pub fn running_metrics(config: &Config) -> Option<BTreeMap<String, u64>> {
+    let raw = std::fs::read_to_string(state_file_path(config)).ok()?;
+    let snapshot: serde_json::Value = serde_json::from_str(&raw).ok()?;
+    metrics_if_fresh(&snapshot, Utc::now())
+}
+
+fn metrics_if_fresh(
+    snapshot: &serde_json::Value,
+    now: chrono::DateTime<Utc>,
+) -> Option<BTreeMap<String, u64>> {
+    let written_at = snapshot.get("written_at")?.as_str()?;
+    let written_at = chrono::DateTime::parse_from_rfc3339(written_at).ok()?;
+    if now.signed_duration_since(written_at).num_seconds() > STATE_STALE_SECONDS {
+        return None;
+    }
+    Some(
+        snapshot
+            .get("metrics")
+            .and_then(serde_json::Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, value)| Some((name.clone(), value.as_u64()?)))
+            .collect(),
+    )
+}
+
 fn spawn_state_writer(config: Config) -> JoinHandle<()> {
     tokio::spawn(async move {
         let path = state_file_path(&config);
@@ -190,171 +236,8 @@ where
 }
 
 async fn run_heartbeat_worker(config: Config) -> Result<()> {
-    let observer: std::sync::Arc<dyn crate::observability::Observer> =
-        std::sync::Arc::from(crate::observability::create_observer(&config.observability));
-    let engine = crate::heartbeat::engine::HeartbeatEngine::new(
-        config.heartbeat.clone(),
-        config.workspace_dir.clone(),
-        observer,
-    );
-    let delivery = heartbeat_delivery_target(&config)?;
-
-    let interval_mins = config.heartbeat.interval_minutes.max(5);
-    let mut interval = tokio::time::interval(Duration::from_secs(u64::from(interval_mins) * 60));
-
-    loop {
-        interval.tick().await;
-
-        let file_tasks = engine.collect_tasks().await?;
-        let tasks = heartbeat_tasks_for_tick(file_tasks, config.heartbeat.message.as_deref());
-        if tasks.is_empty() {
-            continue;
-        }
-
-        for task in tasks {
-            let prompt = format!("[Heartbeat Task] {task}");
-            let temp = config.default_temperature;
-            match crate::agent::run(
-                config.clone(),
-                Some(prompt),
-                None,
-                None,
-                temp,
-                vec![],
-                false,
-            )
-            .await
-            {
-                Ok(output) => {
-                    crate::health::mark_component_ok("heartbeat");
-                    if let Some(announcement) = heartbeat_announcement_text(&output) {
-                        if let Some((channel, target)) = &delivery {
-                            if let Err(e) = crate::cron::scheduler::deliver_announcement(
-                                &config,
-                                channel,
-                                target,
-                                &announcement,
-                            )
-                            .await
-                            {
-                                crate::health::mark_component_error(
-                                    "heartbeat",
-                                    format!("delivery failed: {e}"),
-                                );
-                                tracing::warn!("Heartbeat delivery failed: {e}");
-                            }
-                        }
-                    } else {
-                        tracing::debug!("Heartbeat returned NO_REPLY sentinel; skipping delivery");
-                    }
-                }
-                Err(e) => {
-                    crate::health::mark_component_error("heartbeat", e.to_string());
-                    tracing::warn!("Heartbeat task failed: {e}");
-                }
-            }
-        }
-    }
-}
-
-fn heartbeat_announcement_text(output: &str) -> Option<String> {
-    if crate::cron::scheduler::is_no_reply_sentinel(output) {
-        return None;
-    }
-    if output.trim().is_empty() {
-        return Some("heartbeat task executed".to_string());
-    }
-    Some(output.to_string())
-}
-
-fn heartbeat_tasks_for_tick(
-    file_tasks: Vec<String>,
-    fallback_message: Option<&str>,
-) -> Vec<String> {
-    if !file_tasks.is_empty() {
-        return file_tasks;
-    }
-
-    fallback_message
-        .map(str::trim)
-        .filter(|message| !message.is_empty())
-        .map(|message| vec![message.to_string()])
-        .unwrap_or_default()
-}
-
-fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>> {
-    let channel = config
-        .heartbeat
-        .target
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty());
-    let target = config
-        .heartbeat
-        .to
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty());
-
-    match (channel, target) {
-        (None, None) => Ok(None),
-        (Some(_), None) => anyhow::bail!("heartbeat.to is required when heartbeat.target is set"),
-        (None, Some(_)) => anyhow::bail!("heartbeat.target is required when heartbeat.to is set"),
-        (Some(channel), Some(target)) => {
-            validate_heartbeat_channel_config(config, channel)?;
-            Ok(Some((channel.to_string(), target.to_string())))
-        }
-    }
-}
-
-fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<()> {
-    let normalized = channel.to_ascii_lowercase();
-    match normalized.as_str() {
-        "telegram" => {
-            if config.channels_config.telegram.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to telegram but channels_config.telegram is not configured"
-                );
-            }
-        }
-        "discord" => {
-            if config.channels_config.discord.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to discord but channels_config.discord is not configured"
-                );
-            }
-        }
-        "slack" => {
-            if config.channels_config.slack.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to slack but channels_config.slack is not configured"
-                );
-            }
-        }
-        "mattermost" => {
-            if config.channels_config.mattermost.is_none() {
-                anyhow::bail!(
-                    "heartbeat.target is set to mattermost but channels_config.mattermost is not configured"
-                );
-            }
-        }
-        "whatsapp" | "whatsapp_web" => {
-            let wa = config.channels_config.whatsapp.as_ref().ok_or_else(|| {
-                anyhow::anyhow!(
-                    "heartbeat.target is set to {channel} but channels_config.whatsapp is not configured"
-                )
-            })?;
-
-            if normalized == "whatsapp_web" && wa.is_cloud_config() && !wa.is_web_config() {
-                anyhow::bail!(
-                    "heartbeat.target is set to whatsapp_web but channels_config.whatsapp is configured for cloud mode (set session_path for web mode)"
-                );
-            }
-        }
-        other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
-    }
-
-    Ok(())
+    let runner = crate::heartbeat::runner::HeartbeatRunner::from_config(&config).await?;
+    std::sync::Arc::new(runner).run_forever().await
 }
 
 fn has_supervised_channels(config: &Config) -> bool {
@@ -381,6 +264,11 @@ async fn check_port_available(host: &str, port: u16) -> Result<()> {
     }
 }
 
+/// Our health endpoint has "status" and "runtime.components".
+fn is_zeroclaw_health(json: &serde_json::Value) -> bool {
+    json.get("status").is_some() && json.get("runtime").is_some()
+}
+
 /// Check if a running daemon on this port is our zeroclaw daemon
 async fn is_zeroclaw_daemon_running(host: &str, port: u16) -> bool {
     let url = format!("http://{}:{}/health", host, port);
@@ -393,8 +281,7 @@ async fn is_zeroclaw_daemon_running(host: &str, port: u16) -> bool {
                 if resp.status().is_success() {
                     // Check if response looks like our health endpoint
                     if let Ok(json) = resp.json::<serde_json::Value>().await {
-                        // Our health endpoint has "status" and "runtime.components"
-                        json.get("status").is_some() && json.get("runtime").is_some()
+                        is_zeroclaw_health(&json)
                     } else {
                         false
                     }
@@ -432,6 +319,25 @@ mod tests {
         assert_eq!(path, tmp.path().join("daemon_state.json"));
     }
 
+    #[test]
+    fn running_metrics_ignore_stale_state() {
+        let now = Utc::now();
+        let snapshot = |written_at: chrono::DateTime<Utc>| {
+            serde_json::json!({
+                "written_at": written_at.to_rfc3339(),
+                "metrics": {"wasm_queue_depth": 3, "wasm_executions_running": 4}
+            })
+        };
+
+        let fresh = metrics_if_fresh(&snapshot(now - chrono::Duration::seconds(5)), now).unwrap();
+        assert_eq!(fresh.get("wasm_queue_depth"), Some(&3));
+        assert_eq!(fresh.len(), 2);
+        assert_eq!(
+            metrics_if_fresh(&snapshot(now - chrono::Duration::seconds(120)), now),
+            None
+        );
+    }
+
     #[tokio::test]
     async fn supervisor_marks_error_and_restart_on_failure() {
         let handle = spawn_component_supervisor("daemon-test-fail", 1, 1, || async {
@@ -542,156 +448,4 @@ mod tests {
         });
         assert!(has_supervised_channels(&config));
     }
-
-    #[test]
-    fn heartbeat_tasks_use_file_tasks_when_available() {
-        let tasks =
-            heartbeat_tasks_for_tick(vec!["From file".to_string()], Some("Fallback from config"));
-        assert_eq!(tasks, vec!["From file".to_string()]);
-    }
-
-    #[test]
-    fn heartbeat_tasks_fall_back_to_config_message() {
-        let tasks = heartbeat_tasks_for_tick(vec![], Some("  check london time  "));
-        assert_eq!(tasks, vec!["check london time".to_string()]);
-    }
-
-    #[test]
-    fn heartbeat_tasks_ignore_empty_fallback_message() {
-        let tasks = heartbeat_tasks_for_tick(vec![], Some("   "));
-        assert!(tasks.is_empty());
-    }
-
-    #[test]
-    fn heartbeat_announcement_text_skips_no_reply_sentinel() {
-        assert!(heartbeat_announcement_text(" NO_reply ").is_none());
-    }
-
-    #[test]
-    fn heartbeat_announcement_text_uses_default_for_empty_output() {
-        assert_eq!(
-            heartbeat_announcement_text(" \n\t "),
-            Some("heartbeat task executed".to_string())
-        );
-    }
-
-    #[test]
-    fn heartbeat_announcement_text_keeps_regular_output() {
-        assert_eq!(
-            heartbeat_announcement_text("system nominal"),
-            Some("system nominal".to_string())
-        );
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_none_when_unset() {
-        let config = Config::default();
-        let target = heartbeat_delivery_target(&config).unwrap();
-        assert!(target.is_none());
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_requires_to_field() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("telegram".into());
-        let err = heartbeat_delivery_target(&config).unwrap_err();
-        assert!(err
-            .to_string()
-            .contains("heartbeat.to is required when heartbeat.target is set"));
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_requires_target_field() {
-        let mut config = Config::default();
-        config.heartbeat.to = Some("123456".into());
-        let err = heartbeat_delivery_target(&config).unwrap_err();
-        assert!(err
-            .to_string()
-            .contains("heartbeat.target is required when heartbeat.to is set"));
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_rejects_unsupported_channel() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("email".into());
-        config.heartbeat.to = Some("ops@example.com".into());
-        let err = heartbeat_delivery_target(&config).unwrap_err();
-        assert!(err
-            .to_string()
-            .contains("unsupported heartbeat.target channel"));
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_requires_channel_configuration() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("telegram".into());
-        config.heartbeat.to = Some("123456".into());
-        let err = heartbeat_delivery_target(&config).unwrap_err();
-        assert!(err
-            .to_string()
-            .contains("channels_config.telegram is not configured"));
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_accepts_telegram_configuration() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("telegram".into());
-        config.heartbeat.to = Some("123456".into());
-        config.channels_config.telegram = Some(crate::config::TelegramConfig {
-            bot_token: "bot-token".into(),
-            allowed_users: vec![],
-            stream_mode: crate::config::StreamMode::default(),
-            draft_update_interval_ms: 1000,
-            interrupt_on_new_message: false,
-            mention_only: false,
-            group_reply: None,
-            base_url: None,
-        });
-
-        let target = heartbeat_delivery_target(&config).unwrap();
-        assert_eq!(target, Some(("telegram".to_string(), "123456".to_string())));
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_accepts_whatsapp_web_target_in_web_mode() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("whatsapp_web".into());
-        config.heartbeat.to = Some("+15551234567".into());
-        config.channels_config.whatsapp = Some(crate::config::schema::WhatsAppConfig {
-            access_token: None,
-            phone_number_id: None,
-            verify_token: None,
-            app_secret: None,
-            session_path: Some("~/.zeroclaw/state/whatsapp-web/session.db".into()),
-            pair_phone: None,
-            pair_code: None,
-            allowed_numbers: vec!["*".into()],
-        });
-
-        let target = heartbeat_delivery_target(&config).unwrap();
-        assert_eq!(
-            target,
-            Some(("whatsapp_web".to_string(), "+15551234567".to_string()))
-        );
-    }
-
-    #[test]
-    fn heartbeat_delivery_target_rejects_whatsapp_web_target_in_cloud_mode() {
-        let mut config = Config::default();
-        config.heartbeat.target = Some("whatsapp_web".into());
-        config.heartbeat.to = Some("+15551234567".into());
-        config.channels_config.whatsapp = Some(crate::config::schema::WhatsAppConfig {
-            access_token: Some("token".into()),
-            phone_number_id: Some("123456".into()),
-            verify_token: Some("verify".into()),
-            app_secret: None,
-            session_path: None,
-            pair_phone: None,
-            pair_code: None,
-            allowed_numbers: vec!["*".into()],
-        });
-
-        let err = heartbeat_delivery_target(&config).unwrap_err();
-        assert!(err.to_string().contains("configured for cloud mode"));
-    }
 }
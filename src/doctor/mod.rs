@@ -81,6 +81,7 @@ pub fn diagnose(config: &Config) -> Vec<DiagResult> {
 
     check_config_semantics(config, &mut items);
     check_workspace(config, &mut items);
+    check_skill_tools(config, &mut items);
     check_daemon_state(config, &mut items);
     check_environment(&mut items);
     check_cli_tools(&mut items);
@@ -766,6 +767,82 @@ fn workspace_probe_path(workspace_dir: &Path) -> std::path::PathBuf {
     ))
 }
 
+// ── Skill tool names ─────────────────────────────────────────────
+
+fn check_skill_tools(config: &Config, items: &mut Vec<DiagItem>) {
+    let cat = "skills";
+    if !config.workspace_dir.exists() {
+        return;
+    }
+    let skills = crate::skills::load_skills_with_config(&config.workspace_dir, config);
+    let names = crate::skills::ToolNames::resolve(&skills, &config.skills.tool_aliases);
+    if names.collisions.is_empty() {
+        let tools: usize = skills.iter().map(|skill| skill.tools.len()).sum();
+        items.push(DiagItem::ok(
+            cat,
+            format!("{tools} skill tools, no name collisions"),
+        ));
+    }
+    for collision in &names.collisions {
+        items.push(DiagItem::warn(
+            cat,
+            format!("tool name collision: {}", collision.describe()),
+        ));
+    }
+    for (tool, skill) in &names.unused_pins {
+        items.push(DiagItem::warn(
+            cat,
+            format!("[skills.tool_aliases] {tool} = \"{skill}\" matches no loaded skill tool"),
+        ));
+    }
+    check_skill_env(config, &skills, items);
+}
+
+/// `required_env` of each skill against `[skills.env]`. Only names are
+/// reported, never values.
+fn check_skill_env(config: &Config, skills: &[crate::skills::Skill], items: &mut Vec<DiagItem>) {
+    let cat = "skills";
+    for skill in skills {
+        let env =
+            crate::skills::env::SkillEnv::new(&skill.name, &skill.required_env, &config.skills.env);
+        if !env.missing().is_empty() {
+            items.push(DiagItem::error(
+                cat,
+                format!(
+                    "skill '{}' needs {} — set under [skills.env.{}]",
+                    skill.name,
+                    env.missing().join(", "),
+                    skill.name
+                ),
+            ));
+        }
+        if !env.undeclared().is_empty() {
+            items.push(DiagItem::warn(
+                cat,
+                format!(
+                    "[skills.env.{}] sets {}, which the skill does not declare in required_env; \
+                     they are not passed to its tools",
+                    skill.name,
+                    env.undeclared().join(", ")
+                ),
+            ));
+        }
+    }
+    let mut unknown: Vec<&String> = config
+        .skills
+        .env
+        .keys()
+        .filter(|name| !skills.iter().any(|skill| &skill.name == *name))
+        .collect();
+    unknown.sort();
+    for name in unknown {
+        items.push(DiagItem::warn(
+            cat,
+            format!("[skills.env.{name}] matches no loaded skill"),
+        ));
+    }
+}
+
 // ── Daemon state (original logic, preserved) ─────────────────────
 
 fn check_daemon_state(config: &Config, items: &mut Vec<DiagItem>) {
@@ -1168,6 +1245,7 @@ mod tests {
             max_tokens: None,
             api_key: None,
             transport: None,
+            fallback: Vec::new(),
         }];
         let mut items = Vec::new();
         check_config_semantics(&config, &mut items);
@@ -0,0 +1,144 @@
+//! Process exit codes.
+//!
+//! Commands fail with an `anyhow` error; `main` turns it into an exit code
+//! so scripts can branch on well-known failures without parsing stderr. The
+//! innermost [`SkillInstallError`] in the chain decides when there is one,
+//! otherwise the innermost network or I/O error does. Anything else exits
+//! with [`FAILURE`].
+
+use crate::skills::SkillInstallError;
+use std::io::ErrorKind;
+
+/// Any failure without a more specific code.
+pub const FAILURE: u8 = 1;
+/// A skill security audit found blocking issues. Invalid command-line
+/// arguments exit with 2 as well.
+pub const AUDIT_FAILED: u8 = 2;
+/// A download, clone, or API request failed.
+pub const NETWORK_ERROR: u8 = 3;
+/// A file, skill, or remote resource does not exist.
+pub const NOT_FOUND: u8 = 4;
+/// The target (an installed skill, a directory) already exists.
+pub const ALREADY_EXISTS: u8 = 5;
+
+/// The codes, as shown at the end of `zeroclaw --help`.
+pub const HELP: &str = "\
+Exit codes:
+  0  success
+  1  other failure
+  2  skill audit failed, or invalid arguments
+  3  network error
+  4  not found
+  5  already exists";
+
+/// Exit code for a command that failed with `err`.
+pub fn for_error(err: &anyhow::Error) -> u8 {
+    if let Some(install) = SkillInstallError::find(err) {
+        return match install {
+            SkillInstallError::AuditFailed(_) => AUDIT_FAILED,
+            SkillInstallError::NetworkError(_) => NETWORK_ERROR,
+            SkillInstallError::SourceNotFound(_) => NOT_FOUND,
+            SkillInstallError::AlreadyExists(_) => ALREADY_EXISTS,
+            SkillInstallError::InvalidSource(_)
+            | SkillInstallError::InvalidArchive(_)
+            | SkillInstallError::ConsentRequired(_)
+            | SkillInstallError::ChecksumMismatch(_) => FAILURE,
+        };
+    }
+    err.chain()
+        .rev()
+        .find_map(|cause| {
+            if cause.is::<reqwest::Error>() {
+                return Some(NETWORK_ERROR);
+            }
+            match cause.downcast_ref::<std::io::Error>()?.kind() {
+                ErrorKind::NotFound => Some(NOT_FOUND),
+                ErrorKind::AlreadyExists => Some(ALREADY_EXISTS),
+                ErrorKind::ConnectionRefused
+                | ErrorKind::ConnectionReset
+                | ErrorKind::ConnectionAborted
+                | ErrorKind::TimedOut => Some(NETWORK_ERROR),
+                _ => None,
+            }
+        })
+        .unwrap_or(FAILURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn skill_install_errors_map_to_their_codes() {
+        let code = |error: SkillInstallError| {
+            for_error(&anyhow::Error::from(error).context("failed to install skill"))
+        };
+        assert_eq!(
+            code(SkillInstallError::AuditFailed("audit".into())),
+            AUDIT_FAILED
+        );
+        assert_eq!(
+            code(SkillInstallError::NetworkError("clone".into())),
+            NETWORK_ERROR
+        );
+        assert_eq!(
+            code(SkillInstallError::SourceNotFound("404".into())),
+            NOT_FOUND
+        );
+        assert_eq!(
+            code(SkillInstallError::AlreadyExists("weather".into())),
+            ALREADY_EXISTS
+        );
+        assert_eq!(
+            code(SkillInstallError::ChecksumMismatch("lock".into())),
+            FAILURE
+        );
+    }
+
+    #[test]
+    fn the_innermost_install_error_wins() {
+        let err = anyhow::Error::from(SkillInstallError::SourceNotFound("HTTP 404".into()))
+            .context(SkillInstallError::NetworkError(
+                "failed to fetch zip".into(),
+            ));
+        assert_eq!(for_error(&err), NOT_FOUND);
+    }
+
+    #[test]
+    fn io_errors_map_by_kind() {
+        let missing = std::fs::read("/nonexistent/zeroclaw/config.toml")
+            .context("failed to read config")
+            .unwrap_err();
+        assert_eq!(for_error(&missing), NOT_FOUND);
+
+        let dir = tempfile::tempdir().unwrap();
+        let exists = std::fs::create_dir(dir.path())
+            .context("failed to create workspace")
+            .unwrap_err();
+        assert_eq!(for_error(&exists), ALREADY_EXISTS);
+
+        let refused = anyhow::Error::from(std::io::Error::from(ErrorKind::ConnectionRefused));
+        assert_eq!(for_error(&refused), NETWORK_ERROR);
+    }
+
+    #[test]
+    fn unclassified_errors_are_generic_failures() {
+        assert_eq!(for_error(&anyhow::anyhow!("Skill audit failed")), FAILURE);
+        let permission = anyhow::Error::from(std::io::Error::from(ErrorKind::PermissionDenied));
+        assert_eq!(for_error(&permission), FAILURE);
+    }
+
+    #[tokio::test]
+    async fn request_failures_are_network_errors() {
+        // Nothing listens on port 9 (discard) on loopback.
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let err = client
+            .get("http://127.0.0.1:9/")
+            .send()
+            .await
+            .context("failed to reach registry")
+            .unwrap_err();
+        assert_eq!(for_error(&err), NETWORK_ERROR);
+    }
+}
@@ -0,0 +1,309 @@
+//! `POST /webhook` with `"mode": "agent"`: the full tool loop, non-interactively.
+//!
+//! Chat mode answers with a single completion. Agent mode builds the same
+//! system prompt channels use (tools, skills, shell policy), runs the
+//! multi-turn tool loop bounded by `agent.max_tool_iterations`, and answers
+//! with the final reply plus every executed tool call.
+//!
+//! Nobody is there to approve anything, so the loop runs on the `webhook`
+//! channel without a non-CLI approval context: a tool that needs approval
+//! under `[autonomy]` is denied, the model is told so, and the denial is
+//! listed in the response. The whole run is capped by
+//! `[gateway] webhook_agent_timeout_secs` (kept below `request_timeout_secs`);
+//! on timeout the tool calls finished so far are returned with `504`.
+
+use super::{filter_outbound_reply, sanitize_gateway_response, AppState};
+use crate::agent::loop_::{
+    build_shell_policy_instructions, build_tool_instructions_from_specs, run_tool_call_loop,
+    scrub_credentials,
+};
+use crate::approval::{ApprovalManager, ApprovalResponse};
+use crate::providers::{self, ChatMessage};
+use crate::tools::{Tool, ToolResult};
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Longest tool output echoed back per call.
+const MAX_TOOL_OUTPUT_CHARS: usize = 2_000;
+
+/// Channel name the tool loop runs under (non-CLI, no approval context).
+const WEBHOOK_CHANNEL: &str = "webhook";
+
+/// One tool call the loop executed.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct ExecutedToolCall {
+    pub tool: String,
+    pub arguments: serde_json::Value,
+    pub success: bool,
+    pub output: String,
+    pub truncated: bool,
+    pub duration_ms: u64,
+}
+
+/// A tool call refused because it needed interactive approval.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct DeniedToolCall {
+    pub tool: String,
+    pub arguments: String,
+    pub reason: &'static str,
+}
+
+/// Agent-mode result, serialized as the webhook response body.
+#[derive(Debug, Serialize)]
+pub(super) struct AgentRunReport {
+    pub mode: &'static str,
+    pub model: String,
+    /// Final answer; `None` when the run failed or timed out.
+    pub response: Option<String>,
+    pub tool_calls: Vec<ExecutedToolCall>,
+    pub denied_tools: Vec<DeniedToolCall>,
+    pub timed_out: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AgentRunReport {
+    pub fn status(&self) -> StatusCode {
+        if self.timed_out {
+            StatusCode::GATEWAY_TIMEOUT
+        } else if self.error.is_some() {
+            StatusCode::INTERNAL_SERVER_ERROR
+        } else {
+            StatusCode::OK
+        }
+    }
+}
+
+/// Forwards to a registry tool and records each execution.
+struct RecordingTool {
+    registry: Arc<Vec<Box<dyn Tool>>>,
+    index: usize,
+    calls: Arc<Mutex<Vec<ExecutedToolCall>>>,
+}
+
+impl RecordingTool {
+    fn inner(&self) -> &dyn Tool {
+        self.registry[self.index].as_ref()
+    }
+}
+
+#[async_trait]
+impl Tool for RecordingTool {
+    fn name(&self) -> &str {
+        self.inner().name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner().description()
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        self.inner().parameters_schema()
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let arguments = scrubbed_arguments(&args);
+        let started = Instant::now();
+        let result = self.inner().execute(args).await;
+        let (success, raw_output) = match &result {
+            Ok(result) => (
+                result.success,
+                match (&result.error, result.output.is_empty()) {
+                    (Some(error), true) => error.clone(),
+                    _ => result.output.clone(),
+                },
+            ),
+            Err(err) => (false, format!("{err:#}")),
+        };
+        let (output, truncated) = truncate_output(&scrub_credentials(&raw_output));
+        self.calls.lock().push(ExecutedToolCall {
+            tool: self.name().to_string(),
+            arguments,
+            success,
+            output,
+            truncated,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        });
+        result
+    }
+}
+
+fn scrubbed_arguments(args: &serde_json::Value) -> serde_json::Value {
+    let scrubbed = scrub_credentials(&args.to_string());
+    serde_json::from_str(&scrubbed).unwrap_or(serde_json::Value::String(scrubbed))
+}
+
+fn truncate_output(output: &str) -> (String, bool) {
+    match output.char_indices().nth(MAX_TOOL_OUTPUT_CHARS) {
+        Some((cut, _)) => (format!("{}…", &output[..cut]), true),
+        None => (output.to_string(), false),
+    }
+}
+
+/// Hard cap for one agent-mode run: the configured timeout, kept a second
+/// under the connection's request timeout so the partial result still
+/// reaches the caller.
+fn run_timeout(gateway: &crate::config::GatewayConfig) -> Duration {
+    let request_cap = gateway.request_timeout_secs.saturating_sub(1).max(1);
+    Duration::from_secs(gateway.webhook_agent_timeout_secs.clamp(1, request_cap))
+}
+
+/// Channel-equivalent system prompt for the gateway's provider and tools.
+fn system_prompt(state: &AppState, config: &crate::config::Config) -> String {
+    let skills = crate::skills::load_prompt_skills(&config.workspace_dir, config);
+    let excluded = &config.autonomy.non_cli_excluded_tools;
+    let mut tool_descs = crate::tools::available_tools(state.tools_registry_exec.as_ref());
+    tool_descs.retain(|(name, _)| !excluded.iter().any(|ex| ex == name));
+
+    let prompt_profile = config.agent.prompt_profile();
+    let native_tools = state.provider.supports_native_tools();
+    let mut prompt = crate::channels::build_system_prompt_with_sections(
+        &config.workspace_dir,
+        &state.model,
+        &tool_descs,
+        &skills,
+        Some(&config.identity),
+        prompt_profile.bootstrap_max_chars,
+        native_tools,
+        config.skills.prompt_injection_mode,
+        &prompt_profile.sections,
+    );
+    if !native_tools && prompt_profile.sections.tools {
+        let specs: Vec<_> = state
+            .tools_registry_exec
+            .iter()
+            .filter(|tool| !excluded.iter().any(|ex| ex == tool.name()))
+            .map(|tool| tool.spec())
+            .collect();
+        prompt.push_str(&build_tool_instructions_from_specs(&specs));
+    }
+    prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
+    prompt
+}
+
+/// Run `message` through the tool loop and collect the report.
+pub(super) async fn run(state: &AppState, message: &str) -> AgentRunReport {
+    let config = state.config.lock().clone();
+    let provider_name = config
+        .default_provider
+        .clone()
+        .unwrap_or_else(|| "unknown".to_string());
+    let approval = ApprovalManager::from_config(&config.autonomy);
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let tools: Vec<Box<dyn Tool>> = (0..state.tools_registry_exec.len())
+        .map(|index| {
+            Box::new(RecordingTool {
+                registry: Arc::clone(&state.tools_registry_exec),
+                index,
+                calls: Arc::clone(&calls),
+            }) as Box<dyn Tool>
+        })
+        .collect();
+    let mut history = vec![
+        ChatMessage::system(system_prompt(state, &config)),
+        ChatMessage::user(message),
+    ];
+
+    let outcome = tokio::time::timeout(
+        run_timeout(&config.gateway),
+        run_tool_call_loop(
+            state.provider.as_ref(),
+            &mut history,
+            &tools,
+            state.observer.as_ref(),
+            &provider_name,
+            &state.model,
+            state.temperature,
+            true,
+            Some(&approval),
+            WEBHOOK_CHANNEL,
+            &state.multimodal,
+            state.max_tool_iterations,
+            None,
+            None,
+            state.hooks.as_deref(),
+            &config.autonomy.non_cli_excluded_tools,
+        ),
+    )
+    .await;
+
+    let (response, timed_out, error) = match outcome {
+        Ok(Ok(reply)) => {
+            let reply = filter_outbound_reply(state, reply).await;
+            (
+                Some(sanitize_gateway_response(
+                    &reply,
+                    state.tools_registry_exec.as_ref(),
+                )),
+                false,
+                None,
+            )
+        }
+        Ok(Err(err)) => {
+            let sanitized = providers::sanitize_api_error(&format!("{err:#}"));
+            tracing::error!("Webhook agent run failed: {sanitized}");
+            (None, false, Some(sanitized))
+        }
+        Err(_) => {
+            tracing::warn!("Webhook agent run hit webhook_agent_timeout_secs");
+            (None, true, None)
+        }
+    };
+
+    let denied_tools = approval
+        .audit_log()
+        .into_iter()
+        .filter(|entry| entry.decision == ApprovalResponse::No)
+        .map(|entry| DeniedToolCall {
+            tool: entry.tool_name,
+            arguments: scrub_credentials(&entry.arguments_summary),
+            reason: "requires approval, which webhook agent mode cannot ask for",
+        })
+        .collect();
+    let tool_calls = std::mem::take(&mut *calls.lock());
+
+    AgentRunReport {
+        mode: "agent",
+        model: state.model.clone(),
+        response,
+        tool_calls,
+        denied_tools,
+        timed_out,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_outputs_are_truncated_on_char_boundaries() {
+        let (short, truncated) = truncate_output("ok");
+        assert_eq!((short.as_str(), truncated), ("ok", false));
+
+        let long = "é".repeat(MAX_TOOL_OUTPUT_CHARS + 10);
+        let (cut, truncated) = truncate_output(&long);
+        assert!(truncated);
+        assert_eq!(cut.chars().count(), MAX_TOOL_OUTPUT_CHARS + 1);
+        assert!(cut.ends_with('…'));
+    }
+
+    #[test]
+    fn run_timeout_stays_below_the_request_timeout() {
+        let mut gateway = crate::config::GatewayConfig::default();
+        gateway.webhook_agent_timeout_secs = 10;
+        assert_eq!(run_timeout(&gateway), Duration::from_secs(10));
+
+        gateway.webhook_agent_timeout_secs = 600;
+        gateway.request_timeout_secs = 30;
+        assert_eq!(run_timeout(&gateway), Duration::from_secs(29));
+
+        gateway.webhook_agent_timeout_secs = 0;
+        assert_eq!(run_timeout(&gateway), Duration::from_secs(1));
+    }
+}
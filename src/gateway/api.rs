@@ -2,9 +2,10 @@
 //!
 //! All `/api/*` routes require bearer token authentication (PairingGuard).
 
+use super::error::{ErrorCode, GatewayError};
 use super::AppState;
 use axum::{
-    extract::{Path, Query, State},
+    extract::{rejection::JsonRejection, Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
 };
@@ -35,12 +36,9 @@ fn require_auth(
     if state.pairing.is_authenticated(token) {
         Ok(())
     } else {
-        Err((
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            })),
-        ))
+        Err(GatewayError::new(ErrorCode::Unauthorized)
+            .message(super::UNPAIRED_MESSAGE)
+            .into_reply())
     }
 }
 
@@ -118,10 +116,8 @@ pub async fn handle_api_config_get(
     let toml_str = match toml::to_string_pretty(&masked_config) {
         Ok(s) => s,
         Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Failed to serialize config: {e}")})),
-            )
+            return GatewayError::new(ErrorCode::Internal)
+                .message(format!("Failed to serialize config: {e}"))
                 .into_response();
         }
     };
@@ -147,10 +143,8 @@ pub async fn handle_api_config_put(
     let mut incoming_toml: toml::Value = match toml::from_str(&body) {
         Ok(v) => v,
         Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": format!("Invalid TOML: {e}")})),
-            )
+            return GatewayError::new(ErrorCode::InvalidRequest)
+                .message(format!("Invalid TOML: {e}"))
                 .into_response();
         }
     };
@@ -158,10 +152,8 @@ pub async fn handle_api_config_put(
     let incoming: crate::config::Config = match incoming_toml.try_into() {
         Ok(c) => c,
         Err(e) => {
-            return (
-                StatusCode::BAD_REQUEST,
-                Json(serde_json::json!({"error": format!("Invalid TOML: {e}")})),
-            )
+            return GatewayError::new(ErrorCode::InvalidRequest)
+                .message(format!("Invalid TOML: {e}"))
                 .into_response();
         }
     };
@@ -170,19 +162,15 @@ pub async fn handle_api_config_put(
     let new_config = hydrate_config_for_save(incoming, &current_config);
 
     if let Err(e) = new_config.validate() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": format!("Invalid config: {e}")})),
-        )
+        return GatewayError::new(ErrorCode::InvalidRequest)
+            .message(format!("Invalid config: {e}"))
             .into_response();
     }
 
     // Save to disk
     if let Err(e) = new_config.save().await {
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to save config: {e}")})),
-        )
+        return GatewayError::new(ErrorCode::Internal)
+            .message(format!("Failed to save config: {e}"))
             .into_response();
     }
 
@@ -244,10 +232,8 @@ pub async fn handle_api_cron_list(
                 .collect();
             Json(serde_json::json!({"jobs": jobs_json})).into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to list cron jobs: {e}")})),
-        )
+        Err(e) => GatewayError::new(ErrorCode::Internal)
+            .message(format!("Failed to list cron jobs: {e}"))
             .into_response(),
     }
 }
@@ -256,11 +242,21 @@ pub async fn handle_api_cron_list(
 pub async fn handle_api_cron_add(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(body): Json<CronAddBody>,
+    body: Result<Json<CronAddBody>, JsonRejection>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let Json(body) = match body {
+        Ok(body) => body,
+        Err(e) => {
+            return GatewayError::json_rejection(
+                &e,
+                "{\"schedule\": \"0 9 * * *\", \"command\": \"...\"}",
+            )
+            .into_response();
+        }
+    };
 
     let config = state.config.lock().clone();
     let schedule = crate::cron::Schedule::Cron {
@@ -279,10 +275,8 @@ pub async fn handle_api_cron_add(
             }
         }))
         .into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to add cron job: {e}")})),
-        )
+        Err(e) => GatewayError::new(ErrorCode::Internal)
+            .message(format!("Failed to add cron job: {e}"))
             .into_response(),
     }
 }
@@ -300,10 +294,8 @@ pub async fn handle_api_cron_delete(
     let config = state.config.lock().clone();
     match crate::cron::remove_job(&config, &id) {
         Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Failed to remove cron job: {e}")})),
-        )
+        Err(e) => GatewayError::new(ErrorCode::Internal)
+            .message(format!("Failed to remove cron job: {e}"))
             .into_response(),
     }
 }
@@ -382,31 +374,30 @@ pub async fn handle_api_memory_list(
         return e.into_response();
     }
 
+    let category = params
+        .category
+        .as_deref()
+        .map(crate::memory::MemoryCategory::from_name);
+
     if let Some(ref query) = params.query {
         // Search mode
-        match state.mem.recall(query, 50, None).await {
+        let categories: Vec<_> = category.into_iter().collect();
+        match state
+            .mem
+            .recall_in_categories(query, 50, None, &categories)
+            .await
+        {
             Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Memory recall failed: {e}")})),
-            )
+            Err(e) => GatewayError::new(ErrorCode::Internal)
+                .message(format!("Memory recall failed: {e}"))
                 .into_response(),
         }
     } else {
         // List mode
-        let category = params.category.as_deref().map(|cat| match cat {
-            "core" => crate::memory::MemoryCategory::Core,
-            "daily" => crate::memory::MemoryCategory::Daily,
-            "conversation" => crate::memory::MemoryCategory::Conversation,
-            other => crate::memory::MemoryCategory::Custom(other.to_string()),
-        });
-
         match state.mem.list(category.as_ref(), None).await {
             Ok(entries) => Json(serde_json::json!({"entries": entries})).into_response(),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Memory list failed: {e}")})),
-            )
+            Err(e) => GatewayError::new(ErrorCode::Internal)
+                .message(format!("Memory list failed: {e}"))
                 .into_response(),
         }
     }
@@ -416,21 +407,23 @@ pub async fn handle_api_memory_list(
 pub async fn handle_api_memory_store(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(body): Json<MemoryStoreBody>,
+    body: Result<Json<MemoryStoreBody>, JsonRejection>,
 ) -> impl IntoResponse {
     if let Err(e) = require_auth(&state, &headers) {
         return e.into_response();
     }
+    let Json(body) = match body {
+        Ok(body) => body,
+        Err(e) => {
+            return GatewayError::json_rejection(&e, "{\"key\": \"...\", \"content\": \"...\"}")
+                .into_response();
+        }
+    };
 
     let category = body
         .category
         .as_deref()
-        .map(|cat| match cat {
-            "core" => crate::memory::MemoryCategory::Core,
-            "daily" => crate::memory::MemoryCategory::Daily,
-            "conversation" => crate::memory::MemoryCategory::Conversation,
-            other => crate::memory::MemoryCategory::Custom(other.to_string()),
-        })
+        .map(crate::memory::MemoryCategory::from_name)
         .unwrap_or(crate::memory::MemoryCategory::Core);
 
     match state
@@ -439,10 +432,8 @@ pub async fn handle_api_memory_store(
         .await
     {
         Ok(()) => Json(serde_json::json!({"status": "ok"})).into_response(),
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Memory store failed: {e}")})),
-        )
+        Err(e) => GatewayError::new(ErrorCode::Internal)
+            .message(format!("Memory store failed: {e}"))
             .into_response(),
     }
 }
@@ -461,10 +452,8 @@ pub async fn handle_api_memory_delete(
         Ok(deleted) => {
             Json(serde_json::json!({"status": "ok", "deleted": deleted})).into_response()
         }
-        Err(e) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"error": format!("Memory forget failed: {e}")})),
-        )
+        Err(e) => GatewayError::new(ErrorCode::Internal)
+            .message(format!("Memory forget failed: {e}"))
             .into_response(),
     }
 }
@@ -481,10 +470,8 @@ pub async fn handle_api_cost(
     if let Some(ref tracker) = state.cost_tracker {
         match tracker.get_summary() {
             Ok(summary) => Json(serde_json::json!({"cost": summary})).into_response(),
-            Err(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(serde_json::json!({"error": format!("Cost summary failed: {e}")})),
-            )
+            Err(e) => GatewayError::new(ErrorCode::Internal)
+                .message(format!("Cost summary failed: {e}"))
                 .into_response(),
         }
     } else {
@@ -0,0 +1,145 @@
+//! In-flight request cap for the gateway (`[gateway] max_concurrent_requests`).
+//!
+//! `max_connections` bounds open sockets; this bounds how many requests are
+//! being handled at once across them, since each one may end in a provider
+//! call. A request that finds every slot taken gets `503` (`busy`) with
+//! `Retry-After` instead of queueing. The slot is held until the handler returns its
+//! response head, so long-lived streams (SSE, WebSocket) don't pin one.
+//! `/health` and `/metrics` are exempt so probes keep answering under load.
+
+use super::error::{ErrorCode, GatewayError};
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+static REQUESTS_REJECTED_BUSY: AtomicU64 = AtomicU64::new(0);
+
+/// Paths served without taking a slot.
+const EXEMPT_PATHS: [&str; 2] = ["/health", "/metrics"];
+
+#[derive(Debug)]
+pub struct RequestLimiter {
+    permits: Arc<Semaphore>,
+    max: usize,
+}
+
+impl RequestLimiter {
+    pub fn new(max_concurrent_requests: usize) -> Self {
+        let max = max_concurrent_requests.clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            permits: Arc::new(Semaphore::new(max)),
+            max,
+        }
+    }
+}
+
+/// Axum middleware: runs the request if a slot is free, else answers `503`.
+pub async fn limit_requests(
+    State(limiter): State<Arc<RequestLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if EXEMPT_PATHS.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+    let Ok(_permit) = Arc::clone(&limiter.permits).try_acquire_owned() else {
+        REQUESTS_REJECTED_BUSY.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!(
+            "Gateway request limit ({}) reached; rejecting {} {}",
+            limiter.max,
+            request.method(),
+            request.uri().path()
+        );
+        let mut response = GatewayError::new(ErrorCode::Busy).into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from_static("1"));
+        return response;
+    };
+    next.run(request).await
+}
+
+/// Request counters in Prometheus text format, appended to `/metrics`.
+pub(super) fn render_metrics() -> String {
+    format!(
+        "# HELP zeroclaw_gateway_requests_rejected_total Gateway requests rejected by max_concurrent_requests\n\
+         # TYPE zeroclaw_gateway_requests_rejected_total counter\n\
+         zeroclaw_gateway_requests_rejected_total{{reason=\"busy\"}} {}\n",
+        REQUESTS_REJECTED_BUSY.load(Ordering::Relaxed),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use http_body_util::BodyExt;
+    use std::sync::atomic::AtomicUsize;
+    use tokio::sync::Notify;
+    use tower::ServiceExt;
+
+    fn get_request(path: &str) -> Request {
+        Request::builder().uri(path).body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_bounded_and_excess_requests_get_503() {
+        let release = Arc::new(Notify::new());
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let slow = {
+            let (release, in_flight, peak) = (release.clone(), in_flight.clone(), peak.clone());
+            move || async move {
+                let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                release.notified().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                "done"
+            }
+        };
+        let app = Router::new()
+            .route("/webhook", get(slow))
+            .route("/health", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(RequestLimiter::new(2)),
+                limit_requests,
+            ));
+
+        let running: Vec<_> = (0..2)
+            .map(|_| tokio::spawn(app.clone().oneshot(get_request("/webhook"))))
+            .collect();
+        while in_flight.load(Ordering::SeqCst) < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        let rejected = app.clone().oneshot(get_request("/webhook")).await.unwrap();
+        assert_eq!(rejected.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(rejected.headers()[header::RETRY_AFTER], "1");
+        let payload = rejected.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(super::super::error::code_of(&body), Some("busy"));
+        let health = app.clone().oneshot(get_request("/health")).await.unwrap();
+        assert_eq!(health.status(), StatusCode::OK);
+
+        release.notify_waiters();
+        for handle in running {
+            assert_eq!(handle.await.unwrap().unwrap().status(), StatusCode::OK);
+        }
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+
+        // Slots are free again once the handlers have returned.
+        let next = tokio::spawn(app.oneshot(get_request("/webhook")));
+        while in_flight.load(Ordering::SeqCst) < 1 {
+            tokio::task::yield_now().await;
+        }
+        release.notify_waiters();
+        assert_eq!(next.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert!(render_metrics().contains("zeroclaw_gateway_requests_rejected_total"));
+    }
+}
@@ -0,0 +1,619 @@
+//! Connection-level limits for the gateway listener.
+//!
+//! `RequestBodyLimitLayer` and `TimeoutLayer` only run once hyper has parsed a
+//! request head, so they cannot stop a client that trickles header bytes or
+//! simply holds sockets open. [`GuardedListener`] wraps the TCP or Unix
+//! socket listener and enforces, per connection:
+//!
+//! - a concurrency cap (`[gateway] max_connections`): connections over the
+//!   cap get an immediate `503` and are closed without reaching hyper;
+//! - a read deadline (`[gateway] request_timeout_secs`): a new connection
+//!   must send its first request, and every request head must be complete,
+//!   within the deadline or the connection gets `408` and is closed. A
+//!   kept-alive connection that sends nothing for the same time after its
+//!   last response is closed too, so idle sockets cannot hold on to a
+//!   `max_connections` slot;
+//! - a size cap (`[gateway] max_request_bytes`): once a request's headers +
+//!   body exceed it the connection gets `413` and is closed.
+//!
+//! Request boundaries are tracked from the byte stream: a request starts with
+//! its first byte, its head ends at `\r\n\r\n`, and it is complete once the
+//! server starts writing the response. Upgraded connections (WebSocket) are
+//! passed through untouched after the handshake. Body reads are bounded by
+//! the router's `TimeoutLayer`, which uses the same timeout. When hyper is
+//! done with a connection the socket is shut down explicitly rather than
+//! left to the peer acting on `Connection: close`.
+
+use axum::serve::Listener;
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::{Instant, Sleep};
+
+const RESPONSE_BUSY: &[u8] = b"HTTP/1.1 503 Service Unavailable\r\n\
+Content-Length: 0\r\nRetry-After: 1\r\nConnection: close\r\n\r\n";
+const RESPONSE_TIMEOUT: &[u8] =
+    b"HTTP/1.1 408 Request Timeout\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+const RESPONSE_TOO_LARGE: &[u8] =
+    b"HTTP/1.1 413 Payload Too Large\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+static CONNECTIONS_TIMED_OUT: AtomicU64 = AtomicU64::new(0);
+static CONNECTIONS_REJECTED_BUSY: AtomicU64 = AtomicU64::new(0);
+static CONNECTIONS_REJECTED_TOO_LARGE: AtomicU64 = AtomicU64::new(0);
+
+/// Per-connection limits, resolved from `[gateway]` config.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ConnectionLimits {
+    pub request_timeout: Duration,
+    pub max_connections: usize,
+    pub max_request_bytes: usize,
+}
+
+impl ConnectionLimits {
+    pub(super) fn from_config(config: &crate::config::GatewayConfig) -> Self {
+        Self {
+            request_timeout: Duration::from_secs(config.request_timeout_secs.max(1)),
+            max_connections: config.max_connections.clamp(1, Semaphore::MAX_PERMITS),
+            max_request_bytes: config.max_request_bytes.max(1),
+        }
+    }
+}
+
+/// Unix socket peers have no IP address. They are reported to handlers as
+/// loopback clients, which is what they are.
+#[cfg(unix)]
+const UNIX_PEER_ADDR: SocketAddr =
+    SocketAddr::new(std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST), 0);
+
+/// A connected byte stream the gateway serves HTTP on.
+pub(super) trait RawStream: AsyncRead + AsyncWrite + Unpin + Send + 'static {
+    /// Non-blocking write used for best-effort error responses.
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize>;
+
+    /// Shut down both directions without waiting, when the connection is
+    /// dropped.
+    fn shutdown_now(&self) -> io::Result<()>;
+}
+
+/// A bound socket the gateway accepts [`RawStream`]s from.
+pub(super) trait Accept: Send + 'static {
+    type Stream: RawStream;
+
+    fn accept_raw(&mut self)
+        -> impl Future<Output = io::Result<(Self::Stream, SocketAddr)>> + Send;
+
+    fn local_addr(&self) -> io::Result<SocketAddr>;
+}
+
+impl RawStream for TcpStream {
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        TcpStream::try_write(self, buf)
+    }
+
+    fn shutdown_now(&self) -> io::Result<()> {
+        socket2::SockRef::from(self).shutdown(std::net::Shutdown::Both)
+    }
+}
+
+impl Accept for TcpListener {
+    type Stream = TcpStream;
+
+    async fn accept_raw(&mut self) -> io::Result<(TcpStream, SocketAddr)> {
+        self.accept().await
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        TcpListener::local_addr(self)
+    }
+}
+
+#[cfg(unix)]
+impl RawStream for UnixStream {
+    fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        UnixStream::try_write(self, buf)
+    }
+
+    fn shutdown_now(&self) -> io::Result<()> {
+        socket2::SockRef::from(self).shutdown(std::net::Shutdown::Both)
+    }
+}
+
+#[cfg(unix)]
+impl Accept for UnixListener {
+    type Stream = UnixStream;
+
+    async fn accept_raw(&mut self) -> io::Result<(UnixStream, SocketAddr)> {
+        let (stream, _) = self.accept().await?;
+        Ok((stream, UNIX_PEER_ADDR))
+    }
+
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(UNIX_PEER_ADDR)
+    }
+}
+
+/// Listener that applies [`ConnectionLimits`] to every accepted connection.
+pub(super) struct GuardedListener<L = TcpListener> {
+    inner: L,
+    limits: ConnectionLimits,
+    permits: Arc<Semaphore>,
+}
+
+impl<L: Accept> GuardedListener<L> {
+    pub(super) fn new(inner: L, limits: ConnectionLimits) -> Self {
+        Self {
+            inner,
+            limits,
+            permits: Arc::new(Semaphore::new(limits.max_connections)),
+        }
+    }
+}
+
+impl<L: Accept> Listener for GuardedListener<L> {
+    type Io = GuardedStream<L::Stream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        loop {
+            let (stream, addr) = match self.inner.accept_raw().await {
+                Ok(accepted) => accepted,
+                Err(err) => {
+                    handle_accept_error(err).await;
+                    continue;
+                }
+            };
+
+            match Arc::clone(&self.permits).try_acquire_owned() {
+                Ok(permit) => return (GuardedStream::new(stream, permit, self.limits), addr),
+                Err(_) => {
+                    CONNECTIONS_REJECTED_BUSY.fetch_add(1, Ordering::Relaxed);
+                    tracing::warn!(
+                        "Gateway connection limit ({}) reached; rejecting {addr}",
+                        self.limits.max_connections
+                    );
+                    tokio::spawn(reject_busy(stream));
+                }
+            }
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// Per-connection errors (reset, aborted) are routine; anything else (e.g.
+/// fd exhaustion) gets a short back-off so the loop does not spin.
+async fn handle_accept_error(err: io::Error) {
+    if matches!(
+        err.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    ) {
+        return;
+    }
+    tracing::error!("Gateway accept error: {err}");
+    tokio::time::sleep(Duration::from_secs(1)).await;
+}
+
+async fn reject_busy<S: RawStream>(mut stream: S) {
+    let _ = tokio::time::timeout(Duration::from_secs(1), async {
+        stream.write_all(RESPONSE_BUSY).await?;
+        stream.shutdown().await
+    })
+    .await;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Accepted, no bytes yet; the deadline runs from accept.
+    AwaitingFirstRequest,
+    /// Between requests on a kept-alive connection; the deadline runs from
+    /// the last byte of the response written.
+    Idle,
+    /// Reading a request head; the deadline runs from its first byte.
+    Headers,
+    /// Head complete; body and handler are bounded by `TimeoutLayer`.
+    Body,
+    /// Upgraded (WebSocket); limits no longer apply.
+    Passthrough,
+    /// Aborted by a limit or closed while idle; further reads fail.
+    Closed,
+}
+
+/// Accepted connection; holds a concurrency permit until dropped.
+pub(super) struct GuardedStream<S: RawStream = TcpStream> {
+    inner: S,
+    _permit: OwnedSemaphorePermit,
+    limits: ConnectionLimits,
+    phase: Phase,
+    deadline: Pin<Box<Sleep>>,
+    request_bytes: usize,
+    head: Vec<u8>,
+}
+
+impl<S: RawStream> GuardedStream<S> {
+    fn new(inner: S, permit: OwnedSemaphorePermit, limits: ConnectionLimits) -> Self {
+        Self {
+            inner,
+            _permit: permit,
+            limits,
+            phase: Phase::AwaitingFirstRequest,
+            deadline: Box::pin(tokio::time::sleep(limits.request_timeout)),
+            request_bytes: 0,
+            head: Vec::new(),
+        }
+    }
+
+    /// Best-effort error response, then fail the read so hyper drops the
+    /// connection.
+    fn abort(&mut self, response: &[u8], counter: &AtomicU64, reason: &str) -> io::Error {
+        counter.fetch_add(1, Ordering::Relaxed);
+        tracing::warn!("Closing gateway connection: {reason}");
+        let _ = self.inner.try_write(response);
+        self.phase = Phase::Closed;
+        self.head = Vec::new();
+        io::Error::new(io::ErrorKind::ConnectionAborted, reason.to_string())
+    }
+
+    fn on_bytes_read(&mut self, chunk: &[u8]) -> io::Result<()> {
+        match self.phase {
+            Phase::Passthrough => return Ok(()),
+            Phase::AwaitingFirstRequest => self.start_request(false),
+            Phase::Idle => self.start_request(true),
+            Phase::Headers | Phase::Body | Phase::Closed => {}
+        }
+
+        self.request_bytes = self.request_bytes.saturating_add(chunk.len());
+        if self.request_bytes > self.limits.max_request_bytes {
+            return Err(self.abort(
+                RESPONSE_TOO_LARGE,
+                &CONNECTIONS_REJECTED_TOO_LARGE,
+                "request exceeds max_request_bytes",
+            ));
+        }
+
+        if self.phase == Phase::Headers {
+            let search_from = self.head.len().saturating_sub(3);
+            self.head.extend_from_slice(chunk);
+            if let Some(pos) = find_header_end(&self.head[search_from..]) {
+                let head_len = search_from + pos;
+                self.phase = if is_upgrade_request(&self.head[..head_len]) {
+                    Phase::Passthrough
+                } else {
+                    Phase::Body
+                };
+                self.head = Vec::new();
+            }
+        }
+        Ok(())
+    }
+
+    fn start_request(&mut self, reset_deadline: bool) {
+        self.phase = Phase::Headers;
+        self.request_bytes = 0;
+        self.head.clear();
+        if reset_deadline {
+            self.reset_deadline();
+        }
+    }
+
+    fn reset_deadline(&mut self) {
+        self.deadline
+            .as_mut()
+            .reset(Instant::now() + self.limits.request_timeout);
+    }
+
+    fn on_write(&mut self) {
+        match self.phase {
+            Phase::Headers | Phase::Body => {
+                self.phase = Phase::Idle;
+                self.head = Vec::new();
+                self.reset_deadline();
+            }
+            Phase::Idle => self.reset_deadline(),
+            Phase::AwaitingFirstRequest | Phase::Passthrough | Phase::Closed => {}
+        }
+    }
+}
+
+impl<S: RawStream> Drop for GuardedStream<S> {
+    fn drop(&mut self) {
+        // Ignored: the peer may already have closed its side.
+        let _ = self.inner.shutdown_now();
+    }
+}
+
+fn find_header_end(bytes: &[u8]) -> Option<usize> {
+    bytes.windows(4).position(|window| window == b"\r\n\r\n")
+}
+
+fn is_upgrade_request(head: &[u8]) -> bool {
+    head.to_ascii_lowercase()
+        .windows(10)
+        .any(|window| window == b"\r\nupgrade:")
+}
+
+impl<S: RawStream> AsyncRead for GuardedStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.phase {
+            Phase::Closed => {
+                return Poll::Ready(Err(io::ErrorKind::ConnectionAborted.into()));
+            }
+            Phase::AwaitingFirstRequest | Phase::Headers => {
+                if this.deadline.as_mut().poll(cx).is_ready() {
+                    return Poll::Ready(Err(this.abort(
+                        RESPONSE_TIMEOUT,
+                        &CONNECTIONS_TIMED_OUT,
+                        "request headers not received within request_timeout_secs",
+                    )));
+                }
+            }
+            Phase::Idle => {
+                if this.deadline.as_mut().poll(cx).is_ready() {
+                    // EOF rather than an error: hyper closes an idle
+                    // keep-alive connection quietly.
+                    CONNECTIONS_TIMED_OUT.fetch_add(1, Ordering::Relaxed);
+                    tracing::debug!("Closing idle gateway connection after request_timeout_secs");
+                    this.phase = Phase::Closed;
+                    return Poll::Ready(Ok(()));
+                }
+            }
+            Phase::Body | Phase::Passthrough => {}
+        }
+
+        let filled_before = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        let chunk = &buf.filled()[filled_before..];
+        if !chunk.is_empty() {
+            this.on_bytes_read(chunk)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: RawStream> AsyncWrite for GuardedStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.on_write();
+        Pin::new(&mut this.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.on_write();
+        Pin::new(&mut this.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+/// Connection counters in Prometheus text format, appended to `/metrics`.
+pub(super) fn render_metrics() -> String {
+    format!(
+        "# HELP zeroclaw_gateway_connections_timed_out_total Gateway connections closed for not sending a request in time\n\
+         # TYPE zeroclaw_gateway_connections_timed_out_total counter\n\
+         zeroclaw_gateway_connections_timed_out_total {}\n\
+         # HELP zeroclaw_gateway_connections_rejected_total Gateway connections rejected by a limit\n\
+         # TYPE zeroclaw_gateway_connections_rejected_total counter\n\
+         zeroclaw_gateway_connections_rejected_total{{reason=\"busy\"}} {}\n\
+         zeroclaw_gateway_connections_rejected_total{{reason=\"too_large\"}} {}\n",
+        CONNECTIONS_TIMED_OUT.load(Ordering::Relaxed),
+        CONNECTIONS_REJECTED_BUSY.load(Ordering::Relaxed),
+        CONNECTIONS_REJECTED_TOO_LARGE.load(Ordering::Relaxed),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    async fn guarded_listener(limits: ConnectionLimits) -> (GuardedListener, SocketAddr) {
+        let inner = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = inner.local_addr().unwrap();
+        (GuardedListener::new(inner, limits), addr)
+    }
+
+    fn limits(
+        timeout_ms: u64,
+        max_connections: usize,
+        max_request_bytes: usize,
+    ) -> ConnectionLimits {
+        ConnectionLimits {
+            request_timeout: Duration::from_millis(timeout_ms),
+            max_connections,
+            max_request_bytes,
+        }
+    }
+
+    /// Read like hyper does until EOF or error.
+    async fn drain(mut stream: GuardedStream) -> io::Result<()> {
+        let mut buf = [0_u8; 1024];
+        loop {
+            if stream.read(&mut buf).await? == 0 {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn read_response(client: &mut TcpStream) -> String {
+        let mut response = Vec::new();
+        let _ =
+            tokio::time::timeout(Duration::from_secs(2), client.read_to_end(&mut response)).await;
+        String::from_utf8_lossy(&response).into_owned()
+    }
+
+    #[tokio::test]
+    async fn slow_writer_times_out_and_releases_permit() {
+        let (mut listener, addr) = guarded_listener(limits(200, 4, 4096)).await;
+        let timed_out_before = CONNECTIONS_TIMED_OUT.load(Ordering::Relaxed);
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (stream, _) = listener.accept().await;
+        assert_eq!(listener.permits.available_permits(), 3);
+
+        // One byte, then nothing: a slowloris client.
+        client.write_all(b"G").await.unwrap();
+
+        let started = std::time::Instant::now();
+        let result = tokio::time::timeout(Duration::from_secs(2), drain(stream))
+            .await
+            .expect("read loop must finish within the deadline");
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::ConnectionAborted);
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        assert!(read_response(&mut client).await.starts_with("HTTP/1.1 408"));
+        assert_eq!(listener.permits.available_permits(), 4);
+        assert!(CONNECTIONS_TIMED_OUT.load(Ordering::Relaxed) > timed_out_before);
+    }
+
+    #[tokio::test]
+    async fn complete_request_head_stops_the_deadline() {
+        let (mut listener, addr) = guarded_listener(limits(100, 4, 4096)).await;
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (mut stream, _) = listener.accept().await;
+        client
+            .write_all(b"POST /webhook HTTP/1.1\r\nHost: x\r\nContent-Length: 5\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut buf = [0_u8; 1024];
+        assert!(stream.read(&mut buf).await.unwrap() > 0);
+
+        // Body pacing is left to TimeoutLayer: no abort while waiting here.
+        let waited = tokio::time::timeout(Duration::from_millis(300), stream.read(&mut buf)).await;
+        assert!(waited.is_err(), "read should still be pending");
+        assert_eq!(stream.phase, Phase::Body);
+    }
+
+    #[tokio::test]
+    async fn idle_keep_alive_connection_is_closed_and_releases_permit() {
+        let (mut listener, addr) = guarded_listener(limits(200, 1, 4096)).await;
+        let timed_out_before = CONNECTIONS_TIMED_OUT.load(Ordering::Relaxed);
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (mut stream, _) = listener.accept().await;
+        client
+            .write_all(b"GET /health HTTP/1.1\r\nHost: x\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = [0_u8; 1024];
+        assert!(stream.read(&mut buf).await.unwrap() > 0);
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(stream.phase, Phase::Idle);
+
+        // The client keeps the connection open and sends nothing more.
+        let started = std::time::Instant::now();
+        let read = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf))
+            .await
+            .expect("idle read must end at the deadline");
+        assert_eq!(read.unwrap(), 0);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(stream.phase, Phase::Closed);
+        assert!(CONNECTIONS_TIMED_OUT.load(Ordering::Relaxed) > timed_out_before);
+
+        drop(stream);
+        assert_eq!(listener.permits.available_permits(), 1);
+        assert!(read_response(&mut client).await.starts_with("HTTP/1.1 200"));
+    }
+
+    #[tokio::test]
+    async fn over_limit_connections_get_503() {
+        let (mut listener, addr) = guarded_listener(limits(5_000, 1, 4096)).await;
+        let rejected_before = CONNECTIONS_REJECTED_BUSY.load(Ordering::Relaxed);
+
+        let _first_client = TcpStream::connect(addr).await.unwrap();
+        let (first, _) = listener.accept().await;
+
+        // While the only permit is held, the next connection is turned away.
+        let mut second_client = TcpStream::connect(addr).await.unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(300), listener.accept())
+                .await
+                .is_err()
+        );
+        assert!(read_response(&mut second_client)
+            .await
+            .starts_with("HTTP/1.1 503"));
+        assert!(CONNECTIONS_REJECTED_BUSY.load(Ordering::Relaxed) > rejected_before);
+
+        // Dropping the first connection releases its permit.
+        drop(first);
+        let third_client = TcpStream::connect(addr).await.unwrap();
+        let (_third, third_addr) = listener.accept().await;
+        assert_eq!(third_addr, third_client.local_addr().unwrap());
+    }
+
+    #[tokio::test]
+    async fn oversized_request_gets_413() {
+        let (mut listener, addr) = guarded_listener(limits(5_000, 4, 64)).await;
+
+        let mut client = TcpStream::connect(addr).await.unwrap();
+        let (stream, _) = listener.accept().await;
+        let mut request = b"GET / HTTP/1.1\r\nX-Padding: ".to_vec();
+        request.extend_from_slice(&[b'a'; 128]);
+        client.write_all(&request).await.unwrap();
+
+        assert!(drain(stream).await.is_err());
+        assert!(read_response(&mut client).await.starts_with("HTTP/1.1 413"));
+    }
+
+    #[test]
+    fn detects_header_end_and_upgrades() {
+        assert_eq!(find_header_end(b"GET / HTTP/1.1\r\n\r\nbody"), Some(14));
+        assert_eq!(find_header_end(b"GET / HTTP/1.1\r\nHost: x\r\n"), None);
+        assert!(is_upgrade_request(
+            b"GET /ws/chat HTTP/1.1\r\nHost: x\r\nUpgrade: websocket\r\nConnection: Upgrade"
+        ));
+        assert!(!is_upgrade_request(
+            b"GET / HTTP/1.1\r\nHost: upgrade.example"
+        ));
+    }
+
+    #[test]
+    fn render_metrics_exposes_counters() {
+        let text = render_metrics();
+        assert!(text.contains("zeroclaw_gateway_connections_timed_out_total "));
+        assert!(text.contains("zeroclaw_gateway_connections_rejected_total{reason=\"busy\"}"));
+        assert!(text.contains("zeroclaw_gateway_connections_rejected_total{reason=\"too_large\"}"));
+    }
+}
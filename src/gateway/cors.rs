@@ -0,0 +1,284 @@
+//! CORS handling for browser-based gateway callers (`[gateway] cors_allowed_origins`).
+//!
+//! CORS is opt-in: with no configured origins the middleware is a pass-through,
+//! so preflights are not answered and responses carry no CORS headers. Origins
+//! are compared exactly (scheme + host + port); `"*"` is honored only when it is
+//! listed explicitly. Requests from non-matching origins are still processed
+//! normally (server-to-server semantics) — they just receive no CORS headers.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+const ALLOWED_METHODS: &str = "GET, POST, HEAD, OPTIONS";
+const ALLOWED_HEADERS: &str =
+    "Authorization, Content-Type, X-Webhook-Secret, X-Idempotency-Key, X-Pairing-Code";
+const PREFLIGHT_MAX_AGE_SECS: &str = "600";
+
+#[derive(Debug, Clone, Default)]
+pub struct CorsPolicy {
+    origins: Vec<String>,
+    allow_any: bool,
+}
+
+impl CorsPolicy {
+    pub fn from_config(allowed_origins: &[String]) -> Self {
+        let mut policy = Self::default();
+        for origin in allowed_origins {
+            let origin = origin.trim();
+            if origin == "*" {
+                policy.allow_any = true;
+            } else if let Some(normalized) = normalize_origin(origin) {
+                policy.origins.push(normalized);
+            }
+        }
+        policy
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.allow_any || !self.origins.is_empty()
+    }
+
+    /// `Access-Control-Allow-Origin` value for `origin`, if it is allowed.
+    fn allow_origin_header(&self, origin: &str) -> Option<HeaderValue> {
+        if self.allow_any {
+            return Some(HeaderValue::from_static("*"));
+        }
+        let normalized = normalize_origin(origin)?;
+        if self.origins.iter().any(|allowed| *allowed == normalized) {
+            HeaderValue::from_str(origin.trim()).ok()
+        } else {
+            None
+        }
+    }
+
+    fn preflight_response(&self, origin: &str) -> Response {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        let headers = response.headers_mut();
+        headers.insert(header::VARY, HeaderValue::from_static("Origin"));
+        if let Some(allow_origin) = self.allow_origin_header(origin) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_METHODS,
+                HeaderValue::from_static(ALLOWED_METHODS),
+            );
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_HEADERS,
+                HeaderValue::from_static(ALLOWED_HEADERS),
+            );
+            headers.insert(
+                header::ACCESS_CONTROL_MAX_AGE,
+                HeaderValue::from_static(PREFLIGHT_MAX_AGE_SECS),
+            );
+        }
+        response
+    }
+
+    fn decorate_response(&self, origin: &str, response: &mut Response) {
+        if let Some(allow_origin) = self.allow_origin_header(origin) {
+            let headers = response.headers_mut();
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+            headers.append(header::VARY, HeaderValue::from_static("Origin"));
+        }
+    }
+}
+
+/// Lowercase and strip a trailing slash; rejects values without a scheme.
+fn normalize_origin(origin: &str) -> Option<String> {
+    let origin = origin.trim().trim_end_matches('/');
+    let (scheme, authority) = origin.split_once("://")?;
+    if scheme.is_empty() || authority.is_empty() || authority.contains('/') {
+        return None;
+    }
+    Some(origin.to_ascii_lowercase())
+}
+
+fn request_origin(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::ORIGIN)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+}
+
+fn is_preflight(request: &Request) -> bool {
+    request.method() == Method::OPTIONS
+        && request
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+}
+
+/// Axum middleware: answers CORS preflights and tags allowed-origin responses.
+pub async fn handle_cors(
+    State(policy): State<Arc<CorsPolicy>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !policy.is_enabled() {
+        return next.run(request).await;
+    }
+    let Some(origin) = request_origin(request.headers()) else {
+        return next.run(request).await;
+    };
+
+    if is_preflight(&request) {
+        return policy.preflight_response(&origin);
+    }
+
+    let mut response = next.run(request).await;
+    policy.decorate_response(&origin, &mut response);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app(origins: &[&str]) -> Router {
+        let policy = Arc::new(CorsPolicy::from_config(
+            &origins.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        ));
+        Router::new()
+            .route("/health", get(|| async { "ok" }))
+            .route(
+                "/webhook",
+                get(|| async { "usage" }).post(|| async { "done" }),
+            )
+            .layer(axum::middleware::from_fn_with_state(policy, handle_cors))
+    }
+
+    fn preflight(origin: &str) -> Request {
+        Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/webhook")
+            .header(header::ORIGIN, origin)
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+            .header(header::ACCESS_CONTROL_REQUEST_HEADERS, "x-webhook-secret")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    fn post_webhook(origin: &str) -> Request {
+        Request::builder()
+            .method(Method::POST)
+            .uri("/webhook")
+            .header(header::ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn matching_origin_gets_preflight_and_allow_origin() {
+        let app = app(&["https://dashboard.internal"]);
+
+        let response = app
+            .clone()
+            .oneshot(preflight("https://dashboard.internal"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dashboard.internal"
+        );
+        let allow_headers = headers[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap();
+        assert!(allow_headers.contains("X-Webhook-Secret"));
+        assert!(allow_headers.contains("Authorization"));
+        assert!(headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap()
+            .contains("POST"));
+
+        let response = app
+            .oneshot(post_webhook("https://dashboard.internal"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://dashboard.internal"
+        );
+    }
+
+    #[tokio::test]
+    async fn non_matching_origin_gets_no_cors_headers_but_is_processed() {
+        let app = app(&["https://dashboard.internal"]);
+
+        for origin in [
+            "https://dashboard.internal.evil.com",
+            "http://dashboard.internal",
+            "https://dashboard.internal:8443",
+        ] {
+            let response = app.clone().oneshot(preflight(origin)).await.unwrap();
+            assert!(response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none());
+
+            let response = app.clone().oneshot(post_webhook(origin)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response
+                .headers()
+                .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn wildcard_requires_explicit_config() {
+        let response = app(&["*"])
+            .oneshot(preflight("https://anything.example"))
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+
+        let response = app(&[])
+            .oneshot(post_webhook("https://anything.example"))
+            .await
+            .unwrap();
+        assert!(response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn head_health_matches_get_headers_without_body() {
+        let app = app(&["https://dashboard.internal"]);
+        let request = |method: Method| {
+            Request::builder()
+                .method(method)
+                .uri("/health")
+                .header(header::ORIGIN, "https://dashboard.internal")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let get_response = app.clone().oneshot(request(Method::GET)).await.unwrap();
+        let head_response = app.oneshot(request(Method::HEAD)).await.unwrap();
+
+        assert_eq!(head_response.status(), StatusCode::OK);
+        assert_eq!(
+            head_response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            get_response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN]
+        );
+        assert_eq!(
+            head_response.headers().get(header::CONTENT_TYPE),
+            get_response.headers().get(header::CONTENT_TYPE)
+        );
+        let body = axum::body::to_bytes(head_response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.is_empty());
+    }
+}
@@ -0,0 +1,462 @@
+//! Error bodies for the gateway's native endpoints.
+//!
+//! Every error carries a stable [`ErrorCode`] from one taxonomy, the HTTP
+//! status that goes with it, and the request id the response is logged under.
+//! [`assign_request_id`] takes the id from `X-Request-Id` (or makes one up),
+//! echoes it back in the same header, and turns the bare `408`/`413` answers
+//! of the timeout and body-limit layers into coded bodies.
+//!
+//! `[gateway] error_format` picks the body shape. `legacy` (the default for
+//! one deprecation window) keeps `"error"` as the message string and adds the
+//! code beside it; `structured` nests it:
+//!
+//! ```json
+//! {"error": {"code": "missing_field", "message": "...", "field": "message"}, "request_id": "3f9c0a1b"}
+//! ```
+//!
+//! The OpenAI-compatible `/v1` routes keep the OpenAI error shape.
+
+use crate::config::GatewayErrorFormat;
+use axum::{
+    extract::{rejection::JsonRejection, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use serde::{Deserialize, Serialize};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request id that is echoed back.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+/// Declares [`ErrorCode`] together with [`ErrorCode::ALL`], so a new code
+/// cannot be left out of the list.
+macro_rules! error_codes {
+    ($(#[$meta:meta])* pub enum $name:ident { $($variant:ident,)+ }) => {
+        $(#[$meta])*
+        pub enum $name {
+            $($variant,)+
+        }
+
+        impl $name {
+            /// Every code, in declaration order.
+            pub const ALL: [Self; [$(stringify!($variant)),+].len()] = [$(Self::$variant),+];
+        }
+    };
+}
+
+error_codes! {
+/// Why a gateway request failed. Names are part of the API: add variants,
+/// never rename them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidJson,
+    MissingField,
+    InvalidRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    RequestTimeout,
+    PayloadTooLarge,
+    UnsupportedMediaType,
+    RateLimited,
+    Busy,
+    BudgetExceeded,
+    ProviderError,
+    Refused,
+    Maintenance,
+    NotImplemented,
+    Internal,
+}
+}
+
+impl ErrorCode {
+    /// The `code` value in error bodies.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InvalidJson => "invalid_json",
+            Self::MissingField => "missing_field",
+            Self::InvalidRequest => "invalid_request",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::NotFound => "not_found",
+            Self::MethodNotAllowed => "method_not_allowed",
+            Self::RequestTimeout => "request_timeout",
+            Self::PayloadTooLarge => "payload_too_large",
+            Self::UnsupportedMediaType => "unsupported_media_type",
+            Self::RateLimited => "rate_limited",
+            Self::Busy => "busy",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::ProviderError => "provider_error",
+            Self::Refused => "refused",
+            Self::Maintenance => "maintenance",
+            Self::NotImplemented => "not_implemented",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn status(self) -> StatusCode {
+        match self {
+            Self::InvalidJson | Self::MissingField | Self::InvalidRequest => {
+                StatusCode::BAD_REQUEST
+            }
+            Self::Unauthorized => StatusCode::UNAUTHORIZED,
+            Self::Forbidden => StatusCode::FORBIDDEN,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
+            Self::RequestTimeout => StatusCode::REQUEST_TIMEOUT,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            Self::Busy | Self::Maintenance => StatusCode::SERVICE_UNAVAILABLE,
+            Self::BudgetExceeded => StatusCode::PAYMENT_REQUIRED,
+            Self::ProviderError => StatusCode::BAD_GATEWAY,
+            Self::Refused => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+            Self::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Message used when the handler has nothing more specific to say.
+    pub fn template(self) -> &'static str {
+        match self {
+            Self::InvalidJson => "Invalid JSON body.",
+            Self::MissingField => "A required field is missing.",
+            Self::InvalidRequest => "The request is not valid for this endpoint.",
+            Self::Unauthorized => "Unauthorized.",
+            Self::Forbidden => "Forbidden.",
+            Self::NotFound => "Not found.",
+            Self::MethodNotAllowed => "Method not allowed on this endpoint.",
+            Self::RequestTimeout => "The request took too long and was aborted.",
+            Self::PayloadTooLarge => "Request body exceeds the size limit for this endpoint.",
+            Self::UnsupportedMediaType => "Expected a `Content-Type: application/json` body.",
+            Self::RateLimited => "Too many requests. Please retry later.",
+            Self::Busy => "Too many concurrent requests; retry shortly.",
+            Self::BudgetExceeded => "The cost budget is exhausted.",
+            Self::ProviderError => "The model provider failed to answer.",
+            Self::Refused => "I can't help with that request.",
+            Self::Maintenance => "The gateway is in maintenance mode.",
+            Self::NotImplemented => "Not implemented.",
+            Self::Internal => "Internal error.",
+        }
+    }
+}
+
+/// What [`assign_request_id`] knows about the request being handled.
+#[derive(Debug, Clone)]
+struct RequestScope {
+    id: String,
+    /// `AppState::error_format`, handed to the middleware as its state.
+    format: GatewayErrorFormat,
+}
+
+tokio::task_local! {
+    static REQUEST: RequestScope;
+}
+
+/// Id of the request being handled; a fresh one outside
+/// [`assign_request_id`] (handlers called directly).
+pub fn current_request_id() -> String {
+    REQUEST
+        .try_with(|scope| scope.id.clone())
+        .unwrap_or_else(|_| crate::channels::error_reply::new_request_id())
+}
+
+/// Body shape of the request being handled; the default outside
+/// [`assign_request_id`].
+fn current_format() -> GatewayErrorFormat {
+    REQUEST.try_with(|scope| scope.format).unwrap_or_default()
+}
+
+/// A failed gateway request.
+#[derive(Debug, Clone)]
+pub struct GatewayError {
+    code: ErrorCode,
+    message: String,
+    field: Option<String>,
+    /// Endpoint-specific keys added to the top level of the body.
+    details: serde_json::Map<String, serde_json::Value>,
+}
+
+impl GatewayError {
+    pub fn new(code: ErrorCode) -> Self {
+        Self {
+            code,
+            message: code.template().to_string(),
+            field: None,
+            details: serde_json::Map::new(),
+        }
+    }
+
+    /// Replace the template message.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// The request field at fault.
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// An extra top-level key, e.g. `retry_after`.
+    pub fn detail(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.details.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// A body that did not parse; `missing field` errors become
+    /// [`ErrorCode::MissingField`] naming the field. `expected` is an
+    /// example of a valid body.
+    pub fn invalid_json(detail: &str, expected: &str) -> Self {
+        match missing_field(detail) {
+            Some(field) => Self::new(ErrorCode::MissingField)
+                .message(format!("The `{field}` field is required."))
+                .field(field),
+            None => Self::new(ErrorCode::InvalidJson)
+                .message(format!("Invalid JSON body. Expected: {expected}")),
+        }
+    }
+
+    /// A rejected `Json` extractor.
+    pub fn json_rejection(rejection: &JsonRejection, expected: &str) -> Self {
+        if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+            return Self::new(ErrorCode::PayloadTooLarge);
+        }
+        Self::invalid_json(&rejection.body_text(), expected)
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    fn body(&self, format: GatewayErrorFormat, request_id: &str) -> serde_json::Value {
+        let mut body = self.details.clone();
+        match format {
+            GatewayErrorFormat::Legacy => {
+                body.insert("error".into(), self.message.clone().into());
+                body.insert("code".into(), self.code.as_str().into());
+                if let Some(field) = &self.field {
+                    body.insert("field".into(), field.clone().into());
+                }
+            }
+            GatewayErrorFormat::Structured => {
+                let mut error = serde_json::json!({
+                    "code": self.code,
+                    "message": self.message,
+                });
+                if let Some(field) = &self.field {
+                    error["field"] = field.clone().into();
+                }
+                body.insert("error".into(), error);
+            }
+        }
+        body.insert("request_id".into(), request_id.into());
+        serde_json::Value::Object(body)
+    }
+
+    /// A `data:` event for streams that have already answered `200`.
+    /// Streams outlive the request scope, so the caller passes what
+    /// [`Self::into_reply`] would have looked up.
+    pub fn sse_event(&self, format: GatewayErrorFormat, request_id: &str) -> String {
+        tracing::debug!(
+            request_id = %request_id,
+            code = self.code.as_str(),
+            "Gateway stream failed: {}",
+            self.message
+        );
+        format!("data: {}\n\n", self.body(format, request_id))
+    }
+
+    /// Status and body, for handlers that answer with tuples.
+    pub fn into_reply(self) -> (StatusCode, Json<serde_json::Value>) {
+        let request_id = current_request_id();
+        tracing::debug!(
+            request_id = %request_id,
+            code = self.code.as_str(),
+            "Gateway request failed: {}",
+            self.message
+        );
+        (
+            self.code.status(),
+            Json(self.body(current_format(), &request_id)),
+        )
+    }
+}
+
+impl IntoResponse for GatewayError {
+    fn into_response(self) -> Response {
+        self.into_reply().into_response()
+    }
+}
+
+/// `field` from serde's "missing field `field`" error text.
+fn missing_field(detail: &str) -> Option<&str> {
+    let rest = &detail[detail.find("missing field `")? + "missing field `".len()..];
+    Some(&rest[..rest.find('`')?])
+}
+
+fn valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+/// Axum middleware: scopes the request id and the configured body shape
+/// around the handler, echoes the id in `X-Request-Id`, and gives bare
+/// timeout and body-limit answers a body.
+pub async fn assign_request_id(
+    State(format): State<GatewayErrorFormat>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|id| valid_request_id(id))
+        .map_or_else(crate::channels::error_reply::new_request_id, str::to_string);
+    let scope = RequestScope {
+        id: request_id.clone(),
+        format,
+    };
+    let mut response = REQUEST
+        .scope(scope, async move {
+            let response = next.run(request).await;
+            let bare = !response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("application/json"));
+            match response.status() {
+                StatusCode::PAYLOAD_TOO_LARGE if bare => {
+                    GatewayError::new(ErrorCode::PayloadTooLarge).into_response()
+                }
+                StatusCode::REQUEST_TIMEOUT if bare => {
+                    GatewayError::new(ErrorCode::RequestTimeout).into_response()
+                }
+                _ => response,
+            }
+        })
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// The code of an error body in either format.
+#[cfg(test)]
+pub(super) fn code_of(body: &serde_json::Value) -> Option<&str> {
+    body["error"]["code"].as_str().or(body["code"].as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, routing::post, Router};
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    #[test]
+    fn every_code_has_a_status_template_and_stable_name() {
+        let names: std::collections::HashSet<_> =
+            ErrorCode::ALL.iter().map(|code| code.as_str()).collect();
+        assert_eq!(names.len(), ErrorCode::ALL.len());
+        for code in ErrorCode::ALL {
+            let status = code.status();
+            assert!(
+                status.is_client_error() || status.is_server_error(),
+                "{code:?}"
+            );
+            assert!(!code.template().is_empty(), "{code:?}");
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                code.as_str(),
+                "{code:?}"
+            );
+            let parsed: ErrorCode = serde_json::from_value(code.as_str().into()).unwrap();
+            assert_eq!(parsed, code);
+        }
+    }
+
+    #[test]
+    fn bodies_carry_code_field_and_request_id_in_both_formats() {
+        let error = GatewayError::invalid_json("missing field `message` at line 1 column 2", "{}")
+            .detail("retry_after", 60);
+        assert_eq!(error.code(), ErrorCode::MissingField);
+
+        let legacy = error.body(GatewayErrorFormat::Legacy, "req-1");
+        assert_eq!(legacy["error"], "The `message` field is required.");
+        assert_eq!(legacy["code"], "missing_field");
+        assert_eq!(legacy["field"], "message");
+        assert_eq!(legacy["request_id"], "req-1");
+        assert_eq!(legacy["retry_after"], 60);
+
+        let structured = error.body(GatewayErrorFormat::Structured, "req-1");
+        assert_eq!(structured["error"]["code"], "missing_field");
+        assert_eq!(structured["error"]["field"], "message");
+        assert_eq!(structured["request_id"], "req-1");
+        assert_eq!(structured["retry_after"], 60);
+        assert_eq!(code_of(&legacy), code_of(&structured));
+
+        let garbled = GatewayError::invalid_json("expected value at line 1 column 1", "{}");
+        assert_eq!(garbled.code(), ErrorCode::InvalidJson);
+    }
+
+    #[test]
+    fn sse_events_use_the_same_body() {
+        let error = GatewayError::new(ErrorCode::Refused);
+        let event = error.sse_event(GatewayErrorFormat::Structured, "req-2");
+        let json = event
+            .strip_prefix("data: ")
+            .and_then(|rest| rest.strip_suffix("\n\n"))
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(body, error.body(GatewayErrorFormat::Structured, "req-2"));
+        assert_eq!(body["error"]["code"], "refused");
+    }
+
+    #[tokio::test]
+    async fn request_id_is_echoed_and_oversized_bodies_get_a_code() {
+        let app = Router::new()
+            .route("/webhook", post(|_: axum::body::Bytes| async { "ok" }))
+            .layer(tower_http::limit::RequestBodyLimitLayer::new(8))
+            .layer(axum::middleware::from_fn_with_state(
+                GatewayErrorFormat::Structured,
+                assign_request_id,
+            ));
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/webhook")
+            .header(REQUEST_ID_HEADER, "client-42")
+            .body(Body::from("x".repeat(64)))
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "client-42");
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let body: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(body["error"]["code"], "payload_too_large");
+        assert_eq!(body["request_id"], "client-42");
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/webhook")
+            .header(REQUEST_ID_HEADER, "not an id!")
+            .body(Body::from("ok"))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let echoed = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert_eq!(echoed.len(), 8);
+    }
+}
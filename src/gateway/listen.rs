@@ -0,0 +1,345 @@
+//! Where the gateway listens: `host:port` over TCP, or a Unix domain socket
+//! given as `unix:<path>` in `[gateway] listen` or `--host`.
+//!
+//! A Unix socket keeps a single-host deployment fronted by nginx (or another
+//! local process) off the network entirely. The socket file is created with
+//! `[gateway] socket_permissions` (default `0600`), a stale file left behind
+//! by a crashed gateway is removed on startup, and the file is removed again
+//! when the gateway stops. Clients on the socket are reported to handlers as
+//! loopback peers, so pairing and the loopback-only rules behave as they do
+//! for a `127.0.0.1` bind.
+
+use super::connection::{Accept, ConnectionLimits, GuardedListener};
+use anyhow::{bail, Context, Result};
+use axum::Router;
+use std::net::SocketAddr;
+use std::path::Path;
+use tokio::net::TcpListener;
+
+/// Prefix of a Unix socket listen address.
+pub const UNIX_SCHEME: &str = "unix:";
+
+/// The socket path of a `unix:<path>` listen address.
+pub fn unix_socket_path(listen: &str) -> Option<&Path> {
+    listen
+        .strip_prefix(UNIX_SCHEME)
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(Path::new)
+}
+
+/// Parse `[gateway] socket_permissions` as an octal mode (`"0600"`, `"660"`).
+pub fn parse_socket_mode(raw: &str) -> Result<u32> {
+    let digits = raw.trim();
+    let digits = digits.strip_prefix("0o").unwrap_or(digits);
+    let mode = u32::from_str_radix(digits, 8).with_context(|| {
+        format!("gateway.socket_permissions must be an octal mode such as \"0600\", got {raw:?}")
+    })?;
+    if mode > 0o777 {
+        bail!("gateway.socket_permissions must be at most 0777, got {raw:?}");
+    }
+    Ok(mode)
+}
+
+/// Config-time check of `[gateway] listen`, so a platform without Unix
+/// sockets fails at `config validate` rather than when the gateway starts.
+pub fn validate(listen: &str, socket_permissions: &str) -> Result<()> {
+    let Some(path) = unix_socket_path(listen) else {
+        bail!(
+            "gateway.listen must be \"unix:<absolute path>\" (use gateway.host and gateway.port for TCP), got {listen:?}"
+        );
+    };
+    if cfg!(not(unix)) {
+        bail!(
+            "gateway.listen = {listen:?}: Unix domain sockets are not supported on this platform; use gateway.host and gateway.port"
+        );
+    }
+    if !path.is_absolute() {
+        bail!("gateway.listen socket path must be absolute, got {listen:?}");
+    }
+    parse_socket_mode(socket_permissions).map(drop)
+}
+
+/// A bound gateway socket.
+pub(super) enum Bound {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: tokio::net::UnixListener,
+        socket: SocketFile,
+    },
+}
+
+impl Bound {
+    /// Bind `host` (`unix:<path>` or an IP address) and `port`.
+    pub(super) async fn bind(
+        host: &str,
+        port: u16,
+        gateway: &crate::config::GatewayConfig,
+    ) -> Result<Self> {
+        if host.starts_with(UNIX_SCHEME) {
+            let Some(path) = unix_socket_path(host) else {
+                bail!("gateway listen address {host:?} has an empty socket path");
+            };
+            return bind_unix(path, parse_socket_mode(&gateway.socket_permissions)?).await;
+        }
+        let addr: SocketAddr = format!("{host}:{port}").parse()?;
+        Ok(Self::Tcp(TcpListener::bind(addr).await?))
+    }
+
+    /// The bound TCP port; `0` for a Unix socket.
+    pub(super) fn port(&self) -> u16 {
+        match self {
+            Self::Tcp(listener) => listener.local_addr().map_or(0, |addr| addr.port()),
+            #[cfg(unix)]
+            Self::Unix { .. } => 0,
+        }
+    }
+
+    pub(super) fn is_unix(&self) -> bool {
+        !matches!(self, Self::Tcp(_))
+    }
+
+    /// `http://host:port` or `unix:<path>`, for the banner.
+    pub(super) fn address(&self, host: &str) -> String {
+        match self {
+            Self::Tcp(_) => format!("http://{host}:{}", self.port()),
+            #[cfg(unix)]
+            Self::Unix { socket, .. } => format!("{UNIX_SCHEME}{}", socket.0.display()),
+        }
+    }
+
+    /// Serve `app` until the server stops; a Unix socket file is removed
+    /// afterwards.
+    pub(super) async fn serve(self, app: Router, limits: ConnectionLimits) -> Result<()> {
+        match self {
+            Self::Tcp(listener) => serve_on(listener, app, limits).await,
+            #[cfg(unix)]
+            Self::Unix { listener, socket } => {
+                let served = serve_on(listener, app, limits).await;
+                drop(socket);
+                served
+            }
+        }
+    }
+}
+
+async fn serve_on<L: Accept>(listener: L, app: Router, limits: ConnectionLimits) -> Result<()> {
+    axum::serve(
+        GuardedListener::new(listener, limits),
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn bind_unix(path: &Path, _mode: u32) -> Result<Bound> {
+    bail!(
+        "cannot listen on unix:{}: Unix domain sockets are not supported on this platform",
+        path.display()
+    );
+}
+
+#[cfg(unix)]
+async fn bind_unix(path: &Path, mode: u32) -> Result<Bound> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create socket directory {}", parent.display()))?;
+    }
+    remove_stale_socket(path).await?;
+    let listener = tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("failed to bind unix:{}", path.display()))?;
+    let socket = SocketFile(path.to_path_buf());
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .with_context(|| format!("failed to set permissions {mode:o} on {}", path.display()))?;
+    Ok(Bound::Unix { listener, socket })
+}
+
+/// Remove a socket file nobody is listening on. A live socket or a file
+/// that is not a socket is left alone and refused.
+#[cfg(unix)]
+async fn remove_stale_socket(path: &Path) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if !metadata.file_type().is_socket() {
+        bail!(
+            "{} exists and is not a socket; refusing to replace it",
+            path.display()
+        );
+    }
+    if tokio::net::UnixStream::connect(path).await.is_ok() {
+        bail!(
+            "another process is already listening on unix:{}",
+            path.display()
+        );
+    }
+    std::fs::remove_file(path)
+        .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    tracing::info!("Removed stale gateway socket {}", path.display());
+    Ok(())
+}
+
+/// Removes the socket file when the gateway stops.
+#[cfg(unix)]
+pub(super) struct SocketFile(std::path::PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// `GET /health` over the Unix socket at `path`; `None` when nothing answers
+/// with a JSON `200` within two seconds.
+#[cfg(unix)]
+pub async fn get_health(path: &Path) -> Option<serde_json::Value> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let request = async {
+        let mut stream = tokio::net::UnixStream::connect(path).await.ok()?;
+        stream
+            .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .ok()?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.ok()?;
+        let response = String::from_utf8(response).ok()?;
+        let (head, body) = response.split_once("\r\n\r\n")?;
+        if !head.starts_with("HTTP/1.1 200") {
+            return None;
+        }
+        serde_json::from_str(body).ok()
+    };
+    tokio::time::timeout(std::time::Duration::from_secs(2), request)
+        .await
+        .ok()
+        .flatten()
+}
+
+#[cfg(not(unix))]
+pub async fn get_health(_path: &Path) -> Option<serde_json::Value> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_addresses_and_modes_parse() {
+        assert_eq!(
+            unix_socket_path("unix:/run/zeroclaw/gateway.sock"),
+            Some(Path::new("/run/zeroclaw/gateway.sock"))
+        );
+        assert_eq!(unix_socket_path("unix:"), None);
+        assert_eq!(unix_socket_path("127.0.0.1"), None);
+
+        assert_eq!(parse_socket_mode("0600").unwrap(), 0o600);
+        assert_eq!(parse_socket_mode("660").unwrap(), 0o660);
+        assert_eq!(parse_socket_mode("0o640").unwrap(), 0o640);
+        assert!(parse_socket_mode("0800").is_err());
+        assert!(parse_socket_mode("1777").is_err());
+
+        assert!(validate("127.0.0.1:8080", "0600").is_err());
+        assert!(validate("unix:relative.sock", "0600").is_err());
+        #[cfg(unix)]
+        assert!(validate("unix:/run/zeroclaw/gateway.sock", "0600").is_ok());
+        #[cfg(not(unix))]
+        assert!(validate("unix:/run/zeroclaw/gateway.sock", "0600")
+            .unwrap_err()
+            .to_string()
+            .contains("not supported on this platform"));
+    }
+
+    #[cfg(unix)]
+    mod unix {
+        use super::*;
+        use axum::extract::ConnectInfo;
+        use axum::routing::get;
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::Duration;
+
+        fn gateway_config() -> crate::config::GatewayConfig {
+            crate::config::GatewayConfig::default()
+        }
+
+        fn limits() -> ConnectionLimits {
+            ConnectionLimits::from_config(&gateway_config())
+        }
+
+        fn app() -> Router {
+            Router::new().route(
+                "/health",
+                get(|ConnectInfo(peer): ConnectInfo<SocketAddr>| async move {
+                    axum::Json(serde_json::json!({
+                        "status": "ok",
+                        "runtime": {},
+                        "loopback_peer": peer.ip().is_loopback(),
+                    }))
+                }),
+            )
+        }
+
+        #[tokio::test]
+        async fn serves_requests_over_a_unix_socket() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("run/gateway.sock");
+            let host = format!("unix:{}", path.display());
+
+            let bound = Bound::bind(&host, 0, &gateway_config()).await.unwrap();
+            assert!(bound.is_unix());
+            assert_eq!(bound.address("ignored"), host);
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            let server = tokio::spawn(bound.serve(app(), limits()));
+            let health = get_health(&path).await.expect("health over the socket");
+            assert_eq!(health["status"], "ok");
+            assert_eq!(health["loopback_peer"], true);
+
+            server.abort();
+            let _ = server.await;
+            assert!(!path.exists(), "socket file is removed on shutdown");
+        }
+
+        #[tokio::test]
+        async fn stale_sockets_are_replaced_but_live_ones_are_not() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("gateway.sock");
+            let host = format!("unix:{}", path.display());
+            let mut config = gateway_config();
+            config.socket_permissions = "0660".into();
+
+            // A crashed gateway leaves its socket file behind.
+            drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+            assert!(path.exists());
+            let bound = Bound::bind(&host, 0, &config).await.unwrap();
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o660);
+
+            // While it is served, a second gateway is refused.
+            let server = tokio::spawn(bound.serve(app(), limits()));
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let err = Bound::bind(&host, 0, &config).await.err().unwrap();
+            assert!(err.to_string().contains("already listening"), "{err}");
+            server.abort();
+            let _ = server.await;
+
+            // A regular file is never deleted.
+            std::fs::write(&path, "not a socket").unwrap();
+            let err = Bound::bind(&host, 0, &config).await.err().unwrap();
+            assert!(err.to_string().contains("not a socket"), "{err}");
+            assert_eq!(std::fs::read_to_string(&path).unwrap(), "not a socket");
+        }
+    }
+}
@@ -0,0 +1,440 @@
+//! mDNS / DNS-SD advertisement of the gateway (`[gateway] advertise_mdns`).
+//!
+//! Publishes `_zeroclaw._tcp.local` so companion apps on the LAN can find the
+//! gateway without typing an IP address. The responder is deliberately tiny:
+//! it announces a fixed record set (PTR, SRV, TXT and A) twice at startup, as
+//! RFC 6762 §8.3 asks, then answers any multicast query that names the
+//! service type, the instance or the host. The socket sets `SO_REUSEADDR` so
+//! it shares UDP 5353 with avahi or another local responder.
+//!
+//! Discovery must never imply open access, so [`advertise_target`] refuses
+//! loopback binds (nothing on the LAN could connect) and gateways that do not
+//! require pairing.
+
+use anyhow::{bail, Context, Result};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::Duration;
+
+/// DNS-SD service type advertised for the gateway.
+pub const SERVICE_TYPE: &str = "_zeroclaw._tcp.local";
+
+/// Meta-query used by browsers to enumerate service types (RFC 6763 §9).
+const SERVICES_META_QUERY: &str = "_services._dns-sd._udp.local";
+
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+const MDNS_PORT: u16 = 5353;
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+const CLASS_IN: u16 = 1;
+/// `CLASS_IN` with the cache-flush bit, for records only this host owns.
+const CLASS_IN_FLUSH: u16 = 0x8001;
+
+/// TTLs recommended by RFC 6762 §10: host-bound records 120s, others 75 min.
+const HOST_RECORD_TTL: u32 = 120;
+const SERVICE_RECORD_TTL: u32 = 4500;
+
+/// Max DNS label length.
+const MAX_LABEL_LEN: usize = 63;
+
+/// The records published for one gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceRecord {
+    /// Instance label, e.g. `ZeroClaw on my-laptop`.
+    pub instance: String,
+    /// Host name the SRV record points at, e.g. `my-laptop.local`.
+    pub host: String,
+    pub addr: Ipv4Addr,
+    pub port: u16,
+    /// TXT key/value pairs (`key=value`).
+    pub txt: Vec<String>,
+}
+
+impl ServiceRecord {
+    /// Records for a gateway on `hostname` reachable at `addr:port`.
+    pub fn new(hostname: &str, addr: Ipv4Addr, port: u16) -> Self {
+        let label = host_label(hostname);
+        Self {
+            instance: truncate_label(&format!("ZeroClaw on {label}")),
+            host: format!("{label}.local"),
+            addr,
+            port,
+            txt: vec![
+                format!("version={}", env!("CARGO_PKG_VERSION")),
+                "pairing=required".to_string(),
+            ],
+        }
+    }
+
+    /// Fully qualified instance name, e.g. `ZeroClaw on my-laptop._zeroclaw._tcp.local`.
+    pub fn instance_name(&self) -> String {
+        format!("{}.{SERVICE_TYPE}", self.instance)
+    }
+
+    /// Unsolicited mDNS response carrying every record.
+    pub fn to_packet(&self) -> Vec<u8> {
+        let instance_name = self.instance_name();
+        let mut packet = Vec::with_capacity(256);
+        // id 0, flags: response + authoritative, 0 questions, 4 answers.
+        for field in [0u16, 0x8400, 0, 4, 0, 0] {
+            packet.extend_from_slice(&field.to_be_bytes());
+        }
+
+        let mut ptr = Vec::new();
+        encode_name(&mut ptr, &instance_name);
+        push_record(
+            &mut packet,
+            SERVICE_TYPE,
+            TYPE_PTR,
+            CLASS_IN,
+            SERVICE_RECORD_TTL,
+            &ptr,
+        );
+
+        let mut srv = Vec::new();
+        srv.extend_from_slice(&0u16.to_be_bytes()); // priority
+        srv.extend_from_slice(&0u16.to_be_bytes()); // weight
+        srv.extend_from_slice(&self.port.to_be_bytes());
+        encode_name(&mut srv, &self.host);
+        push_record(
+            &mut packet,
+            &instance_name,
+            TYPE_SRV,
+            CLASS_IN_FLUSH,
+            HOST_RECORD_TTL,
+            &srv,
+        );
+
+        let mut txt = Vec::new();
+        for entry in &self.txt {
+            let bytes = &entry.as_bytes()[..entry.len().min(255)];
+            #[allow(clippy::cast_possible_truncation)]
+            txt.push(bytes.len() as u8);
+            txt.extend_from_slice(bytes);
+        }
+        push_record(
+            &mut packet,
+            &instance_name,
+            TYPE_TXT,
+            CLASS_IN_FLUSH,
+            SERVICE_RECORD_TTL,
+            &txt,
+        );
+
+        push_record(
+            &mut packet,
+            &self.host,
+            TYPE_A,
+            CLASS_IN_FLUSH,
+            HOST_RECORD_TTL,
+            &self.addr.octets(),
+        );
+        packet
+    }
+
+    /// Whether `packet` is a query for any name this record set answers.
+    pub fn answers(&self, packet: &[u8]) -> bool {
+        let instance_name = self.instance_name();
+        query_names(packet).iter().any(|name| {
+            [
+                SERVICE_TYPE,
+                SERVICES_META_QUERY,
+                instance_name.as_str(),
+                self.host.as_str(),
+            ]
+            .iter()
+            .any(|ours| name.eq_ignore_ascii_case(ours))
+        })
+    }
+}
+
+/// The IPv4 address to advertise for a gateway bound to `host`, or why the
+/// gateway must not be advertised. `lan_ipv4` resolves the LAN address when
+/// the gateway is bound to every interface.
+pub fn advertise_target(
+    host: &str,
+    require_pairing: bool,
+    lan_ipv4: impl FnOnce() -> Option<Ipv4Addr>,
+) -> Result<Ipv4Addr> {
+    if !require_pairing {
+        bail!("pairing is disabled, so discovery would advertise an open gateway");
+    }
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost") {
+        bail!("the gateway is bound to loopback ({host}) and is not reachable from the LAN");
+    }
+    let ip: IpAddr = host
+        .parse()
+        .with_context(|| format!("bind host {host} is not an IP address"))?;
+    if ip.is_loopback() {
+        bail!("the gateway is bound to loopback ({host}) and is not reachable from the LAN");
+    }
+    match ip {
+        IpAddr::V4(addr) if !addr.is_unspecified() => Ok(addr),
+        _ if ip.is_unspecified() => {
+            lan_ipv4().context("could not determine a LAN IPv4 address for this host")
+        }
+        _ => bail!("only IPv4 binds can be advertised (bound to {host})"),
+    }
+}
+
+/// The address this host uses to reach the mDNS group, if any. Connecting a
+/// UDP socket only selects a route; nothing is sent.
+pub fn lan_ipv4() -> Option<Ipv4Addr> {
+    let socket = std::net::UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).ok()?;
+    socket.connect((MDNS_GROUP, MDNS_PORT)).ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(addr) if !addr.is_unspecified() && !addr.is_loopback() => Some(addr),
+        _ => None,
+    }
+}
+
+/// Announce `record` and answer queries for it until the task is aborted.
+pub fn spawn(record: ServiceRecord) -> Result<tokio::task::JoinHandle<()>> {
+    let socket = bind_socket(record.addr)?;
+    Ok(tokio::spawn(async move {
+        let group = SocketAddr::V4(SocketAddrV4::new(MDNS_GROUP, MDNS_PORT));
+        let packet = record.to_packet();
+        for _ in 0..2 {
+            if let Err(e) = socket.send_to(&packet, group).await {
+                tracing::warn!("mDNS announcement failed: {e}");
+            }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        let mut buf = vec![0u8; 9000];
+        loop {
+            let len = match socket.recv_from(&mut buf).await {
+                Ok((len, _)) => len,
+                Err(e) => {
+                    tracing::warn!("mDNS receive failed: {e}");
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            };
+            if record.answers(&buf[..len]) {
+                if let Err(e) = socket.send_to(&packet, group).await {
+                    tracing::debug!("mDNS response failed: {e}");
+                }
+            }
+        }
+    }))
+}
+
+fn bind_socket(interface: Ipv4Addr) -> Result<tokio::net::UdpSocket> {
+    use socket2::{Domain, Protocol, Socket, Type};
+
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))
+        .context("failed to create mDNS socket")?;
+    socket.set_reuse_address(true)?;
+    socket
+        .bind(&SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, MDNS_PORT)).into())
+        .context("failed to bind UDP 5353 for mDNS")?;
+    socket
+        .join_multicast_v4(&MDNS_GROUP, &interface)
+        .context("failed to join the mDNS multicast group")?;
+    socket.set_multicast_if_v4(&interface)?;
+    socket.set_multicast_ttl_v4(255)?;
+    socket.set_nonblocking(true)?;
+    Ok(tokio::net::UdpSocket::from_std(socket.into())?)
+}
+
+/// `hostname` reduced to one DNS label of `[a-z0-9-]`.
+fn host_label(hostname: &str) -> String {
+    let first = hostname.split('.').next().unwrap_or_default();
+    let label: String = first
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let label = truncate_label(label.trim_matches('-'));
+    if label.is_empty() {
+        "zeroclaw".to_string()
+    } else {
+        label
+    }
+}
+
+fn truncate_label(label: &str) -> String {
+    let mut end = label.len().min(MAX_LABEL_LEN);
+    while !label.is_char_boundary(end) {
+        end -= 1;
+    }
+    label[..end].to_string()
+}
+
+/// Uncompressed wire form of a dotted name. The instance label may contain
+/// spaces and dots never appear inside our labels, so a plain split is exact.
+fn encode_name(out: &mut Vec<u8>, name: &str) {
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        let bytes = &label.as_bytes()[..label.len().min(MAX_LABEL_LEN)];
+        #[allow(clippy::cast_possible_truncation)]
+        out.push(bytes.len() as u8);
+        out.extend_from_slice(bytes);
+    }
+    out.push(0);
+}
+
+fn push_record(out: &mut Vec<u8>, name: &str, rtype: u16, class: u16, ttl: u32, rdata: &[u8]) {
+    encode_name(out, name);
+    out.extend_from_slice(&rtype.to_be_bytes());
+    out.extend_from_slice(&class.to_be_bytes());
+    out.extend_from_slice(&ttl.to_be_bytes());
+    #[allow(clippy::cast_possible_truncation)]
+    out.extend_from_slice(&(rdata.len() as u16).to_be_bytes());
+    out.extend_from_slice(rdata);
+}
+
+/// Question names of an mDNS query; empty for responses or malformed packets.
+fn query_names(packet: &[u8]) -> Vec<String> {
+    if packet.len() < 12 || packet[2] & 0x80 != 0 {
+        return Vec::new();
+    }
+    let questions = u16::from_be_bytes([packet[4], packet[5]]);
+    let mut offset = 12;
+    let mut names = Vec::new();
+    for _ in 0..questions {
+        let Some((name, next)) = read_name(packet, offset) else {
+            break;
+        };
+        names.push(name);
+        offset = next + 4; // QTYPE + QCLASS
+    }
+    names
+}
+
+/// Decode the name at `offset`, following compression pointers. Returns the
+/// dotted name and the offset just past it in the original position.
+fn read_name(packet: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut end = None;
+    for _ in 0..128 {
+        let len = *packet.get(offset)?;
+        match len {
+            0 => {
+                return Some((labels.join("."), end.unwrap_or(offset + 1)));
+            }
+            len if len & 0xC0 == 0xC0 => {
+                let low = *packet.get(offset + 1)?;
+                end.get_or_insert(offset + 2);
+                offset = usize::from(u16::from_be_bytes([len & 0x3F, low]));
+            }
+            len => {
+                let start = offset + 1;
+                let label = packet.get(start..start + usize::from(len))?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                offset = start + usize::from(len);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (name, type, class, ttl, rdata) for each answer in `packet`.
+    fn answers(packet: &[u8]) -> Vec<(String, u16, u16, u32, Vec<u8>)> {
+        let count = u16::from_be_bytes([packet[6], packet[7]]);
+        let mut offset = 12;
+        let mut out = Vec::new();
+        for _ in 0..count {
+            let (name, next) = read_name(packet, offset).unwrap();
+            let field = |at: usize| u16::from_be_bytes([packet[at], packet[at + 1]]);
+            let rtype = field(next);
+            let class = field(next + 2);
+            let ttl = u32::from_be_bytes(packet[next + 4..next + 8].try_into().unwrap());
+            let len = usize::from(field(next + 8));
+            let rdata = packet[next + 10..next + 10 + len].to_vec();
+            out.push((name, rtype, class, ttl, rdata));
+            offset = next + 10 + len;
+        }
+        out
+    }
+
+    #[test]
+    fn service_record_publishes_ptr_srv_txt_and_a() {
+        let record = ServiceRecord::new("My_Laptop.lan", Ipv4Addr::new(192, 168, 1, 20), 42617);
+        assert_eq!(record.host, "my-laptop.local");
+        assert_eq!(
+            record.instance_name(),
+            "ZeroClaw on my-laptop._zeroclaw._tcp.local"
+        );
+
+        let packet = record.to_packet();
+        let records = answers(&packet);
+        assert_eq!(records.len(), 4);
+
+        let (name, rtype, _, _, rdata) = &records[0];
+        assert_eq!((name.as_str(), *rtype), (SERVICE_TYPE, TYPE_PTR));
+        assert_eq!(read_name(rdata, 0).unwrap().0, record.instance_name());
+
+        let (name, rtype, class, _, rdata) = &records[1];
+        assert_eq!((name.clone(), *rtype), (record.instance_name(), TYPE_SRV));
+        assert_eq!(*class, CLASS_IN_FLUSH);
+        assert_eq!(u16::from_be_bytes([rdata[4], rdata[5]]), 42617);
+        assert_eq!(read_name(rdata, 6).unwrap().0, "my-laptop.local");
+
+        let (_, rtype, _, _, rdata) = &records[2];
+        assert_eq!(*rtype, TYPE_TXT);
+        assert!(String::from_utf8_lossy(rdata).contains("pairing=required"));
+
+        let (name, rtype, _, ttl, rdata) = &records[3];
+        assert_eq!((name.as_str(), *rtype), ("my-laptop.local", TYPE_A));
+        assert_eq!(*ttl, HOST_RECORD_TTL);
+        assert_eq!(rdata.as_slice(), [192, 168, 1, 20]);
+    }
+
+    #[test]
+    fn answers_queries_for_the_service_and_ignores_others() {
+        let record = ServiceRecord::new("nas", Ipv4Addr::new(10, 0, 0, 5), 42617);
+        let query = |name: &str| {
+            let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+            encode_name(&mut packet, name);
+            packet.extend_from_slice(&TYPE_PTR.to_be_bytes());
+            packet.extend_from_slice(&CLASS_IN.to_be_bytes());
+            packet
+        };
+        assert!(record.answers(&query("_zeroclaw._tcp.local")));
+        assert!(record.answers(&query("_ZEROCLAW._tcp.local")));
+        assert!(record.answers(&query("nas.local")));
+        assert!(!record.answers(&query("_http._tcp.local")));
+        // Our own announcement is a response, not a query.
+        assert!(!record.answers(&record.to_packet()));
+    }
+
+    #[test]
+    fn advertise_target_requires_lan_bind_and_pairing() {
+        let lan = || Some(Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(
+            advertise_target("192.168.1.7", true, lan).unwrap(),
+            Ipv4Addr::new(192, 168, 1, 7)
+        );
+        assert_eq!(
+            advertise_target("0.0.0.0", true, lan).unwrap(),
+            Ipv4Addr::new(192, 168, 1, 20)
+        );
+        assert_eq!(
+            advertise_target("[::]", true, lan).unwrap(),
+            Ipv4Addr::new(192, 168, 1, 20)
+        );
+        for host in ["127.0.0.1", "localhost", "[::1]"] {
+            let err = advertise_target(host, true, lan).unwrap_err().to_string();
+            assert!(err.contains("loopback"), "{host}: {err}");
+        }
+        let err = advertise_target("192.168.1.7", false, lan)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("pairing is disabled"));
+        assert!(advertise_target("0.0.0.0", true, || None).is_err());
+        assert!(advertise_target("fd00::7", true, lan).is_err());
+    }
+}
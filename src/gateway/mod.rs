@@ -7,7 +7,14 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
+mod agent_mode;
 pub mod api;
+mod concurrency;
+mod connection;
+mod cors;
+pub mod error;
+pub mod listen;
+pub mod mdns;
 mod openai_compat;
 mod openclaw_compat;
 pub mod sse;
@@ -18,9 +25,10 @@ use crate::channels::{
     Channel, LinqChannel, NextcloudTalkChannel, QQChannel, SendMessage, WatiChannel,
     WhatsAppChannel,
 };
-use crate::config::Config;
+use crate::config::{Config, GatewayErrorFormat, StartupBanner};
 use crate::cost::CostTracker;
 use crate::memory::{self, Memory, MemoryCategory};
+use crate::observability::banner;
 use crate::providers::{self, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
@@ -31,12 +39,13 @@ use crate::util::truncate_with_ellipsis;
 use anyhow::{Context, Result};
 use axum::{
     body::{Body, Bytes},
-    extract::{ConnectInfo, Query, State},
+    extract::{ConnectInfo, Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{delete, get, post, put},
     Router,
 };
+use error::{ErrorCode, GatewayError};
 use futures_util::StreamExt;
 use parking_lot::Mutex;
 use std::collections::HashMap;
@@ -49,7 +58,8 @@ use uuid::Uuid;
 
 /// Maximum request body size (64KB) — prevents memory exhaustion
 pub const MAX_BODY_SIZE: usize = 65_536;
-/// Request timeout (30s) — prevents slow-loris attacks
+/// Default request timeout (30s) — prevents slow-loris attacks
+/// (overridable via `[gateway] request_timeout_secs`)
 pub const REQUEST_TIMEOUT_SECS: u64 = 30;
 /// Sliding window used by gateway rate limiting.
 pub const RATE_LIMIT_WINDOW_SECS: u64 = 60;
@@ -89,6 +99,20 @@ fn hash_webhook_secret(value: &str) -> String {
     hex::encode(digest)
 }
 
+/// Check the `X-Webhook-Secret` header against the configured secret hash.
+///
+/// Both sides are SHA-256 hex digests of equal length, and the comparison is
+/// constant-time, so response timing reveals neither the secret's length nor
+/// how many leading bytes a guess got right.
+pub(super) fn webhook_secret_matches(secret_hash: &str, headers: &HeaderMap) -> bool {
+    headers
+        .get("X-Webhook-Secret")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .is_some_and(|value| constant_time_eq(&hash_webhook_secret(value), secret_hash))
+}
+
 /// How often the rate limiter sweeps stale IP entries from its map.
 const RATE_LIMITER_SWEEP_INTERVAL_SECS: u64 = 300; // 5 minutes
 
@@ -296,8 +320,18 @@ pub struct AppState {
     pub auto_save: bool,
     /// SHA-256 hash of `X-Webhook-Secret` (hex-encoded), never plaintext.
     pub webhook_secret_hash: Option<Arc<str>>,
+    /// HMAC-SHA256 key for `X-Hub-Signature-256` body signatures.
+    pub webhook_hmac_secret: Option<Arc<str>>,
+    /// Maintenance switch: canned replies instead of provider calls.
+    pub maintenance: Arc<crate::maintenance::MaintenanceMode>,
+    /// Lifecycle hooks; `[hooks.outbound]` rules filter every reply.
+    pub hooks: Option<Arc<crate::hooks::HookRunner>>,
+    /// Delegate agents a webhook request can target with `"agent": "<name>"`.
+    pub delegate_agents: Arc<crate::agent::delegates::DelegateAgentPool>,
     pub pairing: Arc<PairingGuard>,
     pub trust_forwarded_headers: bool,
+    /// Error body shape (`[gateway] error_format`).
+    pub error_format: GatewayErrorFormat,
     pub rate_limiter: Arc<GatewayRateLimiter>,
     pub idempotency_store: Arc<IdempotencyStore>,
     pub whatsapp: Option<Arc<WhatsAppChannel>>,
@@ -328,31 +362,46 @@ pub struct AppState {
     pub event_tx: tokio::sync::broadcast::Sender<serde_json::Value>,
 }
 
+/// Refuse a non-loopback bind unless a tunnel fronts the gateway or the
+/// operator opted in with `[gateway] allow_public_bind = true`.
+fn ensure_bind_allowed(host: &str, config: &Config) -> Result<()> {
+    let tunnel_active = config.tunnel.provider != "none";
+    if !is_public_bind(host) || tunnel_active || config.gateway.allow_public_bind {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
+         Anyone who can reach this address could drive the agent through its webhook\n\
+         endpoints and spend your LLM credits.\n\
+         Fix: use --host 127.0.0.1 (default), configure a tunnel, or set\n\
+         [gateway] allow_public_bind = true in config.toml (NOT recommended)."
+    );
+}
+
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
+///
+/// `host` is an IP address or hostname, or `unix:<path>` to listen on a Unix
+/// domain socket instead of `port` (see [`listen`]).
 #[allow(clippy::too_many_lines)]
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     // ── Security: refuse public bind without tunnel or explicit opt-in ──
-    if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
-    {
-        anyhow::bail!(
-            "🛑 Refusing to bind to {host} — gateway would be exposed to the internet.\n\
-             Fix: use --host 127.0.0.1 (default), configure a tunnel, or set\n\
-             [gateway] allow_public_bind = true in config.toml (NOT recommended)."
-        );
+    if !host.starts_with(listen::UNIX_SCHEME) {
+        ensure_bind_allowed(host, &config)?;
     }
     let config_state = Arc::new(Mutex::new(config.clone()));
 
     // ── Hooks ──────────────────────────────────────────────────────
     let hooks: Option<std::sync::Arc<crate::hooks::HookRunner>> = if config.hooks.enabled {
-        Some(std::sync::Arc::new(crate::hooks::HookRunner::new()))
+        Some(std::sync::Arc::new(crate::hooks::HookRunner::from_config(
+            &config.hooks,
+        )?))
     } else {
         None
     };
 
-    let addr: SocketAddr = format!("{host}:{port}").parse()?;
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    let actual_port = listener.local_addr()?.port();
-    let display_addr = format!("{host}:{actual_port}");
+    let listener = listen::Bound::bind(host, port, &config.gateway).await?;
+    let actual_port = listener.port();
+    let address = listener.address(host);
 
     let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
@@ -370,6 +419,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
             max_tokens_override: None,
             model_support_vision: config.model_support_vision,
+            prompt_caching: config.provider.prompt_caching,
+            ollama: config.provider.ollama.clone(),
         },
     )?);
     let model = config
@@ -443,6 +494,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
                     .then(|| Arc::<str>::from(hash_webhook_secret(trimmed_secret)))
             })
         });
+    let webhook_hmac_secret: Option<Arc<str>> = config
+        .channels_config
+        .webhook
+        .as_ref()
+        .and_then(|webhook| webhook.hmac_secret.as_deref())
+        .map(str::trim)
+        .filter(|secret| !secret.is_empty())
+        .map(Arc::from);
 
     // WhatsApp channel (if configured)
     let whatsapp_channel: Option<Arc<WhatsAppChannel>> = config
@@ -593,67 +652,138 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let tunnel = crate::tunnel::create_tunnel(&config.tunnel)?;
     let mut tunnel_url: Option<String> = None;
 
-    if let Some(ref tun) = tunnel {
-        println!("🔗 Starting {} tunnel...", tun.name());
+    let banner_variant = banner::effective(config.observability.startup_banner);
+    if tunnel.is_some() && listener.is_unix() {
+        banner::note(
+            banner_variant,
+            "⚠️  Tunnel not started: tunnels forward to a TCP port and the gateway listens on a Unix socket.",
+        );
+    } else if let Some(ref tun) = tunnel {
+        banner::note(
+            banner_variant,
+            &format!("🔗 Starting {} tunnel...", tun.name()),
+        );
         match tun.start(host, actual_port).await {
             Ok(url) => {
-                println!("🌐 Tunnel active: {url}");
+                banner::note(banner_variant, &format!("🌐 Tunnel active: {url}"));
                 tunnel_url = Some(url);
             }
             Err(e) => {
-                println!("⚠️  Tunnel failed to start: {e}");
-                println!("   Falling back to local-only mode.");
+                banner::note(
+                    banner_variant,
+                    &format!("⚠️  Tunnel failed to start: {e}; falling back to local-only mode."),
+                );
             }
         }
     }
 
-    println!("🦀 ZeroClaw Gateway listening on http://{display_addr}");
+    let mut routes = Vec::new();
     if let Some(ref url) = tunnel_url {
-        println!("  🌐 Public URL: {url}");
+        routes.push(format!("  🌐 Public URL: {url}"));
     }
-    println!("  🌐 Web Dashboard: http://{display_addr}/");
-    println!("  POST /pair      — pair a new client (X-Pairing-Code header)");
-    println!("  POST /webhook   — {{\"message\": \"your prompt\"}}");
-    println!("  POST /api/chat  — {{\"message\": \"...\", \"context\": [...]}} (tools-enabled, OpenClaw compat)");
+    if !listener.is_unix() {
+        routes.push(format!("  🌐 Web Dashboard: {address}/"));
+    }
+    routes.push("  POST /pair      — pair a new client (X-Pairing-Code header)".into());
+    routes.push("  POST /webhook   — {\"message\": \"your prompt\"}".into());
+    routes.push("  POST /api/chat  — {\"message\": \"...\", \"context\": [...]} (tools-enabled, OpenClaw compat)".into());
     if whatsapp_channel.is_some() {
-        println!("  GET  /whatsapp  — Meta webhook verification");
-        println!("  POST /whatsapp  — WhatsApp message webhook");
+        routes.push("  GET  /whatsapp  — Meta webhook verification".into());
+        routes.push("  POST /whatsapp  — WhatsApp message webhook".into());
     }
     if linq_channel.is_some() {
-        println!("  POST /linq      — Linq message webhook (iMessage/RCS/SMS)");
+        routes.push("  POST /linq      — Linq message webhook (iMessage/RCS/SMS)".into());
     }
     if wati_channel.is_some() {
-        println!("  GET  /wati      — WATI webhook verification");
-        println!("  POST /wati      — WATI message webhook");
+        routes.push("  GET  /wati      — WATI webhook verification".into());
+        routes.push("  POST /wati      — WATI message webhook".into());
     }
     if nextcloud_talk_channel.is_some() {
-        println!("  POST /nextcloud-talk — Nextcloud Talk bot webhook");
+        routes.push("  POST /nextcloud-talk — Nextcloud Talk bot webhook".into());
     }
     if qq_webhook_enabled {
-        println!("  POST /qq        — QQ Bot webhook (validation + events)");
+        routes.push("  POST /qq        — QQ Bot webhook (validation + events)".into());
     }
     if config.gateway.node_control.enabled {
-        println!("  POST /api/node-control — experimental node-control RPC scaffold");
-    }
-    println!("  POST /v1/chat/completions — OpenAI-compatible (full agent loop)");
-    println!("  GET  /v1/models — list available models");
-    println!("  GET  /api/*     — REST API (bearer token required)");
-    println!("  GET  /ws/chat   — WebSocket agent chat");
-    println!("  GET  /health    — health check");
-    println!("  GET  /metrics   — Prometheus metrics");
-    if let Some(code) = pairing.pairing_code() {
-        println!();
-        println!("  🔐 PAIRING REQUIRED — use this one-time code:");
-        println!("     ┌──────────────┐");
-        println!("     │  {code}  │");
-        println!("     └──────────────┘");
-        println!("     Send: POST /pair with header X-Pairing-Code: {code}");
+        routes.push("  POST /api/node-control — experimental node-control RPC scaffold".into());
+    }
+    if !config.agents.is_empty() {
+        let mut agents: Vec<&str> = config.agents.keys().map(String::as_str).collect();
+        agents.sort_unstable();
+        routes.push(format!(
+            "  POST /webhook {{\"agent\": ...}} — delegate agents: {}",
+            agents.join(", ")
+        ));
+    }
+    if config.gateway.admin_token.is_some() {
+        routes.push("  GET  /pair/tokens — list paired token fingerprints (admin token)".into());
+        routes.push(
+            "  DELETE /pair/tokens/{fingerprint} — revoke a paired token (admin token)".into(),
+        );
+    }
+    routes.push("  POST /v1/chat/completions — OpenAI-compatible (full agent loop)".into());
+    routes.push("  GET  /v1/models — list available models".into());
+    routes.push("  GET  /api/*     — REST API (bearer token required)".into());
+    routes.push("  GET  /ws/chat   — WebSocket agent chat".into());
+    routes.push("  GET  /health    — health check (HEAD supported)".into());
+    routes.push("  GET  /metrics   — Prometheus metrics".into());
+    let pairing_code = pairing.pairing_code();
+    if let Some(code) = &pairing_code {
+        routes.push(String::new());
+        routes.push("  🔐 PAIRING REQUIRED — use this one-time code:".into());
+        routes.push("     ┌──────────────┐".into());
+        routes.push(format!("     │  {code}  │"));
+        routes.push("     └──────────────┘".into());
+        routes.push(format!(
+            "     Send: POST /pair with header X-Pairing-Code: {code}"
+        ));
     } else if pairing.require_pairing() {
-        println!("  🔒 Pairing: ACTIVE (bearer token required)");
+        routes.push("  🔒 Pairing: ACTIVE (bearer token required)".into());
+    } else {
+        routes.push("  ⚠️  Pairing: DISABLED (all requests accepted)".into());
+    }
+    let webhook_channels: Vec<&str> = [
+        (whatsapp_channel.is_some(), "whatsapp"),
+        (linq_channel.is_some(), "linq"),
+        (wati_channel.is_some(), "wati"),
+        (nextcloud_talk_channel.is_some(), "nextcloud-talk"),
+        (qq_webhook_enabled, "qq"),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, name)| enabled.then_some(name))
+    .collect();
+    let model = config
+        .default_model
+        .as_deref()
+        .unwrap_or("(provider default)");
+    banner::print(
+        banner_variant,
+        &banner::BannerInfo {
+            title: "ZeroClaw Gateway",
+            address: Some(address),
+            model: Some(model),
+            workspace: Some(&config.workspace_dir),
+            config_path: Some(&config.config_path),
+            channels: webhook_channels,
+            details: routes,
+            notice: pairing_code.map(|code| format!("pairing code: {code}")),
+            ..banner::BannerInfo::default()
+        },
+    );
+    let mdns_task = if config.gateway.advertise_mdns && listener.is_unix() {
+        banner::note(
+            banner_variant,
+            "  📡 mDNS: not advertising — the gateway listens on a Unix socket",
+        );
+        None
+    } else if config.gateway.advertise_mdns {
+        start_mdns(host, actual_port, pairing.require_pairing(), banner_variant)
     } else {
-        println!("  ⚠️  Pairing: DISABLED (all requests accepted)");
+        None
+    };
+    if banner_variant == StartupBanner::Full {
+        println!("  Press Ctrl+C to stop.\n");
     }
-    println!("  Press Ctrl+C to stop.\n");
 
     crate::health::mark_component_ok("gateway");
 
@@ -680,8 +810,15 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         mem,
         auto_save: config.memory.auto_save,
         webhook_secret_hash,
+        webhook_hmac_secret,
+        maintenance: Arc::new(crate::maintenance::MaintenanceMode::from_config(&config)),
+        hooks: hooks.clone(),
+        delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::from_config(
+            &config,
+        )),
         pairing,
         trust_forwarded_headers: config.gateway.trust_forwarded_headers,
+        error_format: config.gateway.error_format,
         rate_limiter,
         idempotency_store,
         whatsapp: whatsapp_channel,
@@ -724,12 +861,31 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE,
         ));
 
+    let connection_limits = connection::ConnectionLimits::from_config(&config.gateway);
+    let request_limiter = Arc::new(concurrency::RequestLimiter::new(
+        config.gateway.max_concurrent_requests,
+    ));
+
+    let cors_policy = Arc::new(cors::CorsPolicy::from_config(
+        &config.gateway.cors_allowed_origins,
+    ));
+    let error_format = state.error_format;
+
     // Build router with middleware
     let app = Router::new()
         // ── Existing routes ──
         .route("/health", get(handle_health))
         .route("/metrics", get(handle_metrics))
         .route("/pair", post(handle_pair))
+        .route("/pair/tokens", get(handle_pair_tokens_list))
+        .route(
+            "/pair/tokens/{fingerprint}",
+            delete(handle_pair_token_revoke),
+        )
+        .route(
+            "/admin/maintenance",
+            get(handle_admin_maintenance_get).post(handle_admin_maintenance_set),
+        )
         .route("/webhook", get(handle_webhook_usage).post(handle_webhook))
         .route("/whatsapp", get(handle_whatsapp_verify))
         .route("/whatsapp", post(handle_whatsapp_message))
@@ -771,24 +927,74 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         // ── Config PUT with larger body limit ──
         .merge(config_put_router)
         .with_state(state)
+        .layer(axum::middleware::from_fn_with_state(
+            request_limiter,
+            concurrency::limit_requests,
+        ))
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
             StatusCode::REQUEST_TIMEOUT,
-            Duration::from_secs(REQUEST_TIMEOUT_SECS),
+            connection_limits.request_timeout,
+        ))
+        // ── CORS (opt-in via [gateway] cors_allowed_origins) ──
+        .layer(axum::middleware::from_fn_with_state(
+            cors_policy,
+            cors::handle_cors,
+        ))
+        // ── Request ids, coded bodies for bare 408/413 ──
+        .layer(axum::middleware::from_fn_with_state(
+            error_format,
+            error::assign_request_id,
         ))
         // ── SPA fallback: non-API GET requests serve index.html ──
         .fallback(get(static_files::handle_spa_fallback));
 
     // Run the server
-    axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .await?;
+    listener.serve(app, connection_limits).await?;
 
+    if let Some(task) = mdns_task {
+        task.abort();
+    }
     Ok(())
 }
 
+/// Advertise the gateway over mDNS when the bind and pairing mode allow it.
+fn start_mdns(
+    host: &str,
+    port: u16,
+    require_pairing: bool,
+    banner_variant: StartupBanner,
+) -> Option<tokio::task::JoinHandle<()>> {
+    let addr = match mdns::advertise_target(host, require_pairing, mdns::lan_ipv4) {
+        Ok(addr) => addr,
+        Err(e) => {
+            banner::note(banner_variant, &format!("  📡 mDNS: not advertising — {e}"));
+            return None;
+        }
+    };
+    let hostname =
+        hostname::get().map_or_else(|_| "zeroclaw".into(), |h| h.to_string_lossy().to_string());
+    let record = mdns::ServiceRecord::new(&hostname, addr, port);
+    match mdns::spawn(record.clone()) {
+        Ok(task) => {
+            banner::note(
+                banner_variant,
+                &format!(
+                    "  📡 mDNS: advertising {} as \"{}\" at {addr}:{port}",
+                    mdns::SERVICE_TYPE,
+                    record.instance
+                ),
+            );
+            Some(task)
+        }
+        Err(e) => {
+            tracing::warn!("mDNS advertisement failed to start: {e:#}");
+            banner::note(banner_variant, &format!("  📡 mDNS: not advertising — {e}"));
+            None
+        }
+    }
+}
+
 // ══════════════════════════════════════════════════════════════════════════════
 // AXUM HANDLERS
 // ══════════════════════════════════════════════════════════════════════════════
@@ -799,6 +1005,11 @@ async fn handle_health(State(state): State<AppState>) -> impl IntoResponse {
         "status": "ok",
         "paired": state.pairing.is_paired(),
         "require_pairing": state.pairing.require_pairing(),
+        "maintenance": state.maintenance.is_active(),
+        "delegate_agents": {
+            "configured": state.delegate_agents.configured(),
+            "instantiated": state.delegate_agents.instantiated(),
+        },
         "runtime": crate::health::snapshot_json(),
     });
     Json(body)
@@ -838,7 +1049,7 @@ async fn handle_metrics(
         );
     }
 
-    let body = if let Some(prom) = state
+    let mut body = if let Some(prom) = state
         .observer
         .as_ref()
         .as_any()
@@ -848,6 +1059,8 @@ async fn handle_metrics(
     } else {
         String::from("# Prometheus backend not enabled. Set [observability] backend = \"prometheus\" in config.\n")
     };
+    body.push_str(&connection::render_metrics());
+    body.push_str(&concurrency::render_metrics());
 
     (
         StatusCode::OK,
@@ -856,6 +1069,95 @@ async fn handle_metrics(
     )
 }
 
+/// Body of `POST /admin/maintenance`.
+#[derive(Debug, serde::Deserialize)]
+pub struct MaintenanceRequest {
+    pub enabled: bool,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Answer to a request without a valid paired bearer token.
+const UNPAIRED_MESSAGE: &str =
+    "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>";
+
+/// Paired bearer token when pairing is required, otherwise loopback only.
+fn authorize_admin(
+    state: &AppState,
+    peer_addr: SocketAddr,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    if state.pairing.require_pairing() {
+        let auth = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        let token = auth.strip_prefix("Bearer ").unwrap_or("").trim();
+        if !state.pairing.is_authenticated(token) {
+            return Err(GatewayError::new(ErrorCode::Unauthorized)
+                .message(UNPAIRED_MESSAGE)
+                .into_reply());
+        }
+    } else if !peer_addr.ip().is_loopback() {
+        return Err(GatewayError::new(ErrorCode::Forbidden)
+            .message("Admin endpoints are loopback-only when pairing is not required")
+            .into_reply());
+    }
+    Ok(())
+}
+
+fn maintenance_status_json(status: &crate::maintenance::MaintenanceStatus) -> serde_json::Value {
+    serde_json::json!({
+        "maintenance": status.active,
+        "message": status.message,
+        "pinned_by_config": status.pinned_by_config,
+        "updated_at": status.updated_at,
+    })
+}
+
+/// GET /admin/maintenance — current maintenance status
+async fn handle_admin_maintenance_get(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(rejection) = authorize_admin(&state, peer_addr, &headers) {
+        return rejection;
+    }
+    (
+        StatusCode::OK,
+        Json(maintenance_status_json(&state.maintenance.status())),
+    )
+}
+
+/// POST /admin/maintenance — toggle maintenance mode without a restart
+async fn handle_admin_maintenance_set(
+    State(state): State<AppState>,
+    ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Result<Json<MaintenanceRequest>, axum::extract::rejection::JsonRejection>,
+) -> impl IntoResponse {
+    if let Err(rejection) = authorize_admin(&state, peer_addr, &headers) {
+        return rejection;
+    }
+    let Json(request) = match body {
+        Ok(body) => body,
+        Err(e) => {
+            return GatewayError::json_rejection(&e, "{\"enabled\": true, \"message\": \"...\"}")
+                .into_reply();
+        }
+    };
+    match state.maintenance.set(request.enabled, request.message) {
+        Ok(status) => (StatusCode::OK, Json(maintenance_status_json(&status))),
+        Err(e) => {
+            tracing::error!("Failed to update maintenance state: {e:#}");
+            GatewayError::new(ErrorCode::Internal)
+                .message("Failed to persist maintenance state")
+                .into_reply()
+        }
+    }
+}
+
 /// POST /pair — exchange one-time code for bearer token
 #[axum::debug_handler]
 async fn handle_pair(
@@ -867,11 +1169,10 @@ async fn handle_pair(
         client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_pair(&rate_key) {
         tracing::warn!("/pair rate limit exceeded");
-        let err = serde_json::json!({
-            "error": "Too many pairing requests. Please retry later.",
-            "retry_after": RATE_LIMIT_WINDOW_SECS,
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return GatewayError::new(ErrorCode::RateLimited)
+            .message("Too many pairing requests. Please retry later.")
+            .detail("retry_after", RATE_LIMIT_WINDOW_SECS)
+            .into_reply();
     }
 
     let code = headers
@@ -903,22 +1204,100 @@ async fn handle_pair(
         }
         Ok(None) => {
             tracing::warn!("🔐 Pairing attempt with invalid code");
-            let err = serde_json::json!({"error": "Invalid pairing code"});
-            (StatusCode::FORBIDDEN, Json(err))
+            GatewayError::new(ErrorCode::Forbidden)
+                .message("Invalid pairing code")
+                .field("X-Pairing-Code")
+                .into_reply()
         }
         Err(lockout_secs) => {
             tracing::warn!(
                 "🔐 Pairing locked out — too many failed attempts ({lockout_secs}s remaining)"
             );
-            let err = serde_json::json!({
-                "error": format!("Too many failed attempts. Try again in {lockout_secs}s."),
-                "retry_after": lockout_secs
-            });
-            (StatusCode::TOO_MANY_REQUESTS, Json(err))
+            GatewayError::new(ErrorCode::RateLimited)
+                .message(format!(
+                    "Too many failed attempts. Try again in {lockout_secs}s."
+                ))
+                .detail("retry_after", lockout_secs)
+                .into_reply()
         }
     }
 }
 
+/// `X-Admin-Token` matching `gateway.admin_token`. Paired bearer tokens are
+/// deliberately not accepted, so a paired client cannot revoke its peers.
+fn authorize_token_admin(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let Some(expected) = state.config.lock().gateway.admin_token.clone() else {
+        return Err(GatewayError::new(ErrorCode::Forbidden)
+            .message("Token management is disabled — set gateway.admin_token to enable it")
+            .into_reply());
+    };
+    let provided = headers
+        .get("X-Admin-Token")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .trim();
+    if expected.trim().is_empty() || !constant_time_eq(provided, expected.trim()) {
+        return Err(GatewayError::new(ErrorCode::Unauthorized)
+            .message("Unauthorized — send a valid X-Admin-Token header")
+            .field("X-Admin-Token")
+            .into_reply());
+    }
+    Ok(())
+}
+
+/// GET /pair/tokens — fingerprints of paired tokens (never the tokens)
+async fn handle_pair_tokens_list(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(rejection) = authorize_token_admin(&state, &headers) {
+        return rejection;
+    }
+    let tokens: Vec<serde_json::Value> = state
+        .pairing
+        .fingerprints()
+        .into_iter()
+        .map(|fingerprint| serde_json::json!({ "fingerprint": fingerprint }))
+        .collect();
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "tokens": tokens })),
+    )
+}
+
+/// DELETE /pair/tokens/{fingerprint} — revoke a paired token and persist
+async fn handle_pair_token_revoke(
+    State(state): State<AppState>,
+    Path(fingerprint): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    if let Err(rejection) = authorize_token_admin(&state, &headers) {
+        return rejection;
+    }
+    if !state.pairing.revoke(&fingerprint) {
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("No paired token with that fingerprint")
+            .into_reply();
+    }
+    tracing::info!("🔐 Revoked paired token {fingerprint}");
+    if let Err(err) = persist_pairing_tokens(state.config.clone(), &state.pairing).await {
+        tracing::error!("🔐 Token revoked but persistence failed: {err:#}");
+        let body = serde_json::json!({
+            "revoked": true,
+            "persisted": false,
+            "message": "Revoked for this process, but failed to persist to config.toml. The token will be accepted again after a restart.",
+        });
+        return (StatusCode::OK, Json(body));
+    }
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "revoked": true, "persisted": true })),
+    )
+}
+
 async fn persist_pairing_tokens(config: Arc<Mutex<Config>>, pairing: &PairingGuard) -> Result<()> {
     let paired_tokens = pairing.tokens();
     // This is needed because parking_lot's guard is not Send so we clone the inner
@@ -946,13 +1325,16 @@ async fn prepare_gateway_messages_for_provider(
     // workspace-aware system context before model invocation.
     let system_prompt = {
         let config_guard = state.config.lock();
-        crate::channels::build_system_prompt(
+        crate::channels::build_system_prompt_with_sections(
             &config_guard.workspace_dir,
             &state.model,
             &[], // tools - empty for simple chat
             &[], // skills
             Some(&config_guard.identity),
             None, // bootstrap_max_chars - use default
+            false,
+            crate::config::SkillsPromptInjectionMode::Full,
+            &config_guard.agent.prompt_sections,
         )
     };
 
@@ -969,21 +1351,115 @@ async fn prepare_gateway_messages_for_provider(
 
 /// Simple chat for webhook endpoint (no tools, for backward compatibility and testing).
 async fn run_gateway_chat_simple(state: &AppState, message: &str) -> anyhow::Result<String> {
+    if let Some(reply) = state.maintenance.canned_reply() {
+        return Ok(reply);
+    }
     let prepared_messages = prepare_gateway_messages_for_provider(state, message).await?;
 
-    state
+    let reply = state
         .provider
         .chat_with_history(&prepared_messages, &state.model, state.temperature)
-        .await
+        .await?;
+    Ok(filter_outbound_reply(state, reply).await)
 }
 
 /// Full-featured chat with tools for channel handlers (WhatsApp, Linq, Nextcloud Talk).
+/// Answers with the maintenance reply instead while maintenance is active.
 pub(super) async fn run_gateway_chat_with_tools(
     state: &AppState,
     message: &str,
 ) -> anyhow::Result<String> {
+    if let Some(reply) = state.maintenance.canned_reply() {
+        return Ok(reply);
+    }
     let config = state.config.lock().clone();
-    crate::agent::process_message(config, message).await
+    let reply = crate::agent::process_message(config, message).await?;
+    Ok(filter_outbound_reply(state, reply).await)
+}
+
+/// Webhook chat answered by a delegate agent: its own system prompt, model
+/// and tool allowlist, and no history shared with the default persona.
+async fn run_gateway_chat_for_agent(
+    state: &AppState,
+    agent: &crate::agent::delegates::AgentTarget,
+    message: &str,
+) -> anyhow::Result<String> {
+    let mut history = Vec::with_capacity(2);
+    if let Some(system_prompt) = agent.config.system_prompt.as_deref() {
+        history.push(ChatMessage::system(system_prompt));
+    }
+    history.push(ChatMessage::user(message));
+    let excluded_tools = agent.excluded_tools(state.tools_registry_exec.iter().map(|t| t.name()));
+
+    let reply = crate::agent::loop_::run_tool_call_loop(
+        agent.provider.as_ref(),
+        &mut history,
+        state.tools_registry_exec.as_ref(),
+        state.observer.as_ref(),
+        &agent.config.provider,
+        &agent.config.model,
+        agent.temperature(),
+        true,
+        None,
+        "webhook",
+        &state.multimodal,
+        agent.config.max_iterations,
+        None,
+        None,
+        state.hooks.as_deref(),
+        &excluded_tools,
+    )
+    .await?;
+    Ok(filter_outbound_reply(state, reply).await)
+}
+
+/// Run the `on_message_sending` hooks (including `[hooks.outbound]`) on a
+/// complete gateway reply. A hook cancelling the send yields the block notice.
+pub(super) async fn filter_outbound_reply(state: &AppState, reply: String) -> String {
+    let Some(hooks) = state.hooks.as_ref() else {
+        return reply;
+    };
+    match hooks
+        .run_on_message_sending("gateway".to_string(), "http".to_string(), reply)
+        .await
+    {
+        crate::hooks::HookResult::Continue((_, _, content)) => content,
+        crate::hooks::HookResult::Cancel(_) => {
+            state.config.lock().hooks.outbound.block_notice.clone()
+        }
+    }
+}
+
+/// Whether partial replies must be held back: outbound rules only see whole replies.
+fn holds_partial_replies(state: &AppState) -> bool {
+    state
+        .hooks
+        .as_ref()
+        .is_some_and(|hooks| hooks.filters_outbound())
+}
+
+/// Category for an auto-saved gateway message, per `[memory.classification]`.
+pub(super) async fn autosave_category(state: &AppState, message: &str) -> MemoryCategory {
+    let (classification, use_model) = {
+        let config = state.config.lock();
+        let rules = &config.memory.classification;
+        (
+            crate::memory::classify::classify(rules, message),
+            rules.enabled && rules.model_classifier,
+        )
+    };
+    if use_model && !classification.explicit {
+        if let Some(category) = crate::memory::classify::classify_with_model(
+            state.provider.as_ref(),
+            &state.model,
+            message,
+        )
+        .await
+        {
+            return category;
+        }
+    }
+    classification.category
 }
 
 fn sanitize_gateway_response(response: &str, tools: &[Box<dyn Tool>]) -> String {
@@ -997,11 +1473,26 @@ fn sanitize_gateway_response(response: &str, tools: &[Box<dyn Tool>]) -> String
 }
 
 /// Webhook request body
-#[derive(serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize)]
 pub struct WebhookBody {
     pub message: String,
     #[serde(default)]
     pub stream: Option<bool>,
+    /// Answer with this `[agents.<name>]` entry instead of the default persona.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// `chat` (default): one completion. `agent`: the full tool loop.
+    #[serde(default)]
+    pub mode: WebhookMode,
+}
+
+/// How `POST /webhook` answers a message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookMode {
+    #[default]
+    Chat,
+    Agent,
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -1044,10 +1535,9 @@ async fn handle_node_control(
             .unwrap_or("");
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
         if !state.pairing.is_authenticated(token) {
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message(UNPAIRED_MESSAGE)
+                .into_reply();
         }
     }
 
@@ -1055,19 +1545,15 @@ async fn handle_node_control(
         Ok(body) => body,
         Err(e) => {
             tracing::warn!("Node-control JSON parse error: {e}");
-            let err = serde_json::json!({
-                "error": "Invalid JSON body for node-control request"
-            });
-            return (StatusCode::BAD_REQUEST, Json(err));
+            return GatewayError::json_rejection(&e, "{\"method\": \"node.list\"}").into_reply();
         }
     };
 
     let node_control = { state.config.lock().gateway.node_control.clone() };
     if !node_control.enabled {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "Node-control API is disabled"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("Node-control API is disabled")
+            .into_reply();
     }
 
     // Optional second-factor shared token for node-control endpoints.
@@ -1083,10 +1569,10 @@ async fn handle_node_control(
             .map(str::trim)
             .unwrap_or("");
         if !constant_time_eq(expected_token, provided_token) {
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": "Invalid X-Node-Control-Token"})),
-            );
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Invalid X-Node-Control-Token")
+                .field("X-Node-Control-Token")
+                .into_reply();
         }
     }
 
@@ -1120,16 +1606,16 @@ async fn handle_node_control(
                 .map(str::trim)
                 .filter(|value| !value.is_empty())
             else {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({"error": "node_id is required for node.describe"})),
-                );
+                return GatewayError::new(ErrorCode::MissingField)
+                    .message("node_id is required for node.describe")
+                    .field("node_id")
+                    .into_reply();
             };
             if !node_id_allowed(node_id, &node_control.allowed_node_ids) {
-                return (
-                    StatusCode::FORBIDDEN,
-                    Json(serde_json::json!({"error": "node_id is not allowed"})),
-                );
+                return GatewayError::new(ErrorCode::Forbidden)
+                    .message("node_id is not allowed")
+                    .field("node_id")
+                    .into_reply();
             }
 
             (
@@ -1153,53 +1639,50 @@ async fn handle_node_control(
                 .map(str::trim)
                 .filter(|value| !value.is_empty())
             else {
-                return (
-                    StatusCode::BAD_REQUEST,
-                    Json(serde_json::json!({"error": "node_id is required for node.invoke"})),
-                );
+                return GatewayError::new(ErrorCode::MissingField)
+                    .message("node_id is required for node.invoke")
+                    .field("node_id")
+                    .into_reply();
             };
             if !node_id_allowed(node_id, &node_control.allowed_node_ids) {
-                return (
-                    StatusCode::FORBIDDEN,
-                    Json(serde_json::json!({"error": "node_id is not allowed"})),
-                );
+                return GatewayError::new(ErrorCode::Forbidden)
+                    .message("node_id is not allowed")
+                    .field("node_id")
+                    .into_reply();
             }
 
-            (
-                StatusCode::NOT_IMPLEMENTED,
-                Json(serde_json::json!({
-                    "ok": false,
-                    "method": "node.invoke",
-                    "node_id": node_id,
-                    "capability": request.capability,
-                    "arguments": request.arguments,
-                    "error": "node.invoke backend is not implemented yet in this scaffold"
-                })),
-            )
+            GatewayError::new(ErrorCode::NotImplemented)
+                .message("node.invoke backend is not implemented yet in this scaffold")
+                .detail("ok", false)
+                .detail("method", "node.invoke")
+                .detail("node_id", node_id)
+                .detail("capability", request.capability)
+                .detail("arguments", request.arguments)
+                .into_reply()
         }
-        _ => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({
-                "error": "Unsupported method",
-                "supported_methods": ["node.list", "node.describe", "node.invoke"]
-            })),
-        ),
+        _ => GatewayError::new(ErrorCode::InvalidRequest)
+            .message("Unsupported method")
+            .field("method")
+            .detail(
+                "supported_methods",
+                serde_json::json!(["node.list", "node.describe", "node.invoke"]),
+            )
+            .into_reply(),
     }
 }
 
 /// POST /webhook — main webhook endpoint
 async fn handle_webhook_usage() -> impl IntoResponse {
-    (
-        StatusCode::METHOD_NOT_ALLOWED,
-        Json(serde_json::json!({
-            "error": "Use POST /webhook with a JSON body: {\"message\":\"...\"}",
-            "method": "POST",
-            "path": "/webhook",
-            "example": {
+    GatewayError::new(ErrorCode::MethodNotAllowed)
+        .message("Use POST /webhook with a JSON body: {\"message\":\"...\"}")
+        .detail("method", "POST")
+        .detail("path", "/webhook")
+        .detail(
+            "example",
+            serde_json::json!({
                 "message": "Hello from webhook"
-            }
-        })),
-    )
+            }),
+        )
 }
 
 fn handle_webhook_streaming(
@@ -1209,12 +1692,13 @@ fn handle_webhook_streaming(
     model_label: String,
     started_at: Instant,
 ) -> Response {
-    if !state.provider.supports_streaming() {
+    if !state.provider.supports_streaming() || holds_partial_replies(&state) {
         let model_for_call = state.model.clone();
         let provider_label_for_call = provider_label.clone();
         let model_label_for_call = model_label.clone();
         let state_for_call = state.clone();
         let messages_for_call = prepared_messages.clone();
+        let request_id = error::current_request_id();
 
         let stream = futures_util::stream::once(async move {
             match state_for_call
@@ -1227,6 +1711,7 @@ fn handle_webhook_streaming(
                 .await
             {
                 Ok(response) => {
+                    let response = filter_outbound_reply(&state_for_call, response).await;
                     let safe_response = sanitize_gateway_response(
                         &response,
                         state_for_call.tools_registry_exec.as_ref(),
@@ -1296,10 +1781,12 @@ fn handle_webhook_streaming(
                     );
 
                     tracing::error!("Webhook provider error: {}", sanitized);
-                    let mut output = format!(
-                        "data: {}\n\n",
-                        serde_json::json!({"error": "LLM request failed"})
-                    );
+                    let error = if providers::RefusedError::find(&e).is_some() {
+                        GatewayError::new(ErrorCode::Refused)
+                    } else {
+                        GatewayError::new(ErrorCode::ProviderError).message("LLM request failed")
+                    };
+                    let mut output = error.sse_event(state_for_call.error_format, &request_id);
                     output.push_str("data: [DONE]\n\n");
                     Ok(Bytes::from(output))
                 }
@@ -1325,6 +1812,7 @@ fn handle_webhook_streaming(
     let state_for_stream = state.clone();
     let provider_label_for_stream = provider_label.clone();
     let model_label_for_stream = model_label.clone();
+    let request_id = error::current_request_id();
     let mut stream_failed = false;
 
     let sse_stream = provider_stream.map(move |result| match result {
@@ -1403,10 +1891,10 @@ fn handle_webhook_streaming(
             );
 
             tracing::error!("Webhook streaming provider error: {}", sanitized);
-            let output = format!(
-                "data: {}\n\ndata: [DONE]\n\n",
-                serde_json::json!({"error": "LLM request failed"})
-            );
+            let mut output = GatewayError::new(ErrorCode::ProviderError)
+                .message("LLM request failed")
+                .sse_event(state_for_stream.error_format, &request_id);
+            output.push_str("data: [DONE]\n\n");
             Ok(Bytes::from(output))
         }
     });
@@ -1425,31 +1913,30 @@ async fn handle_webhook(
     State(state): State<AppState>,
     ConnectInfo(peer_addr): ConnectInfo<SocketAddr>,
     headers: HeaderMap,
-    body: Result<Json<WebhookBody>, axum::extract::rejection::JsonRejection>,
+    body: Bytes,
 ) -> Response {
     let rate_key =
         client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_webhook(&rate_key) {
         tracing::warn!("/webhook rate limit exceeded");
-        let err = serde_json::json!({
-            "error": "Too many webhook requests. Please retry later.",
-            "retry_after": RATE_LIMIT_WINDOW_SECS,
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err)).into_response();
+        return GatewayError::new(ErrorCode::RateLimited)
+            .message("Too many webhook requests. Please retry later.")
+            .detail("retry_after", RATE_LIMIT_WINDOW_SECS)
+            .into_response();
     }
 
     // Require at least one auth layer for non-loopback traffic.
     if !state.pairing.require_pairing()
         && state.webhook_secret_hash.is_none()
+        && state.webhook_hmac_secret.is_none()
         && !peer_addr.ip().is_loopback()
     {
         tracing::warn!(
             "Webhook: rejected unauthenticated non-loopback request (pairing disabled and no webhook secret configured)"
         );
-        let err = serde_json::json!({
-            "error": "Unauthorized — configure pairing or X-Webhook-Secret for non-local webhook access"
-        });
-        return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
+        return GatewayError::new(ErrorCode::Unauthorized)
+            .message("Unauthorized — configure pairing, X-Webhook-Secret, or an HMAC secret for non-local webhook access")
+            .into_response();
     }
 
     // ── Bearer token auth (pairing) ──
@@ -1461,40 +1948,48 @@ async fn handle_webhook(
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
         if !state.pairing.is_authenticated(token) {
             tracing::warn!("Webhook: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message(UNPAIRED_MESSAGE)
+                .into_response();
         }
     }
 
     // ── Webhook secret auth (optional, additional layer) ──
     if let Some(ref secret_hash) = state.webhook_secret_hash {
-        let header_hash = headers
-            .get("X-Webhook-Secret")
+        if !webhook_secret_matches(secret_hash, &headers) {
+            tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Unauthorized — invalid or missing X-Webhook-Secret header")
+                .field("X-Webhook-Secret")
+                .into_response();
+        }
+    }
+
+    // ── HMAC body signature (optional, additional layer) ──
+    if let Some(ref hmac_secret) = state.webhook_hmac_secret {
+        let signature = headers
+            .get("X-Hub-Signature-256")
             .and_then(|v| v.to_str().ok())
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
-            .map(hash_webhook_secret);
-        match header_hash {
-            Some(val) if constant_time_eq(&val, secret_hash.as_ref()) => {}
-            _ => {
-                tracing::warn!("Webhook: rejected request — invalid or missing X-Webhook-Secret");
-                let err = serde_json::json!({"error": "Unauthorized — invalid or missing X-Webhook-Secret header"});
-                return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
-            }
+            .unwrap_or("");
+        if !verify_webhook_signature(hmac_secret, &body, signature) {
+            tracing::warn!("Webhook: rejected request — invalid or missing X-Hub-Signature-256");
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Unauthorized — invalid or missing X-Hub-Signature-256 header")
+                .field("X-Hub-Signature-256")
+                .into_response();
         }
     }
 
     // ── Parse body ──
-    let Json(webhook_body) = match body {
+    if !has_json_content_type(&headers) {
+        return GatewayError::new(ErrorCode::UnsupportedMediaType).into_response();
+    }
+    let webhook_body: WebhookBody = match serde_json::from_slice(&body) {
         Ok(b) => b,
         Err(e) => {
             tracing::warn!("Webhook JSON parse error: {e}");
-            let err = serde_json::json!({
-                "error": "Invalid JSON body. Expected: {\"message\": \"...\"}"
-            });
-            return (StatusCode::BAD_REQUEST, Json(err)).into_response();
+            return GatewayError::invalid_json(&e.to_string(), "{\"message\": \"...\"}")
+                .into_response();
         }
     };
 
@@ -1518,27 +2013,86 @@ async fn handle_webhook(
 
     let message = webhook_body.message.trim();
     if message.is_empty() {
-        let err = serde_json::json!({
-            "error": "The `message` field is required and must be a non-empty string."
+        return GatewayError::new(ErrorCode::MissingField)
+            .message("The `message` field is required and must be a non-empty string.")
+            .field("message")
+            .into_response();
+    }
+
+    // ── Delegate agent target (optional) ──
+    let agent_name = webhook_body
+        .agent
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty());
+    if agent_name.is_some() && webhook_body.mode == WebhookMode::Agent {
+        return GatewayError::new(ErrorCode::InvalidRequest)
+            .message(
+                "`mode: \"agent\"` runs the default persona and cannot be combined with `agent`",
+            )
+            .field("agent")
+            .into_response();
+    }
+    if let Some(name) = agent_name {
+        if !state.delegate_agents.contains(name) {
+            let configured = state.delegate_agents.configured();
+            return GatewayError::new(ErrorCode::InvalidRequest)
+                .message(crate::agent::delegates::unknown_agent_message(
+                    name,
+                    &configured,
+                ))
+                .field("agent")
+                .detail("agents", configured)
+                .into_response();
+        }
+    }
+
+    // ── Maintenance: canned reply, no provider call ──
+    if let Some(reply) = state.maintenance.canned_reply() {
+        let body = serde_json::json!({
+            "response": reply,
+            "model": state.model,
+            "maintenance": true,
         });
-        return (StatusCode::BAD_REQUEST, Json(err)).into_response();
+        return (StatusCode::OK, Json(body)).into_response();
     }
 
+    let agent = match agent_name.map(|name| state.delegate_agents.target(name)) {
+        None => None,
+        Some(Ok(target)) => Some(target),
+        Some(Err(e)) => {
+            let sanitized = providers::sanitize_api_error(&format!("{e:#}"));
+            tracing::error!("Webhook agent initialization failed: {sanitized}");
+            return GatewayError::new(ErrorCode::Internal)
+                .message("Failed to initialize agent")
+                .into_response();
+        }
+    };
+
     if state.auto_save {
         let key = webhook_memory_key();
+        let category = autosave_category(&state, message).await;
+        let session = agent
+            .as_ref()
+            .map(crate::agent::delegates::AgentTarget::memory_session);
         let _ = state
             .mem
-            .store(&key, message, MemoryCategory::Conversation, None)
+            .store(&key, message, category, session.as_deref())
             .await;
     }
 
-    let provider_label = state
-        .config
-        .lock()
-        .default_provider
-        .clone()
-        .unwrap_or_else(|| "unknown".to_string());
-    let model_label = state.model.clone();
+    let provider_label = match &agent {
+        Some(target) => target.config.provider.clone(),
+        None => state
+            .config
+            .lock()
+            .default_provider
+            .clone()
+            .unwrap_or_else(|| "unknown".to_string()),
+    };
+    let model_label = agent
+        .as_ref()
+        .map_or_else(|| state.model.clone(), |target| target.config.model.clone());
     let started_at = Instant::now();
 
     state
@@ -1555,7 +2109,36 @@ async fn handle_webhook(
             messages_count: 1,
         });
 
-    if webhook_body.stream.unwrap_or(false) {
+    // Agent-mode and delegate-agent requests are answered in one response.
+    if webhook_body.mode == WebhookMode::Agent {
+        let report = agent_mode::run(&state, message).await;
+        let duration = started_at.elapsed();
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::LlmResponse {
+                provider: provider_label.clone(),
+                model: model_label.clone(),
+                duration,
+                success: report.error.is_none() && !report.timed_out,
+                error_message: report.error.clone(),
+                input_tokens: None,
+                output_tokens: None,
+            });
+        state
+            .observer
+            .record_metric(&crate::observability::traits::ObserverMetric::RequestLatency(duration));
+        state
+            .observer
+            .record_event(&crate::observability::ObserverEvent::AgentEnd {
+                provider: provider_label,
+                model: model_label,
+                duration,
+                tokens_used: None,
+                cost_usd: None,
+            });
+        return (report.status(), Json(report)).into_response();
+    }
+    if webhook_body.stream.unwrap_or(false) && agent.is_none() {
         let prepared_messages = match prepare_gateway_messages_for_provider(&state, message).await {
             Ok(messages) => messages,
             Err(e) => {
@@ -1591,9 +2174,7 @@ async fn handle_webhook(
                         cost_usd: None,
                     });
 
-                tracing::error!("Webhook streaming setup failed: {}", sanitized);
-                let err = serde_json::json!({"error": "LLM request failed"});
-                return (StatusCode::INTERNAL_SERVER_ERROR, Json(err)).into_response();
+                return webhook_error(&state, &e).into_response();
             }
         };
 
@@ -1606,11 +2187,19 @@ async fn handle_webhook(
         );
     }
 
-    match run_gateway_chat_simple(&state, message).await {
+    let result = match &agent {
+        Some(target) => run_gateway_chat_for_agent(&state, target, message).await,
+        None => run_gateway_chat_simple(&state, message).await,
+    };
+    match result {
         Ok(response) => {
             let safe_response =
                 sanitize_gateway_response(&response, state.tools_registry_exec.as_ref());
             let duration = started_at.elapsed();
+            let mut body = serde_json::json!({"response": safe_response, "model": model_label});
+            if let Some(target) = &agent {
+                body["agent"] = serde_json::json!(target.name);
+            }
             state
                 .observer
                 .record_event(&crate::observability::ObserverEvent::LlmResponse {
@@ -1635,7 +2224,6 @@ async fn handle_webhook(
                     cost_usd: None,
                 });
 
-            let body = serde_json::json!({"response": safe_response, "model": state.model});
             (StatusCode::OK, Json(body)).into_response()
         }
         Err(e) => {
@@ -1672,13 +2260,32 @@ async fn handle_webhook(
                     cost_usd: None,
                 });
 
-            tracing::error!("Webhook provider error: {}", sanitized);
-            let err = serde_json::json!({"error": "LLM request failed"});
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(err)).into_response()
+            webhook_error(&state, &e).into_response()
         }
     }
 }
 
+/// Friendly error for a failed `/webhook` request, in the channels' error
+/// language. The full error is only logged, under the request id.
+fn webhook_error(state: &AppState, err: &anyhow::Error) -> GatewayError {
+    use crate::providers::reliable::ErrorClass;
+
+    let replies = crate::channels::error_reply::ErrorReplies::from_config(
+        &state.config.lock().channels_config,
+    )
+    .unwrap_or_default();
+    let reply = replies.render_as(err, error::current_request_id());
+    let code = match reply.class {
+        ErrorClass::BudgetExceeded => ErrorCode::BudgetExceeded,
+        ErrorClass::Refused => ErrorCode::Refused,
+        ErrorClass::ToolFailure | ErrorClass::Internal => ErrorCode::Internal,
+        _ => ErrorCode::ProviderError,
+    };
+    GatewayError::new(code)
+        .message(reply.text)
+        .detail("error_class", reply.class.as_str())
+}
+
 /// `WhatsApp` verification query params
 #[derive(serde::Deserialize)]
 pub struct WhatsAppVerifyQuery {
@@ -1720,13 +2327,17 @@ async fn handle_whatsapp_verify(
 /// Returns true if the signature is valid, false otherwise.
 /// See: <https://developers.facebook.com/docs/graph-api/webhooks/getting-started#verification-requests>
 pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header: &str) -> bool {
-    use hmac::{Hmac, Mac};
-    use sha2::Sha256;
-
     // Signature format: "sha256=<hex_signature>"
     let Some(hex_sig) = signature_header.strip_prefix("sha256=") else {
         return false;
     };
+    verify_hmac_sha256_hex(app_secret, body, hex_sig)
+}
+
+/// Check a hex-encoded HMAC-SHA256 of `body` in constant time.
+fn verify_hmac_sha256_hex(secret: &str, body: &[u8], hex_sig: &str) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
 
     // Decode hex signature
     let Ok(expected) = hex::decode(hex_sig) else {
@@ -1734,7 +2345,7 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
     };
 
     // Compute HMAC-SHA256
-    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(app_secret.as_bytes()) else {
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
         return false;
     };
     mac.update(body);
@@ -1743,17 +2354,47 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
     mac.verify_slice(&expected).is_ok()
 }
 
-/// POST /whatsapp — incoming message webhook
+/// Verify an HMAC-SHA256 signature of the raw `/webhook` body.
+///
+/// Accepts `sha256=<hex>` (GitHub `X-Hub-Signature-256`) or bare hex; the
+/// digest comparison is constant-time.
+pub fn verify_webhook_signature(secret: &str, body: &[u8], signature_header: &str) -> bool {
+    let signature = signature_header.trim();
+    let hex_sig = signature.strip_prefix("sha256=").unwrap_or(signature);
+    !hex_sig.is_empty() && verify_hmac_sha256_hex(secret, body, hex_sig)
+}
+
+/// Whether `Content-Type` is `application/json` or a `+json` type, the
+/// check axum's `Json` extractor made before `/webhook` took raw bytes.
+fn has_json_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json"
+        || essence
+            .strip_prefix("application/")
+            .is_some_and(|subtype| subtype.ends_with("+json"))
+}
+
+/// POST /whatsapp — incoming message webhook
 async fn handle_whatsapp_message(
     State(state): State<AppState>,
     headers: HeaderMap,
     body: Bytes,
 ) -> impl IntoResponse {
     let Some(ref wa) = state.whatsapp else {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "WhatsApp not configured"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("WhatsApp not configured")
+            .into_reply();
     };
 
     // ── Security: Verify X-Hub-Signature-256 if app_secret is configured ──
@@ -1772,19 +2413,17 @@ async fn handle_whatsapp_message(
                     "invalid"
                 }
             );
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": "Invalid signature"})),
-            );
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Invalid signature")
+                .into_reply();
         }
     }
 
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Invalid JSON payload"})),
-        );
+        return GatewayError::new(ErrorCode::InvalidJson)
+            .message("Invalid JSON payload")
+            .into_reply();
     };
 
     // Parse messages from the webhook payload
@@ -1806,10 +2445,8 @@ async fn handle_whatsapp_message(
         // Auto-save to memory
         if state.auto_save {
             let key = whatsapp_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+            let category = autosave_category(&state, &msg.content).await;
+            let _ = state.mem.store(&key, &msg.content, category, None).await;
         }
 
         match run_gateway_chat_with_tools(&state, &msg.content).await {
@@ -1847,10 +2484,9 @@ async fn handle_linq_webhook(
     body: Bytes,
 ) -> impl IntoResponse {
     let Some(ref linq) = state.linq else {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "Linq not configured"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("Linq not configured")
+            .into_reply();
     };
 
     let body_str = String::from_utf8_lossy(&body);
@@ -1881,19 +2517,17 @@ async fn handle_linq_webhook(
                     "invalid"
                 }
             );
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": "Invalid signature"})),
-            );
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Invalid signature")
+                .into_reply();
         }
     }
 
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Invalid JSON payload"})),
-        );
+        return GatewayError::new(ErrorCode::InvalidJson)
+            .message("Invalid JSON payload")
+            .into_reply();
     };
 
     // Parse messages from the webhook payload
@@ -1924,10 +2558,8 @@ async fn handle_linq_webhook(
         // Auto-save to memory
         if state.auto_save {
             let key = linq_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+            let category = autosave_category(&state, &msg.content).await;
+            let _ = state.mem.store(&key, &msg.content, category, None).await;
         }
 
         // Call the LLM
@@ -1986,18 +2618,16 @@ pub struct WatiVerifyQuery {
 /// POST /wati — incoming WATI WhatsApp message webhook
 async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl IntoResponse {
     let Some(ref wati) = state.wati else {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "WATI not configured"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("WATI not configured")
+            .into_reply();
     };
 
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Invalid JSON payload"})),
-        );
+        return GatewayError::new(ErrorCode::InvalidJson)
+            .message("Invalid JSON payload")
+            .into_reply();
     };
 
     // Parse messages from the webhook payload
@@ -2018,10 +2648,8 @@ async fn handle_wati_webhook(State(state): State<AppState>, body: Bytes) -> impl
         // Auto-save to memory
         if state.auto_save {
             let key = wati_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+            let category = autosave_category(&state, &msg.content).await;
+            let _ = state.mem.store(&key, &msg.content, category, None).await;
         }
 
         // Call the LLM
@@ -2060,10 +2688,9 @@ async fn handle_nextcloud_talk_webhook(
     body: Bytes,
 ) -> impl IntoResponse {
     let Some(ref nextcloud_talk) = state.nextcloud_talk else {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "Nextcloud Talk not configured"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("Nextcloud Talk not configured")
+            .into_reply();
     };
 
     let body_str = String::from_utf8_lossy(&body);
@@ -2094,19 +2721,17 @@ async fn handle_nextcloud_talk_webhook(
                     "invalid"
                 }
             );
-            return (
-                StatusCode::UNAUTHORIZED,
-                Json(serde_json::json!({"error": "Invalid signature"})),
-            );
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Invalid signature")
+                .into_reply();
         }
     }
 
     // Parse JSON body
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Invalid JSON payload"})),
-        );
+        return GatewayError::new(ErrorCode::InvalidJson)
+            .message("Invalid JSON payload")
+            .into_reply();
     };
 
     // Parse messages from webhook payload
@@ -2125,10 +2750,8 @@ async fn handle_nextcloud_talk_webhook(
 
         if state.auto_save {
             let key = nextcloud_talk_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+            let category = autosave_category(&state, &msg.content).await;
+            let _ = state.mem.store(&key, &msg.content, category, None).await;
         }
 
         match run_gateway_chat_with_tools(&state, &msg.content).await {
@@ -2164,17 +2787,15 @@ async fn handle_qq_webhook(
     body: Bytes,
 ) -> impl IntoResponse {
     let Some(ref qq) = state.qq else {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "QQ not configured"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("QQ not configured")
+            .into_reply();
     };
 
     if !state.qq_webhook_enabled {
-        return (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({"error": "QQ webhook mode not enabled"})),
-        );
+        return GatewayError::new(ErrorCode::NotFound)
+            .message("QQ webhook mode not enabled")
+            .into_reply();
     }
 
     let app_id_header = headers
@@ -2184,17 +2805,15 @@ async fn handle_qq_webhook(
         .unwrap_or("");
     if !app_id_header.is_empty() && !constant_time_eq(app_id_header, qq.app_id()) {
         tracing::warn!("QQ webhook rejected due to mismatched X-Bot-Appid");
-        return (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"error": "Invalid X-Bot-Appid"})),
-        );
+        return GatewayError::new(ErrorCode::Unauthorized)
+            .message("Invalid X-Bot-Appid")
+            .into_reply();
     }
 
     let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({"error": "Invalid JSON payload"})),
-        );
+        return GatewayError::new(ErrorCode::InvalidJson)
+            .message("Invalid JSON payload")
+            .into_reply();
     };
 
     if let Some(validation_response) = qq.build_webhook_validation_response(&payload) {
@@ -2216,10 +2835,8 @@ async fn handle_qq_webhook(
 
         if state.auto_save {
             let key = qq_memory_key(msg);
-            let _ = state
-                .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation, None)
-                .await;
+            let category = autosave_category(&state, &msg.content).await;
+            let _ = state.mem.store(&key, &msg.content, category, None).await;
         }
 
         match run_gateway_chat_with_tools(&state, &msg.content).await {
@@ -2352,8 +2969,13 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2408,8 +3030,13 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2450,8 +3077,13 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2493,8 +3125,13 @@ mod tests {
             mem: Arc::new(MockMemory),
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(true, std::slice::from_ref(&paired_token))),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2948,6 +3585,137 @@ Reminder set successfully."#;
         ConnectInfo(SocketAddr::from(([203, 0, 113, 10], 30_300)))
     }
 
+    fn webhook_json(body: WebhookBody) -> Bytes {
+        Bytes::from(serde_json::to_vec(&body).unwrap())
+    }
+
+    /// Headers of a plain JSON `/webhook` request.
+    fn json_headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers
+    }
+
+    /// The `code` of an error response, in either body format.
+    async fn error_code(response: Response) -> String {
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert!(parsed["request_id"].is_string(), "{parsed}");
+        error::code_of(&parsed).unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn webhook_body_errors_are_coded() {
+        let mut state = hmac_webhook_state(Arc::new(MockProvider::default()), "x");
+        state.webhook_hmac_secret = None;
+        let send = |body: &'static [u8]| {
+            handle_webhook(
+                State(state.clone()),
+                test_connect_info(),
+                json_headers(),
+                Bytes::from_static(body),
+            )
+        };
+
+        let response = send(br#"{"text": "hi"}"#).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(response).await, "missing_field");
+
+        let response = send(b"{not json").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(response).await, "invalid_json");
+    }
+
+    #[tokio::test]
+    async fn webhook_requires_a_json_content_type() {
+        let mut state = hmac_webhook_state(Arc::new(MockProvider::default()), "x");
+        state.webhook_hmac_secret = None;
+        let body = Bytes::from_static(br#"{"text": "hi"}"#);
+        let send = |content_type: Option<&'static str>| {
+            let mut headers = HeaderMap::new();
+            if let Some(content_type) = content_type {
+                headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+            }
+            handle_webhook(
+                State(state.clone()),
+                test_connect_info(),
+                headers,
+                body.clone(),
+            )
+        };
+
+        for content_type in [None, Some("text/plain"), Some("application/jsonp")] {
+            let response = send(content_type).await;
+            assert_eq!(
+                response.status(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "{content_type:?}"
+            );
+            assert_eq!(error_code(response).await, "unsupported_media_type");
+        }
+
+        // Accepted types reach the body parser, which finds no `message`.
+        for content_type in [
+            "application/json; charset=utf-8",
+            "application/vnd.api+json",
+        ] {
+            let response = send(Some(content_type)).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{content_type}");
+            assert_eq!(error_code(response).await, "missing_field");
+        }
+    }
+
+    /// Fails every request with `message`.
+    struct FailingProvider {
+        message: &'static str,
+    }
+
+    #[async_trait]
+    impl Provider for FailingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            anyhow::bail!("{}", self.message)
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_provider_failures_map_to_codes() {
+        let cases = [
+            (
+                "Anthropic API error (529): overloaded",
+                StatusCode::BAD_GATEWAY,
+                "provider_error",
+            ),
+            (
+                "OpenAI API error (429): insufficient_quota",
+                StatusCode::PAYMENT_REQUIRED,
+                "budget_exceeded",
+            ),
+        ];
+        for (message, status, code) in cases {
+            let mut state = hmac_webhook_state(Arc::new(FailingProvider { message }), "x");
+            state.webhook_hmac_secret = None;
+            let body = webhook_json(WebhookBody {
+                message: "hello".into(),
+                stream: None,
+                agent: None,
+                mode: WebhookMode::Chat,
+            });
+            let response =
+                handle_webhook(State(state), test_connect_info(), json_headers(), body).await;
+            assert_eq!(response.status(), status, "{message}");
+            assert_eq!(error_code(response).await, code, "{message}");
+        }
+    }
+
     #[tokio::test]
     async fn webhook_idempotency_skips_duplicate_provider_calls() {
         let provider_impl = Arc::new(MockProvider::default());
@@ -2962,8 +3730,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -2984,13 +3757,15 @@ Reminder set successfully."#;
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
 
-        let mut headers = HeaderMap::new();
+        let mut headers = json_headers();
         headers.insert("X-Idempotency-Key", HeaderValue::from_static("abc-123"));
 
-        let body = Ok(Json(WebhookBody {
+        let body = webhook_json(WebhookBody {
             message: "hello".into(),
             stream: None,
-        }));
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
         let first = handle_webhook(
             State(state.clone()),
             test_connect_info(),
@@ -3001,10 +3776,12 @@ Reminder set successfully."#;
         .into_response();
         assert_eq!(first.status(), StatusCode::OK);
 
-        let body = Ok(Json(WebhookBody {
+        let body = webhook_json(WebhookBody {
             message: "hello".into(),
             stream: None,
-        }));
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
         let second = handle_webhook(State(state), test_connect_info(), headers, body)
             .await
             .into_response();
@@ -3031,8 +3808,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3056,16 +3838,19 @@ Reminder set successfully."#;
         let response = handle_webhook(
             State(state),
             test_public_connect_info(),
-            HeaderMap::new(),
-            Ok(Json(WebhookBody {
+            json_headers(),
+            webhook_json(WebhookBody {
                 message: "hello".into(),
                 stream: None,
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
         )
         .await
         .into_response();
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(error_code(response).await, "unauthorized");
     }
 
     #[tokio::test]
@@ -3082,8 +3867,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3107,16 +3897,19 @@ Reminder set successfully."#;
         let response = handle_webhook(
             State(state),
             test_connect_info(),
-            HeaderMap::new(),
-            Ok(Json(WebhookBody {
+            json_headers(),
+            webhook_json(WebhookBody {
                 message: "   ".into(),
                 stream: None,
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
         )
         .await
         .into_response();
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(response).await, "missing_field");
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
     }
 
@@ -3134,8 +3927,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3159,11 +3957,13 @@ Reminder set successfully."#;
         let response = handle_webhook(
             State(state),
             test_connect_info(),
-            HeaderMap::new(),
-            Ok(Json(WebhookBody {
+            json_headers(),
+            webhook_json(WebhookBody {
                 message: "stream me".into(),
                 stream: Some(true),
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
         )
         .await
         .into_response();
@@ -3195,8 +3995,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3230,6 +4035,7 @@ Reminder set successfully."#;
         .await
         .into_response();
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error_code(response).await, "not_found");
     }
 
     #[tokio::test]
@@ -3249,8 +4055,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3308,8 +4119,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: true,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3330,12 +4146,14 @@ Reminder set successfully."#;
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
 
-        let headers = HeaderMap::new();
+        let headers = json_headers();
 
-        let body1 = Ok(Json(WebhookBody {
+        let body1 = webhook_json(WebhookBody {
             message: "hello one".into(),
             stream: None,
-        }));
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
         let first = handle_webhook(
             State(state.clone()),
             test_connect_info(),
@@ -3346,10 +4164,12 @@ Reminder set successfully."#;
         .into_response();
         assert_eq!(first.status(), StatusCode::OK);
 
-        let body2 = Ok(Json(WebhookBody {
+        let body2 = webhook_json(WebhookBody {
             message: "hello two".into(),
             stream: None,
-        }));
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
         let second = handle_webhook(State(state), test_connect_info(), headers, body2)
             .await
             .into_response();
@@ -3376,6 +4196,36 @@ Reminder set successfully."#;
         assert_eq!(one.len(), 64);
     }
 
+    #[test]
+    fn webhook_secret_matches_only_the_exact_secret() {
+        let secret = "s3cret-webhook-value";
+        let secret_hash = hash_webhook_secret(secret);
+        let with_secret = |value: &str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("X-Webhook-Secret", HeaderValue::from_str(value).unwrap());
+            headers
+        };
+
+        assert!(webhook_secret_matches(&secret_hash, &with_secret(secret)));
+        assert!(webhook_secret_matches(
+            &secret_hash,
+            &with_secret(&format!("  {secret} "))
+        ));
+
+        for wrong in [
+            "s3cret",
+            "s3cret-webhook-valuX",
+            "s3cret-webhook-value-extra",
+            " ",
+        ] {
+            assert!(
+                !webhook_secret_matches(&secret_hash, &with_secret(wrong)),
+                "{wrong:?} must be rejected"
+            );
+        }
+        assert!(!webhook_secret_matches(&secret_hash, &HeaderMap::new()));
+    }
+
     #[tokio::test]
     async fn webhook_secret_hash_rejects_missing_header() {
         let provider_impl = Arc::new(MockProvider::default());
@@ -3391,8 +4241,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3416,11 +4271,13 @@ Reminder set successfully."#;
         let response = handle_webhook(
             State(state),
             test_connect_info(),
-            HeaderMap::new(),
-            Ok(Json(WebhookBody {
+            json_headers(),
+            webhook_json(WebhookBody {
                 message: "hello".into(),
                 stream: None,
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
         )
         .await
         .into_response();
@@ -3445,8 +4302,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&valid_secret))),
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3467,7 +4329,7 @@ Reminder set successfully."#;
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
 
-        let mut headers = HeaderMap::new();
+        let mut headers = json_headers();
         headers.insert(
             "X-Webhook-Secret",
             HeaderValue::from_str(&wrong_secret).unwrap(),
@@ -3477,10 +4339,12 @@ Reminder set successfully."#;
             State(state),
             test_connect_info(),
             headers,
-            Ok(Json(WebhookBody {
+            webhook_json(WebhookBody {
                 message: "hello".into(),
                 stream: None,
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
         )
         .await
         .into_response();
@@ -3504,8 +4368,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: Some(Arc::from(hash_webhook_secret(&secret))),
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3526,25 +4395,658 @@ Reminder set successfully."#;
             event_tx: tokio::sync::broadcast::channel(16).0,
         };
 
-        let mut headers = HeaderMap::new();
+        let mut headers = json_headers();
         headers.insert("X-Webhook-Secret", HeaderValue::from_str(&secret).unwrap());
 
         let response = handle_webhook(
             State(state),
             test_connect_info(),
             headers,
-            Ok(Json(WebhookBody {
+            webhook_json(WebhookBody {
                 message: "hello".into(),
                 stream: None,
-            })),
+                agent: None,
+                mode: WebhookMode::Chat,
+            }),
+        )
+        .await
+        .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
+    }
+
+    fn hmac_webhook_state(provider: Arc<dyn Provider>, hmac_secret: &str) -> AppState {
+        AppState {
+            config: Arc::new(Mutex::new(Config::default())),
+            provider,
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            webhook_secret_hash: None,
+            webhook_hmac_secret: Some(Arc::from(hmac_secret)),
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            linq: None,
+            linq_signing_secret: None,
+            nextcloud_talk: None,
+            nextcloud_talk_webhook_secret: None,
+            wati: None,
+            qq: None,
+            qq_webhook_enabled: false,
+            observer: Arc::new(crate::observability::NoopObserver),
+            tools_registry: Arc::new(Vec::new()),
+            tools_registry_exec: Arc::new(Vec::new()),
+            multimodal: crate::config::MultimodalConfig::default(),
+            max_tool_iterations: 10,
+            cost_tracker: None,
+            event_tx: tokio::sync::broadcast::channel(16).0,
+        }
+    }
+
+    fn sign_webhook_body(secret: &str, body: &[u8]) -> String {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[tokio::test]
+    async fn webhook_hmac_accepts_valid_signature_from_public_peer() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let secret = generate_test_secret();
+        let state = hmac_webhook_state(provider_impl.clone(), &secret);
+        let body = webhook_json(WebhookBody {
+            message: "hello".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
+
+        let mut headers = json_headers();
+        headers.insert(
+            "X-Hub-Signature-256",
+            HeaderValue::from_str(&sign_webhook_body(&secret, &body)).unwrap(),
+        );
+
+        let response = handle_webhook(State(state), test_public_connect_info(), headers, body)
+            .await
+            .into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn webhook_hmac_rejects_missing_or_invalid_signature() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let secret = generate_test_secret();
+        let body = webhook_json(WebhookBody {
+            message: "hello".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
+        let tampered = webhook_json(WebhookBody {
+            message: "hello!".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
+
+        let signatures = [
+            None,
+            Some(sign_webhook_body("wrong-secret", &body)),
+            // Signed over a different body.
+            Some(sign_webhook_body(&secret, &tampered)),
+            Some("sha256=not-hex".to_string()),
+        ];
+        for signature in signatures {
+            let mut headers = json_headers();
+            if let Some(signature) = signature {
+                headers.insert(
+                    "X-Hub-Signature-256",
+                    HeaderValue::from_str(&signature).unwrap(),
+                );
+            }
+            let state = hmac_webhook_state(provider_impl.clone(), &secret);
+            let response = handle_webhook(State(state), test_connect_info(), headers, body.clone())
+                .await
+                .into_response();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+            assert_eq!(error_code(response).await, "unauthorized");
+        }
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn webhook_answers_with_canned_reply_during_maintenance() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let secret = generate_test_secret();
+        let mut state = hmac_webhook_state(provider_impl.clone(), &secret);
+        let tmp = tempfile::tempdir().unwrap();
+        let maintenance = crate::maintenance::MaintenanceMode::new(
+            tmp.path().join(crate::maintenance::STATE_FILE),
+            false,
+            "Down for maintenance. {message}",
+            false,
+        );
+        maintenance.set(true, Some("Back at 5pm".into())).unwrap();
+        state.maintenance = Arc::new(maintenance);
+
+        let body = webhook_json(WebhookBody {
+            message: "hello".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
+        let mut headers = json_headers();
+        headers.insert(
+            "X-Hub-Signature-256",
+            HeaderValue::from_str(&sign_webhook_body(&secret, &body)).unwrap(),
+        );
+
+        let response = handle_webhook(
+            State(state.clone()),
+            test_public_connect_info(),
+            headers,
+            body,
         )
         .await
         .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["response"], "Down for maintenance. Back at 5pm");
+        assert_eq!(parsed["maintenance"], true);
+        assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 0);
 
+        let health = handle_health(State(state)).await.into_response();
+        let payload = health.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["maintenance"], true);
+    }
+
+    #[tokio::test]
+    async fn webhook_reply_passes_through_outbound_filter() {
+        let provider_impl = Arc::new(MockProvider::default());
+        let secret = generate_test_secret();
+        let mut state = hmac_webhook_state(provider_impl.clone(), &secret);
+        let mut hooks_config = crate::config::HooksConfig::default();
+        hooks_config
+            .outbound
+            .redactions
+            .push(crate::config::OutboundRedactionRule {
+                pattern: "^ok$".into(),
+                replacement: "[filtered]".into(),
+            });
+        let hooks = crate::hooks::HookRunner::from_config(&hooks_config).unwrap();
+        assert!(hooks.filters_outbound());
+        state.hooks = Some(Arc::new(hooks));
+
+        let body = webhook_json(WebhookBody {
+            message: "hello".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Chat,
+        });
+        let mut headers = json_headers();
+        headers.insert(
+            "X-Hub-Signature-256",
+            HeaderValue::from_str(&sign_webhook_body(&secret, &body)).unwrap(),
+        );
+
+        let response = handle_webhook(State(state), test_public_connect_info(), headers, body)
+            .await
+            .into_response();
         assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["response"], "[filtered]");
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 1);
     }
 
+    /// Replies from a script, then hangs once it runs out.
+    struct ScriptedWebhookProvider {
+        replies: Mutex<std::collections::VecDeque<&'static str>>,
+    }
+
+    impl ScriptedWebhookProvider {
+        fn new(replies: &[&'static str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().copied().collect()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedWebhookProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let next = self.replies.lock().pop_front();
+            match next {
+                Some(reply) => Ok(reply.to_string()),
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    struct EchoTool {
+        runs: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the given text"
+        }
+
+        fn parameters_schema(&self) -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": { "text": { "type": "string" } }
+            })
+        }
+
+        async fn execute(&self, args: serde_json::Value) -> anyhow::Result<tools::ToolResult> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(tools::ToolResult {
+                success: true,
+                output: format!("echo:{}", args["text"].as_str().unwrap_or_default()),
+                error: None,
+            })
+        }
+    }
+
+    const ECHO_CALL: &str = r#"<tool_call>
+{"name":"echo","arguments":{"text":"build log"}}
+</tool_call>"#;
+
+    async fn run_agent_mode_webhook(
+        replies: &[&'static str],
+        configure: impl FnOnce(&mut Config),
+    ) -> (StatusCode, serde_json::Value, usize) {
+        let workspace = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.workspace_dir = workspace.path().to_path_buf();
+        configure(&mut config);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut state = hmac_webhook_state(Arc::new(ScriptedWebhookProvider::new(replies)), "x");
+        state.webhook_hmac_secret = None;
+        state.config = Arc::new(Mutex::new(config));
+        state.tools_registry_exec = Arc::new(vec![Box::new(EchoTool {
+            runs: Arc::clone(&runs),
+        })]);
+
+        let body = webhook_json(WebhookBody {
+            message: "CI failed, investigate".into(),
+            stream: None,
+            agent: None,
+            mode: WebhookMode::Agent,
+        });
+        let response = handle_webhook(State(state), test_connect_info(), json_headers(), body)
+            .await
+            .into_response();
+        let status = response.status();
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        (status, parsed, runs.load(Ordering::SeqCst))
+    }
+
+    #[tokio::test]
+    async fn webhook_agent_mode_runs_tools_and_reports_them() {
+        let (status, body, runs) = run_agent_mode_webhook(&[ECHO_CALL, "Log says OOM."], |c| {
+            c.autonomy.level = crate::security::AutonomyLevel::Full;
+        })
+        .await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(runs, 1);
+        assert_eq!(body["mode"], "agent");
+        assert_eq!(body["response"], "Log says OOM.");
+        assert_eq!(body["timed_out"], false);
+        let calls = body["tool_calls"].as_array().unwrap();
+        assert_eq!(calls.len(), 1, "{body}");
+        assert_eq!(calls[0]["tool"], "echo");
+        assert_eq!(calls[0]["arguments"]["text"], "build log");
+        assert_eq!(calls[0]["success"], true);
+        assert_eq!(calls[0]["output"], "echo:build log");
+        assert_eq!(calls[0]["truncated"], false);
+        assert!(body["denied_tools"].as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn webhook_agent_mode_denies_tools_that_need_approval() {
+        // Supervised by default; `echo` is not auto-approved and nobody can
+        // answer the prompt, so the call is refused instead of hanging.
+        let (status, body, runs) =
+            run_agent_mode_webhook(&[ECHO_CALL, "Could not read the log."], |_| {}).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(runs, 0);
+        assert_eq!(body["response"], "Could not read the log.");
+        assert!(body["tool_calls"].as_array().unwrap().is_empty());
+        let denied = body["denied_tools"].as_array().unwrap();
+        assert_eq!(denied.len(), 1, "{body}");
+        assert_eq!(denied[0]["tool"], "echo");
+    }
+
+    #[tokio::test]
+    async fn webhook_agent_mode_returns_partial_results_on_timeout() {
+        // The provider never answers after the tool call.
+        let (status, body, runs) = run_agent_mode_webhook(&[ECHO_CALL], |c| {
+            c.autonomy.level = crate::security::AutonomyLevel::Full;
+            c.gateway.webhook_agent_timeout_secs = 1;
+        })
+        .await;
+        assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(runs, 1);
+        assert_eq!(body["timed_out"], true);
+        assert!(body["response"].is_null());
+        assert_eq!(body["tool_calls"][0]["output"], "echo:build log");
+    }
+
+    #[tokio::test]
+    async fn webhook_agent_mode_cannot_target_a_delegate_agent() {
+        let mut state = hmac_webhook_state(Arc::new(MockProvider::default()), "x");
+        state.webhook_hmac_secret = None;
+        let body = webhook_json(WebhookBody {
+            message: "hi".into(),
+            stream: None,
+            agent: Some("homelab".into()),
+            mode: WebhookMode::Agent,
+        });
+        let response = handle_webhook(State(state), test_connect_info(), json_headers(), body)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(error_code(response).await, "invalid_request");
+    }
+
+    /// Records every system prompt, message and model it is asked about.
+    #[derive(Default)]
+    struct RecordingProvider {
+        reply: &'static str,
+        seen: Mutex<Vec<(Option<String>, String, String)>>,
+    }
+
+    #[async_trait]
+    impl Provider for RecordingProvider {
+        async fn chat_with_system(
+            &self,
+            system_prompt: Option<&str>,
+            message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.seen.lock().push((
+                system_prompt.map(str::to_string),
+                message.to_string(),
+                model.to_string(),
+            ));
+            Ok(self.reply.into())
+        }
+    }
+
+    fn delegate_agent(model: &str, system_prompt: &str) -> crate::config::DelegateAgentConfig {
+        crate::config::DelegateAgentConfig {
+            provider: "ollama".into(),
+            model: model.into(),
+            system_prompt: Some(system_prompt.into()),
+            api_key: None,
+            temperature: None,
+            max_depth: 3,
+            agentic: false,
+            allowed_tools: Vec::new(),
+            max_iterations: 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_agent_field_routes_to_that_agents_provider() {
+        let default_provider = Arc::new(MockProvider::default());
+        let triager = Arc::new(RecordingProvider {
+            reply: "ticket filed",
+            ..RecordingProvider::default()
+        });
+        let homelab = Arc::new(RecordingProvider {
+            reply: "nas is up",
+            ..RecordingProvider::default()
+        });
+        let mut state = hmac_webhook_state(default_provider.clone(), "unused");
+        state.webhook_hmac_secret = None;
+        state.delegate_agents = Arc::new(
+            crate::agent::delegates::DelegateAgentPool::new(
+                HashMap::from([
+                    (
+                        "support-triager".to_string(),
+                        delegate_agent("triage-model", "You triage support tickets."),
+                    ),
+                    (
+                        "homelab".to_string(),
+                        delegate_agent("homelab-model", "You run the homelab."),
+                    ),
+                ]),
+                None,
+                providers::ProviderRuntimeOptions::default(),
+            )
+            .with_provider("support-triager", triager.clone())
+            .with_provider("homelab", homelab.clone()),
+        );
+
+        let send = |agent: &str, message: &str| {
+            let body = webhook_json(WebhookBody {
+                message: message.into(),
+                stream: None,
+                agent: Some(agent.into()),
+                mode: WebhookMode::Chat,
+            });
+            handle_webhook(
+                State(state.clone()),
+                test_connect_info(),
+                json_headers(),
+                body,
+            )
+        };
+
+        let response = send("support-triager", "printer on fire").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["response"], "ticket filed");
+        assert_eq!(parsed["agent"], "support-triager");
+        assert_eq!(parsed["model"], "triage-model");
+
+        let response = send("homelab", "is the nas up?").await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert_eq!(default_provider.calls.load(Ordering::SeqCst), 0);
+        let triager_seen = triager.seen.lock().clone();
+        let homelab_seen = homelab.seen.lock().clone();
+        assert_eq!(triager_seen.len(), 1);
+        assert_eq!(homelab_seen.len(), 1);
+        let (system, message, model) = &homelab_seen[0];
+        assert_eq!(system.as_deref(), Some("You run the homelab."));
+        assert_eq!(model, "homelab-model");
+        assert!(message.contains("is the nas up?"));
+        assert!(!message.contains("printer on fire"));
+        assert!(!triager_seen[0].1.contains("nas"));
+
+        let response = send("ghost", "hello").await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(error::code_of(&parsed), Some("invalid_request"));
+        assert_eq!(
+            parsed["agents"],
+            serde_json::json!(["homelab", "support-triager"])
+        );
+    }
+
+    async fn token_admin_state(paired: &[&str]) -> (tempfile::TempDir, AppState) {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = Config::default();
+        config.config_path = temp.path().join("config.toml");
+        config.workspace_dir = temp.path().join("workspace");
+        config.gateway.admin_token = Some("admin-secret".into());
+        config.save().await.unwrap();
+
+        let mut state = hmac_webhook_state(Arc::new(MockProvider::default()), "unused");
+        state.webhook_hmac_secret = None;
+        state.config = Arc::new(Mutex::new(config));
+        let paired: Vec<String> = paired.iter().map(|t| (*t).to_string()).collect();
+        state.pairing = Arc::new(PairingGuard::new(true, &paired));
+        (temp, state)
+    }
+
+    fn fingerprint_of(token: &str) -> String {
+        use sha2::{Digest, Sha256};
+        crate::security::pairing::token_fingerprint(&hex::encode(Sha256::digest(token)))
+    }
+
+    #[tokio::test]
+    async fn pair_tokens_list_requires_admin_token_and_hides_tokens() {
+        let (_temp, state) = token_admin_state(&["zc_alpha", "zc_beta"]).await;
+
+        let mut bearer = HeaderMap::new();
+        bearer.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer zc_alpha"),
+        );
+        let response = handle_pair_tokens_list(State(state.clone()), bearer)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let mut admin = HeaderMap::new();
+        admin.insert("X-Admin-Token", HeaderValue::from_static("admin-secret"));
+        let response = handle_pair_tokens_list(State(state.clone()), admin.clone())
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let text = String::from_utf8(payload.to_vec()).unwrap();
+        assert!(!text.contains("zc_"));
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        let mut listed: Vec<&str> = parsed["tokens"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["fingerprint"].as_str().unwrap())
+            .collect();
+        listed.sort_unstable();
+        let mut expected = [fingerprint_of("zc_alpha"), fingerprint_of("zc_beta")];
+        expected.sort();
+        assert_eq!(listed, expected);
+
+        state.config.lock().gateway.admin_token = None;
+        let response = handle_pair_tokens_list(State(state), admin)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn revoked_pair_token_is_rejected_on_webhook() {
+        let (_temp, state) = token_admin_state(&["zc_alpha", "zc_beta"]).await;
+        let mut admin = HeaderMap::new();
+        admin.insert("X-Admin-Token", HeaderValue::from_static("admin-secret"));
+
+        let response = handle_pair_token_revoke(
+            State(state.clone()),
+            Path(fingerprint_of("zc_alpha")),
+            admin.clone(),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["persisted"], true);
+
+        let response = handle_pair_token_revoke(State(state.clone()), Path("0".repeat(16)), admin)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error_code(response).await, "not_found");
+
+        let webhook = |token: &'static str| {
+            let mut headers = json_headers();
+            headers.insert(
+                header::AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+            );
+            let body = webhook_json(WebhookBody {
+                message: "hello".into(),
+                stream: None,
+                agent: None,
+                mode: WebhookMode::Chat,
+            });
+            handle_webhook(State(state.clone()), test_connect_info(), headers, body)
+        };
+        assert_eq!(webhook("zc_alpha").await.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(webhook("zc_beta").await.status(), StatusCode::OK);
+
+        let config_path = state.config.lock().config_path.clone();
+        let saved = tokio::fs::read_to_string(config_path).await.unwrap();
+        let parsed: Config = toml::from_str(&saved).unwrap();
+        assert_eq!(parsed.gateway.paired_tokens.len(), 1);
+    }
+
+    #[test]
+    fn webhook_signature_accepts_prefixed_and_bare_hex() {
+        let body = br#"{"message":"hi"}"#;
+        let signed = sign_webhook_body("secret", body);
+        let bare = signed.strip_prefix("sha256=").unwrap();
+
+        assert!(verify_webhook_signature("secret", body, &signed));
+        assert!(verify_webhook_signature("secret", body, bare));
+        assert!(!verify_webhook_signature("secret", body, ""));
+        assert!(!verify_webhook_signature("secret", body, "sha256="));
+    }
+
+    #[test]
+    fn webhook_signature_comparison_rejects_near_misses() {
+        let body = b"payload";
+        let signed = sign_webhook_body("secret", body);
+
+        // Flip the last hex digit: same length, one nibble different.
+        let mut near = signed.clone();
+        let last = near.pop().unwrap();
+        near.push(if last == '0' { '1' } else { '0' });
+        assert!(!verify_webhook_signature("secret", body, &near));
+
+        // Truncated or extended digests never match, even as a prefix.
+        assert!(!verify_webhook_signature(
+            "secret",
+            body,
+            &signed[..signed.len() - 2]
+        ));
+        assert!(!verify_webhook_signature(
+            "secret",
+            body,
+            &format!("{signed}00")
+        ));
+    }
+
     fn compute_nextcloud_signature_hex(secret: &str, random: &str, body: &str) -> String {
         use hmac::{Hmac, Mac};
         use sha2::Sha256;
@@ -3568,8 +5070,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3599,6 +5106,7 @@ Reminder set successfully."#;
         .into_response();
 
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(error_code(response).await, "not_found");
     }
 
     #[tokio::test]
@@ -3627,8 +5135,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3679,8 +5192,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -3730,8 +5248,13 @@ Reminder set successfully."#;
             mem: memory,
             auto_save: false,
             webhook_secret_hash: None,
+            webhook_hmac_secret: None,
+            maintenance: Arc::new(crate::maintenance::MaintenanceMode::disabled()),
+            hooks: None,
+            delegate_agents: Arc::new(crate::agent::delegates::DelegateAgentPool::default()),
             pairing: Arc::new(PairingGuard::new(false, &[])),
             trust_forwarded_headers: false,
+            error_format: GatewayErrorFormat::Legacy,
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300), 1000)),
             whatsapp: None,
@@ -4163,4 +5686,34 @@ Reminder set successfully."#;
         // Should be allowed again
         assert!(limiter.allow("burst-ip"));
     }
+
+    #[test]
+    fn loopback_bind_is_always_allowed() {
+        let config = Config::default();
+        for host in ["127.0.0.1", "localhost", "::1", "[::1]"] {
+            assert!(ensure_bind_allowed(host, &config).is_ok(), "{host}");
+        }
+    }
+
+    #[test]
+    fn public_bind_without_opt_in_is_rejected() {
+        let config = Config::default();
+        let err = ensure_bind_allowed("0.0.0.0", &config)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Refusing to bind to 0.0.0.0"));
+        assert!(err.contains("allow_public_bind = true"));
+        assert!(ensure_bind_allowed("192.168.1.10", &config).is_err());
+    }
+
+    #[test]
+    fn public_bind_with_opt_in_or_tunnel_is_allowed() {
+        let mut config = Config::default();
+        config.gateway.allow_public_bind = true;
+        assert!(ensure_bind_allowed("0.0.0.0", &config).is_ok());
+
+        let mut config = Config::default();
+        config.tunnel.provider = "cloudflare".into();
+        assert!(ensure_bind_allowed("0.0.0.0", &config).is_ok());
+    }
 }
@@ -220,6 +220,15 @@ pub async fn handle_v1_chat_completions(
         })
         .collect();
 
+    // Maintenance: answer with the canned reply (always non-streaming).
+    if let Some(reply) = state.maintenance.canned_reply() {
+        return (
+            StatusCode::OK,
+            Json(serde_json::to_value(maintenance_completion(model, reply)).unwrap()),
+        )
+            .into_response();
+    }
+
     let provider_label = state
         .config
         .lock()
@@ -260,6 +269,29 @@ pub async fn handle_v1_chat_completions(
     }
 }
 
+/// Completion carrying the maintenance reply; no provider call, zero usage.
+fn maintenance_completion(model: String, reply: String) -> ChatCompletionsResponse {
+    ChatCompletionsResponse {
+        id: format!("chatcmpl-{}", Uuid::new_v4()),
+        object: "chat.completion",
+        created: unix_timestamp(),
+        model,
+        choices: vec![ChatCompletionsChoice {
+            index: 0,
+            message: ChatCompletionsResponseMessage {
+                role: "assistant",
+                content: reply,
+            },
+            finish_reason: "stop",
+        }],
+        usage: ChatCompletionsUsage {
+            prompt_tokens: 0,
+            completion_tokens: 0,
+            total_tokens: 0,
+        },
+    }
+}
+
 /// Non-streaming chat completions.
 async fn handle_non_streaming(
     state: AppState,
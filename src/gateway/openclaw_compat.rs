@@ -25,11 +25,12 @@
 //! is provided for backward compatibility and may be deprecated once all callers
 //! have migrated to the native endpoint.
 
+use super::error::{ErrorCode, GatewayError};
 use super::{
-    client_key_from_request, run_gateway_chat_with_tools, sanitize_gateway_response, AppState,
-    RATE_LIMIT_WINDOW_SECS,
+    autosave_category, client_key_from_request, run_gateway_chat_with_tools,
+    sanitize_gateway_response, webhook_error, webhook_secret_matches, AppState,
+    RATE_LIMIT_WINDOW_SECS, UNPAIRED_MESSAGE,
 };
-use crate::memory::MemoryCategory;
 use crate::providers;
 use axum::{
     body::Body,
@@ -83,11 +84,10 @@ pub async fn handle_api_chat(
         client_key_from_request(Some(peer_addr), &headers, state.trust_forwarded_headers);
     if !state.rate_limiter.allow_webhook(&rate_key) {
         tracing::warn!("/api/chat rate limit exceeded");
-        let err = serde_json::json!({
-            "error": "Too many chat requests. Please retry later.",
-            "retry_after": RATE_LIMIT_WINDOW_SECS,
-        });
-        return (StatusCode::TOO_MANY_REQUESTS, Json(err));
+        return GatewayError::new(ErrorCode::RateLimited)
+            .message("Too many chat requests. Please retry later.")
+            .detail("retry_after", RATE_LIMIT_WINDOW_SECS)
+            .into_reply();
     }
 
     // ── Auth: require at least one layer for non-loopback ──
@@ -96,10 +96,9 @@ pub async fn handle_api_chat(
         && !peer_addr.ip().is_loopback()
     {
         tracing::warn!("/api/chat: rejected unauthenticated non-loopback request");
-        let err = serde_json::json!({
-            "error": "Unauthorized — configure pairing or X-Webhook-Secret for non-local access"
-        });
-        return (StatusCode::UNAUTHORIZED, Json(err));
+        return GatewayError::new(ErrorCode::Unauthorized)
+            .message("Unauthorized — configure pairing or X-Webhook-Secret for non-local access")
+            .into_reply();
     }
 
     // ── Bearer token auth (pairing) ──
@@ -111,10 +110,20 @@ pub async fn handle_api_chat(
         let token = auth.strip_prefix("Bearer ").unwrap_or("");
         if !state.pairing.is_authenticated(token) {
             tracing::warn!("/api/chat: rejected — not paired / invalid bearer token");
-            let err = serde_json::json!({
-                "error": "Unauthorized — pair first via POST /pair, then send Authorization: Bearer <token>"
-            });
-            return (StatusCode::UNAUTHORIZED, Json(err));
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message(UNPAIRED_MESSAGE)
+                .into_reply();
+        }
+    }
+
+    // ── Webhook secret auth (optional, additional layer) ──
+    if let Some(ref secret_hash) = state.webhook_secret_hash {
+        if !webhook_secret_matches(secret_hash, &headers) {
+            tracing::warn!("/api/chat: rejected — invalid or missing X-Webhook-Secret");
+            return GatewayError::new(ErrorCode::Unauthorized)
+                .message("Unauthorized — invalid or missing X-Webhook-Secret header")
+                .field("X-Webhook-Secret")
+                .into_reply();
         }
     }
 
@@ -123,26 +132,23 @@ pub async fn handle_api_chat(
         Ok(b) => b,
         Err(e) => {
             tracing::warn!("/api/chat JSON parse error: {e}");
-            let err = serde_json::json!({
-                "error": "Invalid JSON body. Expected: {\"message\": \"...\"}"
-            });
-            return (StatusCode::BAD_REQUEST, Json(err));
+            return GatewayError::json_rejection(&e, "{\"message\": \"...\"}").into_reply();
         }
     };
 
     let message = chat_body.message.trim();
     if message.is_empty() {
-        let err = serde_json::json!({ "error": "Message cannot be empty" });
-        return (StatusCode::BAD_REQUEST, Json(err));
+        return GatewayError::new(ErrorCode::MissingField)
+            .message("Message cannot be empty")
+            .field("message")
+            .into_reply();
     }
 
     // ── Auto-save to memory ──
     if state.auto_save {
         let key = api_chat_memory_key();
-        let _ = state
-            .mem
-            .store(&key, message, MemoryCategory::Conversation, None)
-            .await;
+        let category = autosave_category(&state, message).await;
+        let _ = state.mem.store(&key, message, category, None).await;
     }
 
     // ── Build enriched message with optional context ──
@@ -252,8 +258,7 @@ pub async fn handle_api_chat(
                 });
 
             tracing::error!("/api/chat provider error: {sanitized}");
-            let err = serde_json::json!({"error": "LLM request failed"});
-            (StatusCode::INTERNAL_SERVER_ERROR, Json(err))
+            webhook_error(&state, &e).into_reply()
         }
     }
 }
@@ -411,6 +416,23 @@ pub async fn handle_v1_chat_completions_with_tools(
         }
     }
 
+    // ── Webhook secret auth (optional, additional layer) ──
+    if let Some(ref secret_hash) = state.webhook_secret_hash {
+        if !webhook_secret_matches(secret_hash, &headers) {
+            tracing::warn!(
+                "/v1/chat/completions (compat): rejected — invalid or missing X-Webhook-Secret"
+            );
+            let err = serde_json::json!({
+                "error": {
+                    "message": "Invalid or missing X-Webhook-Secret header",
+                    "type": "invalid_request_error",
+                    "code": "invalid_api_key"
+                }
+            });
+            return (StatusCode::UNAUTHORIZED, Json(err)).into_response();
+        }
+    }
+
     // ── Body size ──
     if body.len() > super::openai_compat::CHAT_COMPLETIONS_MAX_BODY_SIZE {
         let err = serde_json::json!({
@@ -521,10 +543,8 @@ pub async fn handle_v1_chat_completions_with_tools(
     // ── Auto-save ──
     if state.auto_save {
         let key = api_chat_memory_key();
-        let _ = state
-            .mem
-            .store(&key, &message, MemoryCategory::Conversation, None)
-            .await;
+        let category = autosave_category(&state, &message).await;
+        let _ = state.mem.store(&key, &message, category, None).await;
     }
 
     // ── Observability ──
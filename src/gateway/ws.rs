@@ -128,23 +128,20 @@ fn build_ws_system_prompt(
         .map(|spec| (spec.name.as_str(), spec.description.as_str()))
         .collect();
 
-    let bootstrap_max_chars = if config.agent.compact_context {
-        Some(6000)
-    } else {
-        None
-    };
+    let prompt_profile = config.agent.prompt_profile();
 
-    let mut prompt = crate::channels::build_system_prompt_with_mode(
+    let mut prompt = crate::channels::build_system_prompt_with_sections(
         &config.workspace_dir,
         model,
         &tool_descs,
         &[],
         Some(&config.identity),
-        bootstrap_max_chars,
+        prompt_profile.bootstrap_max_chars,
         native_tools,
         config.skills.prompt_injection_mode,
+        &prompt_profile.sections,
     );
-    if !native_tools {
+    if !native_tools && prompt_profile.sections.tools {
         prompt.push_str(&build_tool_instructions_from_specs(&tool_specs));
     }
     prompt.push_str(&build_shell_policy_instructions(&config.autonomy));
@@ -20,11 +20,15 @@ pub struct HealthSnapshot {
     pub updated_at: String,
     pub uptime_seconds: u64,
     pub components: BTreeMap<String, ComponentHealth>,
+    /// Runtime gauges and counters, e.g. `wasm_queue_depth`.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub metrics: BTreeMap<String, u64>,
 }
 
 struct HealthRegistry {
     started_at: Instant,
     components: Mutex<BTreeMap<String, ComponentHealth>>,
+    metrics: Mutex<BTreeMap<String, u64>>,
 }
 
 static REGISTRY: OnceLock<HealthRegistry> = OnceLock::new();
@@ -33,6 +37,7 @@ fn registry() -> &'static HealthRegistry {
     REGISTRY.get_or_init(|| HealthRegistry {
         started_at: Instant::now(),
         components: Mutex::new(BTreeMap::new()),
+        metrics: Mutex::new(BTreeMap::new()),
     })
 }
 
@@ -82,14 +87,21 @@ pub fn bump_component_restart(component: &str) {
     });
 }
 
+/// Record the current value of a runtime metric.
+pub fn set_metric(name: &str, value: u64) {
+    registry().metrics.lock().insert(name.to_string(), value);
+}
+
 pub fn snapshot() -> HealthSnapshot {
     let components = registry().components.lock().clone();
+    let metrics = registry().metrics.lock().clone();
 
     HealthSnapshot {
         pid: std::process::id(),
         updated_at: now_rfc3339(),
         uptime_seconds: registry().started_at.elapsed().as_secs(),
         components,
+        metrics,
     }
 }
 
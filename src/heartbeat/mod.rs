@@ -1,4 +1,5 @@
 pub mod engine;
+pub mod runner;
 
 #[cfg(test)]
 mod tests {
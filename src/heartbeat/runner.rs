@@ -0,0 +1,960 @@
+//! Heartbeat runs.
+//!
+//! Every `[heartbeat] interval_minutes` the runner sends one prompt — the
+//! tasks from `HEARTBEAT.md` plus a short status summary — to the provider,
+//! on `[heartbeat] heartbeat_model` when set (e.g. a `hint:` route to a
+//! cheaper model). Actionable output goes to the admin channel named by
+//! `[heartbeat] target`/`to`; `NO_REPLY` or an empty answer is not sent.
+//!
+//! A run is cut off after `max_runtime_secs`, and a tick that arrives while
+//! the previous run is still going is skipped rather than queued. Runs are
+//! skipped during maintenance mode and once the cost budget is exhausted.
+//! The latest runs are kept in `heartbeat_runs.json`, next to
+//! `config.toml`, for `zeroclaw status`.
+
+use super::engine::HeartbeatEngine;
+use crate::config::Config;
+use crate::cost::{BudgetCheck, CostTracker};
+use crate::maintenance::MaintenanceMode;
+use crate::observability::{Observer, ObserverEvent};
+use crate::providers::Provider;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::{self, Duration, Instant, MissedTickBehavior};
+
+/// Run history file, relative to the config directory.
+pub const RUNS_FILE: &str = "heartbeat_runs.json";
+
+/// How many runs `heartbeat_runs.json` keeps.
+const KEPT_RUNS: usize = 20;
+
+/// Shortest interval between scheduled runs, in minutes.
+const MIN_INTERVAL_MINUTES: u32 = 5;
+
+/// Characters of delivered output kept in the run record.
+const DETAIL_CHARS: usize = 200;
+
+const SYSTEM_PROMPT: &str = "You are the periodic heartbeat of a ZeroClaw agent. \
+Work through the listed tasks using the status summary. If something needs the \
+administrator's attention, reply with a short plain-text note saying what and why. \
+If nothing does, reply with exactly NO_REPLY.";
+
+/// How a heartbeat run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeartbeatOutcome {
+    /// Actionable output was sent to the admin channel.
+    Delivered,
+    /// Actionable output, but no `[heartbeat] target` to send it to.
+    Undelivered,
+    /// The model had nothing to report.
+    Quiet,
+    /// Neither `HEARTBEAT.md` nor `[heartbeat] message` lists a task.
+    NoTasks,
+    /// Skipped: maintenance mode is on.
+    Maintenance,
+    /// Skipped: the daily or monthly cost limit is reached.
+    OverBudget,
+    /// Skipped: the previous run had not finished.
+    Overlap,
+    /// Cut off at `max_runtime_secs`.
+    TimedOut,
+    Failed,
+}
+
+impl HeartbeatOutcome {
+    /// Whether the run failed rather than ran or was skipped.
+    pub fn is_error(self) -> bool {
+        matches!(self, Self::TimedOut | Self::Failed)
+    }
+}
+
+impl fmt::Display for HeartbeatOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Delivered => "delivered",
+            Self::Undelivered => "not delivered (no target)",
+            Self::Quiet => "nothing to report",
+            Self::NoTasks => "no tasks",
+            Self::Maintenance => "skipped (maintenance)",
+            Self::OverBudget => "skipped (over budget)",
+            Self::Overlap => "skipped (previous run still going)",
+            Self::TimedOut => "timed out",
+            Self::Failed => "failed",
+        })
+    }
+}
+
+/// One entry of `heartbeat_runs.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeartbeatRun {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub outcome: HeartbeatOutcome,
+    /// Error, skip reason or (the start of) the delivered output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl HeartbeatRun {
+    /// One line for `zeroclaw status` and `zeroclaw heartbeat run-once`.
+    pub fn describe(&self) -> String {
+        #[allow(clippy::cast_precision_loss)]
+        let seconds = self.duration_ms as f64 / 1000.0;
+        let mut line = format!(
+            "{} at {} ({seconds:.1}s)",
+            self.outcome,
+            self.started_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+        );
+        if let Some(detail) = &self.detail {
+            let _ = write!(line, ": {detail}");
+        }
+        line
+    }
+}
+
+/// Location of the run history for `config`.
+pub fn runs_path(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(&config.workspace_dir)
+        .join(RUNS_FILE)
+}
+
+/// Recorded runs, oldest first; a missing or unreadable file means none.
+pub fn load_runs(path: &Path) -> Vec<HeartbeatRun> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn record_run(path: &Path, run: &HeartbeatRun) -> Result<()> {
+    let mut runs = load_runs(path);
+    runs.push(run.clone());
+    let excess = runs.len().saturating_sub(KEPT_RUNS);
+    runs.drain(..excess);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&runs)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// Where actionable heartbeat output is sent.
+#[async_trait]
+pub trait HeartbeatSink: Send + Sync {
+    async fn deliver(&self, text: &str) -> Result<()>;
+}
+
+/// Sends to `[heartbeat] target`/`to` the way cron announcements are sent.
+struct AnnouncementSink {
+    config: Config,
+    channel: String,
+    target: String,
+}
+
+#[async_trait]
+impl HeartbeatSink for AnnouncementSink {
+    async fn deliver(&self, text: &str) -> Result<()> {
+        crate::cron::scheduler::deliver_announcement(
+            &self.config,
+            &self.channel,
+            &self.target,
+            text,
+        )
+        .await
+    }
+}
+
+/// Clears the running flag when a run ends, however it ends.
+struct RunningGuard<'a>(&'a AtomicBool);
+
+impl<'a> RunningGuard<'a> {
+    fn acquire(flag: &'a AtomicBool) -> Option<Self> {
+        (!flag.swap(true, Ordering::AcqRel)).then(|| Self(flag))
+    }
+}
+
+impl Drop for RunningGuard<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+pub struct HeartbeatRunner {
+    config: Config,
+    engine: HeartbeatEngine,
+    provider: Arc<dyn Provider>,
+    model: String,
+    sink: Option<Arc<dyn HeartbeatSink>>,
+    maintenance: MaintenanceMode,
+    observer: Arc<dyn Observer>,
+    runs_path: PathBuf,
+    running: AtomicBool,
+}
+
+impl HeartbeatRunner {
+    pub fn new(
+        config: Config,
+        provider: Arc<dyn Provider>,
+        sink: Option<Arc<dyn HeartbeatSink>>,
+        observer: Arc<dyn Observer>,
+    ) -> Self {
+        let model = heartbeat_model(&config).to_string();
+        Self {
+            engine: HeartbeatEngine::new(
+                config.heartbeat.clone(),
+                config.workspace_dir.clone(),
+                Arc::clone(&observer),
+            ),
+            provider,
+            model,
+            sink,
+            maintenance: MaintenanceMode::from_config(&config),
+            observer,
+            runs_path: runs_path(&config),
+            running: AtomicBool::new(false),
+            config,
+        }
+    }
+
+    /// A runner on the configured provider (with `[[model_routes]]`, so a
+    /// `hint:` heartbeat model resolves) delivering to `[heartbeat] target`.
+    pub async fn from_config(config: &Config) -> Result<Self> {
+        let model = heartbeat_model(config);
+        if let Some(hint) = model.strip_prefix("hint:") {
+            if !config.model_routes.iter().any(|route| route.hint == hint) {
+                anyhow::bail!(
+                    "[heartbeat] heartbeat_model = \"{model}\" but no [[model_routes]] entry has hint = \"{hint}\""
+                );
+            }
+        }
+        let sink = heartbeat_delivery_target(config)?.map(|(channel, target)| {
+            Arc::new(AnnouncementSink {
+                config: config.clone(),
+                channel,
+                target,
+            }) as Arc<dyn HeartbeatSink>
+        });
+
+        let provider_config = config.clone();
+        let provider = tokio::task::spawn_blocking(move || {
+            let config = provider_config;
+            let options = crate::providers::ProviderRuntimeOptions {
+                auth_profile_override: None,
+                provider_api_url: config.api_url.clone(),
+                provider_transport: config.effective_provider_transport(),
+                zeroclaw_dir: config.config_path.parent().map(PathBuf::from),
+                secrets_encrypt: config.secrets.encrypt,
+                reasoning_enabled: config.runtime.reasoning_enabled,
+                reasoning_level: config.effective_provider_reasoning_level(),
+                custom_provider_api_mode: config.provider_api.map(|mode| mode.as_compatible_mode()),
+                max_tokens_override: None,
+                model_support_vision: config.model_support_vision,
+                prompt_caching: config.provider.prompt_caching,
+                ollama: config.provider.ollama.clone(),
+            };
+            crate::providers::create_routed_provider_with_options(
+                config.default_provider.as_deref().unwrap_or("openrouter"),
+                config.api_key.as_deref(),
+                config.api_url.as_deref(),
+                &config.reliability,
+                &config.model_routes,
+                default_model(&config),
+                &options,
+            )
+        })
+        .await
+        .context("failed to join heartbeat provider initialization")??;
+
+        let observer: Arc<dyn Observer> =
+            Arc::from(crate::observability::create_observer(&config.observability));
+        Ok(Self::new(
+            config.clone(),
+            Arc::from(provider),
+            sink,
+            observer,
+        ))
+    }
+
+    /// Run every `interval_minutes` (at least five), starting one interval
+    /// from now. Each run is spawned so a slow one cannot delay the next
+    /// tick; that tick is skipped instead.
+    pub async fn run_forever(self: Arc<Self>) -> Result<()> {
+        let minutes = self
+            .config
+            .heartbeat
+            .interval_minutes
+            .max(MIN_INTERVAL_MINUTES);
+        let period = Duration::from_secs(u64::from(minutes) * 60);
+        tracing::info!("💓 Heartbeat started: every {minutes} minutes");
+
+        let mut interval = time::interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            let runner = Arc::clone(&self);
+            tokio::spawn(async move {
+                runner.run_once().await;
+            });
+        }
+    }
+
+    /// One heartbeat now, recorded in the run history.
+    pub async fn run_once(&self) -> HeartbeatRun {
+        let started_at = Utc::now();
+        let clock = Instant::now();
+        let (outcome, detail) = match RunningGuard::acquire(&self.running) {
+            None => (HeartbeatOutcome::Overlap, None),
+            Some(_guard) => {
+                self.observer.record_event(&ObserverEvent::HeartbeatTick);
+                let max_runtime_secs = self.config.heartbeat.max_runtime_secs.max(1);
+                match time::timeout(Duration::from_secs(max_runtime_secs), self.execute()).await {
+                    Ok(Ok(result)) => result,
+                    Ok(Err(e)) => (HeartbeatOutcome::Failed, Some(format!("{e:#}"))),
+                    Err(_) => (
+                        HeartbeatOutcome::TimedOut,
+                        Some(format!("exceeded max_runtime_secs = {max_runtime_secs}")),
+                    ),
+                }
+            }
+        };
+        let run = HeartbeatRun {
+            started_at,
+            duration_ms: u64::try_from(clock.elapsed().as_millis()).unwrap_or(u64::MAX),
+            outcome,
+            detail,
+        };
+        self.report(&run);
+        run
+    }
+
+    fn report(&self, run: &HeartbeatRun) {
+        if run.outcome.is_error() {
+            let message = run
+                .detail
+                .clone()
+                .unwrap_or_else(|| run.outcome.to_string());
+            tracing::warn!("💓 Heartbeat {}: {message}", run.outcome);
+            crate::health::mark_component_error("heartbeat", &message);
+            self.observer.record_event(&ObserverEvent::Error {
+                component: "heartbeat".into(),
+                message,
+            });
+        } else {
+            if run.outcome == HeartbeatOutcome::Overlap {
+                tracing::warn!("💓 Heartbeat tick skipped: the previous run is still going");
+            }
+            crate::health::mark_component_ok("heartbeat");
+        }
+        if let Err(e) = record_run(&self.runs_path, run) {
+            tracing::warn!("Failed to record heartbeat run: {e:#}");
+        }
+    }
+
+    async fn execute(&self) -> Result<(HeartbeatOutcome, Option<String>)> {
+        if self.maintenance.is_active() {
+            return Ok((HeartbeatOutcome::Maintenance, None));
+        }
+        let tasks = heartbeat_tasks_for_tick(
+            self.engine.collect_tasks().await?,
+            self.config.heartbeat.message.as_deref(),
+        );
+        if tasks.is_empty() {
+            return Ok((HeartbeatOutcome::NoTasks, None));
+        }
+
+        let costs = if self.config.cost.enabled {
+            Some(CostTracker::new(
+                self.config.cost.clone(),
+                &self.config.workspace_dir,
+            )?)
+        } else {
+            None
+        };
+        if let Some(costs) = &costs {
+            if let BudgetCheck::Exceeded {
+                current_usd,
+                limit_usd,
+                period,
+            } = costs.check_budget(0.0)?
+            {
+                return Ok((
+                    HeartbeatOutcome::OverBudget,
+                    Some(format!(
+                        "{period:?} spend ${current_usd:.2} has reached the ${limit_usd:.2} limit"
+                    )),
+                ));
+            }
+        }
+
+        let prompt = heartbeat_prompt(&tasks, &self.status_summary(costs.as_ref()));
+        let output = self
+            .provider
+            .chat_with_system(
+                Some(SYSTEM_PROMPT),
+                &prompt,
+                &self.model,
+                self.config.default_temperature,
+            )
+            .await?;
+        let Some(text) = actionable_output(&output) else {
+            return Ok((HeartbeatOutcome::Quiet, None));
+        };
+        let detail = Some(crate::util::truncate_with_ellipsis(text, DETAIL_CHARS));
+        let Some(sink) = &self.sink else {
+            tracing::info!("💓 Heartbeat (no [heartbeat] target set): {text}");
+            return Ok((HeartbeatOutcome::Undelivered, detail));
+        };
+        sink.deliver(text)
+            .await
+            .context("heartbeat delivery failed")?;
+        Ok((HeartbeatOutcome::Delivered, detail))
+    }
+
+    /// Due cron jobs, budget usage, component errors and the previous run.
+    fn status_summary(&self, costs: Option<&CostTracker>) -> String {
+        let mut summary = String::new();
+
+        if self.config.cron.enabled {
+            match crate::cron::due_jobs(&self.config, Utc::now()) {
+                Ok(jobs) => {
+                    let _ = writeln!(summary, "- Due cron jobs: {}", jobs.len());
+                }
+                Err(e) => {
+                    let _ = writeln!(summary, "- Due cron jobs: unavailable ({e})");
+                }
+            }
+        }
+
+        match costs.map(CostTracker::get_summary) {
+            Some(Ok(spent)) => {
+                let _ = writeln!(
+                    summary,
+                    "- Budget: ${:.2} of ${:.2} today, ${:.2} of ${:.2} this month",
+                    spent.daily_cost_usd,
+                    self.config.cost.daily_limit_usd,
+                    spent.monthly_cost_usd,
+                    self.config.cost.monthly_limit_usd
+                );
+            }
+            Some(Err(e)) => {
+                let _ = writeln!(summary, "- Budget: unavailable ({e})");
+            }
+            None => summary.push_str("- Budget: cost tracking is off\n"),
+        }
+
+        let errors: Vec<String> = crate::health::snapshot()
+            .components
+            .into_iter()
+            .filter(|(_, component)| component.status == "error")
+            .map(|(name, component)| {
+                format!(
+                    "{name}: {}",
+                    component.last_error.as_deref().unwrap_or("unknown error")
+                )
+            })
+            .collect();
+        if errors.is_empty() {
+            summary.push_str("- Component errors: none\n");
+        } else {
+            let _ = writeln!(summary, "- Component errors: {}", errors.join("; "));
+        }
+
+        if let Some(previous) = load_runs(&self.runs_path).pop() {
+            let _ = writeln!(summary, "- Previous heartbeat: {}", previous.describe());
+        }
+        summary
+    }
+}
+
+fn default_model(config: &Config) -> &str {
+    config
+        .default_model
+        .as_deref()
+        .unwrap_or("anthropic/claude-sonnet-4")
+}
+
+/// `[heartbeat] heartbeat_model`, falling back to the default model.
+fn heartbeat_model(config: &Config) -> &str {
+    config
+        .heartbeat
+        .heartbeat_model
+        .as_deref()
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .unwrap_or_else(|| default_model(config))
+}
+
+fn heartbeat_prompt(tasks: &[String], status: &str) -> String {
+    let mut prompt = String::from("[Heartbeat]\n\nTasks:\n");
+    for task in tasks {
+        let _ = writeln!(prompt, "- {task}");
+    }
+    let _ = write!(prompt, "\nStatus:\n{status}");
+    prompt
+}
+
+/// The output worth sending, if any: not empty and not `NO_REPLY`.
+fn actionable_output(output: &str) -> Option<&str> {
+    let output = output.trim();
+    (!output.is_empty() && !crate::cron::scheduler::is_no_reply_sentinel(output)).then_some(output)
+}
+
+pub(crate) fn heartbeat_tasks_for_tick(
+    file_tasks: Vec<String>,
+    fallback_message: Option<&str>,
+) -> Vec<String> {
+    if !file_tasks.is_empty() {
+        return file_tasks;
+    }
+
+    fallback_message
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+        .map(|message| vec![message.to_string()])
+        .unwrap_or_default()
+}
+
+pub(crate) fn heartbeat_delivery_target(config: &Config) -> Result<Option<(String, String)>> {
+    let channel = config
+        .heartbeat
+        .target
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let target = config
+        .heartbeat
+        .to
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    match (channel, target) {
+        (None, None) => Ok(None),
+        (Some(_), None) => anyhow::bail!("heartbeat.to is required when heartbeat.target is set"),
+        (None, Some(_)) => anyhow::bail!("heartbeat.target is required when heartbeat.to is set"),
+        (Some(channel), Some(target)) => {
+            validate_heartbeat_channel_config(config, channel)?;
+            Ok(Some((channel.to_string(), target.to_string())))
+        }
+    }
+}
+
+fn validate_heartbeat_channel_config(config: &Config, channel: &str) -> Result<()> {
+    let normalized = channel.to_ascii_lowercase();
+    match normalized.as_str() {
+        "telegram" => {
+            if config.channels_config.telegram.is_none() {
+                anyhow::bail!(
+                    "heartbeat.target is set to telegram but channels_config.telegram is not configured"
+                );
+            }
+        }
+        "discord" => {
+            if config.channels_config.discord.is_none() {
+                anyhow::bail!(
+                    "heartbeat.target is set to discord but channels_config.discord is not configured"
+                );
+            }
+        }
+        "slack" => {
+            if config.channels_config.slack.is_none() {
+                anyhow::bail!(
+                    "heartbeat.target is set to slack but channels_config.slack is not configured"
+                );
+            }
+        }
+        "mattermost" => {
+            if config.channels_config.mattermost.is_none() {
+                anyhow::bail!(
+                    "heartbeat.target is set to mattermost but channels_config.mattermost is not configured"
+                );
+            }
+        }
+        "whatsapp" | "whatsapp_web" => {
+            let wa = config.channels_config.whatsapp.as_ref().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "heartbeat.target is set to {channel} but channels_config.whatsapp is not configured"
+                )
+            })?;
+
+            if normalized == "whatsapp_web" && wa.is_cloud_config() && !wa.is_web_config() {
+                anyhow::bail!(
+                    "heartbeat.target is set to whatsapp_web but channels_config.whatsapp is configured for cloud mode (set session_path for web mode)"
+                );
+            }
+        }
+        other => anyhow::bail!("unsupported heartbeat.target channel: {other}"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+    use parking_lot::Mutex;
+    use std::sync::atomic::AtomicUsize;
+    use tokio::sync::Notify;
+
+    /// Answers `reply`, optionally after `delay` or once `gate` is notified.
+    struct MockProvider {
+        reply: String,
+        delay: Option<Duration>,
+        gate: Option<Arc<Notify>>,
+        calls: AtomicUsize,
+        last: Mutex<Option<(String, String)>>,
+    }
+
+    impl MockProvider {
+        fn replying(reply: &str) -> Self {
+            Self {
+                reply: reply.into(),
+                delay: None,
+                gate: None,
+                calls: AtomicUsize::new(0),
+                last: Mutex::new(None),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl Provider for MockProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            model: &str,
+            _temperature: f64,
+        ) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            *self.last.lock() = Some((message.to_string(), model.to_string()));
+            if let Some(delay) = self.delay {
+                time::sleep(delay).await;
+            }
+            if let Some(gate) = &self.gate {
+                gate.notified().await;
+            }
+            Ok(self.reply.clone())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl HeartbeatSink for RecordingSink {
+        async fn deliver(&self, text: &str) -> Result<()> {
+            self.sent.lock().push(text.to_string());
+            Ok(())
+        }
+    }
+
+    fn test_config(dir: &Path) -> Config {
+        std::fs::write(dir.join("HEARTBEAT.md"), "# Tasks\n- Check the disk\n").unwrap();
+        let mut config = Config {
+            workspace_dir: dir.to_path_buf(),
+            config_path: dir.join("config.toml"),
+            ..Config::default()
+        };
+        config.heartbeat.enabled = true;
+        config.heartbeat.interval_minutes = 5;
+        config.cron.enabled = false;
+        config
+    }
+
+    fn runner(
+        config: Config,
+        provider: &Arc<MockProvider>,
+        sink: Option<&Arc<RecordingSink>>,
+    ) -> HeartbeatRunner {
+        HeartbeatRunner::new(
+            config,
+            Arc::clone(provider) as Arc<dyn Provider>,
+            sink.map(|sink| Arc::clone(sink) as Arc<dyn HeartbeatSink>),
+            Arc::new(NoopObserver),
+        )
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_once_per_interval_starting_one_interval_in() {
+        let temp = tempfile::tempdir().unwrap();
+        let provider = Arc::new(MockProvider::replying("NO_REPLY"));
+        let runner = Arc::new(runner(test_config(temp.path()), &provider, None));
+        let handle = tokio::spawn(Arc::clone(&runner).run_forever());
+
+        time::sleep(Duration::from_secs(4 * 60)).await;
+        assert_eq!(provider.calls(), 0, "no run at startup");
+        time::sleep(Duration::from_secs(2 * 60)).await;
+        assert_eq!(provider.calls(), 1);
+        time::sleep(Duration::from_secs(5 * 60)).await;
+        assert_eq!(provider.calls(), 2);
+        handle.abort();
+
+        let runs = load_runs(&runs_path(&runner.config));
+        assert_eq!(runs.len(), 2);
+        assert!(runs
+            .iter()
+            .all(|run| run.outcome == HeartbeatOutcome::Quiet));
+    }
+
+    #[tokio::test]
+    async fn a_run_is_skipped_while_the_previous_one_is_still_going() {
+        let temp = tempfile::tempdir().unwrap();
+        let gate = Arc::new(Notify::new());
+        let provider = Arc::new(MockProvider {
+            gate: Some(Arc::clone(&gate)),
+            ..MockProvider::replying("NO_REPLY")
+        });
+        let runner = Arc::new(runner(test_config(temp.path()), &provider, None));
+
+        let first = tokio::spawn({
+            let runner = Arc::clone(&runner);
+            async move { runner.run_once().await }
+        });
+        while provider.calls() == 0 {
+            tokio::task::yield_now().await;
+        }
+        let second = runner.run_once().await;
+        assert_eq!(second.outcome, HeartbeatOutcome::Overlap);
+        assert_eq!(provider.calls(), 1);
+
+        gate.notify_one();
+        assert_eq!(first.await.unwrap().outcome, HeartbeatOutcome::Quiet);
+        assert!(!runner.running.load(Ordering::SeqCst), "guard released");
+    }
+
+    #[tokio::test]
+    async fn actionable_output_is_delivered_to_the_admin_channel() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = test_config(temp.path());
+        config.heartbeat.heartbeat_model = Some("hint:cheap".into());
+        let provider = Arc::new(MockProvider::replying("Disk is 95% full.\n"));
+        let sink = Arc::new(RecordingSink::default());
+        let runner = runner(config, &provider, Some(&sink));
+
+        let run = runner.run_once().await;
+        assert_eq!(run.outcome, HeartbeatOutcome::Delivered);
+        assert_eq!(*sink.sent.lock(), ["Disk is 95% full."]);
+
+        let (prompt, model) = provider.last.lock().clone().unwrap();
+        assert_eq!(model, "hint:cheap");
+        assert!(prompt.contains("- Check the disk"), "{prompt}");
+        assert!(
+            prompt.contains("- Budget: cost tracking is off"),
+            "{prompt}"
+        );
+
+        let quiet = Arc::new(MockProvider::replying(" no_reply "));
+        let runner = self::runner(test_config(temp.path()), &quiet, Some(&sink));
+        assert_eq!(runner.run_once().await.outcome, HeartbeatOutcome::Quiet);
+        assert_eq!(sink.sent.lock().len(), 1, "NO_REPLY is not delivered");
+        let (prompt, _) = quiet.last.lock().clone().unwrap();
+        assert!(
+            prompt.contains("- Previous heartbeat: delivered"),
+            "{prompt}"
+        );
+    }
+
+    #[tokio::test]
+    async fn maintenance_pauses_runs() {
+        let temp = tempfile::tempdir().unwrap();
+        let provider = Arc::new(MockProvider::replying("Something is wrong"));
+        let sink = Arc::new(RecordingSink::default());
+        let runner = runner(test_config(temp.path()), &provider, Some(&sink));
+        runner.maintenance.set(true, None).unwrap();
+
+        assert_eq!(
+            runner.run_once().await.outcome,
+            HeartbeatOutcome::Maintenance
+        );
+        assert_eq!(provider.calls(), 0);
+        assert!(sink.sent.lock().is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn runs_are_cut_off_at_max_runtime() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut config = test_config(temp.path());
+        config.heartbeat.max_runtime_secs = 30;
+        let provider = Arc::new(MockProvider {
+            delay: Some(Duration::from_secs(3600)),
+            ..MockProvider::replying("late")
+        });
+        let runner = runner(config, &provider, None);
+
+        let run = runner.run_once().await;
+        assert_eq!(run.outcome, HeartbeatOutcome::TimedOut);
+        assert_eq!(run.duration_ms, 30_000);
+        // The guard is released for the next tick.
+        assert_eq!(runner.run_once().await.outcome, HeartbeatOutcome::TimedOut);
+    }
+
+    #[test]
+    fn run_history_keeps_the_latest_runs() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join(RUNS_FILE);
+        for ms in 0..25 {
+            let run = HeartbeatRun {
+                started_at: Utc::now(),
+                duration_ms: ms,
+                outcome: HeartbeatOutcome::Quiet,
+                detail: None,
+            };
+            record_run(&path, &run).unwrap();
+        }
+        let runs = load_runs(&path);
+        assert_eq!(runs.len(), KEPT_RUNS);
+        assert_eq!(runs[0].duration_ms, 5);
+    }
+
+    #[test]
+    fn heartbeat_tasks_use_file_tasks_when_available() {
+        let tasks =
+            heartbeat_tasks_for_tick(vec!["From file".to_string()], Some("Fallback from config"));
+        assert_eq!(tasks, vec!["From file".to_string()]);
+    }
+
+    #[test]
+    fn heartbeat_tasks_fall_back_to_config_message() {
+        let tasks = heartbeat_tasks_for_tick(vec![], Some("  check london time  "));
+        assert_eq!(tasks, vec!["check london time".to_string()]);
+    }
+
+    #[test]
+    fn heartbeat_tasks_ignore_empty_fallback_message() {
+        let tasks = heartbeat_tasks_for_tick(vec![], Some("   "));
+        assert!(tasks.is_empty());
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_none_when_unset() {
+        let config = Config::default();
+        let target = heartbeat_delivery_target(&config).unwrap();
+        assert!(target.is_none());
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_requires_to_field() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("telegram".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("heartbeat.to is required when heartbeat.target is set"));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_requires_target_field() {
+        let mut config = Config::default();
+        config.heartbeat.to = Some("123456".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("heartbeat.target is required when heartbeat.to is set"));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_rejects_unsupported_channel() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("email".into());
+        config.heartbeat.to = Some("ops@example.com".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported heartbeat.target channel"));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_requires_channel_configuration() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("telegram".into());
+        config.heartbeat.to = Some("123456".into());
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("channels_config.telegram is not configured"));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_accepts_telegram_configuration() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("telegram".into());
+        config.heartbeat.to = Some("123456".into());
+        config.channels_config.telegram = Some(crate::config::TelegramConfig {
+            bot_token: "bot-token".into(),
+            allowed_users: vec![],
+            stream_mode: crate::config::StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            interrupt_on_new_message: false,
+            mention_only: false,
+            group_reply: None,
+            base_url: None,
+        });
+
+        let target = heartbeat_delivery_target(&config).unwrap();
+        assert_eq!(target, Some(("telegram".to_string(), "123456".to_string())));
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_accepts_whatsapp_web_target_in_web_mode() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("whatsapp_web".into());
+        config.heartbeat.to = Some("+15551234567".into());
+        config.channels_config.whatsapp = Some(crate::config::schema::WhatsAppConfig {
+            access_token: None,
+            phone_number_id: None,
+            verify_token: None,
+            app_secret: None,
+            session_path: Some("~/.zeroclaw/state/whatsapp-web/session.db".into()),
+            pair_phone: None,
+            pair_code: None,
+            allowed_numbers: vec!["*".into()],
+        });
+
+        let target = heartbeat_delivery_target(&config).unwrap();
+        assert_eq!(
+            target,
+            Some(("whatsapp_web".to_string(), "+15551234567".to_string()))
+        );
+    }
+
+    #[test]
+    fn heartbeat_delivery_target_rejects_whatsapp_web_target_in_cloud_mode() {
+        let mut config = Config::default();
+        config.heartbeat.target = Some("whatsapp_web".into());
+        config.heartbeat.to = Some("+15551234567".into());
+        config.channels_config.whatsapp = Some(crate::config::schema::WhatsAppConfig {
+            access_token: Some("token".into()),
+            phone_number_id: Some("123456".into()),
+            verify_token: Some("verify".into()),
+            app_secret: None,
+            session_path: None,
+            pair_phone: None,
+            pair_code: None,
+            allowed_numbers: vec!["*".into()],
+        });
+
+        let err = heartbeat_delivery_target(&config).unwrap_err();
+        assert!(err.to_string().contains("configured for cloud mode"));
+    }
+}
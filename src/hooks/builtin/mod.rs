@@ -1,3 +1,5 @@
 pub mod command_logger;
+pub mod outbound_filter;
 
 pub use command_logger::CommandLoggerHook;
+pub use outbound_filter::OutboundFilterHook;
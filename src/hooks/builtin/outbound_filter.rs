@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+
+use crate::hooks::outbound::OutboundFilter;
+use crate::hooks::traits::{HookHandler, HookResult};
+use crate::observability::runtime_trace;
+
+/// Applies `[hooks.outbound]` redaction and banned-phrase rules to outbound
+/// replies. Blocked and flagged replies are recorded (redacted) in the
+/// runtime trace.
+pub struct OutboundFilterHook {
+    filter: OutboundFilter,
+}
+
+impl OutboundFilterHook {
+    pub fn new(filter: OutboundFilter) -> Self {
+        Self { filter }
+    }
+}
+
+#[async_trait]
+impl HookHandler for OutboundFilterHook {
+    fn name(&self) -> &str {
+        "outbound-filter"
+    }
+
+    /// Runs after every other modifying hook so their output is filtered too.
+    fn priority(&self) -> i32 {
+        -100
+    }
+
+    async fn on_message_sending(
+        &self,
+        channel: String,
+        recipient: String,
+        content: String,
+    ) -> HookResult<(String, String, String)> {
+        let verdict = self.filter.apply(&content);
+        if verdict.is_notable() {
+            tracing::warn!(
+                hook = "outbound-filter",
+                channel = %channel,
+                blocked = verdict.blocked,
+                flags = ?verdict.flags,
+                "outbound reply matched banned phrases"
+            );
+            runtime_trace::record_event(
+                "outbound_filter",
+                Some(channel.as_str()),
+                None,
+                None,
+                None,
+                Some(!verdict.blocked),
+                Some(if verdict.blocked {
+                    "blocked"
+                } else {
+                    "flagged"
+                }),
+                serde_json::json!({
+                    "recipient": recipient,
+                    "flags": verdict.flags,
+                    "content": verdict.withheld.as_deref().unwrap_or(&verdict.text),
+                }),
+            );
+        }
+        HookResult::Continue((channel, recipient, verdict.text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BannedPhraseAction, BannedPhraseRule, OutboundHooksConfig};
+
+    #[tokio::test]
+    async fn blocked_reply_is_replaced_by_notice() {
+        let config = OutboundHooksConfig {
+            banned_phrases: vec![BannedPhraseRule {
+                phrase: "launch code".into(),
+                regex: false,
+                action: BannedPhraseAction::Block,
+            }],
+            block_notice: "Withheld.".into(),
+            ..OutboundHooksConfig::default()
+        };
+        let hook = OutboundFilterHook::new(OutboundFilter::compile(&config).unwrap());
+        let result = hook
+            .on_message_sending(
+                "telegram".into(),
+                "alice".into(),
+                "The launch code is 1234".into(),
+            )
+            .await;
+        match result {
+            HookResult::Continue((channel, recipient, content)) => {
+                assert_eq!(
+                    (channel.as_str(), recipient.as_str()),
+                    ("telegram", "alice")
+                );
+                assert_eq!(content, "Withheld.");
+            }
+            HookResult::Cancel(reason) => panic!("unexpected cancel: {reason}"),
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Outbound reply filtering (`[hooks.outbound]`).
+//!
+//! Redaction rules and banned phrases are compiled once into an
+//! [`OutboundFilter`] and applied to the whole reply before it is split into
+//! channel-sized chunks, so a match can never straddle a chunk boundary.
+//! Channels run it through [`super::builtin::OutboundFilterHook`]; the gateway
+//! runs the same hook on its HTTP replies.
+
+use anyhow::{Context, Result};
+use regex::Regex;
+
+use crate::config::{BannedPhraseAction, OutboundHooksConfig};
+
+/// Replacement used by banned phrases with `action = "redact"`.
+pub const BANNED_PHRASE_REPLACEMENT: &str = "[REDACTED]";
+
+#[derive(Debug)]
+struct Redaction {
+    regex: Regex,
+    replacement: String,
+}
+
+#[derive(Debug)]
+struct BannedPhrase {
+    regex: Regex,
+    action: BannedPhraseAction,
+    label: String,
+}
+
+/// Compiled `[hooks.outbound]` rules.
+#[derive(Debug)]
+pub struct OutboundFilter {
+    redactions: Vec<Redaction>,
+    banned: Vec<BannedPhrase>,
+    block_notice: String,
+}
+
+/// Result of filtering one reply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundVerdict {
+    /// What to send: the redacted reply, or the block notice.
+    pub text: String,
+    pub blocked: bool,
+    /// Banned phrases that matched with `block` or `flag`.
+    pub flags: Vec<String>,
+    /// The redacted reply that was withheld, when blocked.
+    pub withheld: Option<String>,
+}
+
+impl OutboundVerdict {
+    /// Worth recording in the runtime trace: blocked or flagged.
+    pub fn is_notable(&self) -> bool {
+        self.blocked || !self.flags.is_empty()
+    }
+}
+
+impl OutboundFilter {
+    /// Compile every rule, naming the offending entry on an invalid regex.
+    pub fn compile(config: &OutboundHooksConfig) -> Result<Self> {
+        let redactions = config
+            .redactions
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let regex = Regex::new(&rule.pattern).with_context(|| {
+                    format!("hooks.outbound.redactions[{i}].pattern is not a valid regex")
+                })?;
+                Ok(Redaction {
+                    regex,
+                    replacement: rule.replacement.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let banned = config
+            .banned_phrases
+            .iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                let pattern = if rule.regex {
+                    rule.phrase.clone()
+                } else {
+                    format!("(?i){}", regex::escape(&rule.phrase))
+                };
+                let regex = Regex::new(&pattern).with_context(|| {
+                    format!("hooks.outbound.banned_phrases[{i}].phrase is not a valid regex")
+                })?;
+                Ok(BannedPhrase {
+                    regex,
+                    action: rule.action,
+                    label: rule.phrase.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            redactions,
+            banned,
+            block_notice: config.block_notice.clone(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.redactions.is_empty() && self.banned.is_empty()
+    }
+
+    /// Filter one complete reply.
+    ///
+    /// Banned phrases are matched against the original text. Redactions (and
+    /// banned phrases with `action = "redact"`) are collected as spans over the
+    /// original text; overlapping spans merge and take the replacement of the
+    /// earliest match, so rule order never lets one replacement be re-matched
+    /// by a later rule.
+    pub fn apply(&self, text: &str) -> OutboundVerdict {
+        let mut blocked = false;
+        let mut flags = Vec::new();
+        // (start, end, rule order, replacement)
+        let mut spans: Vec<(usize, usize, usize, String)> = Vec::new();
+
+        for (order, rule) in self.redactions.iter().enumerate() {
+            for caps in rule.regex.captures_iter(text) {
+                let whole = caps.get(0).expect("capture 0 is the whole match");
+                if whole.is_empty() {
+                    continue;
+                }
+                let mut replacement = String::new();
+                caps.expand(&rule.replacement, &mut replacement);
+                spans.push((whole.start(), whole.end(), order, replacement));
+            }
+        }
+
+        let banned_order = self.redactions.len();
+        for (i, rule) in self.banned.iter().enumerate() {
+            match rule.action {
+                BannedPhraseAction::Redact => {
+                    for found in rule.regex.find_iter(text).filter(|m| !m.is_empty()) {
+                        spans.push((
+                            found.start(),
+                            found.end(),
+                            banned_order + i,
+                            BANNED_PHRASE_REPLACEMENT.to_string(),
+                        ));
+                    }
+                }
+                BannedPhraseAction::Block | BannedPhraseAction::Flag => {
+                    if rule.regex.is_match(text) {
+                        blocked |= rule.action == BannedPhraseAction::Block;
+                        flags.push(rule.label.clone());
+                    }
+                }
+            }
+        }
+
+        let redacted = apply_spans(text, spans);
+        if blocked {
+            OutboundVerdict {
+                text: self.block_notice.clone(),
+                blocked,
+                flags,
+                withheld: Some(redacted),
+            }
+        } else {
+            OutboundVerdict {
+                text: redacted,
+                blocked,
+                flags,
+                withheld: None,
+            }
+        }
+    }
+}
+
+fn apply_spans(text: &str, mut spans: Vec<(usize, usize, usize, String)>) -> String {
+    if spans.is_empty() {
+        return text.to_string();
+    }
+    spans.sort_by_key(|&(start, _, order, _)| (start, order));
+
+    let mut out = String::with_capacity(text.len());
+    let mut cursor = 0;
+    let mut iter = spans.into_iter().peekable();
+    while let Some((start, mut end, _, replacement)) = iter.next() {
+        while let Some(&(next_start, next_end, _, _)) = iter.peek() {
+            if next_start >= end {
+                break;
+            }
+            end = end.max(next_end);
+            iter.next();
+        }
+        out.push_str(&text[cursor..start]);
+        out.push_str(&replacement);
+        cursor = end;
+    }
+    out.push_str(&text[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BannedPhraseRule, OutboundRedactionRule};
+
+    fn build(redactions: &[(&str, &str)], banned: &[(&str, BannedPhraseAction)]) -> OutboundFilter {
+        OutboundFilter::compile(&OutboundHooksConfig {
+            redactions: redactions
+                .iter()
+                .map(|(pattern, replacement)| OutboundRedactionRule {
+                    pattern: (*pattern).into(),
+                    replacement: (*replacement).into(),
+                })
+                .collect(),
+            banned_phrases: banned
+                .iter()
+                .map(|(phrase, action)| BannedPhraseRule {
+                    phrase: (*phrase).into(),
+                    regex: false,
+                    action: *action,
+                })
+                .collect(),
+            ..OutboundHooksConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn overlapping_rules_merge_into_one_replacement() {
+        let filter = build(
+            &[
+                (r"[a-z0-9-]+\.corp\.internal", "[host]"),
+                (r"CUST-\d{6}", "[customer]"),
+                (r"db-\d+", "[db]"),
+            ],
+            &[],
+        );
+        let verdict = filter.apply("Ask db-7.corp.internal about CUST-123456 and CUST-654321.");
+        assert_eq!(verdict.text, "Ask [host] about [customer] and [customer].");
+        assert!(!verdict.is_notable());
+
+        // The replacement of an earlier rule is never re-matched by a later one.
+        let filter = build(&[("secret", "[x]"), (r"\[x\]", "LEAK")], &[]);
+        assert_eq!(filter.apply("a secret").text, "a [x]");
+    }
+
+    #[test]
+    fn redaction_before_chunking_covers_a_match_across_the_boundary() {
+        let filter = build(&[(r"[a-z0-9-]+\.corp\.internal", "[host]")], &[]);
+        let reply = format!("{} db-7.corp.internal is down", "x".repeat(94));
+        // Split into 100-char chunks the host would straddle chunks 1 and 2.
+        assert!(!reply[..100].contains("corp.internal"));
+
+        let filtered = filter.apply(&reply).text;
+        let chunks: Vec<String> = filtered
+            .chars()
+            .collect::<Vec<_>>()
+            .chunks(100)
+            .map(|chunk| chunk.iter().collect())
+            .collect();
+        assert!(chunks.iter().all(|chunk| !chunk.contains("corp")));
+        assert!(!chunks.concat().contains("internal"));
+        assert!(chunks.concat().contains("[host] is down"));
+    }
+
+    #[test]
+    fn block_action_replaces_reply_and_keeps_redacted_original() {
+        let filter = build(
+            &[(r"CUST-\d{6}", "[customer]")],
+            &[
+                ("Project Nightjar", BannedPhraseAction::Block),
+                ("refund", BannedPhraseAction::Flag),
+                ("darn", BannedPhraseAction::Redact),
+            ],
+        );
+
+        let verdict = filter.apply("project nightjar ships for CUST-123456");
+        assert!(verdict.blocked);
+        assert_eq!(verdict.text, OutboundHooksConfig::default().block_notice);
+        assert_eq!(verdict.flags, ["Project Nightjar"]);
+        assert_eq!(
+            verdict.withheld.as_deref(),
+            Some("project nightjar ships for [customer]")
+        );
+
+        let verdict = filter.apply("A darn refund is on its way");
+        assert!(!verdict.blocked);
+        assert_eq!(verdict.flags, ["refund"]);
+        assert_eq!(verdict.text, "A [REDACTED] refund is on its way");
+    }
+
+    #[test]
+    fn invalid_regex_names_the_rule() {
+        let err = OutboundFilter::compile(&OutboundHooksConfig {
+            redactions: vec![OutboundRedactionRule {
+                pattern: "(unclosed".into(),
+                replacement: "x".into(),
+            }],
+            ..OutboundHooksConfig::default()
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("hooks.outbound.redactions[0]"));
+    }
+}
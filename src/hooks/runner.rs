@@ -18,6 +18,7 @@ use super::traits::{HookHandler, HookResult};
 /// and short-circuiting on `Cancel`.
 pub struct HookRunner {
     handlers: Vec<Box<dyn HookHandler>>,
+    filters_outbound: bool,
 }
 
 impl HookRunner {
@@ -25,9 +26,32 @@ impl HookRunner {
     pub fn new() -> Self {
         Self {
             handlers: Vec::new(),
+            filters_outbound: false,
         }
     }
 
+    /// Runner with the built-in hooks enabled in `[hooks]`. Fails only on
+    /// invalid `[hooks.outbound]` regexes.
+    pub fn from_config(config: &crate::config::HooksConfig) -> anyhow::Result<Self> {
+        let mut runner = Self::new();
+        if config.builtin.command_logger {
+            runner.register(Box::new(super::builtin::CommandLoggerHook::new()));
+        }
+        if !config.outbound.is_empty() {
+            let filter = super::OutboundFilter::compile(&config.outbound)?;
+            runner.register(Box::new(super::builtin::OutboundFilterHook::new(filter)));
+            runner.filters_outbound = true;
+        }
+        Ok(runner)
+    }
+
+    /// Whether `[hooks.outbound]` rules are active. Partial replies (draft
+    /// streaming, SSE deltas) must not be sent then, since rules only see
+    /// the complete reply.
+    pub fn filters_outbound(&self) -> bool {
+        self.filters_outbound
+    }
+
     /// Register a handler and re-sort by descending priority.
     pub fn register(&mut self, handler: Box<dyn HookHandler>) {
         self.handlers.push(handler);
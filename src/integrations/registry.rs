@@ -871,6 +871,9 @@ mod tests {
             room_id: "!r:m".into(),
             allowed_users: vec![],
             mention_only: false,
+            stream_mode: StreamMode::default(),
+            draft_update_interval_ms: 1000,
+            reply_style: None,
         });
         let entries = all_integrations();
         let mx = entries.iter().find(|e| e.name == "Matrix").unwrap();
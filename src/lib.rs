@@ -47,9 +47,9 @@ pub mod config;
 pub mod coordination;
 pub(crate) mod cost;
 pub(crate) mod cron;
-pub mod economic;
 pub(crate) mod daemon;
 pub(crate) mod doctor;
+pub mod economic;
 pub mod gateway;
 pub mod goals;
 pub(crate) mod hardware;
@@ -59,6 +59,8 @@ pub mod hooks;
 pub(crate) mod identity;
 // Intentionally unused re-export — public API surface for plugin authors.
 pub(crate) mod integrations;
+pub(crate) mod links;
+pub(crate) mod maintenance;
 pub mod memory;
 pub(crate) mod migration;
 pub(crate) mod multimodal;
@@ -75,8 +77,10 @@ pub(crate) mod service;
 pub(crate) mod skills;
 pub mod tools;
 pub(crate) mod tunnel;
+pub(crate) mod uninstall;
 pub mod update;
 pub(crate) mod util;
+pub(crate) mod workspace;
 
 pub use config::Config;
 
@@ -103,7 +107,41 @@ pub enum ChannelCommands {
     /// List all configured channels
     List,
     /// Start all configured channels (handled in main.rs for async)
-    Start,
+    #[command(long_about = "\
+Start configured channels and route messages to the agent.
+
+Use --only to run a subset of configured channels (useful when \
+debugging one integration), or --except to skip some. Names are \
+config keys such as telegram, discord, mattermost, dingtalk.
+
+--debug raises channel log verbosity and prints full message \
+bodies (with secrets scrubbed) instead of truncated previews.
+
+--output jsonl prints one JSON object per event (message received, \
+reply sent, error) on stdout instead of the human-readable lines.
+
+Examples:
+  zeroclaw channel start --only dingtalk --debug
+  zeroclaw channel start --except email,irc
+  zeroclaw channel start --output jsonl | jq .")]
+    Start {
+        /// Only start these channels (comma-separated config keys)
+        #[arg(long, value_delimiter = ',', conflicts_with = "except")]
+        only: Vec<String>,
+        /// Start every configured channel except these (comma-separated config keys)
+        #[arg(long, value_delimiter = ',')]
+        except: Vec<String>,
+        /// Foreground debug mode: verbose channel logs and full message bodies
+        #[arg(long)]
+        debug: bool,
+        /// Console output format: human-readable lines or JSON lines
+        #[arg(long, value_enum, default_value_t)]
+        output: crate::channels::console::OutputFormat,
+        /// Take over channels another zeroclaw process is serving; that
+        /// process stops their listeners on its next heartbeat
+        #[arg(long)]
+        takeover: bool,
+    },
     /// Run health checks for configured channels (handled in main.rs for async)
     Doctor,
     /// Add a new channel configuration
@@ -144,13 +182,39 @@ Examples:
         /// Telegram identity to allow (username without '@' or numeric user ID)
         identity: String,
     },
+    /// Check sample text against `[channels_config.filters]`
+    #[command(long_about = "\
+Check sample text against the inbound filters in [channels_config.filters].
+
+Prints the rule that would skip the message (bare_url, too_short, or \
+the matching pattern) and the acknowledgment it would send, if any. \
+Runtime commands such as /new or /models are reported as handled \
+before the filters.
+
+Examples:
+  zeroclaw channel test-filter \"/start\"
+  zeroclaw channel test-filter \"https://example.com/article\"")]
+    TestFilter {
+        /// Message text to check
+        text: String,
+        /// Channel the message arrives on (affects which runtime commands apply)
+        #[arg(long, default_value = "telegram")]
+        channel: String,
+    },
 }
 
 /// Skills management subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum SkillCommands {
     /// List all installed skills
-    List,
+    List {
+        /// Print where each skill lives (absolute path to its SKILL.md or SKILL.toml)
+        #[arg(long)]
+        show_location: bool,
+        /// Print locations relative to the workspace where possible; implies --show-location
+        #[arg(long)]
+        relative: bool,
+    },
     /// Scaffold a new skill project from a template
     New {
         /// Skill name (snake_case recommended, e.g. my_weather_tool)
@@ -163,30 +227,328 @@ pub enum SkillCommands {
     Test {
         /// Path to the skill directory or installed skill name
         path: String,
-        /// Optional tool name inside the skill (defaults to first tool found)
+        /// Tool name inside the skill; required when it has more than one tool
+        #[arg(long)]
+        tool: Option<String>,
+        /// JSON arguments to pass to the tool, e.g. '{"city":"Hanoi"}';
+        /// '-' reads them from stdin until EOF (8 MiB at most)
+        #[arg(long, short)]
+        args: Option<String>,
+        /// Pass the input to the tool byte for byte: no JSON validation, and
+        /// stdout is printed as-is without the result summary. Structured
+        /// fields are not matched in this mode, so it can't be combined with
+        /// --compare-engines
+        #[arg(long, conflicts_with = "compare_engines")]
+        stdin_raw: bool,
+        /// Re-run the test whenever files in the skill directory change
+        #[arg(long)]
+        watch: bool,
+        /// Run the args through both the wasmtime CLI and the in-process engine
+        /// and fail if the results differ
+        #[arg(long, conflicts_with = "watch")]
+        compare_engines: bool,
+    },
+    /// Run a skill tool repeatedly and report wall time, memory and output size
+    #[command(long_about = "\
+Run a skill tool repeatedly and report wall time, memory and output size.
+
+The first run is reported as the cold start; min/p50/p95/max cover the \
+rest. Runs use the in-process engine when built with --features \
+wasm-tools, which also reports peak memory; otherwise the wasmtime CLI \
+is used and only wall times are reported.
+
+Examples:
+  zeroclaw skill bench ./my-skill --args '{\"city\":\"Hanoi\"}'
+  zeroclaw skill bench weather -n 50 --json
+  zeroclaw skill bench weather --fail-if-p95-ms 200")]
+    Bench {
+        /// Path to the skill directory or installed skill name
+        path: String,
+        /// Tool name inside the skill; required when it has more than one tool
         #[arg(long)]
         tool: Option<String>,
-        /// JSON arguments to pass to the tool, e.g. '{"city":"Hanoi"}'
+        /// JSON arguments to pass to the tool, as for `skill test`
         #[arg(long, short)]
         args: Option<String>,
+        /// Number of runs; the first is reported as the cold start
+        #[arg(long, short = 'n', default_value_t = 20)]
+        iterations: usize,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+        /// Fail when the p95 wall time exceeds this many milliseconds
+        #[arg(long, value_name = "MS")]
+        fail_if_p95_ms: Option<u64>,
+    },
+    /// Show what a skill tool would execute, without executing it
+    #[command(long_about = "\
+Show what a skill tool would execute, without executing it.
+
+The call is prepared as for a real run: `{{arg}}` placeholders in the \
+tool's command are filled from --args and `{{env.NAME}}` from \
+`[skills.env.<skill>]`, and the autonomy policy is applied. Shell tools \
+print the command line, working directory and environment and are not \
+spawned; HTTP tools print the request and whether the [http_request] \
+domain allowlist permits it, and nothing is sent. WASM tools run, \
+without preopened directories or network access. Secret values are \
+masked, and the approval a real run would need is shown.
+
+Examples:
+  zeroclaw skill dry-run weather --tool forecast --args '{\"city\":\"Hanoi\"}'
+  zeroclaw skill dry-run ./my-skill --tool deploy")]
+    DryRun {
+        /// Path to the skill directory or installed skill name
+        path: String,
+        /// Tool name inside the skill
+        #[arg(long)]
+        tool: String,
+        /// JSON object of arguments for the tool
+        #[arg(long, short, default_value = "{}")]
+        args: String,
     },
     /// Audit a skill source directory or installed skill name
     Audit {
         /// Skill path or installed skill name
         source: String,
+        /// Move recoverable offenders (stray scripts) into `.quarantine/` and re-audit
+        #[arg(long)]
+        fix: bool,
+        /// Extract a zip file, zip URL or ClawhHub skill into a throwaway
+        /// directory and run the full directory audit on it
+        #[arg(long, conflicts_with = "fix")]
+        sandbox: bool,
+        /// Audit every skill directory (with SKILL.md or SKILL.toml) under the
+        /// source, e.g. a repository of several skills, and report each one
+        #[arg(long, conflicts_with_all = ["fix", "sandbox"])]
+        recursive: bool,
     },
-    /// Install a new skill from a local path, git URL, or registry (namespace/name)
+    /// Install new skills from local paths, git URLs, or registries (namespace/name)
     Install {
-        /// Source: local path, git URL, or registry package (e.g. acme/my-tool)
-        source: String,
+        /// Sources: local paths, git URLs, or registry packages (e.g. acme/my-tool).
+        /// Several sources are installed in parallel and reported per source
+        #[arg(required_unless_present = "from_lockfile")]
+        sources: Vec<String>,
+        /// With several sources: how many to install at once
+        #[arg(long, default_value_t = 4, value_name = "N")]
+        concurrency: usize,
+        /// Lowest audit severity that blocks this install (low, medium, high, critical).
+        /// Overrides `[skills] block_at_severity`; lower findings only warn.
+        #[arg(long, value_parser = clap::value_parser!(crate::skills::AuditSeverity))]
+        severity_threshold: Option<crate::skills::AuditSeverity>,
+        /// Accept the `[skill.capabilities]` the skills declare. Required when
+        /// `[skills] require_capability_consent = true`
+        #[arg(long)]
+        accept_capabilities: bool,
+        /// Install skills marked deprecated by their manifest or the registry
+        /// without asking. Without it a deprecated skill is confirmed on the
+        /// terminal, or refused when there is none to ask on
+        #[arg(long)]
+        accept_deprecated: bool,
+        /// Registry to fetch `namespace/name` packages from for this install
+        /// (https:// only). Overrides `[wasm] registry_url`
+        #[arg(long, value_name = "URL")]
+        registry: Option<String>,
+        /// Keep the `.git` directory of git installs, for skills that use git
+        /// at runtime. It is audited with the skill
+        #[arg(long)]
+        keep_git: bool,
+        /// Install every skill pinned in a lockfile (default: skills/skills.lock)
+        /// and verify checksums; failures are reported per skill
+        #[arg(long, num_args = 0..=1, value_name = "PATH", conflicts_with = "sources")]
+        from_lockfile: Option<Option<std::path::PathBuf>>,
+        /// With --from-lockfile: fail remote sources immediately instead of fetching
+        #[arg(long, requires = "from_lockfile")]
+        offline: bool,
+        /// Replace a skill already installed under the same name, after showing
+        /// what changes in it
+        #[arg(long, conflicts_with = "from_lockfile")]
+        overwrite: bool,
+        /// With --overwrite: apply the changes without asking
+        #[arg(long, requires = "overwrite")]
+        yes: bool,
+        /// With --overwrite: fetch and show the changes without applying them
+        #[arg(long, requires = "overwrite", conflicts_with = "yes")]
+        diff_only: bool,
+        /// Print one JSON result per source on stdout, with a stable error
+        /// `code` for failures; progress messages go to stderr
+        #[arg(long, conflicts_with_all = ["from_lockfile", "overwrite"])]
+        json: bool,
+    },
+    /// Check git and registry installed skills for upstream updates
+    #[command(long_about = "\
+Check installed skills for upstream updates.
+
+Git-installed skills compare the commit recorded at install time against \
+`git ls-remote <url> HEAD`; registry packages compare the installed version \
+against the registry's latest. Zip and local installs are reported as \
+unknown. A failing remote is reported on its own row and does not fail \
+the command.
+
+Examples:
+  zeroclaw skill outdated
+  zeroclaw skill outdated --json")]
+    Outdated {
+        /// Emit machine-readable JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Re-fetch an installed skill from its source and replace it after review
+    #[command(long_about = "\
+Re-fetch an installed skill from the source it was installed from and \
+replace it.
+
+The fetched copy is audited like a fresh install. Before anything is \
+replaced, a unified diff of SKILL.md, SKILL.toml and tool manifest.json \
+files is shown (paged when long); other files such as tool.wasm are \
+listed with their size and checksum. The update is applied after \
+confirmation, or straight away with --yes, and recorded with the diff \
+in the audit log.
+
+Examples:
+  zeroclaw skill update weather
+  zeroclaw skill update weather --diff-only
+  zeroclaw skill update weather --yes")]
+    Update {
+        /// Installed skill name
+        name: String,
+        /// Apply the changes without asking
+        #[arg(long)]
+        yes: bool,
+        /// Fetch and show the changes without applying them
+        #[arg(long, conflicts_with = "yes")]
+        diff_only: bool,
+        /// Update the skill even if it is frozen
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the JSON Schema of the tool result envelope skills write to stdout
+    #[command(long_about = "\
+Print the JSON Schema of the tool result envelope.
+
+Skill tools write one JSON object to stdout: `success`, `output`, optional \
+`error`, optional `data` (arbitrary JSON), and optional `attachment`. Use \
+this schema to validate tool output from non-Rust skills.
+
+Examples:
+  zeroclaw skill protocol-schema > tool-result.schema.json")]
+    ProtocolSchema,
+    /// Show details of an installed skill, including install source and freeze status
+    Info {
+        /// Installed skill name
+        name: String,
+    },
+    /// Copy an installed skill to a directory for editing
+    #[command(long_about = "\
+Copy an installed skill to a directory for editing.
+
+The skill directory is copied as is; skills containing symlinks are \
+refused. The destination must be empty or missing and outside the \
+workspace skills directory. With --clean, install provenance \
+(`_install.json`, `_meta.json`, `.quarantine/`) is left out so the copy \
+is a plain editable project.
+
+Examples:
+  zeroclaw skill export weather ./weather
+  zeroclaw skill export weather ~/src/weather --clean")]
+    Export {
+        /// Installed skill name
+        name: String,
+        /// Directory to copy the skill into
+        dest: std::path::PathBuf,
+        /// Leave out install records and registry metadata
+        #[arg(long)]
+        clean: bool,
+    },
+    /// Protect an installed skill from bulk updates and automatic syncs
+    #[command(long_about = "\
+Protect an installed skill from bulk updates and automatic syncs.
+
+Frozen skills are recorded in `skills/.frozen`. Bulk operations (pack \
+dependency resolution, `remove --with-dependencies`, open-skills syncs) \
+skip them with a \"skipped (frozen)\" line, and explicit changes such as \
+`skill remove <name>` require --force.
+
+Examples:
+  zeroclaw skill freeze weather --reason \"patched timeout locally\"
+  zeroclaw skill unfreeze weather")]
+    Freeze {
+        /// Installed skill name
+        name: String,
+        /// Why the skill is frozen (shown by `skill info`)
+        #[arg(long)]
+        reason: Option<String>,
+    },
+    /// Allow bulk updates and syncs to change a frozen skill again
+    Unfreeze {
+        /// Installed skill name
+        name: String,
     },
     /// Remove an installed skill
     Remove {
         /// Skill name to remove
         name: String,
+        /// Also remove dependencies that no other installed skill needs
+        #[arg(long)]
+        with_dependencies: bool,
+        /// Remove the skill even if it is frozen
+        #[arg(long)]
+        force: bool,
     },
     /// List all available skill templates
     Templates,
+    /// Template developer tools
+    #[command(hide = true)]
+    Template {
+        #[command(subcommand)]
+        template_command: SkillTemplateCommands,
+    },
+}
+
+/// Skill template developer subcommands (`zeroclaw skill template ...`)
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SkillTemplateCommands {
+    /// Scaffold templates into a temp dir and check the output
+    #[command(long_about = "\
+Scaffold templates into a temporary directory and check the output.
+
+For each template: the template's files, SKILL.md, README.md, .gitignore, \
+manifest.json and the build entry point (Cargo.toml, package.json, go.mod \
+or main.py) must exist, manifest.json must name the skill, and no \
+__PLACEHOLDER__ token may remain. Exits non-zero if any template fails.
+
+Examples:
+  zeroclaw skill template validate
+  zeroclaw skill template validate weather_lookup")]
+    Validate {
+        /// Template name or language alias (default: every template)
+        name: Option<String>,
+    },
+}
+
+/// Workspace subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkspaceCommands {
+    /// Scaffold the bootstrap identity files (AGENTS.md, SOUL.md, ...) with starter templates
+    #[command(long_about = "\
+Scaffold the workspace bootstrap files with commented starter templates.
+
+Creates AGENTS.md, SOUL.md, TOOLS.md, IDENTITY.md, USER.md, HEARTBEAT.md \
+and MEMORY.md, plus the skills/ and memory/ directories. Existing \
+non-empty files are never overwritten. Ends with a table showing how \
+much each file contributes to the system prompt.
+
+Examples:
+  zeroclaw workspace init
+  zeroclaw workspace init --interactive
+  zeroclaw workspace init --force")]
+    Init {
+        /// Ask for agent name, your name, tone, and timezone
+        #[arg(long)]
+        interactive: bool,
+        /// Append template sections missing from existing files (never rewrites content)
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 /// Migration subcommands
@@ -324,7 +686,8 @@ Examples:
 pub enum MemoryCommands {
     /// List memory entries with optional filters
     List {
-        /// Filter by category (core, daily, conversation, or custom name)
+        /// Filter by category (core, daily, conversation, fact, preference, task,
+        /// ephemeral, or a custom name)
         #[arg(long)]
         category: Option<String>,
         /// Filter by session ID
@@ -356,6 +719,15 @@ pub enum MemoryCommands {
         #[arg(long)]
         yes: bool,
     },
+    /// Restore forgotten memories (lists recent deletions when no target is given)
+    Restore {
+        /// Deletion id or batch id, as printed when the memories were forgotten
+        #[arg(conflicts_with = "last")]
+        id: Option<String>,
+        /// Restore the most recent deletion batch
+        #[arg(long)]
+        last: bool,
+    },
 }
 
 /// Integration subcommands
@@ -0,0 +1,450 @@
+//! Identity links: one conversation across channels and the local CLI.
+//!
+//! `zeroclaw link create` issues a one-time code. Sending that code to the
+//! bot on any channel links `channel:sender` to the local CLI identity
+//! ([`CLI_IDENTITY`]); from then on that sender and the CLI read the same
+//! conversation history, so a chat started on Telegram can be continued
+//! with `zeroclaw agent`.
+//!
+//! A link is either:
+//! - **merged**: the sender's turns go into the shared log and it reads the
+//!   shared log instead of its own per-channel history;
+//! - **mirrored**: the sender reads the shared log (read-only) ahead of its
+//!   own history, but nothing it says is written back.
+//!
+//! Codes are single-use and expire after [`CODE_TTL_SECS`]. Everything lives
+//! in `state/links.db` under the workspace; handshake messages are answered
+//! directly and never enter any history. Memory is not partitioned by
+//! channel, so linked identities already share recall.
+
+use crate::providers::ChatMessage;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+
+/// Identity of the local CLI (`zeroclaw agent`).
+pub const CLI_IDENTITY: &str = "cli:local";
+
+/// How long a code from `zeroclaw link create` stays redeemable.
+pub const CODE_TTL_SECS: i64 = 10 * 60;
+
+/// Prefix of every link code, e.g. `LINK-7QH2M9XD`.
+const CODE_PREFIX: &str = "LINK-";
+const CODE_LEN: usize = 8;
+/// 32 symbols without look-alikes (no I, O, 0, 1), so `byte % 32` is unbiased.
+const CODE_ALPHABET: &[u8; 32] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Oldest shared turns are dropped beyond this.
+const MAX_SHARED_TURNS: usize = 200;
+
+/// How a linked identity shares the conversation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkMode {
+    Merged,
+    Mirrored,
+}
+
+impl LinkMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Merged => "merged",
+            Self::Mirrored => "mirrored",
+        }
+    }
+
+    fn parse(raw: &str) -> Self {
+        if raw == "mirrored" {
+            Self::Mirrored
+        } else {
+            Self::Merged
+        }
+    }
+}
+
+/// One row of `zeroclaw link list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityLink {
+    pub identity: String,
+    pub mode: LinkMode,
+    pub linked_at: DateTime<Utc>,
+}
+
+/// `channel:sender` as stored in the links table.
+pub fn identity(channel: &str, sender: &str) -> String {
+    format!("{channel}:{sender}")
+}
+
+/// Whether a whole inbound message is a link code (and nothing else).
+pub fn looks_like_code(message: &str) -> bool {
+    let message = message.trim();
+    message.len() == CODE_PREFIX.len() + CODE_LEN
+        && message
+            .get(..CODE_PREFIX.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(CODE_PREFIX))
+        && message[CODE_PREFIX.len()..]
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric())
+}
+
+fn db_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join("links.db")
+}
+
+fn generate_code() -> String {
+    let bytes: [u8; CODE_LEN] = rand::random();
+    let suffix: String = bytes
+        .iter()
+        .map(|b| char::from(CODE_ALPHABET[usize::from(*b) % CODE_ALPHABET.len()]))
+        .collect();
+    format!("{CODE_PREFIX}{suffix}")
+}
+
+pub struct LinkStore {
+    conn: Connection,
+}
+
+impl LinkStore {
+    /// Open (creating if needed) the store under `workspace_dir`.
+    pub fn open(workspace_dir: &Path) -> Result<Self> {
+        let path = db_path(workspace_dir);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let conn = Connection::open(&path)
+            .with_context(|| format!("failed to open links DB: {}", path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS link_codes (
+                code       TEXT PRIMARY KEY,
+                mode       TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                used       INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE TABLE IF NOT EXISTS identity_links (
+                identity  TEXT PRIMARY KEY,
+                mode      TEXT NOT NULL,
+                linked_at INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS shared_turns (
+                id         INTEGER PRIMARY KEY AUTOINCREMENT,
+                origin     TEXT NOT NULL,
+                role       TEXT NOT NULL,
+                content    TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );",
+        )
+        .context("failed to initialize links schema")?;
+        Ok(Self { conn })
+    }
+
+    /// Open the store only if `zeroclaw link` has ever been used here.
+    pub fn open_existing(workspace_dir: &Path) -> Result<Option<Self>> {
+        if !db_path(workspace_dir).exists() {
+            return Ok(None);
+        }
+        Self::open(workspace_dir).map(Some)
+    }
+
+    /// Issue a fresh single-use code; expired and used codes are pruned.
+    pub fn create_code(&self, mode: LinkMode, now: i64) -> Result<String> {
+        self.conn.execute(
+            "DELETE FROM link_codes WHERE used = 1 OR created_at <= ?1",
+            params![now - CODE_TTL_SECS],
+        )?;
+        let code = generate_code();
+        self.conn.execute(
+            "INSERT INTO link_codes (code, mode, created_at) VALUES (?1, ?2, ?3)",
+            params![code, mode.as_str(), now],
+        )?;
+        Ok(code)
+    }
+
+    /// Consume `code` and link `identity`. `None` when the code is unknown,
+    /// already used or expired.
+    pub fn redeem(&self, code: &str, identity: &str, now: i64) -> Result<Option<LinkMode>> {
+        if identity == CLI_IDENTITY {
+            bail!("the CLI identity cannot be linked to itself");
+        }
+        let code = code.trim().to_ascii_uppercase();
+        let mode: Option<String> = self
+            .conn
+            .query_row(
+                "UPDATE link_codes SET used = 1
+                 WHERE code = ?1 AND used = 0 AND created_at > ?2
+                 RETURNING mode",
+                params![code, now - CODE_TTL_SECS],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let Some(mode) = mode.as_deref().map(LinkMode::parse) else {
+            return Ok(None);
+        };
+        self.conn.execute(
+            "INSERT OR REPLACE INTO identity_links (identity, mode, linked_at)
+             VALUES (?1, ?2, ?3)",
+            params![identity, mode.as_str(), now],
+        )?;
+        Ok(Some(mode))
+    }
+
+    pub fn links(&self) -> Result<Vec<IdentityLink>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT identity, mode, linked_at FROM identity_links ORDER BY linked_at")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(IdentityLink {
+                identity: row.get(0)?,
+                mode: LinkMode::parse(&row.get::<_, String>(1)?),
+                linked_at: DateTime::from_timestamp(row.get(2)?, 0).unwrap_or_default(),
+            })
+        })?;
+        let links = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(links)
+    }
+
+    /// How `identity` shares the conversation; the CLI is always merged once
+    /// anything is linked to it.
+    pub fn mode_for(&self, identity: &str) -> Result<Option<LinkMode>> {
+        if identity == CLI_IDENTITY {
+            let linked: i64 =
+                self.conn
+                    .query_row("SELECT COUNT(*) FROM identity_links", [], |row| row.get(0))?;
+            return Ok((linked > 0).then_some(LinkMode::Merged));
+        }
+        let mode: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT mode FROM identity_links WHERE identity = ?1",
+                params![identity],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(mode.as_deref().map(LinkMode::parse))
+    }
+
+    /// Unlink `identity`; `false` when it was not linked. Turns it already
+    /// contributed stay in the shared log.
+    pub fn remove(&self, identity: &str) -> Result<bool> {
+        let removed = self.conn.execute(
+            "DELETE FROM identity_links WHERE identity = ?1",
+            params![identity],
+        )?;
+        Ok(removed > 0)
+    }
+
+    fn append_turn(&self, origin: &str, turn: &ChatMessage, now: i64) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO shared_turns (origin, role, content, created_at) VALUES (?1, ?2, ?3, ?4)",
+            params![origin, turn.role, turn.content, now],
+        )?;
+        self.conn.execute(
+            "DELETE FROM shared_turns WHERE id <= (SELECT MAX(id) FROM shared_turns) - ?1",
+            params![i64::try_from(MAX_SHARED_TURNS).unwrap_or(i64::MAX)],
+        )?;
+        Ok(())
+    }
+
+    /// Most recent `limit` shared turns, oldest first.
+    fn shared_turns(&self, limit: usize) -> Result<Vec<ChatMessage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT role, content FROM (
+                SELECT id, role, content FROM shared_turns ORDER BY id DESC LIMIT ?1
+             ) ORDER BY id",
+        )?;
+        let rows = stmt.query_map(params![i64::try_from(limit).unwrap_or(i64::MAX)], |row| {
+            Ok(ChatMessage {
+                role: row.get(0)?,
+                content: row.get(1)?,
+            })
+        })?;
+        let turns = rows.collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(turns)
+    }
+}
+
+/// The shared conversation as seen by one linked identity.
+pub struct LinkedConversation {
+    store: LinkStore,
+    identity: String,
+    mode: LinkMode,
+}
+
+impl LinkedConversation {
+    /// `None` when `identity` is not linked (or the store is unreadable,
+    /// which is logged and treated the same).
+    pub fn resolve(workspace_dir: &Path, identity: &str) -> Option<Self> {
+        let lookup = || -> Result<Option<Self>> {
+            let Some(store) = LinkStore::open_existing(workspace_dir)? else {
+                return Ok(None);
+            };
+            let mode = store.mode_for(identity)?;
+            Ok(mode.map(|mode| Self {
+                store,
+                identity: identity.to_string(),
+                mode,
+            }))
+        };
+        lookup().unwrap_or_else(|err| {
+            tracing::warn!("Failed to read identity links: {err:#}");
+            None
+        })
+    }
+
+    pub fn mode(&self) -> LinkMode {
+        self.mode
+    }
+
+    /// Last `limit` turns of the shared log.
+    pub fn turns(&self, limit: usize) -> Vec<ChatMessage> {
+        self.store.shared_turns(limit).unwrap_or_else(|err| {
+            tracing::warn!("Failed to read shared history: {err:#}");
+            Vec::new()
+        })
+    }
+
+    /// Add a turn to the shared log; a no-op for mirrored (read-only) links.
+    pub fn record(&self, turn: &ChatMessage) {
+        if self.mode != LinkMode::Merged {
+            return;
+        }
+        if let Err(err) = self
+            .store
+            .append_turn(&self.identity, turn, Utc::now().timestamp())
+        {
+            tracing::warn!("Failed to record shared turn: {err:#}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const NOW: i64 = 1_770_000_000;
+
+    #[test]
+    fn codes_are_single_use_and_expire() {
+        let tmp = TempDir::new().unwrap();
+        let store = LinkStore::open(tmp.path()).unwrap();
+
+        let code = store.create_code(LinkMode::Merged, NOW).unwrap();
+        assert!(looks_like_code(&code), "{code}");
+        assert!(looks_like_code(&format!("  {}\n", code.to_lowercase())));
+        assert!(!looks_like_code("link me please"));
+
+        let telegram = identity("telegram", "alice");
+        assert_eq!(
+            store
+                .redeem(&code.to_lowercase(), &telegram, NOW + 5)
+                .unwrap(),
+            Some(LinkMode::Merged)
+        );
+        // Used once; a second channel can't reuse it.
+        assert_eq!(
+            store
+                .redeem(&code, &identity("discord", "alice"), NOW + 6)
+                .unwrap(),
+            None
+        );
+
+        let stale = store.create_code(LinkMode::Mirrored, NOW).unwrap();
+        assert_eq!(
+            store
+                .redeem(&stale, &identity("slack", "alice"), NOW + CODE_TTL_SECS)
+                .unwrap(),
+            None
+        );
+        assert!(store
+            .redeem("LINK-NOTACODE", &telegram, NOW)
+            .unwrap()
+            .is_none());
+
+        let links = store.links().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].identity, "telegram:alice");
+        assert_eq!(
+            store.mode_for(CLI_IDENTITY).unwrap(),
+            Some(LinkMode::Merged)
+        );
+    }
+
+    #[test]
+    fn merged_identities_recall_each_others_turns() {
+        let tmp = TempDir::new().unwrap();
+        let store = LinkStore::open(tmp.path()).unwrap();
+        for sender in ["telegram:alice", "discord:alice"] {
+            let code = store.create_code(LinkMode::Merged, NOW).unwrap();
+            store.redeem(&code, sender, NOW + 1).unwrap().unwrap();
+        }
+
+        let telegram = LinkedConversation::resolve(tmp.path(), "telegram:alice").unwrap();
+        telegram.record(&ChatMessage::user("my flight is LH 400"));
+        telegram.record(&ChatMessage::assistant("Noted: LH 400."));
+
+        let discord = LinkedConversation::resolve(tmp.path(), "discord:alice").unwrap();
+        let seen = discord.turns(50);
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].content, "my flight is LH 400");
+
+        let cli = LinkedConversation::resolve(tmp.path(), CLI_IDENTITY).unwrap();
+        assert_eq!(cli.turns(1)[0].content, "Noted: LH 400.");
+        assert!(LinkedConversation::resolve(tmp.path(), "slack:bob").is_none());
+    }
+
+    #[test]
+    fn mirrored_links_read_without_writing() {
+        let tmp = TempDir::new().unwrap();
+        let store = LinkStore::open(tmp.path()).unwrap();
+        let code = store.create_code(LinkMode::Mirrored, NOW).unwrap();
+        store.redeem(&code, "telegram:alice", NOW).unwrap();
+
+        let cli = LinkedConversation::resolve(tmp.path(), CLI_IDENTITY).unwrap();
+        cli.record(&ChatMessage::user("from the terminal"));
+        let telegram = LinkedConversation::resolve(tmp.path(), "telegram:alice").unwrap();
+        assert_eq!(telegram.mode(), LinkMode::Mirrored);
+        telegram.record(&ChatMessage::user("from the phone"));
+        let turns = cli.turns(50);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].content, "from the terminal");
+    }
+
+    #[test]
+    fn unlinking_separates_later_history() {
+        let tmp = TempDir::new().unwrap();
+        let store = LinkStore::open(tmp.path()).unwrap();
+        let code = store.create_code(LinkMode::Merged, NOW).unwrap();
+        store.redeem(&code, "telegram:alice", NOW).unwrap();
+        LinkedConversation::resolve(tmp.path(), "telegram:alice")
+            .unwrap()
+            .record(&ChatMessage::user("before unlink"));
+
+        assert!(store.remove("telegram:alice").unwrap());
+        assert!(!store.remove("telegram:alice").unwrap());
+        assert!(LinkedConversation::resolve(tmp.path(), "telegram:alice").is_none());
+        // Nothing is linked any more, so the CLI is back on its own too.
+        assert!(LinkedConversation::resolve(tmp.path(), CLI_IDENTITY).is_none());
+
+        let turns = store.shared_turns(50).unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].content, "before unlink");
+    }
+
+    #[test]
+    fn shared_log_keeps_only_recent_turns() {
+        let tmp = TempDir::new().unwrap();
+        let store = LinkStore::open(tmp.path()).unwrap();
+        for i in 0..MAX_SHARED_TURNS + 5 {
+            store
+                .append_turn(CLI_IDENTITY, &ChatMessage::user(format!("turn {i}")), NOW)
+                .unwrap();
+        }
+        let turns = store.shared_turns(usize::MAX).unwrap();
+        assert_eq!(turns.len(), MAX_SHARED_TURNS);
+        assert_eq!(turns[0].content, "turn 5");
+        assert!(LinkStore::open_existing(&tmp.path().join("elsewhere"))
+            .unwrap()
+            .is_none());
+    }
+}
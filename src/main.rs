@@ -62,6 +62,7 @@ mod cost;
 mod cron;
 mod daemon;
 mod doctor;
+mod exit_code;
 mod gateway;
 mod goals;
 mod hardware;
@@ -70,6 +71,8 @@ mod heartbeat;
 mod hooks;
 mod identity;
 mod integrations;
+mod links;
+mod maintenance;
 mod memory;
 mod migration;
 mod multimodal;
@@ -84,15 +87,17 @@ mod skillforge;
 mod skills;
 mod tools;
 mod tunnel;
+mod uninstall;
 mod update;
 mod util;
+mod workspace;
 
 use config::Config;
 
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
     ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    PeripheralCommands, ServiceCommands, SkillCommands, SkillTemplateCommands, WorkspaceCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -127,10 +132,16 @@ enum EstopLevelArg {
 #[command(author = "theonlyhennygod")]
 #[command(version)]
 #[command(about = "The fastest, smallest AI assistant.", long_about = None)]
+#[command(after_help = exit_code::HELP)]
 struct Cli {
     #[arg(long, global = true)]
     config_dir: Option<String>,
 
+    /// Suppress startup banners and install progress; servers log their
+    /// banner through tracing instead
+    #[arg(long, short, global = true)]
+    quiet: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -236,12 +247,13 @@ Start the gateway server (webhooks, websockets).
 
 Runs the HTTP/WebSocket gateway that accepts incoming webhook events \
 and WebSocket connections. Bind address defaults to the values in \
-your config file (gateway.host / gateway.port).
+your config file (gateway.listen, or gateway.host / gateway.port).
 
 Examples:
   zeroclaw gateway                  # use config defaults
   zeroclaw gateway -p 8080          # listen on port 8080
   zeroclaw gateway --host 0.0.0.0   # bind to all interfaces
+  zeroclaw gateway --host unix:/run/zeroclaw/gateway.sock   # Unix socket
   zeroclaw gateway -p 0             # random available port
   zeroclaw gateway --new-pairing    # clear tokens and generate fresh pairing code")]
     Gateway {
@@ -249,7 +261,7 @@ Examples:
         #[arg(short, long)]
         port: Option<u16>,
 
-        /// Host to bind to; defaults to config gateway.host
+        /// Host to bind to, or unix:<path> for a Unix socket; defaults to config gateway.listen or gateway.host
         #[arg(long)]
         host: Option<String>,
 
@@ -279,7 +291,7 @@ Examples:
         #[arg(short, long)]
         port: Option<u16>,
 
-        /// Host to bind to; defaults to config gateway.host
+        /// Host to bind to, or unix:<path> for a Unix socket; defaults to config gateway.listen or gateway.host
         #[arg(long)]
         host: Option<String>,
     },
@@ -353,6 +365,85 @@ Examples:
         tools: Vec<String>,
     },
 
+    /// Put the agent in (or take it out of) maintenance mode.
+    ///
+    /// While on, the gateway and channels answer every message with
+    /// `[agent] maintenance_reply` and make no provider calls. Running
+    /// instances pick up the change without a restart.
+    ///
+    /// Examples:
+    /// - `zeroclaw maintenance on --message "Back at 5pm"`
+    /// - `zeroclaw maintenance off`
+    /// - `zeroclaw maintenance status`
+    Maintenance {
+        #[command(subcommand)]
+        maintenance_command: MaintenanceSubcommands,
+    },
+
+    /// Run the `[heartbeat]` self-check.
+    ///
+    /// The daemon and `zeroclaw channel start` run it every
+    /// `[heartbeat] interval_minutes`; `run-once` runs it now, with the same
+    /// maintenance, budget and delivery rules, and records it for
+    /// `zeroclaw status`.
+    ///
+    /// Examples:
+    /// - `zeroclaw heartbeat run-once`
+    Heartbeat {
+        #[command(subcommand)]
+        heartbeat_command: HeartbeatSubcommands,
+    },
+
+    /// Link channel senders to the local CLI so they share one conversation.
+    ///
+    /// `create` prints a one-time code (valid 10 minutes); send it to the bot
+    /// from Telegram, Discord, ... and that chat's history is shared with
+    /// `zeroclaw agent`.
+    ///
+    /// Examples:
+    /// - `zeroclaw link create`
+    /// - `zeroclaw link create --mirrored`
+    /// - `zeroclaw link list`
+    /// - `zeroclaw link remove telegram:alice`
+    Link {
+        #[command(subcommand)]
+        link_command: LinkCommands,
+    },
+
+    /// Remove ZeroClaw state (config, workspace, memory, skills) from this machine.
+    ///
+    /// Prints an inventory first and asks you to type the workspace directory
+    /// name. Symlinks are never followed; files ZeroClaw did not create are
+    /// left in place.
+    ///
+    /// Examples:
+    /// - `zeroclaw uninstall`
+    /// - `zeroclaw uninstall --keep-memory --keep-skills`
+    /// - `zeroclaw uninstall --yes`
+    Uninstall {
+        /// Skip the typed confirmation.
+        #[arg(long)]
+        yes: bool,
+        /// Keep the memory store and MEMORY.md.
+        #[arg(long)]
+        keep_memory: bool,
+        /// Keep installed skills and the open-skills clone.
+        #[arg(long)]
+        keep_skills: bool,
+        /// With --yes, allow removing a workspace outside the home directory.
+        #[arg(long)]
+        allow_outside_home: bool,
+    },
+
+    /// Inspect lifecycle hooks.
+    ///
+    /// Examples:
+    /// - `zeroclaw hooks test-outbound "Ping db-7.corp.internal for CUST-123456"`
+    Hooks {
+        #[command(subcommand)]
+        hooks_command: HooksCommands,
+    },
+
     /// Configure and manage scheduled tasks
     #[command(long_about = "\
 Configure and manage scheduled tasks.
@@ -384,8 +475,11 @@ Examples:
         model_command: ModelCommands,
     },
 
-    /// List supported AI providers
-    Providers,
+    /// List supported AI providers, or benchmark them
+    Providers {
+        #[command(subcommand)]
+        provider_command: Option<ProviderCommands>,
+    },
 
     /// Manage channels (telegram, discord, slack)
     #[command(long_about = "\
@@ -419,6 +513,12 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Scaffold and inspect the agent workspace
+    Workspace {
+        #[command(subcommand)]
+        workspace_command: WorkspaceCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
@@ -473,14 +573,16 @@ Manage agent memory entries.
 
 List, inspect, and clear memory entries stored by the agent. \
 Supports filtering by category and session, pagination, and \
-batch clearing with confirmation.
+batch clearing with confirmation. Cleared entries can be restored \
+for `[memory] undo_retention_days` (default 7).
 
 Examples:
   zeroclaw memory stats
   zeroclaw memory list
   zeroclaw memory list --category core --limit 10
   zeroclaw memory get <key>
-  zeroclaw memory clear --category conversation --yes")]
+  zeroclaw memory clear --category conversation --yes
+  zeroclaw memory restore --last")]
     Memory {
         #[command(subcommand)]
         memory_command: MemoryCommands,
@@ -492,16 +594,37 @@ Manage ZeroClaw configuration.
 
 Inspect and export configuration settings. Use 'schema' to dump \
 the full JSON Schema for the config file, which documents every \
-available key, type, and default value.
+available key, type, and default value. Use 'diff' to list only the \
+settings you have changed from the defaults (secrets redacted).
 
 Examples:
   zeroclaw config schema              # print JSON Schema to stdout
-  zeroclaw config schema > schema.json")]
+  zeroclaw config schema > schema.json
+  zeroclaw config diff                # show non-default settings")]
     Config {
         #[command(subcommand)]
         config_command: ConfigCommands,
     },
 
+    /// Encrypt config secrets in place or rotate the encryption key
+    #[command(long_about = "\
+Manage secrets stored in config.toml.
+
+Secrets (API keys, channel tokens, webhook secrets, ...) are encrypted at \
+rest with the key in ~/.zeroclaw/.secret_key, or a key derived from \
+ZEROCLAW_SECRETS_PASSPHRASE. Plaintext values keep working; 'migrate' \
+encrypts them in place without touching comments or formatting. 'rotate' \
+re-encrypts every secret under a new key, including auth profiles and the \
+OTP secret. Stop the daemon before rotating.
+
+Examples:
+  zeroclaw secrets migrate
+  zeroclaw secrets rotate")]
+    Secrets {
+        #[command(subcommand)]
+        secrets_command: SecretsCommands,
+    },
+
     /// Generate shell completion script to stdout
     #[command(long_about = "\
 Generate shell completion scripts for `zeroclaw`.
@@ -521,8 +644,46 @@ Examples:
 
 #[derive(Subcommand, Debug)]
 enum ConfigCommands {
-    /// Dump the full configuration JSON Schema to stdout
-    Schema,
+    /// Dump the configuration JSON Schema to stdout
+    #[command(long_about = "\
+Dump the configuration JSON Schema (draft 2020-12) to stdout.
+
+Point an editor's TOML language server (taplo / Even Better TOML) at the \
+output for completion and inline validation of config.toml. `--path` prints \
+one section instead, with the definitions it uses.
+
+Examples:
+  zeroclaw config schema > zeroclaw-config.schema.json
+  zeroclaw config schema --path skills
+  zeroclaw config schema --path channels_config.telegram")]
+    Schema {
+        /// Dotted path of a section, e.g. `skills` or `gateway`
+        #[arg(long)]
+        path: Option<String>,
+    },
+    /// Show only the settings that differ from built-in defaults (secrets redacted)
+    Diff,
+    /// Check the config for values that would fail at runtime, including provider names
+    #[command(long_about = "\
+Validate the loaded configuration.
+
+Runs the same checks as config loading, then builds every provider \
+referenced by `default_provider` and `[[model_routes]]` (no network calls) \
+so typos and malformed `custom:` URLs are reported up front. All provider \
+problems are listed together with their config path; the command exits \
+non-zero if any are found.
+
+Examples:
+  zeroclaw config validate")]
+    Validate,
+}
+
+#[derive(Subcommand, Debug)]
+enum SecretsCommands {
+    /// Encrypt plaintext secrets in config.toml (and upgrade legacy `enc:` values)
+    Migrate,
+    /// Re-encrypt every secret under a freshly generated key
+    Rotate,
 }
 
 #[derive(Subcommand, Debug)]
@@ -546,6 +707,49 @@ enum EstopSubcommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+enum MaintenanceSubcommands {
+    /// Enable maintenance mode.
+    On {
+        /// Note appended to the canned reply, e.g. "Back at 5pm".
+        #[arg(long)]
+        message: Option<String>,
+    },
+    /// Disable maintenance mode (queued channel messages are then replayed).
+    Off,
+    /// Print current maintenance status.
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+enum HeartbeatSubcommands {
+    /// Run one heartbeat now.
+    RunOnce,
+}
+
+#[derive(Subcommand, Debug)]
+enum LinkCommands {
+    /// Print a one-time code to send to the bot from the chat to link.
+    Create {
+        /// The linked chat reads the CLI conversation but does not add to it.
+        #[arg(long)]
+        mirrored: bool,
+    },
+    /// List linked identities.
+    List,
+    /// Unlink an identity (`channel:sender`, as shown by `link list`).
+    Remove { identity: String },
+}
+
+#[derive(Subcommand, Debug)]
+enum HooksCommands {
+    /// Run `[hooks.outbound]` rules on sample text and print the result.
+    TestOutbound {
+        /// Reply text to filter.
+        text: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum AuthCommands {
     /// Login with OAuth (OpenAI Codex or Gemini)
@@ -629,6 +833,32 @@ enum AuthCommands {
     Status,
 }
 
+#[derive(Subcommand, Debug)]
+enum ProviderCommands {
+    /// Send the same prompt to each provider and report p50/p95 latency and tokens/sec
+    Benchmark {
+        /// Comma-separated provider names (e.g. openrouter,groq,ollama)
+        #[arg(long, value_delimiter = ',', required = true)]
+        providers: Vec<String>,
+
+        /// Model to request from every provider (defaults to the configured default model)
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Prompt sent on every run
+        #[arg(long, default_value = "Reply with the single word: ok")]
+        prompt: String,
+
+        /// Measured requests per provider
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+
+        /// Unmeasured requests sent to each provider first
+        #[arg(long, default_value_t = 1)]
+        warmup: usize,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 enum ModelCommands {
     /// Refresh and cache provider models
@@ -720,8 +950,18 @@ enum MemoryCommands {
 }
 
 #[tokio::main]
+async fn main() -> std::process::ExitCode {
+    match run().await {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err:?}");
+            exit_code::for_error(&err).into()
+        }
+    }
+}
+
 #[allow(clippy::too_many_lines)]
-async fn main() -> Result<()> {
+async fn run() -> Result<()> {
     // Install default crypto provider for Rustls TLS.
     // This prevents the error: "could not automatically determine the process-level CryptoProvider"
     // when both aws-lc-rs and ring features are available (or neither is explicitly selected).
@@ -737,6 +977,7 @@ async fn main() -> Result<()> {
         }
         std::env::set_var("ZEROCLAW_CONFIG_DIR", config_dir);
     }
+    observability::banner::set_quiet(cli.quiet);
 
     // Completions must remain stdout-only and should not load config or initialize logging.
     // This avoids warnings/log lines corrupting sourced completion scripts.
@@ -747,11 +988,29 @@ async fn main() -> Result<()> {
     }
 
     // Initialize logging - respects RUST_LOG env var, defaults to INFO
+    let mut env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    if let Commands::Channel {
+        channel_command:
+            ChannelCommands::Start {
+                only,
+                except,
+                debug: true,
+                ..
+            },
+    } = &cli.command
+    {
+        let selection = channels::ChannelSelection {
+            only: only.clone(),
+            except: except.clone(),
+        };
+        for directive in selection.debug_log_directives() {
+            env_filter = env_filter.add_directive(directive.parse()?);
+        }
+    }
     let subscriber = fmt::Subscriber::builder()
         .with_timer(tracing_subscriber::fmt::time::ChronoLocal::rfc_3339())
-        .with_env_filter(
-            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
-        )
+        .with_env_filter(env_filter)
         .finish();
 
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
@@ -902,8 +1161,12 @@ async fn main() -> Result<()> {
                 info!("🔐 Cleared paired tokens — a fresh pairing code will be generated");
             }
             let port = port.unwrap_or(config.gateway.port);
-            let host = host.unwrap_or_else(|| config.gateway.host.clone());
-            if port == 0 {
+            let host = host
+                .or_else(|| config.gateway.listen.clone())
+                .unwrap_or_else(|| config.gateway.host.clone());
+            if host.starts_with(gateway::listen::UNIX_SCHEME) {
+                info!("🚀 Starting ZeroClaw Gateway on {host}");
+            } else if port == 0 {
                 info!("🚀 Starting ZeroClaw Gateway on {host} (random port)");
             } else {
                 info!("🚀 Starting ZeroClaw Gateway on {host}:{port}");
@@ -913,8 +1176,12 @@ async fn main() -> Result<()> {
 
         Commands::Daemon { port, host } => {
             let port = port.unwrap_or(config.gateway.port);
-            let host = host.unwrap_or_else(|| config.gateway.host.clone());
-            if port == 0 {
+            let host = host
+                .or_else(|| config.gateway.listen.clone())
+                .unwrap_or_else(|| config.gateway.host.clone());
+            if host.starts_with(gateway::listen::UNIX_SCHEME) {
+                info!("🧠 Starting ZeroClaw Daemon on {host}");
+            } else if port == 0 {
                 info!("🧠 Starting ZeroClaw Daemon on {host} (random port)");
             } else {
                 info!("🧠 Starting ZeroClaw Daemon on {host}:{port}");
@@ -956,11 +1223,44 @@ async fn main() -> Result<()> {
                     "disabled".into()
                 }
             );
+            if let Some(last) =
+                heartbeat::runner::load_runs(&heartbeat::runner::runs_path(&config)).pop()
+            {
+                println!("   Last run:      {}", last.describe());
+            }
             println!(
                 "🧠 Memory:         {} (auto-save: {})",
                 effective_memory_backend,
                 if config.memory.auto_save { "on" } else { "off" }
             );
+            let maintenance_status = maintenance::MaintenanceMode::from_config(&config).status();
+            println!(
+                "🚧 Maintenance:    {}",
+                describe_maintenance(&maintenance_status)
+            );
+            println!(
+                "🧩 WASM:           up to {} at once, queue timeout {}s",
+                config.wasm.max_concurrent, config.wasm.queue_timeout_secs
+            );
+            if let Some(metrics) = daemon::running_metrics(&config) {
+                let metric = |name: &str| metrics.get(name).copied().unwrap_or(0);
+                println!(
+                    "   Queue:         {} running, {} waiting, {} rejected",
+                    metric(tools::wasm_queue::METRIC_RUNNING),
+                    metric(tools::wasm_queue::METRIC_QUEUE_DEPTH),
+                    metric(tools::wasm_queue::METRIC_REJECTED)
+                );
+                println!(
+                    "🔀 Failover:       {} active, {} failovers, {} recoveries",
+                    metric(providers::failover::METRIC_ACTIVE),
+                    metric(providers::failover::METRIC_FAILOVERS),
+                    metric(providers::failover::METRIC_RECOVERIES)
+                );
+                println!(
+                    "🙅 Refusals:       {}",
+                    metric(providers::reliable::METRIC_REFUSALS)
+                );
+            }
 
             println!();
             println!("Security:");
@@ -1002,6 +1302,41 @@ async fn main() -> Result<()> {
                 );
             }
             println!();
+            println!("Delegate agents:");
+            if config.agents.is_empty() {
+                println!("  (none configured)");
+            } else {
+                let running = agent::delegates::daemon_instantiated_agents(&config);
+                let mut names: Vec<&String> = config.agents.keys().collect();
+                names.sort();
+                for name in names {
+                    let delegate = &config.agents[name];
+                    let mut channels: Vec<&str> = config
+                        .channels_config
+                        .agents
+                        .iter()
+                        .filter(|(_, agent_name)| agent_name.trim() == name.as_str())
+                        .map(|(channel, _)| channel.as_str())
+                        .collect();
+                    channels.sort_unstable();
+                    let state = match &running {
+                        Some(running) if running.contains(name) => "instantiated",
+                        Some(_) => "not yet used",
+                        None => "daemon not running",
+                    };
+                    println!(
+                        "  {name:<16} {}/{}  channels: {}  ({state})",
+                        delegate.provider,
+                        delegate.model,
+                        if channels.is_empty() {
+                            "-".to_string()
+                        } else {
+                            channels.join(", ")
+                        }
+                    );
+                }
+            }
+            println!();
             println!("Peripherals:");
             println!(
                 "  Enabled:   {}",
@@ -1028,6 +1363,33 @@ async fn main() -> Result<()> {
             tools,
         } => handle_estop_command(&config, estop_command, level, domains, tools),
 
+        Commands::Maintenance {
+            maintenance_command,
+        } => handle_maintenance_command(&config, maintenance_command),
+
+        Commands::Heartbeat { heartbeat_command } => {
+            handle_heartbeat_command(&config, heartbeat_command).await
+        }
+
+        Commands::Link { link_command } => handle_link_command(&config, link_command),
+
+        Commands::Hooks { hooks_command } => handle_hooks_command(&config, hooks_command),
+
+        Commands::Uninstall {
+            yes,
+            keep_memory,
+            keep_skills,
+            allow_outside_home,
+        } => uninstall::handle_command(
+            &config,
+            uninstall::UninstallOptions {
+                keep_memory,
+                keep_skills,
+            },
+            yes,
+            allow_outside_home,
+        ),
+
         Commands::Cron { cron_command } => cron::handle_command(cron_command, &config),
 
         Commands::Models { model_command } => match model_command {
@@ -1052,7 +1414,36 @@ async fn main() -> Result<()> {
             ModelCommands::Status => onboard::run_models_status(&config).await,
         },
 
-        Commands::Providers => {
+        Commands::Providers {
+            provider_command:
+                Some(ProviderCommands::Benchmark {
+                    providers,
+                    model,
+                    prompt,
+                    runs,
+                    warmup,
+                }),
+        } => {
+            let Some(model) = model.or_else(|| config.default_model.clone()) else {
+                bail!("no model given: pass --model or set default_model in config");
+            };
+            let options = providers::benchmark::BenchmarkOptions {
+                providers: providers
+                    .iter()
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect(),
+                model,
+                prompt,
+                runs,
+                warmup,
+            };
+            providers::benchmark::run(&config, &options).await
+        }
+
+        Commands::Providers {
+            provider_command: None,
+        } => {
             let providers = providers::list_providers();
             let current = config
                 .default_provider
@@ -1114,7 +1505,25 @@ async fn main() -> Result<()> {
         },
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
+            ChannelCommands::Start {
+                only,
+                except,
+                debug,
+                output,
+                takeover,
+            } => {
+                channels::start_channels_with_options(
+                    config,
+                    channels::ChannelStartOptions {
+                        selection: channels::ChannelSelection { only, except },
+                        debug,
+                        output,
+                        heartbeat: true,
+                        takeover,
+                    },
+                )
+                .await
+            }
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
             other => channels::handle_command(other, &config).await,
         },
@@ -1125,6 +1534,10 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::Workspace { workspace_command } => {
+            workspace::handle_command(workspace_command, &config).await
+        }
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
@@ -1144,12 +1557,57 @@ async fn main() -> Result<()> {
         }
 
         Commands::Config { config_command } => match config_command {
-            ConfigCommands::Schema => {
-                let schema = schemars::schema_for!(config::Config);
+            ConfigCommands::Schema { path } => {
+                let schema = config::json_schema::section_schema(
+                    &config::json_schema::config_schema(),
+                    path.as_deref().unwrap_or_default(),
+                )?;
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                Ok(())
+            }
+            ConfigCommands::Validate => {
+                config.validate()?;
+                providers::validate_configured_providers(&config)?;
+                println!("✓ Configuration is valid: {}", config.config_path.display());
+                Ok(())
+            }
+            ConfigCommands::Diff => {
+                let entries = config::diff::diff_against_defaults(&config)?;
+                if entries.is_empty() {
+                    println!("Configuration matches built-in defaults.");
+                } else {
+                    print!("{}", config::diff::render_diff(&entries));
+                }
+                Ok(())
+            }
+        },
+
+        Commands::Secrets { secrets_command } => match secrets_command {
+            SecretsCommands::Migrate => {
+                let count = config::secrets::migrate_config_file(&config.config_path)?;
+                if count == 0 {
+                    println!(
+                        "No plaintext secrets found in {}",
+                        config.config_path.display()
+                    );
+                } else {
+                    println!(
+                        "✓ Encrypted {count} secret(s) in {}",
+                        config.config_path.display()
+                    );
+                }
+                Ok(())
+            }
+            SecretsCommands::Rotate => {
+                let summary = config::secrets::rotate_secrets(&config.config_path)?;
                 println!(
-                    "{}",
-                    serde_json::to_string_pretty(&schema).expect("failed to serialize JSON Schema")
+                    "✓ Rotated the secrets key; re-encrypted {} value(s) in {}",
+                    summary.config_values,
+                    config.config_path.display()
                 );
+                for path in &summary.other_files {
+                    println!("  also re-encrypted {}", path.display());
+                }
                 Ok(())
             }
         },
@@ -1289,6 +1747,133 @@ fn build_resume_selector(
     Ok(security::ResumeSelector::KillAll)
 }
 
+fn handle_maintenance_command(
+    config: &Config,
+    maintenance_command: MaintenanceSubcommands,
+) -> Result<()> {
+    let mode = maintenance::MaintenanceMode::from_config(config);
+    let status = match maintenance_command {
+        MaintenanceSubcommands::On { message } => mode.set(true, message)?,
+        MaintenanceSubcommands::Off => {
+            let status = mode.set(false, None)?;
+            if status.pinned_by_config {
+                println!(
+                    "Runtime toggle cleared, but [agent] maintenance = true in config.toml keeps maintenance on."
+                );
+            }
+            status
+        }
+        MaintenanceSubcommands::Status => mode.status(),
+    };
+    println!("Maintenance: {}", describe_maintenance(&status));
+    if status.active {
+        println!("Reply:       {}", mode.canned_reply().unwrap_or_default());
+    }
+    Ok(())
+}
+
+async fn handle_heartbeat_command(
+    config: &Config,
+    heartbeat_command: HeartbeatSubcommands,
+) -> Result<()> {
+    match heartbeat_command {
+        HeartbeatSubcommands::RunOnce => {
+            heartbeat::engine::HeartbeatEngine::ensure_heartbeat_file(&config.workspace_dir)
+                .await?;
+            let runner = heartbeat::runner::HeartbeatRunner::from_config(config).await?;
+            let run = runner.run_once().await;
+            println!("Heartbeat: {}", run.describe());
+            if run.outcome.is_error() {
+                bail!("heartbeat run {}", run.outcome);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn handle_link_command(config: &Config, link_command: LinkCommands) -> Result<()> {
+    let store = links::LinkStore::open(&config.workspace_dir)?;
+    match link_command {
+        LinkCommands::Create { mirrored } => {
+            let mode = if mirrored {
+                links::LinkMode::Mirrored
+            } else {
+                links::LinkMode::Merged
+            };
+            let code = store.create_code(mode, chrono::Utc::now().timestamp())?;
+            println!("Link code: {code}");
+            println!(
+                "Send it to the bot from the chat to link within {} minutes ({}, single use).",
+                links::CODE_TTL_SECS / 60,
+                mode.as_str()
+            );
+        }
+        LinkCommands::List => {
+            let linked = store.links()?;
+            if linked.is_empty() {
+                println!("No linked identities. Run `zeroclaw link create` to add one.");
+            }
+            for link in linked {
+                println!(
+                    "{:<32} {:<9} linked {}",
+                    link.identity,
+                    link.mode.as_str(),
+                    config::timezone::display(link.linked_at)
+                );
+            }
+        }
+        LinkCommands::Remove { identity } => {
+            if !store.remove(&identity)? {
+                bail!("{identity} is not linked (see `zeroclaw link list`)");
+            }
+            println!("Unlinked {identity}. Its later messages use their own history again.");
+        }
+    }
+    Ok(())
+}
+
+fn handle_hooks_command(config: &Config, hooks_command: HooksCommands) -> Result<()> {
+    match hooks_command {
+        HooksCommands::TestOutbound { text } => {
+            let filter = hooks::OutboundFilter::compile(&config.hooks.outbound)?;
+            if filter.is_empty() {
+                println!("No [hooks.outbound] rules configured; replies are sent unchanged.");
+            }
+            if !config.hooks.enabled {
+                println!(
+                    "Note: [hooks] enabled = false, so these rules are not applied at runtime."
+                );
+            }
+            let verdict = filter.apply(&text);
+            if verdict.blocked {
+                println!("Blocked:  yes");
+            }
+            if !verdict.flags.is_empty() {
+                println!("Matched:  {}", verdict.flags.join(", "));
+            }
+            if let Some(withheld) = &verdict.withheld {
+                println!("Withheld: {withheld}");
+            }
+            println!("Sent:     {}", verdict.text);
+            Ok(())
+        }
+    }
+}
+
+fn describe_maintenance(status: &maintenance::MaintenanceStatus) -> String {
+    if !status.active {
+        return "off".into();
+    }
+    let mut line = String::from("on");
+    if status.pinned_by_config {
+        line.push_str(" (pinned by [agent] maintenance)");
+    }
+    if let Some(updated_at) = &status.updated_at {
+        line.push_str(&format!(" since {updated_at}"));
+    }
+    line
+}
+
 fn print_estop_status(state: &security::EstopState) {
     println!("Estop status:");
     println!(
@@ -2075,6 +2660,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn quiet_is_a_global_flag() {
+        for args in [
+            ["zeroclaw", "--quiet", "gateway"].as_slice(),
+            ["zeroclaw", "channel", "start", "-q"].as_slice(),
+            ["zeroclaw", "skills", "install", "./my-skill", "--quiet"].as_slice(),
+        ] {
+            let cli = Cli::try_parse_from(args).expect("--quiet should parse anywhere");
+            assert!(cli.quiet, "{args:?}");
+        }
+        assert!(!Cli::try_parse_from(["zeroclaw", "gateway"]).unwrap().quiet);
+    }
+
     #[test]
     fn completion_generation_mentions_binary_name() {
         let mut output = Vec::new();
@@ -2142,4 +2740,63 @@ mod tests {
             other => panic!("expected estop resume command, got {other:?}"),
         }
     }
+
+    #[test]
+    fn cli_parses_maintenance_on_with_message() {
+        let cli =
+            Cli::try_parse_from(["zeroclaw", "maintenance", "on", "--message", "Back at 5pm"])
+                .expect("maintenance on command should parse");
+
+        match cli.command {
+            Commands::Maintenance {
+                maintenance_command: MaintenanceSubcommands::On { message },
+            } => assert_eq!(message.as_deref(), Some("Back at 5pm")),
+            other => panic!("expected maintenance on command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_heartbeat_run_once() {
+        let cli = Cli::try_parse_from(["zeroclaw", "heartbeat", "run-once"])
+            .expect("heartbeat run-once command should parse");
+
+        assert!(matches!(
+            cli.command,
+            Commands::Heartbeat {
+                heartbeat_command: HeartbeatSubcommands::RunOnce
+            }
+        ));
+    }
+
+    #[test]
+    fn cli_parses_uninstall_partial_modes() {
+        let cli = Cli::try_parse_from(["zeroclaw", "uninstall", "--keep-memory", "--yes"])
+            .expect("uninstall command should parse");
+
+        match cli.command {
+            Commands::Uninstall {
+                yes,
+                keep_memory,
+                keep_skills,
+                allow_outside_home,
+            } => {
+                assert!(yes && keep_memory);
+                assert!(!keep_skills && !allow_outside_home);
+            }
+            other => panic!("expected uninstall command, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn cli_parses_hooks_test_outbound() {
+        let cli = Cli::try_parse_from(["zeroclaw", "hooks", "test-outbound", "hello there"])
+            .expect("hooks test-outbound command should parse");
+
+        match cli.command {
+            Commands::Hooks {
+                hooks_command: HooksCommands::TestOutbound { text },
+            } => assert_eq!(text, "hello there"),
+            other => panic!("expected hooks test-outbound command, got {other:?}"),
+        }
+    }
 }
@@ -0,0 +1,396 @@
+//! Maintenance mode.
+//!
+//! While maintenance is active the gateway and channels answer every inbound
+//! message with a canned reply and make no provider calls. It is on when
+//! `[agent] maintenance = true` or when the runtime state file
+//! (`maintenance_state.json`, next to `config.toml`) says so. That file is
+//! what `zeroclaw maintenance on|off` and `POST /admin/maintenance` write;
+//! running instances notice changes to it on the next message or health check.
+//!
+//! With `[agent] queue_during_maintenance = true`, channel messages received
+//! meanwhile are written to `maintenance_queue.json` beside the state file and
+//! replayed once maintenance ends, including after a restart.
+//!
+//! Both files stand in for a control socket and an outbox, which zeroclaw
+//! does not have: they work whether or not an instance is running, and are
+//! shared by every process using the same config directory.
+
+use crate::channels::traits::ChannelMessage;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Runtime state file, relative to the config directory.
+pub const STATE_FILE: &str = "maintenance_state.json";
+
+/// Messages held for replay, in the same directory as [`STATE_FILE`].
+pub const QUEUE_FILE: &str = "maintenance_queue.json";
+
+/// Placeholder in `[agent] maintenance_reply` replaced by the operator's message.
+pub const MESSAGE_PLACEHOLDER: &str = "{message}";
+
+/// Upper bound on messages held while maintenance is active.
+const MAX_QUEUED_MESSAGES: usize = 500;
+
+/// Persisted runtime toggle.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceState {
+    #[serde(default)]
+    pub active: bool,
+    /// Operator note, e.g. "back at 5pm".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl MaintenanceState {
+    /// Read the state file; a missing file means "not in maintenance".
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("invalid {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))
+    }
+}
+
+/// Effective maintenance status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaintenanceStatus {
+    pub active: bool,
+    pub message: Option<String>,
+    /// Forced on by `[agent] maintenance = true`; runtime toggles cannot clear it.
+    pub pinned_by_config: bool,
+    pub updated_at: Option<String>,
+}
+
+/// Location of the runtime state file for `config`.
+pub fn state_path(config: &Config) -> PathBuf {
+    config
+        .config_path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(&config.workspace_dir)
+        .join(STATE_FILE)
+}
+
+/// Fill `template` with the operator message (or nothing).
+pub fn render_reply(template: &str, message: Option<&str>) -> String {
+    let message = message.map(str::trim).unwrap_or_default();
+    let rendered = template.replace(MESSAGE_PLACEHOLDER, message);
+    rendered.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct CachedState {
+    state: MaintenanceState,
+    stamp: Option<(SystemTime, u64)>,
+}
+
+/// Shared maintenance switch for one running instance.
+pub struct MaintenanceMode {
+    pinned: bool,
+    reply_template: String,
+    queue_enabled: bool,
+    state_path: PathBuf,
+    cached: Mutex<CachedState>,
+    queue_path: PathBuf,
+    /// Mirror of the queue file, written through on every change.
+    queued: Mutex<Vec<ChannelMessage>>,
+}
+
+impl MaintenanceMode {
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(
+            state_path(config),
+            config.agent.maintenance,
+            &config.agent.maintenance_reply,
+            config.agent.queue_during_maintenance,
+        )
+    }
+
+    pub fn new(state_path: PathBuf, pinned: bool, reply_template: &str, queue: bool) -> Self {
+        let queue_path = state_path.with_file_name(QUEUE_FILE);
+        let queued = if queue {
+            load_queue(&queue_path)
+        } else {
+            Vec::new()
+        };
+        if !queued.is_empty() {
+            tracing::info!(
+                count = queued.len(),
+                "Restored messages queued during maintenance"
+            );
+        }
+        let mode = Self {
+            pinned,
+            reply_template: reply_template.to_string(),
+            queue_enabled: queue,
+            state_path,
+            cached: Mutex::new(CachedState {
+                state: MaintenanceState::default(),
+                stamp: None,
+            }),
+            queue_path,
+            queued: Mutex::new(queued),
+        };
+        if pinned {
+            tracing::info!("Maintenance mode enabled by [agent] maintenance = true");
+        }
+        // Prime the cache so a state file left on disk is logged at startup.
+        mode.status();
+        mode
+    }
+
+    /// A switch that is off and backed by no state file.
+    pub fn disabled() -> Self {
+        Self::new(PathBuf::new(), false, "", false)
+    }
+
+    /// Current status, re-reading the state file if it changed on disk.
+    pub fn status(&self) -> MaintenanceStatus {
+        let mut cached = self.cached.lock();
+        let stamp = file_stamp(&self.state_path);
+        if stamp != cached.stamp {
+            let next = MaintenanceState::load(&self.state_path).unwrap_or_else(|err| {
+                tracing::warn!("ignoring maintenance state file: {err:#}");
+                cached.state.clone()
+            });
+            if next.active != cached.state.active {
+                log_change(next.active, next.message.as_deref());
+            }
+            cached.state = next;
+            cached.stamp = stamp;
+        }
+        MaintenanceStatus {
+            active: self.pinned || cached.state.active,
+            message: cached.state.message.clone(),
+            pinned_by_config: self.pinned,
+            updated_at: cached.state.updated_at.clone(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status().active
+    }
+
+    /// Turn the runtime toggle on or off and persist it for other processes.
+    pub fn set(&self, active: bool, message: Option<String>) -> Result<MaintenanceStatus> {
+        let state = MaintenanceState {
+            active,
+            message: message
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty()),
+            updated_at: Some(chrono::Utc::now().to_rfc3339()),
+        };
+        state.save(&self.state_path)?;
+        {
+            let mut cached = self.cached.lock();
+            if cached.state.active != active {
+                log_change(active, state.message.as_deref());
+            }
+            cached.stamp = file_stamp(&self.state_path);
+            cached.state = state;
+        }
+        Ok(self.status())
+    }
+
+    /// The canned reply to send instead of calling the provider, if active.
+    pub fn canned_reply(&self) -> Option<String> {
+        let status = self.status();
+        status
+            .active
+            .then(|| render_reply(&self.reply_template, status.message.as_deref()))
+    }
+
+    /// Hold `msg` for replay after maintenance. Returns whether it was queued.
+    pub fn enqueue(&self, msg: ChannelMessage) -> bool {
+        if !self.queue_enabled {
+            return false;
+        }
+        let mut queued = self.queued.lock();
+        if queued.len() >= MAX_QUEUED_MESSAGES {
+            tracing::warn!(
+                channel = %msg.channel,
+                "Maintenance queue full ({MAX_QUEUED_MESSAGES}); dropping message"
+            );
+            return false;
+        }
+        queued.push(msg);
+        self.save_queue(&queued);
+        true
+    }
+
+    /// Queued messages to replay, oldest first. Empty while still active.
+    pub fn take_queued(&self) -> Vec<ChannelMessage> {
+        if self.is_active() {
+            return Vec::new();
+        }
+        let mut queued = self.queued.lock();
+        if !queued.is_empty() {
+            self.save_queue(&[]);
+        }
+        std::mem::take(&mut *queued)
+    }
+
+    pub fn queued_len(&self) -> usize {
+        self.queued.lock().len()
+    }
+
+    /// Rewrite the queue file; an empty queue removes it. A failed write is
+    /// logged and the messages stay queued in memory.
+    fn save_queue(&self, queued: &[ChannelMessage]) {
+        let result = if queued.is_empty() {
+            match std::fs::remove_file(&self.queue_path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+                _ => Ok(()),
+            }
+        } else {
+            serde_json::to_string(queued)
+                .map_err(anyhow::Error::from)
+                .and_then(|json| {
+                    std::fs::write(&self.queue_path, json).map_err(anyhow::Error::from)
+                })
+        };
+        if let Err(err) = result {
+            tracing::warn!("failed to update {}: {err:#}", self.queue_path.display());
+        }
+    }
+}
+
+/// Messages left in the queue file by an earlier run; an unreadable file is
+/// logged and ignored.
+fn load_queue(path: &Path) -> Vec<ChannelMessage> {
+    let Ok(raw) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    serde_json::from_str(&raw).unwrap_or_else(|err| {
+        tracing::warn!("ignoring {}: {err}", path.display());
+        Vec::new()
+    })
+}
+
+/// Modification time and size, used to notice writes from other processes.
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    std::fs::metadata(path).ok().map(|meta| {
+        (
+            meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            meta.len(),
+        )
+    })
+}
+
+fn log_change(active: bool, message: Option<&str>) {
+    if active {
+        tracing::info!(message = message.unwrap_or(""), "Maintenance mode enabled");
+    } else {
+        tracing::info!("Maintenance mode disabled");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(id: &str) -> ChannelMessage {
+        ChannelMessage {
+            id: id.into(),
+            sender: "alice".into(),
+            reply_target: "alice".into(),
+            content: format!("hello {id}"),
+            channel: "test-channel".into(),
+            timestamp: 1,
+            thread_ts: None,
+        }
+    }
+
+    #[test]
+    fn render_reply_fills_optional_message() {
+        let template = "Down for maintenance. {message}";
+        assert_eq!(
+            render_reply(template, Some(" back at 5pm ")),
+            "Down for maintenance. back at 5pm"
+        );
+        assert_eq!(render_reply(template, None), "Down for maintenance.");
+    }
+
+    #[test]
+    fn runtime_toggle_is_shared_through_the_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        let gateway = MaintenanceMode::new(path.clone(), false, "Maintenance. {message}", false);
+        assert!(gateway.canned_reply().is_none());
+
+        // Another process (the CLI) flips the switch.
+        let cli = MaintenanceMode::new(path, false, "", false);
+        cli.set(true, Some("back at 5pm".into())).unwrap();
+
+        assert_eq!(
+            gateway.canned_reply().as_deref(),
+            Some("Maintenance. back at 5pm")
+        );
+        cli.set(false, None).unwrap();
+        assert!(!gateway.is_active());
+    }
+
+    #[test]
+    fn config_pin_cannot_be_cleared_at_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let mode = MaintenanceMode::new(dir.path().join(STATE_FILE), true, "x", false);
+        let status = mode.set(false, None).unwrap();
+        assert!(status.active);
+        assert!(status.pinned_by_config);
+    }
+
+    #[test]
+    fn queued_messages_are_released_only_after_maintenance() {
+        let dir = tempfile::tempdir().unwrap();
+        let mode = MaintenanceMode::new(dir.path().join(STATE_FILE), false, "x", true);
+        mode.set(true, None).unwrap();
+
+        assert!(mode.enqueue(message("1")));
+        assert!(mode.enqueue(message("2")));
+        assert!(mode.take_queued().is_empty(), "held while active");
+        assert_eq!(mode.queued_len(), 2);
+
+        mode.set(false, None).unwrap();
+        let drained: Vec<_> = mode.take_queued().into_iter().map(|m| m.id).collect();
+        assert_eq!(drained, ["1", "2"]);
+        assert_eq!(mode.queued_len(), 0);
+
+        let no_queue = MaintenanceMode::new(dir.path().join("other.json"), true, "x", false);
+        assert!(!no_queue.enqueue(message("3")));
+    }
+
+    #[test]
+    fn queued_messages_survive_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATE_FILE);
+        let queue_file = dir.path().join(QUEUE_FILE);
+        let before = MaintenanceMode::new(path.clone(), false, "x", true);
+        before.set(true, None).unwrap();
+        assert!(before.enqueue(message("1")));
+        assert!(before.enqueue(message("2")));
+        assert!(queue_file.is_file());
+        drop(before);
+
+        let after = MaintenanceMode::new(path, false, "x", true);
+        assert_eq!(after.queued_len(), 2);
+        after.set(false, None).unwrap();
+        let drained: Vec<_> = after.take_queued().into_iter().map(|m| m.content).collect();
+        assert_eq!(drained, ["hello 1", "hello 2"]);
+        assert!(!queue_file.exists(), "drained queue leaves no file behind");
+    }
+}
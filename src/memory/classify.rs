@@ -0,0 +1,162 @@
+//! Categorization of auto-saved messages.
+//!
+//! Channel and gateway auto-save run every user message through
+//! [`classify`]: the `[memory.classification]` marker table maps explicit
+//! phrasing ("remember that ...", "note: ...") to `fact`/`preference`/`task`,
+//! and everything else is `conversation` or, when very short, `ephemeral`.
+//! [`classify_with_model`] is the optional model-based fallback for messages
+//! no rule matched.
+
+use super::traits::MemoryCategory;
+use crate::config::MemoryClassificationConfig;
+use crate::providers::Provider;
+
+/// Category chosen for an auto-saved message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    pub category: MemoryCategory,
+    /// A marker rule matched, i.e. the user explicitly asked for this to be kept.
+    pub explicit: bool,
+}
+
+/// Categorize `message` with the configured marker table. No provider call.
+pub fn classify(config: &MemoryClassificationConfig, message: &str) -> Classification {
+    if !config.enabled {
+        return Classification {
+            category: MemoryCategory::Conversation,
+            explicit: false,
+        };
+    }
+
+    let lower = message.to_lowercase();
+    for rule in &config.rules {
+        let hit = rule
+            .markers
+            .iter()
+            .map(|marker| marker.trim().to_lowercase())
+            .any(|marker| !marker.is_empty() && lower.contains(&marker));
+        if hit {
+            return Classification {
+                category: MemoryCategory::from_name(&rule.category.trim().to_lowercase()),
+                explicit: true,
+            };
+        }
+    }
+
+    let category = if message.trim().chars().count() < config.ephemeral_max_chars {
+        MemoryCategory::Ephemeral
+    } else {
+        MemoryCategory::Conversation
+    };
+    Classification {
+        category,
+        explicit: false,
+    }
+}
+
+const MODEL_CLASSIFIER_PROMPT: &str = "Classify the user's message for long-term memory. \
+Answer with exactly one word: fact (something to remember), preference (how the user likes \
+things done), task (a todo or reminder), conversation (ordinary chat), or ephemeral \
+(small talk not worth keeping).";
+
+/// Ask `provider` to categorize `message`. Returns `None` when the call fails
+/// or the answer is not one of the built-in auto-save categories.
+pub async fn classify_with_model(
+    provider: &dyn Provider,
+    model: &str,
+    message: &str,
+) -> Option<MemoryCategory> {
+    let answer = match provider
+        .chat_with_system(Some(MODEL_CLASSIFIER_PROMPT), message, model, 0.0)
+        .await
+    {
+        Ok(answer) => answer,
+        Err(err) => {
+            tracing::debug!("memory model classifier failed: {err:#}");
+            return None;
+        }
+    };
+    let word = answer
+        .trim()
+        .trim_matches(|c: char| !c.is_ascii_alphabetic())
+        .to_ascii_lowercase();
+    match MemoryCategory::from_name(&word) {
+        category @ (MemoryCategory::Fact
+        | MemoryCategory::Preference
+        | MemoryCategory::Task
+        | MemoryCategory::Conversation
+        | MemoryCategory::Ephemeral) => Some(category),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MemoryClassificationRule;
+
+    fn category(message: &str) -> MemoryCategory {
+        classify(&MemoryClassificationConfig::default(), message).category
+    }
+
+    #[test]
+    fn default_markers_map_to_categories() {
+        assert_eq!(
+            category("Remember that my sister's birthday is March 3rd"),
+            MemoryCategory::Fact
+        );
+        assert_eq!(
+            category("note: the wifi password is hunter2"),
+            MemoryCategory::Fact
+        );
+        assert_eq!(
+            category("Remember that I prefer metric units in every answer"),
+            MemoryCategory::Preference
+        );
+        assert_eq!(
+            category("todo: renew the domain before Friday"),
+            MemoryCategory::Task
+        );
+        assert!(classify(&MemoryClassificationConfig::default(), "note: hi").explicit);
+    }
+
+    #[test]
+    fn unmatched_messages_are_conversation_or_ephemeral_by_length() {
+        assert_eq!(category("ok thanks!"), MemoryCategory::Ephemeral);
+        assert_eq!(
+            category("Can you walk me through how the scheduler decides which job runs first?"),
+            MemoryCategory::Conversation
+        );
+        let result = classify(&MemoryClassificationConfig::default(), "ok thanks!");
+        assert!(!result.explicit);
+    }
+
+    #[test]
+    fn custom_rules_replace_defaults_and_disabled_is_conversation() {
+        let config = MemoryClassificationConfig {
+            rules: vec![MemoryClassificationRule {
+                category: "Recipe".into(),
+                markers: vec!["ingredients:".into()],
+            }],
+            ephemeral_max_chars: 0,
+            ..MemoryClassificationConfig::default()
+        };
+        assert_eq!(
+            classify(&config, "Ingredients: flour, eggs").category,
+            MemoryCategory::Custom("recipe".into())
+        );
+        assert_eq!(
+            classify(&config, "remember that x").category,
+            MemoryCategory::Conversation
+        );
+
+        let disabled = MemoryClassificationConfig {
+            enabled: false,
+            ..MemoryClassificationConfig::default()
+        };
+        assert_eq!(
+            classify(&disabled, "remember that x").category,
+            MemoryCategory::Conversation
+        );
+    }
+}
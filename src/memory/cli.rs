@@ -1,3 +1,4 @@
+use super::tombstone::{self, soft_forget_all, RestoreTarget, Tombstones};
 use super::traits::{Memory, MemoryCategory};
 use super::{
     classify_memory_backend, create_memory_for_migration, effective_memory_backend_name,
@@ -23,12 +24,13 @@ pub async fn handle_command(command: crate::MemoryCommands, config: &Config) ->
         crate::MemoryCommands::Clear { key, category, yes } => {
             handle_clear(config, key, category, yes).await
         }
+        crate::MemoryCommands::Restore { id, last } => handle_restore(config, id, last).await,
     }
 }
 
 /// Create a lightweight memory backend for CLI management operations.
 ///
-/// CLI commands (list/get/stats/clear) never use vector search, so we skip
+/// CLI commands (list/get/stats/clear/restore) never use vector search, so we skip
 /// embedding provider initialisation for local backends by using the
 /// migration factory.  Postgres still needs its full connection config.
 fn create_cli_memory(config: &Config) -> Result<Box<dyn Memory>> {
@@ -157,7 +159,10 @@ async fn handle_get(config: &Config, key: &str) -> Result<()> {
 fn print_entry(entry: &super::traits::MemoryEntry) {
     println!("Key:       {}", style(&entry.key).white().bold());
     println!("Category:  {}", entry.category);
-    println!("Timestamp: {}", entry.timestamp);
+    println!(
+        "Timestamp: {}",
+        crate::config::timezone::display_stored(&entry.timestamp)
+    );
     if let Some(sid) = &entry.session_id {
         println!("Session:   {sid}");
     }
@@ -209,7 +214,7 @@ async fn handle_clear(
 
     // Single-key deletion (exact or prefix match).
     if let Some(key) = key {
-        return handle_clear_key(&*mem, &key, yes).await;
+        return handle_clear_key(config, &*mem, &key, yes).await;
     }
 
     // Batch deletion by category (or all).
@@ -235,24 +240,21 @@ async fn handle_clear(
         }
     }
 
-    let mut deleted = 0usize;
-    for entry in &entries {
-        if mem.forget(&entry.key).await? {
-            deleted += 1;
-        }
-    }
+    let keys: Vec<String> = entries.iter().map(|e| e.key.clone()).collect();
+    let (batch_id, deleted) = soft_forget_all(&*mem, &cli_tombstones(config), &keys).await?;
 
     println!(
         "{} Cleared {deleted}/{} entries.",
         style("✓").green().bold(),
         entries.len(),
     );
+    print_undo_hint(config, &batch_id);
 
     Ok(())
 }
 
 /// Delete a single entry by exact key or prefix match.
-async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()> {
+async fn handle_clear_key(config: &Config, mem: &dyn Memory, key: &str, yes: bool) -> Result<()> {
     // Resolve the target key (exact match or unique prefix).
     let target = if mem.get(key).await?.is_some() {
         key.to_string()
@@ -291,20 +293,85 @@ async fn handle_clear_key(mem: &dyn Memory, key: &str, yes: bool) -> Result<()>
         }
     }
 
-    if mem.forget(&target).await? {
+    let (batch_id, deleted) =
+        soft_forget_all(mem, &cli_tombstones(config), std::slice::from_ref(&target)).await?;
+    if deleted > 0 {
         println!("{} Deleted key: {target}", style("✓").green().bold());
+        print_undo_hint(config, &batch_id);
     }
 
     Ok(())
 }
 
-fn parse_category(s: &str) -> MemoryCategory {
-    match s.trim().to_ascii_lowercase().as_str() {
-        "core" => MemoryCategory::Core,
-        "daily" => MemoryCategory::Daily,
-        "conversation" => MemoryCategory::Conversation,
-        other => MemoryCategory::Custom(other.to_string()),
+fn cli_tombstones(config: &Config) -> Tombstones {
+    Tombstones::new(&config.workspace_dir, config.memory.undo_retention_days)
+}
+
+fn print_undo_hint(config: &Config, batch_id: &str) {
+    println!(
+        "  Restore within {} days with: zeroclaw memory restore {batch_id}",
+        config.memory.undo_retention_days
+    );
+}
+
+async fn handle_restore(config: &Config, id: Option<String>, last: bool) -> Result<()> {
+    let tombstones = cli_tombstones(config);
+    let target = match (id, last) {
+        (Some(id), _) => RestoreTarget::Id(id.trim().to_string()),
+        (None, true) => RestoreTarget::Last,
+        (None, false) => return list_tombstones(&tombstones),
+    };
+
+    let mem = create_cli_memory(config)?;
+    let report = tombstone::restore(&*mem, &tombstones, &target).await?;
+
+    if report.restored.is_empty() && report.conflicts.is_empty() {
+        match target {
+            RestoreTarget::Last => println!("Nothing to restore."),
+            RestoreTarget::Id(id) => bail!(
+                "No forgotten memory with id '{id}'. Run 'zeroclaw memory restore' to list them."
+            ),
+        }
+        return Ok(());
+    }
+
+    for key in &report.restored {
+        println!("{} Restored key: {key}", style("✓").green().bold());
+    }
+    for key in &report.conflicts {
+        println!(
+            "{} Skipped key: {key} (a memory with this key exists again; forget it first to restore)",
+            style("!").yellow().bold()
+        );
+    }
+    Ok(())
+}
+
+fn list_tombstones(tombstones: &Tombstones) -> Result<()> {
+    let recent = tombstones.list(50)?;
+    if recent.is_empty() {
+        println!("No forgotten memories to restore.");
+        return Ok(());
     }
+
+    println!("Recently forgotten memories (newest first):\n");
+    for tombstone in &recent {
+        let deleted_at = chrono::DateTime::from_timestamp(tombstone.deleted_at, 0)
+            .map(crate::config::timezone::display)
+            .unwrap_or_default();
+        println!(
+            "- {} [{}] {deleted_at}",
+            style(&tombstone.entry.key).white().bold(),
+            tombstone.entry.category,
+        );
+        println!("    id: {}  batch: {}", tombstone.id, tombstone.batch_id);
+    }
+    println!("\nRestore one entry or a whole batch with 'zeroclaw memory restore <id>', or the latest batch with --last.");
+    Ok(())
+}
+
+fn parse_category(s: &str) -> MemoryCategory {
+    MemoryCategory::from_name(&s.trim().to_ascii_lowercase())
 }
 
 fn truncate_content(s: &str, max_len: usize) -> String {
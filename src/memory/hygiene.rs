@@ -3,6 +3,7 @@ use anyhow::Result;
 use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration as StdDuration, SystemTime};
@@ -16,7 +17,9 @@ struct HygieneReport {
     archived_session_files: u64,
     purged_memory_archives: u64,
     purged_session_archives: u64,
-    pruned_conversation_rows: u64,
+    /// Rows removed by per-category retention (conversation, ephemeral, ...)
+    #[serde(alias = "pruned_conversation_rows")]
+    pruned_memory_rows: u64,
 }
 
 impl HygieneReport {
@@ -25,7 +28,7 @@ impl HygieneReport {
             + self.archived_session_files
             + self.purged_memory_archives
             + self.purged_session_archives
-            + self.pruned_conversation_rows
+            + self.pruned_memory_rows
     }
 }
 
@@ -55,22 +58,19 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         archived_session_files: archive_session_files(workspace_dir, config.archive_after_days)?,
         purged_memory_archives: purge_memory_archives(workspace_dir, config.purge_after_days)?,
         purged_session_archives: purge_session_archives(workspace_dir, config.purge_after_days)?,
-        pruned_conversation_rows: prune_conversation_rows(
-            workspace_dir,
-            config.conversation_retention_days,
-        )?,
+        pruned_memory_rows: prune_category_rows(workspace_dir, &category_retention(config))?,
     };
 
     write_state(workspace_dir, &report)?;
 
     if report.total_actions() > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_memory_rows={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
-            report.pruned_conversation_rows,
+            report.pruned_memory_rows,
         );
     }
 
@@ -295,8 +295,26 @@ fn purge_session_archives(workspace_dir: &Path, purge_after_days: u32) -> Result
     Ok(removed)
 }
 
-fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<u64> {
-    if retention_days == 0 {
+/// Retention in days per category: `conversation_retention_days` for
+/// `conversation`, overridden and extended by `category_retention_days`.
+/// Categories without an entry (core, fact, preference, ...) are kept forever.
+fn category_retention(config: &MemoryConfig) -> BTreeMap<String, u32> {
+    let mut retention = BTreeMap::from([(
+        "conversation".to_string(),
+        config.conversation_retention_days,
+    )]);
+    retention.extend(
+        config
+            .category_retention_days
+            .iter()
+            .map(|(category, days)| (category.trim().to_lowercase(), *days)),
+    );
+    retention.retain(|_, days| *days > 0);
+    retention
+}
+
+fn prune_category_rows(workspace_dir: &Path, retention: &BTreeMap<String, u32>) -> Result<u64> {
+    if retention.is_empty() {
         return Ok(0);
     }
 
@@ -308,14 +326,18 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     let conn = Connection::open(db_path)?;
     // Use WAL so hygiene pruning doesn't block agent reads
     conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
-    let cutoff = (Local::now() - Duration::days(i64::from(retention_days))).to_rfc3339();
 
-    let affected = conn.execute(
-        "DELETE FROM memories WHERE category = 'conversation' AND updated_at < ?1",
-        params![cutoff],
-    )?;
+    let mut pruned = 0_u64;
+    for (category, days) in retention {
+        let cutoff = (Local::now() - Duration::days(i64::from(*days))).to_rfc3339();
+        let affected = conn.execute(
+            "DELETE FROM memories WHERE category = ?1 AND updated_at < ?2",
+            params![category, cutoff],
+        )?;
+        pruned += u64::try_from(affected).unwrap_or(0);
+    }
 
-    Ok(u64::try_from(affected).unwrap_or(0))
+    Ok(pruned)
 }
 
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
@@ -539,4 +561,57 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn ephemeral_rows_are_pruned_aggressively_while_facts_are_kept() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+
+        let mem = SqliteMemory::new(workspace).unwrap();
+        for (key, category) in [
+            ("chatter", MemoryCategory::Ephemeral),
+            ("birthday", MemoryCategory::Fact),
+            ("units", MemoryCategory::Preference),
+            ("recent_conv", MemoryCategory::Conversation),
+        ] {
+            mem.store(key, "content", category, None).await.unwrap();
+        }
+        drop(mem);
+
+        let conn = Connection::open(workspace.join("memory").join("brain.db")).unwrap();
+        let three_days_ago = (Local::now() - Duration::days(3)).to_rfc3339();
+        conn.execute(
+            "UPDATE memories SET created_at = ?1, updated_at = ?1",
+            params![three_days_ago],
+        )
+        .unwrap();
+        drop(conn);
+
+        let mut cfg = default_cfg();
+        cfg.archive_after_days = 0;
+        cfg.purge_after_days = 0;
+        cfg.conversation_retention_days = 30;
+        assert_eq!(cfg.category_retention_days.get("ephemeral"), Some(&2));
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let mem2 = SqliteMemory::new(workspace).unwrap();
+        assert!(mem2.get("chatter").await.unwrap().is_none());
+        assert!(mem2.get("birthday").await.unwrap().is_some());
+        assert!(mem2.get("units").await.unwrap().is_some());
+        assert!(mem2.get("recent_conv").await.unwrap().is_some());
+    }
+
+    #[test]
+    fn category_retention_overrides_conversation_default() {
+        let mut cfg = default_cfg();
+        cfg.conversation_retention_days = 30;
+        cfg.category_retention_days =
+            BTreeMap::from([("Conversation".to_string(), 7), ("fact".to_string(), 0)]);
+
+        let retention = category_retention(&cfg);
+        assert_eq!(retention.get("conversation"), Some(&7));
+        assert!(!retention.contains_key("fact"), "0 disables pruning");
+        assert!(!retention.contains_key("ephemeral"));
+    }
 }
@@ -134,10 +134,10 @@ impl LucidMemory {
 
     fn to_lucid_type(category: &MemoryCategory) -> &'static str {
         match category {
-            MemoryCategory::Core => "decision",
-            MemoryCategory::Daily => "context",
-            MemoryCategory::Conversation => "conversation",
-            MemoryCategory::Custom(_) => "learning",
+            MemoryCategory::Core | MemoryCategory::Preference => "decision",
+            MemoryCategory::Daily | MemoryCategory::Task => "context",
+            MemoryCategory::Conversation | MemoryCategory::Ephemeral => "conversation",
+            MemoryCategory::Fact | MemoryCategory::Custom(_) => "learning",
         }
     }
 
@@ -1,6 +1,6 @@
 use super::traits::{Memory, MemoryCategory, MemoryEntry};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::Utc;
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
@@ -29,7 +29,10 @@ impl MarkdownMemory {
     }
 
     fn daily_path(&self) -> PathBuf {
-        let date = Local::now().format("%Y-%m-%d").to_string();
+        let date =
+            crate::config::timezone::local_date(Utc::now(), crate::config::timezone::effective())
+                .format("%Y-%m-%d")
+                .to_string();
         self.memory_dir().join(format!("{date}.md"))
     }
 
@@ -51,7 +54,12 @@ impl MarkdownMemory {
             let header = if path == self.core_path() {
                 "# Long-Term Memory\n\n"
             } else {
-                let date = Local::now().format("%Y-%m-%d").to_string();
+                let date = crate::config::timezone::local_date(
+                    Utc::now(),
+                    crate::config::timezone::effective(),
+                )
+                .format("%Y-%m-%d")
+                .to_string();
                 &format!("# Daily Log — {date}\n\n")
             };
             format!("{header}{content}\n")
@@ -147,7 +155,9 @@ impl Memory for MarkdownMemory {
     ) -> anyhow::Result<()> {
         let entry = format!("- **{key}**: {content}");
         let path = match category {
-            MemoryCategory::Core => self.core_path(),
+            MemoryCategory::Core | MemoryCategory::Fact | MemoryCategory::Preference => {
+                self.core_path()
+            }
             _ => self.daily_path(),
         };
         self.append_to_file(&path, &entry).await
@@ -1,5 +1,6 @@
 pub mod backend;
 pub mod chunker;
+pub mod classify;
 pub mod cli;
 pub mod embeddings;
 pub mod hybrid;
@@ -13,6 +14,7 @@ pub mod qdrant;
 pub mod response_cache;
 pub mod snapshot;
 pub mod sqlite;
+pub mod tombstone;
 pub mod traits;
 pub mod vector;
 
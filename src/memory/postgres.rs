@@ -183,21 +183,11 @@ impl PostgresMemory {
     }
 
     fn category_to_str(category: &MemoryCategory) -> String {
-        match category {
-            MemoryCategory::Core => "core".to_string(),
-            MemoryCategory::Daily => "daily".to_string(),
-            MemoryCategory::Conversation => "conversation".to_string(),
-            MemoryCategory::Custom(name) => name.clone(),
-        }
+        category.to_string()
     }
 
     fn parse_category(value: &str) -> MemoryCategory {
-        match value {
-            "core" => MemoryCategory::Core,
-            "daily" => MemoryCategory::Daily,
-            "conversation" => MemoryCategory::Conversation,
-            other => MemoryCategory::Custom(other.to_string()),
-        }
+        MemoryCategory::from_name(value)
     }
 
     fn row_to_entry(row: &Row) -> Result<MemoryEntry> {
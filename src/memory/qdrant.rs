@@ -161,21 +161,11 @@ impl QdrantMemory {
     }
 
     fn category_to_str(category: &MemoryCategory) -> String {
-        match category {
-            MemoryCategory::Core => "core".to_string(),
-            MemoryCategory::Daily => "daily".to_string(),
-            MemoryCategory::Conversation => "conversation".to_string(),
-            MemoryCategory::Custom(name) => name.clone(),
-        }
+        category.to_string()
     }
 
     fn parse_category(value: &str) -> MemoryCategory {
-        match value {
-            "core" => MemoryCategory::Core,
-            "daily" => MemoryCategory::Daily,
-            "conversation" => MemoryCategory::Conversation,
-            other => MemoryCategory::Custom(other.to_string()),
-        }
+        MemoryCategory::from_name(value)
     }
 }
 
@@ -187,21 +187,11 @@ impl SqliteMemory {
     }
 
     fn category_to_str(cat: &MemoryCategory) -> String {
-        match cat {
-            MemoryCategory::Core => "core".into(),
-            MemoryCategory::Daily => "daily".into(),
-            MemoryCategory::Conversation => "conversation".into(),
-            MemoryCategory::Custom(name) => name.clone(),
-        }
+        cat.to_string()
     }
 
     fn str_to_category(s: &str) -> MemoryCategory {
-        match s {
-            "core" => MemoryCategory::Core,
-            "daily" => MemoryCategory::Daily,
-            "conversation" => MemoryCategory::Conversation,
-            other => MemoryCategory::Custom(other.to_string()),
-        }
+        MemoryCategory::from_name(s)
     }
 
     /// Deterministic content hash for embedding cache.
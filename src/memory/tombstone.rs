@@ -0,0 +1,351 @@
+//! Soft deletion for memories.
+//!
+//! Forgetting a memory moves a copy of the entry into a tombstone table
+//! (`memory/tombstones.db`) before removing it from the active backend, so
+//! `zeroclaw memory restore` can bring it back. Entries deleted together share
+//! a batch id; tombstones older than `[memory] undo_retention_days` are purged
+//! on the next deletion. Works on top of any [`Memory`] backend.
+
+use super::traits::{Memory, MemoryEntry};
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// A forgotten memory that can still be restored.
+#[derive(Debug, Clone)]
+pub struct Tombstone {
+    pub id: String,
+    /// Shared by every entry removed in the same operation.
+    pub batch_id: String,
+    pub entry: MemoryEntry,
+    /// Unix timestamp (seconds) of the deletion.
+    pub deleted_at: i64,
+}
+
+/// What `restore` should bring back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreTarget {
+    /// The most recent deletion batch.
+    Last,
+    /// A single tombstone id, or a batch id (restores the whole batch).
+    Id(String),
+}
+
+/// Outcome of a restore.
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    pub restored: Vec<String>,
+    /// Keys left in the tombstone table because a live memory now uses them.
+    pub conflicts: Vec<String>,
+}
+
+/// Tombstone table for one workspace.
+#[derive(Debug, Clone)]
+pub struct Tombstones {
+    db_path: PathBuf,
+    retention_days: u32,
+}
+
+impl Tombstones {
+    pub fn new(workspace_dir: &Path, retention_days: u32) -> Self {
+        Self {
+            db_path: workspace_dir.join("memory").join("tombstones.db"),
+            retention_days,
+        }
+    }
+
+    fn open(&self) -> Result<Connection> {
+        if let Some(parent) = self.db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&self.db_path)
+            .with_context(|| format!("failed to open {}", self.db_path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS tombstones (
+                id          TEXT PRIMARY KEY,
+                batch_id    TEXT NOT NULL,
+                key         TEXT NOT NULL,
+                entry       TEXT NOT NULL,
+                deleted_at  INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_tombstones_batch ON tombstones(batch_id);
+            CREATE INDEX IF NOT EXISTS idx_tombstones_deleted ON tombstones(deleted_at);",
+        )?;
+        Ok(conn)
+    }
+
+    /// Drop tombstones past the retention window. Returns how many were purged.
+    pub fn purge_expired(&self) -> Result<usize> {
+        let cutoff = chrono::Utc::now().timestamp() - i64::from(self.retention_days) * 24 * 60 * 60;
+        let conn = self.open()?;
+        Ok(conn.execute(
+            "DELETE FROM tombstones WHERE deleted_at < ?1",
+            params![cutoff],
+        )?)
+    }
+
+    fn bury(&self, batch_id: &str, entry: &MemoryEntry) -> Result<String> {
+        let id = Uuid::new_v4().to_string();
+        let json = serde_json::to_string(entry).context("failed to serialize memory entry")?;
+        self.open()?.execute(
+            "INSERT INTO tombstones (id, batch_id, key, entry, deleted_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                id,
+                batch_id,
+                entry.key,
+                json,
+                chrono::Utc::now().timestamp()
+            ],
+        )?;
+        Ok(id)
+    }
+
+    fn discard(&self, id: &str) -> Result<()> {
+        self.open()?
+            .execute("DELETE FROM tombstones WHERE id = ?1", params![id])?;
+        Ok(())
+    }
+
+    /// Most recent tombstones first.
+    pub fn list(&self, limit: usize) -> Result<Vec<Tombstone>> {
+        let conn = self.open()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, batch_id, entry, deleted_at FROM tombstones
+             ORDER BY deleted_at DESC, rowid DESC LIMIT ?1",
+        )?;
+        let rows = stmt.query_map(
+            params![i64::try_from(limit).unwrap_or(i64::MAX)],
+            tombstone_row,
+        )?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(decode(row?)?);
+        }
+        Ok(out)
+    }
+
+    fn select(&self, target: &RestoreTarget) -> Result<Vec<Tombstone>> {
+        let conn = self.open()?;
+        let id = match target {
+            RestoreTarget::Id(id) => id.clone(),
+            RestoreTarget::Last => {
+                let last: Option<String> = conn
+                    .query_row(
+                        "SELECT batch_id FROM tombstones ORDER BY deleted_at DESC, rowid DESC LIMIT 1",
+                        [],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                match last {
+                    Some(batch_id) => batch_id,
+                    None => return Ok(Vec::new()),
+                }
+            }
+        };
+        let mut stmt = conn.prepare(
+            "SELECT id, batch_id, entry, deleted_at FROM tombstones
+             WHERE id = ?1 OR batch_id = ?1 ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![id], tombstone_row)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(decode(row?)?);
+        }
+        Ok(out)
+    }
+}
+
+type TombstoneRow = (String, String, String, i64);
+
+fn tombstone_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<TombstoneRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+}
+
+fn decode((id, batch_id, entry, deleted_at): TombstoneRow) -> Result<Tombstone> {
+    let entry: MemoryEntry =
+        serde_json::from_str(&entry).with_context(|| format!("corrupt tombstone {id}"))?;
+    Ok(Tombstone {
+        id,
+        batch_id,
+        entry,
+        deleted_at,
+    })
+}
+
+/// New id grouping the entries of one forget operation.
+fn new_batch_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Soft-delete `key`: tombstone the entry, then remove it from `mem`.
+/// Returns false (and records nothing) if no such memory exists.
+async fn soft_forget(
+    mem: &dyn Memory,
+    tombstones: &Tombstones,
+    batch_id: &str,
+    key: &str,
+) -> Result<bool> {
+    let Some(entry) = mem.get(key).await? else {
+        return Ok(false);
+    };
+    let id = tombstones.bury(batch_id, &entry)?;
+    match mem.forget(key).await {
+        Ok(true) => Ok(true),
+        Ok(false) => {
+            tombstones.discard(&id)?;
+            Ok(false)
+        }
+        Err(err) => {
+            tombstones.discard(&id)?;
+            Err(err)
+        }
+    }
+}
+
+/// Soft-delete every key in `keys` as one batch. Returns the batch id and the
+/// number of entries actually removed.
+pub async fn soft_forget_all(
+    mem: &dyn Memory,
+    tombstones: &Tombstones,
+    keys: &[String],
+) -> Result<(String, usize)> {
+    if let Err(err) = tombstones.purge_expired() {
+        tracing::warn!("memory tombstone purge failed: {err:#}");
+    }
+    let batch_id = new_batch_id();
+    let mut removed = 0usize;
+    for key in keys {
+        if soft_forget(mem, tombstones, &batch_id, key).await? {
+            removed += 1;
+        }
+    }
+    Ok((batch_id, removed))
+}
+
+/// Put tombstoned entries back into `mem`. Entries whose key is live again
+/// are left in place (reported as conflicts) rather than overwritten.
+pub async fn restore(
+    mem: &dyn Memory,
+    tombstones: &Tombstones,
+    target: &RestoreTarget,
+) -> Result<RestoreReport> {
+    let mut report = RestoreReport::default();
+    for tombstone in tombstones.select(target)? {
+        let entry = &tombstone.entry;
+        if mem.get(&entry.key).await?.is_some() {
+            report.conflicts.push(entry.key.clone());
+            continue;
+        }
+        mem.store(
+            &entry.key,
+            &entry.content,
+            entry.category.clone(),
+            entry.session_id.as_deref(),
+        )
+        .await?;
+        tombstones.discard(&tombstone.id)?;
+        report.restored.push(entry.key.clone());
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+
+    async fn seeded(dir: &Path, keys: &[&str]) -> SqliteMemory {
+        let mem = SqliteMemory::new(dir).unwrap();
+        for key in keys {
+            mem.store(
+                key,
+                &format!("content of {key}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        mem
+    }
+
+    #[tokio::test]
+    async fn restore_last_brings_back_the_latest_batch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mem = seeded(tmp.path(), &["a", "b", "c"]).await;
+        let tombstones = Tombstones::new(tmp.path(), 7);
+
+        soft_forget_all(&mem, &tombstones, &["a".into()])
+            .await
+            .unwrap();
+        let (_, removed) =
+            soft_forget_all(&mem, &tombstones, &["b".into(), "c".into(), "x".into()])
+                .await
+                .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(mem.count().await.unwrap(), 0);
+
+        let report = restore(&mem, &tombstones, &RestoreTarget::Last)
+            .await
+            .unwrap();
+        assert_eq!(report.restored, ["b", "c"]);
+        assert_eq!(mem.get("b").await.unwrap().unwrap().content, "content of b");
+        assert!(mem.get("a").await.unwrap().is_none());
+
+        let remaining = tombstones.list(10).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].entry.key, "a");
+    }
+
+    #[tokio::test]
+    async fn restore_by_id_skips_keys_that_are_live_again() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mem = seeded(tmp.path(), &["a"]).await;
+        let tombstones = Tombstones::new(tmp.path(), 7);
+
+        soft_forget_all(&mem, &tombstones, &["a".into()])
+            .await
+            .unwrap();
+        let id = tombstones.list(1).unwrap()[0].id.clone();
+        mem.store("a", "newer", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let report = restore(&mem, &tombstones, &RestoreTarget::Id(id.clone()))
+            .await
+            .unwrap();
+        assert!(report.restored.is_empty());
+        assert_eq!(report.conflicts, ["a"]);
+        assert_eq!(mem.get("a").await.unwrap().unwrap().content, "newer");
+
+        mem.forget("a").await.unwrap();
+        let report = restore(&mem, &tombstones, &RestoreTarget::Id(id))
+            .await
+            .unwrap();
+        assert_eq!(report.restored, ["a"]);
+        assert_eq!(mem.get("a").await.unwrap().unwrap().content, "content of a");
+    }
+
+    #[tokio::test]
+    async fn expired_tombstones_are_purged() {
+        let tmp = tempfile::tempdir().unwrap();
+        let mem = seeded(tmp.path(), &["a"]).await;
+        let tombstones = Tombstones::new(tmp.path(), 7);
+        soft_forget_all(&mem, &tombstones, &["a".into()])
+            .await
+            .unwrap();
+
+        tombstones
+            .open()
+            .unwrap()
+            .execute(
+                "UPDATE tombstones SET deleted_at = deleted_at - 8 * 86400",
+                [],
+            )
+            .unwrap();
+        assert_eq!(tombstones.purge_expired().unwrap(), 1);
+        assert!(tombstones.list(10).unwrap().is_empty());
+    }
+}
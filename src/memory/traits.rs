@@ -36,16 +36,56 @@ pub enum MemoryCategory {
     Daily,
     /// Conversation context
     Conversation,
+    /// Something the user explicitly asked to be remembered ("remember that ...")
+    Fact,
+    /// A stated user preference ("I prefer ...")
+    Preference,
+    /// A task or reminder ("todo: ...")
+    Task,
+    /// Short-lived chatter, pruned aggressively by hygiene
+    Ephemeral,
     /// User-defined custom category
     Custom(String),
 }
 
+impl MemoryCategory {
+    /// Names of the built-in categories, as exposed in tool schemas.
+    pub const BUILTIN_NAMES: [&'static str; 7] = [
+        "core",
+        "daily",
+        "conversation",
+        "fact",
+        "preference",
+        "task",
+        "ephemeral",
+    ];
+
+    /// Parse a stored or user-supplied category name. Unknown names become
+    /// [`MemoryCategory::Custom`].
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "core" => Self::Core,
+            "daily" => Self::Daily,
+            "conversation" => Self::Conversation,
+            "fact" => Self::Fact,
+            "preference" => Self::Preference,
+            "task" => Self::Task,
+            "ephemeral" => Self::Ephemeral,
+            other => Self::Custom(other.to_string()),
+        }
+    }
+}
+
 impl std::fmt::Display for MemoryCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Core => write!(f, "core"),
             Self::Daily => write!(f, "daily"),
             Self::Conversation => write!(f, "conversation"),
+            Self::Fact => write!(f, "fact"),
+            Self::Preference => write!(f, "preference"),
+            Self::Task => write!(f, "task"),
+            Self::Ephemeral => write!(f, "ephemeral"),
             Self::Custom(name) => write!(f, "{name}"),
         }
     }
@@ -74,6 +114,27 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>>;
 
+    /// Recall memories matching a query, keeping only entries in `categories`
+    /// (all categories when empty). Backends without native filtering
+    /// over-fetch and filter the ranked results.
+    async fn recall_in_categories(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+        categories: &[MemoryCategory],
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        if categories.is_empty() {
+            return self.recall(query, limit, session_id).await;
+        }
+        let mut entries = self
+            .recall(query, limit.saturating_mul(4).max(limit), session_id)
+            .await?;
+        entries.retain(|entry| categories.contains(&entry.category));
+        entries.truncate(limit);
+        Ok(entries)
+    }
+
     /// Get a specific memory by key
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>>;
 
@@ -109,6 +170,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn memory_category_names_round_trip() {
+        for name in MemoryCategory::BUILTIN_NAMES {
+            let category = MemoryCategory::from_name(name);
+            assert!(!matches!(category, MemoryCategory::Custom(_)), "{name}");
+            assert_eq!(category.to_string(), name);
+        }
+        assert_eq!(
+            MemoryCategory::from_name("project_notes"),
+            MemoryCategory::Custom("project_notes".into())
+        );
+    }
+
     #[test]
     fn memory_category_serde_uses_snake_case() {
         let core = serde_json::to_string(&MemoryCategory::Core).unwrap();
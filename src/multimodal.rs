@@ -1,9 +1,11 @@
-use crate::config::{build_runtime_proxy_client_with_timeouts, MultimodalConfig};
+use crate::config::{apply_runtime_proxy_to_builder, parse_cidr_notation, MultimodalConfig};
 use crate::providers::ChatMessage;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use reqwest::Client;
 use std::io::Cursor;
+use std::net::{IpAddr, SocketAddr};
 use std::path::Path;
+use std::sync::LazyLock;
+use std::time::Duration;
 
 const IMAGE_MARKER_PREFIX: &str = "[IMAGE:";
 const OPTIMIZED_IMAGE_MAX_DIMENSION: u32 = 512;
@@ -15,6 +17,37 @@ const ALLOWED_IMAGE_MIME_TYPES: &[&str] = &[
     "image/gif",
     "image/bmp",
 ];
+const MAX_REMOTE_IMAGE_REDIRECTS: usize = 3;
+/// Destinations a remote image fetch must never reach: loopback, RFC 1918,
+/// link-local (cloud metadata), CGNAT, benchmarking, multicast/reserved, and
+/// their IPv6 counterparts, plus the NAT64 and 6to4 prefixes that embed an
+/// IPv4 address. IPv4-mapped IPv6 addresses are checked as IPv4.
+const BLOCKED_REMOTE_IMAGE_CIDRS: &[&str] = &[
+    "0.0.0.0/8",
+    "10.0.0.0/8",
+    "100.64.0.0/10",
+    "127.0.0.0/8",
+    "169.254.0.0/16",
+    "172.16.0.0/12",
+    "192.0.0.0/24",
+    "192.168.0.0/16",
+    "198.18.0.0/15",
+    "224.0.0.0/4",
+    "240.0.0.0/4",
+    "::/128",
+    "::1/128",
+    "64:ff9b::/96",
+    "2002::/16",
+    "fc00::/7",
+    "fe80::/10",
+    "ff00::/8",
+];
+static BLOCKED_REMOTE_IMAGE_NETS: LazyLock<Vec<(IpAddr, u8)>> = LazyLock::new(|| {
+    BLOCKED_REMOTE_IMAGE_CIDRS
+        .iter()
+        .map(|cidr| parse_cidr_notation(cidr).expect("BLOCKED_REMOTE_IMAGE_CIDRS entry"))
+        .collect()
+});
 
 #[derive(Debug, Clone)]
 pub struct PreparedMessages {
@@ -46,6 +79,9 @@ pub enum MultimodalError {
     #[error("invalid multimodal image marker '{input}': {reason}")]
     InvalidMarker { input: String, reason: String },
 
+    #[error("multimodal remote image fetch blocked for '{input}': {reason}")]
+    RemoteFetchBlocked { input: String, reason: String },
+
     #[error("failed to download remote image '{input}': {reason}")]
     RemoteFetchFailed { input: String, reason: String },
 
@@ -121,6 +157,9 @@ pub async fn prepare_messages_for_provider(
 ) -> anyhow::Result<PreparedMessages> {
     let (max_images, max_image_size_mb) = config.effective_limits();
     let max_bytes = max_image_size_mb.saturating_mul(1024 * 1024);
+    let max_dimension = config
+        .effective_max_dimension_px()
+        .unwrap_or(OPTIMIZED_IMAGE_MAX_DIMENSION);
 
     let found_images = count_image_markers(messages);
     if found_images > max_images {
@@ -138,8 +177,6 @@ pub async fn prepare_messages_for_provider(
         });
     }
 
-    let remote_client = build_runtime_proxy_client_with_timeouts("provider.ollama", 30, 10);
-
     let mut normalized_messages = Vec::with_capacity(messages.len());
     for message in messages {
         if message.role != "user" {
@@ -156,7 +193,7 @@ pub async fn prepare_messages_for_provider(
         let mut normalized_refs = Vec::with_capacity(refs.len());
         for reference in refs {
             let data_uri =
-                normalize_image_reference(&reference, config, max_bytes, &remote_client).await?;
+                normalize_image_reference(&reference, config, max_bytes, max_dimension).await?;
             normalized_refs.push(data_uri);
         }
 
@@ -198,10 +235,10 @@ async fn normalize_image_reference(
     source: &str,
     config: &MultimodalConfig,
     max_bytes: usize,
-    remote_client: &Client,
+    max_dimension: u32,
 ) -> anyhow::Result<String> {
     if source.starts_with("data:") {
-        return normalize_data_uri(source, max_bytes).await;
+        return normalize_data_uri(source, max_bytes, max_dimension).await;
     }
 
     if source.starts_with("http://") || source.starts_with("https://") {
@@ -212,13 +249,17 @@ async fn normalize_image_reference(
             .into());
         }
 
-        return normalize_remote_image(source, max_bytes, remote_client).await;
+        return normalize_remote_image(source, max_bytes, max_dimension).await;
     }
 
-    normalize_local_image(source, max_bytes).await
+    normalize_local_image(source, max_bytes, max_dimension).await
 }
 
-async fn normalize_data_uri(source: &str, max_bytes: usize) -> anyhow::Result<String> {
+async fn normalize_data_uri(
+    source: &str,
+    max_bytes: usize,
+    max_dimension: u32,
+) -> anyhow::Result<String> {
     let Some(comma_idx) = source.find(',') else {
         return Err(MultimodalError::InvalidMarker {
             input: source.to_string(),
@@ -256,7 +297,7 @@ async fn normalize_data_uri(source: &str, max_bytes: usize) -> anyhow::Result<St
         })?;
 
     let (optimized_bytes, optimized_mime) =
-        optimize_image_for_prompt(source, decoded, &mime).await?;
+        optimize_image_for_prompt(source, decoded, &mime, max_dimension).await?;
     validate_size(source, optimized_bytes.len(), max_bytes)?;
 
     Ok(format!(
@@ -268,64 +309,221 @@ async fn normalize_data_uri(source: &str, max_bytes: usize) -> anyhow::Result<St
 async fn normalize_remote_image(
     source: &str,
     max_bytes: usize,
-    remote_client: &Client,
+    max_dimension: u32,
 ) -> anyhow::Result<String> {
-    let response = remote_client.get(source).send().await.map_err(|error| {
-        MultimodalError::RemoteFetchFailed {
+    let (bytes, content_type) = fetch_remote_image(source, max_bytes).await?;
+
+    let mime = detect_mime(None, &bytes, None)
+        .or(content_type)
+        .ok_or_else(|| MultimodalError::UnsupportedMime {
             input: source.to_string(),
-            reason: error.to_string(),
-        }
+            mime: "unknown".to_string(),
+        })?;
+
+    validate_mime(source, &mime)?;
+    let (optimized_bytes, optimized_mime) =
+        optimize_image_for_prompt(source, bytes, &mime, max_dimension).await?;
+    validate_size(source, optimized_bytes.len(), max_bytes)?;
+
+    Ok(format!(
+        "data:{optimized_mime};base64,{}",
+        STANDARD.encode(optimized_bytes)
+    ))
+}
+
+/// Download a remote image with SSRF protection.
+///
+/// Every hop (including redirects, which are followed manually) must be
+/// HTTPS and resolve only to public addresses; the connection is pinned to
+/// the vetted address so a second DNS answer cannot swap in a private one.
+/// The body is streamed and aborted as soon as it exceeds `max_bytes`.
+/// Returns the body and the declared `image/*` content type.
+async fn fetch_remote_image(
+    source: &str,
+    max_bytes: usize,
+) -> anyhow::Result<(Vec<u8>, Option<String>)> {
+    let fetch_failed = |reason: String| MultimodalError::RemoteFetchFailed {
+        input: source.to_string(),
+        reason,
+    };
+
+    let mut url = reqwest::Url::parse(source).map_err(|error| MultimodalError::InvalidMarker {
+        input: source.to_string(),
+        reason: format!("invalid image URL: {error}"),
     })?;
 
-    let status = response.status();
-    if !status.is_success() {
-        return Err(MultimodalError::RemoteFetchFailed {
-            input: source.to_string(),
-            reason: format!("HTTP {status}"),
+    for _ in 0..=MAX_REMOTE_IMAGE_REDIRECTS {
+        let (host, addr) = resolve_remote_image_target(source, &url).await?;
+        let client = apply_runtime_proxy_to_builder(
+            reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .connect_timeout(Duration::from_secs(10))
+                .redirect(reqwest::redirect::Policy::none())
+                .resolve(&host, addr),
+            "provider.ollama",
+        )
+        .build()
+        .map_err(|error| fetch_failed(error.to_string()))?;
+
+        let response = client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(|error| fetch_failed(error.to_string()))?;
+
+        let status = response.status();
+        if status.is_redirection() {
+            let location = response
+                .headers()
+                .get(reqwest::header::LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| fetch_failed(format!("HTTP {status} without Location")))?;
+            url = url
+                .join(location)
+                .map_err(|error| fetch_failed(format!("invalid redirect target: {error}")))?;
+            continue;
         }
-        .into());
+        if !status.is_success() {
+            return Err(fetch_failed(format!("HTTP {status}")).into());
+        }
+
+        let content_type = validate_remote_content_type(
+            source,
+            response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok()),
+        )?;
+
+        let bytes = read_remote_body_capped(source, response, max_bytes).await?;
+        return Ok((bytes, Some(content_type)));
     }
 
-    if let Some(content_length) = response.content_length() {
-        let content_length = usize::try_from(content_length).unwrap_or(usize::MAX);
-        validate_size(source, content_length, max_bytes)?;
+    Err(fetch_failed(format!("more than {MAX_REMOTE_IMAGE_REDIRECTS} redirects")).into())
+}
+
+/// Check scheme and credentials, then resolve the host and reject it if any
+/// address is non-public. Returns the host and the address to pin.
+async fn resolve_remote_image_target(
+    source: &str,
+    url: &reqwest::Url,
+) -> Result<(String, SocketAddr), MultimodalError> {
+    let blocked = |reason: String| MultimodalError::RemoteFetchBlocked {
+        input: source.to_string(),
+        reason,
+    };
+
+    if url.scheme() != "https" {
+        return Err(blocked(format!(
+            "only https:// image URLs are allowed, got {}://",
+            url.scheme()
+        )));
     }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(blocked("URL credentials are not allowed".to_string()));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| blocked("URL has no host".to_string()))?;
+    let port = url.port_or_known_default().unwrap_or(443);
+
+    let bare_host = host.trim_start_matches('[').trim_end_matches(']');
+    let addrs: Vec<SocketAddr> = if let Ok(ip) = bare_host.parse::<IpAddr>() {
+        vec![SocketAddr::new(ip, port)]
+    } else {
+        tokio::net::lookup_host((bare_host, port))
+            .await
+            .map_err(|error| MultimodalError::RemoteFetchFailed {
+                input: source.to_string(),
+                reason: format!("DNS lookup failed: {error}"),
+            })?
+            .collect()
+    };
 
-    let content_type = response
-        .headers()
-        .get(reqwest::header::CONTENT_TYPE)
-        .and_then(|value| value.to_str().ok())
-        .map(ToString::to_string);
+    if let Some(addr) = addrs.iter().find(|addr| is_blocked_remote_ip(addr.ip())) {
+        return Err(blocked(format!(
+            "{host} resolves to non-public address {}",
+            addr.ip()
+        )));
+    }
+    let addr = addrs
+        .first()
+        .copied()
+        .ok_or_else(|| blocked(format!("{host} did not resolve to any address")))?;
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|error| MultimodalError::RemoteFetchFailed {
-            input: source.to_string(),
-            reason: error.to_string(),
-        })?;
+    Ok((bare_host.to_string(), addr))
+}
 
-    validate_size(source, bytes.len(), max_bytes)?;
+fn is_blocked_remote_ip(ip: IpAddr) -> bool {
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        IpAddr::V4(_) => ip,
+    };
+    BLOCKED_REMOTE_IMAGE_NETS
+        .iter()
+        .any(|&(network, prefix)| cidr_contains(network, prefix, ip))
+}
 
-    let mime = detect_mime(None, bytes.as_ref(), content_type.as_deref()).ok_or_else(|| {
-        MultimodalError::UnsupportedMime {
-            input: source.to_string(),
-            mime: "unknown".to_string(),
+fn cidr_contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
         }
-    })?;
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - u32::from(prefix)).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
 
-    validate_mime(source, &mime)?;
-    let (optimized_bytes, optimized_mime) =
-        optimize_image_for_prompt(source, bytes.to_vec(), &mime).await?;
-    validate_size(source, optimized_bytes.len(), max_bytes)?;
+/// Require an `image/*` content type on remote responses.
+fn validate_remote_content_type(
+    source: &str,
+    content_type: Option<&str>,
+) -> Result<String, MultimodalError> {
+    match content_type.and_then(normalize_content_type) {
+        Some(mime) if mime.starts_with("image/") => Ok(mime),
+        other => Err(MultimodalError::UnsupportedMime {
+            input: source.to_string(),
+            mime: other.unwrap_or_else(|| "missing content-type".to_string()),
+        }),
+    }
+}
 
-    Ok(format!(
-        "data:{optimized_mime};base64,{}",
-        STANDARD.encode(optimized_bytes)
-    ))
+/// Stream a response body, aborting once it grows past `max_bytes`.
+async fn read_remote_body_capped(
+    source: &str,
+    mut response: reqwest::Response,
+    max_bytes: usize,
+) -> anyhow::Result<Vec<u8>> {
+    if let Some(content_length) = response.content_length() {
+        let content_length = usize::try_from(content_length).unwrap_or(usize::MAX);
+        validate_size(source, content_length, max_bytes)?;
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) =
+        response
+            .chunk()
+            .await
+            .map_err(|error| MultimodalError::RemoteFetchFailed {
+                input: source.to_string(),
+                reason: error.to_string(),
+            })?
+    {
+        validate_size(source, body.len().saturating_add(chunk.len()), max_bytes)?;
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
 }
 
-async fn normalize_local_image(source: &str, max_bytes: usize) -> anyhow::Result<String> {
+async fn normalize_local_image(
+    source: &str,
+    max_bytes: usize,
+    max_dimension: u32,
+) -> anyhow::Result<String> {
     let path = Path::new(source);
     if !path.exists() || !path.is_file() {
         return Err(MultimodalError::ImageSourceNotFound {
@@ -364,7 +562,8 @@ async fn normalize_local_image(source: &str, max_bytes: usize) -> anyhow::Result
         })?;
 
     validate_mime(source, &mime)?;
-    let (optimized_bytes, optimized_mime) = optimize_image_for_prompt(source, bytes, &mime).await?;
+    let (optimized_bytes, optimized_mime) =
+        optimize_image_for_prompt(source, bytes, &mime, max_dimension).await?;
     validate_size(source, optimized_bytes.len(), max_bytes)?;
 
     Ok(format!(
@@ -377,13 +576,14 @@ async fn optimize_image_for_prompt(
     source: &str,
     bytes: Vec<u8>,
     mime: &str,
+    max_dimension: u32,
 ) -> anyhow::Result<(Vec<u8>, String)> {
     validate_mime(source, mime)?;
 
     let source_owned = source.to_string();
     let mime_owned = mime.to_string();
     tokio::task::spawn_blocking(move || {
-        optimize_image_for_prompt_blocking(source_owned, bytes, mime_owned)
+        optimize_image_for_prompt_blocking(source_owned, bytes, mime_owned, max_dimension)
     })
     .await
     .map_err(|error| MultimodalError::InvalidMarker {
@@ -396,16 +596,23 @@ fn optimize_image_for_prompt_blocking(
     source: String,
     bytes: Vec<u8>,
     mime: String,
+    max_dimension: u32,
 ) -> anyhow::Result<(Vec<u8>, String)> {
     let decoded = match image::load_from_memory(&bytes) {
         Ok(decoded) => decoded,
-        Err(_) => return Ok((bytes, mime)),
+        Err(error) => {
+            tracing::warn!(
+                input = source.as_str(),
+                mime = mime.as_str(),
+                "multimodal image could not be decoded; passing through without downscaling: {error}"
+            );
+            return Ok((bytes, mime));
+        }
     };
 
-    let resized = if decoded.width() > OPTIMIZED_IMAGE_MAX_DIMENSION
-        || decoded.height() > OPTIMIZED_IMAGE_MAX_DIMENSION
-    {
-        decoded.thumbnail(OPTIMIZED_IMAGE_MAX_DIMENSION, OPTIMIZED_IMAGE_MAX_DIMENSION)
+    // `thumbnail` preserves aspect ratio within the bounding box.
+    let resized = if decoded.width() > max_dimension || decoded.height() > max_dimension {
+        decoded.thumbnail(max_dimension, max_dimension)
     } else {
         decoded
     };
@@ -591,6 +798,7 @@ mod tests {
             max_images: 1,
             max_image_size_mb: 5,
             allow_remote_fetch: false,
+            max_dimension_px: None,
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -633,6 +841,7 @@ mod tests {
             max_images: 4,
             max_image_size_mb: 1,
             allow_remote_fetch: false,
+            max_dimension_px: None,
         };
 
         let error = prepare_messages_for_provider(&messages, &config)
@@ -661,7 +870,7 @@ mod tests {
         let original_size = png_bytes.len();
 
         let source = format!("data:image/png;base64,{}", STANDARD.encode(&png_bytes));
-        let optimized = normalize_data_uri(&source, 5 * 1024 * 1024)
+        let optimized = normalize_data_uri(&source, 5 * 1024 * 1024, OPTIMIZED_IMAGE_MAX_DIMENSION)
             .await
             .expect("data uri should normalize");
         assert!(optimized.starts_with("data:image/jpeg;base64,"));
@@ -681,6 +890,145 @@ mod tests {
         assert!(optimized_image.height() <= OPTIMIZED_IMAGE_MAX_DIMENSION);
     }
 
+    #[tokio::test]
+    async fn prepare_messages_downscales_to_configured_max_dimension() {
+        let image = image::RgbImage::from_pixel(1000, 500, image::Rgb([10, 120, 200]));
+        let mut png_bytes = Vec::new();
+        image::DynamicImage::ImageRgb8(image)
+            .write_to(
+                &mut std::io::Cursor::new(&mut png_bytes),
+                image::ImageFormat::Png,
+            )
+            .unwrap();
+
+        let messages = vec![ChatMessage::user(format!(
+            "[IMAGE:data:image/png;base64,{}]",
+            STANDARD.encode(&png_bytes)
+        ))];
+        let config = MultimodalConfig {
+            max_dimension_px: Some(200),
+            ..MultimodalConfig::default()
+        };
+
+        let prepared = prepare_messages_for_provider(&messages, &config)
+            .await
+            .unwrap();
+
+        let (_, refs) = parse_image_markers(&prepared.messages[0].content);
+        let payload = refs[0].split_once(',').map(|(_, p)| p).unwrap();
+        let resized = image::load_from_memory(&STANDARD.decode(payload).unwrap()).unwrap();
+        assert_eq!((resized.width(), resized.height()), (200, 100));
+    }
+
+    #[test]
+    fn effective_max_dimension_px_is_clamped() {
+        let mut config = MultimodalConfig::default();
+        assert_eq!(config.effective_max_dimension_px(), None);
+        config.max_dimension_px = Some(1);
+        assert_eq!(config.effective_max_dimension_px(), Some(64));
+        config.max_dimension_px = Some(100_000);
+        assert_eq!(config.effective_max_dimension_px(), Some(4096));
+    }
+
+    async fn remote_target_error(url: &str) -> MultimodalError {
+        let parsed = reqwest::Url::parse(url).unwrap();
+        resolve_remote_image_target(url, &parsed)
+            .await
+            .expect_err("target should be rejected")
+    }
+
+    #[tokio::test]
+    async fn remote_fetch_rejects_private_and_non_https_targets() {
+        for url in [
+            "https://127.0.0.1/a.png",
+            "https://10.1.2.3/a.png",
+            "https://172.16.0.9/a.png",
+            "https://192.168.1.1/a.png",
+            "https://169.254.169.254/latest/meta-data",
+            "https://100.64.0.1/a.png",
+            "https://[::1]/a.png",
+            "https://[fd00::1]/a.png",
+            "https://[::ffff:10.0.0.1]/a.png",
+            "https://[64:ff9b::a9fe:a9fe]/a.png",
+            "https://[2002:c0a8:101::1]/a.png",
+        ] {
+            assert!(
+                matches!(
+                    remote_target_error(url).await,
+                    MultimodalError::RemoteFetchBlocked { .. }
+                ),
+                "{url} should be blocked"
+            );
+        }
+
+        let error = remote_target_error("http://93.184.216.34/a.png").await;
+        assert!(error.to_string().contains("only https://"));
+        let error = remote_target_error("https://user:pw@93.184.216.34/a.png").await;
+        assert!(error.to_string().contains("credentials"));
+    }
+
+    #[test]
+    fn blocked_remote_image_cidrs_all_parse() {
+        for cidr in BLOCKED_REMOTE_IMAGE_CIDRS {
+            assert!(parse_cidr_notation(cidr).is_ok(), "{cidr} should parse");
+        }
+        assert_eq!(
+            BLOCKED_REMOTE_IMAGE_NETS.len(),
+            BLOCKED_REMOTE_IMAGE_CIDRS.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn remote_fetch_allows_public_ip_literal() {
+        let url = reqwest::Url::parse("https://93.184.216.34/a.png").unwrap();
+        let (host, addr) = resolve_remote_image_target(url.as_str(), &url)
+            .await
+            .unwrap();
+        assert_eq!(host, "93.184.216.34");
+        assert_eq!(addr.port(), 443);
+        assert!(!is_blocked_remote_ip("8.8.8.8".parse().unwrap()));
+        assert!(!is_blocked_remote_ip("2606:4700::1111".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn remote_body_read_aborts_past_size_limit() {
+        let chunks = (0..32).map(|_| Ok::<_, std::io::Error>(vec![0u8; 64 * 1024]));
+        let response = reqwest::Response::from(
+            axum::http::Response::builder()
+                .body(reqwest::Body::wrap_stream(futures_util::stream::iter(
+                    chunks,
+                )))
+                .unwrap(),
+        );
+
+        let error = read_remote_body_capped("https://example.com/a.png", response, 1024 * 1024)
+            .await
+            .expect_err("oversized stream should abort");
+        assert!(error
+            .to_string()
+            .contains("multimodal image size limit exceeded"));
+
+        let response = reqwest::Response::from(
+            axum::http::Response::builder()
+                .body(reqwest::Body::from(vec![1u8; 1024]))
+                .unwrap(),
+        );
+        let body = read_remote_body_capped("https://example.com/a.png", response, 1024 * 1024)
+            .await
+            .unwrap();
+        assert_eq!(body.len(), 1024);
+    }
+
+    #[test]
+    fn remote_content_type_must_be_image() {
+        assert_eq!(
+            validate_remote_content_type("u", Some("image/png; charset=binary")).unwrap(),
+            "image/png"
+        );
+        assert!(validate_remote_content_type("u", Some("text/html")).is_err());
+        assert!(validate_remote_content_type("u", None).is_err());
+    }
+
     #[test]
     fn extract_ollama_image_payload_supports_data_uris() {
         let payload = extract_ollama_image_payload("data:image/png;base64,abcd==")
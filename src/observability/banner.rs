@@ -0,0 +1,208 @@
+//! Startup banner of `zeroclaw channel start` and `zeroclaw gateway`.
+//!
+//! `[observability] startup_banner` picks the variant: `full` prints the
+//! multi-line banner, `minimal` a single line with the version and where the
+//! server listens, and `off` prints nothing on stdout and logs the banner
+//! through tracing at info instead, so a systemd unit's journal only carries
+//! log lines. The global `--quiet` flag forces `off` for any command.
+//!
+//! [`lines`] builds the banner without printing it.
+
+use crate::config::StartupBanner;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Record the global `--quiet` flag for the rest of the process.
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Whether `--quiet` was given.
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// The variant to use for `configured`: `Off` under `--quiet`.
+pub fn effective(configured: StartupBanner) -> StartupBanner {
+    if quiet() {
+        StartupBanner::Off
+    } else {
+        configured
+    }
+}
+
+/// What a banner describes.
+#[derive(Debug, Clone, Default)]
+pub struct BannerInfo<'a> {
+    /// Server name, e.g. "ZeroClaw Gateway".
+    pub title: &'a str,
+    /// Listening address; servers without one (the channel server) leave it
+    /// `None` and the minimal line names the channels instead.
+    pub address: Option<String>,
+    pub model: Option<&'a str>,
+    pub memory: Option<String>,
+    pub workspace: Option<&'a Path>,
+    pub config_path: Option<&'a Path>,
+    pub channels: Vec<&'a str>,
+    /// Server-specific lines appended to the full banner (routes, limits).
+    pub details: Vec<String>,
+    /// Something the operator must see in every printed variant, such as the
+    /// gateway's one-time pairing code. Appended to the minimal line.
+    pub notice: Option<String>,
+}
+
+impl BannerInfo<'_> {
+    fn listening_on(&self) -> String {
+        match (&self.address, self.channels.is_empty()) {
+            (Some(address), _) => address.clone(),
+            (None, false) => self.channels.join(", "),
+            (None, true) => "no channels".into(),
+        }
+    }
+}
+
+/// The stdout lines of `variant`; empty for [`StartupBanner::Off`].
+pub fn lines(variant: StartupBanner, info: &BannerInfo<'_>) -> Vec<String> {
+    let version = env!("CARGO_PKG_VERSION");
+    match variant {
+        StartupBanner::Off => Vec::new(),
+        StartupBanner::Minimal => {
+            let mut line = format!(
+                "{} v{version} listening on {}",
+                info.title,
+                info.listening_on()
+            );
+            if let Some(notice) = &info.notice {
+                line.push_str(" — ");
+                line.push_str(notice);
+            }
+            vec![line]
+        }
+        StartupBanner::Full => {
+            let mut lines = vec![format!("🦀 {} v{version}", info.title)];
+            if let Some(address) = &info.address {
+                lines.push(format!("  🔌 Listening: {address}"));
+            }
+            if let Some(model) = info.model {
+                lines.push(format!("  🤖 Model:     {model}"));
+            }
+            if let Some(memory) = &info.memory {
+                lines.push(format!("  🧠 Memory:    {memory}"));
+            }
+            if let Some(workspace) = info.workspace {
+                lines.push(format!("  📂 Workspace: {}", workspace.display()));
+            }
+            if let Some(config_path) = info.config_path {
+                lines.push(format!("  ⚙️  Config:    {}", config_path.display()));
+            }
+            if !info.channels.is_empty() {
+                lines.push(format!("  📡 Channels:  {}", info.channels.join(", ")));
+            }
+            lines.extend(info.details.iter().cloned());
+            lines
+        }
+    }
+}
+
+/// Print the banner, or log it at info when `variant` is `Off`.
+pub fn print(variant: StartupBanner, info: &BannerInfo<'_>) {
+    if variant == StartupBanner::Off {
+        tracing::info!(
+            version = env!("CARGO_PKG_VERSION"),
+            listening = %info.listening_on(),
+            model = info.model.unwrap_or_default(),
+            workspace = %info.workspace.map(|path| path.display().to_string()).unwrap_or_default(),
+            config = %info.config_path.map(|path| path.display().to_string()).unwrap_or_default(),
+            notice = info.notice.as_deref().unwrap_or_default(),
+            "{} started",
+            info.title
+        );
+        return;
+    }
+    for line in lines(variant, info) {
+        println!("{line}");
+    }
+}
+
+/// A startup progress note (tunnel, mDNS): printed with the full banner,
+/// logged at info otherwise.
+pub fn note(variant: StartupBanner, line: &str) {
+    if variant == StartupBanner::Full {
+        println!("{line}");
+    } else {
+        tracing::info!("{}", line.trim());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gateway_info() -> BannerInfo<'static> {
+        BannerInfo {
+            title: "ZeroClaw Gateway",
+            address: Some("http://127.0.0.1:42617".into()),
+            model: Some("anthropic/claude-sonnet-4-6"),
+            memory: Some("sqlite (auto-save: on)".into()),
+            workspace: Some(Path::new("/srv/zeroclaw/workspace")),
+            config_path: Some(Path::new("/srv/zeroclaw/config.toml")),
+            channels: vec!["whatsapp"],
+            details: vec!["  GET  /health    — health check".into()],
+            notice: Some("pairing code: 123456".into()),
+        }
+    }
+
+    #[test]
+    fn full_banner_shows_workspace_config_and_channels() {
+        let banner = lines(StartupBanner::Full, &gateway_info());
+        let text = banner.join("\n");
+        assert!(banner[0].starts_with("🦀 ZeroClaw Gateway v"), "{text}");
+        for expected in [
+            "Listening: http://127.0.0.1:42617",
+            "Model:     anthropic/claude-sonnet-4-6",
+            "Memory:    sqlite (auto-save: on)",
+            "Workspace: /srv/zeroclaw/workspace",
+            "Config:    /srv/zeroclaw/config.toml",
+            "Channels:  whatsapp",
+            "GET  /health",
+        ] {
+            assert!(text.contains(expected), "missing {expected:?} in:\n{text}");
+        }
+        // The full banner leaves the pairing box to the details.
+        assert!(!text.contains("123456"), "{text}");
+    }
+
+    #[test]
+    fn minimal_banner_is_one_line_without_the_model() {
+        assert_eq!(
+            lines(StartupBanner::Minimal, &gateway_info()),
+            [format!(
+                "ZeroClaw Gateway v{} listening on http://127.0.0.1:42617 — pairing code: 123456",
+                env!("CARGO_PKG_VERSION")
+            )]
+        );
+
+        let channel_server = BannerInfo {
+            title: "ZeroClaw Channel Server",
+            model: Some("gpt-4o"),
+            channels: vec!["telegram", "discord"],
+            ..BannerInfo::default()
+        };
+        let line = &lines(StartupBanner::Minimal, &channel_server)[0];
+        assert!(line.ends_with("listening on telegram, discord"), "{line}");
+        assert!(!line.contains("gpt-4o"), "{line}");
+    }
+
+    #[test]
+    fn off_banner_prints_nothing_and_quiet_forces_it() {
+        assert!(lines(StartupBanner::Off, &gateway_info()).is_empty());
+
+        assert_eq!(effective(StartupBanner::Full), StartupBanner::Full);
+        set_quiet(true);
+        assert_eq!(effective(StartupBanner::Full), StartupBanner::Off);
+        assert_eq!(effective(StartupBanner::Minimal), StartupBanner::Off);
+        set_quiet(false);
+    }
+}
@@ -40,6 +40,20 @@ impl CostObserver {
             return (pricing.input, pricing.output);
         }
 
+        // Try the same model under another name for the provider
+        // (e.g. "google/gemini-2.0-flash" for provider "gemini")
+        let canonical = crate::providers::canonical_provider_name(provider);
+        let aliased = self.prices.iter().find(|(key, _)| {
+            key.split_once('/')
+                .is_some_and(|(key_provider, key_model)| {
+                    key_model == model
+                        && crate::providers::canonical_provider_name(key_provider) == canonical
+                })
+        });
+        if let Some((_, pricing)) = aliased {
+            return (pricing.input, pricing.output);
+        }
+
         // Try just the model name
         if let Some(pricing) = self.prices.get(model) {
             return (pricing.input, pricing.output);
@@ -233,6 +247,41 @@ mod tests {
         assert!((summary.session_cost_usd - 18.0).abs() < 0.01);
     }
 
+    #[test]
+    fn cost_observer_prices_provider_aliases() {
+        let (_tmp, tracker) = create_test_tracker();
+        let mut prices = HashMap::new();
+        prices.insert(
+            "google/gemini-2.0-flash".into(),
+            ModelPricing {
+                input: 0.1,
+                output: 0.4,
+            },
+        );
+        prices.insert(
+            "gemini-2.0-flash".into(),
+            ModelPricing {
+                input: 9.0,
+                output: 9.0,
+            },
+        );
+
+        let observer = CostObserver::new(tracker.clone(), prices);
+        assert_eq!(
+            observer.get_pricing("gemini", "gemini-2.0-flash"),
+            (0.1, 0.4)
+        );
+        assert_eq!(
+            observer.get_pricing("google-gemini", "gemini-2.0-flash"),
+            (0.1, 0.4)
+        );
+        // Other providers serving the model fall through to the bare name.
+        assert_eq!(
+            observer.get_pricing("openrouter", "gemini-2.0-flash"),
+            (9.0, 9.0)
+        );
+    }
+
     #[test]
     fn cost_observer_matches_model_family() {
         let (_tmp, tracker) = create_test_tracker();
@@ -1,3 +1,4 @@
+pub mod banner;
 pub mod cost;
 pub mod log;
 pub mod multi;
@@ -53,7 +53,8 @@ pub enum ObserverEvent {
     ChannelMessage {
         /// Channel name (e.g., `"telegram"`, `"discord"`).
         channel: String,
-        /// `"inbound"` or `"outbound"`.
+        /// `"inbound"`, `"outbound"`, or `"filtered"` (an inbound message
+        /// skipped by `[channels_config.filters]`).
         direction: String,
     },
     /// Periodic heartbeat tick from the runtime keep-alive loop.
@@ -398,6 +398,8 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         archive_after_days: if profile.uses_sqlite_hygiene { 7 } else { 0 },
         purge_after_days: if profile.uses_sqlite_hygiene { 30 } else { 0 },
         conversation_retention_days: 30,
+        category_retention_days: std::collections::BTreeMap::from([("ephemeral".to_string(), 2)]),
+        classification: crate::config::MemoryClassificationConfig::default(),
         embedding_provider: "none".to_string(),
         embedding_model: "text-embedding-3-small".to_string(),
         embedding_dimensions: 1536,
@@ -417,6 +419,9 @@ fn memory_config_defaults_for_backend(backend: &str) -> MemoryConfig {
         snapshot_on_hygiene: false,
         auto_hydrate: true,
         sqlite_open_timeout_secs: None,
+        allow_bulk_forget: false,
+        bulk_forget_max_entries: 100,
+        undo_retention_days: 7,
         qdrant: crate::config::QdrantConfig::default(),
     }
 }
@@ -4138,6 +4143,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     listen_to_bots: false,
                     mention_only: false,
                     group_reply: None,
+                    slash_commands: vec![],
+                    reply_style: None,
                 });
             }
             ChannelMenuChoice::Slack => {
@@ -4266,6 +4273,10 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     },
                     allowed_users,
                     group_reply: None,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
+                    slash_commands: vec![],
+                    reply_style: None,
                 });
             }
             ChannelMenuChoice::IMessage => {
@@ -4423,6 +4434,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     room_id,
                     allowed_users,
                     mention_only: false,
+                    stream_mode: StreamMode::default(),
+                    draft_update_interval_ms: 1000,
+                    reply_style: None,
                 });
             }
             ChannelMenuChoice::Signal => {
@@ -4957,6 +4971,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     } else {
                         Some(secret)
                     },
+                    hmac_secret: None,
                 });
                 println!(
                     "  {} Webhook on port {}",
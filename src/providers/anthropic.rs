@@ -1,6 +1,7 @@
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, TokenUsage, ToolCall as ProviderToolCall,
+    ChatMessage, ChatOutcome, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, ProviderCapabilities, TokenUsage,
+    ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -10,8 +11,16 @@ use serde::{Deserialize, Serialize};
 pub struct AnthropicProvider {
     credential: Option<String>,
     base_url: String,
+    prompt_caching: bool,
 }
 
+/// `stop_reason` of a response the model declined for policy reasons.
+const REFUSAL_STOP_REASON: &str = "refusal";
+
+/// First heading of the per-turn part of the system prompt built by
+/// `build_system_prompt`; everything before it is stable across turns.
+const DYNAMIC_SYSTEM_PROMPT_MARKER: &str = "## Current Date & Time";
+
 #[derive(Debug, Serialize)]
 struct ChatRequest {
     model: String,
@@ -31,6 +40,8 @@ struct Message {
 #[derive(Debug, Deserialize)]
 struct ChatResponse {
     content: Vec<ContentBlock>,
+    #[serde(default)]
+    stop_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -39,6 +50,23 @@ struct ContentBlock {
     kind: String,
     #[serde(default)]
     text: Option<String>,
+    /// Arguments of a `tool_use` block.
+    #[serde(default)]
+    input: Option<serde_json::Value>,
+}
+
+/// Structured output via tool forcing: the model must call the single tool,
+/// and its input is the answer.
+#[derive(Debug, Serialize)]
+struct StructuredChatRequest<'a> {
+    model: String,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
+    messages: Vec<Message>,
+    temperature: f64,
+    tools: Vec<NativeToolSpec<'a>>,
+    tool_choice: serde_json::Value,
 }
 
 #[derive(Debug, Serialize)]
@@ -140,6 +168,8 @@ struct NativeChatResponse {
     content: Vec<NativeContentIn>,
     #[serde(default)]
     usage: Option<AnthropicUsage>,
+    #[serde(default)]
+    stop_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -180,9 +210,16 @@ impl AnthropicProvider {
                 .filter(|k| !k.is_empty())
                 .map(ToString::to_string),
             base_url,
+            prompt_caching: true,
         }
     }
 
+    /// Enable or disable `cache_control` breakpoints (`[provider] prompt_caching`).
+    pub fn with_prompt_caching(mut self, enabled: bool) -> Self {
+        self.prompt_caching = enabled;
+        self
+    }
+
     fn is_setup_token(token: &str) -> bool {
         token.starts_with("sk-ant-oat01-")
     }
@@ -391,11 +428,7 @@ impl AnthropicProvider {
         // Convert system text to SystemPrompt with cache control if large
         let system_prompt = system_text.map(|text| {
             if Self::should_cache_system(&text) {
-                SystemPrompt::Blocks(vec![SystemBlock {
-                    block_type: "text".to_string(),
-                    text,
-                    cache_control: Some(CacheControl::ephemeral()),
-                }])
+                Self::cacheable_system_blocks(text)
             } else {
                 SystemPrompt::String(text)
             }
@@ -404,13 +437,119 @@ impl AnthropicProvider {
         (system_prompt, native_messages)
     }
 
+    /// Split the system prompt into a cached stable prefix and an uncached
+    /// dynamic tail, so the per-turn timestamp does not invalidate the cache.
+    fn cacheable_system_blocks(text: String) -> SystemPrompt {
+        let split_at = text
+            .find(DYNAMIC_SYSTEM_PROMPT_MARKER)
+            .filter(|idx| Self::should_cache_system(&text[..*idx]));
+        let Some(split_at) = split_at else {
+            return SystemPrompt::Blocks(vec![SystemBlock {
+                block_type: "text".to_string(),
+                text,
+                cache_control: Some(CacheControl::ephemeral()),
+            }]);
+        };
+
+        let dynamic = text[split_at..].to_string();
+        let mut stable = text;
+        stable.truncate(split_at);
+        SystemPrompt::Blocks(vec![
+            SystemBlock {
+                block_type: "text".to_string(),
+                text: stable,
+                cache_control: Some(CacheControl::ephemeral()),
+            },
+            SystemBlock {
+                block_type: "text".to_string(),
+                text: dynamic,
+                cache_control: None,
+            },
+        ])
+    }
+
+    /// Build the native request body, honoring the `prompt_caching` setting.
+    fn build_native_request<'a>(
+        &self,
+        request: &ProviderChatRequest<'a>,
+        model: &str,
+        temperature: f64,
+    ) -> NativeChatRequest<'a> {
+        let (system_prompt, mut messages) = Self::convert_messages(request.messages);
+        let mut tools = Self::convert_tools(request.tools);
+
+        let system_prompt = if self.prompt_caching {
+            // Auto-cache last message if conversation is long
+            if Self::should_cache_conversation(request.messages) {
+                Self::apply_cache_to_last_message(&mut messages);
+            }
+            system_prompt
+        } else {
+            if let Some(tools) = tools.as_mut() {
+                for tool in tools {
+                    tool.cache_control = None;
+                }
+            }
+            system_prompt.map(|prompt| match prompt {
+                SystemPrompt::Blocks(blocks) => SystemPrompt::String(
+                    blocks
+                        .into_iter()
+                        .map(|block| block.text)
+                        .collect::<String>(),
+                ),
+                plain @ SystemPrompt::String(_) => plain,
+            })
+        };
+
+        NativeChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system: system_prompt,
+            messages,
+            temperature,
+            tools,
+        }
+    }
+
+    fn structured_request<'a>(
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: &'a serde_json::Value,
+    ) -> StructuredChatRequest<'a> {
+        StructuredChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system: system_prompt.map(ToString::to_string),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: message.to_string(),
+            }],
+            temperature,
+            tools: vec![NativeToolSpec {
+                name: super::structured::STRUCTURED_OUTPUT_TOOL,
+                description: "Return the final answer as structured data.",
+                input_schema: schema,
+                cache_control: None,
+            }],
+            tool_choice: serde_json::json!({
+                "type": "tool",
+                "name": super::structured::STRUCTURED_OUTPUT_TOOL,
+            }),
+        }
+    }
+
     fn parse_text_response(response: ChatResponse) -> anyhow::Result<String> {
-        response
+        let text = response
             .content
             .into_iter()
             .find(|c| c.kind == "text")
-            .and_then(|c| c.text)
-            .ok_or_else(|| anyhow::anyhow!("No response from Anthropic"))
+            .and_then(|c| c.text);
+        if response.stop_reason.as_deref() == Some(REFUSAL_STOP_REASON) {
+            ChatOutcome::refused(text.as_deref(), REFUSAL_STOP_REASON).ensure_completed()?;
+        }
+        text.ok_or_else(|| anyhow::anyhow!("No response from Anthropic"))
     }
 
     fn parse_native_response(response: NativeChatResponse) -> ProviderChatResponse {
@@ -449,15 +588,23 @@ impl AnthropicProvider {
             }
         }
 
+        let text = if text_parts.is_empty() {
+            None
+        } else {
+            Some(text_parts.join("\n"))
+        };
+        if response.stop_reason.as_deref() == Some(REFUSAL_STOP_REASON) {
+            return ProviderChatResponse::refused(
+                ChatOutcome::refused(text.as_deref(), REFUSAL_STOP_REASON),
+                usage,
+            );
+        }
         ProviderChatResponse {
-            text: if text_parts.is_empty() {
-                None
-            } else {
-                Some(text_parts.join("\n"))
-            },
+            text,
             tool_calls,
             usage,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         }
     }
 
@@ -511,33 +658,76 @@ impl Provider for AnthropicProvider {
         Self::parse_text_response(chat_response)
     }
 
-    async fn chat(
+    async fn chat_json(
         &self,
-        request: ProviderChatRequest<'_>,
+        system_prompt: Option<&str>,
+        message: &str,
         model: &str,
         temperature: f64,
-    ) -> anyhow::Result<ProviderChatResponse> {
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        // Tool inputs must be objects; anything else goes through the prompt.
+        let Some(tool_schema) = schema
+            .as_ref()
+            .filter(|schema| schema.get("type").and_then(|t| t.as_str()) == Some("object"))
+        else {
+            let system = super::structured::json_mode_system_prompt(system_prompt, schema.as_ref());
+            let text = self
+                .chat_with_system(Some(&system), message, model, temperature)
+                .await?;
+            return super::structured::parse_json_reply(&text, schema.as_ref());
+        };
+
         let credential = self.credential.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
             )
         })?;
+        let request =
+            Self::structured_request(system_prompt, message, model, temperature, tool_schema);
+        let request = self
+            .http_client()
+            .post(format!("{}/v1/messages", self.base_url))
+            .header("anthropic-version", "2023-06-01")
+            .header("content-type", "application/json")
+            .json(&request);
+        let response = self.apply_auth(request, credential).send().await?;
 
-        let (system_prompt, mut messages) = Self::convert_messages(request.messages);
+        if !response.status().is_success() {
+            return Err(super::api_error("Anthropic", response).await);
+        }
 
-        // Auto-cache last message if conversation is long
-        if Self::should_cache_conversation(request.messages) {
-            Self::apply_cache_to_last_message(&mut messages);
+        let chat_response: ChatResponse = response.json().await?;
+        let forced = chat_response
+            .content
+            .iter()
+            .find(|block| block.kind == "tool_use")
+            .and_then(|block| block.input.clone());
+        match forced {
+            Some(value) => {
+                super::structured::validate_json(&value, tool_schema)?;
+                Ok(value)
+            }
+            None => {
+                let text = Self::parse_text_response(chat_response)?;
+                super::structured::parse_json_reply(&text, Some(tool_schema))
+            }
         }
+    }
 
-        let native_request = NativeChatRequest {
-            model: model.to_string(),
-            max_tokens: 4096,
-            system: system_prompt,
-            messages,
-            temperature,
-            tools: Self::convert_tools(request.tools),
-        };
+    async fn chat(
+        &self,
+        request: ProviderChatRequest<'_>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<ProviderChatResponse> {
+        let credential = self.credential.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
+            )
+        })?;
+
+        let native_request = self.build_native_request(&request, model, temperature);
 
         let req = self
             .http_client()
@@ -857,6 +1047,30 @@ mod tests {
         assert_eq!(json, r#"{"type":"ephemeral"}"#);
     }
 
+    #[test]
+    fn structured_request_forces_the_output_tool() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } }
+        });
+        let request =
+            AnthropicProvider::structured_request(Some("sys"), "where?", "claude", 0.0, &schema);
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["tool_choice"]["type"], "tool");
+        assert_eq!(json["tool_choice"]["name"], "structured_output");
+        assert_eq!(json["tools"][0]["name"], "structured_output");
+        assert_eq!(json["tools"][0]["input_schema"], schema);
+
+        let response: ChatResponse = serde_json::from_str(
+            r#"{"content":[{"type":"tool_use","id":"t1","name":"structured_output","input":{"city":"Oslo"}}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.content[0].input,
+            Some(serde_json::json!({"city": "Oslo"}))
+        );
+    }
+
     #[test]
     fn system_prompt_string_variant_serializes() {
         let prompt = SystemPrompt::String("You are a helpful assistant".to_string());
@@ -1192,6 +1406,66 @@ mod tests {
         }
     }
 
+    fn channel_style_system_prompt() -> String {
+        format!(
+            "## Tools\n\n{}\n\n## Available Skills\n\nweather\n\n{DYNAMIC_SYSTEM_PROMPT_MARKER}\n\n2026-01-01 10:00:00 (UTC)\n\n## Runtime\n\nHost: test\n",
+            "tool description ".repeat(300)
+        )
+    }
+
+    #[test]
+    fn prompt_caching_emits_breakpoint_on_stable_system_prefix() {
+        let messages = vec![
+            ChatMessage::system(channel_style_system_prompt()),
+            ChatMessage::user("hi"),
+        ];
+        let provider = AnthropicProvider::new(Some("key")).with_prompt_caching(true);
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: None,
+        };
+
+        let native = provider.build_native_request(&request, "claude-sonnet-4-6", 0.7);
+        let json = serde_json::to_value(&native).unwrap();
+
+        let system = json["system"].as_array().expect("system should be blocks");
+        assert_eq!(system.len(), 2);
+        assert_eq!(system[0]["cache_control"]["type"], "ephemeral");
+        assert!(system[0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("## Available Skills"));
+        assert!(system[1].get("cache_control").is_none());
+        assert!(system[1]["text"]
+            .as_str()
+            .unwrap()
+            .starts_with(DYNAMIC_SYSTEM_PROMPT_MARKER));
+    }
+
+    #[test]
+    fn prompt_caching_disabled_emits_no_cache_control() {
+        let mut messages = vec![ChatMessage::system(channel_style_system_prompt())];
+        for i in 0..6 {
+            messages.push(ChatMessage::user(format!("turn {i}")));
+        }
+        let tools = vec![ToolSpec {
+            name: "shell".to_string(),
+            description: "Run commands".to_string(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let provider = AnthropicProvider::new(Some("key")).with_prompt_caching(false);
+        let request = ProviderChatRequest {
+            messages: &messages,
+            tools: Some(&tools),
+        };
+
+        let native = provider.build_native_request(&request, "claude-sonnet-4-6", 0.7);
+        let json = serde_json::to_string(&native).unwrap();
+
+        assert!(!json.contains("cache_control"));
+        assert!(json.contains("2026-01-01 10:00:00"));
+    }
+
     #[test]
     fn backward_compatibility_native_chat_request() {
         // Test that requests without cache_control serialize identically to old format
@@ -1297,6 +1571,7 @@ mod tests {
         let provider = AnthropicProvider {
             credential: Some("test-key".to_string()),
             base_url: format!("http://{addr}"),
+            prompt_caching: true,
         };
 
         // Multi-turn conversation: system → user (Go code) → assistant (code response) → user (follow-up)
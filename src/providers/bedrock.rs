@@ -5,9 +5,9 @@
 //! using hmac/sha2 crates — no AWS SDK dependency.
 
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, ProviderCapabilities, StreamChunk, StreamError, StreamOptions, StreamResult,
-    TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
+    ChatMessage, ChatOutcome, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, ProviderCapabilities, StreamChunk, StreamError,
+    StreamOptions, StreamResult, TokenUsage, ToolCall as ProviderToolCall, ToolsPayload,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -882,6 +882,7 @@ impl BedrockProvider {
             tool_calls,
             usage,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         }
     }
 
@@ -0,0 +1,258 @@
+//! `zeroclaw providers benchmark`: latency and throughput per provider.
+//!
+//! Each provider is created through [`create_provider_with_url`] and sent the
+//! same single-message prompt `warmup + runs` times, one request at a time.
+//! Warmup requests are discarded; the measured runs are summarized as
+//! p50/p95/mean latency and, when the provider reports output token usage,
+//! tokens per second. A provider that cannot be created is skipped, and
+//! request errors are counted and reported in sanitized form.
+
+use super::traits::{ChatMessage, ChatRequest};
+use super::{create_provider_with_url, sanitize_api_error, Provider};
+use crate::config::Config;
+use anyhow::{bail, Result};
+use std::time::{Duration, Instant};
+
+/// One measured request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub latency: Duration,
+    /// Output tokens reported by the provider, when it reports usage.
+    pub output_tokens: Option<u64>,
+}
+
+/// Aggregated results for one provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkStats {
+    pub succeeded: usize,
+    pub failed: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+    /// Output tokens per second over the runs that reported usage.
+    pub tokens_per_sec: Option<f64>,
+}
+
+impl BenchmarkStats {
+    /// Summarize successful samples; `None` when every run failed.
+    pub fn from_samples(samples: &[Sample], failed: usize) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut latencies: Vec<Duration> = samples.iter().map(|s| s.latency).collect();
+        latencies.sort_unstable();
+        let total: Duration = latencies.iter().sum();
+        let count = u32::try_from(latencies.len()).unwrap_or(u32::MAX);
+
+        let (tokens, token_time) = samples
+            .iter()
+            .filter_map(|s| s.output_tokens.map(|tokens| (tokens, s.latency)))
+            .fold((0u64, Duration::ZERO), |(tokens, time), (t, latency)| {
+                (tokens + t, time + latency)
+            });
+        #[allow(clippy::cast_precision_loss)]
+        let tokens_per_sec =
+            (!token_time.is_zero()).then(|| tokens as f64 / token_time.as_secs_f64());
+
+        Some(Self {
+            succeeded: samples.len(),
+            failed,
+            p50: percentile(&latencies, 50),
+            p95: percentile(&latencies, 95),
+            mean: total / count,
+            tokens_per_sec,
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending, non-empty slice.
+fn percentile(sorted: &[Duration], pct: usize) -> Duration {
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// What to run, from the CLI.
+#[derive(Debug, Clone)]
+pub struct BenchmarkOptions {
+    pub providers: Vec<String>,
+    pub model: String,
+    pub prompt: String,
+    pub runs: usize,
+    pub warmup: usize,
+}
+
+/// Outcome for one provider.
+#[derive(Debug)]
+enum ProviderOutcome {
+    Skipped(String),
+    Failed { failed: usize, error: String },
+    Measured(BenchmarkStats),
+}
+
+pub async fn run(config: &Config, options: &BenchmarkOptions) -> Result<()> {
+    if options.runs == 0 {
+        bail!("--runs must be at least 1");
+    }
+    if options.providers.is_empty() {
+        bail!("--providers needs at least one provider name");
+    }
+
+    println!(
+        "Benchmarking model '{}' with {} run(s) per provider ({} warmup)...\n",
+        options.model, options.runs, options.warmup
+    );
+    let mut results = Vec::with_capacity(options.providers.len());
+    for name in &options.providers {
+        let outcome = match create_for_benchmark(config, name) {
+            Ok(provider) => benchmark_provider(provider.as_ref(), config, options).await,
+            Err(err) => ProviderOutcome::Skipped(sanitize_api_error(&format!("{err:#}"))),
+        };
+        results.push((name.as_str(), outcome));
+    }
+
+    println!(
+        "  {:<20} {:>7} {:>9} {:>9} {:>9} {:>9}",
+        "PROVIDER", "OK", "P50", "P95", "MEAN", "TOK/S"
+    );
+    println!("  {}", "─".repeat(68));
+    for (name, outcome) in &results {
+        match outcome {
+            ProviderOutcome::Measured(stats) => println!(
+                "  {:<20} {:>7} {:>9} {:>9} {:>9} {:>9}",
+                name,
+                format!("{}/{}", stats.succeeded, stats.succeeded + stats.failed),
+                format_ms(stats.p50),
+                format_ms(stats.p95),
+                format_ms(stats.mean),
+                stats
+                    .tokens_per_sec
+                    .map_or_else(|| "-".to_string(), |rate| format!("{rate:.1}")),
+            ),
+            ProviderOutcome::Failed { failed, error } => {
+                println!(
+                    "  {name:<20} {:>7}  all runs failed: {error}",
+                    format!("0/{failed}")
+                );
+            }
+            ProviderOutcome::Skipped(reason) => {
+                println!("  {name:<20} {:>7}  skipped: {reason}", "-");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Credentials and base URL from config apply to the configured default
+/// provider only; every other provider resolves its key from the environment.
+fn create_for_benchmark(config: &Config, name: &str) -> Result<Box<dyn Provider>> {
+    let is_default = config
+        .default_provider
+        .as_deref()
+        .is_some_and(|default| default.trim().eq_ignore_ascii_case(name.trim()));
+    if is_default {
+        create_provider_with_url(name, config.api_key.as_deref(), config.api_url.as_deref())
+    } else {
+        create_provider_with_url(name, None, None)
+    }
+}
+
+async fn benchmark_provider(
+    provider: &dyn Provider,
+    config: &Config,
+    options: &BenchmarkOptions,
+) -> ProviderOutcome {
+    let messages = [ChatMessage::user(options.prompt.clone())];
+    let mut samples = Vec::with_capacity(options.runs);
+    let mut failed = 0;
+    let mut last_error = None;
+
+    for attempt in 0..options.warmup + options.runs {
+        let started = Instant::now();
+        let result = provider
+            .chat(
+                ChatRequest {
+                    messages: &messages,
+                    tools: None,
+                },
+                &options.model,
+                config.default_temperature,
+            )
+            .await;
+        let latency = started.elapsed();
+        if attempt < options.warmup {
+            continue;
+        }
+        match result {
+            Ok(response) => samples.push(Sample {
+                latency,
+                output_tokens: response.usage.and_then(|usage| usage.output_tokens),
+            }),
+            Err(err) => {
+                failed += 1;
+                last_error = Some(sanitize_api_error(&format!("{err:#}")));
+            }
+        }
+    }
+
+    match BenchmarkStats::from_samples(&samples, failed) {
+        Some(stats) => ProviderOutcome::Measured(stats),
+        None => ProviderOutcome::Failed {
+            failed,
+            error: last_error.unwrap_or_default(),
+        },
+    }
+}
+
+fn format_ms(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(ms: u64, tokens: Option<u64>) -> Sample {
+        Sample {
+            latency: Duration::from_millis(ms),
+            output_tokens: tokens,
+        }
+    }
+
+    #[test]
+    fn stats_use_nearest_rank_percentiles() {
+        // Deliberately unsorted: 100..=2000 ms in 100 ms steps.
+        let samples: Vec<Sample> = [
+            7, 3, 20, 1, 15, 9, 12, 2, 18, 5, 4, 11, 6, 19, 8, 14, 10, 17, 13, 16,
+        ]
+        .iter()
+        .map(|step| sample(step * 100, None))
+        .collect();
+        let stats = BenchmarkStats::from_samples(&samples, 2).unwrap();
+        assert_eq!(stats.succeeded, 20);
+        assert_eq!(stats.failed, 2);
+        assert_eq!(stats.p50, Duration::from_millis(1000));
+        assert_eq!(stats.p95, Duration::from_millis(1900));
+        assert_eq!(stats.mean, Duration::from_millis(1050));
+        assert_eq!(stats.tokens_per_sec, None);
+
+        let single = BenchmarkStats::from_samples(&[sample(250, None)], 0).unwrap();
+        assert_eq!(
+            (single.p50, single.p95),
+            (Duration::from_millis(250), Duration::from_millis(250))
+        );
+        assert!(BenchmarkStats::from_samples(&[], 3).is_none());
+    }
+
+    #[test]
+    fn throughput_counts_only_runs_that_report_usage() {
+        let samples = [
+            sample(500, Some(50)),
+            sample(1500, Some(250)),
+            // No usage: excluded from both tokens and time.
+            sample(4000, None),
+        ];
+        let stats = BenchmarkStats::from_samples(&samples, 0).unwrap();
+        let rate = stats.tokens_per_sec.unwrap();
+        assert!((rate - 150.0).abs() < f64::EPSILON, "{rate}");
+    }
+}
@@ -4,9 +4,9 @@
 
 use crate::multimodal;
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, StreamChunk, StreamError, StreamOptions, StreamResult, TokenUsage,
-    ToolCall as ProviderToolCall,
+    ChatMessage, ChatOutcome, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, StreamChunk, StreamError, StreamOptions,
+    StreamResult, TokenUsage, ToolCall as ProviderToolCall,
 };
 use async_trait::async_trait;
 use futures_util::{stream, SinkExt, StreamExt};
@@ -60,8 +60,35 @@ pub enum AuthStyle {
     Bearer,
     /// `x-api-key: <key>` (used by some Chinese providers)
     XApiKey,
-    /// Custom header name
-    Custom(String),
+    /// The raw key in the named header, e.g. `api-key: <key>`.
+    Header(String),
+    /// The key in the named query parameter, e.g. `?key=<key>`.
+    QueryParam(String),
+}
+
+impl AuthStyle {
+    /// Attach `credential` to an HTTP request.
+    pub(crate) fn apply(
+        &self,
+        req: reqwest::RequestBuilder,
+        credential: &str,
+    ) -> reqwest::RequestBuilder {
+        match self {
+            Self::Bearer => req.header("Authorization", format!("Bearer {credential}")),
+            Self::XApiKey => req.header("x-api-key", credential),
+            Self::Header(name) => req.header(name.as_str(), credential),
+            Self::QueryParam(name) => req.query(&[(name.as_str(), credential)]),
+        }
+    }
+
+    /// Drop the request URL from a transport error when the URL carries the
+    /// key, so it cannot end up in logs or replies.
+    pub(crate) fn redact_error(&self, error: reqwest::Error) -> reqwest::Error {
+        match self {
+            Self::QueryParam(_) => error.without_url(),
+            _ => error,
+        }
+    }
 }
 
 /// API mode for OpenAI-compatible endpoints.
@@ -403,6 +430,8 @@ struct ApiChatRequest {
     tools: Option<Vec<serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -448,6 +477,25 @@ struct UsageInfo {
 #[derive(Debug, Deserialize)]
 struct Choice {
     message: ResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
+}
+
+impl Choice {
+    /// The refusal this choice carries, if the provider declined to answer.
+    fn refusal(&self) -> Option<ChatOutcome> {
+        let has_output = self.message.effective_content_optional().is_some()
+            || self
+                .message
+                .tool_calls
+                .as_ref()
+                .is_some_and(|calls| !calls.is_empty());
+        super::openai_refusal(
+            self.finish_reason.as_deref(),
+            self.message.refusal.as_deref(),
+            has_output,
+        )
+    }
 }
 
 /// Remove `<think>...</think>` blocks from model output.
@@ -484,6 +532,9 @@ struct ResponseMessage {
     reasoning_content: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<ToolCall>>,
+    /// Set instead of `content` when the model declines the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    refusal: Option<String>,
 }
 
 impl ResponseMessage {
@@ -936,6 +987,7 @@ fn parse_responses_chat_response(response: ResponsesResponse) -> ProviderChatRes
         tool_calls,
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -1177,10 +1229,12 @@ impl OpenAiCompatibleProvider {
             AuthStyle::XApiKey => {
                 headers.insert("x-api-key", WsHeaderValue::from_str(credential)?);
             }
-            AuthStyle::Custom(header) => {
+            AuthStyle::Header(header) => {
                 let name = HeaderName::from_bytes(header.as_bytes())?;
                 headers.insert(name, WsHeaderValue::from_str(credential)?);
             }
+            // Added to the URL by the caller.
+            AuthStyle::QueryParam(_) => {}
         }
 
         if let Some(ua) = self.user_agent.as_deref() {
@@ -1218,7 +1272,12 @@ impl OpenAiCompatibleProvider {
             tools,
         };
 
-        let ws_url = self.responses_websocket_url(model)?;
+        let mut ws_url = self.responses_websocket_url(model)?;
+        if let AuthStyle::QueryParam(name) = &self.auth_header {
+            let mut url = reqwest::Url::parse(&ws_url)?;
+            url.query_pairs_mut().append_pair(name, credential);
+            ws_url = url.into();
+        }
         let mut request = ws_url
             .into_client_request()
             .map_err(|error| anyhow::anyhow!("invalid websocket request URL: {error}"))?;
@@ -1296,7 +1355,8 @@ impl OpenAiCompatibleProvider {
         let response = self
             .apply_auth_header(self.http_client().post(&url).json(&request), credential)
             .send()
-            .await?;
+            .await
+            .map_err(|error| self.auth_header.redact_error(error))?;
 
         if !response.status().is_success() {
             let error = response.text().await?;
@@ -1339,11 +1399,7 @@ impl OpenAiCompatibleProvider {
         req: reqwest::RequestBuilder,
         credential: &str,
     ) -> reqwest::RequestBuilder {
-        match &self.auth_header {
-            AuthStyle::Bearer => req.header("Authorization", format!("Bearer {credential}")),
-            AuthStyle::XApiKey => req.header("x-api-key", credential),
-            AuthStyle::Custom(header) => req.header(header, credential),
-        }
+        self.auth_header.apply(req, credential)
     }
 
     async fn chat_via_responses(
@@ -1578,6 +1634,7 @@ impl OpenAiCompatibleProvider {
             tool_calls,
             usage: None,
             reasoning_content,
+            outcome: ChatOutcome::Completed,
         }
     }
 
@@ -1601,26 +1658,15 @@ impl OpenAiCompatibleProvider {
         .iter()
         .any(|hint| lower.contains(hint))
     }
-}
-
-#[async_trait]
-impl Provider for OpenAiCompatibleProvider {
-    fn capabilities(&self) -> crate::providers::traits::ProviderCapabilities {
-        crate::providers::traits::ProviderCapabilities {
-            // Providers that require system-prompt merging (e.g. MiniMax) also
-            // reject OpenAI-style `tools` in the request body. Fall back to
-            // prompt-guided tool calling for those providers.
-            native_tool_calling: self.native_tool_calling,
-            vision: self.supports_vision,
-        }
-    }
 
-    async fn chat_with_system(
+    /// One-shot chat completion; `response_format` switches on JSON mode.
+    async fn send_system_chat(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+        response_format: Option<serde_json::Value>,
     ) -> anyhow::Result<String> {
         let credential = self.credential.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -1661,6 +1707,7 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(false),
             tools: None,
             tool_choice: None,
+            response_format,
         };
 
         let url = self.chat_completions_url();
@@ -1686,6 +1733,7 @@ impl Provider for OpenAiCompatibleProvider {
             .apply_auth_header(self.http_client().post(&url).json(&request), credential)
             .send()
             .await
+            .map_err(|error| self.auth_header.redact_error(error))
         {
             Ok(response) => response,
             Err(chat_error) => {
@@ -1729,27 +1777,72 @@ impl Provider for OpenAiCompatibleProvider {
         let body = response.text().await?;
         let chat_response = parse_chat_response_body(&self.name, &body)?;
 
-        chat_response
+        let c = chat_response
             .choices
             .into_iter()
             .next()
-            .map(|c| {
-                // If tool_calls are present, serialize the full message as JSON
-                // so parse_tool_calls can handle the OpenAI-style format
-                if c.message.tool_calls.is_some()
-                    && c.message
-                        .tool_calls
-                        .as_ref()
-                        .map_or(false, |t| !t.is_empty())
-                {
-                    serde_json::to_string(&c.message)
-                        .unwrap_or_else(|_| c.message.effective_content())
-                } else {
-                    // No tool calls, return content (with reasoning_content fallback)
-                    c.message.effective_content()
-                }
-            })
-            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
+            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
+        if let Some(refused) = c.refusal() {
+            refused.ensure_completed()?;
+        }
+        // If tool_calls are present, serialize the full message as JSON
+        // so parse_tool_calls can handle the OpenAI-style format
+        if c.message.tool_calls.is_some()
+            && c.message
+                .tool_calls
+                .as_ref()
+                .map_or(false, |t| !t.is_empty())
+        {
+            Ok(serde_json::to_string(&c.message).unwrap_or_else(|_| c.message.effective_content()))
+        } else {
+            // No tool calls, return content (with reasoning_content fallback)
+            Ok(c.message.effective_content())
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiCompatibleProvider {
+    fn capabilities(&self) -> crate::providers::traits::ProviderCapabilities {
+        crate::providers::traits::ProviderCapabilities {
+            // Providers that require system-prompt merging (e.g. MiniMax) also
+            // reject OpenAI-style `tools` in the request body. Fall back to
+            // prompt-guided tool calling for those providers.
+            native_tool_calling: self.native_tool_calling,
+            vision: self.supports_vision,
+        }
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.send_system_chat(system_prompt, message, model, temperature, None)
+            .await
+    }
+
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let response_format = super::structured::openai_response_format(schema.as_ref());
+        let text = self
+            .send_system_chat(
+                system_prompt,
+                message,
+                model,
+                temperature,
+                Some(response_format),
+            )
+            .await?;
+        super::structured::parse_json_reply(&text, schema.as_ref())
     }
 
     async fn chat_with_history(
@@ -1790,6 +1883,7 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(false),
             tools: None,
             tool_choice: None,
+            response_format: None,
         };
 
         if self.should_use_responses_mode() {
@@ -1803,6 +1897,7 @@ impl Provider for OpenAiCompatibleProvider {
             .apply_auth_header(self.http_client().post(&url).json(&request), credential)
             .send()
             .await
+            .map_err(|error| self.auth_header.redact_error(error))
         {
             Ok(response) => response,
             Err(chat_error) => {
@@ -1845,27 +1940,27 @@ impl Provider for OpenAiCompatibleProvider {
         let body = response.text().await?;
         let chat_response = parse_chat_response_body(&self.name, &body)?;
 
-        chat_response
+        let c = chat_response
             .choices
             .into_iter()
             .next()
-            .map(|c| {
-                // If tool_calls are present, serialize the full message as JSON
-                // so parse_tool_calls can handle the OpenAI-style format
-                if c.message.tool_calls.is_some()
-                    && c.message
-                        .tool_calls
-                        .as_ref()
-                        .map_or(false, |t| !t.is_empty())
-                {
-                    serde_json::to_string(&c.message)
-                        .unwrap_or_else(|_| c.message.effective_content())
-                } else {
-                    // No tool calls, return content (with reasoning_content fallback)
-                    c.message.effective_content()
-                }
-            })
-            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
+            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
+        if let Some(refused) = c.refusal() {
+            refused.ensure_completed()?;
+        }
+        // If tool_calls are present, serialize the full message as JSON
+        // so parse_tool_calls can handle the OpenAI-style format
+        if c.message.tool_calls.is_some()
+            && c.message
+                .tool_calls
+                .as_ref()
+                .map_or(false, |t| !t.is_empty())
+        {
+            Ok(serde_json::to_string(&c.message).unwrap_or_else(|_| c.message.effective_content()))
+        } else {
+            // No tool calls, return content (with reasoning_content fallback)
+            Ok(c.message.effective_content())
+        }
     }
 
     async fn chat_with_tools(
@@ -1915,6 +2010,7 @@ impl Provider for OpenAiCompatibleProvider {
             } else {
                 Some("auto".to_string())
             },
+            response_format: None,
         };
 
         if self.should_use_responses_mode() {
@@ -1933,6 +2029,7 @@ impl Provider for OpenAiCompatibleProvider {
             .apply_auth_header(self.http_client().post(&url).json(&request), credential)
             .send()
             .await
+            .map_err(|error| self.auth_header.redact_error(error))
         {
             Ok(response) => response,
             Err(error) => {
@@ -1946,6 +2043,7 @@ impl Provider for OpenAiCompatibleProvider {
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 });
             }
         };
@@ -1976,6 +2074,9 @@ impl Provider for OpenAiCompatibleProvider {
             .into_iter()
             .next()
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
+        if let Some(refused) = choice.refusal() {
+            return Ok(ProviderChatResponse::refused(refused, usage));
+        }
 
         let text = choice.message.effective_content_optional();
         let reasoning_content = choice.message.reasoning_content;
@@ -2001,6 +2102,7 @@ impl Provider for OpenAiCompatibleProvider {
             tool_calls,
             usage,
             reasoning_content,
+            outcome: ChatOutcome::Completed,
         })
     }
 
@@ -2056,6 +2158,7 @@ impl Provider for OpenAiCompatibleProvider {
             )
             .send()
             .await
+            .map_err(|error| self.auth_header.redact_error(error))
         {
             Ok(response) => response,
             Err(chat_error) => {
@@ -2097,6 +2200,7 @@ impl Provider for OpenAiCompatibleProvider {
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 });
             }
 
@@ -2125,14 +2229,16 @@ impl Provider for OpenAiCompatibleProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
         });
-        let message = native_response
+        let choice = native_response
             .choices
             .into_iter()
             .next()
-            .map(|choice| choice.message)
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))?;
+        if let Some(refused) = choice.refusal() {
+            return Ok(ProviderChatResponse::refused(refused, usage));
+        }
 
-        let mut result = Self::parse_native_response(message);
+        let mut result = Self::parse_native_response(choice.message);
         result.usage = usage;
         Ok(result)
     }
@@ -2187,6 +2293,7 @@ impl Provider for OpenAiCompatibleProvider {
             stream: Some(options.enabled),
             tools: None,
             tool_choice: None,
+            response_format: None,
         };
 
         let url = self.chat_completions_url();
@@ -2200,20 +2307,17 @@ impl Provider for OpenAiCompatibleProvider {
             // Build request with auth
             let mut req_builder = client.post(&url).json(&request);
 
-            // Apply auth header
-            req_builder = match &auth_header {
-                AuthStyle::Bearer => {
-                    req_builder.header("Authorization", format!("Bearer {}", credential))
-                }
-                AuthStyle::XApiKey => req_builder.header("x-api-key", &credential),
-                AuthStyle::Custom(header) => req_builder.header(header, &credential),
-            };
+            req_builder = auth_header.apply(req_builder, &credential);
 
             // Set accept header for streaming
             req_builder = req_builder.header("Accept", "text/event-stream");
 
             // Send request
-            let response = match req_builder.send().await {
+            let response = match req_builder
+                .send()
+                .await
+                .map_err(|e| auth_header.redact_error(e))
+            {
                 Ok(r) => r,
                 Err(e) => {
                     let _ = tx.send(Err(StreamError::Http(e))).await;
@@ -2259,7 +2363,8 @@ impl Provider for OpenAiCompatibleProvider {
             let _ = self
                 .apply_auth_header(self.http_client().get(&url), credential)
                 .send()
-                .await?;
+                .await
+                .map_err(|error| self.auth_header.redact_error(error))?;
         }
         Ok(())
     }
@@ -2310,6 +2415,44 @@ mod tests {
             .contains("Venice API key not set"));
     }
 
+    #[tokio::test]
+    async fn chat_json_sets_response_format_from_schema() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/chat/completions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "choices": [{"message": {"content": "{\"city\": \"Oslo\"}"}}]
+            })))
+            .mount(&server)
+            .await;
+
+        let p = make_provider("test", &format!("{}/v1", server.uri()), Some("key"));
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "city": { "type": "string" } },
+            "required": ["city"]
+        });
+        let value = p
+            .chat_json(None, "where?", "model", 0.0, Some(schema.clone()))
+            .await
+            .unwrap();
+        assert_eq!(value, serde_json::json!({"city": "Oslo"}));
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+        assert_eq!(body["response_format"]["type"], "json_schema");
+        assert_eq!(body["response_format"]["json_schema"]["schema"], schema);
+
+        // Plain chat never sends a response_format.
+        p.chat_with_system(None, "hi", "model", 0.0).await.unwrap();
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[1].body).unwrap();
+        assert!(body.get("response_format").is_none());
+    }
+
     #[test]
     fn request_serializes_correctly() {
         let req = ApiChatRequest {
@@ -2329,6 +2472,7 @@ mod tests {
             stream: Some(false),
             tools: None,
             tool_choice: None,
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("llama-3.3-70b"));
@@ -2392,14 +2536,51 @@ mod tests {
     }
 
     #[test]
-    fn custom_auth_style() {
+    fn header_auth_style() {
         let p = OpenAiCompatibleProvider::new(
             "custom",
             "https://api.example.com",
             Some("key"),
-            AuthStyle::Custom("X-Custom-Key".into()),
+            AuthStyle::Header("X-Custom-Key".into()),
+        );
+        assert!(matches!(p.auth_header, AuthStyle::Header(_)));
+    }
+
+    #[test]
+    fn auth_styles_place_the_key_on_the_request() {
+        let build = |style: AuthStyle| {
+            let p = OpenAiCompatibleProvider::new(
+                "custom",
+                "https://api.example.com/v1",
+                Some("sk-secret"),
+                style,
+            );
+            p.apply_auth_header(
+                reqwest::Client::new().post(p.chat_completions_url()),
+                "sk-secret",
+            )
+            .build()
+            .unwrap()
+        };
+
+        let bearer = build(AuthStyle::Bearer);
+        assert_eq!(bearer.headers()["authorization"], "Bearer sk-secret");
+        assert_eq!(bearer.url().query(), None);
+
+        let x_api_key = build(AuthStyle::XApiKey);
+        assert_eq!(x_api_key.headers()["x-api-key"], "sk-secret");
+        assert!(x_api_key.headers().get("authorization").is_none());
+
+        let header = build(AuthStyle::Header("api-key".into()));
+        assert_eq!(header.headers()["api-key"], "sk-secret");
+        assert!(header.headers().get("authorization").is_none());
+
+        let query = build(AuthStyle::QueryParam("key".into()));
+        assert!(query.headers().get("authorization").is_none());
+        assert_eq!(
+            query.url().as_str(),
+            "https://api.example.com/v1/chat/completions?key=sk-secret"
         );
-        assert!(matches!(p.auth_header, AuthStyle::Custom(_)));
     }
 
     #[test]
@@ -2879,6 +3060,7 @@ mod tests {
                 parameters: None,
             }]),
             reasoning_content: None,
+            refusal: None,
         };
 
         let parsed = OpenAiCompatibleProvider::parse_native_response(message);
@@ -3177,6 +3359,7 @@ mod tests {
             stream: Some(false),
             tools: Some(tools),
             tool_choice: Some("auto".to_string()),
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"tools\""));
@@ -3496,6 +3679,7 @@ mod tests {
                 arguments: None,
                 parameters: None,
             }]),
+            refusal: None,
         };
 
         let parsed = OpenAiCompatibleProvider::parse_native_response(message);
@@ -3510,6 +3694,7 @@ mod tests {
             content: Some("hello".to_string()),
             reasoning_content: None,
             tool_calls: None,
+            refusal: None,
         };
 
         let parsed = OpenAiCompatibleProvider::parse_native_response(message);
@@ -12,8 +12,8 @@
 //! third-party integrations simultaneously.
 
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    ChatMessage, ChatOutcome, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -379,6 +379,7 @@ impl CopilotProvider {
             tool_calls,
             usage,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         })
     }
 
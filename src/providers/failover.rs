@@ -0,0 +1,280 @@
+//! Provider failover events.
+//!
+//! [`super::reliable::ReliableProvider`] publishes a [`FailoverEvent`] when a
+//! request is first served by a fallback provider, and again when the primary
+//! serves a request after that. Events go onto a process-wide broadcast bus
+//! ([`subscribe`]) so the channel server can notify an admin target
+//! (`[reliability] notify_channel`) without the provider layer depending on
+//! channels. Counters are kept as health metrics for `zeroclaw status`.
+
+use super::reliable::ErrorClass;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+pub const METRIC_FAILOVERS: &str = "provider_failovers_total";
+pub const METRIC_RECOVERIES: &str = "provider_recoveries_total";
+pub const METRIC_ACTIVE: &str = "provider_failovers_active";
+
+/// Notifications of one kind for one provider pair are sent at most this often.
+pub const NOTIFY_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailoverEvent {
+    /// `fallback` served a request because `primary` failed with `error_class`.
+    FailedOver {
+        primary: String,
+        fallback: String,
+        error_class: ErrorClass,
+    },
+    /// `primary` served a request again after `affected_requests` went to
+    /// fallbacks, the last of them `fallback`.
+    Recovered {
+        primary: String,
+        fallback: String,
+        affected_requests: u64,
+    },
+}
+
+impl FailoverEvent {
+    /// The admin notification for this event.
+    pub fn message(&self) -> String {
+        match self {
+            Self::FailedOver {
+                primary,
+                fallback,
+                error_class,
+            } => format!(
+                "\u{26A0}\u{FE0F} Provider failover: {primary} failed ({}), requests are going to {fallback}.",
+                error_class.as_str()
+            ),
+            Self::Recovered {
+                primary,
+                fallback,
+                affected_requests,
+            } => {
+                let requests = if *affected_requests == 1 {
+                    "request was"
+                } else {
+                    "requests were"
+                };
+                format!(
+                    "\u{2705} Provider recovered: back on {primary}; {affected_requests} {requests} served by {fallback} meanwhile."
+                )
+            }
+        }
+    }
+
+    fn rate_limit_key(&self) -> (&str, &str, bool) {
+        match self {
+            Self::FailedOver {
+                primary, fallback, ..
+            } => (primary.as_str(), fallback.as_str(), false),
+            Self::Recovered {
+                primary, fallback, ..
+            } => (primary.as_str(), fallback.as_str(), true),
+        }
+    }
+}
+
+fn bus() -> &'static broadcast::Sender<FailoverEvent> {
+    static BUS: OnceLock<broadcast::Sender<FailoverEvent>> = OnceLock::new();
+    BUS.get_or_init(|| broadcast::channel(64).0)
+}
+
+/// Receive failover events published from now on.
+pub fn subscribe() -> broadcast::Receiver<FailoverEvent> {
+    bus().subscribe()
+}
+
+static FAILOVERS: AtomicU64 = AtomicU64::new(0);
+static RECOVERIES: AtomicU64 = AtomicU64::new(0);
+static ACTIVE: AtomicU64 = AtomicU64::new(0);
+
+fn record_metrics(event: &FailoverEvent) {
+    let (counter, name) = match event {
+        FailoverEvent::FailedOver { .. } => (&FAILOVERS, METRIC_FAILOVERS),
+        FailoverEvent::Recovered { .. } => (&RECOVERIES, METRIC_RECOVERIES),
+    };
+    crate::health::set_metric(name, counter.fetch_add(1, Ordering::Relaxed) + 1);
+    crate::health::set_metric(METRIC_ACTIVE, ACTIVE.load(Ordering::Relaxed));
+}
+
+#[derive(Debug, Default)]
+struct TrackerState {
+    last_primary_error: Option<ErrorClass>,
+    /// Fallback currently serving, and requests it served so far.
+    active: Option<(String, u64)>,
+}
+
+/// Watches which provider of one fallback chain serves each request.
+#[derive(Debug)]
+pub(crate) struct FailoverTracker {
+    primary: String,
+    state: Mutex<TrackerState>,
+    events: broadcast::Sender<FailoverEvent>,
+}
+
+impl FailoverTracker {
+    pub(crate) fn new(primary: &str) -> Self {
+        Self::with_events(primary, bus().clone())
+    }
+
+    pub(crate) fn with_events(primary: &str, events: broadcast::Sender<FailoverEvent>) -> Self {
+        Self {
+            primary: primary.to_string(),
+            state: Mutex::new(TrackerState::default()),
+            events,
+        }
+    }
+
+    /// The primary provider failed an attempt.
+    pub(crate) fn primary_failed(&self, class: ErrorClass) {
+        self.state.lock().last_primary_error = Some(class);
+    }
+
+    /// `provider` answered a request.
+    pub(crate) fn served_by(&self, provider: &str) {
+        let event = {
+            let mut state = self.state.lock();
+            if provider == self.primary {
+                state.last_primary_error = None;
+                state.active.take().map(|(fallback, affected_requests)| {
+                    ACTIVE.fetch_sub(1, Ordering::Relaxed);
+                    FailoverEvent::Recovered {
+                        primary: self.primary.clone(),
+                        fallback,
+                        affected_requests,
+                    }
+                })
+            } else {
+                let error_class = state.last_primary_error.unwrap_or(ErrorClass::Internal);
+                match &mut state.active {
+                    Some((fallback, affected)) if fallback == provider => {
+                        *affected += 1;
+                        None
+                    }
+                    Some((fallback, affected)) => {
+                        // A second fallback took over; keep counting from the
+                        // first failover.
+                        *fallback = provider.to_string();
+                        *affected += 1;
+                        Some(FailoverEvent::FailedOver {
+                            primary: self.primary.clone(),
+                            fallback: provider.to_string(),
+                            error_class,
+                        })
+                    }
+                    None => {
+                        ACTIVE.fetch_add(1, Ordering::Relaxed);
+                        state.active = Some((provider.to_string(), 1));
+                        Some(FailoverEvent::FailedOver {
+                            primary: self.primary.clone(),
+                            fallback: provider.to_string(),
+                            error_class,
+                        })
+                    }
+                }
+            }
+        };
+        if let Some(event) = event {
+            tracing::warn!("{}", event.message());
+            record_metrics(&event);
+            // No subscribers (no notify channel configured) is fine.
+            let _ = self.events.send(event);
+        }
+    }
+}
+
+/// Rate limit for admin notifications: one failover and one recovery notice
+/// per provider pair within [`NOTIFY_WINDOW`], so a flapping provider does not
+/// flood the admin channel.
+#[derive(Debug, Default)]
+pub struct NotifyLimiter {
+    last_sent: HashMap<(String, String, bool), Instant>,
+}
+
+impl NotifyLimiter {
+    /// Whether `event` may be sent at `now`; records it when it may.
+    pub fn admit(&mut self, event: &FailoverEvent, now: Instant) -> bool {
+        let (primary, fallback, recovered) = event.rate_limit_key();
+        let key = (primary.to_string(), fallback.to_string(), recovered);
+        if self
+            .last_sent
+            .get(&key)
+            .is_some_and(|sent| now.duration_since(*sent) < NOTIFY_WINDOW)
+        {
+            return false;
+        }
+        self.last_sent.insert(key, now);
+        true
+    }
+}
+
+/// Pass the message of each event from `events` to `notify`, rate limited by
+/// [`NotifyLimiter`], until the bus closes.
+pub async fn forward_notifications<F, Fut>(
+    mut events: broadcast::Receiver<FailoverEvent>,
+    mut notify: F,
+) where
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut limiter = NotifyLimiter::default();
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                if limiter.admit(&event, Instant::now()) {
+                    notify(event.message()).await;
+                } else {
+                    tracing::debug!("Suppressed failover notification: {}", event.message());
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!("Skipped {skipped} failover events");
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limiter_allows_each_kind_once_per_window() {
+        let failed_over = FailoverEvent::FailedOver {
+            primary: "anthropic".into(),
+            fallback: "ollama".into(),
+            error_class: ErrorClass::Overloaded,
+        };
+        let recovered = FailoverEvent::Recovered {
+            primary: "anthropic".into(),
+            fallback: "ollama".into(),
+            affected_requests: 1,
+        };
+        let other_pair = FailoverEvent::FailedOver {
+            primary: "anthropic".into(),
+            fallback: "openai".into(),
+            error_class: ErrorClass::Overloaded,
+        };
+        let start = Instant::now();
+        let mut limiter = NotifyLimiter::default();
+
+        assert!(limiter.admit(&failed_over, start));
+        assert!(limiter.admit(&recovered, start));
+        assert!(limiter.admit(&other_pair, start));
+        assert!(!limiter.admit(&failed_over, start + Duration::from_secs(60)));
+        assert!(limiter.admit(&failed_over, start + NOTIFY_WINDOW));
+        assert_eq!(
+            recovered.message(),
+            "\u{2705} Provider recovered: back on anthropic; 1 request was served by ollama meanwhile."
+        );
+    }
+}
@@ -5,7 +5,7 @@
 //! - Google Cloud ADC (`GOOGLE_APPLICATION_CREDENTIALS`)
 
 use crate::auth::AuthService;
-use crate::providers::traits::{ChatMessage, ChatResponse, Provider, TokenUsage};
+use crate::providers::traits::{ChatMessage, ChatOutcome, ChatResponse, Provider, TokenUsage};
 use async_trait::async_trait;
 use base64::Engine;
 use directories::UserDirs;
@@ -144,6 +144,30 @@ struct GenerationConfig {
     temperature: f64,
     #[serde(rename = "maxOutputTokens")]
     max_output_tokens: u32,
+    #[serde(rename = "responseMimeType", skip_serializing_if = "Option::is_none")]
+    response_mime_type: Option<String>,
+    #[serde(rename = "responseSchema", skip_serializing_if = "Option::is_none")]
+    response_schema: Option<serde_json::Value>,
+}
+
+impl GenerationConfig {
+    fn text(temperature: f64) -> Self {
+        Self {
+            temperature,
+            max_output_tokens: 8192,
+            response_mime_type: None,
+            response_schema: None,
+        }
+    }
+
+    /// JSON mode, constrained to `schema` when one is given.
+    fn json(temperature: f64, schema: Option<serde_json::Value>) -> Self {
+        Self {
+            response_mime_type: Some("application/json".to_string()),
+            response_schema: schema,
+            ..Self::text(temperature)
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -938,7 +962,7 @@ impl GeminiProvider {
         contents: Vec<Content>,
         system_instruction: Option<Content>,
         model: &str,
-        temperature: f64,
+        generation_config: GenerationConfig,
     ) -> anyhow::Result<(String, Option<TokenUsage>)> {
         let auth = self.auth.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
@@ -985,10 +1009,7 @@ impl GeminiProvider {
         let request = GenerateContentRequest {
             contents,
             system_instruction,
-            generation_config: GenerationConfig {
-                temperature,
-                max_output_tokens: 8192,
-            },
+            generation_config,
         };
 
         let url = Self::build_generate_content_url(model, auth);
@@ -1167,11 +1188,48 @@ impl Provider for GeminiProvider {
         }];
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::text(temperature),
+            )
             .await?;
         Ok(text)
     }
 
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let system_instruction = system_prompt.map(|sys| Content {
+            role: None,
+            parts: vec![Part {
+                text: sys.to_string(),
+            }],
+        });
+        let contents = vec![Content {
+            role: Some("user".to_string()),
+            parts: vec![Part {
+                text: message.to_string(),
+            }],
+        }];
+
+        let (text, _usage) = self
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::json(temperature, schema.clone()),
+            )
+            .await?;
+        super::structured::parse_json_reply(&text, schema.as_ref())
+    }
+
     async fn chat_with_history(
         &self,
         messages: &[ChatMessage],
@@ -1219,7 +1277,12 @@ impl Provider for GeminiProvider {
         };
 
         let (text, _usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::text(temperature),
+            )
             .await?;
         Ok(text)
     }
@@ -1264,7 +1327,12 @@ impl Provider for GeminiProvider {
         };
 
         let (text, usage) = self
-            .send_generate_content(contents, system_instruction, model, temperature)
+            .send_generate_content(
+                contents,
+                system_instruction,
+                model,
+                GenerationConfig::text(temperature),
+            )
             .await?;
 
         Ok(ChatResponse {
@@ -1272,6 +1340,7 @@ impl Provider for GeminiProvider {
             tool_calls: Vec::new(),
             usage,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         })
     }
 
@@ -1536,6 +1605,18 @@ mod tests {
         assert!(url.contains("models/gemini-2.0-flash"));
     }
 
+    #[test]
+    fn json_generation_config_sets_mime_type_and_schema() {
+        let schema = serde_json::json!({"type": "object"});
+        let json = serde_json::to_value(GenerationConfig::json(0.2, Some(schema.clone()))).unwrap();
+        assert_eq!(json["responseMimeType"], "application/json");
+        assert_eq!(json["responseSchema"], schema);
+
+        let text = serde_json::to_value(GenerationConfig::text(0.2)).unwrap();
+        assert!(text.get("responseMimeType").is_none());
+        assert!(text.get("responseSchema").is_none());
+    }
+
     #[test]
     fn oauth_request_uses_bearer_auth_header() {
         let provider = test_provider(Some(test_oauth_auth("ya29.mock-token")));
@@ -1552,6 +1633,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1593,6 +1676,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1637,6 +1722,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1674,6 +1761,8 @@ mod tests {
             generation_config: GenerationConfig {
                 temperature: 0.7,
                 max_output_tokens: 8192,
+                response_mime_type: None,
+                response_schema: None,
             },
         };
 
@@ -1703,6 +1792,8 @@ mod tests {
                 generation_config: Some(GenerationConfig {
                     temperature: 0.7,
                     max_output_tokens: 8192,
+                    response_mime_type: None,
+                    response_schema: None,
                 }),
             },
         };
@@ -18,8 +18,10 @@
 
 pub mod anthropic;
 pub mod bedrock;
+pub mod benchmark;
 pub mod compatible;
 pub mod copilot;
+pub mod failover;
 pub mod gemini;
 pub mod ollama;
 pub mod openai;
@@ -27,13 +29,14 @@ pub mod openai_codex;
 pub mod openrouter;
 pub mod reliable;
 pub mod router;
+pub mod structured;
 pub mod telnyx;
 pub mod traits;
 
 #[allow(unused_imports)]
 pub use traits::{
-    ChatMessage, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderCapabilityError,
-    ToolCall, ToolResultMessage,
+    ChatMessage, ChatOutcome, ChatRequest, ChatResponse, ConversationMessage, Provider,
+    ProviderCapabilityError, ProviderHttpError, RefusedError, ToolCall, ToolResultMessage,
 };
 
 use crate::auth::AuthService;
@@ -632,6 +635,38 @@ pub(crate) fn canonical_china_provider_name(name: &str) -> Option<&'static str>
     }
 }
 
+/// The canonical id of a provider name: regional and vendor aliases map to
+/// the name `list_providers` uses (`kimi` → `moonshot`, `google` → `gemini`,
+/// `grok` → `xai`, `glm-cn` → `glm`). Unknown names, including `custom:` URLs,
+/// are returned unchanged.
+pub fn canonical_provider_name(name: &str) -> &str {
+    if let Some(canonical) = canonical_china_provider_name(name) {
+        return canonical;
+    }
+    list_providers()
+        .into_iter()
+        .find(|provider| {
+            provider.name == name || provider.aliases.iter().any(|alias| *alias == name)
+        })
+        .map_or(name, |provider| provider.name)
+}
+
+/// What two provider names must share to be served by one instance: the
+/// canonical id, the regional endpoint an alias selects, and whether the
+/// alias uses the OAuth flow.
+fn provider_instance_identity(name: &str) -> (&str, Option<&'static str>, bool) {
+    let regional_endpoint = minimax_base_url(name)
+        .or_else(|| glm_base_url(name))
+        .or_else(|| moonshot_base_url(name))
+        .or_else(|| qwen_base_url(name))
+        .or_else(|| zai_base_url(name));
+    (
+        canonical_provider_name(name),
+        regional_endpoint,
+        is_qwen_oauth_alias(name),
+    )
+}
+
 fn minimax_base_url(name: &str) -> Option<&'static str> {
     if is_minimax_cn_alias(name) {
         Some(MINIMAX_CN_BASE_URL)
@@ -698,6 +733,8 @@ pub struct ProviderRuntimeOptions {
     pub custom_provider_api_mode: Option<CompatibleApiMode>,
     pub max_tokens_override: Option<u32>,
     pub model_support_vision: Option<bool>,
+    pub prompt_caching: bool,
+    pub ollama: crate::config::OllamaProviderConfig,
 }
 
 impl Default for ProviderRuntimeOptions {
@@ -713,6 +750,8 @@ impl Default for ProviderRuntimeOptions {
             custom_provider_api_mode: None,
             max_tokens_override: None,
             model_support_vision: None,
+            prompt_caching: true,
+            ollama: crate::config::OllamaProviderConfig::default(),
         }
     }
 }
@@ -818,8 +857,27 @@ pub async fn api_error(provider: &str, response: reqwest::Response) -> anyhow::E
         .text()
         .await
         .unwrap_or_else(|_| "<failed to read provider error body>".to_string());
-    let sanitized = sanitize_api_error(&body);
-    anyhow::anyhow!("{provider} API error ({status}): {sanitized}")
+    ProviderHttpError {
+        provider: provider.to_string(),
+        status,
+        body: sanitize_api_error(&body),
+    }
+    .into()
+}
+
+/// The refusal in an OpenAI-style completion choice: an explicit `refusal`
+/// message, or a `content_filter` finish that left nothing to show.
+/// `has_output` is whether the choice carries text or tool calls.
+pub(crate) fn openai_refusal(
+    finish_reason: Option<&str>,
+    refusal: Option<&str>,
+    has_output: bool,
+) -> Option<ChatOutcome> {
+    if refusal.is_some_and(|refusal| !refusal.trim().is_empty()) {
+        return Some(ChatOutcome::refused(refusal, "refusal"));
+    }
+    (finish_reason == Some("content_filter") && !has_output)
+        .then(|| ChatOutcome::refused(None, "content_filter"))
 }
 
 /// Resolve API key for a provider from config and environment variables.
@@ -1067,18 +1125,19 @@ fn create_provider_with_url_and_options(
             key,
             options.max_tokens_override,
         ))),
-        "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
+        "anthropic" => Ok(Box::new(
+            anthropic::AnthropicProvider::new(key).with_prompt_caching(options.prompt_caching),
+        )),
         "openai" => Ok(Box::new(openai::OpenAiProvider::with_base_url_and_max_tokens(
             api_url,
             key,
             options.max_tokens_override,
         ))),
         // Ollama uses api_url for custom base URL (e.g. remote Ollama instance)
-        "ollama" => Ok(Box::new(ollama::OllamaProvider::new_with_reasoning(
-            api_url,
-            key,
-            options.reasoning_enabled,
-        ))),
+        "ollama" => Ok(Box::new(
+            ollama::OllamaProvider::new_with_reasoning(api_url, key, options.reasoning_enabled)
+                .with_request_options(options.ollama.clone()),
+        )),
         "gemini" | "google" | "google-gemini" => {
             let state_dir = options
                 .zeroclaw_dir
@@ -1350,10 +1409,10 @@ fn create_provider_with_url_and_options(
                 "Anthropic-custom provider",
                 "anthropic-custom:https://your-api.com",
             )?;
-            Ok(Box::new(anthropic::AnthropicProvider::with_base_url(
-                key,
-                Some(&base_url),
-            )))
+            Ok(Box::new(
+                anthropic::AnthropicProvider::with_base_url(key, Some(&base_url))
+                    .with_prompt_caching(options.prompt_caching),
+            ))
         }
 
         _ => anyhow::bail!(
@@ -1457,7 +1516,8 @@ pub fn create_resilient_provider_with_options(
     )
     .with_api_keys(reliability.api_keys.clone())
     .with_model_fallbacks(reliability.model_fallbacks.clone())
-    .with_vision_override(options.model_support_vision);
+    .with_vision_override(options.model_support_vision)
+    .with_fallback_on_refusal(reliability.fallback_on_refusal);
 
     Ok(Box::new(reliable))
 }
@@ -1485,6 +1545,16 @@ pub fn create_routed_provider(
 }
 
 /// Create a routed provider using explicit runtime options.
+/// Inputs a routed provider instance was built from.
+#[derive(PartialEq)]
+struct RoutedInstance<'a> {
+    identity: (&'a str, Option<&'static str>, bool),
+    key: Option<&'a str>,
+    url: Option<&'a str>,
+    transport: Option<String>,
+    max_tokens: Option<u32>,
+}
+
 pub fn create_routed_provider_with_options(
     primary_name: &str,
     api_key: Option<&str>,
@@ -1512,8 +1582,59 @@ pub fn create_routed_provider_with_options(
         reliability,
         options,
     )?;
+    let (providers, routes) = routed_providers(
+        primary_name,
+        default_provider,
+        api_key,
+        api_url,
+        reliability,
+        model_routes,
+        options,
+    );
+
+    let fallbacks = model_routes
+        .iter()
+        .filter(|route| !route.fallback.is_empty())
+        .map(|route| (route.hint.clone(), route.fallback.clone()))
+        .collect();
+
+    Ok(Box::new(
+        router::RouterProvider::new(providers, routes, default_model.to_string())
+            .with_vision_override(options.model_support_vision)
+            .with_route_fallbacks(fallbacks)
+            .with_fallback_on_refusal(reliability.fallback_on_refusal),
+    ))
+}
+
+/// The provider instances and hint routes of a routed provider, with the
+/// primary provider first. Routes that fail to initialize are left out.
+fn routed_providers(
+    primary_name: &str,
+    default_provider: Box<dyn Provider>,
+    api_key: Option<&str>,
+    api_url: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    model_routes: &[crate::config::ModelRouteConfig],
+    options: &ProviderRuntimeOptions,
+) -> (
+    Vec<(String, Box<dyn Provider>)>,
+    Vec<(String, router::Route)>,
+) {
     let mut providers: Vec<(String, Box<dyn Provider>)> =
         vec![(primary_name.to_string(), default_provider)];
+    let primary_identity = provider_instance_identity(primary_name);
+    // Instances built so far, keyed by everything that went into building
+    // them, so routes that only differ by an alias share one instance.
+    let mut built: Vec<(RoutedInstance<'_>, String)> = vec![(
+        RoutedInstance {
+            identity: primary_identity,
+            key: api_key,
+            url: api_url,
+            transport: options.provider_transport.clone(),
+            max_tokens: None,
+        },
+        primary_name.to_string(),
+    )];
 
     // Build hint routes with dedicated provider instances so per-route API keys
     // and max_tokens overrides do not bleed across routes.
@@ -1525,9 +1646,8 @@ pub fn create_routed_provider_with_options(
         });
         let key = routed_credential.or(api_key);
         // Only use api_url for routes targeting the same provider namespace.
-        let url = (route.provider == primary_name)
-            .then_some(api_url)
-            .flatten();
+        let identity = provider_instance_identity(&route.provider);
+        let url = (identity == primary_identity).then_some(api_url).flatten();
 
         let mut route_options = options.clone();
         if let Some(transport) = route
@@ -1539,6 +1659,24 @@ pub fn create_routed_provider_with_options(
             route_options.provider_transport = Some(transport.to_string());
         }
 
+        let instance = RoutedInstance {
+            identity,
+            key,
+            url,
+            transport: route_options.provider_transport.clone(),
+            max_tokens: route.max_tokens,
+        };
+        if let Some((_, provider_id)) = built.iter().find(|(existing, _)| *existing == instance) {
+            routes.push((
+                route.hint.clone(),
+                router::Route {
+                    provider_name: provider_id.clone(),
+                    model: route.model.clone(),
+                },
+            ));
+            continue;
+        }
+
         match create_resilient_provider_with_options(
             &route.provider,
             key,
@@ -1549,6 +1687,7 @@ pub fn create_routed_provider_with_options(
             Ok(provider) => {
                 let provider_id = format!("{}#{}", route.provider, route.hint);
                 providers.push((provider_id.clone(), provider));
+                built.push((instance, provider_id.clone()));
                 routes.push((
                     route.hint.clone(),
                     router::Route {
@@ -1569,11 +1708,83 @@ pub fn create_routed_provider_with_options(
 
     // Keep only successfully initialized routed providers and preserve
     // their provider-id bindings (e.g. "<provider>#<hint>").
+    (providers, routes)
+}
 
-    Ok(Box::new(
-        router::RouterProvider::new(providers, routes, default_model.to_string())
-            .with_vision_override(options.model_support_vision),
-    ))
+/// Check that every provider named in `config` can be constructed.
+///
+/// Runs `default_provider` and each `model_routes[].provider` through the same
+/// factory calls used at startup (including `custom:`/`anthropic-custom:` URL
+/// checks) without making any network requests. Routed providers that fail are
+/// only logged at runtime, so this is the place they surface as errors. All
+/// problems are reported together, each prefixed with its config path.
+pub fn validate_configured_providers(config: &crate::config::Config) -> anyhow::Result<()> {
+    let options = ProviderRuntimeOptions {
+        provider_transport: config.effective_provider_transport(),
+        ..ProviderRuntimeOptions::default()
+    };
+    let mut problems = Vec::new();
+
+    let primary_name = config.default_provider.as_deref();
+    if let Some(name) = primary_name {
+        let created = match name {
+            "openai-codex" | "openai_codex" | "codex" => {
+                create_provider_with_options(name, config.api_key.as_deref(), &options)
+            }
+            _ => create_provider_with_url_and_options(
+                name,
+                config.api_key.as_deref(),
+                config.api_url.as_deref(),
+                &options,
+            ),
+        };
+        if let Err(error) = created {
+            problems.push(format!("default_provider = \"{name}\": {error}"));
+        }
+    }
+
+    for (index, route) in config.model_routes.iter().enumerate() {
+        let key = route
+            .api_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+            .or(config.api_key.as_deref());
+        let url = (Some(route.provider.as_str()) == primary_name)
+            .then_some(config.api_url.as_deref())
+            .flatten();
+        let mut route_options = options.clone();
+        if let Some(transport) = route
+            .transport
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            route_options.provider_transport = Some(transport.to_string());
+        }
+
+        let created = match route.provider.as_str() {
+            "openai-codex" | "openai_codex" | "codex" => {
+                create_provider_with_options(&route.provider, key, &route_options)
+            }
+            name => create_provider_with_url_and_options(name, key, url, &route_options),
+        };
+        if let Err(error) = created {
+            problems.push(format!(
+                "model_routes[{index}].provider = \"{}\" (hint \"{}\"): {error}",
+                route.provider, route.hint
+            ));
+        }
+    }
+
+    if !problems.is_empty() {
+        anyhow::bail!(
+            "{} invalid provider reference(s):\n  - {}",
+            problems.len(),
+            problems.join("\n  - ")
+        );
+    }
+    Ok(())
 }
 
 /// Information about a supported provider for display purposes.
@@ -2663,6 +2874,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         let provider = create_resilient_provider(
@@ -2702,6 +2916,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         // Primary uses a ZAI key; fallbacks (lmstudio, ollama) should NOT
@@ -2724,6 +2941,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         let provider =
@@ -2750,6 +2970,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -2782,6 +3005,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         let provider = create_resilient_provider("zai", Some("zai-test-key"), None, &reliability);
@@ -3091,6 +3317,7 @@ mod tests {
             max_tokens: Some(4096),
             api_key: None,
             transport: None,
+            fallback: Vec::new(),
         }];
 
         let provider = create_routed_provider_with_options(
@@ -3105,6 +3332,91 @@ mod tests {
         assert!(provider.is_ok());
     }
 
+    #[test]
+    fn aliases_collapse_to_canonical_provider_names() {
+        for (alias, canonical) in [
+            ("kimi", "moonshot"),
+            ("moonshot-intl", "moonshot"),
+            ("google", "gemini"),
+            ("google-gemini", "gemini"),
+            ("grok", "xai"),
+            ("zhipu", "glm"),
+            ("glm-cn", "glm"),
+            ("dashscope", "qwen"),
+            ("openrouter", "openrouter"),
+        ] {
+            assert_eq!(canonical_provider_name(alias), canonical, "{alias}");
+        }
+        assert_eq!(
+            canonical_provider_name("custom:https://llm.example.com/v1"),
+            "custom:https://llm.example.com/v1"
+        );
+        assert_eq!(canonical_provider_name("not-a-provider"), "not-a-provider");
+    }
+
+    #[test]
+    fn regional_aliases_keep_separate_instances() {
+        assert_eq!(
+            provider_instance_identity("kimi"),
+            provider_instance_identity("moonshot")
+        );
+        assert_ne!(
+            provider_instance_identity("moonshot-cn"),
+            provider_instance_identity("moonshot-intl")
+        );
+        assert_ne!(
+            provider_instance_identity("qwen-oauth"),
+            provider_instance_identity("qwen-cn")
+        );
+    }
+
+    #[test]
+    fn routed_provider_shares_instances_across_aliases() {
+        let reliability = crate::config::ReliabilityConfig::default();
+        let route =
+            |hint: &str, provider: &str, api_key: Option<&str>| crate::config::ModelRouteConfig {
+                hint: hint.to_string(),
+                provider: provider.to_string(),
+                model: "gemini-2.0-flash".to_string(),
+                max_tokens: None,
+                api_key: api_key.map(str::to_string),
+                transport: None,
+                fallback: Vec::new(),
+            };
+        let routes = vec![
+            route("fast", "google", None),
+            route("vision", "google-gemini", None),
+            route("billing", "google", Some("other-key")),
+        ];
+
+        let options = ProviderRuntimeOptions::default();
+        let default_provider = create_resilient_provider_with_options(
+            "gemini",
+            Some("gemini-test-key"),
+            None,
+            &reliability,
+            &options,
+        )
+        .unwrap();
+        let (providers, routes) = routed_providers(
+            "gemini",
+            default_provider,
+            Some("gemini-test-key"),
+            None,
+            &reliability,
+            &routes,
+            &options,
+        );
+
+        let ids: Vec<&str> = providers.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, ["gemini", "google#billing"]);
+        let targets: Vec<&str> = routes
+            .iter()
+            .map(|(_, route)| route.provider_name.as_str())
+            .collect();
+        assert_eq!(targets, ["gemini", "gemini", "google#billing"]);
+    }
+
     // --- parse_provider_profile ---
 
     #[test]
@@ -3153,6 +3465,64 @@ mod tests {
 
     // --- resilient fallback with profile syntax ---
 
+    fn route(hint: &str, provider: &str) -> crate::config::ModelRouteConfig {
+        crate::config::ModelRouteConfig {
+            hint: hint.into(),
+            provider: provider.into(),
+            model: "some-model".into(),
+            max_tokens: None,
+            api_key: None,
+            transport: None,
+            fallback: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn validate_configured_providers_accepts_known_names() {
+        let mut config = crate::config::Config::default();
+        config.default_provider = Some("openrouter".into());
+        config.model_routes = vec![
+            route("fast", "groq"),
+            route("local", "custom:http://localhost:8080/v1"),
+        ];
+
+        assert!(validate_configured_providers(&config).is_ok());
+    }
+
+    #[test]
+    fn validate_configured_providers_rejects_bad_default_provider() {
+        let mut config = crate::config::Config::default();
+        config.default_provider = Some("opnrouter".into());
+
+        let message = validate_configured_providers(&config)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("1 invalid provider reference"));
+        assert!(message.contains("default_provider = \"opnrouter\""));
+    }
+
+    #[test]
+    fn validate_configured_providers_reports_every_bad_route() {
+        let mut config = crate::config::Config::default();
+        config.default_provider = Some("openrouter".into());
+        config.model_routes = vec![
+            route("fast", "groq"),
+            route("reasoning", "not-a-provider"),
+            route("custom", "custom:ftp://example.com"),
+        ];
+
+        let message = validate_configured_providers(&config)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("2 invalid provider reference(s)"));
+        assert!(
+            message.contains("model_routes[1].provider = \"not-a-provider\" (hint \"reasoning\")")
+        );
+        assert!(message.contains("model_routes[2].provider"));
+        assert!(message.contains("http:// or https://"));
+        assert!(!message.contains("model_routes[0]"));
+    }
+
     #[test]
     fn resilient_fallback_with_profile_syntax() {
         let _guard = env_lock();
@@ -3167,6 +3537,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         // openai-codex resolves its own OAuth credential; it should not
@@ -3196,6 +3569,9 @@ mod tests {
             channel_max_backoff_secs: 60,
             scheduler_poll_secs: 15,
             scheduler_retries: 2,
+            notify_channel: None,
+            notify_recipient: None,
+            fallback_on_refusal: false,
         };
 
         let provider = create_resilient_provider("ollama", None, None, &reliability);
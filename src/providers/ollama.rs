@@ -1,16 +1,27 @@
+use crate::config::OllamaProviderConfig;
 use crate::multimodal;
 use crate::providers::traits::{
-    ChatMessage, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
+    ChatMessage, ChatOutcome, ChatResponse, Provider, ProviderCapabilities, TokenUsage, ToolCall,
 };
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Context window Ollama applies when no `num_ctx` is sent (older releases).
+const OLLAMA_DEFAULT_NUM_CTX: u64 = 2048;
+
+/// Tokens kept free for the reply when checking whether a prompt fits.
+const REPLY_HEADROOM_TOKENS: u64 = 512;
+
 pub struct OllamaProvider {
     base_url: String,
     api_key: Option<String>,
     reasoning_enabled: Option<bool>,
+    request_options: OllamaProviderConfig,
+    /// Maximum context length per model from `/api/show` (`None` = unknown).
+    model_context: Mutex<HashMap<String, Option<u64>>>,
 }
 
 // ─── Request Structures ───────────────────────────────────────────────────────
@@ -22,6 +33,8 @@ struct ChatRequest {
     stream: bool,
     options: Options,
     #[serde(skip_serializing_if = "Option::is_none")]
+    keep_alive: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     think: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     tools: Option<Vec<serde_json::Value>>,
@@ -56,6 +69,15 @@ struct OutgoingFunction {
 #[derive(Debug, Serialize)]
 struct Options {
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<i32>,
+}
+
+#[derive(Debug, Serialize)]
+struct ShowRequest<'a> {
+    model: &'a str,
 }
 
 // ─── Response Structures ──────────────────────────────────────────────────────
@@ -93,6 +115,76 @@ struct OllamaFunction {
     arguments: serde_json::Value,
 }
 
+#[derive(Debug, Deserialize)]
+struct ShowResponse {
+    #[serde(default)]
+    model_info: HashMap<String, serde_json::Value>,
+}
+
+impl ShowResponse {
+    /// `model_info` keys are architecture-prefixed, e.g. `llama.context_length`.
+    fn context_length(&self) -> Option<u64> {
+        self.model_info
+            .iter()
+            .filter(|(key, _)| key.ends_with(".context_length"))
+            .find_map(|(_, value)| value.as_u64())
+    }
+}
+
+/// How an estimated prompt compares to the context window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContextFit {
+    Fits,
+    /// `auto_num_ctx` raised the window for this request.
+    Raised {
+        from: u64,
+        to: u64,
+    },
+    /// The prompt likely exceeds `num_ctx` and Ollama will truncate it.
+    Overflow {
+        estimated: u64,
+        num_ctx: u64,
+        model_max: Option<u64>,
+    },
+}
+
+impl ContextFit {
+    fn check(
+        estimated: u64,
+        configured: Option<u64>,
+        model_max: Option<u64>,
+        auto_raise: bool,
+    ) -> Self {
+        let current = configured.unwrap_or(OLLAMA_DEFAULT_NUM_CTX);
+        let needed = estimated + REPLY_HEADROOM_TOKENS;
+        if needed <= current {
+            return Self::Fits;
+        }
+
+        let raised = model_max
+            .filter(|max| auto_raise && *max > current)
+            .map(|max| needed.next_power_of_two().min(max));
+        match raised {
+            Some(to) if to >= needed => Self::Raised { from: current, to },
+            raised => Self::Overflow {
+                estimated,
+                num_ctx: raised.unwrap_or(current),
+                model_max,
+            },
+        }
+    }
+
+    /// `num_ctx` to send; stays unset unless configured or raised.
+    fn num_ctx(self, configured: Option<u64>) -> Option<u64> {
+        let current = configured.unwrap_or(OLLAMA_DEFAULT_NUM_CTX);
+        match self {
+            Self::Raised { to, .. } => Some(to),
+            Self::Overflow { num_ctx, .. } if num_ctx != current => Some(num_ctx),
+            _ => configured,
+        }
+    }
+}
+
 // ─── Implementation ───────────────────────────────────────────────────────────
 
 impl OllamaProvider {
@@ -127,9 +219,17 @@ impl OllamaProvider {
             base_url: Self::normalize_base_url(base_url.unwrap_or("http://localhost:11434")),
             api_key,
             reasoning_enabled,
+            request_options: OllamaProviderConfig::default(),
+            model_context: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Apply `[provider.ollama]` options (`keep_alive`, `num_ctx`, `num_predict`).
+    pub fn with_request_options(mut self, options: OllamaProviderConfig) -> Self {
+        self.request_options = options;
+        self
+    }
+
     fn is_local_endpoint(&self) -> bool {
         reqwest::Url::parse(&self.base_url)
             .ok()
@@ -210,12 +310,123 @@ impl OllamaProvider {
             model: model.to_string(),
             messages,
             stream: false,
-            options: Options { temperature },
+            options: Options {
+                temperature,
+                num_ctx: self.request_options.num_ctx.map(u64::from),
+                num_predict: self.request_options.num_predict,
+            },
+            keep_alive: self.request_options.keep_alive.clone(),
             think: self.reasoning_enabled,
             tools: tools.map(|t| t.to_vec()),
         }
     }
 
+    /// Cheap token estimate (~4 characters per token) for the full request.
+    fn estimate_prompt_tokens(request: &ChatRequest) -> u64 {
+        let message_chars: usize = request
+            .messages
+            .iter()
+            .map(|message| {
+                message.content.as_ref().map_or(0, String::len)
+                    + message.tool_calls.as_ref().map_or(0, |calls| {
+                        calls
+                            .iter()
+                            .map(|call| {
+                                call.function.name.len() + call.function.arguments.to_string().len()
+                            })
+                            .sum()
+                    })
+            })
+            .sum();
+        let tool_chars = request.tools.as_ref().map_or(0, |tools| {
+            serde_json::to_string(tools).map_or(0, |raw| raw.len())
+        });
+        let per_message_overhead = 4 * request.messages.len() as u64;
+        ((message_chars + tool_chars) as u64).div_ceil(4) + per_message_overhead
+    }
+
+    /// Maximum context length of `model`, queried once via `/api/show`.
+    async fn model_context_length(&self, model: &str, should_auth: bool) -> Option<u64> {
+        if let Some(known) = self.model_context.lock().get(model) {
+            return *known;
+        }
+
+        let url = format!("{}/api/show", self.base_url);
+        let mut request_builder = self.http_client().post(&url).json(&ShowRequest { model });
+        if should_auth {
+            if let Some(key) = self.api_key.as_ref() {
+                request_builder = request_builder.bearer_auth(key);
+            }
+        }
+
+        let context_length = match request_builder.send().await {
+            Ok(response) if response.status().is_success() => response
+                .json::<ShowResponse>()
+                .await
+                .ok()
+                .and_then(|show| show.context_length()),
+            Ok(response) => {
+                tracing::debug!(
+                    "Ollama /api/show for '{model}' returned {}",
+                    response.status()
+                );
+                None
+            }
+            Err(err) => {
+                tracing::debug!("Ollama /api/show for '{model}' failed: {err}");
+                None
+            }
+        };
+        self.model_context
+            .lock()
+            .insert(model.to_string(), context_length);
+        context_length
+    }
+
+    /// Compare the estimated prompt with the context window, raising `num_ctx`
+    /// when allowed and warning when Ollama would truncate the prompt.
+    async fn fit_context(&self, request: &mut ChatRequest, should_auth: bool) -> ContextFit {
+        let estimated = Self::estimate_prompt_tokens(request);
+        let configured = self.request_options.num_ctx.map(u64::from);
+        let current = configured.unwrap_or(OLLAMA_DEFAULT_NUM_CTX);
+        if estimated + REPLY_HEADROOM_TOKENS <= current {
+            return ContextFit::Fits;
+        }
+
+        let model_max = self.model_context_length(&request.model, should_auth).await;
+        let fit = ContextFit::check(
+            estimated,
+            configured,
+            model_max,
+            self.request_options.auto_num_ctx,
+        );
+        request.options.num_ctx = fit.num_ctx(configured);
+
+        match fit {
+            ContextFit::Fits => {}
+            ContextFit::Raised { from, to } => tracing::info!(
+                "Raising Ollama num_ctx for model '{}' from {from} to {to} (prompt ~{estimated} tokens)",
+                request.model
+            ),
+            ContextFit::Overflow {
+                estimated,
+                num_ctx,
+                model_max,
+            } => {
+                let max_hint = model_max
+                    .map(|max| format!(" (model maximum: {max})"))
+                    .unwrap_or_default();
+                tracing::warn!(
+                    "⚠️  Ollama prompt for model '{}' is ~{estimated} tokens but num_ctx is {num_ctx}; \
+                     Ollama will silently truncate it, dropping the start of the system prompt. \
+                     Raise [provider.ollama] num_ctx{max_hint} or set auto_num_ctx = true.",
+                    request.model
+                );
+            }
+        }
+        fit
+    }
+
     fn convert_user_message_content(&self, content: &str) -> (Option<String>, Option<Vec<String>>) {
         let (cleaned, image_refs) = multimodal::parse_image_markers(content);
         if image_refs.is_empty() {
@@ -353,16 +564,18 @@ impl OllamaProvider {
         should_auth: bool,
         tools: Option<&[serde_json::Value]>,
     ) -> anyhow::Result<ApiChatResponse> {
-        let request = self.build_chat_request(messages, model, temperature, tools);
+        let mut request = self.build_chat_request(messages, model, temperature, tools);
+        self.fit_context(&mut request, should_auth).await;
 
         let url = format!("{}/api/chat", self.base_url);
 
         tracing::debug!(
-            "Ollama request: url={} model={} message_count={} temperature={} think={:?} tool_count={}",
+            "Ollama request: url={} model={} message_count={} temperature={} num_ctx={:?} think={:?} tool_count={}",
             url,
             model,
             request.messages.len(),
             temperature,
+            request.options.num_ctx,
             request.think,
             request.tools.as_ref().map_or(0, |t| t.len()),
         );
@@ -649,6 +862,7 @@ impl Provider for OllamaProvider {
                 tool_calls,
                 usage,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
 
@@ -667,6 +881,7 @@ impl Provider for OllamaProvider {
             tool_calls: vec![],
             usage,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         })
     }
 
@@ -714,6 +929,7 @@ impl Provider for OllamaProvider {
             tool_calls: vec![],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         })
     }
 }
@@ -1069,4 +1285,193 @@ mod tests {
         assert!(resp.prompt_eval_count.is_none());
         assert!(resp.eval_count.is_none());
     }
+
+    fn user_message(content: &str) -> Message {
+        Message {
+            role: "user".to_string(),
+            content: Some(content.to_string()),
+            images: None,
+            tool_calls: None,
+            tool_name: None,
+        }
+    }
+
+    #[test]
+    fn request_omits_ollama_options_when_unset() {
+        let provider = OllamaProvider::new(None, None);
+        let request = provider.build_chat_request(vec![user_message("hello")], "llama3", 0.7, None);
+
+        let json = serde_json::to_value(request).unwrap();
+        assert!(json.get("keep_alive").is_none());
+        assert_eq!(json["options"], serde_json::json!({"temperature": 0.7}));
+    }
+
+    #[test]
+    fn request_includes_configured_ollama_options() {
+        let provider = OllamaProvider::new(None, None).with_request_options(OllamaProviderConfig {
+            keep_alive: Some("10m".into()),
+            num_ctx: Some(8192),
+            num_predict: Some(256),
+            auto_num_ctx: false,
+        });
+        let request = provider.build_chat_request(vec![user_message("hello")], "llama3", 0.7, None);
+
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["keep_alive"], "10m");
+        assert_eq!(json["options"]["num_ctx"], 8192);
+        assert_eq!(json["options"]["num_predict"], 256);
+    }
+
+    #[test]
+    fn context_fit_checks_estimate_against_window() {
+        assert_eq!(ContextFit::check(1000, None, None, false), ContextFit::Fits);
+        assert_eq!(
+            ContextFit::check(5000, None, Some(32768), false),
+            ContextFit::Overflow {
+                estimated: 5000,
+                num_ctx: OLLAMA_DEFAULT_NUM_CTX,
+                model_max: Some(32768),
+            }
+        );
+        assert_eq!(
+            ContextFit::check(5000, Some(4096), Some(32768), true),
+            ContextFit::Raised {
+                from: 4096,
+                to: 8192
+            }
+        );
+
+        // Even the model maximum is too small: raise as far as possible, still warn.
+        let fit = ContextFit::check(9000, None, Some(8192), true);
+        assert_eq!(
+            fit,
+            ContextFit::Overflow {
+                estimated: 9000,
+                num_ctx: 8192,
+                model_max: Some(8192),
+            }
+        );
+        assert_eq!(fit.num_ctx(None), Some(8192));
+
+        // Without a raise, an unset num_ctx stays unset.
+        assert_eq!(
+            ContextFit::check(5000, None, None, true).num_ctx(None),
+            None
+        );
+    }
+
+    #[test]
+    fn show_response_extracts_context_length() {
+        let show: ShowResponse = serde_json::from_str(
+            r#"{"model_info": {"general.architecture": "llama", "llama.context_length": 131072}}"#,
+        )
+        .unwrap();
+        assert_eq!(show.context_length(), Some(131_072));
+
+        let show: ShowResponse = serde_json::from_str("{}").unwrap();
+        assert_eq!(show.context_length(), None);
+    }
+
+    async fn mock_ollama(context_length: u64) -> wiremock::MockServer {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/api/show"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "model_info": {"llama.context_length": context_length}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/api/chat"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "message": {"content": "ok"}
+            })))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn chat_bodies(server: &wiremock::MockServer) -> Vec<serde_json::Value> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path() == "/api/chat")
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn long_prompt_raises_num_ctx_from_model_maximum() {
+        let server = mock_ollama(32768).await;
+        let provider = OllamaProvider::new(Some(&server.uri()), None).with_request_options(
+            OllamaProviderConfig {
+                keep_alive: Some("10m".into()),
+                auto_num_ctx: true,
+                ..OllamaProviderConfig::default()
+            },
+        );
+        let system_prompt = "a".repeat(20_000);
+
+        for _ in 0..2 {
+            let reply = provider
+                .chat_with_system(Some(&system_prompt), "hi", "llama3", 0.7)
+                .await
+                .unwrap();
+            assert_eq!(reply, "ok");
+        }
+
+        let bodies = chat_bodies(&server).await;
+        assert_eq!(bodies.len(), 2);
+        for body in &bodies {
+            assert_eq!(body["keep_alive"], "10m");
+            assert_eq!(body["options"]["num_ctx"], 8192);
+            assert!(body["options"].get("num_predict").is_none());
+        }
+
+        let show_requests = server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|request| request.url.path() == "/api/show")
+            .count();
+        assert_eq!(show_requests, 1, "model maximum is cached per model");
+    }
+
+    #[tokio::test]
+    async fn long_prompt_without_opt_in_is_sent_unchanged() {
+        let server = mock_ollama(32768).await;
+        let provider = OllamaProvider::new(Some(&server.uri()), None);
+        let system_prompt = "a".repeat(20_000);
+
+        let mut request =
+            provider.build_chat_request(vec![user_message(&system_prompt)], "llama3", 0.7, None);
+        let fit = provider.fit_context(&mut request, false).await;
+        assert!(matches!(
+            fit,
+            ContextFit::Overflow {
+                num_ctx: OLLAMA_DEFAULT_NUM_CTX,
+                model_max: Some(32768),
+                ..
+            }
+        ));
+        assert!(request.options.num_ctx.is_none());
+
+        provider
+            .chat_with_system(Some(&system_prompt), "hi", "llama3", 0.7)
+            .await
+            .unwrap();
+        let bodies = chat_bodies(&server).await;
+        assert_eq!(bodies.len(), 1);
+        assert!(bodies[0].get("keep_alive").is_none());
+        assert_eq!(
+            bodies[0]["options"],
+            serde_json::json!({"temperature": 0.7})
+        );
+    }
 }
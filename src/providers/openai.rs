@@ -1,6 +1,6 @@
 use crate::providers::traits::{
-    ChatMessage, ChatRequest as ProviderChatRequest, ChatResponse as ProviderChatResponse,
-    Provider, TokenUsage, ToolCall as ProviderToolCall,
+    ChatMessage, ChatOutcome, ChatRequest as ProviderChatRequest,
+    ChatResponse as ProviderChatResponse, Provider, TokenUsage, ToolCall as ProviderToolCall,
 };
 use crate::tools::ToolSpec;
 use async_trait::async_trait;
@@ -20,6 +20,8 @@ struct ChatRequest {
     temperature: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Serialize)]
@@ -36,6 +38,8 @@ struct ChatResponse {
 #[derive(Debug, Deserialize)]
 struct Choice {
     message: ResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -45,6 +49,9 @@ struct ResponseMessage {
     /// Reasoning/thinking models may return output in `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    /// Set instead of `content` when the model declines the request.
+    #[serde(default)]
+    refusal: Option<String>,
 }
 
 impl ResponseMessage {
@@ -145,6 +152,8 @@ struct UsageInfo {
 #[derive(Debug, Deserialize)]
 struct NativeChoice {
     message: NativeResponseMessage,
+    #[serde(default)]
+    finish_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -154,6 +163,9 @@ struct NativeResponseMessage {
     /// Reasoning/thinking models may return output in `reasoning_content`.
     #[serde(default)]
     reasoning_content: Option<String>,
+    /// Set instead of `content` when the model declines the request.
+    #[serde(default)]
+    refusal: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<NativeToolCall>>,
 }
@@ -301,22 +313,42 @@ impl OpenAiProvider {
             tool_calls,
             usage: None,
             reasoning_content,
+            outcome: ChatOutcome::Completed,
+        }
+    }
+
+    /// [`Self::parse_native_response`], or the refusal the choice carries.
+    fn parse_native_choice(choice: NativeChoice) -> ProviderChatResponse {
+        let message = choice.message;
+        let has_output = message
+            .effective_content()
+            .is_some_and(|text| !text.trim().is_empty())
+            || message
+                .tool_calls
+                .as_ref()
+                .is_some_and(|calls| !calls.is_empty());
+        if let Some(refused) = super::openai_refusal(
+            choice.finish_reason.as_deref(),
+            message.refusal.as_deref(),
+            has_output,
+        ) {
+            return ProviderChatResponse::refused(refused, None);
         }
+        Self::parse_native_response(message)
     }
 
     fn http_client(&self) -> Client {
         crate::config::build_runtime_proxy_client_with_timeouts("provider.openai", 120, 10)
     }
-}
 
-#[async_trait]
-impl Provider for OpenAiProvider {
-    async fn chat_with_system(
+    /// One-shot chat completion; `response_format` switches on JSON mode.
+    async fn send_system_chat(
         &self,
         system_prompt: Option<&str>,
         message: &str,
         model: &str,
         temperature: f64,
+        response_format: Option<serde_json::Value>,
     ) -> anyhow::Result<String> {
         let credential = self.credential.as_ref().ok_or_else(|| {
             anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
@@ -341,6 +373,7 @@ impl Provider for OpenAiProvider {
             messages,
             temperature,
             max_tokens: self.max_tokens_override,
+            response_format,
         };
 
         let response = self
@@ -357,12 +390,55 @@ impl Provider for OpenAiProvider {
 
         let chat_response: ChatResponse = response.json().await?;
 
-        chat_response
+        let choice = chat_response
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message.effective_content())
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
+        let content = choice.message.effective_content();
+        if let Some(refused) = super::openai_refusal(
+            choice.finish_reason.as_deref(),
+            choice.message.refusal.as_deref(),
+            !content.trim().is_empty(),
+        ) {
+            refused.ensure_completed()?;
+        }
+        Ok(content)
+    }
+}
+
+#[async_trait]
+impl Provider for OpenAiProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.send_system_chat(system_prompt, message, model, temperature, None)
+            .await
+    }
+
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let response_format = super::structured::openai_response_format(schema.as_ref());
+        let text = self
+            .send_system_chat(
+                system_prompt,
+                message,
+                model,
+                temperature,
+                Some(response_format),
+            )
+            .await?;
+        super::structured::parse_json_reply(&text, schema.as_ref())
     }
 
     async fn chat(
@@ -402,13 +478,12 @@ impl Provider for OpenAiProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
         });
-        let message = native_response
+        let choice = native_response
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        let mut result = Self::parse_native_response(message);
+        let mut result = Self::parse_native_choice(choice);
         result.usage = usage;
         Ok(result)
     }
@@ -466,13 +541,12 @@ impl Provider for OpenAiProvider {
             input_tokens: u.prompt_tokens,
             output_tokens: u.completion_tokens,
         });
-        let message = native_response
+        let choice = native_response
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message)
             .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))?;
-        let mut result = Self::parse_native_response(message);
+        let mut result = Self::parse_native_choice(choice);
         result.usage = usage;
         Ok(result)
     }
@@ -545,6 +619,7 @@ mod tests {
             ],
             temperature: 0.7,
             max_tokens: None,
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"role\":\"system\""));
@@ -562,6 +637,7 @@ mod tests {
             }],
             temperature: 0.0,
             max_tokens: None,
+            response_format: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("system"));
@@ -612,6 +688,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn refusals_and_filtered_completions_are_refused() {
+        let outcome = |json: &str| {
+            let choice: NativeChoice = serde_json::from_str(json).unwrap();
+            OpenAiProvider::parse_native_choice(choice).outcome
+        };
+        assert_eq!(
+            outcome(
+                r#"{"message":{"content":null,"refusal":"I can't assist with that."},"finish_reason":"stop"}"#
+            ),
+            ChatOutcome::Refused {
+                reason: "I can't assist with that.".into()
+            }
+        );
+        assert_eq!(
+            outcome(r#"{"message":{"content":""},"finish_reason":"content_filter"}"#),
+            ChatOutcome::Refused {
+                reason: "content_filter".into()
+            }
+        );
+        // Partial output cut by the filter is still an answer.
+        assert_eq!(
+            outcome(r#"{"message":{"content":"Here is"},"finish_reason":"content_filter"}"#),
+            ChatOutcome::Completed
+        );
+    }
+
     #[tokio::test]
     async fn warmup_without_key_is_noop() {
         let provider = OpenAiProvider::new(None);
@@ -1593,6 +1593,8 @@ data: [DONE]
             custom_provider_api_mode: None,
             max_tokens_override: None,
             model_support_vision: None,
+            prompt_caching: true,
+            ollama: Default::default(),
         };
         let provider =
             OpenAiCodexProvider::new(&options, None).expect("provider should initialize");
@@ -1,62 +1,88 @@
+use super::failover::{FailoverEvent, FailoverTracker};
 use super::traits::{
-    ChatMessage, ChatRequest, ChatResponse, StreamChunk, StreamOptions, StreamResult,
+    ChatMessage, ChatOutcome, ChatRequest, ChatResponse, RefusedError, StreamChunk, StreamOptions,
+    StreamResult,
 };
-use super::Provider;
+use super::{Provider, ProviderHttpError};
 use async_trait::async_trait;
 use futures_util::{stream, StreamExt};
 use std::collections::{HashMap, HashSet};
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::time::Duration;
+use tokio::sync::broadcast;
 
 // ── Error Classification ─────────────────────────────────────────────────
 // Errors are split into retryable (transient server/network failures) and
 // non-retryable (permanent client errors). This distinction drives whether
 // the retry loop continues, falls back to the next provider, or aborts
 // immediately — avoiding wasted latency on errors that cannot self-heal.
+// Refusals are not failures of the provider at all: every provider declines
+// the same question, so a `ChatOutcome::Refused` response is returned as is,
+// and a `RefusedError` from the text-only methods ends the request, unless
+// `fallback_on_refusal` is set.
+
+pub const METRIC_REFUSALS: &str = "provider_refusals_total";
+
+static REFUSALS: AtomicU64 = AtomicU64::new(0);
+
+const AUTH_FAILURE_HINTS: [&str; 11] = [
+    "invalid api key",
+    "incorrect api key",
+    "missing api key",
+    "api key not set",
+    "authentication failed",
+    "auth failed",
+    "unauthorized",
+    "forbidden",
+    "permission denied",
+    "access denied",
+    "invalid token",
+];
+
+/// HTTP status of a provider error: the typed status of a
+/// [`ProviderHttpError`] or `reqwest::Error` anywhere in the chain, else the
+/// first 4xx/5xx code in the message (some providers embed codes in error
+/// messages rather than returning typed HTTP errors).
+fn http_status(err: &anyhow::Error) -> Option<u16> {
+    for cause in err.chain() {
+        if let Some(http) = cause.downcast_ref::<ProviderHttpError>() {
+            return Some(http.status.as_u16());
+        }
+        if let Some(status) = cause
+            .downcast_ref::<reqwest::Error>()
+            .and_then(reqwest::Error::status)
+        {
+            return Some(status.as_u16());
+        }
+    }
+    err.to_string()
+        .split(|c: char| !c.is_ascii_digit())
+        .filter_map(|word| word.parse::<u16>().ok())
+        .find(|code| (400..600).contains(code))
+}
+
+/// 4xx errors are terminal (bad request, auth failure, etc.), except 429
+/// (rate limit — transient) and 408 (timeout — worth retrying). 5xx errors
+/// are retryable.
+fn is_terminal_status(code: u16) -> bool {
+    (400..500).contains(&code) && code != 429 && code != 408
+}
 
 /// Check if an error is non-retryable (client errors that won't resolve with retries).
 fn is_non_retryable(err: &anyhow::Error) -> bool {
-    if is_context_window_exceeded(err) {
+    if is_refusal(err) || is_context_window_exceeded(err) {
         return true;
     }
 
-    // 4xx errors are generally non-retryable (bad request, auth failure, etc.),
-    // except 429 (rate-limit — transient) and 408 (timeout — worth retrying).
-    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
-        if let Some(status) = reqwest_err.status() {
-            let code = status.as_u16();
-            return status.is_client_error() && code != 429 && code != 408;
-        }
+    if let Some(code) = http_status(err) {
+        return is_terminal_status(code);
     }
-    // Fallback: parse status codes from stringified errors (some providers
-    // embed codes in error messages rather than returning typed HTTP errors).
     let msg = err.to_string();
-    for word in msg.split(|c: char| !c.is_ascii_digit()) {
-        if let Ok(code) = word.parse::<u16>() {
-            if (400..500).contains(&code) {
-                return code != 429 && code != 408;
-            }
-        }
-    }
 
     // Heuristic: detect auth/model failures by keyword when no HTTP status
     // is available (e.g. gRPC or custom transport errors).
     let msg_lower = msg.to_lowercase();
-    let auth_failure_hints = [
-        "invalid api key",
-        "incorrect api key",
-        "missing api key",
-        "api key not set",
-        "authentication failed",
-        "auth failed",
-        "unauthorized",
-        "forbidden",
-        "permission denied",
-        "access denied",
-        "invalid token",
-    ];
-
-    if auth_failure_hints
+    if AUTH_FAILURE_HINTS
         .iter()
         .any(|hint| msg_lower.contains(hint))
     {
@@ -71,6 +97,19 @@ fn is_non_retryable(err: &anyhow::Error) -> bool {
             || msg_lower.contains("invalid"))
 }
 
+/// Check if the provider declined the request (content policy or filter).
+fn is_refusal(err: &anyhow::Error) -> bool {
+    RefusedError::find(err).is_some()
+}
+
+/// Count a refusal for `zeroclaw status`.
+fn record_refusal() {
+    crate::health::set_metric(
+        METRIC_REFUSALS,
+        REFUSALS.fetch_add(1, Ordering::Relaxed) + 1,
+    );
+}
+
 fn is_context_window_exceeded(err: &anyhow::Error) -> bool {
     let lower = err.to_string().to_lowercase();
     let hints = [
@@ -145,6 +184,131 @@ fn is_non_retryable_rate_limit(err: &anyhow::Error) -> bool {
     false
 }
 
+/// What kind of failure a provider error is, for people rather than the
+/// retry loop: channel and webhook error replies pick their wording from it.
+/// Built from the same checks that decide retries above, so a reply never
+/// says "try again in a minute" for an error the retry loop gave up on as
+/// permanent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorClass {
+    RateLimited,
+    Auth,
+    Network,
+    Overloaded,
+    BudgetExceeded,
+    ContextTooLong,
+    Unsupported,
+    /// The provider declined to answer (content policy or filter); not a
+    /// technical failure.
+    Refused,
+    ToolFailure,
+    Internal,
+}
+
+impl ErrorClass {
+    pub const ALL: [Self; 10] = [
+        Self::RateLimited,
+        Self::Auth,
+        Self::Network,
+        Self::Overloaded,
+        Self::BudgetExceeded,
+        Self::ContextTooLong,
+        Self::Unsupported,
+        Self::Refused,
+        Self::ToolFailure,
+        Self::Internal,
+    ];
+
+    /// Key used in config (`[channels_config.error_templates.<lang>]`) and logs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::Auth => "auth",
+            Self::Network => "network",
+            Self::Overloaded => "overloaded",
+            Self::BudgetExceeded => "budget_exceeded",
+            Self::ContextTooLong => "context_too_long",
+            Self::Unsupported => "unsupported",
+            Self::Refused => "refused",
+            Self::ToolFailure => "tool_failure",
+            Self::Internal => "internal",
+        }
+    }
+
+    pub fn parse(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|class| class.as_str() == key)
+    }
+}
+
+/// Classify a provider error (possibly the aggregated "All providers/models
+/// failed" error). Agent-loop failures are not provider errors and come out
+/// as [`ErrorClass::Internal`]; callers that know better map them first.
+pub fn classify_error(err: &anyhow::Error) -> ErrorClass {
+    if err
+        .downcast_ref::<super::ProviderCapabilityError>()
+        .is_some()
+    {
+        return ErrorClass::Unsupported;
+    }
+    // The retry checks read the top-level message; callers may have wrapped
+    // the provider error in context, so look at the whole chain.
+    let flat = anyhow::Error::msg(format!("{err:#}"));
+    // With `fallback_on_refusal` every provider may have refused, leaving
+    // only the failure lists of the aggregated error to tell.
+    if is_refusal(err) || flat.to_string().contains(": refused; error=") {
+        return ErrorClass::Refused;
+    }
+    if is_context_window_exceeded(&flat) {
+        return ErrorClass::ContextTooLong;
+    }
+    let lower = flat.to_string().to_lowercase();
+    if is_non_retryable_rate_limit(&flat)
+        || lower.contains("402 payment required")
+        || lower.contains("budget exceeded")
+        || lower.contains("insufficient_quota")
+        || lower.contains("insufficient quota")
+        || lower.contains("insufficient balance")
+    {
+        return ErrorClass::BudgetExceeded;
+    }
+    if is_rate_limited(err) || is_rate_limited(&flat) {
+        return ErrorClass::RateLimited;
+    }
+
+    let status = http_status(err).or_else(|| http_status(&flat));
+    if matches!(status, Some(401 | 403))
+        || AUTH_FAILURE_HINTS.iter().any(|hint| lower.contains(hint))
+    {
+        return ErrorClass::Auth;
+    }
+    if let Some(reqwest_err) = err.downcast_ref::<reqwest::Error>() {
+        if reqwest_err.is_timeout() || reqwest_err.is_connect() {
+            return ErrorClass::Network;
+        }
+    }
+    if status.is_some_and(|code| code >= 500)
+        || lower.contains("overloaded")
+        || lower.contains("server is busy")
+        || lower.contains("over capacity")
+    {
+        return ErrorClass::Overloaded;
+    }
+    let network_hints = [
+        "error sending request",
+        "connection refused",
+        "connection reset",
+        "connection closed",
+        "failed to connect",
+        "dns error",
+        "timed out",
+        "408 request timeout",
+    ];
+    if network_hints.iter().any(|hint| lower.contains(hint)) {
+        return ErrorClass::Network;
+    }
+    ErrorClass::Internal
+}
+
 /// Try to extract a Retry-After value (in milliseconds) from an error message.
 /// Looks for patterns like `Retry-After: 5` or `retry_after: 2.5` in the error string.
 fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
@@ -178,8 +342,10 @@ fn parse_retry_after_ms(err: &anyhow::Error) -> Option<u64> {
     None
 }
 
-fn failure_reason(rate_limited: bool, non_retryable: bool) -> &'static str {
-    if rate_limited && non_retryable {
+fn failure_reason(err: &anyhow::Error, rate_limited: bool, non_retryable: bool) -> &'static str {
+    if is_refusal(err) {
+        "refused"
+    } else if rate_limited && non_retryable {
         "rate_limited_non_retryable"
     } else if rate_limited {
         "rate_limited"
@@ -235,6 +401,10 @@ pub struct ReliableProvider {
     provider_model_fallbacks: HashMap<String, Vec<String>>,
     /// Vision support override from config (`None` = defer to provider).
     vision_override: Option<bool>,
+    /// Try the next provider/model when one refuses instead of giving up.
+    fallback_on_refusal: bool,
+    /// Publishes failover and recovery events (see [`super::failover`]).
+    failover: FailoverTracker,
 }
 
 impl ReliableProvider {
@@ -243,6 +413,8 @@ impl ReliableProvider {
         max_retries: u32,
         base_backoff_ms: u64,
     ) -> Self {
+        let primary = providers.first().map_or("", |(name, _)| name.as_str());
+        let failover = FailoverTracker::new(primary);
         Self {
             providers,
             max_retries,
@@ -252,6 +424,8 @@ impl ReliableProvider {
             model_fallbacks: HashMap::new(),
             provider_model_fallbacks: HashMap::new(),
             vision_override: None,
+            fallback_on_refusal: false,
+            failover,
         }
     }
 
@@ -288,6 +462,20 @@ impl ReliableProvider {
         self
     }
 
+    /// Try fallback providers and models when a provider refuses a request
+    /// (`[reliability] fallback_on_refusal`). Refusals are never retried.
+    pub fn with_fallback_on_refusal(mut self, fallback_on_refusal: bool) -> Self {
+        self.fallback_on_refusal = fallback_on_refusal;
+        self
+    }
+
+    /// Publish failover events to `events` instead of the process-wide bus.
+    pub fn with_failover_events(mut self, events: broadcast::Sender<FailoverEvent>) -> Self {
+        let primary = self.providers.first().map_or("", |(name, _)| name.as_str());
+        self.failover = FailoverTracker::with_events(primary, events);
+        self
+    }
+
     /// Build the list of models to try: [original, fallback1, fallback2, ...]
     fn model_chain<'a>(&'a self, model: &'a str) -> Vec<&'a str> {
         let mut chain = vec![model];
@@ -388,6 +576,159 @@ impl Provider for ReliableProvider {
                             .await
                         {
                             Ok(resp) => {
+                                self.failover.served_by(provider_name);
+                                if attempt > 0 || sent_model != model {
+                                    tracing::info!(
+                                        provider = provider_name,
+                                        model = sent_model,
+                                        attempt,
+                                        original_model = model,
+                                        "Provider recovered (failover/retry)"
+                                    );
+                                }
+                                return Ok(resp);
+                            }
+                            Err(e) => {
+                                if provider_index == 0 {
+                                    self.failover.primary_failed(classify_error(&e));
+                                }
+                                let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
+                                let non_retryable =
+                                    is_non_retryable(&e) || non_retryable_rate_limit;
+                                let rate_limited = is_rate_limited(&e);
+                                let failure_reason =
+                                    failure_reason(&e, rate_limited, non_retryable);
+                                let error_detail = compact_error_detail(&e);
+
+                                push_failure(
+                                    &mut failures,
+                                    provider_name,
+                                    sent_model,
+                                    attempt + 1,
+                                    self.max_retries + 1,
+                                    failure_reason,
+                                    &error_detail,
+                                );
+
+                                // Rate-limit with rotatable keys: cycle to the next API key
+                                // so the retry hits a different quota bucket.
+                                if rate_limited && !non_retryable_rate_limit {
+                                    if let Some(new_key) = self.rotate_key() {
+                                        tracing::warn!(
+                                            provider = provider_name,
+                                            error = %error_detail,
+                                            "Rate limited; key rotation selected key ending ...{} \
+                                             but cannot apply (Provider trait has no set_api_key). \
+                                             Retrying with original key.",
+                                            &new_key[new_key.len().saturating_sub(4)..]
+                                        );
+                                    }
+                                }
+
+                                if non_retryable {
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        model = sent_model,
+                                        error = %error_detail,
+                                        "Non-retryable error, moving on"
+                                    );
+
+                                    if is_refusal(&e) {
+                                        record_refusal();
+                                        if !self.fallback_on_refusal {
+                                            return Err(e.context(format!(
+                                                "Provider refused the request; retries and fallbacks were skipped. Attempts:\n{}",
+                                                failures.join("\n")
+                                            )));
+                                        }
+                                    }
+
+                                    if is_context_window_exceeded(&e) {
+                                        anyhow::bail!(
+                                            "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
+                                            failures.join("\n")
+                                        );
+                                    }
+
+                                    break;
+                                }
+
+                                if attempt < self.max_retries {
+                                    let wait = self.compute_backoff(backoff_ms, &e);
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        model = sent_model,
+                                        attempt = attempt + 1,
+                                        backoff_ms = wait,
+                                        reason = failure_reason,
+                                        error = %error_detail,
+                                        "Provider call failed, retrying"
+                                    );
+                                    tokio::time::sleep(Duration::from_millis(wait)).await;
+                                    backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
+                                }
+                            }
+                        }
+                    }
+
+                    tracing::warn!(
+                        provider = provider_name,
+                        model = sent_model,
+                        "Exhausted retries, trying next provider/model"
+                    );
+                }
+            }
+
+            if *current_model != model {
+                tracing::warn!(
+                    original_model = model,
+                    fallback_model = *current_model,
+                    "Model fallback exhausted all providers, trying next fallback model"
+                );
+            }
+        }
+
+        anyhow::bail!(
+            "All providers/models failed. Attempts:\n{}",
+            failures.join("\n")
+        )
+    }
+
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let models = self.model_chain(model);
+        let mut failures = Vec::new();
+
+        // Outer: model fallback chain. Middle: provider priority. Inner: retries.
+        // Each iteration: attempt one (provider, model) call. On success, return
+        // immediately. On non-retryable error, break to next provider. On
+        // retryable error, sleep with exponential backoff and retry.
+        for current_model in &models {
+            for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
+                let sent_models =
+                    self.provider_model_chain(current_model, provider_name, provider_index == 0);
+                for sent_model in sent_models {
+                    let mut backoff_ms = self.base_backoff_ms;
+
+                    for attempt in 0..=self.max_retries {
+                        match provider
+                            .chat_json(
+                                system_prompt,
+                                message,
+                                sent_model,
+                                temperature,
+                                schema.clone(),
+                            )
+                            .await
+                        {
+                            Ok(resp) => {
+                                self.failover.served_by(provider_name);
                                 if attempt > 0 || sent_model != model {
                                     tracing::info!(
                                         provider = provider_name,
@@ -400,11 +741,15 @@ impl Provider for ReliableProvider {
                                 return Ok(resp);
                             }
                             Err(e) => {
+                                if provider_index == 0 {
+                                    self.failover.primary_failed(classify_error(&e));
+                                }
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
                                     is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
-                                let failure_reason = failure_reason(rate_limited, non_retryable);
+                                let failure_reason =
+                                    failure_reason(&e, rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
 
                                 push_failure(
@@ -440,6 +785,16 @@ impl Provider for ReliableProvider {
                                         "Non-retryable error, moving on"
                                     );
 
+                                    if is_refusal(&e) {
+                                        record_refusal();
+                                        if !self.fallback_on_refusal {
+                                            return Err(e.context(format!(
+                                                "Provider refused the request; retries and fallbacks were skipped. Attempts:\n{}",
+                                                failures.join("\n")
+                                            )));
+                                        }
+                                    }
+
                                     if is_context_window_exceeded(&e) {
                                         anyhow::bail!(
                                             "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
@@ -513,6 +868,7 @@ impl Provider for ReliableProvider {
                             .await
                         {
                             Ok(resp) => {
+                                self.failover.served_by(provider_name);
                                 if attempt > 0 || sent_model != model {
                                     tracing::info!(
                                         provider = provider_name,
@@ -525,11 +881,15 @@ impl Provider for ReliableProvider {
                                 return Ok(resp);
                             }
                             Err(e) => {
+                                if provider_index == 0 {
+                                    self.failover.primary_failed(classify_error(&e));
+                                }
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
                                     is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
-                                let failure_reason = failure_reason(rate_limited, non_retryable);
+                                let failure_reason =
+                                    failure_reason(&e, rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
 
                                 push_failure(
@@ -563,6 +923,16 @@ impl Provider for ReliableProvider {
                                         "Non-retryable error, moving on"
                                     );
 
+                                    if is_refusal(&e) {
+                                        record_refusal();
+                                        if !self.fallback_on_refusal {
+                                            return Err(e.context(format!(
+                                                "Provider refused the request; retries and fallbacks were skipped. Attempts:\n{}",
+                                                failures.join("\n")
+                                            )));
+                                        }
+                                    }
+
                                     if is_context_window_exceeded(&e) {
                                         anyhow::bail!(
                                             "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
@@ -630,6 +1000,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut refusal = None;
 
         for current_model in &models {
             for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
@@ -644,6 +1015,30 @@ impl Provider for ReliableProvider {
                             .await
                         {
                             Ok(resp) => {
+                                self.failover.served_by(provider_name);
+                                if let ChatOutcome::Refused { reason } = &resp.outcome {
+                                    record_refusal();
+                                    if !self.fallback_on_refusal {
+                                        return Ok(resp);
+                                    }
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        model = sent_model,
+                                        reason = %reason,
+                                        "Provider refused, trying next provider/model"
+                                    );
+                                    push_failure(
+                                        &mut failures,
+                                        provider_name,
+                                        sent_model,
+                                        attempt + 1,
+                                        self.max_retries + 1,
+                                        "refused",
+                                        reason,
+                                    );
+                                    refusal = Some(resp);
+                                    break;
+                                }
                                 if attempt > 0 || sent_model != model {
                                     tracing::info!(
                                         provider = provider_name,
@@ -656,11 +1051,15 @@ impl Provider for ReliableProvider {
                                 return Ok(resp);
                             }
                             Err(e) => {
+                                if provider_index == 0 {
+                                    self.failover.primary_failed(classify_error(&e));
+                                }
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
                                     is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
-                                let failure_reason = failure_reason(rate_limited, non_retryable);
+                                let failure_reason =
+                                    failure_reason(&e, rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
 
                                 push_failure(
@@ -694,6 +1093,16 @@ impl Provider for ReliableProvider {
                                         "Non-retryable error, moving on"
                                     );
 
+                                    if is_refusal(&e) {
+                                        record_refusal();
+                                        if !self.fallback_on_refusal {
+                                            return Err(e.context(format!(
+                                                "Provider refused the request; retries and fallbacks were skipped. Attempts:\n{}",
+                                                failures.join("\n")
+                                            )));
+                                        }
+                                    }
+
                                     if is_context_window_exceeded(&e) {
                                         anyhow::bail!(
                                             "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
@@ -731,6 +1140,11 @@ impl Provider for ReliableProvider {
             }
         }
 
+        // Everything that answered refused: that is the answer.
+        if let Some(resp) = refusal {
+            return Ok(resp);
+        }
+
         anyhow::bail!(
             "All providers/models failed. Attempts:\n{}",
             failures.join("\n")
@@ -745,6 +1159,7 @@ impl Provider for ReliableProvider {
     ) -> anyhow::Result<ChatResponse> {
         let models = self.model_chain(model);
         let mut failures = Vec::new();
+        let mut refusal = None;
 
         for current_model in &models {
             for (provider_index, (provider_name, provider)) in self.providers.iter().enumerate() {
@@ -760,6 +1175,30 @@ impl Provider for ReliableProvider {
                         };
                         match provider.chat(req, sent_model, temperature).await {
                             Ok(resp) => {
+                                self.failover.served_by(provider_name);
+                                if let ChatOutcome::Refused { reason } = &resp.outcome {
+                                    record_refusal();
+                                    if !self.fallback_on_refusal {
+                                        return Ok(resp);
+                                    }
+                                    tracing::warn!(
+                                        provider = provider_name,
+                                        model = sent_model,
+                                        reason = %reason,
+                                        "Provider refused, trying next provider/model"
+                                    );
+                                    push_failure(
+                                        &mut failures,
+                                        provider_name,
+                                        sent_model,
+                                        attempt + 1,
+                                        self.max_retries + 1,
+                                        "refused",
+                                        reason,
+                                    );
+                                    refusal = Some(resp);
+                                    break;
+                                }
                                 if attempt > 0 || sent_model != model {
                                     tracing::info!(
                                         provider = provider_name,
@@ -772,11 +1211,15 @@ impl Provider for ReliableProvider {
                                 return Ok(resp);
                             }
                             Err(e) => {
+                                if provider_index == 0 {
+                                    self.failover.primary_failed(classify_error(&e));
+                                }
                                 let non_retryable_rate_limit = is_non_retryable_rate_limit(&e);
                                 let non_retryable =
                                     is_non_retryable(&e) || non_retryable_rate_limit;
                                 let rate_limited = is_rate_limited(&e);
-                                let failure_reason = failure_reason(rate_limited, non_retryable);
+                                let failure_reason =
+                                    failure_reason(&e, rate_limited, non_retryable);
                                 let error_detail = compact_error_detail(&e);
 
                                 push_failure(
@@ -810,6 +1253,16 @@ impl Provider for ReliableProvider {
                                         "Non-retryable error, moving on"
                                     );
 
+                                    if is_refusal(&e) {
+                                        record_refusal();
+                                        if !self.fallback_on_refusal {
+                                            return Err(e.context(format!(
+                                                "Provider refused the request; retries and fallbacks were skipped. Attempts:\n{}",
+                                                failures.join("\n")
+                                            )));
+                                        }
+                                    }
+
                                     if is_context_window_exceeded(&e) {
                                         anyhow::bail!(
                                             "Request exceeds model context window; retries and fallbacks were skipped. Attempts:\n{}",
@@ -855,6 +1308,11 @@ impl Provider for ReliableProvider {
             }
         }
 
+        // Everything that answered refused: that is the answer.
+        if let Some(resp) = refusal {
+            return Ok(resp);
+        }
+
         anyhow::bail!(
             "All providers/models failed. Attempts:\n{}",
             failures.join("\n")
@@ -1163,6 +1621,207 @@ mod tests {
         )));
     }
 
+    /// Declines every request, counting calls.
+    struct RefusingProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for RefusingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(RefusedError {
+                reason: "content_filter".into(),
+            }
+            .into())
+        }
+    }
+
+    /// Each provider's refusal shape is answered after one call, as a refused
+    /// outcome: no retry, no fallback. Text-only callers get the refusal
+    /// template.
+    #[tokio::test]
+    async fn provider_refusals_are_not_retried_or_failed_over() {
+        use super::super::anthropic::AnthropicProvider;
+        use super::super::compatible::{AuthStyle, OpenAiCompatibleProvider};
+        use super::super::openai::OpenAiProvider;
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let shapes = [
+            (
+                "anthropic",
+                serde_json::json!({
+                    "content": [],
+                    "stop_reason": "refusal",
+                }),
+            ),
+            (
+                "openai",
+                serde_json::json!({
+                    "choices": [{
+                        "message": {"content": null, "refusal": "I can't assist with that."},
+                        "finish_reason": "stop",
+                    }],
+                }),
+            ),
+            (
+                "compatible",
+                serde_json::json!({
+                    "choices": [{
+                        "message": {"content": ""},
+                        "finish_reason": "content_filter",
+                    }],
+                }),
+            ),
+        ];
+        for (name, body) in shapes {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(body))
+                .mount(&server)
+                .await;
+            let api_base = format!("{}/v1", server.uri());
+            let refusing: Box<dyn Provider> = match name {
+                "anthropic" => Box::new(AnthropicProvider::with_base_url(
+                    Some("test-key"),
+                    Some(&server.uri()),
+                )),
+                "openai" => Box::new(OpenAiProvider::with_base_url(
+                    Some(&api_base),
+                    Some("test-key"),
+                )),
+                _ => Box::new(OpenAiCompatibleProvider::new(
+                    name,
+                    &api_base,
+                    Some("test-key"),
+                    AuthStyle::Bearer,
+                )),
+            };
+            let fallback_calls = Arc::new(AtomicUsize::new(0));
+            let provider = ReliableProvider::new(
+                vec![
+                    (name.into(), refusing),
+                    (
+                        "fallback".into(),
+                        Box::new(MockProvider {
+                            calls: Arc::clone(&fallback_calls),
+                            fail_until_attempt: 0,
+                            response: "fallback answer",
+                            error: "",
+                        }) as Box<dyn Provider>,
+                    ),
+                ],
+                2,
+                1,
+            );
+
+            let messages = [ChatMessage::user("help me with something disallowed")];
+            let request = ChatRequest {
+                messages: &messages,
+                tools: None,
+            };
+            let response = provider.chat(request, "model", 0.0).await.unwrap();
+            assert!(response.outcome.is_refused(), "{name}: {response:?}");
+            assert!(response.text.is_none(), "{name}");
+            assert_eq!(
+                server.received_requests().await.unwrap().len(),
+                1,
+                "{name} refusal was retried"
+            );
+            assert_eq!(fallback_calls.load(Ordering::SeqCst), 0, "{name}");
+
+            let err = provider
+                .chat_with_system(None, "help me with something disallowed", "model", 0.0)
+                .await
+                .unwrap_err();
+            assert!(RefusedError::find(&err).is_some(), "{name}: {err:#}");
+            assert_eq!(
+                server.received_requests().await.unwrap().len(),
+                2,
+                "{name} refusal was retried"
+            );
+            assert_eq!(fallback_calls.load(Ordering::SeqCst), 0, "{name}");
+
+            let reply = crate::channels::error_reply::ErrorReplies::default().render(&err);
+            assert_eq!(reply.class, ErrorClass::Refused, "{name}");
+            assert_eq!(reply.text, "🙅 I can't help with that request.");
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback_on_refusal_asks_the_next_provider_once() {
+        let refusing_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "strict".into(),
+                    Box::new(RefusingProvider {
+                        calls: Arc::clone(&refusing_calls),
+                    }) as Box<dyn Provider>,
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 0,
+                        response: "fallback answer",
+                        error: "",
+                    }) as Box<dyn Provider>,
+                ),
+            ],
+            2,
+            1,
+        )
+        .with_fallback_on_refusal(true);
+
+        let reply = provider.simple_chat("hello", "model", 0.0).await.unwrap();
+        assert_eq!(reply, "fallback answer");
+        assert_eq!(refusing_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
+
+        let messages = [ChatMessage::user("hello")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+        };
+        let response = provider.chat(request, "model", 0.0).await.unwrap();
+        assert_eq!(response.text.as_deref(), Some("fallback answer"));
+        assert_eq!(refusing_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 2);
+
+        // Refused everywhere: the aggregated error still reads as a refusal.
+        let provider = ReliableProvider::new(
+            vec![(
+                "strict".into(),
+                Box::new(RefusingProvider {
+                    calls: Arc::clone(&refusing_calls),
+                }) as Box<dyn Provider>,
+            )],
+            2,
+            1,
+        )
+        .with_fallback_on_refusal(true);
+        let err = provider
+            .simple_chat("hello", "model", 0.0)
+            .await
+            .unwrap_err();
+        assert_eq!(refusing_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(classify_error(&err), ErrorClass::Refused, "{err:#}");
+
+        // Structured calls answer with the refusal itself.
+        let response = provider.chat(request, "model", 0.0).await.unwrap();
+        assert!(response.outcome.is_refused());
+        assert_eq!(refusing_calls.load(Ordering::SeqCst), 4);
+    }
+
     #[tokio::test]
     async fn context_window_error_aborts_retries_and_model_fallbacks() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -1721,6 +2380,89 @@ mod tests {
         );
     }
 
+    /// Mock that always fails with a typed HTTP error, like `api_error` builds.
+    struct HttpStatusMock {
+        calls: Arc<AtomicUsize>,
+        status: reqwest::StatusCode,
+    }
+
+    #[async_trait]
+    impl Provider for HttpStatusMock {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(ProviderHttpError {
+                provider: "mock".into(),
+                status: self.status,
+                // A body that mentions other codes must not change the class.
+                body: "upstream said 404 after 450ms".into(),
+            }
+            .into())
+        }
+    }
+
+    async fn calls_for_status(status: reqwest::StatusCode) -> usize {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = ReliableProvider::new(
+            vec![(
+                "primary".into(),
+                Box::new(HttpStatusMock {
+                    calls: Arc::clone(&calls),
+                    status,
+                }),
+            )],
+            2,
+            1,
+        );
+        assert!(provider.simple_chat("hello", "test", 0.0).await.is_err());
+        calls.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn typed_http_status_decides_whether_to_retry() {
+        assert_eq!(calls_for_status(reqwest::StatusCode::UNAUTHORIZED).await, 1);
+        assert_eq!(calls_for_status(reqwest::StatusCode::BAD_REQUEST).await, 1);
+        assert_eq!(
+            calls_for_status(reqwest::StatusCode::SERVICE_UNAVAILABLE).await,
+            3,
+            "503 must be retried max_retries times"
+        );
+    }
+
+    #[test]
+    fn http_status_prefers_the_typed_status_over_the_message() {
+        let err: anyhow::Error = ProviderHttpError {
+            provider: "openai".into(),
+            status: reqwest::StatusCode::SERVICE_UNAVAILABLE,
+            body: "token 401 rejected".into(),
+        }
+        .into();
+        assert_eq!(http_status(&err), Some(503));
+        assert!(!is_non_retryable(&err));
+        assert_eq!(
+            err.to_string(),
+            "openai API error (503 Service Unavailable): token 401 rejected"
+        );
+
+        // Wrapped in context, the typed status is still found.
+        let wrapped = err.context("primary provider failed");
+        assert_eq!(http_status(&wrapped), Some(503));
+
+        // Untyped messages fall back to the first 4xx/5xx code.
+        assert_eq!(
+            http_status(&anyhow::anyhow!("503 Service Unavailable, retry in 450ms")),
+            Some(503)
+        );
+        assert!(!is_non_retryable(&anyhow::anyhow!(
+            "503 Service Unavailable, retry in 450ms"
+        )));
+    }
+
     #[tokio::test]
     async fn non_retryable_rate_limit_skips_retries_for_plan_errors() {
         let calls = Arc::new(AtomicUsize::new(0));
@@ -1807,6 +2549,7 @@ mod tests {
                 tool_calls: self.tool_calls.clone(),
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             })
         }
     }
@@ -2000,6 +2743,7 @@ mod tests {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             })
         }
     }
@@ -2180,4 +2924,84 @@ mod tests {
         // No override set → should defer to provider default (false)
         assert!(!provider.supports_vision());
     }
+
+    /// Primary that fails while `down` is set.
+    struct FlakyPrimary {
+        down: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    #[async_trait]
+    impl Provider for FlakyPrimary {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            if self.down.load(Ordering::SeqCst) {
+                anyhow::bail!("503 Service Unavailable: overloaded");
+            }
+            Ok("primary".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn failover_and_recovery_notify_once_per_window() {
+        let down = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (events, rx) = broadcast::channel(16);
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "anthropic".into(),
+                    Box::new(FlakyPrimary {
+                        down: Arc::clone(&down),
+                    }) as Box<dyn Provider>,
+                ),
+                (
+                    "ollama".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::new(AtomicUsize::new(0)),
+                        fail_until_attempt: 0,
+                        response: "fallback",
+                        error: "",
+                    }),
+                ),
+            ],
+            0,
+            1,
+        )
+        .with_failover_events(events);
+
+        // Fail over for two requests, recover, then flap again right away.
+        for (primary_down, expected) in [
+            (true, "fallback"),
+            (true, "fallback"),
+            (false, "primary"),
+            (true, "fallback"),
+            (false, "primary"),
+        ] {
+            down.store(primary_down, Ordering::SeqCst);
+            let reply = provider.simple_chat("hello", "model", 0.0).await.unwrap();
+            assert_eq!(reply, expected);
+        }
+        drop(provider);
+
+        let mut sent = Vec::new();
+        crate::providers::failover::forward_notifications(rx, |text| {
+            sent.push(text);
+            async {}
+        })
+        .await;
+
+        assert_eq!(
+            sent,
+            [
+                "\u{26A0}\u{FE0F} Provider failover: anthropic failed (overloaded), \
+                 requests are going to ollama.",
+                "\u{2705} Provider recovered: back on anthropic; 2 requests were served \
+                 by ollama meanwhile.",
+            ]
+        );
+    }
 }
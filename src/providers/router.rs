@@ -1,7 +1,25 @@
-use super::traits::{ChatMessage, ChatRequest, ChatResponse};
+use super::traits::{ChatMessage, ChatRequest, ChatResponse, RefusedError};
 use super::Provider;
 use async_trait::async_trait;
 use std::collections::HashMap;
+use std::future::Future;
+
+/// A dispatched call's answer, which may be a refusal.
+trait Answer {
+    fn is_refused(&self) -> bool {
+        false
+    }
+}
+
+impl Answer for String {}
+
+impl Answer for serde_json::Value {}
+
+impl Answer for ChatResponse {
+    fn is_refused(&self) -> bool {
+        self.outcome.is_refused()
+    }
+}
 
 /// A single route: maps a task hint to a provider + model combo.
 #[derive(Debug, Clone)]
@@ -25,6 +43,10 @@ pub struct RouterProvider {
     default_model: String,
     /// Vision support override from config (`None` = defer to providers).
     vision_override: Option<bool>,
+    /// Hints to try, in order, when a route's provider fails.
+    fallbacks: HashMap<String, Vec<String>>,
+    /// Try fallback routes when a route's provider refuses the request.
+    fallback_on_refusal: bool,
 }
 
 impl RouterProvider {
@@ -69,6 +91,8 @@ impl RouterProvider {
             default_index: 0,
             default_model,
             vision_override: None,
+            fallbacks: HashMap::new(),
+            fallback_on_refusal: false,
         }
     }
 
@@ -78,6 +102,20 @@ impl RouterProvider {
         self
     }
 
+    /// Set per-route fallback chains: hint → hints to try when that route
+    /// fails. Entries may carry the `hint:` prefix.
+    pub fn with_route_fallbacks(mut self, fallbacks: HashMap<String, Vec<String>>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    /// Try fallback routes when a provider refuses a request
+    /// (`[reliability] fallback_on_refusal`).
+    pub fn with_fallback_on_refusal(mut self, fallback_on_refusal: bool) -> Self {
+        self.fallback_on_refusal = fallback_on_refusal;
+        self
+    }
+
     /// Resolve a model parameter to a (provider, actual_model) pair.
     ///
     /// If the model starts with "hint:", look up the hint in the route table.
@@ -97,6 +135,78 @@ impl RouterProvider {
         // Not a hint or hint not found — use default provider with the model as-is
         (self.default_index, model.to_string())
     }
+
+    /// The routes to try for `model`: the resolved route, then the routes of
+    /// its fallback hints.
+    fn candidates(&self, model: &str) -> Vec<(usize, String)> {
+        let mut candidates = vec![self.resolve(model)];
+        let Some(chain) = model
+            .strip_prefix("hint:")
+            .and_then(|hint| self.fallbacks.get(hint))
+        else {
+            return candidates;
+        };
+        for fallback in chain {
+            let fallback = fallback.trim();
+            let hint = fallback.strip_prefix("hint:").unwrap_or(fallback);
+            match self.routes.get(hint) {
+                Some((idx, resolved_model)) => {
+                    let candidate = (*idx, resolved_model.clone());
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+                None => tracing::warn!(
+                    hint = hint,
+                    "Route fallback references unknown hint, skipping"
+                ),
+            }
+        }
+        candidates
+    }
+
+    /// Run `call` against each candidate route for `model` until one
+    /// succeeds. When all fail, the last route's error is returned. A
+    /// refusal ends the chain unless `fallback_on_refusal` is set.
+    async fn dispatch<'s, T, F, Fut>(&'s self, model: &str, mut call: F) -> anyhow::Result<T>
+    where
+        T: Answer,
+        F: FnMut(&'s dyn Provider, String) -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let candidates = self.candidates(model);
+        let mut remaining = candidates.len();
+        let mut result = Err(anyhow::anyhow!("no route for model {model}"));
+        for (provider_idx, resolved_model) in candidates {
+            remaining -= 1;
+            let (provider_name, provider) = &self.providers[provider_idx];
+            tracing::info!(
+                provider = provider_name.as_str(),
+                model = resolved_model.as_str(),
+                "Router dispatching request"
+            );
+            result = call(provider.as_ref(), resolved_model).await;
+            let refused = match &result {
+                Ok(answer) => answer.is_refused(),
+                Err(error) => RefusedError::find(error).is_some(),
+            };
+            if remaining == 0 || (refused && !self.fallback_on_refusal) {
+                break;
+            }
+            match &result {
+                Ok(_) if refused => tracing::warn!(
+                    provider = provider_name.as_str(),
+                    "Routed provider refused, trying fallback route"
+                ),
+                Err(error) => tracing::warn!(
+                    provider = provider_name.as_str(),
+                    "Routed provider failed, trying fallback route: {error}"
+                ),
+                Ok(_) => break,
+            }
+        }
+        result
+    }
 }
 
 #[async_trait]
@@ -108,18 +218,31 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-
-        let (provider_name, provider) = &self.providers[provider_idx];
-        tracing::info!(
-            provider = provider_name.as_str(),
-            model = resolved_model.as_str(),
-            "Router dispatching request"
-        );
+        self.dispatch(model, |provider, resolved_model| async move {
+            provider
+                .chat_with_system(system_prompt, message, &resolved_model, temperature)
+                .await
+        })
+        .await
+    }
 
-        provider
-            .chat_with_system(system_prompt, message, &resolved_model, temperature)
-            .await
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        self.dispatch(model, |provider, resolved_model| {
+            let schema = schema.clone();
+            async move {
+                provider
+                    .chat_json(system_prompt, message, &resolved_model, temperature, schema)
+                    .await
+            }
+        })
+        .await
     }
 
     async fn chat_with_history(
@@ -128,11 +251,12 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider
-            .chat_with_history(messages, &resolved_model, temperature)
-            .await
+        self.dispatch(model, |provider, resolved_model| async move {
+            provider
+                .chat_with_history(messages, &resolved_model, temperature)
+                .await
+        })
+        .await
     }
 
     async fn chat(
@@ -141,9 +265,10 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider.chat(request, &resolved_model, temperature).await
+        self.dispatch(model, |provider, resolved_model| async move {
+            provider.chat(request, &resolved_model, temperature).await
+        })
+        .await
     }
 
     async fn chat_with_tools(
@@ -153,11 +278,12 @@ impl Provider for RouterProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let (provider_idx, resolved_model) = self.resolve(model);
-        let (_, provider) = &self.providers[provider_idx];
-        provider
-            .chat_with_tools(messages, tools, &resolved_model, temperature)
-            .await
+        self.dispatch(model, |provider, resolved_model| async move {
+            provider
+                .chat_with_tools(messages, tools, &resolved_model, temperature)
+                .await
+        })
+        .await
     }
 
     fn supports_native_tools(&self) -> bool {
@@ -189,6 +315,7 @@ impl Provider for RouterProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::traits::ChatOutcome;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
@@ -472,4 +599,170 @@ mod tests {
         assert_eq!(mocks[1].last_model(), "claude-opus");
         assert_eq!(mocks[0].call_count(), 0);
     }
+
+    /// Provider that always fails.
+    struct DownProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for DownProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            anyhow::bail!("503 Service Unavailable")
+        }
+    }
+
+    fn router_with_down_route(fallback: &[&str]) -> (RouterProvider, Arc<AtomicUsize>) {
+        let down_calls = Arc::new(AtomicUsize::new(0));
+        let providers: Vec<(String, Box<dyn Provider>)> = vec![
+            (
+                "default".into(),
+                Box::new(MockProvider::new("default-response")),
+            ),
+            (
+                "down".into(),
+                Box::new(DownProvider {
+                    calls: Arc::clone(&down_calls),
+                }),
+            ),
+            ("fast".into(), Box::new(MockProvider::new("fast-response"))),
+        ];
+        let route = |provider: &str, model: &str| Route {
+            provider_name: provider.to_string(),
+            model: model.to_string(),
+        };
+        let router = RouterProvider::new(
+            providers,
+            vec![
+                ("reasoning".into(), route("down", "claude-opus")),
+                ("fast".into(), route("fast", "llama-3-70b")),
+            ],
+            "default-model".to_string(),
+        )
+        .with_route_fallbacks(HashMap::from([(
+            "reasoning".to_string(),
+            fallback.iter().map(ToString::to_string).collect(),
+        )]));
+        (router, down_calls)
+    }
+
+    #[tokio::test]
+    async fn failed_route_falls_back_to_next_hint() {
+        let (router, down_calls) = router_with_down_route(&["hint:missing", "hint:fast"]);
+
+        let result = router
+            .simple_chat("hello", "hint:reasoning", 0.5)
+            .await
+            .unwrap();
+        assert_eq!(result, "fast-response");
+        assert_eq!(down_calls.load(Ordering::SeqCst), 1);
+
+        // Routes without a fallback chain still fail as before.
+        let (router, _) = router_with_down_route(&[]);
+        let err = router
+            .simple_chat("hello", "hint:reasoning", 0.5)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("503"));
+    }
+
+    /// Provider that declines every request.
+    struct RefusingProvider;
+
+    #[async_trait]
+    impl Provider for RefusingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Err(RefusedError {
+                reason: "content_filter".into(),
+            }
+            .into())
+        }
+    }
+
+    #[tokio::test]
+    async fn refusals_skip_fallback_routes_unless_enabled() {
+        let router = |fallback_on_refusal: bool| {
+            let providers: Vec<(String, Box<dyn Provider>)> = vec![
+                (
+                    "default".into(),
+                    Box::new(MockProvider::new("default-response")),
+                ),
+                ("strict".into(), Box::new(RefusingProvider)),
+                ("fast".into(), Box::new(MockProvider::new("fast-response"))),
+            ];
+            RouterProvider::new(
+                providers,
+                vec![
+                    (
+                        "reasoning".into(),
+                        Route {
+                            provider_name: "strict".into(),
+                            model: "claude-opus".into(),
+                        },
+                    ),
+                    (
+                        "fast".into(),
+                        Route {
+                            provider_name: "fast".into(),
+                            model: "llama-3-70b".into(),
+                        },
+                    ),
+                ],
+                "default-model".to_string(),
+            )
+            .with_route_fallbacks(HashMap::from([(
+                "reasoning".to_string(),
+                vec!["hint:fast".to_string()],
+            )]))
+            .with_fallback_on_refusal(fallback_on_refusal)
+        };
+
+        let err = router(false)
+            .simple_chat("hello", "hint:reasoning", 0.5)
+            .await
+            .unwrap_err();
+        assert!(RefusedError::find(&err).is_some(), "{err:#}");
+
+        let reply = router(true)
+            .simple_chat("hello", "hint:reasoning", 0.5)
+            .await
+            .unwrap();
+        assert_eq!(reply, "fast-response");
+
+        // Structured calls see the refusal as an outcome, not an error.
+        let messages = [ChatMessage::user("hello")];
+        let request = ChatRequest {
+            messages: &messages,
+            tools: None,
+        };
+        let response = router(false)
+            .chat(request, "hint:reasoning", 0.5)
+            .await
+            .unwrap();
+        assert_eq!(
+            response.outcome,
+            ChatOutcome::Refused {
+                reason: "content_filter".into()
+            }
+        );
+
+        let response = router(true)
+            .chat(request, "hint:reasoning", 0.5)
+            .await
+            .unwrap();
+        assert_eq!(response.text.as_deref(), Some("fast-response"));
+    }
 }
@@ -0,0 +1,272 @@
+//! Helpers for JSON-mode / structured output ([`Provider::chat_json`]).
+//!
+//! Providers with a native structured-output mode (OpenAI `response_format`,
+//! Anthropic tool forcing, Gemini `responseSchema`) still return text, so every
+//! reply goes through [`parse_json_reply`]: lenient extraction followed by a
+//! check against the caller's schema. The validator covers the JSON Schema
+//! subset those APIs accept — `type`, `properties`, `required`, `items` and
+//! `enum` — and ignores every other keyword.
+//!
+//! [`Provider::chat_json`]: super::traits::Provider::chat_json
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+/// Tool name used when a provider forces structured output through a tool call.
+pub const STRUCTURED_OUTPUT_TOOL: &str = "structured_output";
+
+/// System prompt for providers without a native JSON mode: the caller's
+/// prompt plus an instruction to answer with JSON only (and the schema, if any).
+pub fn json_mode_system_prompt(system_prompt: Option<&str>, schema: Option<&Value>) -> String {
+    let mut prompt = String::new();
+    if let Some(system_prompt) = system_prompt.filter(|s| !s.trim().is_empty()) {
+        prompt.push_str(system_prompt);
+        prompt.push_str("\n\n");
+    }
+    prompt.push_str(
+        "Respond with a single valid JSON value only: no prose, no Markdown code fences.",
+    );
+    if let Some(schema) = schema {
+        prompt.push_str("\nThe JSON must conform to this JSON Schema:\n");
+        prompt.push_str(&schema.to_string());
+    }
+    prompt
+}
+
+/// OpenAI-style `response_format`: `json_schema` when a schema is given,
+/// plain `json_object` otherwise.
+pub fn openai_response_format(schema: Option<&Value>) -> Value {
+    match schema {
+        Some(schema) => serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": STRUCTURED_OUTPUT_TOOL,
+                "schema": schema,
+            },
+        }),
+        None => serde_json::json!({ "type": "json_object" }),
+    }
+}
+
+/// Extract the JSON value from a model reply and check it against `schema`.
+pub fn parse_json_reply(text: &str, schema: Option<&Value>) -> Result<Value> {
+    let value = extract_json(text)?;
+    if let Some(schema) = schema {
+        validate_json(&value, schema)?;
+    }
+    Ok(value)
+}
+
+/// Leniently pull one JSON value out of a reply.
+///
+/// Accepts bare JSON, JSON inside a Markdown code fence, and JSON surrounded
+/// by prose (the first balanced object or array wins).
+pub fn extract_json(text: &str) -> Result<Value> {
+    let trimmed = text.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Ok(value);
+    }
+    if let Some(fenced) = fenced_block(trimmed) {
+        if let Ok(value) = serde_json::from_str(fenced.trim()) {
+            return Ok(value);
+        }
+    }
+    for (start, _) in trimmed.match_indices(['{', '[']) {
+        if let Some(candidate) = balanced_prefix(&trimmed[start..]) {
+            if let Ok(value) = serde_json::from_str(candidate) {
+                return Ok(value);
+            }
+        }
+    }
+    bail!(
+        "model reply is not valid JSON: {}",
+        crate::util::truncate_with_ellipsis(trimmed, 200)
+    )
+}
+
+fn fenced_block(text: &str) -> Option<&str> {
+    let after_open = &text[text.find("```")? + 3..];
+    // Skip an info string such as `json`.
+    let body = &after_open[after_open.find('\n')? + 1..];
+    Some(&body[..body.find("```")?])
+}
+
+/// The shortest prefix of `text` (which starts with `{` or `[`) whose
+/// brackets balance, skipping brackets inside string literals.
+fn balanced_prefix(text: &str) -> Option<&str> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(&text[..=i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Check `value` against the supported JSON Schema subset.
+pub fn validate_json(value: &Value, schema: &Value) -> Result<()> {
+    validate_at(value, schema, "$").context("model reply does not match the requested schema")
+}
+
+fn validate_at(value: &Value, schema: &Value, path: &str) -> Result<()> {
+    if let Some(expected) = schema.get("type") {
+        let allowed: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !allowed.is_empty() && !allowed.iter().any(|name| matches_type(value, name)) {
+            bail!(
+                "{path}: expected {}, got {}",
+                allowed.join(" or "),
+                type_name(value)
+            );
+        }
+    }
+
+    if let Some(options) = schema.get("enum").and_then(Value::as_array) {
+        if !options.contains(value) {
+            bail!("{path}: {value} is not one of the allowed values");
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(key) {
+                    bail!("{path}: missing required field `{key}`");
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, field_schema) in properties {
+                if let Some(field) = fields.get(key) {
+                    validate_at(field, field_schema, &format!("{path}.{key}"))?;
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            validate_at(item, item_schema, &format!("{path}[{i}]"))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn matches_type(value: &Value, name: &str) -> bool {
+    match name {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        // Unknown type names are not ours to reject.
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn weather_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "city": { "type": "string" },
+                "temp_c": { "type": "number" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "unit": { "enum": ["c", "f"] }
+            },
+            "required": ["city", "temp_c"]
+        })
+    }
+
+    #[test]
+    fn extracts_json_from_fences_and_prose() {
+        let expected = json!({"city": "Oslo", "temp_c": 3});
+        for reply in [
+            r#"{"city": "Oslo", "temp_c": 3}"#,
+            "```json\n{\"city\": \"Oslo\", \"temp_c\": 3}\n```",
+            r#"Sure! Here you go: {"city": "Oslo", "temp_c": 3} Let me know."#,
+        ] {
+            assert_eq!(extract_json(reply).unwrap(), expected, "reply: {reply}");
+        }
+        assert_eq!(
+            extract_json(r#"Note {braces} first, then {"a": "}"}"#).unwrap(),
+            json!({"a": "}"})
+        );
+        assert!(extract_json("no json here").is_err());
+    }
+
+    #[test]
+    fn validates_against_schema_subset() {
+        let schema = weather_schema();
+        assert!(parse_json_reply(
+            r#"{"city": "Oslo", "temp_c": 3.5, "tags": ["cold"], "unit": "c"}"#,
+            Some(&schema)
+        )
+        .is_ok());
+
+        let missing = parse_json_reply(r#"{"city": "Oslo"}"#, Some(&schema)).unwrap_err();
+        assert!(format!("{missing:#}").contains("missing required field `temp_c`"));
+
+        let wrong_item = parse_json_reply(
+            r#"{"city": "Oslo", "temp_c": 3, "tags": [1]}"#,
+            Some(&schema),
+        )
+        .unwrap_err();
+        assert!(format!("{wrong_item:#}").contains("$.tags[0]: expected string, got number"));
+
+        let bad_enum = parse_json_reply(
+            r#"{"city": "Oslo", "temp_c": 3, "unit": "k"}"#,
+            Some(&schema),
+        )
+        .unwrap_err();
+        assert!(format!("{bad_enum:#}").contains("$.unit"));
+    }
+
+    #[test]
+    fn response_format_uses_schema_when_given() {
+        assert_eq!(openai_response_format(None), json!({"type": "json_object"}));
+        let format = openai_response_format(Some(&weather_schema()));
+        assert_eq!(format["type"], "json_schema");
+        assert_eq!(format["json_schema"]["schema"], weather_schema());
+    }
+}
@@ -70,6 +70,8 @@ pub struct ChatResponse {
     /// sent back in subsequent API requests — some providers reject tool-call
     /// history that omits this field.
     pub reasoning_content: Option<String>,
+    /// How the completion ended; a refusal carries no text or tool calls.
+    pub outcome: ChatOutcome,
 }
 
 impl ChatResponse {
@@ -82,6 +84,17 @@ impl ChatResponse {
     pub fn text_or_empty(&self) -> &str {
         self.text.as_deref().unwrap_or("")
     }
+
+    /// A response for a request the provider declined.
+    pub fn refused(outcome: ChatOutcome, usage: Option<TokenUsage>) -> Self {
+        Self {
+            text: None,
+            tool_calls: Vec::new(),
+            usage,
+            reasoning_content: None,
+            outcome,
+        }
+    }
 }
 
 /// Request payload for provider chat calls.
@@ -218,6 +231,103 @@ pub struct ProviderCapabilityError {
     pub message: String,
 }
 
+/// Structured error for a non-success HTTP response from a provider API,
+/// built by [`super::api_error`]. Carries the status so retry decisions do
+/// not depend on parsing the message.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{provider} API error ({status}): {body}")]
+pub struct ProviderHttpError {
+    pub provider: String,
+    pub status: reqwest::StatusCode,
+    /// Sanitized response body.
+    pub body: String,
+}
+
+/// How a completion ended.
+///
+/// A refusal is an answer, not a failure: the provider declined the request
+/// (a content-policy refusal, or output removed by its content filter) and
+/// every other provider would decline the same question.
+/// [`super::reliable::ReliableProvider`] therefore neither retries nor falls
+/// back on it (unless `[reliability] fallback_on_refusal` is set), and
+/// channels answer with the `refused` template instead of an error reply.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ChatOutcome {
+    /// The model answered with text, tool calls, or both.
+    #[default]
+    Completed,
+    /// `reason` is the provider's refusal message, or its stop reason when it
+    /// gave none.
+    Refused { reason: String },
+}
+
+impl ChatOutcome {
+    /// A refusal explained by `message` when the provider sent one, else by
+    /// the `stop_reason` it finished with.
+    pub fn refused(message: Option<&str>, stop_reason: &str) -> Self {
+        let reason = message
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+            .unwrap_or(stop_reason);
+        Self::Refused {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn is_refused(&self) -> bool {
+        matches!(self, Self::Refused { .. })
+    }
+
+    /// `Ok` for a completed answer. The text-only chat methods have no
+    /// outcome to return, so they report a refusal as [`RefusedError`].
+    pub fn ensure_completed(&self) -> Result<(), RefusedError> {
+        match self {
+            Self::Completed => Ok(()),
+            Self::Refused { reason } => Err(RefusedError {
+                reason: reason.clone(),
+            }),
+        }
+    }
+}
+
+/// A [`ChatOutcome::Refused`] seen by a caller that can only take text:
+/// `chat_with_system`, `chat_with_history`, or a turn of the agent loop.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("provider refused the request: {reason}")]
+pub struct RefusedError {
+    pub reason: String,
+}
+
+impl RefusedError {
+    /// The refusal carried anywhere in `err`'s chain.
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+}
+
+/// The [`ChatResponse`] for the result of a text-only chat method, turning a
+/// [`RefusedError`] back into a refused outcome.
+fn chat_response_from_text(result: anyhow::Result<String>) -> anyhow::Result<ChatResponse> {
+    match result {
+        Ok(text) => Ok(ChatResponse {
+            text: Some(text),
+            tool_calls: Vec::new(),
+            usage: None,
+            reasoning_content: None,
+            outcome: ChatOutcome::Completed,
+        }),
+        Err(err) => match RefusedError::find(&err) {
+            Some(refused) => Ok(ChatResponse::refused(
+                ChatOutcome::Refused {
+                    reason: refused.reason.clone(),
+                },
+                None,
+            )),
+            None => Err(err),
+        },
+    }
+}
+
 /// Provider capabilities declaration.
 ///
 /// Describes what features a provider supports, enabling intelligent
@@ -357,25 +467,36 @@ pub trait Provider: Send + Sync {
 
                 let text = self
                     .chat_with_history(&modified_messages, model, temperature)
-                    .await?;
-                return Ok(ChatResponse {
-                    text: Some(text),
-                    tool_calls: Vec::new(),
-                    usage: None,
-                    reasoning_content: None,
-                });
+                    .await;
+                return chat_response_from_text(text);
             }
         }
 
         let text = self
             .chat_with_history(request.messages, model, temperature)
+            .await;
+        chat_response_from_text(text)
+    }
+
+    /// One-shot chat that must answer with JSON, optionally matching `schema`.
+    ///
+    /// Providers with a native JSON / structured-output mode override this to
+    /// switch it on. The default asks for JSON in the system prompt and
+    /// extracts it leniently from the reply. Either way the parsed value is
+    /// checked against `schema` (see [`super::structured`]).
+    async fn chat_json(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+        schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<serde_json::Value> {
+        let system = super::structured::json_mode_system_prompt(system_prompt, schema.as_ref());
+        let text = self
+            .chat_with_system(Some(&system), message, model, temperature)
             .await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: Vec::new(),
-            usage: None,
-            reasoning_content: None,
-        })
+        super::structured::parse_json_reply(&text, schema.as_ref())
     }
 
     /// Whether provider supports native tool calls over API.
@@ -404,13 +525,8 @@ pub trait Provider: Send + Sync {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<ChatResponse> {
-        let text = self.chat_with_history(messages, model, temperature).await?;
-        Ok(ChatResponse {
-            text: Some(text),
-            tool_calls: Vec::new(),
-            usage: None,
-            reasoning_content: None,
-        })
+        let text = self.chat_with_history(messages, model, temperature).await;
+        chat_response_from_text(text)
     }
 
     /// Whether provider supports streaming responses.
@@ -539,6 +655,7 @@ mod tests {
             tool_calls: vec![],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         };
         assert!(!empty.has_tool_calls());
         assert_eq!(empty.text_or_empty(), "");
@@ -552,6 +669,7 @@ mod tests {
             }],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         };
         assert!(with_tools.has_tool_calls());
         assert_eq!(with_tools.text_or_empty(), "Let me check");
@@ -574,6 +692,7 @@ mod tests {
                 output_tokens: Some(50),
             }),
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         };
         assert_eq!(resp.usage.as_ref().unwrap().input_tokens, Some(100));
         assert_eq!(resp.usage.as_ref().unwrap().output_tokens, Some(50));
@@ -952,4 +1071,44 @@ mod tests {
 
         assert!(message.contains("non-prompt-guided"));
     }
+
+    /// Answers with fenced JSON when the system prompt asks for JSON.
+    struct FencedJsonProvider;
+
+    #[async_trait]
+    impl Provider for FencedJsonProvider {
+        async fn chat_with_system(
+            &self,
+            system: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let system = system.unwrap_or_default();
+            assert!(system.starts_with("BASE"));
+            assert!(system.contains("JSON Schema"));
+            Ok("Here it is:\n```json\n{\"answer\": 42}\n```".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn chat_json_default_extracts_and_validates() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "answer": { "type": "integer" } },
+            "required": ["answer"]
+        });
+        let value = FencedJsonProvider
+            .chat_json(Some("BASE"), "question", "model", 0.0, Some(schema))
+            .await
+            .unwrap();
+        assert_eq!(value, serde_json::json!({"answer": 42}));
+
+        let strict = serde_json::json!({ "type": "object", "required": ["reason"] });
+        let err = FencedJsonProvider
+            .chat_json(Some("BASE"), "question", "model", 0.0, Some(strict))
+            .await
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("missing required field `reason`"));
+    }
 }
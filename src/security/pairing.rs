@@ -24,6 +24,8 @@ const MAX_TRACKED_CLIENTS: usize = 10_000;
 const FAILED_ATTEMPT_RETENTION_SECS: u64 = 900; // 15 min
 /// Minimum interval between full sweeps of the failed-attempt map.
 const FAILED_ATTEMPT_SWEEP_INTERVAL_SECS: u64 = 300; // 5 min
+/// Hex characters of a token hash shown as its fingerprint.
+const TOKEN_FINGERPRINT_LEN: usize = 16;
 
 /// Per-client failed attempt state with optional absolute lockout deadline.
 #[derive(Debug, Clone, Copy)]
@@ -220,6 +222,34 @@ impl PairingGuard {
         let tokens = self.paired_tokens.lock();
         tokens.iter().cloned().collect()
     }
+
+    /// Fingerprints of all paired tokens, sorted. Safe to show to operators:
+    /// they are derived from the stored hash, never from the token itself.
+    pub fn fingerprints(&self) -> Vec<String> {
+        let tokens = self.paired_tokens.lock();
+        let mut fingerprints: Vec<String> = tokens.iter().map(|h| token_fingerprint(h)).collect();
+        fingerprints.sort();
+        fingerprints
+    }
+
+    /// Revoke the paired token with this fingerprint. Returns false when no
+    /// token matches. Revoking the last token does not issue a new pairing
+    /// code; restart the gateway to pair again.
+    pub fn revoke(&self, fingerprint: &str) -> bool {
+        let fingerprint = fingerprint.trim().to_ascii_lowercase();
+        if fingerprint.len() != TOKEN_FINGERPRINT_LEN {
+            return false;
+        }
+        let mut tokens = self.paired_tokens.lock();
+        let before = tokens.len();
+        tokens.retain(|hash| token_fingerprint(hash) != fingerprint);
+        tokens.len() != before
+    }
+}
+
+/// Fingerprint of a stored token hash: its first 16 hex characters.
+pub fn token_fingerprint(hash: &str) -> String {
+    hash.chars().take(TOKEN_FINGERPRINT_LEN).collect()
 }
 
 /// Normalize a client identifier: trim whitespace, map empty to `"unknown"`.
@@ -309,11 +339,17 @@ pub fn constant_time_eq(a: &str, b: &str) -> bool {
 }
 
 /// Check if a host string represents a non-localhost bind address.
+///
+/// Any loopback IP (`127.0.0.0/8`, `::1`) counts as local; unparseable
+/// hostnames other than `localhost` are treated as public.
 pub fn is_public_bind(host: &str) -> bool {
-    !matches!(
-        host,
-        "127.0.0.1" | "localhost" | "::1" | "[::1]" | "0:0:0:0:0:0:0:1"
-    )
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost") {
+        return false;
+    }
+    !host
+        .parse::<std::net::IpAddr>()
+        .is_ok_and(|ip| ip.is_loopback())
 }
 
 #[cfg(test)]
@@ -409,6 +445,22 @@ mod tests {
         }
     }
 
+    #[test]
+    async fn revoke_by_fingerprint_rejects_only_that_token() {
+        let guard = PairingGuard::new(true, &["zc_a".into(), "zc_b".into()]);
+        let fingerprints = guard.fingerprints();
+        assert_eq!(fingerprints.len(), 2);
+        assert!(fingerprints.iter().all(|f| f.len() == 16));
+
+        let revoked = token_fingerprint(&hash_token("zc_a"));
+        assert!(!guard.revoke(&revoked[..8]), "prefixes must not match");
+        assert!(guard.revoke(&revoked.to_ascii_uppercase()));
+        assert!(!guard.revoke(&revoked));
+        assert!(!guard.is_authenticated("zc_a"));
+        assert!(guard.is_authenticated("zc_b"));
+        assert_eq!(guard.fingerprints().len(), 1);
+    }
+
     #[test]
     async fn pair_then_authenticate() {
         let guard = PairingGuard::new(true, &[]);
@@ -453,6 +505,9 @@ mod tests {
         assert!(!is_public_bind("localhost"));
         assert!(!is_public_bind("::1"));
         assert!(!is_public_bind("[::1]"));
+        assert!(!is_public_bind("0:0:0:0:0:0:0:1"));
+        assert!(!is_public_bind("127.0.0.2"));
+        assert!(!is_public_bind("LOCALHOST"));
     }
 
     #[test]
@@ -464,6 +519,8 @@ mod tests {
     async fn real_ip_is_public() {
         assert!(is_public_bind("192.168.1.100"));
         assert!(is_public_bind("10.0.0.1"));
+        assert!(is_public_bind("::"));
+        assert!(is_public_bind("gateway.example.com"));
     }
 
     // ── constant_time_eq ─────────────────────────────────────
@@ -0,0 +1,417 @@
+//! Path containment primitives for untrusted path input.
+//!
+//! Anything that turns a name from an archive, a manifest, a CLI argument or
+//! a network peer into a filesystem path goes through these helpers instead
+//! of ad-hoc `contains("..")` / `canonicalize()` checks:
+//!
+//! - [`is_single_component`] for names that must stay one path segment
+//!   (skill names, tool names).
+//! - [`join_checked`] for relative paths resolved under a base directory.
+//! - [`assert_contained`] for verifying that an already-built path stays
+//!   inside a base directory, following symlinks that exist on disk.
+//!
+//! Both `/` and `\` count as separators on every platform, and a component
+//! made only of dots and spaces (`...`, `.. `) is rejected because Windows
+//! trims it to `..`. Every check fails closed: a path that cannot be
+//! resolved is treated as escaping.
+
+use anyhow::{bail, Context, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Longest single path component most filesystems accept (`NAME_MAX`).
+pub const MAX_COMPONENT_BYTES: usize = 255;
+
+/// Whether `name` is exactly one normal path segment: non-empty, no
+/// separators of either kind, not `.`/`..` (or a Windows spelling of them),
+/// no drive prefix, no NUL, and at most [`MAX_COMPONENT_BYTES`] long.
+pub fn is_single_component(name: &str) -> bool {
+    check_component(name).is_ok() && !name.contains(['/', '\\']) && !has_drive_prefix(name) && {
+        let mut components = Path::new(name).components();
+        matches!(components.next(), Some(Component::Normal(part)) if part == name)
+            && components.next().is_none()
+    }
+}
+
+/// Normalize an untrusted relative path into native form.
+///
+/// Splits on `/` and `\`, drops empty and `.` segments, and rejects absolute
+/// paths, drive prefixes, `..` (in any position), NUL bytes and oversized
+/// components. An input with no segments left normalizes to an empty path.
+pub fn normalize_relative(untrusted: &str) -> Result<PathBuf> {
+    if untrusted.contains('\0') {
+        bail!("path contains a null byte: {untrusted:?}");
+    }
+    if untrusted.starts_with(['/', '\\']) || has_drive_prefix(untrusted) {
+        bail!("path must be relative: {untrusted}");
+    }
+    let mut out = PathBuf::new();
+    for segment in untrusted.split(['/', '\\']) {
+        if segment.is_empty() || segment == "." {
+            continue;
+        }
+        check_component(segment).with_context(|| format!("unsafe path: {untrusted}"))?;
+        out.push(segment);
+    }
+    Ok(out)
+}
+
+/// Resolve `untrusted_relative` under `base` and verify the result stays
+/// inside it. The target does not need to exist; existing ancestors are
+/// resolved so a symlink inside `base` cannot redirect the path out of it.
+pub fn join_checked(base: &Path, untrusted_relative: &str) -> Result<PathBuf> {
+    let joined = base.join(normalize_relative(untrusted_relative)?);
+    assert_contained(base, &joined)?;
+    Ok(joined)
+}
+
+/// Fail unless `candidate` lies inside `base` (or is `base`).
+///
+/// The check is lexical first (`..` may not climb above `base`), then on
+/// disk: the deepest existing ancestor of `candidate` is canonicalized and
+/// compared against the canonical `base`. If anything that exists cannot be
+/// resolved (a dangling symlink, a permission error) the path is rejected.
+/// A `base` that does not exist yet leaves only the lexical check, since
+/// nothing below it exists either.
+pub fn assert_contained(base: &Path, candidate: &Path) -> Result<()> {
+    let lexical_base = lexical_normalize(base)
+        .with_context(|| format!("cannot resolve base directory {}", base.display()))?;
+    let lexical = lexical_normalize(candidate)
+        .with_context(|| format!("{} escapes {}", candidate.display(), base.display()))?;
+    if !lexical.starts_with(&lexical_base) {
+        bail!("{} escapes {}", candidate.display(), base.display());
+    }
+
+    let canonical_base = match base.canonicalize() {
+        Ok(path) => path,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound && !exists(base) => return Ok(()),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("cannot resolve base directory {}", base.display()))
+        }
+    };
+    let existing = lexical
+        .ancestors()
+        .find(|ancestor| exists(ancestor))
+        .unwrap_or(&lexical_base);
+    let canonical = existing.canonicalize().with_context(|| {
+        format!(
+            "cannot resolve {} while checking {}",
+            existing.display(),
+            candidate.display()
+        )
+    })?;
+    if !is_within(&canonical, &canonical_base) {
+        bail!(
+            "{} escapes {} (resolves to {})",
+            candidate.display(),
+            base.display(),
+            canonical.display()
+        );
+    }
+    Ok(())
+}
+
+/// Whether `path` lies inside `root`, ignoring verbatim prefixes on either
+/// side and (on Windows) ASCII case. Both should already be canonicalized.
+pub fn is_within(path: &Path, root: &Path) -> bool {
+    let path = comparable(path);
+    let root = comparable(root);
+    Path::new(&path).starts_with(Path::new(&root))
+}
+
+/// Strip a Windows verbatim prefix: `\\?\C:\x` → `C:\x`,
+/// `\\?\UNC\server\share` → `\\server\share`. Other strings are unchanged.
+pub fn strip_verbatim_prefix(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{rest}")
+    } else if let Some(rest) = path.strip_prefix(r"\\?\") {
+        rest.to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+pub(crate) fn has_drive_prefix(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+fn comparable(path: &Path) -> String {
+    let stripped = strip_verbatim_prefix(&path.to_string_lossy());
+    if cfg!(windows) {
+        stripped.replace('/', "\\").to_ascii_lowercase()
+    } else {
+        stripped
+    }
+}
+
+fn check_component(component: &str) -> Result<()> {
+    if component.is_empty() {
+        bail!("empty path component");
+    }
+    if component.contains('\0') {
+        bail!("path component contains a null byte");
+    }
+    if component.len() > MAX_COMPONENT_BYTES {
+        bail!(
+            "path component is {} bytes (limit {MAX_COMPONENT_BYTES})",
+            component.len()
+        );
+    }
+    // `.`, `..`, and what Windows trims to them (`...`, `.. `, `. `).
+    if component.trim_end_matches(['.', ' ']).is_empty() {
+        bail!("path component {component:?} refers to the current or parent directory");
+    }
+    Ok(())
+}
+
+/// Resolve `.` and `..` without touching the filesystem. Fails if `..`
+/// climbs above the start of `path`.
+fn lexical_normalize(path: &Path) -> Result<PathBuf> {
+    let mut out = PathBuf::new();
+    let mut depth = 0usize;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => out.push(component),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if depth == 0 {
+                    bail!("{} climbs above its root", path.display());
+                }
+                out.pop();
+                depth -= 1;
+            }
+            Component::Normal(part) => {
+                out.push(part);
+                depth += 1;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Existence without following a final symlink, so a dangling link still
+/// counts as present (and then fails to canonicalize).
+fn exists(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_components() {
+        for ok in [
+            "weather",
+            "my-tool_2",
+            "v1.2.0",
+            "notes.",
+            "\u{2025}",
+            "ｔｏｏｌ",
+        ] {
+            assert!(is_single_component(ok), "expected {ok:?} to be accepted");
+        }
+        let long = "a".repeat(MAX_COMPONENT_BYTES + 1);
+        for bad in [
+            "",
+            ".",
+            "..",
+            "...",
+            ".. ",
+            ". ",
+            "a/b",
+            "a\\b",
+            "/abs",
+            "\\abs",
+            "C:",
+            "c:tool",
+            "nul\0byte",
+            long.as_str(),
+        ] {
+            assert!(!is_single_component(bad), "expected {bad:?} to be rejected");
+        }
+        assert!(is_single_component(&"a".repeat(MAX_COMPONENT_BYTES)));
+    }
+
+    #[test]
+    fn relative_paths_normalize_or_fail() {
+        let expected: PathBuf = ["docs", "guide.md"].iter().collect();
+        for raw in [
+            "docs/guide.md",
+            "docs\\guide.md",
+            "./docs//guide.md",
+            "docs/./guide.md",
+        ] {
+            assert_eq!(normalize_relative(raw).unwrap(), expected, "{raw:?}");
+        }
+        assert_eq!(normalize_relative("./").unwrap(), PathBuf::new());
+
+        let long = format!("docs/{}", "x".repeat(MAX_COMPONENT_BYTES + 1));
+        for raw in [
+            "..",
+            "../x",
+            "./..",
+            "./../x",
+            "docs/../../x",
+            "docs/../x",
+            "docs\\..\\x",
+            "docs/.../x",
+            "docs/.. /x",
+            "docs/. /x",
+            "/etc/passwd",
+            "\\Windows\\system.ini",
+            "C:\\x",
+            "c:x",
+            "docs/\0",
+            long.as_str(),
+        ] {
+            assert!(
+                normalize_relative(raw).is_err(),
+                "expected {raw:?} to be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn unicode_lookalikes_stay_literal_and_contained() {
+        let base = tempfile::tempdir().unwrap();
+        // Two-dot leader, fullwidth dots, division and fullwidth slashes are
+        // ordinary characters to the filesystem, not separators or `..`.
+        for raw in [
+            "\u{2025}/x",
+            "\u{FF0E}\u{FF0E}/x",
+            "a\u{2215}..\u{2215}b",
+            "a\u{FF0F}..\u{FF0F}b",
+        ] {
+            let joined = join_checked(base.path(), raw).unwrap();
+            assert!(joined.starts_with(base.path()), "{raw:?}");
+            assert_eq!(
+                joined
+                    .strip_prefix(base.path())
+                    .unwrap()
+                    .components()
+                    .count(),
+                raw.split('/').count(),
+                "{raw:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn join_checked_does_not_require_the_target_to_exist() {
+        let base = tempfile::tempdir().unwrap();
+        let joined = join_checked(base.path(), "new/dir/file.md").unwrap();
+        assert_eq!(joined, base.path().join("new").join("dir").join("file.md"));
+        assert!(!joined.exists());
+
+        let missing_base = base.path().join("not-yet");
+        assert!(join_checked(&missing_base, "a/b").is_ok());
+        assert!(join_checked(&missing_base, "a/../../b").is_err());
+    }
+
+    #[test]
+    fn assert_contained_is_lexical_and_on_disk() {
+        let base = tempfile::tempdir().unwrap();
+        let root = base.path();
+        assert!(assert_contained(root, root).is_ok());
+        assert!(assert_contained(root, &root.join("a/./b/../c")).is_ok());
+        assert!(assert_contained(root, &root.join("a/../../escape")).is_err());
+        assert!(assert_contained(root, &root.join("..")).is_err());
+        let sibling = root.with_file_name(format!(
+            "{}-evil",
+            root.file_name().unwrap().to_string_lossy()
+        ));
+        assert!(assert_contained(root, &sibling.join("x")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_out_of_base_are_rejected() {
+        let outside = tempfile::tempdir().unwrap();
+        let base = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), base.path().join("link")).unwrap();
+        let err = join_checked(base.path(), "link/new-file").unwrap_err();
+        assert!(err.to_string().contains("escapes"), "{err:#}");
+
+        std::fs::create_dir(base.path().join("real")).unwrap();
+        std::os::unix::fs::symlink(base.path().join("real"), base.path().join("inner")).unwrap();
+        assert!(join_checked(base.path(), "inner/file").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unresolvable_paths_fail_closed() {
+        let base = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(
+            base.path().join("does-not-exist"),
+            base.path().join("dangling"),
+        )
+        .unwrap();
+        let err = assert_contained(base.path(), &base.path().join("dangling")).unwrap_err();
+        assert!(err.to_string().contains("cannot resolve"), "{err:#}");
+        assert!(join_checked(base.path(), "dangling/child").is_err());
+    }
+
+    /// Tiny deterministic generator so failures reproduce from the seed.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+            items[usize::try_from(self.next_u64() % items.len() as u64).unwrap()]
+        }
+    }
+
+    #[test]
+    fn random_segment_sequences_never_escape() {
+        const SEGMENTS: &[&str] = &[
+            "a", "b", "skill", ".", "..", "...", ".. ", "", "x.", "\u{2025}", "C:", "con",
+        ];
+        const SEPARATORS: &[&str] = &["/", "\\", "//", "/./"];
+        let base = tempfile::tempdir().unwrap();
+        let mut rng = XorShift(0x5eed_cafe_f00d_d00d);
+
+        for _ in 0..5_000 {
+            let count = 1 + rng.next_u64() % 6;
+            let mut raw = String::new();
+            if rng.next_u64() % 8 == 0 {
+                raw.push_str(rng.pick(SEPARATORS));
+            }
+            for i in 0..count {
+                if i > 0 {
+                    raw.push_str(rng.pick(SEPARATORS));
+                }
+                raw.push_str(rng.pick(SEGMENTS));
+            }
+
+            let traverses = raw.split(['/', '\\']).any(|segment| {
+                !segment.is_empty()
+                    && segment.trim_end_matches(['.', ' ']).is_empty()
+                    && segment != "."
+            });
+            match join_checked(base.path(), &raw) {
+                Ok(joined) => {
+                    assert!(!traverses, "{raw:?} was accepted");
+                    assert!(joined.starts_with(base.path()), "{raw:?} -> {joined:?}");
+                    assert!(
+                        joined
+                            .components()
+                            .all(|c| !matches!(c, Component::ParentDir | Component::CurDir)),
+                        "{raw:?} -> {joined:?}"
+                    );
+                }
+                Err(_) => assert!(
+                    traverses || raw.starts_with(['/', '\\']) || has_drive_prefix(&raw),
+                    "{raw:?} was rejected"
+                ),
+            }
+        }
+    }
+}
@@ -16,6 +16,12 @@
 //
 // For sovereign users who prefer plaintext, `secrets.encrypt = false` disables this.
 //
+// Setting `ZEROCLAW_SECRETS_PASSPHRASE` replaces the key file with a key derived
+// from the passphrase (PBKDF2-HMAC-SHA256 over a random per-install salt in
+// `~/.zeroclaw/.secret_salt`), so copying the config and salt alone is not
+// enough to recover secrets. Values encrypted under one key source cannot be
+// decrypted under the other.
+//
 // Migration: values with the legacy `enc:` prefix (XOR cipher) are decrypted
 // using the old algorithm for backward compatibility. New encryptions always
 // produce `enc2:` (ChaCha20-Poly1305).
@@ -25,7 +31,9 @@ use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
 use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
 use std::fs;
 use std::io::Write;
+use std::num::NonZeroU32;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 /// Length of the random encryption key in bytes (256-bit, matches `ChaCha20`).
 const KEY_LEN: usize = 32;
@@ -33,22 +41,133 @@ const KEY_LEN: usize = 32;
 /// ChaCha20-Poly1305 nonce length in bytes.
 const NONCE_LEN: usize = 12;
 
+/// Environment variable holding an optional passphrase for key derivation.
+pub const SECRETS_PASSPHRASE_ENV: &str = "ZEROCLAW_SECRETS_PASSPHRASE";
+
+/// Salt length for passphrase key derivation.
+const SALT_LEN: usize = 16;
+
+/// PBKDF2-HMAC-SHA256 iteration count for passphrase-derived keys.
+const PBKDF2_ITERATIONS: u32 = 600_000;
+
 /// Manages encrypted storage of secrets (API keys, tokens, etc.)
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct SecretStore {
     /// Path to the key file (`~/.zeroclaw/.secret_key`)
     key_path: PathBuf,
+    /// Path to the passphrase salt (`~/.zeroclaw/.secret_salt`)
+    salt_path: PathBuf,
     /// Whether encryption is enabled
     enabled: bool,
+    /// Passphrase to derive the key from instead of reading `key_path`
+    passphrase: Option<String>,
+    /// Derived key, computed once per store (PBKDF2 is deliberately slow)
+    derived_key: Arc<OnceLock<Vec<u8>>>,
+}
+
+impl std::fmt::Debug for SecretStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretStore")
+            .field("key_path", &self.key_path)
+            .field("enabled", &self.enabled)
+            .field("passphrase_configured", &self.passphrase.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl SecretStore {
     /// Create a new secret store rooted at the given directory.
+    ///
+    /// Uses `ZEROCLAW_SECRETS_PASSPHRASE` for key derivation when it is set and
+    /// non-empty; otherwise the random key file.
     pub fn new(zeroclaw_dir: &Path, enabled: bool) -> Self {
+        let passphrase = std::env::var(SECRETS_PASSPHRASE_ENV)
+            .ok()
+            .filter(|value| !value.is_empty());
+        Self::with_passphrase(zeroclaw_dir, enabled, passphrase)
+    }
+
+    /// Create a store with an explicit passphrase (`None` uses the key file).
+    pub fn with_passphrase(zeroclaw_dir: &Path, enabled: bool, passphrase: Option<String>) -> Self {
         Self {
             key_path: zeroclaw_dir.join(".secret_key"),
+            salt_path: zeroclaw_dir.join(".secret_salt"),
             enabled,
+            passphrase,
+            derived_key: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Path of the key file this store reads (`~/.zeroclaw/.secret_key`).
+    pub fn key_path(&self) -> &Path {
+        &self.key_path
+    }
+
+    /// Start a key rotation: a store that encrypts under a fresh key staged
+    /// next to the current one (`.secret_key.rotating`, or a new salt in
+    /// passphrase mode). Readers keep using the old key until
+    /// [`Self::commit_rotation`] moves the staged file into place.
+    pub fn stage_rotation(&self) -> Result<Self> {
+        let current = if self.passphrase.is_some() {
+            &self.salt_path
+        } else {
+            &self.key_path
+        };
+        let staged_path = rotating_path(current);
+        // A leftover means an earlier rotation stopped part-way, and some
+        // files may already be encrypted under it. Never overwrite it.
+        anyhow::ensure!(
+            !staged_path.exists(),
+            "{} is left over from an interrupted rotation. If secrets fail to \
+             decrypt, move it over {}; otherwise delete it, then rotate again",
+            staged_path.display(),
+            current.display()
+        );
+
+        let mut staged = self.clone();
+        staged.derived_key = Arc::new(OnceLock::new());
+        if self.passphrase.is_some() {
+            staged.salt_path = staged_path;
+        } else {
+            staged.key_path = staged_path;
         }
+        staged.encryption_key()?;
+        Ok(staged)
+    }
+
+    /// Replace the current key (or salt) with the one staged by
+    /// [`Self::stage_rotation`]. Call only after everything encrypted under
+    /// the old key has been rewritten.
+    pub fn commit_rotation(&self) -> Result<()> {
+        let staged = if self.passphrase.is_some() {
+            &self.salt_path
+        } else {
+            &self.key_path
+        };
+        let file_name = staged
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(ROTATING_SUFFIX))
+            .context("Store was not created by stage_rotation")?;
+        let target = staged.with_file_name(file_name);
+        fs::rename(staged, &target).with_context(|| {
+            format!(
+                "Failed to move {} into place at {}",
+                staged.display(),
+                target.display()
+            )
+        })
+    }
+
+    /// Drop a rotation staged by [`Self::stage_rotation`] before anything was
+    /// written under it.
+    pub fn discard_rotation(&self) -> Result<()> {
+        let staged = if self.passphrase.is_some() {
+            &self.salt_path
+        } else {
+            &self.key_path
+        };
+        fs::remove_file(staged).with_context(|| format!("Failed to remove {}", staged.display()))
     }
 
     /// Encrypt a plaintext secret. Returns hex-encoded ciphertext prefixed with `enc2:`.
@@ -59,7 +178,7 @@ impl SecretStore {
             return Ok(plaintext.to_string());
         }
 
-        let key_bytes = self.load_or_create_key()?;
+        let key_bytes = self.encryption_key()?;
         let key = Key::from_slice(&key_bytes);
         let cipher = ChaCha20Poly1305::new(key);
 
@@ -136,13 +255,23 @@ impl SecretStore {
 
         let (nonce_bytes, ciphertext) = blob.split_at(NONCE_LEN);
         let nonce = Nonce::from_slice(nonce_bytes);
-        let key_bytes = self.load_or_create_key()?;
+        let key_bytes = self.encryption_key()?;
         let key = Key::from_slice(&key_bytes);
         let cipher = ChaCha20Poly1305::new(key);
 
-        let plaintext_bytes = cipher
-            .decrypt(nonce, ciphertext)
-            .map_err(|_| anyhow::anyhow!("Decryption failed — wrong key or tampered data"))?;
+        let plaintext_bytes = cipher.decrypt(nonce, ciphertext).map_err(|_| {
+            if self.passphrase.is_some() {
+                anyhow::anyhow!(
+                    "Decryption failed — wrong {SECRETS_PASSPHRASE_ENV}, value encrypted with the key file, or tampered data"
+                )
+            } else {
+                anyhow::anyhow!(
+                    "Decryption failed — wrong key or tampered data (key file: {}). \
+                     A config copied from another machine needs that machine's key file too",
+                    self.key_path.display()
+                )
+            }
+        })?;
 
         String::from_utf8(plaintext_bytes)
             .context("Decrypted secret is not valid UTF-8 — corrupt data")
@@ -168,6 +297,63 @@ impl SecretStore {
         value.starts_with("enc2:")
     }
 
+    /// Key for `enc2:` values: passphrase-derived when configured, otherwise
+    /// the key file.
+    fn encryption_key(&self) -> Result<Vec<u8>> {
+        let Some(passphrase) = self.passphrase.as_deref() else {
+            return self.load_or_create_key();
+        };
+        if let Some(key) = self.derived_key.get() {
+            return Ok(key.clone());
+        }
+        let salt = self.load_or_create_salt()?;
+        let key = derive_key_from_passphrase(passphrase, &salt);
+        Ok(self.derived_key.get_or_init(|| key).clone())
+    }
+
+    /// Load the passphrase salt from disk, or create one if it doesn't exist.
+    fn load_or_create_salt(&self) -> Result<Vec<u8>> {
+        if self.salt_path.exists() {
+            let hex_salt =
+                fs::read_to_string(&self.salt_path).context("Failed to read secret salt file")?;
+            return hex_decode(hex_salt.trim()).context("Secret salt file is corrupt");
+        }
+
+        if let Some(parent) = self.salt_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let salt: [u8; SALT_LEN] = rand::random();
+        match fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&self.salt_path)
+        {
+            Ok(mut salt_file) => {
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    salt_file
+                        .set_permissions(fs::Permissions::from_mode(0o600))
+                        .context("Failed to set salt file permissions")?;
+                }
+                salt_file
+                    .write_all(hex_encode(&salt).as_bytes())
+                    .context("Failed to write secret salt file")?;
+                salt_file
+                    .sync_all()
+                    .context("Failed to fsync secret salt file")?;
+                Ok(salt.to_vec())
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
+                let hex_salt = fs::read_to_string(&self.salt_path)
+                    .context("Failed to read concurrently created secret salt file")?;
+                hex_decode(hex_salt.trim())
+                    .context("Secret salt file is corrupt after concurrent create")
+            }
+            Err(err) => Err(err).context("Failed to create secret salt file"),
+        }
+    }
+
     /// Load the encryption key from disk, or create one if it doesn't exist.
     fn load_or_create_key(&self) -> Result<Vec<u8>> {
         if self.key_path.exists() {
@@ -253,6 +439,15 @@ impl SecretStore {
     }
 }
 
+/// Suffix of a key or salt file staged by [`SecretStore::stage_rotation`].
+const ROTATING_SUFFIX: &str = ".rotating";
+
+fn rotating_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(ROTATING_SUFFIX);
+    path.with_file_name(name)
+}
+
 /// XOR cipher with repeating key. Same function for encrypt and decrypt.
 fn xor_cipher(data: &[u8], key: &[u8]) -> Vec<u8> {
     if key.is_empty() {
@@ -264,6 +459,20 @@ fn xor_cipher(data: &[u8], key: &[u8]) -> Vec<u8> {
         .collect()
 }
 
+/// Derive a 256-bit key from a passphrase with PBKDF2-HMAC-SHA256.
+fn derive_key_from_passphrase(passphrase: &str, salt: &[u8]) -> Vec<u8> {
+    let mut key = vec![0u8; KEY_LEN];
+    let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("iteration count is non-zero");
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    key
+}
+
 /// Generate a random 256-bit key using the OS CSPRNG.
 ///
 /// Uses `OsRng` (via `getrandom`) directly, providing full 256-bit entropy
@@ -345,6 +554,41 @@ mod tests {
         assert_eq!(result, "sk-plaintext-key");
     }
 
+    #[test]
+    fn passphrase_store_roundtrip_without_key_file() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::with_passphrase(tmp.path(), true, Some("correct horse".into()));
+
+        let encrypted = store.encrypt("sk-passphrase-secret").unwrap();
+        assert!(encrypted.starts_with("enc2:"));
+        assert_eq!(store.decrypt(&encrypted).unwrap(), "sk-passphrase-secret");
+        assert!(!tmp.path().join(".secret_key").exists());
+        assert!(tmp.path().join(".secret_salt").exists());
+
+        // A fresh store with the same passphrase and salt decrypts.
+        let reopened = SecretStore::with_passphrase(tmp.path(), true, Some("correct horse".into()));
+        assert_eq!(
+            reopened.decrypt(&encrypted).unwrap(),
+            "sk-passphrase-secret"
+        );
+
+        let wrong = SecretStore::with_passphrase(tmp.path(), true, Some("battery staple".into()));
+        let err = wrong.decrypt(&encrypted).unwrap_err().to_string();
+        assert!(err.contains(SECRETS_PASSPHRASE_ENV));
+
+        let key_file_store = SecretStore::with_passphrase(tmp.path(), true, None);
+        assert!(key_file_store.decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    fn debug_output_hides_passphrase() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::with_passphrase(tmp.path(), true, Some("hunter2".into()));
+        let debug = format!("{store:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("passphrase_configured: true"));
+    }
+
     #[test]
     fn disabled_store_returns_plaintext() {
         let tmp = TempDir::new().unwrap();
@@ -467,6 +711,62 @@ mod tests {
         assert!(result.is_err(), "Decrypting with a different key must fail");
     }
 
+    #[test]
+    fn wrong_key_error_names_the_key_file() {
+        let tmp1 = TempDir::new().unwrap();
+        let tmp2 = TempDir::new().unwrap();
+        let store1 = SecretStore::with_passphrase(tmp1.path(), true, None);
+        let store2 = SecretStore::with_passphrase(tmp2.path(), true, None);
+
+        let encrypted = store1.encrypt("secret-for-store1").unwrap();
+        let err = store2.decrypt(&encrypted).unwrap_err().to_string();
+        assert!(err.contains("wrong key"), "{err}");
+        assert!(
+            err.contains(&tmp2.path().join(".secret_key").display().to_string()),
+            "{err}"
+        );
+        assert!(!err.contains("secret-for-store1"));
+    }
+
+    #[test]
+    fn rotation_switches_key_only_on_commit() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::with_passphrase(tmp.path(), true, None);
+        let old_value = store.encrypt("sk-rotate-me").unwrap();
+        let old_key = fs::read_to_string(store.key_path()).unwrap();
+
+        let staged = store.stage_rotation().unwrap();
+        let new_value = staged.encrypt("sk-rotate-me").unwrap();
+        assert!(tmp.path().join(".secret_key.rotating").exists());
+        assert_eq!(fs::read_to_string(store.key_path()).unwrap(), old_key);
+        assert!(store.decrypt(&new_value).is_err());
+
+        staged.commit_rotation().unwrap();
+        assert!(!tmp.path().join(".secret_key.rotating").exists());
+        assert_ne!(fs::read_to_string(store.key_path()).unwrap(), old_key);
+
+        let reopened = SecretStore::with_passphrase(tmp.path(), true, None);
+        assert_eq!(reopened.decrypt(&new_value).unwrap(), "sk-rotate-me");
+        assert!(reopened.decrypt(&old_value).is_err());
+    }
+
+    #[test]
+    fn passphrase_rotation_replaces_the_salt() {
+        let tmp = TempDir::new().unwrap();
+        let passphrase = Some("correct horse".to_string());
+        let store = SecretStore::with_passphrase(tmp.path(), true, passphrase.clone());
+        let old_value = store.encrypt("sk-rotate-me").unwrap();
+
+        let staged = store.stage_rotation().unwrap();
+        let new_value = staged.encrypt("sk-rotate-me").unwrap();
+        staged.commit_rotation().unwrap();
+        assert!(!tmp.path().join(".secret_key").exists());
+
+        let reopened = SecretStore::with_passphrase(tmp.path(), true, passphrase);
+        assert_eq!(reopened.decrypt(&new_value).unwrap(), "sk-rotate-me");
+        assert!(reopened.decrypt(&old_value).is_err());
+    }
+
     #[test]
     fn truncated_ciphertext_returns_error() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,331 @@
+//! `zeroclaw skill bench`: run a skill tool repeatedly and report wall time,
+//! memory and output size.
+//!
+//! Runs go through the in-process engine when this build has it. The first
+//! run compiles the module and is reported as the cold start; the rest reuse
+//! the compiled module, like a loaded tool called on every heartbeat.
+//! Without `--features wasm-tools` the `wasmtime` CLI is used instead: it
+//! compiles on every run and its memory is not observable, so only wall
+//! times are reported.
+//!
+//! Fuel is not reported: the runtime engine does not enable fuel metering.
+
+use super::harness::{self, ExecutionEngine, ExecutionOutcome, ExitBehavior};
+use super::progress::format_size;
+use anyhow::{bail, Result};
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+const CLI_ENGINE_NOTE: &str = "resource metrics are unavailable with the wasmtime CLI engine; \
+                               build with '--features wasm-tools' to measure memory";
+
+/// What one run contributes to the report.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Sample {
+    pub duration: Duration,
+    pub output_bytes: usize,
+    pub memory_bytes: Option<u64>,
+    pub memory_limit_bytes: Option<u64>,
+    pub completed: bool,
+}
+
+impl From<&ExecutionOutcome> for Sample {
+    fn from(outcome: &ExecutionOutcome) -> Self {
+        Self {
+            duration: outcome.duration,
+            output_bytes: outcome.stdout.len(),
+            memory_bytes: outcome.memory_bytes,
+            memory_limit_bytes: outcome.memory_limit_bytes,
+            completed: outcome.status == ExitBehavior::Completed,
+        }
+    }
+}
+
+/// Wall time percentiles (nearest rank).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(super) struct Percentiles {
+    #[serde(rename = "min_ms", serialize_with = "as_millis")]
+    pub min: Duration,
+    #[serde(rename = "p50_ms", serialize_with = "as_millis")]
+    pub p50: Duration,
+    #[serde(rename = "p95_ms", serialize_with = "as_millis")]
+    pub p95: Duration,
+    #[serde(rename = "max_ms", serialize_with = "as_millis")]
+    pub max: Duration,
+}
+
+impl Percentiles {
+    fn of(durations: &[Duration]) -> Option<Self> {
+        let mut sorted = durations.to_vec();
+        sorted.sort_unstable();
+        let rank = |percent: usize| sorted[(percent * sorted.len()).div_ceil(100).max(1) - 1];
+        Some(Self {
+            min: *sorted.first()?,
+            p50: rank(50),
+            p95: rank(95),
+            max: rank(100),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub(super) struct OutputSizes {
+    pub min: usize,
+    pub mean: usize,
+    pub max: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct BenchReport {
+    pub engine: &'static str,
+    pub iterations: usize,
+    #[serde(rename = "cold_start_ms", serialize_with = "as_millis")]
+    pub cold_start: Duration,
+    /// Runs after the first; `None` with a single iteration.
+    pub warm: Option<Percentiles>,
+    pub output_bytes: OutputSizes,
+    pub peak_memory_bytes: Option<u64>,
+    pub memory_limit_bytes: Option<u64>,
+    /// Runs that did not complete (non-zero exit, trap, timeout).
+    pub failures: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<&'static str>,
+}
+
+impl BenchReport {
+    pub fn from_samples(engine: &'static str, samples: &[Sample]) -> Result<Self> {
+        let Some((cold, warm)) = samples.split_first() else {
+            bail!("a benchmark needs at least one run");
+        };
+        let warm_durations: Vec<Duration> = warm.iter().map(|sample| sample.duration).collect();
+        let output_sizes: Vec<usize> = samples.iter().map(|sample| sample.output_bytes).collect();
+        Ok(Self {
+            engine,
+            iterations: samples.len(),
+            cold_start: cold.duration,
+            warm: Percentiles::of(&warm_durations),
+            output_bytes: OutputSizes {
+                min: output_sizes.iter().copied().min().unwrap_or(0),
+                mean: output_sizes.iter().sum::<usize>() / samples.len(),
+                max: output_sizes.iter().copied().max().unwrap_or(0),
+            },
+            peak_memory_bytes: samples.iter().filter_map(|s| s.memory_bytes).max(),
+            memory_limit_bytes: samples.iter().find_map(|s| s.memory_limit_bytes),
+            failures: samples.iter().filter(|sample| !sample.completed).count(),
+            note: None,
+        })
+    }
+
+    /// The p95 `--fail-if-p95-ms` checks: warm runs, or the cold start when
+    /// there was only one run.
+    pub fn gated_p95(&self) -> Duration {
+        self.warm.map_or(self.cold_start, |warm| warm.p95)
+    }
+}
+
+/// Run `args_json` through `engine` `iterations` times.
+pub(super) fn run(
+    engine: &dyn ExecutionEngine,
+    wasm: &Path,
+    args_json: &str,
+    iterations: usize,
+) -> Result<BenchReport> {
+    if iterations == 0 {
+        bail!("--iterations must be at least 1");
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        samples.push(Sample::from(&engine.run(wasm, args_json.as_bytes())?));
+    }
+    BenchReport::from_samples(engine.name(), &samples)
+}
+
+/// `zeroclaw skill bench`.
+pub(super) fn bench_skill(
+    skill_path: &Path,
+    tool_name: Option<&str>,
+    args_json: &str,
+    iterations: usize,
+    json: bool,
+    fail_if_p95_ms: Option<u64>,
+    skills_env: &HashMap<String, HashMap<String, String>>,
+) -> Result<()> {
+    let wasm_path = super::resolve_test_input(skill_path, tool_name, args_json)?;
+    let guest_env = super::skill_guest_env(skill_path, skills_env)?;
+    let in_process = harness::InProcess::with_env(guest_env.clone());
+    let cli = harness::WasmtimeCli {
+        guest_env,
+        ..Default::default()
+    };
+    let (engine, note): (&dyn ExecutionEngine, _) = if in_process.unavailable_reason().is_none() {
+        (&in_process, None)
+    } else {
+        (&cli, Some(CLI_ENGINE_NOTE))
+    };
+
+    if !json {
+        println!(
+            "  Benchmarking {} ({iterations} runs, {} engine)",
+            console::style(wasm_path.display()).cyan(),
+            engine.name()
+        );
+        println!("  Input:   {args_json}");
+        println!();
+    }
+    let mut report = run(engine, &wasm_path, args_json, iterations)?;
+    report.note = note;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+
+    if let Some(limit_ms) = fail_if_p95_ms {
+        let p95 = report.gated_p95();
+        if p95 > Duration::from_millis(limit_ms) {
+            bail!(
+                "p95 wall time {} exceeds --fail-if-p95-ms {limit_ms}",
+                millis(p95)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn print_report(report: &BenchReport) {
+    println!("  Cold start:   {}", millis(report.cold_start));
+    match report.warm {
+        Some(warm) => println!(
+            "  Warm:         min {}  p50 {}  p95 {}  max {}  ({} runs)",
+            millis(warm.min),
+            millis(warm.p50),
+            millis(warm.p95),
+            millis(warm.max),
+            report.iterations - 1
+        ),
+        None => println!("  Warm:         (single run)"),
+    }
+    println!(
+        "  Output:       min {}  mean {}  max {}",
+        format_size(report.output_bytes.min as u64),
+        format_size(report.output_bytes.mean as u64),
+        format_size(report.output_bytes.max as u64)
+    );
+    match (report.peak_memory_bytes, report.memory_limit_bytes) {
+        (Some(peak), Some(limit)) if limit > 0 => println!(
+            "  Peak memory:  {} of {} ({}%)",
+            format_size(peak),
+            format_size(limit),
+            peak.saturating_mul(100) / limit
+        ),
+        (Some(peak), _) => println!(
+            "  Peak memory:  {} (the module declares no maximum)",
+            format_size(peak)
+        ),
+        (None, _) => println!("  Peak memory:  unavailable"),
+    }
+    println!("  Fuel:         not metered by the runtime engine");
+    if report.failures > 0 {
+        println!(
+            "  {} {} of {} runs did not complete; run 'zeroclaw skill test' to see why",
+            console::style("!").yellow().bold(),
+            report.failures,
+            report.iterations
+        );
+    }
+    if let Some(note) = report.note {
+        println!("  {} {note}", console::style("!").yellow().bold());
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn as_millis<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(ms: u64, output_bytes: usize, memory_bytes: Option<u64>) -> Sample {
+        Sample {
+            duration: Duration::from_millis(ms),
+            output_bytes,
+            memory_bytes,
+            memory_limit_bytes: memory_bytes.map(|_| 4 << 20),
+            completed: true,
+        }
+    }
+
+    #[test]
+    fn statistics_separate_the_cold_start_from_warm_runs() {
+        // Cold start first, then warm runs of 2..=21 ms in shuffled order.
+        let mut samples = vec![sample(250, 10, Some(1 << 20))];
+        for ms in [
+            7, 21, 2, 15, 9, 20, 3, 11, 18, 5, 14, 4, 19, 8, 12, 6, 17, 10, 16, 13,
+        ] {
+            samples.push(sample(ms, 30, Some(2 << 20)));
+        }
+        samples[3].completed = false;
+
+        let report = BenchReport::from_samples("in-process", &samples).unwrap();
+        assert_eq!(report.iterations, 21);
+        assert_eq!(report.cold_start, Duration::from_millis(250));
+        assert_eq!(
+            report.warm,
+            Some(Percentiles {
+                min: Duration::from_millis(2),
+                p50: Duration::from_millis(11),
+                p95: Duration::from_millis(20),
+                max: Duration::from_millis(21),
+            })
+        );
+        assert_eq!(report.gated_p95(), Duration::from_millis(20));
+        assert_eq!(
+            report.output_bytes,
+            OutputSizes {
+                min: 10,
+                mean: 29,
+                max: 30
+            }
+        );
+        assert_eq!(report.peak_memory_bytes, Some(2 << 20));
+        assert_eq!(report.memory_limit_bytes, Some(4 << 20));
+        assert_eq!(report.failures, 1);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["cold_start_ms"], 250.0);
+        assert_eq!(json["warm"]["p50_ms"], 11.0);
+        assert!(json.get("note").is_none());
+    }
+
+    #[test]
+    fn a_single_run_gates_on_the_cold_start() {
+        let report = BenchReport::from_samples("wasmtime CLI", &[sample(40, 5, None)]).unwrap();
+        assert_eq!(report.warm, None);
+        assert_eq!(report.gated_p95(), Duration::from_millis(40));
+        assert_eq!(report.peak_memory_bytes, None);
+        assert!(BenchReport::from_samples("wasmtime CLI", &[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn echo_fixture_runs_every_iteration() {
+        let fixture = harness::tests::fixture();
+        let engine = harness::tests::engine(&fixture, None);
+        let report = run(&engine, &fixture.wasm, r#"{"city":"Hanoi"}"#, 3).unwrap();
+        assert_eq!(report.engine, "wasmtime CLI");
+        assert_eq!(report.iterations, 3);
+        assert_eq!(report.failures, 0);
+        assert!(report.warm.is_some());
+        assert!(report.output_bytes.min > 0);
+        assert_eq!(report.peak_memory_bytes, None);
+
+        assert!(run(&engine, &fixture.wasm, "{}", 0).is_err());
+    }
+}
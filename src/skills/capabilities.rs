@@ -0,0 +1,191 @@
+//! `[skill.capabilities]`: what a skill says it needs.
+//!
+//! ```toml
+//! [skill.capabilities]
+//! network = true
+//! shell = true
+//! env = ["GITHUB_TOKEN"]
+//! ```
+//!
+//! The declaration makes a skill's implicit requirements explicit on top of
+//! the install audit. `skill install` prints it as a consent summary; with
+//! `[skills] require_capability_consent = true` the install stops until it is
+//! re-run with `--accept-capabilities`. The accepted list is recorded in
+//! `_install.json`. Nothing is enforced at runtime from the declaration.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Capabilities declared in `SKILL.toml`. Unknown keys are rejected so a typo
+/// can't hide a requirement.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct SkillCapabilities {
+    #[serde(default)]
+    pub network: bool,
+    #[serde(default)]
+    pub filesystem: bool,
+    #[serde(default)]
+    pub shell: bool,
+    /// Environment variables the skill reads.
+    #[serde(default)]
+    pub env: Vec<String>,
+}
+
+impl SkillCapabilities {
+    /// Read `[skill.capabilities]` from `skill_dir/SKILL.toml`. A skill without
+    /// a manifest or without the table declares nothing.
+    pub fn read(skill_dir: &Path) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Manifest {
+            skill: Meta,
+        }
+        #[derive(Deserialize)]
+        struct Meta {
+            #[serde(default)]
+            capabilities: SkillCapabilities,
+        }
+
+        let manifest_path = skill_dir.join("SKILL.toml");
+        if !manifest_path.is_file() {
+            return Ok(Self::default());
+        }
+        let raw = std::fs::read_to_string(&manifest_path)
+            .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+        let manifest: Manifest = toml::from_str(&raw).with_context(|| {
+            format!(
+                "invalid [skill.capabilities] in {}",
+                manifest_path.display()
+            )
+        })?;
+        let capabilities = manifest.skill.capabilities;
+        if let Some(bad) = capabilities.env.iter().find(|var| !is_env_var_name(var)) {
+            anyhow::bail!(
+                "invalid environment variable '{bad}' in [skill.capabilities] env of {}",
+                manifest_path.display()
+            );
+        }
+        Ok(capabilities)
+    }
+
+    /// `network`, `filesystem`, `shell`, then `env:VAR` per variable.
+    pub fn labels(&self) -> Vec<String> {
+        [
+            (self.network, "network"),
+            (self.filesystem, "filesystem"),
+            (self.shell, "shell"),
+        ]
+        .into_iter()
+        .filter(|(declared, _)| *declared)
+        .map(|(_, label)| label.to_string())
+        .chain(self.env.iter().map(|var| format!("env:{var}")))
+        .collect()
+    }
+}
+
+fn is_env_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Print what `name` requests and decide whether the install may continue.
+/// Returns the capability labels to record as accepted.
+pub(super) fn confirm(
+    name: &str,
+    capabilities: &SkillCapabilities,
+    require_consent: bool,
+) -> Result<Vec<String>> {
+    let labels = capabilities.labels();
+    if labels.is_empty() {
+        return Ok(labels);
+    }
+    say!(
+        "  Skill '{name}' requests: {}",
+        console::style(labels.join(", ")).yellow().bold()
+    );
+    if require_consent {
+        anyhow::bail!(super::SkillInstallError::ConsentRequired(format!(
+            "skill '{name}' requests capabilities ({}); review them and re-run with \
+             --accept-capabilities to install it ([skills] require_capability_consent = true)",
+            labels.join(", ")
+        )));
+    }
+    Ok(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_manifest(dir: &Path, capabilities: &str) {
+        std::fs::write(
+            dir.join("SKILL.toml"),
+            format!(
+                "[skill]\nname = \"caps\"\ndescription = \"Needs things\"\n\n\
+                 [skill.capabilities]\n{capabilities}"
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn capabilities_parse_into_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        write_manifest(
+            dir.path(),
+            "shell = true\nnetwork = true\nenv = [\"GITHUB_TOKEN\", \"_PRIVATE\"]\n",
+        );
+        let capabilities = SkillCapabilities::read(dir.path()).unwrap();
+        assert_eq!(
+            capabilities.labels(),
+            ["network", "shell", "env:GITHUB_TOKEN", "env:_PRIVATE"]
+        );
+
+        // No manifest, or no table, declares nothing.
+        let empty = tempfile::tempdir().unwrap();
+        assert_eq!(
+            SkillCapabilities::read(empty.path()).unwrap(),
+            SkillCapabilities::default()
+        );
+        std::fs::write(
+            empty.path().join("SKILL.toml"),
+            "[skill]\nname = \"plain\"\ndescription = \"x\"\n",
+        )
+        .unwrap();
+        assert!(SkillCapabilities::read(empty.path())
+            .unwrap()
+            .labels()
+            .is_empty());
+    }
+
+    #[test]
+    fn unknown_capabilities_and_bad_env_names_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        write_manifest(dir.path(), "netwrk = true\n");
+        let err = SkillCapabilities::read(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("netwrk"), "{err:#}");
+
+        write_manifest(dir.path(), "env = [\"PATH=/tmp\"]\n");
+        let err = SkillCapabilities::read(dir.path()).unwrap_err();
+        assert!(err.to_string().contains("PATH=/tmp"), "{err:#}");
+    }
+
+    #[test]
+    fn strict_mode_requires_consent_only_when_something_is_requested() {
+        let requested = SkillCapabilities {
+            network: true,
+            ..SkillCapabilities::default()
+        };
+        assert_eq!(confirm("caps", &requested, false).unwrap(), ["network"]);
+        let err = confirm("caps", &requested, true).unwrap_err();
+        assert!(err.to_string().contains("--accept-capabilities"), "{err}");
+
+        assert!(confirm("plain", &SkillCapabilities::default(), true)
+            .unwrap()
+            .is_empty());
+    }
+}
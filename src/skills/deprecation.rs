@@ -0,0 +1,219 @@
+//! Skill deprecation notices.
+//!
+//! A skill is deprecated by `deprecated = true` in the `[skill]` table of
+//! `SKILL.toml`, optionally with `deprecation_message` and `superseded_by`.
+//! Registry package indexes carry the same three fields for the version they
+//! describe; registry installs copy them into the generated `SKILL.toml`.
+//! `skill list` tags deprecated skills, the system prompt follows
+//! `[skills] include_deprecated`, and installing one needs confirmation.
+
+use super::SkillInstallError;
+use crate::config::SkillsIncludeDeprecated;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Why a skill is deprecated and what replaces it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillDeprecation {
+    #[serde(default)]
+    pub message: Option<String>,
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+}
+
+impl SkillDeprecation {
+    /// `Some` when `deprecated` is set. The message and replacement are only
+    /// read for a deprecated skill; blank values are dropped.
+    pub fn from_fields(
+        deprecated: bool,
+        message: Option<&str>,
+        superseded_by: Option<&str>,
+    ) -> Option<Self> {
+        let clean = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        deprecated.then(|| Self {
+            message: clean(message),
+            superseded_by: clean(superseded_by),
+        })
+    }
+
+    /// One line for `skill list` and prompts: the message, then the
+    /// replacement when one is named.
+    pub fn summary(&self) -> String {
+        match (&self.message, &self.superseded_by) {
+            (Some(message), Some(next)) => format!("{message} (use `{next}` instead)"),
+            (Some(message), None) => message.clone(),
+            (None, Some(next)) => format!("use `{next}` instead"),
+            (None, None) => "no reason given".to_string(),
+        }
+    }
+}
+
+/// Apply `[skills] include_deprecated` to the skills going into a prompt:
+/// `exclude` drops deprecated skills, `note` keeps them so the prompt shows
+/// their deprecation, and `full` keeps them without it.
+pub fn prompt_skills(
+    skills: Vec<super::Skill>,
+    mode: SkillsIncludeDeprecated,
+) -> Vec<super::Skill> {
+    match mode {
+        SkillsIncludeDeprecated::Exclude => skills
+            .into_iter()
+            .filter(|skill| skill.deprecation.is_none())
+            .collect(),
+        SkillsIncludeDeprecated::Note => skills,
+        SkillsIncludeDeprecated::Full => skills
+            .into_iter()
+            .map(|mut skill| {
+                skill.deprecation = None;
+                skill
+            })
+            .collect(),
+    }
+}
+
+/// What an install does when it fetched a deprecated skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum InstallConfirmation {
+    /// Fail with [`SkillInstallError::ConsentRequired`] (the default, and
+    /// what the `skill_install` tool gets).
+    Refuse,
+    /// Ask on the terminal.
+    Ask,
+    /// Continue after printing the notice (`--accept-deprecated`).
+    Accept,
+}
+
+static CONFIRMATION: AtomicU8 = AtomicU8::new(0);
+
+/// How installs for the rest of the process confirm a deprecated skill.
+pub(super) fn set_install_confirmation(confirmation: InstallConfirmation) {
+    let raw = match confirmation {
+        InstallConfirmation::Refuse => 0,
+        InstallConfirmation::Ask => 1,
+        InstallConfirmation::Accept => 2,
+    };
+    CONFIRMATION.store(raw, Ordering::Relaxed);
+}
+
+pub(super) fn install_confirmation() -> InstallConfirmation {
+    match CONFIRMATION.load(Ordering::Relaxed) {
+        1 => InstallConfirmation::Ask,
+        2 => InstallConfirmation::Accept,
+        _ => InstallConfirmation::Refuse,
+    }
+}
+
+/// Print the deprecation of `name` and decide whether its install may
+/// continue. `ask` is only called for [`InstallConfirmation::Ask`].
+pub(super) fn confirm_install(
+    name: &str,
+    deprecation: &SkillDeprecation,
+    confirmation: InstallConfirmation,
+    ask: impl FnOnce(&str) -> Result<bool>,
+) -> Result<()> {
+    say!(
+        "  Skill '{name}' is {}: {}",
+        console::style("deprecated").yellow().bold(),
+        deprecation.message.as_deref().unwrap_or("no reason given")
+    );
+    if let Some(next) = &deprecation.superseded_by {
+        say!("  Suggested replacement: {}", console::style(next).cyan());
+    }
+    let replacement = deprecation
+        .superseded_by
+        .as_deref()
+        .map(|next| format!(" (suggested replacement: {next})"))
+        .unwrap_or_default();
+    match confirmation {
+        InstallConfirmation::Accept => Ok(()),
+        InstallConfirmation::Ask => {
+            if ask(&format!("  Install deprecated skill '{name}' anyway?"))? {
+                Ok(())
+            } else {
+                anyhow::bail!(SkillInstallError::ConsentRequired(format!(
+                    "install of deprecated skill '{name}' declined{replacement}"
+                )))
+            }
+        }
+        InstallConfirmation::Refuse => anyhow::bail!(SkillInstallError::ConsentRequired(format!(
+            "skill '{name}' is deprecated{replacement}; re-run with --accept-deprecated \
+             to install it anyway"
+        ))),
+    }
+}
+
+/// Yes/no on the terminal, defaulting to no.
+pub(super) fn ask_on_terminal(prompt: &str) -> Result<bool> {
+    Ok(dialoguer::Confirm::new()
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deprecated() -> SkillDeprecation {
+        SkillDeprecation::from_fields(true, Some("Unmaintained"), Some("acme/weather-v2")).unwrap()
+    }
+
+    #[test]
+    fn fields_only_count_when_deprecated() {
+        assert_eq!(
+            SkillDeprecation::from_fields(false, Some("old"), Some("new")),
+            None
+        );
+        let bare = SkillDeprecation::from_fields(true, Some("  "), None).unwrap();
+        assert_eq!(bare, SkillDeprecation::default());
+        assert_eq!(bare.summary(), "no reason given");
+        assert_eq!(
+            deprecated().summary(),
+            "Unmaintained (use `acme/weather-v2` instead)"
+        );
+    }
+
+    #[test]
+    fn install_gate_refuses_asks_or_accepts() {
+        let never_asked = |_: &str| -> Result<bool> { panic!("should not ask") };
+
+        let err = confirm_install(
+            "weather",
+            &deprecated(),
+            InstallConfirmation::Refuse,
+            never_asked,
+        )
+        .unwrap_err();
+        assert_eq!(SkillInstallError::code_of(&err), "consent_required");
+        let message = err.to_string();
+        assert!(message.contains("acme/weather-v2"), "{message}");
+        assert!(message.contains("--accept-deprecated"), "{message}");
+
+        confirm_install(
+            "weather",
+            &deprecated(),
+            InstallConfirmation::Accept,
+            never_asked,
+        )
+        .unwrap();
+
+        let mut prompt = String::new();
+        let err = confirm_install("weather", &deprecated(), InstallConfirmation::Ask, |text| {
+            prompt = text.to_string();
+            Ok(false)
+        })
+        .unwrap_err();
+        assert!(prompt.contains("deprecated skill 'weather'"));
+        assert!(err.to_string().contains("declined"));
+        confirm_install("weather", &deprecated(), InstallConfirmation::Ask, |_| {
+            Ok(true)
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,605 @@
+//! `zeroclaw skill dry-run`: show what a skill tool would do without doing it.
+//!
+//! A call is prepared as for a real run: `{{arg}}` placeholders in the tool's
+//! `command` are filled from the `--args` object, `{{env.NAME}}` from the
+//! skill's `[skills.env.<skill>]` values, and the result is checked against
+//! the autonomy policy and, for HTTP tools, the `[http_request]` domain
+//! allowlist. The prepared call is then printed instead of carried out: shell
+//! commands are never spawned and HTTP requests never sent. WASM tools do
+//! run, in the sandbox the runtime always gives them (no preopened
+//! directories, no network), so their output can be inspected. Secret values
+//! are masked in everything printed.
+
+use super::env::SkillEnv;
+use super::harness::{self, ExecutionEngine};
+use super::SkillTool;
+use crate::approval::ApprovalManager;
+use crate::config::Config;
+use crate::security::{AutonomyLevel, SecurityPolicy};
+use crate::tools::url_validation::{
+    normalize_allowed_domains, validate_url, DomainPolicy, UrlSchemePolicy,
+};
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Printed in place of secret values.
+const MASK: &str = "***";
+
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS"];
+
+/// How a placeholder value is written into the command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quoting {
+    /// Single-quoted, so a value is always one shell word.
+    Shell,
+    /// Percent-encoded.
+    Url,
+}
+
+/// A command with its placeholders filled, once with the real values and
+/// once with secrets masked for display.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct Filled {
+    pub value: String,
+    display: String,
+    /// Arguments consumed by placeholders.
+    used: Vec<String>,
+}
+
+fn quote(value: &str, quoting: Quoting) -> String {
+    match quoting {
+        Quoting::Shell => format!("'{}'", value.replace('\'', r"'\''")),
+        Quoting::Url => urlencoding::encode(value).into_owned(),
+    }
+}
+
+fn arg_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Fill the placeholders of `template`. A placeholder without a value, or
+/// naming a variable the skill does not declare, is an error.
+fn fill(
+    template: &str,
+    args: &Map<String, Value>,
+    env: &[(String, String)],
+    quoting: Quoting,
+) -> Result<Filled> {
+    let mut filled = Filled::default();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        let key = rest[start + 2..start + 2 + len].trim();
+        filled.value.push_str(&rest[..start]);
+        filled.display.push_str(&rest[..start]);
+        if let Some(name) = key.strip_prefix("env.") {
+            let Some((_, value)) = env.iter().find(|(var, _)| var == name) else {
+                bail!(
+                    "placeholder {{{{env.{name}}}}} names a variable missing from the skill's required_env"
+                );
+            };
+            filled.value.push_str(&quote(value, quoting));
+            filled.display.push_str(MASK);
+        } else {
+            let Some(value) = args.get(key) else {
+                bail!("missing argument '{key}' for placeholder {{{{{key}}}}}");
+            };
+            let quoted = quote(&arg_text(value), quoting);
+            filled.value.push_str(&quoted);
+            filled.display.push_str(&quoted);
+            filled.used.push(key.to_string());
+        }
+        rest = &rest[start + 2 + len + 2..];
+    }
+    filled.value.push_str(rest);
+    filled.display.push_str(rest);
+    Ok(filled)
+}
+
+/// What the autonomy policy would require for a real run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Approval {
+    NotNeeded,
+    Required(String),
+    Blocked(String),
+}
+
+impl Approval {
+    fn describe(&self) -> String {
+        match self {
+            Self::NotNeeded => "not needed (runs without asking)".into(),
+            Self::Required(reason) => format!("required: {reason}"),
+            Self::Blocked(reason) => format!("blocked: {reason}"),
+        }
+    }
+
+    /// Approval for a call the policy already allows.
+    fn for_tool(approvals: &ApprovalManager, tool: &str) -> Self {
+        if approvals.needs_approval(tool) {
+            Self::Required(format!(
+                "supervised autonomy asks before each `{tool}` call (not in auto_approve)"
+            ))
+        } else {
+            Self::NotNeeded
+        }
+    }
+}
+
+/// A shell tool call ready to spawn: `sh -c <command>` in the skill
+/// directory, with the skill's declared variables and `PATH` only.
+#[derive(Debug)]
+pub(super) struct PreparedShell {
+    pub command: Filled,
+    pub cwd: PathBuf,
+    pub env: Vec<(String, String)>,
+}
+
+/// An HTTP tool call ready to send. The command is `[METHOD ]<url>`; for
+/// methods with a body, the arguments no placeholder used are sent as a JSON
+/// object.
+#[derive(Debug)]
+pub(super) struct PreparedHttp {
+    pub method: String,
+    pub url: Filled,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+    /// Why the domain allowlist would refuse the URL.
+    denied: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) enum Prepared {
+    Shell(PreparedShell),
+    Http(PreparedHttp),
+    Wasm(PathBuf),
+}
+
+/// Everything a real run would need besides the call itself.
+pub(super) struct RunContext<'a> {
+    pub skill_dir: &'a Path,
+    pub env: &'a [(String, String)],
+    pub policy: &'a SecurityPolicy,
+    pub approvals: &'a ApprovalManager,
+    pub http: &'a crate::config::HttpRequestConfig,
+    pub url_access: &'a crate::config::UrlAccessConfig,
+}
+
+/// What a [`RunContext`] borrows besides the config, loaded for one skill.
+pub(super) struct Host {
+    env: Vec<(String, String)>,
+    policy: SecurityPolicy,
+    approvals: ApprovalManager,
+    http: crate::config::HttpRequestConfig,
+}
+
+impl Host {
+    pub(super) fn load(skill_dir: &Path, config: &Config) -> Result<Self> {
+        let mut http = config.http_request.clone();
+        http.allowed_domains = normalize_allowed_domains(http.allowed_domains);
+        Ok(Self {
+            env: SkillEnv::for_dir(skill_dir, &config.skills.env)?
+                .vars()?
+                .to_vec(),
+            policy: SecurityPolicy::from_config(&config.autonomy, &config.workspace_dir),
+            approvals: ApprovalManager::from_config(&config.autonomy),
+            http,
+        })
+    }
+
+    pub(super) fn cx<'a>(&'a self, skill_dir: &'a Path, config: &'a Config) -> RunContext<'a> {
+        RunContext {
+            skill_dir,
+            env: &self.env,
+            policy: &self.policy,
+            approvals: &self.approvals,
+            http: &self.http,
+            url_access: &config.security.url_access,
+        }
+    }
+}
+
+/// The `[[tools]]` entry named `tool` in the skill's SKILL.toml, if it has
+/// one; WASM-only skills have none.
+pub(super) fn declared_tool(skill_dir: &Path, tool: &str) -> Result<Option<SkillTool>> {
+    let manifest = skill_dir.join("SKILL.toml");
+    if !manifest.is_file() {
+        return Ok(None);
+    }
+    Ok(super::load_skill_toml(&manifest)
+        .with_context(|| format!("failed to read {}", manifest.display()))?
+        .tools
+        .into_iter()
+        .find(|declared| declared.name == tool))
+}
+
+/// Prepare `tool` (a `[[tools]]` entry) with `args`.
+pub(super) fn prepare(
+    tool: &SkillTool,
+    args: &Map<String, Value>,
+    cx: &RunContext,
+) -> Result<Prepared> {
+    match tool.kind.as_str() {
+        "shell" | "script" => Ok(Prepared::Shell(PreparedShell {
+            command: fill(&tool.command, args, cx.env, Quoting::Shell)?,
+            cwd: cx.skill_dir.to_path_buf(),
+            env: cx.env.to_vec(),
+        })),
+        "http" => {
+            let (method, url) = match tool.command.trim().split_once(' ') {
+                Some((method, url)) if HTTP_METHODS.contains(&method) => (method, url.trim()),
+                _ => ("GET", tool.command.trim()),
+            };
+            let url = fill(url, args, cx.env, Quoting::Url)?;
+            let unused: Map<String, Value> = args
+                .iter()
+                .filter(|(name, _)| !url.used.contains(name))
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect();
+            let body = matches!(method, "POST" | "PUT" | "PATCH")
+                .then(|| Value::Object(unused).to_string());
+            let mut headers = vec![("User-Agent".to_string(), cx.http.user_agent.clone())];
+            if body.is_some() {
+                headers.push(("Content-Type".to_string(), "application/json".to_string()));
+            }
+            let denied = validate_url(&url.value, &http_policy(cx))
+                .err()
+                .map(|err| format!("{err:#}"));
+            Ok(Prepared::Http(PreparedHttp {
+                method: method.to_string(),
+                url,
+                headers,
+                body,
+                denied,
+            }))
+        }
+        other => bail!("tool '{}' has unsupported kind '{other}'", tool.name),
+    }
+}
+
+impl Prepared {
+    /// What the policy would require for a real run of `tool`.
+    pub(super) fn approval(&self, tool: &str, cx: &RunContext) -> Approval {
+        if cx.policy.autonomy == AutonomyLevel::ReadOnly {
+            return Approval::Blocked("autonomy level is read_only".into());
+        }
+        match self {
+            // Checked with secrets masked: the policy only looks at the
+            // command's shape, and its messages quote the command.
+            Self::Shell(shell) => {
+                let command = &shell.command.display;
+                match cx.policy.validate_command_execution(command, false) {
+                    Ok(_) => Approval::for_tool(cx.approvals, tool),
+                    Err(reason) => match cx.policy.validate_command_execution(command, true) {
+                        Ok(_) => Approval::Required(reason),
+                        Err(reason) => Approval::Blocked(reason),
+                    },
+                }
+            }
+            Self::Http(http) => match &http.denied {
+                None => Approval::for_tool(cx.approvals, tool),
+                Some(reason) => Approval::Blocked(reason.clone()),
+            },
+            Self::Wasm(_) => Approval::for_tool(cx.approvals, tool),
+        }
+    }
+
+    /// The prepared call as printed by the dry run.
+    fn render(&self, tool: &str, approval: &Approval, cx: &RunContext) -> String {
+        let mut out = String::new();
+        match self {
+            Self::Shell(shell) => {
+                let _ = writeln!(out, "  Mode:      shell, not executed");
+                let _ = writeln!(out, "  Command:   sh -c {}", shell.command.display);
+                let _ = writeln!(out, "  Directory: {}", shell.cwd.display());
+                let env = if shell.env.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    shell
+                        .env
+                        .iter()
+                        .map(|(name, _)| format!("{name}={MASK}"))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                let _ = writeln!(out, "  Env:       {env} (plus PATH; the rest of the host environment is not passed)");
+                let risk = format!("{:?}", cx.policy.command_risk_level(&shell.command.display));
+                let _ = writeln!(out, "  Risk:      {}", risk.to_lowercase());
+            }
+            Self::Http(http) => {
+                let _ = writeln!(out, "  Mode:      http, not sent");
+                let _ = writeln!(out, "  Request:   {} {}", http.method, http.url.display);
+                for (name, value) in &http.headers {
+                    let _ = writeln!(out, "  Header:    {name}: {value}");
+                }
+                if let Some(body) = &http.body {
+                    let _ = writeln!(out, "  Body:      {body}");
+                }
+                let verdict = match &http.denied {
+                    None => "allowed".to_string(),
+                    Some(reason) => format!("denied ({reason})"),
+                };
+                let _ = writeln!(out, "  Allowlist: {verdict}");
+            }
+            Self::Wasm(wasm) => {
+                let _ = writeln!(
+                    out,
+                    "  Mode:      wasm, executed read-only (no preopened directories, network denied)"
+                );
+                let _ = writeln!(out, "  Module:    {}", wasm.display());
+            }
+        }
+        let _ = writeln!(out, "  Approval:  {} (tool `{tool}`)", approval.describe());
+        out
+    }
+}
+
+fn http_policy<'a>(cx: &'a RunContext) -> DomainPolicy<'a> {
+    DomainPolicy {
+        allowed_domains: &cx.http.allowed_domains,
+        blocked_domains: &[],
+        allowed_field_name: "http_request.allowed_domains",
+        blocked_field_name: None,
+        empty_allowed_message: "no [http_request].allowed_domains are configured",
+        scheme_policy: UrlSchemePolicy::HttpOrHttps,
+        ipv6_error_context: "skill dry-run",
+        url_access: Some(cx.url_access),
+    }
+}
+
+/// `zeroclaw skill dry-run`: prepare `tool` of the skill in `skill_dir` with
+/// `args_json` and print the call. Only WASM tools run.
+pub(super) fn dry_run(
+    skill_dir: &Path,
+    tool: &str,
+    args_json: &str,
+    config: &Config,
+) -> Result<()> {
+    let args: Map<String, Value> = serde_json::from_str(args_json)
+        .with_context(|| format!("--args is not a JSON object: {args_json}"))?;
+    let host = Host::load(skill_dir, config)?;
+    let cx = host.cx(skill_dir, config);
+
+    let prepared = match declared_tool(skill_dir, tool)? {
+        Some(declared) => prepare(&declared, &args, &cx)?,
+        None => Prepared::Wasm(super::resolve_wasm_path(skill_dir, Some(tool))?),
+    };
+    let approval = prepared.approval(tool, &cx);
+
+    println!(
+        "  Dry run of {} with {args_json}",
+        console::style(tool).cyan()
+    );
+    print!("{}", prepared.render(tool, &approval, &cx));
+    if let Prepared::Wasm(wasm) = &prepared {
+        println!();
+        let in_process = harness::InProcess::with_env(host.env.clone());
+        let cli = harness::WasmtimeCli {
+            guest_env: host.env.clone(),
+            ..Default::default()
+        };
+        // Neither engine is given a directory or network access.
+        let engine: &dyn ExecutionEngine = if in_process.unavailable_reason().is_none() {
+            &in_process
+        } else {
+            &cli
+        };
+        let outcome = engine.run(wasm, args_json.as_bytes())?;
+        harness::report_single(&outcome, false)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AutonomyConfig, HttpRequestConfig, UrlAccessConfig};
+    use serde_json::json;
+
+    fn tool(kind: &str, command: &str) -> SkillTool {
+        SkillTool {
+            name: "probe".into(),
+            description: "test".into(),
+            kind: kind.into(),
+            command: command.into(),
+            args: Default::default(),
+            timeout_secs: super::super::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+            max_output_bytes: super::super::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
+        }
+    }
+
+    fn args(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    struct Setup {
+        policy: SecurityPolicy,
+        approvals: ApprovalManager,
+        http: HttpRequestConfig,
+        url_access: UrlAccessConfig,
+        env: Vec<(String, String)>,
+    }
+
+    impl Setup {
+        fn new(autonomy: &AutonomyConfig) -> Self {
+            let url_access = UrlAccessConfig {
+                block_private_ip: false,
+                ..UrlAccessConfig::default()
+            };
+            Self {
+                policy: SecurityPolicy::from_config(autonomy, Path::new("/tmp")),
+                approvals: ApprovalManager::from_config(autonomy),
+                http: HttpRequestConfig {
+                    allowed_domains: vec!["api.example.com".into()],
+                    ..HttpRequestConfig::default()
+                },
+                url_access,
+                env: vec![("WEATHER_API_KEY".into(), "s3cret-key".into())],
+            }
+        }
+
+        fn cx<'a>(&'a self, skill_dir: &'a Path) -> RunContext<'a> {
+            RunContext {
+                skill_dir,
+                env: &self.env,
+                policy: &self.policy,
+                approvals: &self.approvals,
+                http: &self.http,
+                url_access: &self.url_access,
+            }
+        }
+    }
+
+    #[test]
+    fn placeholders_are_quoted_and_secrets_masked() {
+        let env = [("TOKEN".to_string(), "s3cret".to_string())];
+        let filled = fill(
+            "echo {{city}} {{ days }} --token {{env.TOKEN}}",
+            &args(json!({"city": "Ha Noi's", "days": 3})),
+            &env,
+            Quoting::Shell,
+        )
+        .unwrap();
+        assert_eq!(filled.value, r"echo 'Ha Noi'\''s' '3' --token 's3cret'");
+        assert_eq!(filled.display, r"echo 'Ha Noi'\''s' '3' --token ***");
+        assert_eq!(filled.used, ["city", "days"]);
+
+        let url = fill(
+            "https://api.example.com/q?city={{city}}",
+            &args(json!({"city": "Hà Nội & co"})),
+            &[],
+            Quoting::Url,
+        )
+        .unwrap();
+        assert_eq!(
+            url.value,
+            "https://api.example.com/q?city=H%C3%A0%20N%E1%BB%99i%20%26%20co"
+        );
+
+        let err = fill("echo {{city}}", &Map::new(), &[], Quoting::Shell).unwrap_err();
+        assert!(err.to_string().contains("missing argument 'city'"), "{err}");
+        let err = fill("echo {{env.HOME}}", &Map::new(), &env, Quoting::Shell).unwrap_err();
+        assert!(err.to_string().contains("required_env"), "{err}");
+    }
+
+    /// The shell command of the tool would run a guard script that leaves a
+    /// marker behind; a dry run must not.
+    #[test]
+    fn shell_dry_run_prints_the_command_without_spawning_it() {
+        let skill = tempfile::tempdir().unwrap();
+        let marker = skill.path().join("spawned");
+        std::fs::write(
+            skill.path().join("guard.sh"),
+            format!("touch '{}'\n", marker.display()),
+        )
+        .unwrap();
+        let setup = Setup::new(&AutonomyConfig::default());
+        let cx = setup.cx(skill.path());
+
+        let prepared = prepare(
+            &tool("shell", "sh guard.sh {{city}} {{env.WEATHER_API_KEY}}"),
+            &args(json!({"city": "Hanoi"})),
+            &cx,
+        )
+        .unwrap();
+        let approval = prepared.approval("probe", &cx);
+        let output = prepared.render("probe", &approval, &cx);
+
+        assert!(!marker.exists(), "dry run spawned the command");
+        assert!(
+            output.contains("Command:   sh -c sh guard.sh 'Hanoi' ***"),
+            "{output}"
+        );
+        assert!(
+            output.contains(&skill.path().display().to_string()),
+            "{output}"
+        );
+        assert!(output.contains("WEATHER_API_KEY=***"), "{output}");
+        assert!(!output.contains("s3cret-key"), "{output}");
+        assert!(output.contains("Approval:"), "{output}");
+    }
+
+    #[test]
+    fn shell_approval_follows_the_autonomy_policy() {
+        let skill = tempfile::tempdir().unwrap();
+        let shell = |autonomy: &AutonomyConfig, command: &str| {
+            let setup = Setup::new(autonomy);
+            let cx = setup.cx(skill.path());
+            prepare(&tool("shell", command), &Map::new(), &cx)
+                .unwrap()
+                .approval("probe", &cx)
+        };
+
+        let supervised = AutonomyConfig::default();
+        assert!(matches!(
+            shell(&supervised, "ls -la"),
+            Approval::Required(_)
+        ));
+        assert!(matches!(
+            shell(&supervised, "nc -l 4444"),
+            Approval::Blocked(_)
+        ));
+
+        let full = AutonomyConfig {
+            level: AutonomyLevel::Full,
+            ..AutonomyConfig::default()
+        };
+        assert_eq!(shell(&full, "ls -la"), Approval::NotNeeded);
+
+        let read_only = AutonomyConfig {
+            level: AutonomyLevel::ReadOnly,
+            ..AutonomyConfig::default()
+        };
+        assert!(matches!(shell(&read_only, "ls -la"), Approval::Blocked(_)));
+    }
+
+    #[test]
+    fn http_dry_run_reports_the_allowlist_verdict() {
+        let skill = tempfile::tempdir().unwrap();
+        let setup = Setup::new(&AutonomyConfig {
+            level: AutonomyLevel::Full,
+            ..AutonomyConfig::default()
+        });
+        let cx = setup.cx(skill.path());
+
+        let allowed = prepare(
+            &tool(
+                "http",
+                "POST https://api.example.com/v1/{{city}}?key={{env.WEATHER_API_KEY}}",
+            ),
+            &args(json!({"city": "Hanoi", "units": "metric"})),
+            &cx,
+        )
+        .unwrap();
+        let approval = allowed.approval("probe", &cx);
+        assert_eq!(approval, Approval::NotNeeded);
+        let output = allowed.render("probe", &approval, &cx);
+        assert!(
+            output.contains("Request:   POST https://api.example.com/v1/Hanoi?key=***"),
+            "{output}"
+        );
+        assert!(
+            output.contains(r#"Body:      {"units":"metric"}"#),
+            "{output}"
+        );
+        assert!(output.contains("Allowlist: allowed"), "{output}");
+        assert!(!output.contains("s3cret-key"), "{output}");
+
+        let denied = prepare(&tool("http", "https://evil.example.org/"), &Map::new(), &cx).unwrap();
+        let approval = denied.approval("probe", &cx);
+        assert!(matches!(approval, Approval::Blocked(_)), "{approval:?}");
+        let output = denied.render("probe", &approval, &cx);
+        assert!(
+            output.contains("Request:   GET https://evil.example.org/"),
+            "{output}"
+        );
+        assert!(output.contains("Allowlist: denied"), "{output}");
+        assert!(!output.contains("Body:"), "{output}");
+    }
+}
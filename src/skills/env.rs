@@ -0,0 +1,231 @@
+//! Per-skill environment variables.
+//!
+//! A skill lists the variables its tools need in `required_env` in the
+//! `[skill]` table of `SKILL.toml`; their values come from the
+//! `[skills.env.<skill>]` table of the config. A tool runs with its own
+//! skill's declared variables only: other skills' tables, undeclared entries
+//! and the host environment are never passed through, and a run fails up
+//! front when a declared variable has no value. Values are kept out of
+//! prompts, logs and `Debug` output.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+/// Host credentials and agent sockets a skill has no business asking for.
+/// `skill audit` flags them in `required_env`.
+const SENSITIVE_ENV: &[&str] = &[
+    "AWS_ACCESS_KEY_ID",
+    "AWS_SECRET_ACCESS_KEY",
+    "AWS_SESSION_TOKEN",
+    "AZURE_CLIENT_SECRET",
+    "GOOGLE_APPLICATION_CREDENTIALS",
+    "GITHUB_TOKEN",
+    "GH_TOKEN",
+    "NPM_TOKEN",
+    "SSH_AUTH_SOCK",
+    "GPG_AGENT_INFO",
+    "KUBECONFIG",
+    "DOCKER_HOST",
+    "LD_PRELOAD",
+    "DYLD_INSERT_LIBRARIES",
+];
+
+/// Whether `name` hands a skill host credentials or an agent socket.
+pub fn is_sensitive(name: &str) -> bool {
+    SENSITIVE_ENV
+        .iter()
+        .any(|sensitive| sensitive.eq_ignore_ascii_case(name))
+}
+
+/// The environment one skill's tools run with.
+#[derive(Clone, Default)]
+pub struct SkillEnv {
+    skill: String,
+    vars: Vec<(String, String)>,
+    missing: Vec<String>,
+    undeclared: Vec<String>,
+}
+
+impl SkillEnv {
+    /// `required` variables of `skill`, valued from its table in
+    /// `configured` (`[skills.env]`). Empty values count as missing.
+    pub fn new(
+        skill: &str,
+        required: &[String],
+        configured: &HashMap<String, HashMap<String, String>>,
+    ) -> Self {
+        let table = configured.get(skill);
+        let mut vars = Vec::new();
+        let mut missing = Vec::new();
+        for name in required {
+            match table
+                .and_then(|table| table.get(name))
+                .filter(|value| !value.is_empty())
+            {
+                Some(value) => vars.push((name.clone(), value.clone())),
+                None => missing.push(name.clone()),
+            }
+        }
+        let mut undeclared: Vec<String> = table
+            .into_iter()
+            .flat_map(HashMap::keys)
+            .filter(|name| !required.contains(name))
+            .cloned()
+            .collect();
+        undeclared.sort();
+        Self {
+            skill: skill.to_string(),
+            vars,
+            missing,
+            undeclared,
+        }
+    }
+
+    /// [`Self::new`] for the skill in `dir`, named and declared by its
+    /// `SKILL.toml`. A directory without one (a WASM tool in dev layout)
+    /// needs nothing.
+    pub fn for_dir(
+        dir: &Path,
+        configured: &HashMap<String, HashMap<String, String>>,
+    ) -> Result<Self> {
+        let manifest = dir.join("SKILL.toml");
+        if !manifest.is_file() {
+            return Ok(Self::default());
+        }
+        let skill = super::load_skill_toml(&manifest)
+            .with_context(|| format!("failed to read {}", manifest.display()))?;
+        Ok(Self::new(&skill.name, &skill.required_env, configured))
+    }
+
+    /// Declared variables without a value.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Entries of `[skills.env.<skill>]` the skill does not declare; they
+    /// are never passed to its tools.
+    pub fn undeclared(&self) -> &[String] {
+        &self.undeclared
+    }
+
+    /// The variables to run the skill's tools with. Fails listing every
+    /// declared variable without a value.
+    pub fn vars(&self) -> Result<&[(String, String)]> {
+        if !self.missing.is_empty() {
+            anyhow::bail!(
+                "skill '{}' is missing required environment variables: {} \
+                 (set them under [skills.env.{}])",
+                self.skill,
+                self.missing.join(", "),
+                self.skill
+            );
+        }
+        Ok(&self.vars)
+    }
+}
+
+/// Names only: values are secrets.
+impl fmt::Debug for SkillEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SkillEnv")
+            .field("skill", &self.skill)
+            .field(
+                "vars",
+                &self.vars.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+            )
+            .field("missing", &self.missing)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configured() -> HashMap<String, HashMap<String, String>> {
+        HashMap::from([
+            (
+                "weather".to_string(),
+                HashMap::from([
+                    ("WEATHER_API_KEY".to_string(), "weather-key".to_string()),
+                    ("EXTRA".to_string(), "not declared".to_string()),
+                ]),
+            ),
+            (
+                "news".to_string(),
+                HashMap::from([("NEWS_API_KEY".to_string(), "news-key".to_string())]),
+            ),
+        ])
+    }
+
+    fn write_skill(root: &Path, name: &str, required: &str) -> std::path::PathBuf {
+        let dir = root.join(name);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.toml"),
+            format!(
+                "[skill]\nname = \"{name}\"\ndescription = \"test\"\nrequired_env = {required}\n"
+            ),
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn missing_variables_fail_before_running() {
+        let env = SkillEnv::new(
+            "weather",
+            &[
+                "WEATHER_API_KEY".into(),
+                "WEATHER_REGION".into(),
+                "UNITS".into(),
+            ],
+            &configured(),
+        );
+        assert_eq!(env.missing(), ["WEATHER_REGION", "UNITS"]);
+        assert_eq!(env.undeclared(), ["EXTRA"]);
+        let message = env.vars().unwrap_err().to_string();
+        assert!(message.contains("WEATHER_REGION, UNITS"), "{message}");
+        assert!(message.contains("[skills.env.weather]"), "{message}");
+        assert!(!message.contains("weather-key"), "{message}");
+        assert!(!format!("{env:?}").contains("weather-key"));
+    }
+
+    #[test]
+    fn sensitive_names_are_recognised() {
+        assert!(is_sensitive("AWS_SECRET_ACCESS_KEY"));
+        assert!(is_sensitive("ssh_auth_sock"));
+        assert!(!is_sensitive("WEATHER_API_KEY"));
+    }
+
+    /// Each skill's tool sees its own declared variables and nothing else.
+    #[cfg(unix)]
+    #[test]
+    fn tools_only_see_their_own_skills_env() {
+        use super::super::harness::{tests::fixture, ExecutionEngine};
+
+        let fixture = fixture();
+        let root = tempfile::tempdir().unwrap();
+        let tool = r#"cat >/dev/null
+printf '{"success":true,"output":"%s|%s|%s"}' "$WEATHER_API_KEY" "$NEWS_API_KEY" "$EXTRA""#;
+        let mut outputs = Vec::new();
+        for (name, required) in [
+            ("weather", r#"["WEATHER_API_KEY"]"#),
+            ("news", r#"["NEWS_API_KEY"]"#),
+        ] {
+            let dir = write_skill(root.path(), name, required);
+            std::fs::write(dir.join("tool.wasm"), tool).unwrap();
+            let env = SkillEnv::for_dir(&dir, &configured()).unwrap();
+            let engine = super::super::harness::WasmtimeCli {
+                program: fixture.wasmtime.clone(),
+                guest_env: env.vars().unwrap().to_vec(),
+                ..Default::default()
+            };
+            let outcome = engine.run(&dir.join("tool.wasm"), b"{}").unwrap();
+            outputs.push(outcome.envelope().unwrap().output);
+        }
+        assert_eq!(outputs, ["weather-key||", "|news-key|"]);
+    }
+}
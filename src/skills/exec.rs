@@ -0,0 +1,370 @@
+//! Real runs of `shell` and `http` skill tools, for `zeroclaw skill test`.
+//!
+//! A call is prepared exactly as for a dry run ([`super::dry_run::prepare`])
+//! and then carried out within the tool's `timeout_secs` and
+//! `max_output_bytes`. Past the time limit the shell process is killed or the
+//! HTTP request aborted; once stdout, stderr or the response body passes the
+//! output cap, the process is killed or the read stopped. The outcome keeps
+//! what was captured up to the limit and records which limit was hit.
+
+use super::dry_run::{self, Approval, Host, Prepared, PreparedHttp, PreparedShell};
+use super::harness::{self, ExecutionOutcome, ExitBehavior, LimitHit};
+use super::{SkillTool, TestInput};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde_json::{Map, Value};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// The limits of one invocation, from the tool's `[[tools]]` entry.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Limits {
+    pub timeout: Duration,
+    pub max_output_bytes: usize,
+}
+
+impl Limits {
+    pub fn of(tool: &SkillTool) -> Self {
+        Self {
+            timeout: Duration::from_secs(tool.timeout_secs.max(1)),
+            max_output_bytes: tool.max_output_bytes,
+        }
+    }
+}
+
+/// Carry out a prepared call. Errors only when the call could not be made;
+/// a failing tool or a limit hit is reported in the outcome.
+pub(super) fn run(prepared: &Prepared, limits: Limits) -> Result<ExecutionOutcome> {
+    match prepared {
+        Prepared::Shell(shell) => run_shell(shell, limits),
+        // `skill` commands run on the CLI's runtime thread, where the
+        // blocking client may not be used.
+        Prepared::Http(http) => {
+            std::thread::scope(|scope| scope.spawn(|| run_http(http, limits)).join())
+                .map_err(|_| anyhow::anyhow!("HTTP request thread panicked"))?
+        }
+        Prepared::Wasm(_) => bail!("WASM tools run through the wasmtime engines"),
+    }
+}
+
+/// `zeroclaw skill test` for a tool declared in SKILL.toml: prepare it as
+/// the dry run does, refuse what the autonomy policy blocks, and run it.
+pub(super) fn test_declared(
+    skill_dir: &Path,
+    tool: &SkillTool,
+    input: &TestInput,
+    config: &Config,
+) -> Result<()> {
+    let TestInput::Bytes(bytes) = input else {
+        bail!("--stdin-raw only applies to WASM tools");
+    };
+    let args: Map<String, Value> = serde_json::from_slice(bytes).with_context(|| {
+        format!(
+            "--args is not a JSON object: {}",
+            String::from_utf8_lossy(bytes)
+        )
+    })?;
+    let host = Host::load(skill_dir, config)?;
+    let cx = host.cx(skill_dir, config);
+    let prepared = dry_run::prepare(tool, &args, &cx)?;
+    if let Approval::Blocked(reason) = prepared.approval(&tool.name, &cx) {
+        bail!("tool '{}' is blocked: {reason}", tool.name);
+    }
+
+    let limits = Limits::of(tool);
+    println!(
+        "  Running: {} ({} tool, {}s / {} byte limits)",
+        console::style(&tool.name).cyan(),
+        tool.kind,
+        limits.timeout.as_secs(),
+        limits.max_output_bytes
+    );
+    println!();
+    harness::report_single(&run(&prepared, limits)?, false)
+}
+
+/// Bytes read from a pipe on its own thread, at most the output cap.
+struct Capture {
+    bytes: Arc<Mutex<Vec<u8>>>,
+    reader: JoinHandle<()>,
+}
+
+impl Capture {
+    /// Read `pipe` until EOF. Past `cap` bytes the reader sets `overflow` and
+    /// stops, so the caller can kill the writer.
+    fn start(
+        pipe: Option<impl Read + Send + 'static>,
+        cap: usize,
+        overflow: &Arc<AtomicBool>,
+    ) -> Self {
+        let bytes = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&bytes);
+        let overflow = Arc::clone(overflow);
+        let reader = std::thread::spawn(move || {
+            let Some(mut pipe) = pipe else {
+                return;
+            };
+            let mut chunk = [0_u8; 8192];
+            loop {
+                let read = match pipe.read(&mut chunk) {
+                    Ok(0) | Err(_) => return,
+                    Ok(read) => read,
+                };
+                let mut bytes = sink.lock();
+                let room = cap - bytes.len();
+                bytes.extend_from_slice(&chunk[..read.min(room)]);
+                if read > room {
+                    overflow.store(true, Ordering::Relaxed);
+                    return;
+                }
+            }
+        });
+        Self { bytes, reader }
+    }
+
+    /// What was read so far; the reader may still be blocked on a pipe a
+    /// killed shell's children hold open.
+    fn text(&self) -> String {
+        String::from_utf8_lossy(&self.bytes.lock()).into_owned()
+    }
+}
+
+fn run_shell(shell: &PreparedShell, limits: Limits) -> Result<ExecutionOutcome> {
+    let started = Instant::now();
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(&shell.command.value)
+        .current_dir(&shell.cwd)
+        .env_clear();
+    if let Some(path) = std::env::var_os("PATH") {
+        command.env("PATH", path);
+    }
+    let mut child = command
+        .envs(shell.env.iter().map(|(name, value)| (name, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to spawn sh")?;
+
+    let overflow = Arc::new(AtomicBool::new(false));
+    let stdout = Capture::start(child.stdout.take(), limits.max_output_bytes, &overflow);
+    let stderr = Capture::start(child.stderr.take(), limits.max_output_bytes, &overflow);
+
+    // Done once the shell has exited and both pipes are drained.
+    let deadline = started + limits.timeout;
+    let mut exit = None;
+    let mut limits_hit = Vec::new();
+    loop {
+        if exit.is_none() {
+            exit = child.try_wait().context("failed to wait for sh")?;
+        }
+        if overflow.load(Ordering::Relaxed) {
+            limits_hit.push(LimitHit::OutputCap);
+            break;
+        }
+        if exit.is_some() && stdout.reader.is_finished() && stderr.reader.is_finished() {
+            break;
+        }
+        if Instant::now() >= deadline {
+            limits_hit.push(LimitHit::Timeout);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    if exit.is_none() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
+    let status = match exit.map(|status| status.code()) {
+        _ if !limits_hit.is_empty() => ExitBehavior::Interrupted,
+        Some(Some(0)) => ExitBehavior::Completed,
+        Some(Some(code)) => ExitBehavior::Exit(code),
+        Some(None) | None => ExitBehavior::Interrupted,
+    };
+    Ok(ExecutionOutcome {
+        engine: "sh",
+        stdout: stdout.text(),
+        stderr: stderr.text(),
+        status,
+        limits_hit,
+        duration: started.elapsed(),
+        memory_bytes: None,
+        memory_limit_bytes: None,
+    })
+}
+
+fn run_http(http: &PreparedHttp, limits: Limits) -> Result<ExecutionOutcome> {
+    let started = Instant::now();
+    let client = reqwest::blocking::Client::builder()
+        .timeout(limits.timeout)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .context("failed to build the HTTP client")?;
+    let method = reqwest::Method::from_bytes(http.method.as_bytes())
+        .with_context(|| format!("invalid HTTP method '{}'", http.method))?;
+    let mut request = client.request(method, &http.url.value);
+    for (name, value) in &http.headers {
+        request = request.header(name, value);
+    }
+    if let Some(body) = &http.body {
+        request = request.body(body.clone());
+    }
+
+    let outcome = |stdout: Vec<u8>, stderr: String, status, limits_hit| ExecutionOutcome {
+        engine: "http",
+        stdout: String::from_utf8_lossy(&stdout).into_owned(),
+        stderr,
+        status,
+        limits_hit,
+        duration: started.elapsed(),
+        memory_bytes: None,
+        memory_limit_bytes: None,
+    };
+    let response = match request.send() {
+        Ok(response) => response,
+        Err(err) if err.is_timeout() => {
+            return Ok(outcome(
+                Vec::new(),
+                err.to_string(),
+                ExitBehavior::Interrupted,
+                vec![LimitHit::Timeout],
+            ));
+        }
+        Err(err) => return Err(err).context("HTTP request failed"),
+    };
+
+    let code = response.status();
+    // One byte past the cap tells a full body from a cut one; dropping the
+    // response then abandons the rest of the read.
+    let mut body = Vec::new();
+    let read = response
+        .take(limits.max_output_bytes as u64 + 1)
+        .read_to_end(&mut body);
+    let limits_hit = match read {
+        Err(_) if started.elapsed() >= limits.timeout => vec![LimitHit::Timeout],
+        Err(err) => return Err(err).context("failed to read the HTTP response"),
+        Ok(_) if body.len() > limits.max_output_bytes => {
+            body.truncate(limits.max_output_bytes);
+            vec![LimitHit::OutputCap]
+        }
+        Ok(_) => Vec::new(),
+    };
+    let status = if !limits_hit.is_empty() {
+        ExitBehavior::Interrupted
+    } else if code.is_success() {
+        ExitBehavior::Completed
+    } else {
+        ExitBehavior::Exit(i32::from(code.as_u16()))
+    };
+    Ok(outcome(body, format!("HTTP {code}"), status, limits_hit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpListener;
+
+    fn prepared(kind: &str, command: &str, skill_dir: &Path) -> Prepared {
+        let tool = SkillTool {
+            name: "probe".into(),
+            description: "test".into(),
+            kind: kind.into(),
+            command: command.into(),
+            args: Default::default(),
+            timeout_secs: super::super::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+            max_output_bytes: super::super::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
+        };
+        let config = Config::default();
+        let host = Host::load(skill_dir, &config).unwrap();
+        dry_run::prepare(&tool, &Map::new(), &host.cx(skill_dir, &config)).unwrap()
+    }
+
+    fn limits(timeout_secs: u64, max_output_bytes: usize) -> Limits {
+        Limits {
+            timeout: Duration::from_secs(timeout_secs),
+            max_output_bytes,
+        }
+    }
+
+    #[test]
+    fn shell_tool_within_limits_completes() {
+        let skill = tempfile::tempdir().unwrap();
+        std::fs::write(skill.path().join("data.txt"), "from the skill dir").unwrap();
+        let outcome = run(
+            &prepared("shell", "cat data.txt; echo oops >&2", skill.path()),
+            limits(10, 1024),
+        )
+        .unwrap();
+        assert_eq!(outcome.status, ExitBehavior::Completed);
+        assert_eq!(outcome.stdout, "from the skill dir");
+        assert_eq!(outcome.stderr, "oops\n");
+        assert!(outcome.limits_hit.is_empty());
+    }
+
+    #[test]
+    fn shell_tool_past_the_output_cap_is_killed_and_truncated() {
+        let skill = tempfile::tempdir().unwrap();
+        let outcome = run(
+            &prepared("shell", "while :; do echo chatty; done", skill.path()),
+            limits(30, 1024),
+        )
+        .unwrap();
+        assert_eq!(outcome.limits_hit, [LimitHit::OutputCap]);
+        assert_eq!(outcome.status, ExitBehavior::Interrupted);
+        assert_eq!(outcome.stdout.len(), 1024);
+        assert!(outcome.stdout.starts_with("chatty\nchatty\n"));
+        assert!(outcome.duration < Duration::from_secs(30));
+    }
+
+    #[test]
+    fn shell_tool_past_the_timeout_is_killed() {
+        let skill = tempfile::tempdir().unwrap();
+        let outcome = run(
+            &prepared("shell", "echo started; sleep 30", skill.path()),
+            limits(1, 1024),
+        )
+        .unwrap();
+        assert_eq!(outcome.limits_hit, [LimitHit::Timeout]);
+        assert_eq!(outcome.status, ExitBehavior::Interrupted);
+        assert_eq!(outcome.stdout, "started\n");
+        assert!(outcome.duration < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn http_tool_past_the_output_cap_stops_reading() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0_u8; 4096];
+            let _ = stream.read(&mut request);
+            let body = "x".repeat(64 * 1024);
+            let _ = write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}",
+                body.len()
+            );
+        });
+
+        let skill = tempfile::tempdir().unwrap();
+        let outcome = run(
+            &prepared("http", &format!("GET http://{addr}/big"), skill.path()),
+            limits(10, 100),
+        )
+        .unwrap();
+        assert_eq!(outcome.limits_hit, [LimitHit::OutputCap]);
+        assert_eq!(outcome.status, ExitBehavior::Interrupted);
+        assert_eq!(outcome.stdout, "x".repeat(100));
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,196 @@
+//! `zeroclaw skill export`: copy an installed skill out of the workspace so
+//! it can be edited as a project of its own.
+//!
+//! The copy goes through `copy_dir_recursive_secure`, so a skill containing
+//! symlinks is refused rather than exported with links pointing elsewhere.
+//! `--clean` drops what ZeroClaw added at install time, leaving only the
+//! skill's own sources.
+
+use super::audit::QUARANTINE_DIR;
+use super::{copy_dir_recursive_secure, INSTALL_RECORD_FILE};
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Install-time provenance removed by `--clean`: the install record, the
+/// ClawhHub/OpenClaw registry metadata, and files moved aside by
+/// `skill audit --fix`.
+const PROVENANCE: [&str; 3] = [INSTALL_RECORD_FILE, "_meta.json", QUARANTINE_DIR];
+
+/// Copy `skill_path` (an installed skill under `skills_path`) to `dest` and
+/// return the relative paths of the exported files, sorted.
+pub(super) fn export_skill(
+    skill_path: &Path,
+    skills_path: &Path,
+    dest: &Path,
+    clean: bool,
+) -> Result<Vec<String>> {
+    let dest = std::path::absolute(dest)
+        .with_context(|| format!("cannot resolve export destination {}", dest.display()))?;
+    if crate::security::paths::assert_contained(skills_path, &dest).is_ok() {
+        bail!(
+            "Export destination {} is inside the skills directory; choose a location outside {}",
+            dest.display(),
+            skills_path.display()
+        );
+    }
+    let existed = dest.exists();
+    if existed
+        && std::fs::read_dir(&dest)
+            .with_context(|| format!("cannot read export destination {}", dest.display()))?
+            .next()
+            .is_some()
+    {
+        bail!("Export destination is not empty: {}", dest.display());
+    }
+
+    if let Err(err) = copy_dir_recursive_secure(skill_path, &dest) {
+        // Leave no half-exported tree behind.
+        if existed {
+            for entry in std::fs::read_dir(&dest).into_iter().flatten().flatten() {
+                let _ = remove_path(&entry.path());
+            }
+        } else {
+            let _ = std::fs::remove_dir_all(&dest);
+        }
+        return Err(err);
+    }
+    if clean {
+        for name in PROVENANCE {
+            let path = dest.join(name);
+            if path.exists() {
+                remove_path(&path)?;
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    collect_files(&dest, &dest, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn remove_path(path: &Path) -> Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+    .with_context(|| format!("failed to remove {}", path.display()))
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, out)?;
+        } else {
+            let relative: PathBuf = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            out.push(
+                relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/"),
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn installed_skill(skills_path: &Path) -> PathBuf {
+        let skill = skills_path.join("weather");
+        fs::create_dir_all(skill.join("src")).unwrap();
+        fs::create_dir_all(skill.join(QUARANTINE_DIR)).unwrap();
+        fs::write(skill.join("SKILL.toml"), "[skill]\nname = \"weather\"\n").unwrap();
+        fs::write(skill.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(skill.join(INSTALL_RECORD_FILE), "{}").unwrap();
+        fs::write(skill.join("_meta.json"), "{}").unwrap();
+        fs::write(skill.join(QUARANTINE_DIR).join("setup.sh"), "echo hi\n").unwrap();
+        skill
+    }
+
+    #[test]
+    fn export_copies_the_skill_and_clean_drops_provenance() {
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        let skill = installed_skill(&skills_path);
+        let out = tempfile::tempdir().unwrap();
+
+        let files = export_skill(&skill, &skills_path, &out.path().join("full"), false).unwrap();
+        assert_eq!(
+            files,
+            [
+                ".quarantine/setup.sh",
+                "SKILL.toml",
+                "_install.json",
+                "_meta.json",
+                "src/main.rs"
+            ]
+        );
+
+        let files = export_skill(&skill, &skills_path, &out.path().join("clean"), true).unwrap();
+        assert_eq!(files, ["SKILL.toml", "src/main.rs"]);
+        // The installed skill is untouched.
+        assert!(skill.join(INSTALL_RECORD_FILE).is_file());
+    }
+
+    #[test]
+    fn export_refuses_non_empty_or_in_workspace_destinations() {
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        let skill = installed_skill(&skills_path);
+
+        let err = export_skill(
+            &skill,
+            &skills_path,
+            &skills_path.join("weather-copy"),
+            false,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("inside the skills directory"),
+            "{err}"
+        );
+
+        let out = tempfile::tempdir().unwrap();
+        fs::write(out.path().join("keep.txt"), "mine").unwrap();
+        let err = export_skill(&skill, &skills_path, out.path(), false).unwrap_err();
+        assert!(err.to_string().contains("not empty"), "{err}");
+        assert_eq!(
+            fs::read_to_string(out.path().join("keep.txt")).unwrap(),
+            "mine"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn export_rejects_symlinked_sources_and_leaves_nothing_behind() {
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        let skill = installed_skill(&skills_path);
+        let outside = tempfile::tempdir().unwrap();
+        fs::write(outside.path().join("secret.txt"), "token").unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.txt"), skill.join("link.txt"))
+            .unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let dest = out.path().join("weather");
+        let err = export_skill(&skill, &skills_path, &dest, false).unwrap_err();
+        assert!(err.to_string().contains("symlink"), "{err}");
+        assert!(!dest.exists());
+
+        // A skill directory that is itself a symlink is refused too.
+        let linked = skills_path.join("linked");
+        std::os::unix::fs::symlink(outside.path(), &linked).unwrap();
+        let err =
+            export_skill(&linked, &skills_path, &out.path().join("linked"), false).unwrap_err();
+        assert!(err.to_string().contains("symlinked skill source"), "{err}");
+    }
+}
@@ -0,0 +1,202 @@
+//! Frozen skills: installed skills protected from bulk and automatic changes.
+//!
+//! `zeroclaw skill freeze <name>` records the skill in `skills/.frozen`. Bulk
+//! paths (pack dependency resolution, `remove --with-dependencies`, the
+//! open-skills sync and same-named open-skills) leave frozen skills alone and
+//! say "skipped (frozen)"; explicit single-skill mutations such as
+//! `skill remove <name>` require `--force`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Workspace-level freeze list, next to the installed skill directories.
+const FROZEN_FILE: &str = ".frozen";
+
+/// Why and when a skill was frozen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct FreezeEntry {
+    /// RFC 3339 timestamp of the freeze.
+    pub frozen_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl FreezeEntry {
+    /// `frozen since <time>` plus the reason, if any.
+    pub fn describe(&self) -> String {
+        match &self.reason {
+            Some(reason) => format!("frozen since {} ({reason})", self.frozen_at),
+            None => format!("frozen since {}", self.frozen_at),
+        }
+    }
+}
+
+/// Frozen skill names (install directory names) for one workspace.
+#[derive(Debug, Default)]
+pub(super) struct FrozenSkills {
+    path: PathBuf,
+    entries: BTreeMap<String, FreezeEntry>,
+}
+
+impl FrozenSkills {
+    pub fn load(skills_path: &Path) -> Result<Self> {
+        let path = skills_path.join(FROZEN_FILE);
+        let entries = if path.is_file() {
+            let raw = std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            serde_json::from_str(&raw)
+                .with_context(|| format!("invalid freeze list {}", path.display()))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, entries })
+    }
+
+    /// Like [`Self::load`], but an unreadable list only logs a warning. Used
+    /// by paths that must keep working (skill loading, bulk operations).
+    pub fn load_or_default(skills_path: &Path) -> Self {
+        Self::load(skills_path).unwrap_or_else(|err| {
+            tracing::warn!("ignoring skill freeze list: {err:#}");
+            Self {
+                path: skills_path.join(FROZEN_FILE),
+                entries: BTreeMap::new(),
+            }
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if self.entries.is_empty() {
+            if self.path.exists() {
+                std::fs::remove_file(&self.path)
+                    .with_context(|| format!("failed to remove {}", self.path.display()))?;
+            }
+            return Ok(());
+        }
+        let json = serde_json::to_string_pretty(&self.entries)
+            .context("failed to serialize freeze list")?;
+        std::fs::write(&self.path, json)
+            .with_context(|| format!("failed to write {}", self.path.display()))
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FreezeEntry> {
+        self.entries.get(name)
+    }
+
+    pub fn is_frozen(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Freeze (or update the reason of) `name`. Returns whether it was newly frozen.
+    pub fn freeze(&mut self, name: &str, reason: Option<String>) -> bool {
+        let reason = reason
+            .map(|reason| reason.trim().to_string())
+            .filter(|reason| !reason.is_empty());
+        match self.entries.get_mut(name) {
+            Some(entry) => {
+                if reason.is_some() {
+                    entry.reason = reason;
+                }
+                false
+            }
+            None => {
+                self.entries.insert(
+                    name.to_string(),
+                    FreezeEntry {
+                        frozen_at: chrono::Utc::now().to_rfc3339(),
+                        reason,
+                    },
+                );
+                true
+            }
+        }
+    }
+
+    /// Returns whether `name` was frozen.
+    pub fn unfreeze(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+
+    /// Refuse `action` on a frozen skill unless `force` is set.
+    pub fn ensure_mutable(&self, name: &str, action: &str, force: bool) -> Result<()> {
+        match self.get(name) {
+            Some(entry) if !force => anyhow::bail!(
+                "Skill '{name}' is {}. Frozen skills are protected from changes; \
+                 re-run with --force to {action} it anyway, or run \
+                 'zeroclaw skill unfreeze {name}' first.",
+                entry.describe()
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Print the standard line for a bulk operation passing over a frozen skill.
+pub(super) fn print_skipped(name: &str) {
+    println!(
+        "  {} {name}: skipped (frozen)",
+        console::style("❄").cyan().bold()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freeze_list_round_trips_and_is_removed_when_empty() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut frozen = FrozenSkills::load(dir.path()).unwrap();
+        assert!(frozen.freeze("weather", Some("patched locally".into())));
+        assert!(!frozen.freeze("weather", None), "already frozen");
+        assert!(frozen.freeze("notes", Some("  ".into())));
+        frozen.save().unwrap();
+
+        let reloaded = FrozenSkills::load(dir.path()).unwrap();
+        assert_eq!(reloaded.names().collect::<Vec<_>>(), ["notes", "weather"]);
+        let entry = reloaded.get("weather").unwrap();
+        assert_eq!(entry.reason.as_deref(), Some("patched locally"));
+        assert!(entry.describe().ends_with("(patched locally)"));
+        assert_eq!(reloaded.get("notes").unwrap().reason, None);
+
+        let mut reloaded = reloaded;
+        assert!(reloaded.unfreeze("weather"));
+        assert!(reloaded.unfreeze("notes"));
+        assert!(!reloaded.unfreeze("notes"));
+        reloaded.save().unwrap();
+        assert!(!dir.path().join(FROZEN_FILE).exists());
+    }
+
+    #[test]
+    fn frozen_skill_requires_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut frozen = FrozenSkills::load(dir.path()).unwrap();
+        frozen.freeze("weather", Some("audited fork".into()));
+
+        let err = frozen
+            .ensure_mutable("weather", "remove", false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("is frozen since"), "{err}");
+        assert!(err.contains("audited fork"), "{err}");
+        assert!(err.contains("--force to remove it anyway"), "{err}");
+
+        assert!(frozen.ensure_mutable("weather", "remove", true).is_ok());
+        assert!(frozen.ensure_mutable("other", "remove", false).is_ok());
+    }
+
+    #[test]
+    fn invalid_freeze_list_is_ignored_by_bulk_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(FROZEN_FILE), "not json").unwrap();
+
+        assert!(FrozenSkills::load(dir.path()).is_err());
+        assert_eq!(FrozenSkills::load_or_default(dir.path()).names().count(), 0);
+    }
+}
@@ -0,0 +1,122 @@
+//! Minimal YAML frontmatter reader for `SKILL.md`.
+//!
+//! Skills often open with a `---` delimited block of metadata (`name:`,
+//! `description:`, ...). Only top-level scalar fields are needed, so this
+//! reads plain, single- and double-quoted values and `|` / `>` block
+//! scalars without pulling in a YAML parser. Anything it can't read is
+//! treated as absent.
+
+/// Split `content` into its frontmatter block and the rest. `None` when the
+/// file does not open with a `---` line or the block is never closed.
+pub(super) fn split(content: &str) -> Option<(&str, &str)> {
+    let rest = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let rest = rest
+        .strip_prefix("---\r\n")
+        .or_else(|| rest.strip_prefix("---\n"))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if matches!(line.trim_end(), "---" | "...") {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Top-level scalar `key` from a frontmatter block, trimmed; `None` when
+/// missing or empty.
+pub(super) fn field(frontmatter: &str, key: &str) -> Option<String> {
+    let mut lines = frontmatter.lines();
+    while let Some(line) = lines.next() {
+        let Some(value) = line
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+        else {
+            continue;
+        };
+        let value = strip_comment(value).trim();
+        let parsed = match value.chars().next() {
+            Some('|' | '>') => {
+                let folded = value.starts_with('>');
+                let block: Vec<&str> = lines
+                    .clone()
+                    .take_while(|l| l.trim().is_empty() || l.starts_with([' ', '\t']))
+                    .map(str::trim)
+                    .collect();
+                if folded {
+                    block
+                        .iter()
+                        .filter(|l| !l.is_empty())
+                        .copied()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                } else {
+                    block.join("\n").trim().to_string()
+                }
+            }
+            Some('"') => unquote(value, '"')?
+                .replace("\\\"", "\"")
+                .replace("\\n", "\n"),
+            Some('\'') => unquote(value, '\'')?.replace("''", "'"),
+            _ => value.to_string(),
+        };
+        let parsed = parsed.trim().to_string();
+        return (!parsed.is_empty()).then_some(parsed);
+    }
+    None
+}
+
+fn unquote(value: &str, quote: char) -> Option<&str> {
+    value.strip_prefix(quote)?.strip_suffix(quote)
+}
+
+/// Drop a trailing ` # comment` from an unquoted value.
+fn strip_comment(value: &str) -> &str {
+    if value.trim_start().starts_with(['"', '\'']) {
+        return value;
+    }
+    value.find(" #").map_or(value, |at| &value[..at])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_plain_quoted_and_block_fields() {
+        let content = "---\nname: weather\ndescription: \"Forecasts: today and \\\"tomorrow\\\"\"\n\
+                       tagline: 'It''s sunny'\nlabel: plain text # shown in lists\nsummary: >\n  Folded\n  onto one line\n\
+                       notes: |\n  kept\n  as lines\nempty:\n---\n# Weather\n";
+        let (frontmatter, body) = split(content).unwrap();
+        assert_eq!(body, "# Weather\n");
+        assert_eq!(field(frontmatter, "name").as_deref(), Some("weather"));
+        assert_eq!(
+            field(frontmatter, "description").as_deref(),
+            Some("Forecasts: today and \"tomorrow\"")
+        );
+        assert_eq!(field(frontmatter, "tagline").as_deref(), Some("It's sunny"));
+        assert_eq!(field(frontmatter, "label").as_deref(), Some("plain text"));
+        assert_eq!(
+            field(frontmatter, "summary").as_deref(),
+            Some("Folded onto one line")
+        );
+        assert_eq!(
+            field(frontmatter, "notes").as_deref(),
+            Some("kept\nas lines")
+        );
+        assert_eq!(field(frontmatter, "empty"), None);
+        assert_eq!(field(frontmatter, "missing"), None);
+    }
+
+    #[test]
+    fn only_a_leading_closed_block_counts() {
+        assert!(split("# Title\n---\ndescription: x\n---\n").is_none());
+        assert!(split("---\ndescription: never closed\n").is_none());
+        let (frontmatter, body) = split("\u{feff}---\r\ndescription: crlf\r\n---\r\nbody").unwrap();
+        assert_eq!(field(frontmatter, "description").as_deref(), Some("crlf"));
+        assert_eq!(body, "body");
+        // Nested keys are not top-level fields.
+        let (frontmatter, _) = split("---\nmeta:\n  description: nested\n---\n").unwrap();
+        assert_eq!(field(frontmatter, "description"), None);
+    }
+}
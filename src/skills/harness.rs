@@ -0,0 +1,792 @@
+//! Execution engines behind `zeroclaw skill test` and `skill bench`.
+//!
+//! A skill tool can run through the `wasmtime` CLI (what `skill test` has
+//! always used) or through the in-process engine the runtime loads tools
+//! with ([`crate::tools::wasm_tool::WasmTool`]). Both return an
+//! [`ExecutionOutcome`] instead of printing, so `skill test` can report one
+//! run, `skill test --compare-engines` can diff two, and `skill bench` can
+//! aggregate many.
+//!
+//! Comparison parses each stdout as a [`ToolEnvelope`], so field order and
+//! whitespace never count as a difference. Compared: the success flag, output
+//! text, error, data payload, exit behavior and which resource limits were
+//! hit. Stderr and timing are shown but never compared.
+
+use super::protocol::ToolEnvelope;
+use crate::tools::wasm_queue::{self, WasmPriority};
+use crate::tools::wasm_tool::{WasmTool, MAX_INPUT_BYTES, MAX_OUTPUT_BYTES, WASM_TIMEOUT_SECS};
+use anyhow::{Context, Result};
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Exit code of the `wasmtime` CLI when the module traps (unix, like abort).
+const CLI_TRAP_EXIT_CODE: i32 = 134;
+
+/// How a run ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum ExitBehavior {
+    /// `_start` returned, or the tool exited with code 0.
+    Completed,
+    /// The tool exited with a non-zero code.
+    Exit(i32),
+    /// The module trapped or could not start.
+    Trap,
+    /// Stopped at the time limit, or killed past the output cap.
+    Interrupted,
+}
+
+impl fmt::Display for ExitBehavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Completed => f.write_str("completed"),
+            Self::Exit(code) => write!(f, "exit {code}"),
+            Self::Trap => f.write_str("trap"),
+            Self::Interrupted => f.write_str("interrupted"),
+        }
+    }
+}
+
+/// A resource limit a run ran into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LimitHit {
+    Timeout,
+    OutputCap,
+}
+
+impl fmt::Display for LimitHit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Timeout => "timeout",
+            Self::OutputCap => "output cap",
+        })
+    }
+}
+
+/// Everything one engine observed while running a tool once.
+#[derive(Debug)]
+pub(super) struct ExecutionOutcome {
+    pub engine: &'static str,
+    pub stdout: String,
+    pub stderr: String,
+    pub status: ExitBehavior,
+    pub limits_hit: Vec<LimitHit>,
+    pub duration: Duration,
+    /// Peak linear memory of the module; only the in-process engine sees it.
+    pub memory_bytes: Option<u64>,
+    /// Maximum memory the module declares, if any.
+    pub memory_limit_bytes: Option<u64>,
+}
+
+impl ExecutionOutcome {
+    /// Stdout parsed as a tool result envelope, if it is one. Progress lines
+    /// written by streaming tools are skipped.
+    pub fn envelope(&self) -> Option<ToolEnvelope> {
+        ToolEnvelope::parse_streamed(self.stdout.trim().as_bytes()).ok()
+    }
+}
+
+/// A way of running a skill's `tool.wasm`.
+pub(super) trait ExecutionEngine {
+    fn name(&self) -> &'static str;
+
+    /// Why this engine can't run here, or `None` when it can.
+    fn unavailable_reason(&self) -> Option<String>;
+
+    /// Run `wasm` once with `input` on stdin. Errors only when the engine
+    /// itself fails; a failing tool is reported in the outcome.
+    fn run(&self, wasm: &Path, input: &[u8]) -> Result<ExecutionOutcome>;
+}
+
+/// Refuse a tool input over [`MAX_INPUT_BYTES`].
+pub(super) fn check_input_size(len: u64) -> Result<()> {
+    if len > MAX_INPUT_BYTES as u64 {
+        anyhow::bail!(
+            "tool input is larger than the {} MiB limit",
+            MAX_INPUT_BYTES / 1_048_576
+        );
+    }
+    Ok(())
+}
+
+/// Read `input` to EOF, failing once it passes [`MAX_INPUT_BYTES`].
+pub(super) fn read_input(input: impl Read) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    input
+        .take(MAX_INPUT_BYTES as u64 + 1)
+        .read_to_end(&mut bytes)
+        .context("failed to read tool input")?;
+    check_input_size(bytes.len() as u64)?;
+    Ok(bytes)
+}
+
+/// The system `wasmtime` CLI, run as a child process.
+pub(super) struct WasmtimeCli {
+    pub program: PathBuf,
+    /// Guest environment. Values go through the child's environment and
+    /// only names through `--env KEY`, so they never show up in `ps`.
+    pub guest_env: Vec<(String, String)>,
+    pub timeout: Duration,
+}
+
+impl Default for WasmtimeCli {
+    fn default() -> Self {
+        Self {
+            program: PathBuf::from("wasmtime"),
+            guest_env: Vec::new(),
+            timeout: Duration::from_secs(WASM_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl ExecutionEngine for WasmtimeCli {
+    fn name(&self) -> &'static str {
+        "wasmtime CLI"
+    }
+
+    fn unavailable_reason(&self) -> Option<String> {
+        let found = Command::new(&self.program)
+            .arg("--version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        (!found).then(|| format!("'{}' was not found on PATH", self.program.display()))
+    }
+
+    fn run(&self, wasm: &Path, input: &[u8]) -> Result<ExecutionOutcome> {
+        check_input_size(input.len() as u64)?;
+        self.run_streaming(wasm, input)
+    }
+}
+
+impl WasmtimeCli {
+    /// [`ExecutionEngine::run`] with stdin copied from `input` as it is read
+    /// instead of from a buffer, e.g. `skill test --args - --stdin-raw`. The
+    /// run fails once more than [`MAX_INPUT_BYTES`] have been copied.
+    pub fn run_streaming(&self, wasm: &Path, input: impl Read) -> Result<ExecutionOutcome> {
+        let mut command = Command::new(&self.program);
+        command.arg("run");
+        for (key, value) in &self.guest_env {
+            command.env(key, value).arg("--env").arg(key);
+        }
+        let _permit = wasm_queue::global().acquire(WasmPriority::Interactive)?;
+        let started = Instant::now();
+        let mut child = command
+            .arg(wasm)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context(
+                "wasmtime not found — install it first:\n\n\
+                 \x20 macOS (Homebrew):  brew install wasmtime\n\
+                 \x20 macOS/Linux:       curl https://wasmtime.dev/install.sh -sSf | bash\n\
+                 \x20 Cargo (slow):      cargo install wasmtime-cli\n\n\
+                 After installing, restart your terminal and run this command again.\n\
+                 Docs: https://wasmtime.dev",
+            )?;
+
+        // Drain both pipes on their own threads so a chatty tool can't block
+        // on a full pipe while we wait for it, or while we still write stdin.
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        // take() moves stdin out so it is dropped (closed) at end of block,
+        // sending EOF to the child process. A tool that exits without reading
+        // its input closes the pipe first; that is not an engine failure.
+        if let Some(mut stdin) = child.stdin.take() {
+            let mut input = input.take(MAX_INPUT_BYTES as u64 + 1);
+            let copied = match std::io::copy(&mut input, &mut stdin) {
+                Ok(copied) => copied,
+                Err(err) if err.kind() == std::io::ErrorKind::BrokenPipe => 0,
+                Err(err) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(err).context("failed to write args to wasmtime");
+                }
+            };
+            if let Err(err) = check_input_size(copied) {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(err);
+            }
+        }
+
+        let deadline = started + self.timeout;
+        let exit = loop {
+            if let Some(status) = child.try_wait().context("failed to wait for wasmtime")? {
+                break Some(status);
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        let duration = started.elapsed();
+        let mut stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        let mut limits_hit = Vec::new();
+        let status = match exit.map(|status| status.code()) {
+            None => {
+                limits_hit.push(LimitHit::Timeout);
+                ExitBehavior::Interrupted
+            }
+            Some(Some(0)) => ExitBehavior::Completed,
+            Some(Some(CLI_TRAP_EXIT_CODE) | None) => ExitBehavior::Trap,
+            Some(Some(code)) => ExitBehavior::Exit(code),
+        };
+        // The in-process engine caps stdout at MAX_OUTPUT_BYTES; apply the
+        // same cap here so the two are comparable.
+        if stdout.len() >= MAX_OUTPUT_BYTES {
+            stdout.truncate(MAX_OUTPUT_BYTES);
+            limits_hit.push(LimitHit::OutputCap);
+        }
+
+        Ok(ExecutionOutcome {
+            engine: self.name(),
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+            status,
+            limits_hit,
+            duration,
+            memory_bytes: None,
+            memory_limit_bytes: None,
+        })
+    }
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// The engine the runtime uses (`--features wasm-tools`).
+#[derive(Default)]
+pub(super) struct InProcess {
+    pub guest_env: Vec<(String, String)>,
+    /// Module compiled by the first run, reused while the path stays the same.
+    compiled: RefCell<Option<(PathBuf, WasmTool)>>,
+}
+
+impl InProcess {
+    pub fn with_env(guest_env: Vec<(String, String)>) -> Self {
+        Self {
+            guest_env,
+            ..Self::default()
+        }
+    }
+}
+
+impl ExecutionEngine for InProcess {
+    fn name(&self) -> &'static str {
+        "in-process"
+    }
+
+    fn unavailable_reason(&self) -> Option<String> {
+        (!cfg!(feature = "wasm-tools"))
+            .then(|| "this build was compiled without '--features wasm-tools'".to_string())
+    }
+
+    /// The first run's duration includes compiling the module, as the CLI
+    /// does on every run; later runs of the same file reuse it, like the
+    /// runtime does once a tool is loaded.
+    fn run(&self, wasm: &Path, input: &[u8]) -> Result<ExecutionOutcome> {
+        check_input_size(input.len() as u64)?;
+        let _permit = wasm_queue::global().acquire(WasmPriority::Interactive)?;
+        let started = Instant::now();
+        let tool = match self.compiled.take() {
+            Some((path, tool)) if path == wasm => tool,
+            _ => WasmTool::load(
+                wasm,
+                "skill_test".into(),
+                String::new(),
+                serde_json::Value::Null,
+            )?,
+        };
+        let run = tool.run_raw(input, &self.guest_env)?;
+        let duration = started.elapsed();
+        self.compiled.replace(Some((wasm.to_path_buf(), tool)));
+
+        let mut limits_hit = Vec::new();
+        let status = if run.timed_out {
+            limits_hit.push(LimitHit::Timeout);
+            ExitBehavior::Interrupted
+        } else {
+            match run.exit_code {
+                Some(0) => ExitBehavior::Completed,
+                Some(code) => ExitBehavior::Exit(code),
+                None => ExitBehavior::Trap,
+            }
+        };
+        if run.stdout.len() >= MAX_OUTPUT_BYTES {
+            limits_hit.push(LimitHit::OutputCap);
+        }
+        let mut stderr = String::from_utf8_lossy(&run.stderr).into_owned();
+        if let Some(err) = run.error.filter(|_| !run.timed_out) {
+            stderr.push_str(&format!("{err:#}\n"));
+        }
+
+        Ok(ExecutionOutcome {
+            engine: self.name(),
+            stdout: String::from_utf8_lossy(&run.stdout).into_owned(),
+            stderr,
+            status,
+            limits_hit,
+            duration,
+            memory_bytes: run.memory_bytes,
+            memory_limit_bytes: run.memory_limit_bytes,
+        })
+    }
+}
+
+// ─── Reporting ───────────────────────────────────────────────────────────────
+
+/// Print one run the way `skill test` always has: raw stdout, then a summary
+/// when stdout is a result envelope. `raw` (`--stdin-raw`) prints stdout
+/// only. A run that didn't complete is an error.
+pub(super) fn report_single(outcome: &ExecutionOutcome, raw: bool) -> Result<()> {
+    if outcome.status != ExitBehavior::Completed {
+        let limits = outcome
+            .limits_hit
+            .iter()
+            .map(|limit| format!(" ({limit} reached)"))
+            .collect::<String>();
+        anyhow::bail!(
+            "{} run ended with {}{limits}:\n{}",
+            outcome.engine,
+            outcome.status,
+            outcome.stderr
+        );
+    }
+
+    println!("{}", outcome.stdout);
+    if let Some(envelope) = outcome.envelope().filter(|_| !raw) {
+        println!();
+        if envelope.success {
+            println!(
+                "  {} Tool returned success",
+                console::style("✓").green().bold()
+            );
+        } else {
+            let err = envelope.error.as_deref().unwrap_or("unknown");
+            println!(
+                "  {} Tool returned failure: {err}",
+                console::style("✗").red().bold()
+            );
+        }
+    }
+    println!(
+        "  {}",
+        console::style(format!(
+            "{} took {}",
+            outcome.engine,
+            millis(outcome.duration)
+        ))
+        .dim()
+    );
+    Ok(())
+}
+
+/// One compared field of two outcomes.
+#[derive(Debug)]
+pub(super) struct FieldComparison {
+    pub field: &'static str,
+    pub left: String,
+    pub right: String,
+    pub same: bool,
+}
+
+/// Compare two outcomes field by field. Stdout is compared raw only when
+/// either side is not a valid envelope.
+pub(super) fn compare_outcomes(
+    left: &ExecutionOutcome,
+    right: &ExecutionOutcome,
+) -> Vec<FieldComparison> {
+    fn row(field: &'static str, left: String, right: String) -> FieldComparison {
+        let same = left == right;
+        FieldComparison {
+            field,
+            left,
+            right,
+            same,
+        }
+    }
+    fn text(value: Option<&str>) -> String {
+        value.map_or_else(|| "-".to_string(), str::to_string)
+    }
+
+    let (left_env, right_env) = (left.envelope(), right.envelope());
+    let mut rows = Vec::new();
+    let field = |envelope: Option<&ToolEnvelope>, get: fn(&ToolEnvelope) -> String| {
+        envelope.map_or_else(|| "(no envelope)".to_string(), get)
+    };
+    rows.push(row(
+        "success",
+        field(left_env.as_ref(), |e| e.success.to_string()),
+        field(right_env.as_ref(), |e| e.success.to_string()),
+    ));
+    rows.push(row(
+        "output",
+        field(left_env.as_ref(), |e| e.output.clone()),
+        field(right_env.as_ref(), |e| e.output.clone()),
+    ));
+    rows.push(row(
+        "error",
+        field(left_env.as_ref(), |e| text(e.error.as_deref())),
+        field(right_env.as_ref(), |e| text(e.error.as_deref())),
+    ));
+    // Compare data as JSON values so object key order never matters.
+    let data = |envelope: &Option<ToolEnvelope>| envelope.as_ref().map(|e| e.data.clone());
+    let render = |data: &Option<Option<serde_json::Value>>| match data {
+        None => "(no envelope)".to_string(),
+        Some(None) => "-".to_string(),
+        Some(Some(value)) => value.to_string(),
+    };
+    let (left_data, right_data) = (data(&left_env), data(&right_env));
+    rows.push(FieldComparison {
+        field: "data",
+        left: render(&left_data),
+        right: render(&right_data),
+        same: left_data == right_data,
+    });
+    if left_env.is_none() || right_env.is_none() {
+        rows.push(row(
+            "stdout",
+            left.stdout.trim().to_string(),
+            right.stdout.trim().to_string(),
+        ));
+    }
+    rows.push(row(
+        "exit",
+        left.status.to_string(),
+        right.status.to_string(),
+    ));
+    let limits = |outcome: &ExecutionOutcome| {
+        if outcome.limits_hit.is_empty() {
+            "none".to_string()
+        } else {
+            outcome
+                .limits_hit
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    rows.push(row("limits", limits(left), limits(right)));
+    rows
+}
+
+/// Run `args_json` through both engines and print a comparison table.
+/// Returns the divergent fields; when an engine is unavailable this prints a
+/// notice, runs the other one alone, and returns no divergences.
+pub(super) fn run_comparison(
+    left: &dyn ExecutionEngine,
+    right: &dyn ExecutionEngine,
+    wasm: &Path,
+    args_json: &str,
+) -> Result<Vec<FieldComparison>> {
+    let unavailable = [left, right]
+        .into_iter()
+        .filter_map(|engine| {
+            engine
+                .unavailable_reason()
+                .map(|reason| (engine.name(), reason))
+        })
+        .collect::<Vec<_>>();
+    if !unavailable.is_empty() {
+        for (name, reason) in &unavailable {
+            println!(
+                "  {} --compare-engines: the {name} engine is unavailable ({reason})",
+                console::style("!").yellow().bold()
+            );
+        }
+        let Some(engine) = [left, right]
+            .into_iter()
+            .find(|engine| engine.unavailable_reason().is_none())
+        else {
+            anyhow::bail!("no skill test engine is available");
+        };
+        println!("  Running with the {} engine only.", engine.name());
+        println!();
+        report_single(&engine.run(wasm, args_json.as_bytes())?, false)?;
+        return Ok(Vec::new());
+    }
+
+    let left_outcome = left
+        .run(wasm, args_json.as_bytes())
+        .with_context(|| format!("{} engine failed", left.name()))?;
+    let right_outcome = right
+        .run(wasm, args_json.as_bytes())
+        .with_context(|| format!("{} engine failed", right.name()))?;
+    let rows = compare_outcomes(&left_outcome, &right_outcome);
+    print_table(&left_outcome, &right_outcome, &rows);
+    Ok(rows.into_iter().filter(|row| !row.same).collect())
+}
+
+fn print_table(left: &ExecutionOutcome, right: &ExecutionOutcome, rows: &[FieldComparison]) {
+    const MAX_CELL: usize = 48;
+    let cell = |value: &str| {
+        let value = value.replace('\n', "\\n");
+        if value.chars().count() > MAX_CELL {
+            let cut: String = value.chars().take(MAX_CELL - 1).collect();
+            format!("{cut}…")
+        } else {
+            value
+        }
+    };
+    let cells: Vec<(String, String)> = rows
+        .iter()
+        .map(|row| (cell(&row.left), cell(&row.right)))
+        .collect();
+    let field_width = rows.iter().map(|row| row.field.len()).max().unwrap_or(0);
+    let left_width = cells
+        .iter()
+        .map(|(left, _)| left.chars().count())
+        .chain([left.engine.len()])
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "  {:field_width$}  {:left_width$}  {}",
+        "",
+        console::style(left.engine).bold(),
+        console::style(right.engine).bold()
+    );
+    for (row, (left_cell, right_cell)) in rows.iter().zip(&cells) {
+        let marker = if row.same {
+            console::style("=").dim()
+        } else {
+            console::style("≠").red().bold()
+        };
+        let pad = left_width.saturating_sub(left_cell.chars().count());
+        println!(
+            "  {marker} {:field_width$}  {left_cell}{}  {right_cell}",
+            row.field,
+            " ".repeat(pad)
+        );
+    }
+    println!();
+    println!(
+        "  Timing: {} {}, {} {}",
+        left.engine,
+        millis(left.duration),
+        right.engine,
+        millis(right.duration)
+    );
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
+
+#[cfg(all(test, unix))]
+pub(super) mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    /// Stands in for the `wasmtime` CLI: exports `--env` names and runs the
+    /// fixture's `tool.wasm` (a shell script) with args on stdin.
+    const FAKE_WASMTIME: &str = r#"#!/bin/sh
+if [ "$1" = "--version" ]; then echo "wasmtime-cli 0.0.0 (fixture)"; exit 0; fi
+shift
+while [ "$1" = "--env" ]; do export "$2"; shift 2; done
+exec sh "$1"
+"#;
+
+    /// Fixture tool: echoes its input. `FIXTURE_DIVERGE` changes what it does.
+    const FIXTURE_TOOL: &str = r#"case "$FIXTURE_DIVERGE" in
+  raw) exec cat ;;
+  count) printf '{"success":true,"output":"%s"}' "$(wc -c | tr -d ' ')"; exit 0 ;;
+esac
+input=$(cat)
+case "$FIXTURE_DIVERGE" in
+  data) printf '{"data":{"n":2,"input":%s},"output":"echo","success":true}' "$input" ;;
+  reordered) printf '{"output":"echo","data":{"n":1,"input":%s},"success":true}' "$input" ;;
+  hang) exec sleep 5 ;;
+  fail) echo "boom" >&2; exit 3 ;;
+  *) printf '{"success":true,"output":"echo","data":{"input":%s,"n":1}}' "$input" ;;
+esac
+"#;
+
+    /// Fake `wasmtime` plus an echo tool; shared with the `skill bench` tests.
+    pub(in crate::skills) struct Fixture {
+        _dir: tempfile::TempDir,
+        pub wasmtime: PathBuf,
+        pub wasm: PathBuf,
+    }
+
+    pub(in crate::skills) fn fixture() -> Fixture {
+        let dir = tempfile::tempdir().unwrap();
+        let wasmtime = dir.path().join("wasmtime");
+        std::fs::write(&wasmtime, FAKE_WASMTIME).unwrap();
+        std::fs::set_permissions(&wasmtime, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let skill = dir.path().join("echo-skill");
+        std::fs::create_dir_all(&skill).unwrap();
+        let wasm = skill.join("tool.wasm");
+        std::fs::write(&wasm, FIXTURE_TOOL).unwrap();
+        Fixture {
+            _dir: dir,
+            wasmtime,
+            wasm,
+        }
+    }
+
+    pub(in crate::skills) fn engine(fixture: &Fixture, diverge: Option<&str>) -> WasmtimeCli {
+        WasmtimeCli {
+            program: fixture.wasmtime.clone(),
+            guest_env: diverge
+                .map(|value| vec![("FIXTURE_DIVERGE".to_string(), value.to_string())])
+                .unwrap_or_default(),
+            timeout: Duration::from_millis(500),
+        }
+    }
+
+    fn diverged_fields(rows: &[FieldComparison]) -> Vec<&'static str> {
+        rows.iter().map(|row| row.field).collect()
+    }
+
+    #[test]
+    fn identical_runs_ignore_field_order() {
+        let fixture = fixture();
+        let outcome = engine(&fixture, None)
+            .run(&fixture.wasm, br#"{"city":"Hanoi"}"#)
+            .unwrap();
+        assert_eq!(outcome.status, ExitBehavior::Completed);
+        assert_eq!(
+            outcome.envelope().unwrap().data,
+            Some(serde_json::json!({"n": 1, "input": {"city": "Hanoi"}}))
+        );
+
+        let divergences = run_comparison(
+            &engine(&fixture, None),
+            &engine(&fixture, Some("reordered")),
+            &fixture.wasm,
+            "{}",
+        )
+        .unwrap();
+        assert!(divergences.is_empty(), "{divergences:?}");
+    }
+
+    #[test]
+    fn injected_divergence_is_reported_per_field() {
+        let fixture = fixture();
+        let divergences = run_comparison(
+            &engine(&fixture, None),
+            &engine(&fixture, Some("data")),
+            &fixture.wasm,
+            "{}",
+        )
+        .unwrap();
+        assert_eq!(diverged_fields(&divergences), ["data"]);
+        assert_eq!(divergences[0].left, r#"{"input":{},"n":1}"#);
+
+        let divergences = run_comparison(
+            &engine(&fixture, None),
+            &engine(&fixture, Some("fail")),
+            &fixture.wasm,
+            "{}",
+        )
+        .unwrap();
+        assert_eq!(
+            diverged_fields(&divergences),
+            ["success", "output", "error", "data", "stdout", "exit"]
+        );
+        assert_eq!(divergences[5].right, "exit 3");
+    }
+
+    #[test]
+    fn timeouts_are_reported_as_limit_divergences() {
+        let fixture = fixture();
+        let divergences = run_comparison(
+            &engine(&fixture, None),
+            &engine(&fixture, Some("hang")),
+            &fixture.wasm,
+            "{}",
+        )
+        .unwrap();
+        let fields = diverged_fields(&divergences);
+        assert!(fields.contains(&"exit"), "{fields:?}");
+        assert!(fields.contains(&"limits"), "{fields:?}");
+        let limits = divergences
+            .iter()
+            .find(|row| row.field == "limits")
+            .unwrap();
+        assert_eq!(
+            (limits.left.as_str(), limits.right.as_str()),
+            ("none", "timeout")
+        );
+    }
+
+    #[test]
+    fn missing_engine_degrades_to_a_single_run() {
+        let fixture = fixture();
+        let missing = WasmtimeCli {
+            program: fixture.wasmtime.with_file_name("no-such-wasmtime"),
+            ..engine(&fixture, None)
+        };
+        assert!(missing.unavailable_reason().is_some());
+        let divergences = run_comparison(
+            &missing,
+            &engine(&fixture, Some("data")),
+            &fixture.wasm,
+            "{}",
+        )
+        .unwrap();
+        assert!(divergences.is_empty());
+    }
+
+    #[test]
+    fn stdin_input_is_streamed_and_capped() {
+        let fixture = fixture();
+        let mut document = String::from("{\"rows\":[");
+        while document.len() < 5_000_000 {
+            document.push_str("\"0123456789abcdef0123456789abcdef\",");
+        }
+        document.push_str("\"end\"]}");
+        serde_json::from_str::<serde_json::Value>(&document).unwrap();
+        let bytes = read_input(document.as_bytes()).unwrap();
+        let outcome = WasmtimeCli {
+            timeout: Duration::from_secs(10),
+            ..engine(&fixture, Some("count"))
+        }
+        .run(&fixture.wasm, &bytes)
+        .unwrap();
+        assert_eq!(outcome.status, ExitBehavior::Completed);
+        assert_eq!(
+            outcome.envelope().unwrap().output,
+            document.len().to_string()
+        );
+
+        // Raw mode: not JSON, passed through byte for byte.
+        let payload = b"line one\nnot {json\x01\xff\n";
+        let outcome = engine(&fixture, Some("raw"))
+            .run_streaming(&fixture.wasm, &payload[..])
+            .unwrap();
+        assert_eq!(outcome.status, ExitBehavior::Completed);
+        assert_eq!(outcome.stdout, String::from_utf8_lossy(payload));
+
+        let oversized = vec![b'x'; MAX_INPUT_BYTES + 1];
+        let err = read_input(&oversized[..]).unwrap_err();
+        assert!(err.to_string().contains("MiB limit"), "{err:#}");
+        let err = WasmtimeCli {
+            timeout: Duration::from_secs(10),
+            ..engine(&fixture, Some("count"))
+        }
+        .run_streaming(&fixture.wasm, &oversized[..])
+        .unwrap_err();
+        assert!(err.to_string().contains("MiB limit"), "{err:#}");
+    }
+}
@@ -0,0 +1,265 @@
+//! Conditional-GET cache for registry package index fetches.
+//!
+//! The last successful body of each index URL is kept under
+//! `<workspace>/state/registry-index/` together with its `ETag` and
+//! `Last-Modified` headers. The next fetch of that URL sends them back as
+//! `If-None-Match` / `If-Modified-Since`, and a `304 Not Modified` reuses the
+//! cached body without downloading it again. A broken or missing cache entry
+//! only costs a full download; it never fails the fetch.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Validators from the cached response, sent with the next request.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(super) struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+
+    /// `If-None-Match` / `If-Modified-Since` request headers.
+    pub fn request_headers(&self) -> Vec<String> {
+        let mut headers = Vec::new();
+        if let Some(etag) = &self.etag {
+            headers.push(format!("If-None-Match: {etag}"));
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.push(format!("If-Modified-Since: {last_modified}"));
+        }
+        headers
+    }
+}
+
+/// What the registry answered to a (possibly conditional) GET.
+#[derive(Debug)]
+pub(super) enum IndexResponse {
+    NotModified,
+    Body {
+        body: Vec<u8>,
+        validators: Validators,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    #[serde(default)]
+    etag: Option<String>,
+    #[serde(default)]
+    last_modified: Option<String>,
+    body: String,
+}
+
+/// Registry index bodies cached by URL.
+#[derive(Debug, Clone)]
+pub(super) struct IndexCache {
+    dir: PathBuf,
+}
+
+impl IndexCache {
+    pub fn for_workspace(workspace_dir: &Path) -> Self {
+        Self::new(workspace_dir.join("state").join("registry-index"))
+    }
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        let digest = Sha256::digest(url.as_bytes());
+        self.dir
+            .join(format!("{}.json", hex::encode(&digest[..16])))
+    }
+
+    fn load(&self, url: &str) -> Option<CacheEntry> {
+        let raw = std::fs::read(self.entry_path(url)).ok()?;
+        serde_json::from_slice::<CacheEntry>(&raw)
+            .ok()
+            .filter(|entry| entry.url == url)
+    }
+
+    /// Write the entry through a temp file and rename, so concurrent fetches
+    /// of the same URL never leave a torn entry behind.
+    fn store(&self, url: &str, body: &[u8], validators: &Validators) -> Result<()> {
+        let body = std::str::from_utf8(body).context("index body is not UTF-8")?;
+        let entry = CacheEntry {
+            url: url.to_string(),
+            etag: validators.etag.clone(),
+            last_modified: validators.last_modified.clone(),
+            body: body.to_string(),
+        };
+        std::fs::create_dir_all(&self.dir)
+            .with_context(|| format!("failed to create {}", self.dir.display()))?;
+        let mut tmp = tempfile::NamedTempFile::new_in(&self.dir)?;
+        serde_json::to_writer(&mut tmp, &entry)?;
+        tmp.persist(self.entry_path(url))
+            .map_err(|err| err.error)
+            .context("failed to write registry index cache entry")?;
+        Ok(())
+    }
+
+    /// Fetch `url` through `get`, which receives the cached validators (empty
+    /// when nothing usable is cached). A 304 returns the cached body as is; a
+    /// fresh body carrying an `ETag` or `Last-Modified` replaces the entry.
+    pub fn fetch(
+        &self,
+        url: &str,
+        get: impl FnOnce(&Validators) -> Result<IndexResponse>,
+    ) -> Result<Vec<u8>> {
+        let cached = self.load(url);
+        let validators = cached
+            .as_ref()
+            .map(|entry| Validators {
+                etag: entry.etag.clone(),
+                last_modified: entry.last_modified.clone(),
+            })
+            .unwrap_or_default();
+
+        match get(&validators)? {
+            IndexResponse::NotModified => match cached {
+                Some(entry) if !validators.is_empty() => Ok(entry.body.into_bytes()),
+                _ => bail!("registry answered 304 Not Modified for {url} without a cached index"),
+            },
+            IndexResponse::Body { body, validators } => {
+                if !validators.is_empty() {
+                    if let Err(err) = self.store(url, &body, &validators) {
+                        tracing::warn!("failed to cache registry index {url}: {err:#}");
+                    }
+                }
+                Ok(body)
+            }
+        }
+    }
+}
+
+/// `ETag` and `Last-Modified` from a `curl --dump-header` file. With
+/// `--location` the file holds every hop; only the final response counts.
+pub(super) fn parse_validators(dumped_headers: &str) -> Validators {
+    let last_response = dumped_headers
+        .split("\r\n\r\n")
+        .flat_map(|block| block.split("\n\n"))
+        .filter(|block| block.trim_start().starts_with("HTTP/"))
+        .next_back()
+        .unwrap_or("");
+    let mut validators = Validators::default();
+    for line in last_response.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            continue;
+        }
+        if name.trim().eq_ignore_ascii_case("etag") {
+            validators.etag = Some(value.to_string());
+        } else if name.trim().eq_ignore_ascii_case("last-modified") {
+            validators.last_modified = Some(value.to_string());
+        }
+    }
+    validators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = r#"{"version":"1.2.0","tools":[]}"#;
+
+    fn fresh(body: &str, etag: &str) -> IndexResponse {
+        IndexResponse::Body {
+            body: body.as_bytes().to_vec(),
+            validators: Validators {
+                etag: Some(etag.to_string()),
+                last_modified: Some("Tue, 13 Oct 2026 08:00:00 GMT".to_string()),
+            },
+        }
+    }
+
+    #[test]
+    fn not_modified_reuses_the_cached_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IndexCache::new(dir.path().join("registry-index"));
+        let url = "https://registry.example/v1/packages/acme/tool";
+
+        let first = cache
+            .fetch(url, |validators| {
+                assert_eq!(validators, &Validators::default());
+                Ok(fresh(INDEX, "\"v1\""))
+            })
+            .unwrap();
+        assert_eq!(first, INDEX.as_bytes());
+
+        // The 304 carries no body to parse; the cached one comes back
+        // untouched and the entry keeps its validators.
+        let second = cache
+            .fetch(url, |validators| {
+                assert_eq!(validators.etag.as_deref(), Some("\"v1\""));
+                assert_eq!(
+                    validators.request_headers(),
+                    [
+                        "If-None-Match: \"v1\"",
+                        "If-Modified-Since: Tue, 13 Oct 2026 08:00:00 GMT"
+                    ]
+                );
+                Ok(IndexResponse::NotModified)
+            })
+            .unwrap();
+        assert_eq!(second, INDEX.as_bytes());
+        assert_eq!(cache.load(url).unwrap().etag.as_deref(), Some("\"v1\""));
+
+        let updated = r#"{"version":"1.3.0","tools":[]}"#;
+        let third = cache.fetch(url, |_| Ok(fresh(updated, "\"v2\""))).unwrap();
+        assert_eq!(third, updated.as_bytes());
+        assert_eq!(cache.load(url).unwrap().etag.as_deref(), Some("\"v2\""));
+    }
+
+    #[test]
+    fn not_modified_without_a_cache_entry_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IndexCache::new(dir.path().to_path_buf());
+        let err = cache
+            .fetch("https://registry.example/x", |_| {
+                Ok(IndexResponse::NotModified)
+            })
+            .unwrap_err();
+        assert!(err.to_string().contains("304"), "{err:#}");
+    }
+
+    #[test]
+    fn corrupt_entry_falls_back_to_an_unconditional_fetch() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = IndexCache::new(dir.path().to_path_buf());
+        let url = "https://registry.example/v1/packages/acme/tool";
+        std::fs::write(cache.entry_path(url), "{not json").unwrap();
+
+        let body = cache
+            .fetch(url, |validators| {
+                assert!(validators.is_empty());
+                Ok(fresh(INDEX, "\"v1\""))
+            })
+            .unwrap();
+        assert_eq!(body, INDEX.as_bytes());
+        assert!(cache.load(url).is_some());
+    }
+
+    #[test]
+    fn validators_come_from_the_final_redirect_hop() {
+        let dumped = "HTTP/1.1 302 Found\r\nLocation: https://cdn.example/x\r\nETag: \"hop\"\r\n\r\n\
+                      HTTP/2 200\r\netag: W/\"final\"\r\nlast-modified: Tue, 13 Oct 2026 08:00:00 GMT\r\n\r\n";
+        assert_eq!(
+            parse_validators(dumped),
+            Validators {
+                etag: Some("W/\"final\"".into()),
+                last_modified: Some("Tue, 13 Oct 2026 08:00:00 GMT".into()),
+            }
+        );
+        assert_eq!(parse_validators(""), Validators::default());
+    }
+}
@@ -0,0 +1,130 @@
+//! Stable codes for `skill install` failures.
+//!
+//! Install failures stay `anyhow` errors with their human messages; the step
+//! that fails puts a [`SkillInstallError`] in the chain, so a caller can
+//! branch on [`SkillInstallError::code`] instead of matching message text.
+//! `skill install --json` reports the code next to the message. A failure
+//! no step classified is reported as [`OTHER_CODE`]. Other skill commands
+//! raise the same errors for the same failures (a failed audit, a missing
+//! skill), and the CLI maps them to process exit codes.
+
+use serde::Serialize;
+
+/// Code of a failure that carries no [`SkillInstallError`].
+pub const OTHER_CODE: &str = "install_failed";
+
+/// Why an install failed. Each variant holds the human message.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SkillInstallError {
+    /// The local path, package, or download does not exist (HTTP 404/410).
+    #[error("{0}")]
+    SourceNotFound(String),
+    /// The source is malformed or names an invalid skill.
+    #[error("{0}")]
+    InvalidSource(String),
+    /// A download or `git clone` failed.
+    #[error("{0}")]
+    NetworkError(String),
+    /// The zip or registry package is malformed or unsafe to unpack.
+    #[error("{0}")]
+    InvalidArchive(String),
+    /// The security audit found issues at or above the block threshold.
+    #[error("{0}")]
+    AuditFailed(String),
+    /// The skill requests capabilities and consent is required.
+    #[error("{0}")]
+    ConsentRequired(String),
+    /// A skill of the same name is already installed.
+    #[error("{0}")]
+    AlreadyExists(String),
+    /// The installed files do not match the lockfile checksum.
+    #[error("{0}")]
+    ChecksumMismatch(String),
+}
+
+impl SkillInstallError {
+    /// Stable snake_case code, e.g. `already_exists`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SourceNotFound(_) => "source_not_found",
+            Self::InvalidSource(_) => "invalid_source",
+            Self::NetworkError(_) => "network_error",
+            Self::InvalidArchive(_) => "invalid_archive",
+            Self::AuditFailed(_) => "audit_failed",
+            Self::ConsentRequired(_) => "consent_required",
+            Self::AlreadyExists(_) => "already_exists",
+            Self::ChecksumMismatch(_) => "checksum_mismatch",
+        }
+    }
+
+    /// The innermost install error in `err`'s chain: the step that failed,
+    /// not a step that wrapped it.
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.chain().rev().find_map(|cause| cause.downcast_ref())
+    }
+
+    /// [`Self::code`] of [`Self::find`], or [`OTHER_CODE`].
+    pub fn code_of(err: &anyhow::Error) -> &'static str {
+        Self::find(err).map_or(OTHER_CODE, Self::code)
+    }
+}
+
+/// One source's line in `skill install --json`.
+#[derive(Debug, Serialize)]
+pub(super) struct InstallOutcome {
+    pub source: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl InstallOutcome {
+    pub fn new(source: &str, result: &anyhow::Result<std::path::PathBuf>) -> Self {
+        match result {
+            Ok(dir) => Self {
+                source: source.to_string(),
+                ok: true,
+                path: Some(dir.display().to_string()),
+                code: None,
+                error: None,
+            },
+            Err(err) => Self {
+                source: source.to_string(),
+                ok: false,
+                path: None,
+                code: Some(SkillInstallError::code_of(err)),
+                error: Some(format!("{err:#}")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn the_innermost_install_error_decides_the_code() {
+        let err = anyhow::Error::from(SkillInstallError::SourceNotFound(
+            "HTTP 404 from https://example.com/x.zip".into(),
+        ))
+        .context(SkillInstallError::NetworkError(
+            "failed to fetch zip".into(),
+        ))
+        .context("failed to install zip URL skill source: x");
+        assert_eq!(SkillInstallError::code_of(&err), "source_not_found");
+        assert_eq!(
+            format!("{err:#}"),
+            "failed to install zip URL skill source: x: failed to fetch zip: \
+             HTTP 404 from https://example.com/x.zip"
+        );
+
+        let plain: anyhow::Result<()> = Err(anyhow::anyhow!("disk full")).context("copying");
+        assert_eq!(SkillInstallError::code_of(&plain.unwrap_err()), OTHER_CODE);
+    }
+}
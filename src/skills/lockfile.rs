@@ -0,0 +1,495 @@
+//! `skills.lock`: a reproducible record of the installed skill set.
+//!
+//! Every `skill install` and `skill remove` rewrites `skills/skills.lock` from
+//! the installed skills' `_install.json` records: source kind, source, the
+//! pinned commit (git) or version (registry), and a SHA-256 checksum over the
+//! skill's files. `zeroclaw skill install --from-lockfile [path]` installs
+//! each entry at its pin and verifies the checksum, reporting every skill on
+//! its own line and continuing past individual failures.
+
+use super::audit::QUARANTINE_DIR;
+use super::{
+    install_from_source_pinned, read_install_record, skill_id, validate_skill_name,
+    InstallSourceKind, SkillInstallError, SkillSource, INSTALL_RECORD_FILE,
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Lockfile name inside the skills directory.
+pub(super) const LOCKFILE_NAME: &str = "skills.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str =
+    "# Generated by `zeroclaw skill install` / `zeroclaw skill remove`.\n\
+# Reproduce this skill set with `zeroclaw skill install --from-lockfile`.\n\n";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct SkillsLock {
+    pub version: u32,
+    #[serde(default, rename = "skill")]
+    pub skills: Vec<LockedSkill>,
+}
+
+/// One installed skill, pinned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(super) struct LockedSkill {
+    /// Stable skill id ([`skill_id`]), the key entries are matched on.
+    /// Lockfiles written before ids existed get it from `name` on read.
+    #[serde(default)]
+    pub id: String,
+    /// Install directory name.
+    pub name: String,
+    pub kind: InstallSourceKind,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// `sha256:<hex>` over the skill's files; see [`checksum_skill_dir`].
+    pub checksum: String,
+}
+
+impl LockedSkill {
+    fn is_remote(&self) -> bool {
+        match self.kind {
+            InstallSourceKind::Git
+            | InstallSourceKind::Registry
+            | InstallSourceKind::Clawhub
+            | InstallSourceKind::Custom => true,
+            InstallSourceKind::Zip => super::is_zip_url_source(&self.source),
+            InstallSourceKind::Local => false,
+        }
+    }
+
+    /// Source string that installs exactly the locked version.
+    fn pinned_source(&self) -> String {
+        match (&self.kind, &self.version) {
+            (InstallSourceKind::Registry, Some(version)) => {
+                let base = self
+                    .source
+                    .split_once('@')
+                    .map_or(self.source.as_str(), |(base, _)| base);
+                format!("{base}@{version}")
+            }
+            _ => self.source.clone(),
+        }
+    }
+}
+
+/// Path of the workspace lockfile.
+pub(super) fn lockfile_path(skills_path: &Path) -> PathBuf {
+    skills_path.join(LOCKFILE_NAME)
+}
+
+/// Checksum of a skill directory: SHA-256 over every regular file's relative
+/// path and contents, in path order. `_install.json` (which carries the
+/// install time), `.quarantine/` and a kept `.git` (which differs between
+/// clones) are excluded.
+pub(super) fn checksum_skill_dir(skill_dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(skill_dir, skill_dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let bytes = std::fs::read(skill_dir.join(&relative))
+            .with_context(|| format!("failed to read {}", skill_dir.join(&relative).display()))?;
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update((bytes.len() as u64).to_le_bytes());
+        hasher.update(&bytes);
+    }
+    Ok(format!("sha256:{:x}", hasher.finalize()))
+}
+
+/// Relative paths (with `/` separators) of the regular files under `dir`.
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<String>) -> Result<()> {
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("failed to read {}", dir.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if relative == INSTALL_RECORD_FILE || relative == QUARANTINE_DIR || relative == ".git" {
+            continue;
+        }
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if file_type.is_file() {
+            out.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Lock entries for every installed skill that has an install record.
+pub(super) fn collect(skills_path: &Path) -> Result<SkillsLock> {
+    let mut skills = Vec::new();
+    if skills_path.is_dir() {
+        let mut dirs: Vec<PathBuf> = std::fs::read_dir(skills_path)
+            .with_context(|| format!("failed to read {}", skills_path.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        dirs.sort();
+        for dir in dirs {
+            let Some(record) = read_install_record(&dir)? else {
+                continue;
+            };
+            let name = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            skills.push(LockedSkill {
+                id: skill_id(&name, SkillSource::Workspace),
+                name,
+                kind: record.kind,
+                source: record.source,
+                commit: record.commit,
+                version: record.version,
+                checksum: checksum_skill_dir(&dir)?,
+            });
+        }
+    }
+    Ok(SkillsLock {
+        version: LOCKFILE_VERSION,
+        skills,
+    })
+}
+
+/// Rewrite the workspace lockfile from the installed skills. Nothing is
+/// written for a workspace that has neither installed skills nor a lockfile.
+pub(super) fn refresh(skills_path: &Path) -> Result<()> {
+    let lock = collect(skills_path)?;
+    let path = lockfile_path(skills_path);
+    if lock.skills.is_empty() && !path.exists() {
+        return Ok(());
+    }
+    let body = toml::to_string(&lock).context("failed to serialize skills.lock")?;
+    std::fs::write(&path, format!("{LOCKFILE_HEADER}{body}"))
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// [`refresh`], logging instead of failing: the install or removal itself
+/// already succeeded.
+pub(super) fn refresh_or_warn(skills_path: &Path) {
+    if let Err(err) = refresh(skills_path) {
+        tracing::warn!("failed to update {LOCKFILE_NAME}: {err:#}");
+    }
+}
+
+pub(super) fn read(path: &Path) -> Result<SkillsLock> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read lockfile {}", path.display()))?;
+    let mut lock: SkillsLock =
+        toml::from_str(&raw).with_context(|| format!("invalid lockfile {}", path.display()))?;
+    if lock.version != LOCKFILE_VERSION {
+        bail!(
+            "unsupported lockfile version {} in {} (expected {LOCKFILE_VERSION})",
+            lock.version,
+            path.display()
+        );
+    }
+    let mut seen = std::collections::HashSet::new();
+    for entry in &mut lock.skills {
+        if entry.id.is_empty() {
+            entry.id = skill_id(&entry.name, SkillSource::Workspace);
+        }
+        if !seen.insert(entry.id.clone()) {
+            bail!(
+                "skill id {} is listed twice in {}",
+                entry.id,
+                path.display()
+            );
+        }
+    }
+    Ok(lock)
+}
+
+/// Install every skill listed in `lock_path`. Each skill is reported on its
+/// own line; failures don't stop the rest. With `offline`, remote sources
+/// fail immediately instead of touching the network.
+pub(super) fn install_from_lockfile(
+    lock_path: &Path,
+    skills_path: &Path,
+    config: &crate::config::Config,
+    offline: bool,
+) -> Result<()> {
+    let lock = read(lock_path)?;
+    println!(
+        "Installing {} skill(s) from {}",
+        lock.skills.len(),
+        lock_path.display()
+    );
+
+    let mut failed = Vec::new();
+    for entry in &lock.skills {
+        match install_locked(entry, skills_path, config, offline) {
+            Ok(status) => println!(
+                "  {} {}: {status}",
+                console::style("✓").green().bold(),
+                entry.name
+            ),
+            Err(err) => {
+                println!(
+                    "  {} {}: {err:#}",
+                    console::style("✗").red().bold(),
+                    entry.name
+                );
+                failed.push(entry.name.clone());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} skill(s) failed to install from {}: {}",
+            failed.len(),
+            lock.skills.len(),
+            lock_path.display(),
+            failed.join(", ")
+        );
+    }
+    refresh_or_warn(skills_path);
+    println!("  All {} skill(s) match the lockfile.", lock.skills.len());
+    Ok(())
+}
+
+/// Install one locked skill and verify it; returns a short status.
+fn install_locked(
+    entry: &LockedSkill,
+    skills_path: &Path,
+    config: &crate::config::Config,
+    offline: bool,
+) -> Result<&'static str> {
+    validate_skill_name(&entry.name)?;
+    let dest = skills_path.join(&entry.name);
+    if dest.exists() {
+        let actual = checksum_skill_dir(&dest)?;
+        if actual == entry.checksum {
+            return Ok("already installed");
+        }
+        bail!(SkillInstallError::ChecksumMismatch(format!(
+            "already installed with different contents (checksum {actual}, lockfile {}); remove it first",
+            entry.checksum
+        )));
+    }
+    if offline && entry.is_remote() {
+        bail!(SkillInstallError::NetworkError(format!(
+            "offline: {} source {} needs the network",
+            entry.kind.as_str(),
+            entry.source
+        )));
+    }
+
+    let installed = install_from_source_pinned(
+        &entry.pinned_source(),
+        skills_path,
+        config,
+        entry.commit.as_deref(),
+    )?;
+    let actual = checksum_skill_dir(&installed)?;
+    if actual != entry.checksum {
+        let _ = std::fs::remove_dir_all(&installed);
+        bail!(SkillInstallError::ChecksumMismatch(format!(
+            "checksum mismatch (got {actual}, lockfile {}); installed copy removed",
+            entry.checksum
+        )));
+    }
+    if installed != dest {
+        let _ = std::fs::remove_dir_all(&installed);
+        bail!(
+            "source installed as '{}', not '{}'; installed copy removed",
+            installed
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default(),
+            entry.name
+        );
+    }
+    Ok("installed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write_skill(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.join("docs")).unwrap();
+        fs::write(path.join("SKILL.md"), format!("# {name}\n{body}\n")).unwrap();
+        fs::write(path.join("docs/usage.md"), "Usage notes.\n").unwrap();
+        path
+    }
+
+    #[test]
+    fn checksum_ignores_install_record_and_tracks_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill = write_skill(dir.path(), "notes", "Take notes.");
+        let before = checksum_skill_dir(&skill).unwrap();
+        assert!(before.starts_with("sha256:"));
+
+        fs::write(skill.join(INSTALL_RECORD_FILE), "{}").unwrap();
+        assert_eq!(checksum_skill_dir(&skill).unwrap(), before);
+
+        fs::write(skill.join("docs/usage.md"), "Changed.\n").unwrap();
+        assert_ne!(checksum_skill_dir(&skill).unwrap(), before);
+    }
+
+    #[test]
+    fn installing_from_lockfile_recreates_recorded_skills() {
+        let sources = tempfile::tempdir().unwrap();
+        let first = write_skill(sources.path(), "notes", "Take notes.");
+        let second = write_skill(sources.path(), "journal", "Keep a journal.");
+
+        let original = tempfile::tempdir().unwrap();
+        let skills_path = original.path().join("skills");
+        fs::create_dir_all(&skills_path).unwrap();
+        let config = crate::config::Config::default();
+        for source in [&first, &second] {
+            install_from_source_pinned(source.to_str().unwrap(), &skills_path, &config, None)
+                .unwrap();
+        }
+        refresh(&skills_path).unwrap();
+        let lock = read(&lockfile_path(&skills_path)).unwrap();
+        let names: Vec<&str> = lock.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["journal", "notes"]);
+        let ids: Vec<&str> = lock.skills.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["workspace:journal", "workspace:notes"]);
+        assert!(lock
+            .skills
+            .iter()
+            .all(|s| s.kind == InstallSourceKind::Local));
+
+        // A fresh machine: only the lockfile is copied over.
+        let fresh = tempfile::tempdir().unwrap();
+        let fresh_skills = fresh.path().join("skills");
+        fs::create_dir_all(&fresh_skills).unwrap();
+        let lock_copy = fresh.path().join("skills.lock");
+        fs::copy(lockfile_path(&skills_path), &lock_copy).unwrap();
+
+        install_from_lockfile(&lock_copy, &fresh_skills, &config, true).unwrap();
+        for entry in &lock.skills {
+            assert_eq!(
+                checksum_skill_dir(&fresh_skills.join(&entry.name)).unwrap(),
+                entry.checksum
+            );
+        }
+        // Re-running is a no-op for skills that already match.
+        install_from_lockfile(&lock_copy, &fresh_skills, &config, true).unwrap();
+        assert_eq!(collect(&fresh_skills).unwrap().skills.len(), 2);
+    }
+
+    #[test]
+    fn lockfile_install_continues_past_failures() {
+        let sources = tempfile::tempdir().unwrap();
+        let good = write_skill(sources.path(), "notes", "Take notes.");
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        fs::create_dir_all(&skills_path).unwrap();
+
+        let lock = SkillsLock {
+            version: LOCKFILE_VERSION,
+            skills: vec![
+                LockedSkill {
+                    id: "workspace:remote".into(),
+                    name: "remote".into(),
+                    kind: InstallSourceKind::Git,
+                    source: "https://example.invalid/remote.git".into(),
+                    commit: Some("0123456789abcdef".into()),
+                    version: None,
+                    checksum: "sha256:00".into(),
+                },
+                LockedSkill {
+                    id: "workspace:notes".into(),
+                    name: "notes".into(),
+                    kind: InstallSourceKind::Local,
+                    source: good.to_string_lossy().into_owned(),
+                    commit: None,
+                    version: None,
+                    checksum: checksum_skill_dir(&good).unwrap(),
+                },
+                LockedSkill {
+                    id: "workspace:tampered".into(),
+                    name: "tampered".into(),
+                    kind: InstallSourceKind::Local,
+                    source: write_skill(sources.path(), "tampered", "Edited since.")
+                        .to_string_lossy()
+                        .into_owned(),
+                    commit: None,
+                    version: None,
+                    checksum: "sha256:deadbeef".into(),
+                },
+            ],
+        };
+        let lock_path = workspace.path().join("skills.lock");
+        fs::write(&lock_path, toml::to_string(&lock).unwrap()).unwrap();
+
+        let err = install_from_lockfile(
+            &lock_path,
+            &skills_path,
+            &crate::config::Config::default(),
+            true,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("2 of 3"), "{err}");
+        assert!(err.contains("remote, tampered"), "{err}");
+        assert!(skills_path.join("notes").is_dir());
+        assert!(!skills_path.join("remote").exists());
+        assert!(
+            !skills_path.join("tampered").exists(),
+            "checksum mismatch must roll back"
+        );
+    }
+
+    #[test]
+    fn registry_entries_pin_their_version() {
+        let entry = LockedSkill {
+            id: "workspace:weather".into(),
+            name: "weather".into(),
+            kind: InstallSourceKind::Registry,
+            source: "acme/weather@1.0.0".into(),
+            commit: None,
+            version: Some("1.2.0".into()),
+            checksum: String::new(),
+        };
+        assert_eq!(entry.pinned_source(), "acme/weather@1.2.0");
+        assert!(entry.is_remote());
+    }
+
+    #[test]
+    fn entries_are_keyed_by_skill_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("skills.lock");
+        // Written before ids existed: the id comes from the install name.
+        fs::write(
+            &path,
+            "version = 1\n\n[[skill]]\nname = \"Weather-Lookup\"\nkind = \"local\"\n\
+             source = \"/tmp/weather\"\nchecksum = \"sha256:00\"\n",
+        )
+        .unwrap();
+        let lock = read(&path).unwrap();
+        assert_eq!(lock.skills[0].id, "workspace:weather_lookup");
+
+        let mut duplicated = lock.clone();
+        let mut renamed = lock.skills[0].clone();
+        renamed.name = "weather_lookup".into();
+        duplicated.skills.push(renamed);
+        fs::write(&path, toml::to_string(&duplicated).unwrap()).unwrap();
+        let err = read(&path).unwrap_err().to_string();
+        assert!(err.contains("workspace:weather_lookup"), "{err}");
+    }
+}
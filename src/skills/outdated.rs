@@ -0,0 +1,538 @@
+//! `zeroclaw skill outdated`: compare installed skills against their upstream.
+//!
+//! Each installed skill is classified from its `_install.json` record:
+//! git installs compare the recorded commit with `git ls-remote <url> HEAD`
+//! (or the ref of a GitHub `tree/<ref>/...` install),
+//! registry installs compare the recorded version with the registry's latest
+//! package index (a conditional GET against the cached copy, see
+//! [`super::index_cache`]), and everything else is reported as unknown. Remote checks
+//! run on a small worker pool with a per-remote timeout; a failing remote only
+//! marks its own row as an error. Registry installs also report when the
+//! registry has deprecated the installed version, whatever the local
+//! manifest says.
+
+use super::index_cache::IndexCache;
+use super::sources::GitHubTree;
+use super::{
+    fetch_registry_index, git_command, read_install_record, skills_dir, InstallRecord,
+    InstallSourceKind, RegistryPackageIndex, SkillDeprecation, GIT_PROXY_SERVICE_KEY,
+};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Maximum number of remotes queried at once.
+const MAX_CONCURRENT_CHECKS: usize = 8;
+/// Per-remote budget for `git ls-remote` and registry lookups.
+const REMOTE_TIMEOUT: Duration = Duration::from_secs(15);
+const SHORT_SHA_LEN: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum OutdatedStatus {
+    UpToDate,
+    Outdated,
+    Unknown,
+    Error,
+}
+
+impl OutdatedStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::UpToDate => "up-to-date",
+            Self::Outdated => "outdated",
+            Self::Unknown => "unknown",
+            Self::Error => "error",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct OutdatedRow {
+    name: String,
+    /// Install source kind, or `None` when the skill has no install record.
+    kind: Option<&'static str>,
+    source: Option<String>,
+    /// Installed commit SHA (git) or version (registry).
+    installed: Option<String>,
+    /// Upstream commit SHA (git) or latest version (registry).
+    latest: Option<String>,
+    status: OutdatedStatus,
+    /// Seconds since the installed commit (or the install itself, if the
+    /// commit time was not recorded).
+    age_secs: Option<u64>,
+    /// The registry's deprecation notice for the installed version.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deprecated: Option<SkillDeprecation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+struct Job {
+    name: String,
+    path: PathBuf,
+}
+
+/// Where registry installs are checked for updates.
+struct Registry<'a> {
+    url: &'a str,
+    index_cache: &'a IndexCache,
+    /// `[skills] download_max_redirects`.
+    max_redirects: u32,
+}
+
+impl Registry<'_> {
+    /// The package index at `path` (`namespace/name[/version]`).
+    fn fetch_index(&self, path: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/v1/packages/{path}", self.url.trim_end_matches('/'));
+        fetch_registry_index(
+            &url,
+            self.index_cache,
+            REMOTE_TIMEOUT.as_secs(),
+            self.max_redirects,
+        )
+    }
+}
+
+pub(super) fn handle_outdated(config: &crate::config::Config, json: bool) -> Result<()> {
+    let skills_path = skills_dir(&config.workspace_dir);
+    let jobs = collect_jobs(&skills_path)?;
+    let registry_url = config.wasm.registry_url.clone();
+    let index_cache = IndexCache::for_workspace(&config.workspace_dir);
+    let registry = Registry {
+        url: &registry_url,
+        index_cache: &index_cache,
+        max_redirects: config.skills.download_max_redirects,
+    };
+    let rows = run_bounded(&jobs, MAX_CONCURRENT_CHECKS, |job| {
+        check_skill(job, &registry)
+    });
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        print_table(&rows);
+    }
+    Ok(())
+}
+
+fn collect_jobs(skills_path: &Path) -> Result<Vec<Job>> {
+    if !skills_path.exists() {
+        return Ok(Vec::new());
+    }
+    let mut jobs = Vec::new();
+    for entry in std::fs::read_dir(skills_path)
+        .with_context(|| format!("failed to read {}", skills_path.display()))?
+    {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        jobs.push(Job {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            path,
+        });
+    }
+    jobs.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(jobs)
+}
+
+/// Run `check` over `jobs` on at most `limit` threads, preserving input order.
+fn run_bounded<T, R, F>(jobs: &[T], limit: usize, check: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<R>>> = Mutex::new((0..jobs.len()).map(|_| None).collect());
+    let workers = limit.clamp(1, jobs.len().max(1));
+
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else {
+                    break;
+                };
+                let result = check(job);
+                results.lock().unwrap_or_else(|e| e.into_inner())[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn check_skill(job: &Job, registry: &Registry<'_>) -> OutdatedRow {
+    let mut row = OutdatedRow {
+        name: job.name.clone(),
+        kind: None,
+        source: None,
+        installed: None,
+        latest: None,
+        status: OutdatedStatus::Unknown,
+        age_secs: None,
+        deprecated: None,
+        error: None,
+    };
+
+    let record = match read_install_record(&job.path) {
+        Ok(Some(record)) => record,
+        Ok(None) => return row,
+        Err(err) => {
+            row.status = OutdatedStatus::Error;
+            row.error = Some(format!("{err:#}"));
+            return row;
+        }
+    };
+    row.kind = Some(record.kind.as_str());
+    row.source = Some(record.source.clone());
+    row.age_secs = record_age_secs(&record);
+
+    let outcome = match record.kind {
+        InstallSourceKind::Git => {
+            row.installed = record.commit.clone();
+            check_git(&record)
+        }
+        InstallSourceKind::Registry => {
+            row.installed = record.version.clone();
+            row.deprecated = registry_deprecation(&record, registry);
+            check_registry(&record, registry)
+        }
+        InstallSourceKind::Clawhub
+        | InstallSourceKind::Zip
+        | InstallSourceKind::Local
+        | InstallSourceKind::Custom => {
+            return row;
+        }
+    };
+
+    match outcome {
+        Ok(Some(latest)) => {
+            row.status = match row.installed.as_deref() {
+                Some(installed) if installed == latest => OutdatedStatus::UpToDate,
+                Some(_) => OutdatedStatus::Outdated,
+                None => OutdatedStatus::Unknown,
+            };
+            row.latest = Some(latest);
+        }
+        Ok(None) => {}
+        Err(err) => {
+            row.status = OutdatedStatus::Error;
+            row.error = Some(format!("{err:#}"));
+        }
+    }
+    row
+}
+
+fn record_age_secs(record: &InstallRecord) -> Option<u64> {
+    let now = chrono::Utc::now().timestamp();
+    let since = record.commit_time.or_else(|| {
+        chrono::DateTime::parse_from_rfc3339(&record.installed_at)
+            .ok()
+            .map(|t| t.timestamp())
+    })?;
+    u64::try_from(now.saturating_sub(since)).ok()
+}
+
+/// Upstream HEAD commit for a git install, or `None` if nothing was recorded
+/// to compare against. GitHub tree installs follow their branch or tag; one
+/// made at a fixed commit has nothing to compare against.
+fn check_git(record: &InstallRecord) -> Result<Option<String>> {
+    if record.commit.is_none() {
+        return Ok(None);
+    }
+    if record.source.starts_with('-') {
+        anyhow::bail!("refusing to query suspicious git source: {}", record.source);
+    }
+    let (remote, patterns, wanted) = match GitHubTree::parse(&record.source)? {
+        Some(tree) if tree.at_commit() => return Ok(None),
+        Some(tree) => {
+            let branch = format!("refs/heads/{}", tree.git_ref);
+            let tag = format!("refs/tags/{}", tree.git_ref);
+            // An annotated tag is listed with the commit it points at as
+            // `<tag>^{}`; prefer that over the tag object.
+            let wanted = vec![branch.clone(), format!("{tag}^{{}}"), tag.clone()];
+            (tree.repo, vec![branch, tag], wanted)
+        }
+        None => (
+            record.source.clone(),
+            vec!["HEAD".to_string()],
+            vec!["HEAD".to_string()],
+        ),
+    };
+    let mut cmd = git_command(GIT_PROXY_SERVICE_KEY);
+    cmd.arg("ls-remote")
+        .arg(&remote)
+        .args(&patterns)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    let stdout = run_with_timeout(cmd, REMOTE_TIMEOUT)
+        .with_context(|| format!("git ls-remote failed for {remote}"))?;
+    parse_ls_remote(&stdout, &wanted)
+        .map(Some)
+        .with_context(|| format!("remote {remote} did not report a commit for {}", wanted[0]))
+}
+
+/// Latest registry version for a `namespace/name[@version]` install.
+fn check_registry(record: &InstallRecord, registry: &Registry<'_>) -> Result<Option<String>> {
+    if record.version.is_none() {
+        return Ok(None);
+    }
+    let bytes = registry.fetch_index(registry_package(record))?;
+    let index: RegistryPackageIndex =
+        serde_json::from_slice(&bytes).context("registry returned invalid package index JSON")?;
+    Ok(Some(index.version))
+}
+
+/// The registry's deprecation of the installed version of a registry
+/// install. A failed lookup counts as not deprecated; [`check_registry`]
+/// reports registry errors.
+fn registry_deprecation(
+    record: &InstallRecord,
+    registry: &Registry<'_>,
+) -> Option<SkillDeprecation> {
+    if !matches!(record.kind, InstallSourceKind::Registry) {
+        return None;
+    }
+    let version = record.version.as_deref()?;
+    let bytes = registry
+        .fetch_index(&format!("{}/{version}", registry_package(record)))
+        .ok()?;
+    serde_json::from_slice::<RegistryPackageIndex>(&bytes)
+        .ok()?
+        .deprecation()
+}
+
+/// [`registry_deprecation`] against the configured registry, for `skill update`.
+pub(super) fn installed_deprecation(
+    record: &InstallRecord,
+    config: &crate::config::Config,
+) -> Option<SkillDeprecation> {
+    let index_cache = IndexCache::for_workspace(&config.workspace_dir);
+    registry_deprecation(
+        record,
+        &Registry {
+            url: &config.wasm.registry_url,
+            index_cache: &index_cache,
+            max_redirects: config.skills.download_max_redirects,
+        },
+    )
+}
+
+/// `namespace/name` of a registry install source, without the `@version`.
+fn registry_package(record: &InstallRecord) -> &str {
+    record
+        .source
+        .split_once('@')
+        .map_or(record.source.as_str(), |(base, _)| base)
+}
+
+fn parse_ls_remote(output: &str, wanted: &[String]) -> Option<String> {
+    wanted.iter().find_map(|wanted| {
+        output.lines().find_map(|line| {
+            let (sha, reference) = line.split_once('\t')?;
+            (reference.trim() == wanted && !sha.is_empty()).then(|| sha.trim().to_string())
+        })
+    })
+}
+
+/// Run `cmd` to completion, killing it once `timeout` elapses. Returns stdout.
+fn run_with_timeout(mut cmd: Command, timeout: Duration) -> Result<String> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to spawn git")?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if child.try_wait()?.is_some() {
+            break;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!("timed out after {}s", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("{}", stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn short_ref(value: &str, kind: Option<&str>) -> String {
+    if kind == Some("git") {
+        value.chars().take(SHORT_SHA_LEN).collect()
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_age(secs: u64) -> String {
+    match secs {
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
+
+fn print_table(rows: &[OutdatedRow]) {
+    if rows.is_empty() {
+        println!("No skills installed.");
+        return;
+    }
+
+    println!(
+        "  {:<24} {:<10} {:<12} {:<12} {:<6} {}",
+        console::style("SKILL").bold(),
+        console::style("SOURCE").bold(),
+        console::style("INSTALLED").bold(),
+        console::style("LATEST").bold(),
+        console::style("AGE").bold(),
+        console::style("STATUS").bold(),
+    );
+    println!("  {}", "─".repeat(80));
+    for row in rows {
+        let installed = row
+            .installed
+            .as_deref()
+            .map_or_else(|| "-".to_string(), |v| short_ref(v, row.kind));
+        let latest = row
+            .latest
+            .as_deref()
+            .map_or_else(|| "-".to_string(), |v| short_ref(v, row.kind));
+        let age = row.age_secs.map_or_else(|| "-".to_string(), format_age);
+        let status = match row.status {
+            OutdatedStatus::UpToDate => console::style(row.status.as_str()).green(),
+            OutdatedStatus::Outdated => console::style(row.status.as_str()).yellow(),
+            OutdatedStatus::Unknown => console::style(row.status.as_str()).dim(),
+            OutdatedStatus::Error => console::style(row.status.as_str()).red(),
+        };
+        println!(
+            "  {:<24} {:<10} {:<12} {:<12} {:<6} {}",
+            row.name,
+            row.kind.unwrap_or("untracked"),
+            installed,
+            latest,
+            age,
+            status
+        );
+        if let Some(deprecation) = &row.deprecated {
+            println!(
+                "    {} {}",
+                console::style("deprecated").yellow().bold(),
+                deprecation.summary()
+            );
+        }
+        if let Some(error) = &row.error {
+            println!("    {}", console::style(error).red());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::write_install_record;
+    use super::*;
+
+    #[test]
+    fn parse_ls_remote_picks_the_wanted_ref() {
+        let head = ["HEAD".to_string()];
+        let output = "3f2a9c1d0e\tHEAD\nabc123\trefs/heads/main\n";
+        assert_eq!(
+            parse_ls_remote(output, &head).as_deref(),
+            Some("3f2a9c1d0e")
+        );
+        assert_eq!(parse_ls_remote("abc\trefs/heads/main\n", &head), None);
+        assert_eq!(parse_ls_remote("", &head), None);
+
+        // An annotated tag resolves to the commit it points at.
+        let tag = [
+            "refs/heads/v1".to_string(),
+            "refs/tags/v1^{}".to_string(),
+            "refs/tags/v1".to_string(),
+        ];
+        let output = "aaa111\trefs/tags/v1\nbbb222\trefs/tags/v1^{}\n";
+        assert_eq!(parse_ls_remote(output, &tag).as_deref(), Some("bbb222"));
+    }
+
+    #[test]
+    fn run_bounded_preserves_order() {
+        let jobs: Vec<u32> = (0..20).collect();
+        let results = run_bounded(&jobs, 3, |n| n * 2);
+        assert_eq!(results, (0..20).map(|n| n * 2).collect::<Vec<_>>());
+        assert!(run_bounded(&Vec::<u32>::new(), 3, |n| *n).is_empty());
+    }
+
+    #[test]
+    fn local_and_untracked_skills_are_unknown_and_bad_remote_is_inline_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let skills = dir.path();
+
+        std::fs::create_dir_all(skills.join("handmade")).unwrap();
+
+        let local = skills.join("local_skill");
+        std::fs::create_dir_all(&local).unwrap();
+        write_install_record(
+            &local,
+            &InstallRecord::new(InstallSourceKind::Local, "/tmp/local_skill"),
+        )
+        .unwrap();
+
+        let git = skills.join("git_skill");
+        std::fs::create_dir_all(&git).unwrap();
+        let mut record = InstallRecord::new(
+            InstallSourceKind::Git,
+            &dir.path().join("missing-remote").display().to_string(),
+        );
+        record.commit = Some("0123456789abcdef".into());
+        write_install_record(&git, &record).unwrap();
+
+        let jobs = collect_jobs(skills).unwrap();
+        let index_cache = IndexCache::new(dir.path().join("registry-index"));
+        let registry = Registry {
+            url: "https://registry.invalid",
+            index_cache: &index_cache,
+            max_redirects: crate::skills::DEFAULT_DOWNLOAD_MAX_REDIRECTS,
+        };
+        let rows = run_bounded(&jobs, MAX_CONCURRENT_CHECKS, |job| {
+            check_skill(job, &registry)
+        });
+
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["git_skill", "handmade", "local_skill"]);
+
+        assert_eq!(rows[0].kind, Some("git"));
+        assert_eq!(rows[0].status, OutdatedStatus::Error);
+        assert!(rows[0].error.is_some());
+        assert_eq!(rows[0].installed.as_deref(), Some("0123456789abcdef"));
+
+        assert_eq!(rows[1].kind, None);
+        assert_eq!(rows[1].status, OutdatedStatus::Unknown);
+
+        assert_eq!(rows[2].kind, Some("local"));
+        assert_eq!(rows[2].status, OutdatedStatus::Unknown);
+        assert!(rows[2].age_secs.is_some());
+
+        let json = serde_json::to_value(&rows).unwrap();
+        assert_eq!(json[0]["status"], "error");
+        assert_eq!(json[2]["status"], "unknown");
+    }
+}
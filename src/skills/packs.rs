@@ -0,0 +1,708 @@
+//! Skill packs: skills that install other skills via `[dependencies]`.
+//!
+//! ```toml
+//! [dependencies]
+//! "acme/git-helper" = "1.2.0"                    # registry, pinned
+//! "clawhub:gog" = "*"                            # any version
+//! "https://github.com/acme/lint-skill.git" = "*" # git
+//! "./vendored/notes" = "0.3.1"                   # local path (relative to the pack source)
+//! ```
+//!
+//! After the parent is installed, each dependency goes through the regular
+//! installer for its source type (including the security audit), then its
+//! `SKILL.toml` version is checked against the pin (`"*"` accepts any).
+//! Resolution recurses up to [`MAX_DEPENDENCY_DEPTH`] levels and refuses
+//! cycles. A failing dependency is rolled back on its own; the dependent skill
+//! stays installed and its `_install.json` lists the failed entries under
+//! `incomplete_dependencies`.
+
+use super::freeze::FrozenSkills;
+use super::{is_registry_source, load_skill_toml, read_install_record, write_install_record};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// How many levels of `[dependencies]` are followed below the installed skill.
+pub(super) const MAX_DEPENDENCY_DEPTH: usize = 3;
+
+#[derive(Debug, Default, Deserialize)]
+struct PackManifest {
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+}
+
+/// `[dependencies]` of an installed skill (empty without a `SKILL.toml`).
+fn read_dependencies(skill_dir: &Path) -> Result<BTreeMap<String, String>> {
+    let path = skill_dir.join("SKILL.toml");
+    if !path.is_file() {
+        return Ok(BTreeMap::new());
+    }
+    let raw = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let manifest: PackManifest =
+        toml::from_str(&raw).with_context(|| format!("invalid {}", path.display()))?;
+    Ok(manifest.dependencies)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DependencyStatus {
+    Installed,
+    AlreadyInstalled,
+    /// Installed and frozen: used as-is, without a pin check.
+    Frozen,
+    Failed(String),
+}
+
+/// One resolved `[dependencies]` entry, in install order.
+#[derive(Debug, Clone)]
+struct DependencyOutcome {
+    /// Skill that declared the dependency.
+    parent: String,
+    /// Key as written in `[dependencies]`.
+    spec: String,
+    pin: String,
+    depth: usize,
+    /// Installed directory name, when known.
+    name: Option<String>,
+    version: Option<String>,
+    status: DependencyStatus,
+}
+
+struct Resolver<'a> {
+    skills_path: &'a Path,
+    max_depth: usize,
+    install: &'a mut dyn FnMut(&str) -> Result<PathBuf>,
+    frozen: FrozenSkills,
+    /// Identity keys and labels of the skills on the current resolution path.
+    stack: Vec<(String, String)>,
+    outcomes: Vec<DependencyOutcome>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(
+        skills_path: &'a Path,
+        max_depth: usize,
+        install: &'a mut dyn FnMut(&str) -> Result<PathBuf>,
+    ) -> Self {
+        Self {
+            skills_path,
+            max_depth,
+            install,
+            frozen: FrozenSkills::load_or_default(skills_path),
+            stack: Vec::new(),
+            outcomes: Vec::new(),
+        }
+    }
+
+    /// Resolve the dependency tree below an already-installed skill.
+    fn resolve(mut self, root_dir: &Path, root_source: &str) -> Vec<DependencyOutcome> {
+        self.stack
+            .push((identity_key(root_source), dir_name(root_dir)));
+        self.resolve_children(root_dir, local_base(root_source).as_deref(), 1);
+        self.outcomes
+    }
+
+    fn resolve_children(&mut self, parent_dir: &Path, base_dir: Option<&Path>, depth: usize) {
+        let parent = dir_name(parent_dir);
+        let dependencies = match read_dependencies(parent_dir) {
+            Ok(dependencies) => dependencies,
+            Err(err) => {
+                self.outcomes.push(DependencyOutcome {
+                    parent: parent.clone(),
+                    spec: "[dependencies]".into(),
+                    pin: String::new(),
+                    depth,
+                    name: None,
+                    version: None,
+                    status: DependencyStatus::Failed(format!("{err:#}")),
+                });
+                mark_dependencies(parent_dir, Vec::new(), vec!["[dependencies]".into()]);
+                return;
+            }
+        };
+        if dependencies.is_empty() {
+            return;
+        }
+
+        let mut satisfied = Vec::new();
+        let mut failed = Vec::new();
+        for (spec, pin) in &dependencies {
+            match self.resolve_one(&parent, spec, pin, base_dir, depth) {
+                Ok(name) => satisfied.push(name),
+                Err(()) => failed.push(spec.clone()),
+            }
+        }
+        mark_dependencies(parent_dir, satisfied, failed);
+    }
+
+    /// Install (or find) one dependency and recurse into it. Returns the
+    /// installed directory name, or `Err` after recording the failure.
+    fn resolve_one(
+        &mut self,
+        parent: &str,
+        spec: &str,
+        pin: &str,
+        base_dir: Option<&Path>,
+        depth: usize,
+    ) -> Result<String, ()> {
+        let mut outcome = DependencyOutcome {
+            parent: parent.to_string(),
+            spec: spec.to_string(),
+            pin: pin.to_string(),
+            depth,
+            name: None,
+            version: None,
+            status: DependencyStatus::Installed,
+        };
+
+        let prepared = self.prepare(spec, pin, base_dir, depth).and_then(|source| {
+            match find_installed_by_source(self.skills_path, &source) {
+                Some(existing) => Ok((source, existing, false)),
+                None => (self.install)(&source).map(|dir| (source, dir, true)),
+            }
+        });
+        let (source, dir, fresh) = match prepared {
+            Ok(prepared) => prepared,
+            Err(err) => {
+                outcome.status = DependencyStatus::Failed(format!("{err:#}"));
+                self.outcomes.push(outcome);
+                return Err(());
+            }
+        };
+
+        let name = dir_name(&dir);
+        let version = installed_version(&dir);
+        outcome.name = Some(name.clone());
+        outcome.version.clone_from(&version);
+        if !fresh && self.frozen.is_frozen(&name) {
+            outcome.status = DependencyStatus::Frozen;
+            self.outcomes.push(outcome);
+            return Ok(name);
+        }
+        if !pin_satisfied(pin, version.as_deref()) {
+            let found = version.as_deref().unwrap_or("unknown");
+            outcome.status = if fresh {
+                let _ = std::fs::remove_dir_all(&dir);
+                DependencyStatus::Failed(format!(
+                    "version {found} does not match pin {pin}; rolled back"
+                ))
+            } else {
+                DependencyStatus::Failed(format!(
+                    "already installed at version {found}, which does not match pin {pin}"
+                ))
+            };
+            self.outcomes.push(outcome);
+            return Err(());
+        }
+
+        if !fresh {
+            outcome.status = DependencyStatus::AlreadyInstalled;
+            self.outcomes.push(outcome);
+            return Ok(name);
+        }
+
+        self.outcomes.push(outcome);
+        self.stack.push((identity_key(&source), name.clone()));
+        self.resolve_children(&dir, local_base(&source).as_deref(), depth + 1);
+        self.stack.pop();
+        Ok(name)
+    }
+
+    /// Depth and cycle checks, then map the spec + pin to an installer source.
+    fn prepare(
+        &self,
+        spec: &str,
+        pin: &str,
+        base_dir: Option<&Path>,
+        depth: usize,
+    ) -> Result<String> {
+        if depth > self.max_depth {
+            anyhow::bail!("dependency depth limit ({}) exceeded", self.max_depth);
+        }
+        let source = dependency_source(spec, pin, base_dir)?;
+        let key = identity_key(&source);
+        if self.stack.iter().any(|(seen, _)| *seen == key) {
+            let path: Vec<&str> = self.stack.iter().map(|(_, label)| label.as_str()).collect();
+            anyhow::bail!("dependency cycle: {} -> {spec}", path.join(" -> "));
+        }
+        Ok(source)
+    }
+}
+
+fn is_local_dependency(spec: &str) -> bool {
+    spec.starts_with("./") || spec.starts_with("../") || Path::new(spec).is_absolute()
+}
+
+/// Installer source for a `[dependencies]` entry. Registry pins become
+/// `namespace/name@version`; relative paths resolve against the dependent
+/// skill's local source directory.
+fn dependency_source(spec: &str, pin: &str, base_dir: Option<&Path>) -> Result<String> {
+    if is_local_dependency(spec) {
+        let path = if Path::new(spec).is_absolute() {
+            PathBuf::from(spec)
+        } else {
+            base_dir
+                .with_context(|| {
+                    format!("relative dependency '{spec}' is only supported for packs installed from a local path")
+                })?
+                .join(spec)
+        };
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("dependency path not found: {}", path.display()))?;
+        return Ok(path.display().to_string());
+    }
+
+    if is_registry_source(spec) {
+        if spec.contains('@') {
+            anyhow::bail!("pin registry dependency '{spec}' in the value, not the key");
+        }
+        return Ok(match normalize_pin(pin) {
+            "*" => spec.to_string(),
+            version => format!("{spec}@{version}"),
+        });
+    }
+
+    Ok(spec.to_string())
+}
+
+fn local_base(source: &str) -> Option<PathBuf> {
+    let path = Path::new(source);
+    path.is_dir().then(|| path.canonicalize().ok()).flatten()
+}
+
+/// Key used for cycle detection: canonical path for local sources, else the
+/// source string without a version suffix.
+fn identity_key(source: &str) -> String {
+    if let Some(path) = local_base(source) {
+        return path.display().to_string();
+    }
+    if is_registry_source(source) {
+        return source.split('@').next().unwrap_or(source).to_string();
+    }
+    source.to_string()
+}
+
+fn normalize_pin(pin: &str) -> &str {
+    match pin.trim().trim_start_matches('=').trim() {
+        "" => "*",
+        pin => pin,
+    }
+}
+
+fn pin_satisfied(pin: &str, version: Option<&str>) -> bool {
+    match normalize_pin(pin) {
+        "*" => true,
+        pin => version
+            .is_some_and(|version| version.trim_start_matches('v') == pin.trim_start_matches('v')),
+    }
+}
+
+fn installed_version(dir: &Path) -> Option<String> {
+    load_skill_toml(&dir.join("SKILL.toml"))
+        .ok()
+        .map(|skill| skill.version)
+        .or_else(|| read_install_record(dir).ok().flatten()?.version)
+}
+
+fn dir_name(dir: &Path) -> String {
+    dir.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.display().to_string())
+}
+
+fn find_installed_by_source(skills_path: &Path, source: &str) -> Option<PathBuf> {
+    let key = identity_key(source);
+    std::fs::read_dir(skills_path)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .find(|path| {
+            read_install_record(path)
+                .ok()
+                .flatten()
+                .is_some_and(|record| {
+                    record.source == source || identity_key(&record.source) == key
+                })
+        })
+}
+
+/// Record resolved and failed dependencies in the dependent's `_install.json`.
+fn mark_dependencies(skill_dir: &Path, satisfied: Vec<String>, failed: Vec<String>) {
+    let Ok(Some(mut record)) = read_install_record(skill_dir) else {
+        return;
+    };
+    record.dependencies = satisfied;
+    record.incomplete_dependencies = failed;
+    if let Err(err) = write_install_record(skill_dir, &record) {
+        tracing::warn!(
+            "failed to record dependencies for {}: {err:#}",
+            skill_dir.display()
+        );
+    }
+}
+
+/// Resolve `[dependencies]` of a freshly installed skill and print a summary.
+///
+/// Fails (after printing) when any dependency could not be installed; the
+/// parent skill itself stays installed.
+pub(super) fn install_pack_dependencies(
+    installed_dir: &Path,
+    source: &str,
+    skills_path: &Path,
+    config: &crate::config::Config,
+) -> Result<()> {
+    if read_dependencies(installed_dir)?.is_empty() {
+        return Ok(());
+    }
+
+    let pack = dir_name(installed_dir);
+    say!();
+    say!("Resolving dependencies of '{pack}'...");
+    let mut install =
+        |dependency: &str| super::install_from_source(dependency, skills_path, config);
+    let outcomes = Resolver::new(skills_path, MAX_DEPENDENCY_DEPTH, &mut install)
+        .resolve(installed_dir, source);
+
+    print_summary(&pack, &outcomes);
+
+    let failed = outcomes
+        .iter()
+        .filter(|outcome| matches!(outcome.status, DependencyStatus::Failed(_)))
+        .count();
+    if failed > 0 {
+        say!();
+        say!(
+            "  {} '{pack}' is installed but incomplete. Fix the errors above, then re-run",
+            console::style("!").yellow().bold()
+        );
+        say!("    zeroclaw skill remove {pack} && zeroclaw skill install {source}");
+        say!("  or install the missing dependencies individually with 'zeroclaw skill install <source>'.");
+        anyhow::bail!(
+            "{failed} dependenc{} of '{pack}' failed to install",
+            if failed == 1 { "y" } else { "ies" }
+        );
+    }
+    Ok(())
+}
+
+fn print_summary(pack: &str, outcomes: &[DependencyOutcome]) {
+    say!();
+    say!("  Dependencies of '{pack}':");
+    for outcome in outcomes {
+        let indent = "  ".repeat(outcome.depth);
+        let label = match (&outcome.name, &outcome.version) {
+            (Some(name), Some(version)) => format!("{name} {version}"),
+            (Some(name), None) => name.clone(),
+            _ => outcome.spec.clone(),
+        };
+        match &outcome.status {
+            DependencyStatus::Installed => say!(
+                "  {indent}{} {label} (installed from {})",
+                console::style("✓").green().bold(),
+                outcome.spec
+            ),
+            DependencyStatus::AlreadyInstalled => say!(
+                "  {indent}{} {label} (already installed)",
+                console::style("=").dim()
+            ),
+            DependencyStatus::Frozen => say!(
+                "  {indent}{} {label}: skipped (frozen)",
+                console::style("❄").cyan().bold()
+            ),
+            DependencyStatus::Failed(err) => say!(
+                "  {indent}{} {} = \"{}\" required by {}: {err}",
+                console::style("✗").red().bold(),
+                outcome.spec,
+                outcome.pin,
+                outcome.parent
+            ),
+        }
+    }
+}
+
+/// Dependency edges recorded in `_install.json` files: skill → dependencies.
+fn dependency_graph(skills_path: &Path) -> BTreeMap<String, Vec<String>> {
+    let Ok(entries) = std::fs::read_dir(skills_path) else {
+        return BTreeMap::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .map(|path| {
+            let dependencies = read_install_record(&path)
+                .ok()
+                .flatten()
+                .map(|record| record.dependencies)
+                .unwrap_or_default();
+            (dir_name(&path), dependencies)
+        })
+        .collect()
+}
+
+/// Installed skills that list `name` as a dependency.
+pub(super) fn dependents_of(skills_path: &Path, name: &str) -> Vec<String> {
+    dependency_graph(skills_path)
+        .into_iter()
+        .filter(|(skill, dependencies)| skill != name && dependencies.iter().any(|dep| dep == name))
+        .map(|(skill, _)| skill)
+        .collect()
+}
+
+/// Dependencies (transitively) left unused by any other installed skill if
+/// `name` were removed.
+pub(super) fn orphaned_dependencies(skills_path: &Path, name: &str) -> Vec<String> {
+    let graph = dependency_graph(skills_path);
+    let mut removed: BTreeSet<&str> = BTreeSet::from([name]);
+    loop {
+        let newly_orphaned: Vec<&str> = graph
+            .keys()
+            .map(String::as_str)
+            .filter(|candidate| !removed.contains(candidate))
+            .filter(|candidate| {
+                let mut dependents = graph
+                    .iter()
+                    .filter(|(_, deps)| deps.iter().any(|dep| dep == candidate))
+                    .map(|(skill, _)| skill.as_str())
+                    .peekable();
+                dependents.peek().is_some() && dependents.all(|skill| removed.contains(skill))
+            })
+            .collect();
+        if newly_orphaned.is_empty() {
+            break;
+        }
+        removed.extend(newly_orphaned);
+    }
+    removed.remove(name);
+    removed.into_iter().map(str::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{install_from_source, InstallRecord};
+    use super::*;
+
+    fn write_skill(
+        dir: &Path,
+        name: &str,
+        version: &str,
+        dependencies: &[(&str, &str)],
+    ) -> PathBuf {
+        let skill_dir = dir.join(name);
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        let mut manifest = format!(
+            "[skill]\nname = \"{name}\"\ndescription = \"{name} skill\"\nversion = \"{version}\"\n"
+        );
+        if !dependencies.is_empty() {
+            manifest.push_str("\n[dependencies]\n");
+            for (spec, pin) in dependencies {
+                manifest.push_str(&format!("{spec:?} = {pin:?}\n"));
+            }
+        }
+        std::fs::write(skill_dir.join("SKILL.toml"), manifest).unwrap();
+        skill_dir
+    }
+
+    fn install_local(skills_path: &Path, source: &str) -> Result<PathBuf> {
+        install_from_source(source, skills_path, &crate::config::Config::default())
+    }
+
+    fn resolve(skills_path: &Path, root_source: &Path, max_depth: usize) -> Vec<DependencyOutcome> {
+        let root_source = root_source.to_str().unwrap();
+        let root_dir = install_local(skills_path, root_source).unwrap();
+        let mut install = |source: &str| install_local(skills_path, source);
+        Resolver::new(skills_path, max_depth, &mut install).resolve(&root_dir, root_source)
+    }
+
+    fn record(skills_path: &Path, name: &str) -> InstallRecord {
+        read_install_record(&skills_path.join(name))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn pack_installs_pinned_and_nested_local_dependencies() {
+        let sources = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        std::fs::create_dir_all(&skills_path).unwrap();
+
+        let git_helper = write_skill(sources.path(), "git-helper", "1.2.0", &[]);
+        write_skill(sources.path(), "notes", "0.3.1", &[]);
+        write_skill(sources.path(), "planner", "2.0.0", &[("../notes", "*")]);
+        let pack = write_skill(
+            sources.path(),
+            "starter-pack",
+            "1.0.0",
+            &[(git_helper.to_str().unwrap(), "1.2.0"), ("../planner", "*")],
+        );
+
+        let outcomes = resolve(&skills_path, &pack, MAX_DEPENDENCY_DEPTH);
+
+        assert!(outcomes
+            .iter()
+            .all(|outcome| outcome.status == DependencyStatus::Installed));
+        let installed: Vec<(&str, usize)> = outcomes
+            .iter()
+            .map(|outcome| (outcome.name.as_deref().unwrap(), outcome.depth))
+            .collect();
+        // `[dependencies]` keys resolve in sorted order, depth-first.
+        assert_eq!(installed, [("planner", 1), ("notes", 2), ("git-helper", 1)]);
+
+        let pack_record = record(&skills_path, "starter-pack");
+        assert_eq!(pack_record.dependencies, ["planner", "git-helper"]);
+        assert!(pack_record.incomplete_dependencies.is_empty());
+        assert_eq!(record(&skills_path, "planner").dependencies, ["notes"]);
+
+        assert_eq!(
+            orphaned_dependencies(&skills_path, "starter-pack"),
+            ["git-helper", "notes", "planner"]
+        );
+        assert_eq!(dependents_of(&skills_path, "notes"), ["planner"]);
+    }
+
+    #[test]
+    fn dependency_cycle_is_reported_without_looping() {
+        let sources = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        std::fs::create_dir_all(&skills_path).unwrap();
+
+        write_skill(sources.path(), "beta", "1.0.0", &[("../alpha", "*")]);
+        let alpha = write_skill(sources.path(), "alpha", "1.0.0", &[("../beta", "*")]);
+
+        let outcomes = resolve(&skills_path, &alpha, MAX_DEPENDENCY_DEPTH);
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[0].status, DependencyStatus::Installed);
+        match &outcomes[1].status {
+            DependencyStatus::Failed(err) => {
+                assert!(
+                    err.contains("dependency cycle: alpha -> beta -> ../alpha"),
+                    "{err}"
+                );
+            }
+            other => panic!("expected cycle failure, got {other:?}"),
+        }
+        assert!(skills_path.join("beta").is_dir());
+        assert_eq!(
+            record(&skills_path, "beta").incomplete_dependencies,
+            ["../alpha"]
+        );
+        assert_eq!(record(&skills_path, "alpha").dependencies, ["beta"]);
+    }
+
+    #[test]
+    fn failing_dependency_is_rolled_back_and_parent_marked_incomplete() {
+        let sources = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        std::fs::create_dir_all(&skills_path).unwrap();
+
+        write_skill(sources.path(), "good", "1.0.0", &[]);
+        write_skill(sources.path(), "wrong-version", "1.0.0", &[]);
+        let pack = write_skill(
+            sources.path(),
+            "pack",
+            "1.0.0",
+            &[
+                ("../good", "*"),
+                ("../missing", "*"),
+                ("../wrong-version", "2.0.0"),
+            ],
+        );
+
+        let outcomes = resolve(&skills_path, &pack, MAX_DEPENDENCY_DEPTH);
+
+        let failures: Vec<&str> = outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, DependencyStatus::Failed(_)))
+            .map(|outcome| outcome.spec.as_str())
+            .collect();
+        assert_eq!(failures, ["../missing", "../wrong-version"]);
+        assert!(skills_path.join("good").is_dir());
+        assert!(
+            !skills_path.join("wrong-version").exists(),
+            "mismatched pin rolls back"
+        );
+
+        let pack_record = record(&skills_path, "pack");
+        assert_eq!(pack_record.dependencies, ["good"]);
+        assert_eq!(
+            pack_record.incomplete_dependencies,
+            ["../missing", "../wrong-version"]
+        );
+    }
+
+    #[test]
+    fn frozen_dependency_is_skipped_without_pin_check() {
+        let sources = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        std::fs::create_dir_all(&skills_path).unwrap();
+
+        let patched = write_skill(sources.path(), "patched", "1.0.0", &[]);
+        install_local(&skills_path, patched.to_str().unwrap()).unwrap();
+        let mut frozen = FrozenSkills::load(&skills_path).unwrap();
+        frozen.freeze("patched", Some("local fix".into()));
+        frozen.save().unwrap();
+
+        let pack = write_skill(sources.path(), "pack", "1.0.0", &[("../patched", "2.0.0")]);
+        let outcomes = resolve(&skills_path, &pack, MAX_DEPENDENCY_DEPTH);
+
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].status, DependencyStatus::Frozen);
+        assert_eq!(
+            installed_version(&skills_path.join("patched")).as_deref(),
+            Some("1.0.0")
+        );
+        assert_eq!(record(&skills_path, "pack").dependencies, ["patched"]);
+        assert!(record(&skills_path, "pack")
+            .incomplete_dependencies
+            .is_empty());
+    }
+
+    #[test]
+    fn depth_limit_stops_deep_chains() {
+        let sources = tempfile::tempdir().unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        let skills_path = workspace.path().join("skills");
+        std::fs::create_dir_all(&skills_path).unwrap();
+
+        write_skill(sources.path(), "c", "1.0.0", &[]);
+        write_skill(sources.path(), "b", "1.0.0", &[("../c", "*")]);
+        let a = write_skill(sources.path(), "a", "1.0.0", &[("../b", "*")]);
+
+        let outcomes = resolve(&skills_path, &a, 1);
+
+        assert_eq!(outcomes[0].status, DependencyStatus::Installed);
+        assert!(matches!(
+            &outcomes[1].status,
+            DependencyStatus::Failed(err) if err.contains("depth limit (1)")
+        ));
+        assert!(!skills_path.join("c").exists());
+    }
+
+    #[test]
+    fn registry_pins_become_versioned_sources() {
+        assert_eq!(
+            dependency_source("acme/git-helper", "1.2.0", None).unwrap(),
+            "acme/git-helper@1.2.0"
+        );
+        assert_eq!(
+            dependency_source("acme/git-helper", "*", None).unwrap(),
+            "acme/git-helper"
+        );
+        assert!(dependency_source("acme/git-helper@1.0.0", "*", None).is_err());
+        assert!(dependency_source("./local", "*", None).is_err());
+        assert_eq!(
+            dependency_source("clawhub:gog", "*", None).unwrap(),
+            "clawhub:gog"
+        );
+        assert!(pin_satisfied("=1.2.0", Some("v1.2.0")));
+        assert!(!pin_satisfied("1.2.0", None));
+    }
+}
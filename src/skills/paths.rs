@@ -0,0 +1,201 @@
+//! Platform-neutral path handling for skill installs.
+//!
+//! Zip archives always use `/` in entry names, but archives built on Windows
+//! sometimes carry `\` instead, and Windows rejects a handful of characters
+//! and device names that are legal elsewhere. `canonicalize()` on Windows also
+//! returns verbatim (`\\?\`) paths, which never `starts_with` a non-verbatim
+//! root, and paths longer than `MAX_PATH` need the verbatim prefix to be
+//! created at all. The string-level helpers here are pure so they are tested
+//! on every platform; only [`long_path_safe`] changes behavior by target.
+//! Traversal and containment checks themselves live in
+//! [`crate::security::paths`].
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::security::paths::{has_drive_prefix, normalize_relative};
+pub use crate::security::paths::{is_within, strip_verbatim_prefix};
+
+/// Classic Win32 path length limit, including the terminating NUL.
+const WINDOWS_MAX_PATH: usize = 260;
+
+/// Characters Windows refuses in file names.
+const WINDOWS_RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+/// Device names Windows reserves regardless of extension (`CON.txt` too).
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Why a path component cannot be created on Windows, if it can't.
+pub fn windows_reserved_reason(component: &str) -> Option<&'static str> {
+    if component
+        .chars()
+        .any(|c| c.is_control() || WINDOWS_RESERVED_CHARS.contains(&c))
+    {
+        return Some("contains a character reserved on Windows");
+    }
+    if component.ends_with('.') || component.ends_with(' ') {
+        return Some("ends with a dot or space, which Windows strips");
+    }
+    let stem = component.split('.').next().unwrap_or(component).trim_end();
+    if WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return Some("is a reserved Windows device name");
+    }
+    None
+}
+
+/// Turn a zip entry name into a relative path using the native separator.
+///
+/// Traversal, absolute paths and drive letters are rejected by
+/// [`normalize_relative`]; on top of that, components Windows cannot create
+/// are refused so an archive extracts the same way on every platform.
+pub fn normalize_zip_entry_name(raw: &str) -> Result<PathBuf> {
+    let relative = normalize_relative(raw).with_context(|| format!("unsafe zip entry {raw:?}"))?;
+    if let Some(reason) = raw.split(['/', '\\']).find_map(windows_reserved_reason) {
+        bail!("zip entry {raw} {reason}");
+    }
+    if relative.as_os_str().is_empty() {
+        bail!("zip entry has an empty path: {raw:?}");
+    }
+    Ok(relative)
+}
+
+/// Verbatim form of an absolute Windows path (`C:\x` → `\\?\C:\x`,
+/// `\\server\share` → `\\?\UNC\server\share`). `None` for relative paths;
+/// already-verbatim paths are returned as they are.
+pub fn to_verbatim(path: &str) -> Option<String> {
+    if path.starts_with(r"\\?\") {
+        return Some(path.to_string());
+    }
+    let path = path.replace('/', "\\");
+    if let Some(unc) = path.strip_prefix(r"\\") {
+        return Some(format!(r"\\?\UNC\{unc}"));
+    }
+    let bytes = path.as_bytes();
+    if has_drive_prefix(&path) && bytes.get(2) == Some(&b'\\') {
+        return Some(format!(r"\\?\{path}"));
+    }
+    None
+}
+
+/// Path to use when creating `path` on disk. On Windows, absolute paths that
+/// reach `MAX_PATH` get the verbatim prefix so deep trees still extract;
+/// elsewhere the path is returned unchanged.
+pub fn long_path_safe(path: &Path) -> PathBuf {
+    if cfg!(windows) && path.as_os_str().len() >= WINDOWS_MAX_PATH {
+        if let Some(verbatim) = to_verbatim(&path.to_string_lossy()) {
+            return PathBuf::from(verbatim);
+        }
+    }
+    path.to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn zip_entry_names_normalize_separators() {
+        let expected: PathBuf = ["tools", "bin", "run.md"].iter().collect();
+        assert_eq!(
+            normalize_zip_entry_name("tools\\bin\\run.md").unwrap(),
+            expected
+        );
+        assert_eq!(
+            normalize_zip_entry_name("./tools/bin//run.md").unwrap(),
+            expected
+        );
+        assert_eq!(
+            normalize_zip_entry_name("docs/").unwrap(),
+            PathBuf::from("docs")
+        );
+    }
+
+    #[test]
+    fn zip_entry_names_reject_unsafe_and_windows_reserved_paths() {
+        for raw in [
+            "../escape.md",
+            "docs\\..\\..\\escape.md",
+            "/etc/passwd",
+            "\\Windows\\system.ini",
+            "C:\\Users\\x.md",
+            "c:relative.md",
+            "docs/a<b>.md",
+            "docs/what?.md",
+            "docs/CON",
+            "aux.txt/readme.md",
+            "notes./readme.md",
+            "",
+            "./",
+        ] {
+            assert!(
+                normalize_zip_entry_name(raw).is_err(),
+                "expected {raw:?} to be rejected"
+            );
+        }
+        assert!(normalize_zip_entry_name("docs/console.md").is_ok());
+    }
+
+    #[test]
+    fn verbatim_prefix_round_trips() {
+        assert_eq!(strip_verbatim_prefix(r"\\?\C:\skills\x"), r"C:\skills\x");
+        assert_eq!(
+            strip_verbatim_prefix(r"\\?\UNC\server\share\x"),
+            r"\\server\share\x"
+        );
+        assert_eq!(strip_verbatim_prefix("/home/u/skills"), "/home/u/skills");
+
+        assert_eq!(
+            to_verbatim("C:/skills/x").as_deref(),
+            Some(r"\\?\C:\skills\x")
+        );
+        assert_eq!(
+            to_verbatim(r"\\server\share\x").as_deref(),
+            Some(r"\\?\UNC\server\share\x")
+        );
+        assert_eq!(to_verbatim(r"\\?\C:\x").as_deref(), Some(r"\\?\C:\x"));
+        assert_eq!(to_verbatim(r"skills\x"), None);
+        assert_eq!(to_verbatim("C:relative"), None);
+    }
+
+    #[test]
+    fn is_within_ignores_verbatim_prefix() {
+        assert!(is_within(
+            Path::new("/home/u/skills/weather"),
+            Path::new("/home/u/skills")
+        ));
+        assert!(!is_within(
+            Path::new("/home/u/skills-evil/weather"),
+            Path::new("/home/u/skills")
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn is_within_matches_verbatim_and_case_on_windows() {
+        assert!(is_within(
+            Path::new(r"\\?\C:\Users\U\skills\weather"),
+            Path::new(r"c:\users\u\skills")
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn long_paths_are_created_with_verbatim_prefix() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut deep = dir.path().to_path_buf();
+        while deep.as_os_str().len() < WINDOWS_MAX_PATH + 40 {
+            deep.push("node_modules_package");
+        }
+        let target = long_path_safe(&deep.join("index.md"));
+        assert!(target.to_string_lossy().starts_with(r"\\?\"));
+        std::fs::create_dir_all(target.parent().unwrap()).unwrap();
+        std::fs::write(&target, "ok").unwrap();
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "ok");
+    }
+}
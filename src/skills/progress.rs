@@ -0,0 +1,270 @@
+//! Install progress on stderr: download bytes, `git clone` output, and file
+//! counts for extraction and audits.
+//!
+//! Nothing is drawn unless stderr is a terminal and `--quiet` was not given,
+//! so piped and scripted installs only see the final report. A progress line
+//! is redrawn in place and cleared when it is dropped, so it never ends up
+//! mixed into what is printed on stdout afterwards.
+
+use std::io::{self, IsTerminal, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Minimum time between two redraws of the same line.
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+
+/// File counts below this finish too fast to be worth a progress line.
+const MIN_FILES: usize = 50;
+
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+const BAR_WIDTH: usize = 24;
+
+/// Suppress progress for the rest of the process (`--quiet`).
+pub(super) fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Keep stdout for a machine-readable report (`skill install --json`):
+/// install messages printed with `say!` go to stderr instead.
+pub(super) fn reserve_stdout(reserved: bool) {
+    STDOUT_RESERVED.store(reserved, Ordering::Relaxed);
+}
+
+/// Whether stdout is kept for a report; see [`reserve_stdout`].
+pub(super) fn stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::Relaxed)
+}
+
+/// Whether progress should be drawn at all.
+pub(super) fn enabled() -> bool {
+    !QUIET.load(Ordering::Relaxed) && io::stderr().is_terminal()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Unit {
+    Bytes,
+    Files,
+}
+
+/// One in-place progress line. Suppressed instances (`out == None`) only
+/// keep count.
+pub(super) struct Progress<W: Write = io::Stderr> {
+    out: Option<W>,
+    label: String,
+    unit: Unit,
+    total: Option<u64>,
+    done: u64,
+    tick: usize,
+    last_draw: Option<Instant>,
+}
+
+impl Progress {
+    /// Bytes transferred; a bar when `total` is known, a spinner otherwise.
+    pub fn bytes(label: impl Into<String>, total: Option<u64>) -> Self {
+        Self::new(enabled().then(io::stderr), label, Unit::Bytes, total)
+    }
+
+    /// Files processed out of `total`; only drawn for [`MIN_FILES`] or more.
+    pub fn files(label: impl Into<String>, total: usize) -> Self {
+        Self::new(
+            (total >= MIN_FILES && enabled()).then(io::stderr),
+            label,
+            Unit::Files,
+            u64::try_from(total).ok(),
+        )
+    }
+}
+
+impl<W: Write> Progress<W> {
+    fn new(out: Option<W>, label: impl Into<String>, unit: Unit, total: Option<u64>) -> Self {
+        Self {
+            out,
+            label: label.into(),
+            unit,
+            total,
+            done: 0,
+            tick: 0,
+            last_draw: None,
+        }
+    }
+
+    pub fn advance(&mut self, n: u64) {
+        self.done = self.done.saturating_add(n);
+        let Some(out) = self.out.as_mut() else {
+            return;
+        };
+        let finished = self.total.is_some_and(|total| self.done >= total);
+        let due = self
+            .last_draw
+            .is_none_or(|last| last.elapsed() >= REDRAW_EVERY);
+        if !(due || finished) {
+            return;
+        }
+        self.last_draw = Some(Instant::now());
+        self.tick = self.tick.wrapping_add(1);
+        let line = render(
+            &self.label,
+            self.unit,
+            self.done,
+            self.total,
+            SPINNER[self.tick % SPINNER.len()],
+        );
+        let _ = write!(out, "\r\x1b[2K{line}");
+        let _ = out.flush();
+    }
+}
+
+impl<W: Write> Drop for Progress<W> {
+    fn drop(&mut self) {
+        if let (Some(out), Some(_)) = (self.out.as_mut(), self.last_draw) {
+            let _ = write!(out, "\r\x1b[2K");
+            let _ = out.flush();
+        }
+    }
+}
+
+fn render(label: &str, unit: Unit, done: u64, total: Option<u64>, spinner: char) -> String {
+    match (unit, total) {
+        (Unit::Bytes, Some(total)) if total > 0 => {
+            let done = done.min(total);
+            let filled =
+                usize::try_from(done.saturating_mul(BAR_WIDTH as u64) / total).unwrap_or(BAR_WIDTH);
+            format!(
+                "  {label} [{}{}] {} / {} ({}%)",
+                "#".repeat(filled),
+                " ".repeat(BAR_WIDTH - filled),
+                format_size(done),
+                format_size(total),
+                done * 100 / total
+            )
+        }
+        (Unit::Bytes, _) => format!("  {spinner} {label} {}", format_size(done)),
+        (Unit::Files, Some(total)) => format!("  {label} {done}/{total} files"),
+        (Unit::Files, None) => format!("  {spinner} {label} {done} files"),
+    }
+}
+
+pub(super) fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Reader that reports every chunk it hands out to a [`Progress`].
+pub(super) struct CountingReader<'a, R, W: Write = io::Stderr> {
+    inner: R,
+    progress: &'a mut Progress<W>,
+}
+
+impl<'a, R: Read, W: Write> CountingReader<'a, R, W> {
+    pub fn new(inner: R, progress: &'a mut Progress<W>) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl<R: Read, W: Write> Read for CountingReader<'_, R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.progress.advance(n as u64);
+        Ok(n)
+    }
+}
+
+/// Copy `source` (a child's stderr) to our stderr as it arrives when
+/// progress is enabled, and return everything read for error messages.
+pub(super) fn relay_stderr(mut source: impl Read) -> Vec<u8> {
+    let mut relay = enabled().then(io::stderr);
+    let mut captured = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        match source.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                captured.extend_from_slice(&buf[..n]);
+                if let Some(out) = relay.as_mut() {
+                    let _ = out.write_all(&buf[..n]);
+                    let _ = out.flush();
+                }
+            }
+        }
+    }
+    captured
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting_reader_accounts_every_byte() {
+        let payload = vec![7u8; 100_000];
+        let mut progress: Progress<Vec<u8>> =
+            Progress::new(None, "Downloading", Unit::Bytes, Some(100_000));
+        let mut body = Vec::new();
+        CountingReader::new(payload.as_slice(), &mut progress)
+            .read_to_end(&mut body)
+            .unwrap();
+        assert_eq!(body, payload);
+        assert_eq!(progress.done, 100_000);
+    }
+
+    #[test]
+    fn suppressed_progress_writes_nothing() {
+        // What `Progress::bytes` builds when stderr is not a TTY or --quiet.
+        let mut progress: Progress<Vec<u8>> = Progress::new(None, "Cloning", Unit::Bytes, None);
+        progress.advance(4096);
+        assert!(progress.out.is_none());
+        assert_eq!(progress.done, 4096);
+
+        set_quiet(true);
+        assert!(!enabled());
+        set_quiet(false);
+    }
+
+    #[test]
+    fn drawn_progress_is_cleared_when_dropped() {
+        let mut sink = Vec::new();
+        {
+            let mut progress = Progress::new(Some(&mut sink), "Extracting", Unit::Files, Some(2));
+            progress.advance(1);
+            progress.advance(1);
+        }
+        let drawn = String::from_utf8(sink).unwrap();
+        assert!(drawn.contains("Extracting 1/2 files"), "{drawn:?}");
+        assert!(drawn.contains("Extracting 2/2 files"), "{drawn:?}");
+        assert!(drawn.ends_with("\r\x1b[2K"), "{drawn:?}");
+    }
+
+    #[test]
+    fn render_shows_a_bar_with_total_and_a_spinner_without() {
+        assert_eq!(
+            render("tool.wasm", Unit::Bytes, 512 * 1024, Some(1024 * 1024), '|'),
+            format!(
+                "  tool.wasm [{}{}] 512.0 KB / 1.0 MB (50%)",
+                "#".repeat(12),
+                " ".repeat(12)
+            )
+        );
+        assert_eq!(
+            render("skill.zip", Unit::Bytes, 2048, None, '/'),
+            "  / skill.zip 2.0 KB"
+        );
+        assert_eq!(
+            render("Auditing", Unit::Files, 3, Some(40), '|'),
+            "  Auditing 3/40 files"
+        );
+    }
+}
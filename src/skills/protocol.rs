@@ -0,0 +1,330 @@
+//! Canonical stdout envelope for WASM skill tools.
+//!
+//! A skill tool reads its args as JSON from stdin and writes one
+//! [`ToolEnvelope`] as JSON to stdout. This module is the single definition of
+//! that shape: `zeroclaw skill test`, the WASM runtime, and the scaffolded
+//! templates all follow it, and `zeroclaw skill protocol-schema` publishes it
+//! as JSON Schema for non-Rust authors.
+//!
+//! Parsing is deliberately lenient so older skills keep working:
+//! - `result` is accepted as a legacy name for `data`;
+//! - a missing `success` is inferred from the absence of `error`;
+//! - a missing `output` becomes `""`, and a non-string `output` is rendered
+//!   as compact JSON.
+//!
+//! Tools whose manifest sets `"streaming": true` may also write
+//! `@progress: <status>` lines before the envelope. The runtime forwards them
+//! to the channel while the tool runs and drops them before parsing.
+
+use crate::tools::ToolResult;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// Prefix of a status line written by a streaming tool before its envelope.
+pub const PROGRESS_PREFIX: &str = "@progress:";
+
+/// The status text of a `@progress:` line, or `None` for any other line
+/// (including a progress line with no text).
+pub fn progress_message(line: &str) -> Option<&str> {
+    line.strip_prefix(PROGRESS_PREFIX)
+        .map(str::trim)
+        .filter(|message| !message.is_empty())
+}
+
+/// Result written by a skill tool to stdout.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ToolEnvelope {
+    /// Whether the tool call succeeded.
+    pub success: bool,
+    /// Human-readable output shown to the model.
+    pub output: String,
+    /// Error message when `success` is false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Optional structured payload (arbitrary JSON).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// Optional reference to a file produced by the tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<ToolAttachment>,
+}
+
+/// File produced by a skill tool, referenced by path or URL.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ToolAttachment {
+    /// Display name (e.g. `report.pdf`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// MIME type (e.g. `image/png`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Path to the file, relative to the tool's working directory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Remote location of the file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl ToolEnvelope {
+    /// Successful result with the given output.
+    pub fn ok(output: impl Into<String>) -> Self {
+        Self {
+            success: true,
+            output: output.into(),
+            error: None,
+            data: None,
+            attachment: None,
+        }
+    }
+
+    /// Failed result with the given error message.
+    pub fn failure(error: impl Into<String>) -> Self {
+        Self {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+            data: None,
+            attachment: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_data(mut self, data: Value) -> Self {
+        self.data = Some(data);
+        self
+    }
+
+    #[must_use]
+    pub fn with_attachment(mut self, attachment: ToolAttachment) -> Self {
+        self.attachment = Some(attachment);
+        self
+    }
+
+    /// Parse tool stdout leniently (see module docs).
+    pub fn parse(raw: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(raw)
+    }
+
+    /// Parse the complete stdout of a streaming tool: lines starting with
+    /// [`PROGRESS_PREFIX`] are dropped, the rest is parsed as by [`Self::parse`].
+    pub fn parse_streamed(raw: &[u8]) -> serde_json::Result<Self> {
+        let envelope: Vec<u8> = raw
+            .split_inclusive(|byte| *byte == b'\n')
+            .filter(|line| !line.starts_with(PROGRESS_PREFIX.as_bytes()))
+            .flatten()
+            .copied()
+            .collect();
+        Self::parse(&envelope)
+    }
+}
+
+/// Wire shape accepted on input, including legacy field names.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawToolEnvelope {
+    success: Option<bool>,
+    output: Option<Value>,
+    error: Option<Value>,
+    data: Option<Value>,
+    result: Option<Value>,
+    attachment: Option<ToolAttachment>,
+}
+
+impl<'de> Deserialize<'de> for ToolEnvelope {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawToolEnvelope::deserialize(deserializer)?;
+
+        let error = match raw.error {
+            None | Some(Value::Null) => None,
+            Some(Value::String(message)) if message.trim().is_empty() => None,
+            Some(Value::String(message)) => Some(message),
+            Some(other) => Some(other.to_string()),
+        };
+        let output = match raw.output {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(output)) => output,
+            Some(other) => other.to_string(),
+        };
+        let data = raw.data.or(raw.result).filter(|value| !value.is_null());
+
+        Ok(Self {
+            success: raw.success.unwrap_or(error.is_none()),
+            output,
+            error,
+            data,
+            attachment: raw.attachment,
+        })
+    }
+}
+
+impl From<ToolEnvelope> for ToolResult {
+    /// Tools that only return `data` still give the model something to read.
+    fn from(envelope: ToolEnvelope) -> Self {
+        let output = match (envelope.output.is_empty(), envelope.data) {
+            (true, Some(data)) => data.to_string(),
+            _ => envelope.output,
+        };
+        ToolResult {
+            success: envelope.success,
+            output,
+            error: envelope.error,
+        }
+    }
+}
+
+/// JSON Schema of [`ToolEnvelope`], printed by `zeroclaw skill protocol-schema`.
+pub fn json_schema() -> Value {
+    serde_json::to_value(schemars::schema_for!(ToolEnvelope))
+        .expect("ToolEnvelope schema is serializable")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn canonical_envelope_round_trips() {
+        let envelope = ToolEnvelope::ok("Hanoi: 28.5°C")
+            .with_data(json!({"city": "Hanoi", "temperature_c": 28.5}))
+            .with_attachment(ToolAttachment {
+                name: Some("chart.png".into()),
+                mime_type: Some("image/png".into()),
+                path: Some("out/chart.png".into()),
+                url: None,
+            });
+
+        let encoded = serde_json::to_vec(&envelope).unwrap();
+        assert_eq!(ToolEnvelope::parse(&encoded).unwrap(), envelope);
+
+        let failure = ToolEnvelope::failure("city not found");
+        let encoded = serde_json::to_string(&failure).unwrap();
+        assert_eq!(
+            encoded,
+            r#"{"success":false,"output":"","error":"city not found"}"#
+        );
+        assert_eq!(ToolEnvelope::parse(encoded.as_bytes()).unwrap(), failure);
+    }
+
+    #[test]
+    fn legacy_result_field_maps_to_data() {
+        let envelope =
+            ToolEnvelope::parse(br#"{"success":true,"output":"3 + 7 = 10","result":10.0}"#)
+                .unwrap();
+        assert_eq!(envelope.data, Some(json!(10.0)));
+
+        // Re-serializing uses the canonical name only.
+        let encoded = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(encoded["data"], json!(10.0));
+        assert!(encoded.get("result").is_none());
+
+        // `data` wins when a tool sends both.
+        let envelope =
+            ToolEnvelope::parse(br#"{"success":true,"output":"","data":1,"result":2}"#).unwrap();
+        assert_eq!(envelope.data, Some(json!(1)));
+    }
+
+    /// Stdout exactly as each scaffolded template writes it.
+    #[test]
+    fn template_outputs_parse_into_the_envelope() {
+        // templates/rust/weather_lookup: object payload.
+        let weather = ToolEnvelope::parse(
+            br#"{"success":true,"output":"Hanoi: 28.5\u00b0C, Partly Cloudy","data":{"city":"Hanoi","temperature_c":28.5,"condition":"Partly Cloudy","humidity_pct":75,"wind_kmh":12}}"#,
+        )
+        .unwrap();
+        assert!(weather.success);
+        assert_eq!(weather.data.as_ref().unwrap()["humidity_pct"], json!(75));
+        assert_eq!(weather.error, None);
+
+        // templates/rust/calculator: scalar payload, and the failure shape.
+        let sum =
+            ToolEnvelope::parse(br#"{"success":true,"output":"3 + 7 = 10","data":10.0}"#).unwrap();
+        assert_eq!(sum.data, Some(json!(10.0)));
+        let div =
+            ToolEnvelope::parse(br#"{"success":false,"output":"","error":"division by zero"}"#)
+                .unwrap();
+        assert_eq!(div, ToolEnvelope::failure("division by zero"));
+
+        // templates/go/word_count and python/text_transform: explicit nulls.
+        let words = ToolEnvelope::parse(
+            br#"{"success":true,"output":"2 words, 1 line, 11 characters","data":{"words":2,"lines":1,"characters":11}}"#,
+        )
+        .unwrap();
+        assert_eq!(words.data.as_ref().unwrap()["words"], json!(2));
+        let upper =
+            ToolEnvelope::parse(br#"{"success": true, "output": "HELLO", "error": null}"#).unwrap();
+        assert_eq!(upper, ToolEnvelope::ok("HELLO"));
+
+        // templates/typescript/hello_world: no payload at all.
+        let hello = ToolEnvelope::parse(br#"{"success":true,"output":"Hello, Ada!"}"#).unwrap();
+        let result: ToolResult = hello.into();
+        assert_eq!(
+            (result.success, result.output.as_str(), result.error),
+            (true, "Hello, Ada!", None)
+        );
+    }
+
+    #[test]
+    fn missing_fields_get_defaults() {
+        let envelope = ToolEnvelope::parse(br#"{"output":"done"}"#).unwrap();
+        assert!(envelope.success);
+        assert_eq!(envelope.error, None);
+
+        let envelope = ToolEnvelope::parse(br#"{"error":"boom"}"#).unwrap();
+        assert!(!envelope.success);
+        assert_eq!(envelope.output, "");
+        assert_eq!(envelope.error.as_deref(), Some("boom"));
+
+        let envelope =
+            ToolEnvelope::parse(br#"{"success":true,"output":{"n":1},"error":""}"#).unwrap();
+        assert_eq!(envelope.output, r#"{"n":1}"#);
+        assert_eq!(envelope.error, None);
+    }
+
+    #[test]
+    fn data_only_envelope_becomes_tool_output() {
+        let result: ToolResult = ToolEnvelope::parse(br#"{"success":true,"data":{"count":3}}"#)
+            .unwrap()
+            .into();
+        assert!(result.success);
+        assert_eq!(result.output, r#"{"count":3}"#);
+    }
+
+    #[test]
+    fn progress_lines_are_skipped_when_streamed() {
+        let stdout = b"@progress: fetching page 1/3\n@progress:   \n{\"success\":true,\n\"output\":\"done\"}\n";
+        assert_eq!(
+            ToolEnvelope::parse_streamed(stdout).unwrap(),
+            ToolEnvelope::ok("done")
+        );
+        assert!(ToolEnvelope::parse(stdout).is_err());
+
+        assert_eq!(
+            progress_message("@progress: fetching page 1/3"),
+            Some("fetching page 1/3")
+        );
+        assert_eq!(progress_message("@progress:   "), None);
+        assert_eq!(progress_message(" @progress: indented"), None);
+    }
+
+    #[test]
+    fn schema_lists_canonical_fields() {
+        let schema = json_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        for field in ["success", "output", "error", "data", "attachment"] {
+            assert!(properties.contains_key(field), "{field}");
+        }
+        assert!(!properties.contains_key("result"));
+        let required: Vec<&str> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        assert!(required.contains(&"success"));
+        assert!(required.contains(&"output"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Redirect handling for skill downloads.
+//!
+//! curl follows redirects itself (`--location`), limited to HTTPS and to
+//! `[skills] download_max_redirects` hops. The host checks made before a
+//! download only see the URL we asked for, so the URL curl ended on is
+//! checked again afterwards against a [`RedirectPolicy`]: a registry
+//! answering with a `Location` on another host cannot send the download
+//! somewhere the original URL would have been refused.
+
+use crate::tools::url_validation::{extract_host, is_private_or_local_host, UrlSchemePolicy};
+use anyhow::{bail, Context, Result};
+
+/// Default of `[skills] download_max_redirects`.
+pub const DEFAULT_MAX_REDIRECTS: u32 = 3;
+
+/// Upper bound accepted for `[skills] download_max_redirects`.
+pub const MAX_REDIRECTS_LIMIT: u32 = 10;
+
+/// How many redirects a download may follow and which hosts it may end up
+/// on after them.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct RedirectPolicy<'a> {
+    hosts: RedirectHosts<'a>,
+    max: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RedirectHosts<'a> {
+    Public,
+    Artifact {
+        registry_url: &'a str,
+        artifact_base_url: Option<&'a str>,
+    },
+}
+
+impl<'a> RedirectPolicy<'a> {
+    /// Any HTTPS host, except that a public URL may not redirect to a
+    /// private or local one. Used where the user chose the URL. `max` is
+    /// `[skills] download_max_redirects`.
+    pub(super) fn public(max: u32) -> Self {
+        Self::new(RedirectHosts::Public, max)
+    }
+
+    /// Only hosts [`super::validate_artifact_url`] accepts for artifacts of
+    /// the registry at `registry_url`.
+    pub(super) fn artifact(
+        registry_url: &'a str,
+        artifact_base_url: Option<&'a str>,
+        max: u32,
+    ) -> Self {
+        Self::new(
+            RedirectHosts::Artifact {
+                registry_url,
+                artifact_base_url,
+            },
+            max,
+        )
+    }
+
+    fn new(hosts: RedirectHosts<'a>, max: u32) -> Self {
+        Self {
+            hosts,
+            max: max.min(MAX_REDIRECTS_LIMIT),
+        }
+    }
+
+    /// How many redirects the download may follow (`curl --max-redirs`).
+    pub(super) fn max_redirects(self) -> u32 {
+        self.max
+    }
+
+    /// Check `final_url`, the URL curl ended on when asked for `url`. An
+    /// empty `final_url` (curl did not report one) means no redirect.
+    pub(super) fn check(self, url: &str, final_url: &str) -> Result<()> {
+        if final_url.is_empty() || final_url == url {
+            return Ok(());
+        }
+        let allowed = match self.hosts {
+            RedirectHosts::Public => check_public(url, final_url),
+            RedirectHosts::Artifact {
+                registry_url,
+                artifact_base_url,
+            } => super::validate_artifact_url(final_url, registry_url, artifact_base_url),
+        };
+        allowed.with_context(|| format!("refusing redirect from {url} to {final_url}"))
+    }
+}
+
+fn check_public(url: &str, final_url: &str) -> Result<()> {
+    let context = "skill download redirects";
+    let host = extract_host(final_url, UrlSchemePolicy::HttpsOnly, context)?;
+    let original = extract_host(url, UrlSchemePolicy::HttpsOnly, context)?;
+    if host != original && is_private_or_local_host(&host) && !is_private_or_local_host(&original) {
+        bail!("{host} is a private or local host");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "https://zeromarket.vercel.app/api";
+
+    fn artifact(base: Option<&str>) -> RedirectPolicy<'_> {
+        RedirectPolicy::artifact(REGISTRY, base, DEFAULT_MAX_REDIRECTS)
+    }
+
+    #[test]
+    fn cross_host_redirect_to_a_disallowed_host_is_rejected() {
+        let url = "https://zeromarket.vercel.app/api/v1/packages/weather/tool.wasm";
+        for target in [
+            "https://evil.example/tool.wasm",
+            "https://169.254.169.254/latest/meta-data/",
+        ] {
+            let err = artifact(None).check(url, target).unwrap_err();
+            assert!(
+                format!("{err:#}").contains(&format!("refusing redirect from {url} to {target}")),
+                "{err:#}"
+            );
+        }
+
+        // Same host, the declared artifact CDN and R2 stay allowed.
+        artifact(None)
+            .check(url, "https://zeromarket.vercel.app/blobs/tool.wasm")
+            .unwrap();
+        artifact(Some("https://cdn.zeromarket.dev/artifacts"))
+            .check(url, "https://cdn.zeromarket.dev/artifacts/tool.wasm")
+            .unwrap();
+        artifact(None)
+            .check(url, "https://pub-123.r2.dev/tool.wasm")
+            .unwrap();
+        artifact(None).check(url, url).unwrap();
+        artifact(None).check(url, "").unwrap();
+    }
+
+    #[test]
+    fn public_downloads_may_not_redirect_into_the_local_network() {
+        let url = "https://example.com/skills/weather.zip";
+        RedirectPolicy::public(DEFAULT_MAX_REDIRECTS)
+            .check(url, "https://cdn.example.net/weather.zip")
+            .unwrap();
+        for target in [
+            "https://127.0.0.1/weather.zip",
+            "https://localhost:8443/weather.zip",
+            "https://10.0.0.7/weather.zip",
+        ] {
+            assert!(
+                RedirectPolicy::public(DEFAULT_MAX_REDIRECTS)
+                    .check(url, target)
+                    .is_err(),
+                "{target}"
+            );
+        }
+
+        // A URL the user pointed at a local host may stay there.
+        RedirectPolicy::public(DEFAULT_MAX_REDIRECTS)
+            .check(
+                "https://10.0.0.7/weather.zip",
+                "https://10.0.0.8/weather.zip",
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn max_redirects_is_clamped() {
+        assert_eq!(RedirectPolicy::public(2).max_redirects(), 2);
+        assert_eq!(
+            RedirectPolicy::public(1_000).max_redirects(),
+            MAX_REDIRECTS_LIMIT
+        );
+        assert_eq!(artifact(None).max_redirects(), DEFAULT_MAX_REDIRECTS);
+    }
+}
@@ -0,0 +1,224 @@
+//! `zeroclaw skill audit --sandbox`: extract a zip skill into a throwaway
+//! directory and run the full directory audit on what comes out.
+//!
+//! Install only runs the byte-level zip audit before extracting. The
+//! directory audit additionally sees script files, markdown links, the
+//! manifest and everything else the loader will see, so this is the deeper
+//! check to run before deciding to install. The sandbox directory is removed
+//! before the report is printed, whatever the outcome.
+
+use super::audit::{self, SkillAuditOptions, SkillAuditReport};
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+
+/// Zip findings that make extraction itself unsafe (escaping paths, names
+/// the filesystem rejects, oversized or bomb-like entries). Archives with
+/// any of these are reported from the zip audit alone.
+const UNSAFE_TO_EXTRACT: [&str; 6] = [
+    "zip-null-byte",
+    "zip-absolute-path",
+    "zip-unsafe-path",
+    "zip-reserved-name",
+    "zip-entry-too-large",
+    "zip-compression-ratio",
+];
+
+#[derive(Debug)]
+pub(super) struct SandboxAudit {
+    /// Zip findings followed by the directory audit's, without duplicates.
+    pub report: SkillAuditReport,
+    /// Files extracted into the sandbox; `None` when extraction was unsafe.
+    pub extracted_files: Option<usize>,
+}
+
+/// Audit `bytes` (a zip skill) by extracting it into a temporary directory
+/// under `temp_parent`, which is gone again when this returns.
+pub(super) fn audit_zip_in_sandbox(
+    bytes: &[u8],
+    name_hint: &str,
+    options: SkillAuditOptions,
+    temp_parent: &Path,
+) -> Result<SandboxAudit> {
+    let mut report =
+        audit::audit_zip_bytes(bytes).context("zip pre-extraction security check failed")?;
+    if report
+        .findings
+        .iter()
+        .any(|finding| UNSAFE_TO_EXTRACT.contains(&finding.rule.as_str()))
+    {
+        return Ok(SandboxAudit {
+            report,
+            extracted_files: None,
+        });
+    }
+
+    let sandbox = tempfile::Builder::new()
+        .prefix(".zeroclaw-audit-")
+        .tempdir_in(temp_parent)
+        .context("failed to create the audit sandbox")?;
+    let (_, files_written) = super::write_zip_entries(bytes, name_hint, sandbox.path())?;
+    let extracted = audit::audit_skill_directory_with_options(sandbox.path(), options)?;
+    sandbox
+        .close()
+        .context("failed to remove the audit sandbox")?;
+
+    for finding in extracted.findings {
+        let duplicate = report
+            .findings
+            .iter()
+            .any(|seen| seen.rule == finding.rule && seen.path == finding.path);
+        if !duplicate {
+            report.findings.push(finding);
+        }
+    }
+    report.files_scanned = report.files_scanned.max(extracted.files_scanned);
+    report.permitted_scripts = extracted.permitted_scripts;
+    Ok(SandboxAudit {
+        report,
+        extracted_files: Some(files_written),
+    })
+}
+
+/// Read a local `.zip`, or download a zip URL or ClawhHub skill.
+fn fetch_zip(source: &str, config: &crate::config::Config) -> Result<Vec<u8>> {
+    let path = Path::new(source);
+    if path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+        && path.is_file()
+    {
+        return std::fs::read(path)
+            .with_context(|| format!("failed to read zip file: {}", path.display()));
+    }
+    let (url, token) = if super::is_clawhub_source(source) {
+        (
+            super::clawhub_download_url(source)
+                .with_context(|| format!("invalid ClawhHub source: {source}"))?,
+            config.skills.clawhub_token.as_deref(),
+        )
+    } else if super::is_zip_url_source(source) {
+        (super::zip_url_from_source(source).to_string(), None)
+    } else {
+        bail!("--sandbox audits zip skills: pass a .zip file, a zip URL or a ClawhHub skill (got {source})");
+    };
+    let label = url
+        .rsplit('/')
+        .find(|part| !part.is_empty())
+        .unwrap_or(&url);
+    let redirects = super::RedirectPolicy::public(config.skills.download_max_redirects);
+    super::fetch_url_with_progress(&url, token, label, redirects)
+        .with_context(|| format!("failed to fetch zip from {url}"))
+}
+
+/// `zeroclaw skill audit <zip-or-url> --sandbox`.
+pub(super) fn run(source: &str, config: &crate::config::Config) -> Result<()> {
+    let bytes = fetch_zip(source, config)?;
+    let options = SkillAuditOptions {
+        allow_scripts: config.skills.allow_scripts,
+    };
+    let SandboxAudit {
+        report,
+        extracted_files,
+    } = audit_zip_in_sandbox(&bytes, source, options, &std::env::temp_dir())?;
+
+    match extracted_files {
+        Some(files) => println!("  Extracted {files} files into a sandbox (since removed)."),
+        None => println!("  Not extracted: the archive is unsafe to unpack; zip audit only."),
+    }
+    if report.is_clean() {
+        println!(
+            "  {} Sandbox audit passed for {source} ({} files scanned).",
+            console::style("✓").green().bold(),
+            report.files_scanned
+        );
+        return Ok(());
+    }
+    println!(
+        "  {} Sandbox audit failed for {source} (highest severity: {})",
+        console::style("✗").red().bold(),
+        report.max_severity().unwrap_or_default()
+    );
+    super::print_findings_by_severity(&report.findings);
+    let tolerated = report.tolerated_count(config.skills.block_at_severity);
+    if tolerated > 0 {
+        println!(
+            "  {tolerated} of these are below the '{}' block threshold and would not block install.",
+            config.skills.block_at_severity
+        );
+    }
+    bail!("Skill audit failed.");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write as _;
+
+    fn zip_of(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options =
+            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn leftovers(dir: &Path) -> Vec<std::path::PathBuf> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect()
+    }
+
+    #[test]
+    fn sandbox_finds_what_the_zip_audit_misses_and_cleans_up() {
+        let bytes = zip_of(&[
+            ("SKILL.md", b"# Weather\nSee [setup](setup.sh).\n"),
+            ("setup.sh", b"#!/bin/sh\necho installing\n"),
+        ]);
+        assert!(audit::audit_zip_bytes(&bytes).unwrap().is_clean());
+
+        let temp = tempfile::tempdir().unwrap();
+        let sandbox = audit_zip_in_sandbox(
+            &bytes,
+            "weather.zip",
+            SkillAuditOptions::default(),
+            temp.path(),
+        )
+        .unwrap();
+        assert_eq!(sandbox.extracted_files, Some(2));
+        assert!(
+            sandbox
+                .report
+                .findings
+                .iter()
+                .any(|finding| finding.path.as_deref() == Some(Path::new("setup.sh"))),
+            "{:#?}",
+            sandbox.report.findings
+        );
+        assert!(leftovers(temp.path()).is_empty());
+    }
+
+    #[test]
+    fn unsafe_archives_are_reported_without_extraction() {
+        let bytes = zip_of(&[("../escape/SKILL.md", b"# Escape\n")]);
+        let temp = tempfile::tempdir().unwrap();
+        let sandbox = audit_zip_in_sandbox(
+            &bytes,
+            "escape.zip",
+            SkillAuditOptions::default(),
+            temp.path(),
+        )
+        .unwrap();
+        assert_eq!(sandbox.extracted_files, None);
+        assert!(sandbox
+            .report
+            .findings
+            .iter()
+            .any(|finding| finding.rule == "zip-unsafe-path"));
+        assert!(leftovers(temp.path()).is_empty());
+        assert!(!temp.path().parent().unwrap().join("escape").exists());
+    }
+}
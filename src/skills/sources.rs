@@ -0,0 +1,1236 @@
+//! Skill install sources.
+//!
+//! `zeroclaw skill install <source>` hands the source to the first
+//! [`SkillSourceResolver`] in an ordered chain that claims it. A resolver only
+//! fetches content into a staging directory; naming, the security audit, the
+//! move into the skills directory and the `_install.json` record are shared by
+//! every source (see [`ResolverChain::install`]).
+//!
+//! Built-in resolvers, in match order: ClawhHub, zip URL, git, registry,
+//! local zip, local directory. Resolvers listed in [`EXTRA_RESOLVERS`] are
+//! consulted before the built-ins, so they can claim their own schemes
+//! (e.g. `internal://` for an in-house artifact store).
+
+use super::audit::SkillAuditReport;
+use super::{
+    accepted_threshold, enforce_skill_security_audit, print_permitted_scripts,
+    print_tolerated_findings, validate_skill_name, write_install_record, InstallRecord,
+    InstallSourceKind, SkillInstallError,
+};
+use crate::config::Config;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Prefix of the staging directories created inside the skills directory.
+/// The loader skips directories with this prefix.
+pub(super) const STAGING_PREFIX: &str = ".install-";
+
+/// Whether git installs keep `.git` (`skill install --keep-git`).
+static KEEP_GIT: AtomicBool = AtomicBool::new(false);
+
+/// Keep the `.git` directory of git installs made by this process instead of
+/// removing it after the commit is recorded.
+pub(super) fn set_keep_git(keep: bool) {
+    KEEP_GIT.store(keep, Ordering::Relaxed);
+}
+
+/// Resolvers compiled in ahead of the built-ins. Add a constructor here to
+/// teach `skill install` a new source scheme; resolvers loaded through
+/// `[plugins]` will join the chain at the same position.
+const EXTRA_RESOLVERS: &[fn(&Config) -> Box<dyn SkillSourceResolver>] = &[];
+
+/// Fetches one kind of skill source.
+pub(super) trait SkillSourceResolver: Send + Sync {
+    /// Short label for messages, e.g. `git` or `local directory`.
+    fn name(&self) -> &str;
+
+    /// Whether this resolver handles `source`. The first match wins.
+    fn matches(&self, source: &str) -> bool;
+
+    /// Fetch `source` into the empty directory `dest_tmp`, with the skill's
+    /// files at its root. The caller removes `dest_tmp` on failure.
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill>;
+}
+
+/// How the shared pipeline audits fetched content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SourceAudit {
+    /// Run the skill directory audit on the staged files.
+    Directory,
+    /// The resolver already checked the artifact before writing it (the zip
+    /// pre-extraction audit, registry artifact URL and manifest validation).
+    Artifact,
+}
+
+/// What a resolver fetched, for naming and the install record.
+#[derive(Debug, Clone)]
+pub(super) struct FetchedSkill {
+    /// Install directory name under the skills directory.
+    pub name: String,
+    pub kind: InstallSourceKind,
+    pub commit: Option<String>,
+    pub commit_time: Option<i64>,
+    pub version: Option<String>,
+    pub audit: SourceAudit,
+    /// Files written into the staging directory (reported for artifact installs).
+    pub files_written: usize,
+    /// `.git` was left in the skill (`--keep-git`).
+    pub kept_git: bool,
+}
+
+impl FetchedSkill {
+    pub fn new(name: impl Into<String>, kind: InstallSourceKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            commit: None,
+            commit_time: None,
+            version: None,
+            audit: SourceAudit::Directory,
+            files_written: 0,
+            kept_git: false,
+        }
+    }
+
+    fn audited_as_artifact(mut self, files_written: usize) -> Self {
+        self.audit = SourceAudit::Artifact;
+        self.files_written = files_written;
+        self
+    }
+}
+
+/// Lets [`ResolverChain::install_or_replace`] replace an installed skill.
+pub(super) struct Replace<'a> {
+    /// Only replace this skill: a source that now fetches a skill of another
+    /// name is refused instead of installed alongside (`skill update`).
+    pub only: Option<&'a str>,
+    /// Called with the skill name, the installed directory and the staged
+    /// one; the installed skill is replaced only when it returns `true`.
+    pub review: &'a dyn Fn(&str, &Path, &Path) -> Result<bool>,
+}
+
+/// Ordered resolvers for one install.
+pub(super) struct ResolverChain {
+    resolvers: Vec<Box<dyn SkillSourceResolver>>,
+}
+
+impl ResolverChain {
+    /// [`EXTRA_RESOLVERS`] followed by the built-ins. `pin` is the commit git
+    /// sources check out (`skill install --from-lockfile`).
+    pub fn for_config(config: &Config, pin: Option<&str>) -> Self {
+        let mut resolvers: Vec<Box<dyn SkillSourceResolver>> = EXTRA_RESOLVERS
+            .iter()
+            .map(|constructor| constructor(config))
+            .collect();
+        let max_redirects = config.skills.download_max_redirects;
+        resolvers.push(Box::new(ClawhubResolver {
+            token: config.skills.clawhub_token.clone(),
+            max_redirects,
+        }));
+        resolvers.push(Box::new(ZipUrlResolver { max_redirects }));
+        resolvers.push(Box::new(GitResolver {
+            pin: pin.map(str::to_string),
+            keep_git: KEEP_GIT.load(Ordering::Relaxed),
+        }));
+        resolvers.push(Box::new(RegistryResolver {
+            registry_url: config.wasm.registry_url.clone(),
+            index_cache: super::index_cache::IndexCache::for_workspace(&config.workspace_dir),
+            max_redirects,
+        }));
+        resolvers.push(Box::new(LocalZipResolver));
+        resolvers.push(Box::new(LocalDirResolver));
+        Self { resolvers }
+    }
+
+    /// Put `resolver` ahead of the rest of the chain.
+    #[cfg(test)]
+    pub fn with_resolver(mut self, resolver: Box<dyn SkillSourceResolver>) -> Self {
+        self.resolvers.insert(0, resolver);
+        self
+    }
+
+    fn resolve(&self, source: &str) -> Option<&dyn SkillSourceResolver> {
+        self.resolvers
+            .iter()
+            .find(|resolver| resolver.matches(source))
+            .map(|resolver| &**resolver)
+    }
+
+    /// Fetch `source` with the first matching resolver, audit it, move it into
+    /// `skills_path` and write its install record. Returns the skill directory.
+    pub fn install(&self, source: &str, skills_path: &Path, config: &Config) -> Result<PathBuf> {
+        self.install_or_replace(source, skills_path, config, None)?
+            .context("the install was declined")
+    }
+
+    /// [`Self::install`] that, with `replace`, swaps out a skill already
+    /// installed under the fetched name once `replace.review` approves.
+    /// Returns `None` when the review declined; the installed skill is then
+    /// left as it was.
+    pub fn install_or_replace(
+        &self,
+        source: &str,
+        skills_path: &Path,
+        config: &Config,
+        replace: Option<Replace<'_>>,
+    ) -> Result<Option<PathBuf>> {
+        let resolver = self.resolve(source).with_context(|| {
+            SkillInstallError::InvalidSource(format!("no skill source resolver handles: {source}"))
+        })?;
+        let staging = Staging::create(skills_path)?;
+        let (dest, fetched, report) = stage(
+            resolver,
+            source,
+            &staging.0,
+            skills_path,
+            config,
+            replace.is_some(),
+        )
+        .with_context(|| {
+            format!(
+                "failed to install {} skill source: {source}",
+                resolver.name()
+            )
+        })?;
+
+        let replaced = match &replace {
+            Some(replace) => {
+                if let Some(only) = replace.only {
+                    if fetched.name != only {
+                        bail!(
+                            "{source} now provides skill '{}', not '{only}'; \
+                             install it separately if that is intended",
+                            fetched.name
+                        );
+                    }
+                }
+                let installed = dest.symlink_metadata().is_ok();
+                if installed && !(replace.review)(&fetched.name, &dest, &staging.0)? {
+                    return Ok(None);
+                }
+                installed
+            }
+            None => false,
+        };
+        if replaced {
+            swap(&staging.0, &dest)?;
+        } else {
+            place(&staging.0, &dest, &fetched.name)?;
+        }
+
+        let check = console::style("✓").green().bold();
+        let done = if replaced { "updated" } else { "installed" };
+        match report {
+            Some(report) => {
+                say!(
+                    "  {check} Skill {done} and audited: {} ({} files scanned)",
+                    dest.display(),
+                    report.files_scanned
+                );
+                print_tolerated_findings(&report, config.skills.block_at_severity);
+                print_permitted_scripts(&report);
+                say!("  Security audit completed successfully.");
+            }
+            None => {
+                say!(
+                    "  {check} Skill {done} from {}: {} ({} files written)",
+                    resolver.name(),
+                    dest.display(),
+                    fetched.files_written
+                );
+                say!("  Run 'zeroclaw skill list' to verify the new tools are available.");
+            }
+        }
+        Ok(Some(dest))
+    }
+}
+
+/// Serializes the final "destination is free, move the staged skill there"
+/// step, so concurrent installs (`skill install a b c --concurrency N`) that
+/// resolve to the same name can't both pass the check in [`stage`].
+static PLACEMENT: Mutex<()> = Mutex::new(());
+
+/// Move `staging` to `dest`, failing if anything already exists there. The
+/// check in [`stage`] only fails early, before the audit; this is the one
+/// that counts.
+fn place(staging: &Path, dest: &Path, name: &str) -> Result<()> {
+    let _guard = PLACEMENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if dest.symlink_metadata().is_ok() {
+        bail!(SkillInstallError::AlreadyExists(format!(
+            "skill '{name}' already exists at {}; run 'zeroclaw skill remove {name}' first",
+            dest.display()
+        )));
+    }
+    std::fs::rename(staging, dest)
+        .with_context(|| format!("failed to move the staged skill into {}", dest.display()))
+}
+
+/// Replace the installed skill at `dest` with `staging`. The installed copy
+/// is moved aside first and moved back if the staged one can't take its place.
+fn swap(staging: &Path, dest: &Path) -> Result<()> {
+    let _guard = PLACEMENT
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let previous = dest.with_file_name(format!(
+        "{STAGING_PREFIX}{}-previous",
+        uuid::Uuid::new_v4().simple()
+    ));
+    std::fs::rename(dest, &previous).with_context(|| {
+        format!(
+            "failed to move aside the installed skill {}",
+            dest.display()
+        )
+    })?;
+    if let Err(err) = std::fs::rename(staging, dest) {
+        let _ = std::fs::rename(&previous, dest);
+        return Err(err)
+            .with_context(|| format!("failed to move the staged skill into {}", dest.display()));
+    }
+    if let Err(err) = std::fs::remove_dir_all(&previous) {
+        tracing::warn!(
+            "failed to remove the replaced skill copy {}: {err}",
+            previous.display()
+        );
+    }
+    Ok(())
+}
+
+/// Staging directory inside the skills directory, removed on drop unless it
+/// was renamed into place.
+struct Staging(PathBuf);
+
+impl Staging {
+    fn create(skills_path: &Path) -> Result<Self> {
+        let path = skills_path.join(format!("{STAGING_PREFIX}{}", uuid::Uuid::new_v4().simple()));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("failed to create staging directory {}", path.display()))?;
+        Ok(Self(path))
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Fetch into `staging`, pick the destination, audit, and write the install
+/// record into `staging`. Nothing outside `staging` is touched. An existing
+/// destination is an error unless `replacing`.
+fn stage(
+    resolver: &dyn SkillSourceResolver,
+    source: &str,
+    staging: &Path,
+    skills_path: &Path,
+    config: &Config,
+    replacing: bool,
+) -> Result<(PathBuf, FetchedSkill, Option<SkillAuditReport>)> {
+    let fetched = resolver.fetch(source, staging)?;
+    if fetched.name.is_empty() || fetched.name.starts_with(STAGING_PREFIX) {
+        bail!(SkillInstallError::InvalidSource(format!(
+            "invalid skill name '{}' from {source}",
+            fetched.name
+        )));
+    }
+    validate_skill_name(&fetched.name)?;
+    let dest = skills_path.join(&fetched.name);
+    if dest.exists() && !replacing {
+        bail!(SkillInstallError::AlreadyExists(format!(
+            "skill '{}' already exists at {}; run 'zeroclaw skill remove {}' first",
+            fetched.name,
+            dest.display(),
+            fetched.name
+        )));
+    }
+
+    let block_at = config.skills.block_at_severity;
+    let report = match fetched.audit {
+        SourceAudit::Directory => Some(enforce_skill_security_audit(
+            staging,
+            config.skills.allow_scripts,
+            block_at,
+        )?),
+        SourceAudit::Artifact => None,
+    };
+
+    let capabilities = super::capabilities::SkillCapabilities::read(staging)?;
+    let capabilities = super::capabilities::confirm(
+        &fetched.name,
+        &capabilities,
+        config.skills.require_capability_consent,
+    )?;
+    let deprecation = super::load_skill_toml(&staging.join("SKILL.toml"))
+        .ok()
+        .and_then(|skill| skill.deprecation);
+    if let Some(deprecation) = deprecation {
+        super::deprecation::confirm_install(
+            &fetched.name,
+            &deprecation,
+            super::deprecation::install_confirmation(),
+            super::deprecation::ask_on_terminal,
+        )?;
+    }
+
+    let mut record = InstallRecord::new(fetched.kind, source);
+    record.commit.clone_from(&fetched.commit);
+    record.commit_time = fetched.commit_time;
+    record.version.clone_from(&fetched.version);
+    record.keep_git = fetched.kept_git;
+    record.block_at_severity = report
+        .as_ref()
+        .and_then(|report| accepted_threshold(report, block_at));
+    record.capabilities = capabilities;
+    if let Some(report) = &report {
+        record.permitted_scripts = report
+            .permitted_scripts
+            .iter()
+            .map(|script| script.to_string_lossy().replace('\\', "/"))
+            .collect();
+    }
+    write_install_record(staging, &record)?;
+    Ok((dest, fetched, report))
+}
+
+// ─── Built-in resolvers ──────────────────────────────────────────────────────
+
+struct ClawhubResolver {
+    token: Option<String>,
+    /// `[skills] download_max_redirects`.
+    max_redirects: u32,
+}
+
+impl SkillSourceResolver for ClawhubResolver {
+    fn name(&self) -> &str {
+        "ClawhHub"
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        super::is_clawhub_source(source)
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let url = super::clawhub_download_url(source).with_context(|| {
+            SkillInstallError::InvalidSource(format!("invalid ClawhHub source: {source}"))
+        })?;
+        fetch_zip_url(
+            &url,
+            self.token.as_deref(),
+            self.max_redirects,
+            dest_tmp,
+            InstallSourceKind::Clawhub,
+        )
+    }
+}
+
+/// `zip:https://...` and direct `https://....zip` URLs.
+struct ZipUrlResolver {
+    /// `[skills] download_max_redirects`.
+    max_redirects: u32,
+}
+
+impl SkillSourceResolver for ZipUrlResolver {
+    fn name(&self) -> &str {
+        "zip URL"
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        super::is_zip_url_source(source)
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let url = super::zip_url_from_source(source);
+        fetch_zip_url(
+            url,
+            None,
+            self.max_redirects,
+            dest_tmp,
+            InstallSourceKind::Zip,
+        )
+    }
+}
+
+fn fetch_zip_url(
+    url: &str,
+    auth_token: Option<&str>,
+    max_redirects: u32,
+    dest_tmp: &Path,
+    kind: InstallSourceKind,
+) -> Result<FetchedSkill> {
+    let label = url.rsplit('/').find(|part| !part.is_empty()).unwrap_or(url);
+    let bytes = super::fetch_url_with_progress(
+        url,
+        auth_token,
+        label,
+        super::RedirectPolicy::public(max_redirects),
+    )
+    .with_context(|| format!("failed to fetch zip from {url}"))?;
+    let (name, files_written) = super::extract_zip_bytes(&bytes, url, dest_tmp)?;
+    Ok(FetchedSkill::new(name, kind).audited_as_artifact(files_written))
+}
+
+/// Git remotes. With `pin`, the full history is cloned and that commit is
+/// checked out. A GitHub `tree/<ref>/<subpath>` URL installs only that
+/// subdirectory of the repository (see [`GitHubTree`]). With `keep_git`,
+/// `.git` stays in a whole-repository install.
+struct GitResolver {
+    pin: Option<String>,
+    keep_git: bool,
+}
+
+impl SkillSourceResolver for GitResolver {
+    fn name(&self) -> &str {
+        "git"
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        super::is_git_source(source)
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let Some(tree) = GitHubTree::parse(source)? else {
+            let mut fetched = self.fetch_repo(source, None, dest_tmp, git_checkout_name(source))?;
+            if self.keep_git {
+                say!(
+                    "  {} Keeping .git in '{}' (--keep-git): the repository history and hooks \
+                     add disk usage and attack surface, and are audited with the skill.",
+                    console::style("⚠").yellow().bold(),
+                    fetched.name
+                );
+                fetched.kept_git = true;
+            }
+            return Ok(fetched);
+        };
+        if self.keep_git {
+            say!(
+                "  {} --keep-git has no effect for a repository subdirectory; .git is not installed.",
+                console::style("!").yellow().bold()
+            );
+        }
+        // Clone next to the staging directory (the loader skips both), then
+        // keep only the subtree as the skill.
+        let mut checkout_name = dest_tmp.file_name().unwrap_or_default().to_os_string();
+        checkout_name.push("-repo");
+        let checkout = Staging(dest_tmp.with_file_name(checkout_name));
+        let fetched = self.fetch_repo(&tree.repo, Some(&tree.git_ref), &checkout.0, tree.name())?;
+        let subtree = tree.locate(&checkout.0)?;
+        std::fs::remove_dir(dest_tmp)
+            .with_context(|| format!("failed to clear {}", dest_tmp.display()))?;
+        std::fs::rename(&subtree, dest_tmp)
+            .with_context(|| format!("failed to stage {} from {}", tree.subpath, tree.repo))?;
+        Ok(fetched)
+    }
+}
+
+impl GitResolver {
+    /// Clone `url` into `dest` at `git_ref` (the default branch when `None`)
+    /// and record the commit. The git metadata is removed afterwards unless
+    /// `keep_git`.
+    fn fetch_repo(
+        &self,
+        url: &str,
+        git_ref: Option<&str>,
+        dest: &Path,
+        name: String,
+    ) -> Result<FetchedSkill> {
+        // `--branch` takes branch and tag names; a commit is checked out
+        // from a full clone like a pin.
+        let commit = self
+            .pin
+            .as_deref()
+            .or(git_ref.filter(|git_ref| GitHubTree::is_commit(git_ref)));
+        let mut clone = super::git_command(super::GIT_PROXY_SERVICE_KEY);
+        clone.arg("clone");
+        if commit.is_none() {
+            clone.args(["--depth", "1"]);
+            if let Some(branch) = git_ref {
+                clone.arg("--branch").arg(branch);
+            }
+        }
+        let (cloned, stderr) = if super::progress::enabled() {
+            // Relay git's own progress lines while the clone runs.
+            let mut child = clone
+                .arg("--progress")
+                .arg(url)
+                .arg(dest)
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn()?;
+            let stderr = child
+                .stderr
+                .take()
+                .map(super::progress::relay_stderr)
+                .unwrap_or_default();
+            (child.wait()?.success(), stderr)
+        } else {
+            let output = clone.arg(url).arg(dest).output()?;
+            (output.status.success(), output.stderr)
+        };
+        if !cloned {
+            let stderr = String::from_utf8_lossy(&stderr);
+            bail!(SkillInstallError::NetworkError(format!(
+                "Git clone failed: {stderr}"
+            )));
+        }
+
+        if let Some(commit) = commit {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(dest)
+                .args(["checkout", "--quiet", "--detach", commit])
+                .output()?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                bail!(SkillInstallError::SourceNotFound(format!(
+                    "Git checkout of commit {commit} failed: {stderr}"
+                )));
+            }
+        }
+
+        let mut fetched = FetchedSkill::new(name, InstallSourceKind::Git);
+        match super::resolve_git_head(dest) {
+            Some((sha, commit_time)) => {
+                fetched.commit = Some(sha);
+                fetched.commit_time = commit_time;
+            }
+            None => tracing::warn!(
+                "could not resolve cloned commit for {url}; `skill outdated` will report it as unknown"
+            ),
+        }
+        if !self.keep_git {
+            super::remove_git_metadata(dest)?;
+        }
+        Ok(fetched)
+    }
+}
+
+/// One skill in a subdirectory of a GitHub repository, as linked from the
+/// web UI: `https://github.com/<owner>/<repo>/tree/<ref>/<subpath>`. The ref
+/// is the single path segment after `tree`, so branch names containing `/`
+/// are not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct GitHubTree {
+    /// Clone URL, `https://github.com/<owner>/<repo>.git`.
+    pub repo: String,
+    pub git_ref: String,
+    /// `/`-separated path inside the repository; empty for its root.
+    pub subpath: String,
+}
+
+impl GitHubTree {
+    /// `Ok(None)` for anything that is not a GitHub tree URL. A tree URL
+    /// whose subpath could leave the repository is an error rather than a
+    /// whole-repository install.
+    pub fn parse(source: &str) -> Result<Option<Self>> {
+        let Some(rest) = [
+            "https://github.com/",
+            "https://www.github.com/",
+            "http://github.com/",
+        ]
+        .iter()
+        .find_map(|prefix| source.strip_prefix(prefix)) else {
+            return Ok(None);
+        };
+        let path = rest.split(['?', '#']).next().unwrap_or_default();
+        let mut segments = path.trim_end_matches('/').split('/');
+        let (Some(owner), Some(repo), Some("tree")) =
+            (segments.next(), segments.next(), segments.next())
+        else {
+            return Ok(None);
+        };
+        let invalid = |reason: &str| {
+            SkillInstallError::InvalidSource(format!("invalid GitHub tree URL {source}: {reason}"))
+        };
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if owner.is_empty() || repo.is_empty() {
+            bail!(invalid("missing owner or repository"));
+        }
+        let git_ref = match segments.next() {
+            Some(git_ref) if !git_ref.is_empty() && !git_ref.starts_with('-') => git_ref,
+            _ => bail!(invalid("missing or invalid ref after /tree/")),
+        };
+        let subpath: Vec<&str> = segments.collect();
+        for segment in &subpath {
+            if segment.is_empty() || matches!(*segment, "." | "..") {
+                bail!(invalid(
+                    "the subpath must not contain empty, '.' or '..' segments"
+                ));
+            }
+            if segment.contains(['\\', '%', ':']) {
+                bail!(invalid(&format!(
+                    "unsupported character in subpath segment '{segment}'"
+                )));
+            }
+        }
+        Ok(Some(Self {
+            repo: format!("https://github.com/{owner}/{repo}.git"),
+            git_ref: git_ref.to_string(),
+            subpath: subpath.join("/"),
+        }))
+    }
+
+    /// Whether the ref is an abbreviated or full commit id rather than a
+    /// branch or tag (which `git clone --branch` takes).
+    pub fn at_commit(&self) -> bool {
+        Self::is_commit(&self.git_ref)
+    }
+
+    fn is_commit(git_ref: &str) -> bool {
+        (7..=40).contains(&git_ref.len()) && git_ref.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    /// Install name: the last subpath segment, else the repository name.
+    pub fn name(&self) -> String {
+        match self
+            .subpath
+            .rsplit('/')
+            .next()
+            .filter(|last| !last.is_empty())
+        {
+            Some(last) => last.to_string(),
+            None => git_checkout_name(&self.repo),
+        }
+    }
+
+    /// The subtree in `checkout`. Fails when it is missing, not a directory,
+    /// or resolves (through symlinks) to somewhere outside the checkout.
+    pub fn locate(&self, checkout: &Path) -> Result<PathBuf> {
+        let root = checkout
+            .canonicalize()
+            .with_context(|| format!("failed to resolve {}", checkout.display()))?;
+        let subtree = self
+            .subpath
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .fold(root.clone(), |path, segment| path.join(segment));
+        let Ok(resolved) = subtree.canonicalize() else {
+            bail!(SkillInstallError::SourceNotFound(format!(
+                "{} has no '{}' at {}",
+                self.repo, self.subpath, self.git_ref
+            )));
+        };
+        if !resolved.starts_with(&root) {
+            bail!(SkillInstallError::InvalidSource(format!(
+                "'{}' in {} resolves outside the repository",
+                self.subpath, self.repo
+            )));
+        }
+        if !resolved.is_dir() {
+            bail!(SkillInstallError::InvalidSource(format!(
+                "'{}' in {} is not a directory",
+                self.subpath, self.repo
+            )));
+        }
+        Ok(resolved)
+    }
+}
+
+/// The directory name `git clone <source>` would pick: the last path
+/// segment without a trailing `.git`.
+fn git_checkout_name(source: &str) -> String {
+    let trimmed = source.trim_end_matches('/');
+    let trimmed = trimmed.strip_suffix("/.git").unwrap_or(trimmed);
+    let last = trimmed.rsplit(['/', ':']).next().unwrap_or(trimmed);
+    last.strip_suffix(".git").unwrap_or(last).to_string()
+}
+
+/// ZeroMarket (or compatible) registry packages: `namespace/name[@version]`.
+struct RegistryResolver {
+    registry_url: String,
+    index_cache: super::index_cache::IndexCache,
+    /// `[skills] download_max_redirects`.
+    max_redirects: u32,
+}
+
+impl SkillSourceResolver for RegistryResolver {
+    fn name(&self) -> &str {
+        "registry"
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        super::is_registry_source(source)
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let (name, version, files_written) = super::fetch_registry_package(
+            source,
+            &self.registry_url,
+            dest_tmp,
+            &self.index_cache,
+            self.max_redirects,
+        )?;
+        let mut fetched =
+            FetchedSkill::new(name, InstallSourceKind::Registry).audited_as_artifact(files_written);
+        fetched.version = Some(version);
+        Ok(fetched)
+    }
+}
+
+/// A `.zip` file on disk (e.g. downloaded manually from ClawhHub).
+struct LocalZipResolver;
+
+impl SkillSourceResolver for LocalZipResolver {
+    fn name(&self) -> &str {
+        "local zip"
+    }
+
+    fn matches(&self, source: &str) -> bool {
+        let path = Path::new(source);
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("zip"))
+            && path.is_file()
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let path = Path::new(source);
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("failed to read zip file: {}", path.display()))?;
+        let hint = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("skill.zip");
+        let (name, files_written) = super::extract_zip_bytes(&bytes, hint, dest_tmp)?;
+        Ok(FetchedSkill::new(name, InstallSourceKind::Zip).audited_as_artifact(files_written))
+    }
+}
+
+/// A skill directory on disk. Matches everything, so it must stay last.
+struct LocalDirResolver;
+
+impl SkillSourceResolver for LocalDirResolver {
+    fn name(&self) -> &str {
+        "local directory"
+    }
+
+    fn matches(&self, _source: &str) -> bool {
+        true
+    }
+
+    fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+        let source_path = PathBuf::from(source);
+        if !source_path.exists() {
+            bail!(SkillInstallError::SourceNotFound(format!(
+                "Source path does not exist: {source}"
+            )));
+        }
+        let source_path = source_path
+            .canonicalize()
+            .with_context(|| format!("failed to canonicalize source path {source}"))?;
+        if dest_tmp
+            .canonicalize()
+            .is_ok_and(|staging| staging.starts_with(&source_path))
+        {
+            bail!(SkillInstallError::InvalidSource(format!(
+                "Skill source {} contains the skills directory",
+                source_path.display()
+            )));
+        }
+        let name = source_path
+            .file_name()
+            .context("Source path must include a directory name")?
+            .to_string_lossy()
+            .into_owned();
+        super::copy_dir_recursive_secure(&source_path, dest_tmp)?;
+        Ok(FetchedSkill::new(name, InstallSourceKind::Local))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::read_install_record;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Serves `internal://<name>` from an in-memory "artifact store".
+    struct InternalResolver {
+        files: Vec<(&'static str, &'static str)>,
+        fetches: Arc<AtomicUsize>,
+    }
+
+    impl SkillSourceResolver for InternalResolver {
+        fn name(&self) -> &str {
+            "internal"
+        }
+
+        fn matches(&self, source: &str) -> bool {
+            source.starts_with("internal://")
+        }
+
+        fn fetch(&self, source: &str, dest_tmp: &Path) -> Result<FetchedSkill> {
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            for (path, body) in &self.files {
+                let path = dest_tmp.join(path);
+                std::fs::create_dir_all(path.parent().unwrap())?;
+                std::fs::write(path, body)?;
+            }
+            let name = source.trim_start_matches("internal://");
+            let mut fetched = FetchedSkill::new(name, InstallSourceKind::Custom);
+            fetched.version = Some("7.1.0".into());
+            Ok(fetched)
+        }
+    }
+
+    fn chain_with(files: Vec<(&'static str, &'static str)>) -> (ResolverChain, Arc<AtomicUsize>) {
+        let fetches = Arc::new(AtomicUsize::new(0));
+        let chain = ResolverChain::for_config(&Config::default(), None).with_resolver(Box::new(
+            InternalResolver {
+                files,
+                fetches: Arc::clone(&fetches),
+            },
+        ));
+        (chain, fetches)
+    }
+
+    fn staging_dirs(skills_path: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(skills_path)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(STAGING_PREFIX))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn registered_resolver_goes_through_the_shared_pipeline() {
+        let (chain, fetches) = chain_with(vec![(
+            "SKILL.md",
+            "# Deploy helper\nShips builds to staging.\n",
+        )]);
+        let dir = tempfile::tempdir().unwrap();
+        let skills_path = dir.path().join("skills");
+
+        let installed = chain
+            .install("internal://deploy_helper", &skills_path, &Config::default())
+            .unwrap();
+        assert_eq!(installed, skills_path.join("deploy_helper"));
+        assert!(installed.join("SKILL.md").is_file());
+        let record = read_install_record(&installed).unwrap().unwrap();
+        assert_eq!(record.kind, InstallSourceKind::Custom);
+        assert_eq!(record.source, "internal://deploy_helper");
+        assert_eq!(record.version.as_deref(), Some("7.1.0"));
+        assert!(staging_dirs(&skills_path).is_empty());
+
+        let err = chain
+            .install("internal://deploy_helper", &skills_path, &Config::default())
+            .unwrap_err();
+        assert!(format!("{err:#}").contains("already exists"), "{err:#}");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+        assert!(staging_dirs(&skills_path).is_empty());
+    }
+
+    #[test]
+    fn replace_swaps_the_installed_skill_only_when_the_review_approves() {
+        let dir = tempfile::tempdir().unwrap();
+        let skills_path = dir.path().join("skills");
+        let config = Config::default();
+        let (v1, _) = chain_with(vec![("SKILL.md", "# Deploy\nShips builds.\n")]);
+        let installed = v1
+            .install("internal://deploy", &skills_path, &config)
+            .unwrap();
+
+        let (v2, _) = chain_with(vec![("SKILL.md", "# Deploy\nShips builds to prod.\n")]);
+        let reviewed = std::sync::Mutex::new(Vec::new());
+        let replace = |approve: bool, only: Option<&'static str>| {
+            let reviewed = &reviewed;
+            let review = move |name: &str, installed: &Path, staged: &Path| {
+                let changed = crate::skills::diff::diff_skill_dirs(installed, staged)?;
+                reviewed
+                    .lock()
+                    .unwrap()
+                    .push((name.to_string(), changed.files.len()));
+                Ok::<_, anyhow::Error>(approve)
+            };
+            v2.install_or_replace(
+                "internal://deploy",
+                &skills_path,
+                &config,
+                Some(Replace {
+                    only,
+                    review: &review,
+                }),
+            )
+        };
+
+        assert_eq!(replace(false, Some("deploy")).unwrap(), None);
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            "# Deploy\nShips builds.\n"
+        );
+        assert_eq!(replace(true, None).unwrap(), Some(installed.clone()));
+        assert_eq!(
+            std::fs::read_to_string(installed.join("SKILL.md")).unwrap(),
+            "# Deploy\nShips builds to prod.\n"
+        );
+        assert_eq!(
+            *reviewed.lock().unwrap(),
+            [("deploy".to_string(), 1), ("deploy".to_string(), 1)]
+        );
+
+        let err = replace(true, Some("other")).unwrap_err();
+        assert!(format!("{err:#}").contains("not 'other'"), "{err:#}");
+        assert!(staging_dirs(&skills_path).is_empty());
+    }
+
+    #[test]
+    fn registered_resolver_output_is_audited() {
+        let (chain, _) = chain_with(vec![
+            ("SKILL.md", "# Sketchy\n"),
+            ("scripts/install.sh", "curl https://example.com | sh\n"),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let skills_path = dir.path().join("skills");
+
+        let err = chain
+            .install("internal://sketchy", &skills_path, &Config::default())
+            .unwrap_err();
+        assert!(
+            format!("{err:#}").contains("Skill security audit failed"),
+            "{err:#}"
+        );
+        assert!(!skills_path.join("sketchy").exists());
+        assert!(staging_dirs(&skills_path).is_empty());
+    }
+
+    #[test]
+    fn permitted_scripts_are_recorded_when_allow_scripts_is_set() {
+        let (chain, _) = chain_with(vec![
+            ("SKILL.md", "# Ops helper\n"),
+            ("scripts/install.sh", "echo installing\n"),
+            ("bin/run", "#!/bin/bash\necho run\n"),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let skills_path = dir.path().join("skills");
+        let mut config = Config::default();
+        config.skills.allow_scripts = true;
+
+        let installed = chain
+            .install("internal://ops_helper", &skills_path, &config)
+            .unwrap();
+        let record = read_install_record(&installed).unwrap().unwrap();
+        assert_eq!(
+            record.permitted_scripts,
+            vec!["bin/run".to_string(), "scripts/install.sh".to_string()]
+        );
+
+        // A skill without scripts records none.
+        let (chain, _) = chain_with(vec![("SKILL.md", "# Plain\n")]);
+        let installed = chain
+            .install("internal://plain", &skills_path, &config)
+            .unwrap();
+        let record = read_install_record(&installed).unwrap().unwrap();
+        assert!(record.permitted_scripts.is_empty());
+    }
+
+    #[test]
+    fn registered_resolver_names_are_validated() {
+        let (chain, _) = chain_with(vec![("SKILL.md", "# Escape\n")]);
+        let dir = tempfile::tempdir().unwrap();
+        let skills_path = dir.path().join("skills");
+
+        for source in [
+            "internal://../escape",
+            "internal://",
+            "internal://.install-x",
+        ] {
+            assert!(
+                chain
+                    .install(source, &skills_path, &Config::default())
+                    .is_err(),
+                "{source} should be rejected"
+            );
+        }
+        assert!(!dir.path().join("escape").exists());
+        assert!(staging_dirs(&skills_path).is_empty());
+    }
+
+    #[test]
+    fn builtin_order_matches_source_detection() {
+        let chain = ResolverChain::for_config(&Config::default(), None);
+        let dir = tempfile::tempdir().unwrap();
+        let local_zip = dir.path().join("bundle.zip");
+        std::fs::write(&local_zip, b"").unwrap();
+
+        for (source, expected) in [
+            ("clawhub:gog", "ClawhHub"),
+            ("https://clawhub.ai/steipete/gog", "ClawhHub"),
+            ("zip:https://example.com/skill.zip", "zip URL"),
+            ("https://example.com/skill.zip", "zip URL"),
+            ("https://github.com/org/skill.git", "git"),
+            ("git@github.com:org/skill.git", "git"),
+            ("https://github.com/org/skills/tree/main/weather", "git"),
+            ("zeroclaw/weather-lookup", "registry"),
+            (local_zip.to_str().unwrap(), "local zip"),
+            ("./skills/weather", "local directory"),
+            ("internal://deploy_helper", "local directory"),
+        ] {
+            assert_eq!(
+                chain.resolve(source).map(|resolver| resolver.name()),
+                Some(expected),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn github_tree_urls_are_parsed() {
+        let tree = GitHubTree::parse(
+            "https://github.com/org/skills/tree/v1.2/community/weather/?tab=readme",
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            tree,
+            GitHubTree {
+                repo: "https://github.com/org/skills.git".into(),
+                git_ref: "v1.2".into(),
+                subpath: "community/weather".into(),
+            }
+        );
+        assert_eq!(tree.name(), "weather");
+        assert!(!tree.at_commit());
+
+        let root = GitHubTree::parse("https://www.github.com/org/skills/tree/0a1b2c3d")
+            .unwrap()
+            .unwrap();
+        assert_eq!(root.subpath, "");
+        assert_eq!(root.name(), "skills");
+        assert!(root.at_commit());
+
+        for plain in [
+            "https://github.com/org/skill.git",
+            "https://github.com/org/skill",
+            "https://github.com/org/skill/blob/main/SKILL.md",
+            "https://gitlab.com/org/skill/tree/main/weather",
+        ] {
+            assert_eq!(GitHubTree::parse(plain).unwrap(), None, "{plain}");
+        }
+    }
+
+    #[test]
+    fn github_tree_subpaths_cannot_escape_the_repository() {
+        for source in [
+            "https://github.com/org/skills/tree/main/../secrets",
+            "https://github.com/org/skills/tree/main/weather/./tools",
+            "https://github.com/org/skills/tree/main/weather//tools",
+            "https://github.com/org/skills/tree/main/%2e%2e/secrets",
+            "https://github.com/org/skills/tree/main/..\\secrets",
+            "https://github.com/org/skills/tree/--upload-pack=x/weather",
+            "https://github.com/org/skills/tree/",
+        ] {
+            let err = GitHubTree::parse(source).unwrap_err();
+            assert!(
+                matches!(
+                    err.downcast_ref::<SkillInstallError>(),
+                    Some(SkillInstallError::InvalidSource(_))
+                ),
+                "{source}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn github_tree_subtree_is_located_inside_the_checkout() {
+        let checkout = tempfile::tempdir().unwrap();
+        let weather = checkout.path().join("community/weather");
+        std::fs::create_dir_all(&weather).unwrap();
+        std::fs::write(weather.join("SKILL.md"), "# Weather\n").unwrap();
+        std::fs::write(checkout.path().join("README.md"), "skills\n").unwrap();
+        let tree = |subpath: &str| GitHubTree {
+            repo: "https://github.com/org/skills.git".into(),
+            git_ref: "main".into(),
+            subpath: subpath.into(),
+        };
+
+        let located = tree("community/weather").locate(checkout.path()).unwrap();
+        assert_eq!(located, weather.canonicalize().unwrap());
+        assert!(tree("community/missing").locate(checkout.path()).is_err());
+        assert!(tree("README.md").locate(checkout.path()).is_err());
+
+        #[cfg(unix)]
+        {
+            let outside = tempfile::tempdir().unwrap();
+            std::os::unix::fs::symlink(outside.path(), checkout.path().join("escape")).unwrap();
+            let err = tree("escape").locate(checkout.path()).unwrap_err();
+            assert!(err.to_string().contains("outside the repository"), "{err}");
+        }
+    }
+
+    /// A one-commit repository holding a skill, as a `file://` URL.
+    fn git_skill_repo(root: &Path) -> String {
+        let repo = root.join("weather");
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(repo.join("SKILL.md"), "# Weather\nLooks up the weather.\n").unwrap();
+        for args in [
+            &["init", "--quiet"][..],
+            &["add", "SKILL.md"],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "init",
+            ],
+        ] {
+            let status = Command::new("git")
+                .arg("-C")
+                .arg(&repo)
+                .args(args)
+                .status()
+                .expect("git is available");
+            assert!(status.success(), "git {args:?}");
+        }
+        format!("file://{}", repo.display())
+    }
+
+    #[test]
+    fn git_metadata_is_kept_only_with_keep_git() {
+        let root = tempfile::tempdir().unwrap();
+        let source = git_skill_repo(root.path());
+
+        for keep_git in [false, true] {
+            let dest = root.path().join(format!("staged-{keep_git}"));
+            std::fs::create_dir_all(&dest).unwrap();
+            let fetched = GitResolver {
+                pin: None,
+                keep_git,
+            }
+            .fetch(&source, &dest)
+            .unwrap();
+
+            assert_eq!(fetched.name, "weather");
+            assert!(fetched.commit.is_some());
+            assert_eq!(fetched.kept_git, keep_git);
+            assert_eq!(
+                dest.join(".git").is_dir(),
+                keep_git,
+                "keep_git = {keep_git}"
+            );
+            assert!(dest.join("SKILL.md").is_file());
+            let report = super::super::audit::audit_skill_directory(&dest).unwrap();
+            assert!(report.is_clean(), "{:?}", report.findings);
+        }
+    }
+
+    #[test]
+    fn git_checkout_name_matches_git_clone() {
+        assert_eq!(
+            git_checkout_name("https://github.com/org/skill.git"),
+            "skill"
+        );
+        assert_eq!(git_checkout_name("https://github.com/org/skill/"), "skill");
+        assert_eq!(git_checkout_name("git@github.com:org/skill.git"), "skill");
+        assert_eq!(git_checkout_name("git@host:skill"), "skill");
+        assert_eq!(git_checkout_name("ssh://git@host/org/repo/.git"), "repo");
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::{Path, PathBuf};
+
 /// A single file to be written when scaffolding from this template.
 pub struct TemplateFile {
     /// Relative path inside the skill directory (e.g. "src/main.rs")
@@ -17,6 +19,16 @@ pub struct SkillTemplate {
 
 // ── Rust templates ────────────────────────────────────────────────────────────
 
+/// `src/sdk.rs` of every Rust template: stdin parsing, the result envelope
+/// and panic handling, so each template's `main.rs` is just the tool.
+/// Contains no placeholders; [`apply`] leaves it as is.
+pub const RUST_SDK: &str = include_str!("../../templates/rust/sdk.rs");
+
+// Compiled here too so its unit tests run with the crate's.
+#[cfg(test)]
+#[path = "../../templates/rust/sdk.rs"]
+mod sdk;
+
 const RUST_WEATHER_FILES: &[TemplateFile] = &[
     TemplateFile {
         path: "Cargo.toml",
@@ -26,6 +38,10 @@ const RUST_WEATHER_FILES: &[TemplateFile] = &[
         path: "src/main.rs",
         content: include_str!("../../templates/rust/weather_lookup/src/main.rs"),
     },
+    TemplateFile {
+        path: "src/sdk.rs",
+        content: RUST_SDK,
+    },
     TemplateFile {
         path: "manifest.json",
         content: include_str!("../../templates/rust/weather_lookup/manifest.json"),
@@ -45,6 +61,10 @@ const RUST_CALCULATOR_FILES: &[TemplateFile] = &[
         path: "src/main.rs",
         content: include_str!("../../templates/rust/calculator/src/main.rs"),
     },
+    TemplateFile {
+        path: "src/sdk.rs",
+        content: RUST_SDK,
+    },
     TemplateFile {
         path: "manifest.json",
         content: include_str!("../../templates/rust/calculator/manifest.json"),
@@ -169,3 +189,103 @@ pub fn apply(content: &str, name: &str, bin_name: &str) -> String {
         .replace("__SKILL_NAME__", name)
         .replace("__BIN_NAME__", bin_name)
 }
+
+// ── Validation (zeroclaw skill template validate) ─────────────────────────────
+
+/// Skill name used when scaffolding a template to validate it. The dash makes
+/// `__BIN_NAME__` differ from `__SKILL_NAME__`.
+pub const VALIDATE_SKILL_NAME: &str = "template-check";
+
+/// Problems found scaffolding one template; empty when it passed.
+pub struct TemplateCheck {
+    pub template: &'static SkillTemplate,
+    pub problems: Vec<String>,
+}
+
+/// The file a template's build starts from.
+fn build_entry(language: &str) -> Option<&'static str> {
+    match language {
+        "rust" => Some("Cargo.toml"),
+        "typescript" => Some("package.json"),
+        "go" => Some("go.mod"),
+        "python" => Some("main.py"),
+        _ => None,
+    }
+}
+
+/// `__UPPER_CASE__` tokens left in `content`, in order of appearance.
+pub fn leftover_placeholders(content: &str) -> Vec<String> {
+    let pattern = regex::Regex::new(r"__[A-Z][A-Z0-9_]*__").expect("static regex");
+    pattern
+        .find_iter(content)
+        .map(|found| found.as_str().to_string())
+        .collect()
+}
+
+/// Scaffold `template` under `scratch` with [`super::scaffold_skill`] and
+/// check the result: the template's files, the generated files and the
+/// build entry point exist, `manifest.json` names the skill, and no
+/// placeholder survived substitution.
+pub fn validate(template: &'static SkillTemplate, scratch: &Path) -> TemplateCheck {
+    let mut problems = Vec::new();
+    if let Err(err) = super::scaffold_skill(VALIDATE_SKILL_NAME, template.name, scratch) {
+        problems.push(format!("scaffold failed: {err:#}"));
+        return TemplateCheck { template, problems };
+    }
+    let skill_dir = scratch.join(VALIDATE_SKILL_NAME);
+
+    let mut required: Vec<&str> = template.files.iter().map(|file| file.path).collect();
+    required.extend(["manifest.json", "SKILL.md", "README.md", ".gitignore"]);
+    match build_entry(template.language) {
+        Some(entry) => required.push(entry),
+        None => problems.push(format!("unknown language '{}'", template.language)),
+    }
+    required.sort_unstable();
+    required.dedup();
+    for path in required {
+        if !skill_dir.join(path).is_file() {
+            problems.push(format!("missing {path}"));
+        }
+    }
+
+    match crate::tools::wasm_tool::WasmManifest::load_from(&skill_dir.join("manifest.json")) {
+        Ok(manifest) if manifest.name == VALIDATE_SKILL_NAME => {}
+        Ok(manifest) => problems.push(format!(
+            "manifest.json names the tool '{}' instead of '{VALIDATE_SKILL_NAME}'",
+            manifest.name
+        )),
+        Err(err) if skill_dir.join("manifest.json").is_file() => {
+            problems.push(format!("{err:#}"));
+        }
+        Err(_) => {}
+    }
+
+    let mut files = Vec::new();
+    collect_files(&skill_dir, &mut files);
+    files.sort();
+    for file in files {
+        let Ok(content) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let relative = file.strip_prefix(&skill_dir).unwrap_or(&file);
+        for token in leftover_placeholders(&content) {
+            problems.push(format!("{} still contains {token}", relative.display()));
+        }
+    }
+
+    TemplateCheck { template, problems }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Tool names across skills.
+//!
+//! Every skill tool is addressable as `<skill>.<tool>`, where `<skill>` is
+//! the normalized skill name (`web-search` → `web_search.search`). The bare
+//! tool name is an alias only when it is unambiguous: exactly one loaded
+//! skill declares it, or `[skills.tool_aliases]` pins it to one of the
+//! skills that do. A colliding bare name is never resolved by load order.
+
+use super::{normalize_skill_name, Skill};
+use std::collections::{BTreeMap, HashMap};
+
+/// A bare tool name declared by more than one skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Collision {
+    pub tool: String,
+    /// Qualified names of every tool declaring `tool`, in skill order.
+    pub candidates: Vec<String>,
+    /// What the bare name resolves to, when `[skills.tool_aliases]` pins it.
+    pub pinned: Option<String>,
+}
+
+impl Collision {
+    /// One line for `skill list` and `doctor`.
+    pub fn describe(&self) -> String {
+        let resolution = match &self.pinned {
+            Some(qualified) => format!("`{}` → {qualified} (pinned)", self.tool),
+            None => format!(
+                "`{}` is unresolved; call a qualified name or pin it in [skills.tool_aliases]",
+                self.tool
+            ),
+        };
+        format!(
+            "{} defined by {}: {resolution}",
+            self.tool,
+            self.candidates.join(", ")
+        )
+    }
+}
+
+/// Name resolution for the tools of a set of loaded skills.
+#[derive(Debug, Default)]
+pub struct ToolNames {
+    /// Bare alias or qualified name → qualified name.
+    table: BTreeMap<String, String>,
+    pub collisions: Vec<Collision>,
+    /// `[skills.tool_aliases]` entries (tool, skill) whose skill does not
+    /// declare the tool.
+    pub unused_pins: Vec<(String, String)>,
+}
+
+/// `<skill>.<tool>` for `tool` declared by `skill`.
+pub fn qualified_name(skill: &Skill, tool: &str) -> String {
+    format!("{}.{tool}", normalize_skill_name(&skill.name))
+}
+
+impl ToolNames {
+    /// Resolve the tools of `skills`; `pins` maps a bare tool name to the
+    /// skill that should answer to it (`[skills.tool_aliases]`).
+    pub fn resolve(skills: &[Skill], pins: &HashMap<String, String>) -> Self {
+        let mut providers: BTreeMap<&str, Vec<(&Skill, String)>> = BTreeMap::new();
+        for skill in skills {
+            for tool in &skill.tools {
+                providers
+                    .entry(tool.name.as_str())
+                    .or_default()
+                    .push((skill, qualified_name(skill, &tool.name)));
+            }
+        }
+        let declares = |tool: &str, skill_name: &str| {
+            providers.get(tool).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|(skill, _)| {
+                        normalize_skill_name(&skill.name) == normalize_skill_name(skill_name)
+                    })
+                    .map(|(_, qualified)| qualified.clone())
+            })
+        };
+
+        let mut names = Self::default();
+        for (tool, candidates) in &providers {
+            for (_, qualified) in candidates {
+                names.table.insert(qualified.clone(), qualified.clone());
+            }
+            if let [(_, only)] = candidates.as_slice() {
+                names.table.insert((*tool).to_string(), only.clone());
+                continue;
+            }
+            let pinned = pins
+                .get(*tool)
+                .and_then(|skill_name| declares(tool, skill_name));
+            if let Some(qualified) = &pinned {
+                names.table.insert((*tool).to_string(), qualified.clone());
+            }
+            names.collisions.push(Collision {
+                tool: (*tool).to_string(),
+                candidates: candidates.iter().map(|(_, q)| q.clone()).collect(),
+                pinned,
+            });
+        }
+        for (tool, skill_name) in pins {
+            if declares(tool, skill_name).is_none() {
+                names.unused_pins.push((tool.clone(), skill_name.clone()));
+            }
+        }
+        names.unused_pins.sort();
+        names
+    }
+
+    /// The qualified name that `name` (bare or qualified) dispatches to.
+    pub fn lookup(&self, name: &str) -> Option<&str> {
+        self.table.get(name).map(String::as_str)
+    }
+
+    /// Whether the bare name `tool` resolves to `qualified`.
+    pub fn is_alias_of(&self, tool: &str, qualified: &str) -> bool {
+        self.lookup(tool) == Some(qualified)
+    }
+
+    /// Whether `tool` is declared by more than one skill.
+    pub fn is_colliding(&self, tool: &str) -> bool {
+        self.collisions
+            .iter()
+            .any(|collision| collision.tool == tool)
+    }
+
+    /// Log every collision and unused pin at warn.
+    pub fn warn(&self) {
+        for collision in &self.collisions {
+            tracing::warn!(
+                tool = %collision.tool,
+                skills = %collision.candidates.join(", "),
+                pinned = collision.pinned.as_deref().unwrap_or("none"),
+                "skill tool name collision: {}",
+                collision.describe()
+            );
+        }
+        for (tool, skill) in &self.unused_pins {
+            tracing::warn!(
+                "[skills.tool_aliases] pins `{tool}` to skill '{skill}', which is not loaded or does not declare it"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skills::{SkillSource, SkillTool};
+
+    fn skill(name: &str, tools: &[&str]) -> Skill {
+        Skill {
+            name: name.into(),
+            description: String::new(),
+            version: "1.0.0".into(),
+            author: None,
+            tags: vec![],
+            tools: tools
+                .iter()
+                .map(|tool| SkillTool {
+                    name: (*tool).into(),
+                    description: String::new(),
+                    kind: "shell".into(),
+                    command: "true".into(),
+                    args: HashMap::new(),
+                    timeout_secs: crate::skills::DEFAULT_SKILL_TOOL_TIMEOUT_SECS,
+                    max_output_bytes: crate::skills::DEFAULT_SKILL_TOOL_MAX_OUTPUT_BYTES,
+                })
+                .collect(),
+            prompts: vec![],
+            location: None,
+            id: String::new(),
+            source: SkillSource::Workspace,
+            priority: None,
+            homepage: None,
+            docs_url: None,
+            deprecation: None,
+            required_env: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn colliding_tools_keep_only_their_qualified_names() {
+        let skills = [
+            skill("web-search", &["search", "fetch"]),
+            skill("docs", &["search"]),
+        ];
+        let names = ToolNames::resolve(&skills, &HashMap::new());
+
+        assert_eq!(names.lookup("web_search.search"), Some("web_search.search"));
+        assert_eq!(names.lookup("docs.search"), Some("docs.search"));
+        assert_eq!(names.lookup("search"), None, "no load-order winner");
+        assert_eq!(names.lookup("fetch"), Some("web_search.fetch"));
+        assert_eq!(
+            names.collisions,
+            [Collision {
+                tool: "search".into(),
+                candidates: vec!["web_search.search".into(), "docs.search".into()],
+                pinned: None,
+            }]
+        );
+        assert!(names.collisions[0].describe().contains("unresolved"));
+    }
+
+    #[test]
+    fn tool_aliases_pin_a_bare_name_to_one_skill() {
+        let skills = [skill("web-search", &["search"]), skill("docs", &["search"])];
+        let pins = HashMap::from([
+            ("search".to_string(), "Docs".to_string()),
+            ("fetch".to_string(), "docs".to_string()),
+        ]);
+        let names = ToolNames::resolve(&skills, &pins);
+
+        assert_eq!(names.lookup("search"), Some("docs.search"));
+        assert!(names.is_alias_of("search", "docs.search"));
+        assert!(!names.is_alias_of("search", "web_search.search"));
+        assert_eq!(names.collisions[0].pinned.as_deref(), Some("docs.search"));
+        assert!(names.collisions[0].describe().ends_with("(pinned)"));
+        assert_eq!(names.unused_pins, [("fetch".into(), "docs".into())]);
+    }
+
+    #[test]
+    fn removing_a_skill_restores_the_bare_alias_for_the_survivor() {
+        let both = [skill("web-search", &["search"]), skill("docs", &["search"])];
+        assert_eq!(
+            ToolNames::resolve(&both, &HashMap::new()).lookup("search"),
+            None
+        );
+
+        let survivor = [skill("docs", &["search"])];
+        let names = ToolNames::resolve(&survivor, &HashMap::new());
+        assert_eq!(names.lookup("search"), Some("docs.search"));
+        assert!(names.collisions.is_empty());
+    }
+}
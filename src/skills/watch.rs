@@ -0,0 +1,287 @@
+//! `zeroclaw skill test --watch`: re-run a skill test whenever its files change.
+//!
+//! Changes are detected by polling modification times under the skill
+//! directory (build output dirs like `target/` are skipped; the copied
+//! `tool.wasm` is not). A burst of writes — e.g. a rebuild touching several
+//! files — is collapsed into a single re-run once the tree has been quiet for
+//! [`DEBOUNCE`].
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+const DEBOUNCE: Duration = Duration::from_millis(500);
+/// Directories that only hold build artifacts or dependencies.
+const IGNORED_DIRS: &[&str] = &[".git", "target", "node_modules", "__pycache__"];
+
+/// Source of "something changed" signals, abstracted so the watch loop can be
+/// driven by a fake in tests.
+trait ChangeSource {
+    /// Whether anything changed since the previous call.
+    fn poll_changed(&mut self) -> bool;
+}
+
+/// Collapses bursts of changes into one trigger after a quiet period.
+struct Debouncer {
+    quiet: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debouncer {
+    fn new(quiet: Duration) -> Self {
+        Self {
+            quiet,
+            last_change: None,
+        }
+    }
+
+    fn record_change(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+
+    /// True once per burst, after no change has been recorded for `quiet`.
+    fn ready(&mut self, now: Instant) -> bool {
+        match self.last_change {
+            Some(last) if now.saturating_duration_since(last) >= self.quiet => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Polls file modification times and sizes under a directory.
+struct PollingWatcher {
+    root: PathBuf,
+    snapshot: HashMap<PathBuf, (Option<SystemTime>, u64)>,
+}
+
+impl PollingWatcher {
+    fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            snapshot: snapshot_tree(root),
+        }
+    }
+}
+
+impl ChangeSource for PollingWatcher {
+    fn poll_changed(&mut self) -> bool {
+        let next = snapshot_tree(&self.root);
+        let changed = next != self.snapshot;
+        self.snapshot = next;
+        changed
+    }
+}
+
+fn snapshot_tree(root: &Path) -> HashMap<PathBuf, (Option<SystemTime>, u64)> {
+    let mut files = HashMap::new();
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if !IGNORED_DIRS.iter().any(|ignored| name == *ignored) {
+                    stack.push(path);
+                }
+            } else if let Ok(meta) = entry.metadata() {
+                files.insert(path, (meta.modified().ok(), meta.len()));
+            }
+        }
+    }
+    files
+}
+
+/// Poll `source` until `stop` is set, calling `on_change` once per debounced
+/// burst. Returns the number of runs triggered.
+fn watch_loop(
+    source: &mut impl ChangeSource,
+    stop: &AtomicBool,
+    debounce: Duration,
+    poll_interval: Duration,
+    mut on_change: impl FnMut(),
+) -> usize {
+    let mut debouncer = Debouncer::new(debounce);
+    let mut runs = 0;
+    while !stop.load(Ordering::Relaxed) {
+        if source.poll_changed() {
+            debouncer.record_change(Instant::now());
+        }
+        if debouncer.ready(Instant::now()) {
+            on_change();
+            runs += 1;
+        }
+        std::thread::sleep(poll_interval);
+    }
+    runs
+}
+
+/// Flag set when the user presses Ctrl+C.
+///
+/// The handler runs on its own thread and runtime because `skill` commands
+/// are synchronous.
+fn ctrl_c_flag() -> Result<Arc<AtomicBool>> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = Arc::clone(&stop);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("failed to start Ctrl+C handler")?;
+    std::thread::spawn(move || {
+        runtime.block_on(async {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                flag.store(true, Ordering::Relaxed);
+            }
+        });
+    });
+    Ok(stop)
+}
+
+/// Run the skill test now and again after every debounced change, until Ctrl+C.
+pub(super) fn watch_and_test(
+    skill_path: &Path,
+    tool_name: Option<&str>,
+    input: &super::TestInput,
+    raw: bool,
+    skills_env: &HashMap<String, HashMap<String, String>>,
+) -> Result<()> {
+    let stop = ctrl_c_flag()?;
+    let run = || {
+        if let Err(err) = super::test_skill_locally(skill_path, tool_name, input, raw, skills_env) {
+            println!("  {} {err:#}", console::style("✗").red().bold());
+        }
+        println!();
+        println!(
+            "  {}",
+            console::style(format!(
+                "Watching {} for changes (Ctrl+C to stop)",
+                skill_path.display()
+            ))
+            .dim()
+        );
+    };
+
+    run();
+    let mut watcher = PollingWatcher::new(skill_path);
+    watch_loop(&mut watcher, &stop, DEBOUNCE, POLL_INTERVAL, || {
+        println!();
+        println!(
+            "  {} Change detected at {} — re-running",
+            console::style("↻").cyan().bold(),
+            chrono::Local::now().format("%H:%M:%S")
+        );
+        println!("  {}", "─".repeat(72));
+        run();
+    });
+    println!("  Stopped watching.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debouncer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(500));
+
+        assert!(!debouncer.ready(start), "no change recorded yet");
+
+        debouncer.record_change(start);
+        debouncer.record_change(start + Duration::from_millis(200));
+        debouncer.record_change(start + Duration::from_millis(400));
+        assert!(!debouncer.ready(start + Duration::from_millis(800)));
+        assert!(debouncer.ready(start + Duration::from_millis(900)));
+
+        // Fires once per burst.
+        assert!(!debouncer.ready(start + Duration::from_millis(2000)));
+    }
+
+    #[test]
+    fn debouncer_fires_again_for_a_later_burst() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+
+        debouncer.record_change(start);
+        assert!(debouncer.ready(start + Duration::from_millis(100)));
+
+        debouncer.record_change(start + Duration::from_millis(300));
+        assert!(!debouncer.ready(start + Duration::from_millis(350)));
+        assert!(debouncer.ready(start + Duration::from_millis(400)));
+    }
+
+    struct FakeSource<'a> {
+        polls: usize,
+        burst: usize,
+        started: Instant,
+        stop_after: Duration,
+        stop: &'a AtomicBool,
+    }
+
+    impl ChangeSource for FakeSource<'_> {
+        fn poll_changed(&mut self) -> bool {
+            if self.started.elapsed() >= self.stop_after {
+                self.stop.store(true, Ordering::Relaxed);
+            }
+            self.polls += 1;
+            self.polls <= self.burst
+        }
+    }
+
+    #[test]
+    fn watch_loop_collapses_burst_into_one_run() {
+        let stop = AtomicBool::new(false);
+        let mut source = FakeSource {
+            polls: 0,
+            burst: 5,
+            started: Instant::now(),
+            stop_after: Duration::from_millis(600),
+            stop: &stop,
+        };
+
+        let mut calls = 0;
+        let runs = watch_loop(
+            &mut source,
+            &stop,
+            Duration::from_millis(150),
+            Duration::from_millis(1),
+            || calls += 1,
+        );
+
+        assert_eq!(runs, 1);
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn polling_watcher_detects_new_and_modified_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}").unwrap();
+        std::fs::create_dir(dir.path().join("target")).unwrap();
+
+        let mut watcher = PollingWatcher::new(dir.path());
+        assert!(!watcher.poll_changed());
+
+        std::fs::write(dir.path().join("target").join("out.wasm"), "ignored").unwrap();
+        assert!(!watcher.poll_changed(), "build dirs are ignored");
+
+        std::fs::write(dir.path().join("tool.wasm"), "wasm").unwrap();
+        assert!(watcher.poll_changed());
+        assert!(!watcher.poll_changed());
+
+        std::fs::write(dir.path().join("main.rs"), "fn main() { run() }").unwrap();
+        assert!(watcher.poll_changed());
+    }
+}
@@ -775,7 +775,7 @@ impl Observer for NoopObserver {
 mod tests {
     use super::*;
     use crate::coordination::CoordinationPayload;
-    use crate::providers::{ChatRequest, ChatResponse, ToolCall};
+    use crate::providers::{ChatOutcome, ChatRequest, ChatResponse, ToolCall};
     use crate::security::{AutonomyLevel, SecurityPolicy};
     use anyhow::anyhow;
 
@@ -880,6 +880,7 @@ mod tests {
                     tool_calls: Vec::new(),
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 })
             } else {
                 Ok(ChatResponse {
@@ -891,6 +892,7 @@ mod tests {
                     }],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 })
             }
         }
@@ -925,6 +927,7 @@ mod tests {
                 }],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             })
         }
     }
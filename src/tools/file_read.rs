@@ -693,7 +693,7 @@ mod tests {
         use crate::config::MemoryConfig;
         use crate::memory::{self, Memory};
         use crate::observability::{NoopObserver, Observer};
-        use crate::providers::{ChatMessage, ChatRequest, ChatResponse, Provider};
+        use crate::providers::{ChatMessage, ChatOutcome, ChatRequest, ChatResponse, Provider};
         use std::sync::{Arc, Mutex};
 
         pub type SharedRequests = Arc<Mutex<Vec<Vec<ChatMessage>>>>;
@@ -744,6 +744,7 @@ mod tests {
                         tool_calls: vec![],
                         usage: None,
                         reasoning_content: None,
+                        outcome: ChatOutcome::Completed,
                     });
                 }
                 Ok(guard.remove(0))
@@ -770,7 +771,7 @@ mod tests {
     async fn e2e_agent_file_read_pdf_extraction() {
         use crate::agent::agent::Agent;
         use crate::agent::dispatcher::NativeToolDispatcher;
-        use crate::providers::{ChatResponse, Provider, ToolCall};
+        use crate::providers::{ChatOutcome, ChatResponse, Provider, ToolCall};
         use e2e_helpers::*;
 
         // ── Set up workspace with PDF fixture ──
@@ -804,6 +805,7 @@ mod tests {
                 }],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             },
             // Turn 1 continued: provider sees tool result and answers
             ChatResponse {
@@ -811,6 +813,7 @@ mod tests {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             },
         ]);
 
@@ -867,7 +870,7 @@ mod tests {
     async fn e2e_agent_file_read_lossy_binary() {
         use crate::agent::agent::Agent;
         use crate::agent::dispatcher::NativeToolDispatcher;
-        use crate::providers::{ChatResponse, Provider, ToolCall};
+        use crate::providers::{ChatOutcome, ChatResponse, Provider, ToolCall};
         use e2e_helpers::*;
 
         // ── Set up workspace with binary file ──
@@ -897,12 +900,14 @@ mod tests {
                 }],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             },
             ChatResponse {
                 text: Some("The file appears to be binary data.".into()),
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             },
         ]);
 
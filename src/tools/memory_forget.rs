@@ -1,20 +1,163 @@
 use super::traits::{Tool, ToolResult};
+use crate::config::MemoryConfig;
+use crate::memory::tombstone::{soft_forget_all, Tombstones};
 use crate::memory::Memory;
 use crate::security::policy::ToolOperation;
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
+use parking_lot::Mutex;
 use serde_json::json;
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-/// Let the agent forget/delete a memory entry
+/// How long a bulk-forget confirmation token stays valid.
+const CONFIRMATION_TTL: Duration = Duration::from_secs(600);
+
+/// Keys listed in a bulk-forget preview before eliding the rest.
+const PREVIEW_KEYS: usize = 20;
+
+/// A bulk deletion waiting for its confirmation token.
+struct PendingForget {
+    keys: Vec<String>,
+    created: Instant,
+}
+
+/// Let the agent forget/delete memory entries.
+///
+/// A single key is forgotten directly. Prefix and query deletes (only with
+/// `[memory] allow_bulk_forget = true`) are two-step: the first call returns
+/// the match count and a confirmation token, and only a second call carrying
+/// that token deletes, at most `bulk_forget_max_entries` entries. Every
+/// deletion is soft — see [`crate::memory::tombstone`].
 pub struct MemoryForgetTool {
     memory: Arc<dyn Memory>,
     security: Arc<SecurityPolicy>,
+    tombstones: Tombstones,
+    allow_bulk: bool,
+    max_entries: usize,
+    pending: Mutex<HashMap<String, PendingForget>>,
 }
 
 impl MemoryForgetTool {
-    pub fn new(memory: Arc<dyn Memory>, security: Arc<SecurityPolicy>) -> Self {
-        Self { memory, security }
+    pub fn new(
+        memory: Arc<dyn Memory>,
+        security: Arc<SecurityPolicy>,
+        workspace_dir: &Path,
+        config: &MemoryConfig,
+    ) -> Self {
+        Self {
+            memory,
+            security,
+            tombstones: Tombstones::new(workspace_dir, config.undo_retention_days),
+            allow_bulk: config.allow_bulk_forget,
+            max_entries: config.bulk_forget_max_entries.max(1),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn failure(error: impl Into<String>) -> ToolResult {
+        ToolResult {
+            success: false,
+            output: String::new(),
+            error: Some(error.into()),
+        }
+    }
+
+    async fn forget_key(&self, key: &str) -> ToolResult {
+        match soft_forget_all(self.memory.as_ref(), &self.tombstones, &[key.to_string()]).await {
+            Ok((_, 0)) => ToolResult {
+                success: true,
+                output: format!("No memory found with key: {key}"),
+                error: None,
+            },
+            Ok(_) => ToolResult {
+                success: true,
+                output: format!("Forgot memory: {key}"),
+                error: None,
+            },
+            Err(e) => Self::failure(format!("Failed to forget memory: {e}")),
+        }
+    }
+
+    /// First step of a bulk delete: record the matches and hand out a token.
+    fn preview(&self, mut keys: Vec<String>, described: &str, more_than_cap: bool) -> ToolResult {
+        if keys.is_empty() {
+            return ToolResult {
+                success: true,
+                output: format!("No memories match {described}. Nothing to forget."),
+                error: None,
+            };
+        }
+
+        let capped = more_than_cap || keys.len() > self.max_entries;
+        keys.truncate(self.max_entries);
+
+        let mut listed = keys
+            .iter()
+            .take(PREVIEW_KEYS)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(", ");
+        if keys.len() > PREVIEW_KEYS {
+            listed.push_str(&format!(", … ({} more)", keys.len() - PREVIEW_KEYS));
+        }
+
+        let count = if capped {
+            format!(
+                "More than {} memories match {described}; only the first {} will be forgotten by this request",
+                self.max_entries, self.max_entries
+            )
+        } else {
+            format!("{} memories match {described}", keys.len())
+        };
+
+        let token = uuid::Uuid::new_v4().to_string();
+        {
+            let mut pending = self.pending.lock();
+            pending.retain(|_, p| p.created.elapsed() < CONFIRMATION_TTL);
+            pending.insert(
+                token.clone(),
+                PendingForget {
+                    keys,
+                    created: Instant::now(),
+                },
+            );
+        }
+
+        ToolResult {
+            success: true,
+            output: format!(
+                "{count}: {listed}\n\nNothing has been deleted. Confirm with the user, then call \
+                 memory_forget with {{\"confirm_token\": \"{token}\"}} within {} minutes to \
+                 forget these entries.",
+                CONFIRMATION_TTL.as_secs() / 60
+            ),
+            error: None,
+        }
+    }
+
+    /// Second step of a bulk delete.
+    async fn confirm(&self, token: &str) -> ToolResult {
+        let pending = self.pending.lock().remove(token);
+        let Some(pending) = pending.filter(|p| p.created.elapsed() < CONFIRMATION_TTL) else {
+            return Self::failure(
+                "Unknown or expired confirmation token. Run the prefix/query request again to get a new one.",
+            );
+        };
+
+        match soft_forget_all(self.memory.as_ref(), &self.tombstones, &pending.keys).await {
+            Ok((batch_id, removed)) => ToolResult {
+                success: true,
+                output: format!(
+                    "Forgot {removed} memories. They can be restored with \
+                     `zeroclaw memory restore {batch_id}`."
+                ),
+                error: None,
+            },
+            Err(e) => Self::failure(format!("Failed to forget memories: {e}")),
+        }
     }
 }
 
@@ -25,7 +168,9 @@ impl Tool for MemoryForgetTool {
     }
 
     fn description(&self) -> &str {
-        "Remove a memory by key. Use to delete outdated facts or sensitive data. Returns whether the memory was found and removed."
+        "Remove memories. Pass 'key' to forget one entry. 'prefix' or 'query' (when bulk forget is enabled) \
+         first return the matching entries and a confirmation token; nothing is deleted until you call again \
+         with 'confirm_token'. Deleted memories can be restored by the user."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -34,46 +179,91 @@ impl Tool for MemoryForgetTool {
             "properties": {
                 "key": {
                     "type": "string",
-                    "description": "The key of the memory to forget"
+                    "description": "The exact key of the memory to forget"
+                },
+                "prefix": {
+                    "type": "string",
+                    "description": "Forget all memories whose key starts with this prefix (two-step, needs confirm_token)"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Forget memories matching this search query (two-step, needs confirm_token)"
+                },
+                "confirm_token": {
+                    "type": "string",
+                    "description": "Token returned by a previous prefix/query call; performs the deletion"
                 }
-            },
-            "required": ["key"]
+            }
         })
     }
 
     async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
-        let key = args
-            .get("key")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing 'key' parameter"))?;
-
-        if let Err(error) = self
-            .security
-            .enforce_tool_operation(ToolOperation::Act, "memory_forget")
-        {
-            return Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(error),
-            });
+        let arg = |name: &str| {
+            args.get(name)
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+        };
+        let key = args.get("key").and_then(|v| v.as_str());
+        let prefix = arg("prefix");
+        let query = arg("query");
+        let confirm_token = arg("confirm_token");
+
+        let selectors = [key.is_some(), prefix.is_some(), query.is_some()]
+            .into_iter()
+            .filter(|given| *given)
+            .count();
+        if confirm_token.is_none() && selectors != 1 {
+            anyhow::bail!(
+                "Provide exactly one of 'key', 'prefix' or 'query' (or a 'confirm_token')"
+            );
         }
 
-        match self.memory.forget(key).await {
-            Ok(true) => Ok(ToolResult {
-                success: true,
-                output: format!("Forgot memory: {key}"),
-                error: None,
-            }),
-            Ok(false) => Ok(ToolResult {
-                success: true,
-                output: format!("No memory found with key: {key}"),
-                error: None,
-            }),
-            Err(e) => Ok(ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("Failed to forget memory: {e}")),
-            }),
+        // Previews delete nothing; only the mutating calls count as actions.
+        if key.is_some() || confirm_token.is_some() {
+            if let Err(error) = self
+                .security
+                .enforce_tool_operation(ToolOperation::Act, "memory_forget")
+            {
+                return Ok(Self::failure(error));
+            }
+        }
+
+        if let Some(token) = confirm_token {
+            return Ok(self.confirm(token).await);
+        }
+        if let Some(key) = key {
+            return Ok(self.forget_key(key).await);
+        }
+
+        if !self.allow_bulk {
+            return Ok(Self::failure(
+                "Bulk forget is disabled. Forget entries one 'key' at a time, or ask the user to \
+                 set [memory] allow_bulk_forget = true.",
+            ));
+        }
+
+        if let Some(prefix) = prefix {
+            let mut keys: Vec<String> = match self.memory.list(None, None).await {
+                Ok(entries) => entries
+                    .into_iter()
+                    .map(|entry| entry.key)
+                    .filter(|k| k.starts_with(prefix))
+                    .collect(),
+                Err(e) => return Ok(Self::failure(format!("Failed to list memories: {e}"))),
+            };
+            keys.sort();
+            return Ok(self.preview(keys, &format!("prefix '{prefix}'"), false));
+        }
+
+        let query = query.unwrap_or_default();
+        match self.memory.recall(query, self.max_entries + 1, None).await {
+            Ok(entries) => {
+                let more = entries.len() > self.max_entries;
+                let keys = entries.into_iter().map(|entry| entry.key).collect();
+                Ok(self.preview(keys, &format!("query '{query}'"), more))
+            }
+            Err(e) => Ok(Self::failure(format!("Failed to search memories: {e}"))),
         }
     }
 }
@@ -97,20 +287,26 @@ mod tests {
 
     #[test]
     fn name_and_schema() {
-        let (_tmp, mem) = test_mem();
-        let tool = MemoryForgetTool::new(mem, test_security());
+        let (tmp, mem) = test_mem();
+        let tool =
+            MemoryForgetTool::new(mem, test_security(), tmp.path(), &MemoryConfig::default());
         assert_eq!(tool.name(), "memory_forget");
         assert!(tool.parameters_schema()["properties"]["key"].is_object());
     }
 
     #[tokio::test]
     async fn forget_existing() {
-        let (_tmp, mem) = test_mem();
+        let (tmp, mem) = test_mem();
         mem.store("temp", "temporary", MemoryCategory::Conversation, None)
             .await
             .unwrap();
 
-        let tool = MemoryForgetTool::new(mem.clone(), test_security());
+        let tool = MemoryForgetTool::new(
+            mem.clone(),
+            test_security(),
+            tmp.path(),
+            &MemoryConfig::default(),
+        );
         let result = tool.execute(json!({"key": "temp"})).await.unwrap();
         assert!(result.success);
         assert!(result.output.contains("Forgot"));
@@ -120,8 +316,9 @@ mod tests {
 
     #[tokio::test]
     async fn forget_nonexistent() {
-        let (_tmp, mem) = test_mem();
-        let tool = MemoryForgetTool::new(mem, test_security());
+        let (tmp, mem) = test_mem();
+        let tool =
+            MemoryForgetTool::new(mem, test_security(), tmp.path(), &MemoryConfig::default());
         let result = tool.execute(json!({"key": "nope"})).await.unwrap();
         assert!(result.success);
         assert!(result.output.contains("No memory found"));
@@ -129,15 +326,16 @@ mod tests {
 
     #[tokio::test]
     async fn forget_missing_key() {
-        let (_tmp, mem) = test_mem();
-        let tool = MemoryForgetTool::new(mem, test_security());
+        let (tmp, mem) = test_mem();
+        let tool =
+            MemoryForgetTool::new(mem, test_security(), tmp.path(), &MemoryConfig::default());
         let result = tool.execute(json!({})).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn forget_blocked_in_readonly_mode() {
-        let (_tmp, mem) = test_mem();
+        let (tmp, mem) = test_mem();
         mem.store("temp", "temporary", MemoryCategory::Conversation, None)
             .await
             .unwrap();
@@ -145,7 +343,8 @@ mod tests {
             autonomy: AutonomyLevel::ReadOnly,
             ..SecurityPolicy::default()
         });
-        let tool = MemoryForgetTool::new(mem.clone(), readonly);
+        let tool =
+            MemoryForgetTool::new(mem.clone(), readonly, tmp.path(), &MemoryConfig::default());
         let result = tool.execute(json!({"key": "temp"})).await.unwrap();
         assert!(!result.success);
         assert!(result
@@ -158,7 +357,7 @@ mod tests {
 
     #[tokio::test]
     async fn forget_blocked_when_rate_limited() {
-        let (_tmp, mem) = test_mem();
+        let (tmp, mem) = test_mem();
         mem.store("temp", "temporary", MemoryCategory::Conversation, None)
             .await
             .unwrap();
@@ -166,7 +365,8 @@ mod tests {
             max_actions_per_hour: 0,
             ..SecurityPolicy::default()
         });
-        let tool = MemoryForgetTool::new(mem.clone(), limited);
+        let tool =
+            MemoryForgetTool::new(mem.clone(), limited, tmp.path(), &MemoryConfig::default());
         let result = tool.execute(json!({"key": "temp"})).await.unwrap();
         assert!(!result.success);
         assert!(result
@@ -176,4 +376,124 @@ mod tests {
             .contains("Rate limit exceeded"));
         assert!(mem.get("temp").await.unwrap().is_some());
     }
+
+    fn bulk_config(max_entries: usize) -> MemoryConfig {
+        MemoryConfig {
+            allow_bulk_forget: true,
+            bulk_forget_max_entries: max_entries,
+            ..MemoryConfig::default()
+        }
+    }
+
+    async fn seed(mem: &Arc<dyn Memory>, keys: &[&str]) {
+        for key in keys {
+            mem.store(
+                key,
+                &format!("note about {key}"),
+                MemoryCategory::Core,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+    }
+
+    fn confirm_token(output: &str) -> String {
+        let start = output.find("\"confirm_token\": \"").unwrap() + "\"confirm_token\": \"".len();
+        output[start..].split('"').next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn bulk_forget_disabled_by_default() {
+        let (tmp, mem) = test_mem();
+        seed(&mem, &["project_a", "project_b"]).await;
+        let tool = MemoryForgetTool::new(
+            mem.clone(),
+            test_security(),
+            tmp.path(),
+            &MemoryConfig::default(),
+        );
+
+        let result = tool.execute(json!({"prefix": "project_"})).await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("allow_bulk_forget"));
+        assert_eq!(mem.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn prefix_forget_requires_confirmation_token() {
+        let (tmp, mem) = test_mem();
+        seed(&mem, &["project_a", "project_b", "other"]).await;
+        let tool =
+            MemoryForgetTool::new(mem.clone(), test_security(), tmp.path(), &bulk_config(100));
+
+        let preview = tool.execute(json!({"prefix": "project_"})).await.unwrap();
+        assert!(preview.success);
+        assert!(preview
+            .output
+            .starts_with("2 memories match prefix 'project_'"));
+        assert!(preview.output.contains("Nothing has been deleted"));
+        assert_eq!(mem.count().await.unwrap(), 3, "preview must not delete");
+
+        let bogus = tool
+            .execute(json!({"confirm_token": "not-a-token"}))
+            .await
+            .unwrap();
+        assert!(!bogus.success);
+        assert_eq!(mem.count().await.unwrap(), 3);
+
+        let token = confirm_token(&preview.output);
+        let done = tool.execute(json!({"confirm_token": token})).await.unwrap();
+        assert!(done.success, "{:?}", done.error);
+        assert!(done.output.starts_with("Forgot 2 memories"));
+        assert!(mem.get("project_a").await.unwrap().is_none());
+        assert!(mem.get("other").await.unwrap().is_some());
+
+        let replay = tool.execute(json!({"confirm_token": token})).await.unwrap();
+        assert!(!replay.success, "tokens are single-use");
+    }
+
+    #[tokio::test]
+    async fn bulk_forget_is_capped_per_call() {
+        let (tmp, mem) = test_mem();
+        seed(&mem, &["log_1", "log_2", "log_3", "log_4", "log_5"]).await;
+        let tool = MemoryForgetTool::new(mem.clone(), test_security(), tmp.path(), &bulk_config(2));
+
+        let preview = tool.execute(json!({"prefix": "log_"})).await.unwrap();
+        assert!(preview.output.starts_with("More than 2 memories match"));
+
+        let token = confirm_token(&preview.output);
+        let done = tool.execute(json!({"confirm_token": token})).await.unwrap();
+        assert!(done.output.starts_with("Forgot 2 memories"));
+        assert_eq!(mem.count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn query_forget_is_restorable() {
+        let (tmp, mem) = test_mem();
+        seed(&mem, &["rust_tip", "python_tip"]).await;
+        let tool =
+            MemoryForgetTool::new(mem.clone(), test_security(), tmp.path(), &bulk_config(100));
+
+        let preview = tool.execute(json!({"query": "rust_tip"})).await.unwrap();
+        assert!(preview.success);
+        let token = confirm_token(&preview.output);
+        let done = tool.execute(json!({"confirm_token": token})).await.unwrap();
+        assert!(done.success);
+        assert!(mem.get("rust_tip").await.unwrap().is_none());
+
+        let tombstones = Tombstones::new(tmp.path(), 7);
+        let report = crate::memory::tombstone::restore(
+            mem.as_ref(),
+            &tombstones,
+            &crate::memory::tombstone::RestoreTarget::Last,
+        )
+        .await
+        .unwrap();
+        assert!(report.restored.contains(&"rust_tip".to_string()));
+        assert_eq!(
+            mem.get("rust_tip").await.unwrap().unwrap().content,
+            "note about rust_tip"
+        );
+    }
 }
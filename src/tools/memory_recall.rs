@@ -1,5 +1,5 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::{Memory, MemoryCategory};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -37,6 +37,14 @@ impl Tool for MemoryRecallTool {
                 "limit": {
                     "type": "integer",
                     "description": "Max results to return (default: 5)"
+                },
+                "categories": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "enum": MemoryCategory::BUILTIN_NAMES
+                    },
+                    "description": "Only return memories in these categories, e.g. [\"fact\", \"preference\"] for things the user explicitly asked to remember. Omit to search everything."
                 }
             },
             "required": ["query"]
@@ -55,7 +63,23 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit, None).await {
+        let categories: Vec<MemoryCategory> = args
+            .get("categories")
+            .and_then(serde_json::Value::as_array)
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(|name| MemoryCategory::from_name(&name.trim().to_ascii_lowercase()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match self
+            .memory
+            .recall_in_categories(query, limit, None, &categories)
+            .await
+        {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -91,7 +115,7 @@ impl Tool for MemoryRecallTool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{MemoryCategory, SqliteMemory};
+    use crate::memory::SqliteMemory;
     use tempfile::TempDir;
 
     fn seeded_mem() -> (TempDir, Arc<dyn Memory>) {
@@ -149,6 +173,36 @@ mod tests {
         assert!(result.output.contains("Found 3"));
     }
 
+    #[tokio::test]
+    async fn recall_filters_by_category() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store(
+            "pref",
+            "Rust over Go, always",
+            MemoryCategory::Preference,
+            None,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "chat",
+            "we talked about Rust",
+            MemoryCategory::Ephemeral,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool
+            .execute(json!({"query": "Rust", "categories": ["fact", "preference"]}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Found 1"), "{}", result.output);
+        assert!(result.output.contains("[preference] pref"));
+    }
+
     #[tokio::test]
     async fn recall_missing_query() {
         let (_tmp, mem) = seeded_mem();
@@ -162,6 +216,14 @@ mod tests {
         let (_tmp, mem) = seeded_mem();
         let tool = MemoryRecallTool::new(mem);
         assert_eq!(tool.name(), "memory_recall");
-        assert!(tool.parameters_schema()["properties"]["query"].is_object());
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["query"].is_object());
+        assert_eq!(
+            schema["properties"]["categories"]["items"]["enum"]
+                .as_array()
+                .unwrap()
+                .len(),
+            MemoryCategory::BUILTIN_NAMES.len()
+        );
     }
 }
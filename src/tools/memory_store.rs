@@ -25,7 +25,7 @@ impl Tool for MemoryStoreTool {
     }
 
     fn description(&self) -> &str {
-        "Store a fact, preference, or note in long-term memory. Use category 'core' for permanent facts, 'daily' for session notes, 'conversation' for chat context, or a custom category name."
+        "Store a fact, preference, or note in long-term memory. Use category 'fact' for things the user asked you to remember, 'preference' for how they like things done, 'task' for todos, 'core' for other permanent facts, 'daily' for session notes, or 'conversation'/'ephemeral' for chat context."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -42,7 +42,8 @@ impl Tool for MemoryStoreTool {
                 },
                 "category": {
                     "type": "string",
-                    "description": "Memory category: 'core' (permanent), 'daily' (session), 'conversation' (chat), or a custom category name. Defaults to 'core'."
+                    "enum": MemoryCategory::BUILTIN_NAMES,
+                    "description": "Memory category. 'fact', 'preference' and 'core' are kept permanently; 'ephemeral' is pruned after a few days. Defaults to 'core'."
                 }
             },
             "required": ["key", "content"]
@@ -60,12 +61,10 @@ impl Tool for MemoryStoreTool {
             .and_then(|v| v.as_str())
             .ok_or_else(|| anyhow::anyhow!("Missing 'content' parameter"))?;
 
-        let category = match args.get("category").and_then(|v| v.as_str()) {
-            Some("core") | None => MemoryCategory::Core,
-            Some("daily") => MemoryCategory::Daily,
-            Some("conversation") => MemoryCategory::Conversation,
-            Some(other) => MemoryCategory::Custom(other.to_string()),
-        };
+        let category = args
+            .get("category")
+            .and_then(|v| v.as_str())
+            .map_or(MemoryCategory::Core, MemoryCategory::from_name);
 
         if let Err(error) = self
             .security
@@ -55,12 +55,15 @@ pub mod memory_store;
 pub mod model_routing_config;
 pub mod pdf_read;
 pub mod process;
+pub mod progress;
 pub mod proxy_config;
 pub mod pushover;
 pub mod schedule;
 pub mod schema;
 pub mod screenshot;
 pub mod shell;
+pub mod skill_install;
+pub mod skill_list;
 pub mod subagent_list;
 pub mod subagent_manage;
 pub mod subagent_registry;
@@ -69,6 +72,7 @@ pub mod task_plan;
 pub mod traits;
 pub mod url_validation;
 pub mod wasm_module;
+pub mod wasm_queue;
 pub mod wasm_tool;
 pub mod web_access_config;
 pub mod web_fetch;
@@ -118,6 +122,8 @@ pub use schedule::ScheduleTool;
 pub use schema::{CleaningStrategy, SchemaCleanr};
 pub use screenshot::ScreenshotTool;
 pub use shell::ShellTool;
+pub use skill_install::SkillInstallTool;
+pub use skill_list::SkillListTool;
 pub use subagent_list::SubAgentListTool;
 pub use subagent_manage::SubAgentManageTool;
 pub use subagent_registry::SubAgentRegistry;
@@ -258,6 +264,7 @@ pub fn all_tools_with_runtime(
 ) -> Vec<Box<dyn Tool>> {
     let has_shell_access = runtime.has_shell_access();
     let has_filesystem_access = runtime.has_filesystem_access();
+    wasm_queue::global().configure(wasm_queue::QueueLimits::from_config(&root_config.wasm));
     let zeroclaw_dir = root_config
         .config_path
         .parent()
@@ -276,9 +283,16 @@ pub fn all_tools_with_runtime(
         Arc::new(CronUpdateTool::new(config.clone(), security.clone())),
         Arc::new(CronRunTool::new(config.clone(), security.clone())),
         Arc::new(CronRunsTool::new(config.clone())),
+        Arc::new(SkillListTool::new(config.clone())),
+        Arc::new(SkillInstallTool::new(config.clone(), security.clone())),
         Arc::new(MemoryStoreTool::new(memory.clone(), security.clone())),
         Arc::new(MemoryRecallTool::new(memory.clone())),
-        Arc::new(MemoryForgetTool::new(memory, security.clone())),
+        Arc::new(MemoryForgetTool::new(
+            memory,
+            security.clone(),
+            workspace_dir,
+            &root_config.memory,
+        )),
         Arc::new(ScheduleTool::new(security.clone(), root_config.clone())),
         Arc::new(TaskPlanTool::new(security.clone())),
         Arc::new(ModelRoutingConfigTool::new(
@@ -464,6 +478,8 @@ pub fn all_tools_with_runtime(
                 .map(|mode| mode.as_compatible_mode()),
             max_tokens_override: None,
             model_support_vision: root_config.model_support_vision,
+            prompt_caching: root_config.provider.prompt_caching,
+            ollama: root_config.provider.ollama.clone(),
         };
         let parent_tools = Arc::new(tool_arcs.clone());
         let mut delegate_tool = DelegateTool::new_with_options(
@@ -573,6 +589,16 @@ pub fn all_tools_with_runtime(
     boxed_registry_from_arcs(tool_arcs)
 }
 
+/// Name/description pairs for the prompt tool list, read from the registry
+/// the caller runs so the advertised tools cannot drift from what is
+/// registered.
+pub fn available_tools(tools: &[Box<dyn Tool>]) -> Vec<(&str, &str)> {
+    tools
+        .iter()
+        .map(|tool| (tool.name(), tool.description()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -983,4 +1009,77 @@ mod tests {
         assert!(names.contains(&"delegate"));
         assert!(!names.contains(&"delegate_coordination_status"));
     }
+
+    fn registry_for(config: &Config) -> Vec<Box<dyn Tool>> {
+        let runtime: Arc<dyn RuntimeAdapter> =
+            Arc::from(crate::runtime::create_runtime(&config.runtime).unwrap());
+        all_tools_with_runtime(
+            Arc::new(config.clone()),
+            &Arc::new(SecurityPolicy::default()),
+            runtime,
+            Arc::new(crate::memory::NoneMemory::new()),
+            config.composio.api_key.as_deref(),
+            None,
+            &config.browser,
+            &config.http_request,
+            &config.web_fetch,
+            &config.workspace_dir,
+            &config.agents,
+            None,
+            config,
+        )
+    }
+
+    #[test]
+    fn available_tools_omits_disabled_tools() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.http_request.enabled = true;
+        let tools = registry_for(&config);
+        let names: Vec<&str> = available_tools(&tools)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(names.contains(&"http_request"));
+
+        config.http_request.enabled = false;
+        config.browser.enabled = false;
+        let tools = registry_for(&config);
+        let names: Vec<&str> = available_tools(&tools)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(!names.contains(&"http_request"));
+        assert!(!names.contains(&"browser_open"));
+        assert!(!names.contains(&"delegate"));
+        assert!(names.contains(&"shell"));
+    }
+
+    #[test]
+    fn available_tools_omits_shell_and_fs_for_wasm_runtime() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = test_config(&tmp);
+        config.runtime.kind = "wasm".into();
+        let tools = registry_for(&config);
+        let names: Vec<&str> = available_tools(&tools)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(!names.contains(&"shell"));
+        assert!(!names.contains(&"file_read"));
+        assert!(names.contains(&"memory_recall"));
+        assert!(names.contains(&"wasm_module"));
+    }
+
+    #[test]
+    fn available_tools_uses_registry_descriptions() {
+        let tmp = TempDir::new().unwrap();
+        let tools = registry_for(&test_config(&tmp));
+        let descs = available_tools(&tools);
+        assert_eq!(descs.len(), tools.len());
+        for ((name, description), tool) in descs.iter().zip(&tools) {
+            assert_eq!(*name, tool.name());
+            assert_eq!(*description, tool.description());
+        }
+    }
 }
@@ -254,6 +254,7 @@ impl ModelRoutingConfigTool {
             "provider": route.provider,
             "model": route.model,
             "transport": route.transport,
+            "fallback": route.fallback,
             "api_key_configured": has_provider_credential(&route.provider, route.api_key.as_deref()),
             "classification": classification,
         })
@@ -505,6 +506,7 @@ impl ModelRoutingConfigTool {
             max_tokens: None,
             api_key: None,
             transport: None,
+            fallback: Vec::new(),
         });
 
         next_route.hint = hint.clone();
@@ -0,0 +1,144 @@
+//! Progress lines from long-running tools.
+//!
+//! A WASM tool whose manifest sets `"streaming": true` may write
+//! `@progress: <status>` lines to stdout while it works (see
+//! [`crate::skills::protocol::PROGRESS_PREFIX`]). The channel runtime scopes a
+//! [`ProgressSink`] around its tool loop with [`with_sink`]; the tool reads it
+//! with [`current_sink`] and reports each progress line as it is written, and
+//! the channel shows them as a throttled status message. Without a sink
+//! (CLI, gateway, cron) the lines are only dropped from the envelope.
+
+use crate::skills::protocol::progress_message;
+use tokio::sync::mpsc;
+
+/// Longest progress line forwarded; the rest of a longer line is dropped.
+const MAX_LINE_BYTES: usize = 4096;
+
+/// One status update from a running tool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToolProgress {
+    pub tool: String,
+    pub message: String,
+}
+
+/// Where running tools report progress.
+#[derive(Debug, Clone)]
+pub struct ProgressSink(mpsc::UnboundedSender<ToolProgress>);
+
+impl ProgressSink {
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<ToolProgress>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self(tx), rx)
+    }
+
+    /// Report a status line. Never blocks, so it is safe from the blocking
+    /// thread a tool runs on; reports after the receiver is gone are dropped.
+    pub fn report(&self, tool: &str, message: &str) {
+        let _ = self.0.send(ToolProgress {
+            tool: tool.to_string(),
+            message: message.to_string(),
+        });
+    }
+}
+
+tokio::task_local! {
+    static SINK: ProgressSink;
+}
+
+/// Run `future` so that tools it executes report progress to `sink`.
+pub async fn with_sink<F: std::future::Future>(sink: Option<ProgressSink>, future: F) -> F::Output {
+    match sink {
+        Some(sink) => SINK.scope(sink, future).await,
+        None => future.await,
+    }
+}
+
+/// The sink of the current task, if any. Read it before moving work to a
+/// blocking thread, where the task-local is not visible.
+pub fn current_sink() -> Option<ProgressSink> {
+    SINK.try_with(Clone::clone).ok()
+}
+
+/// Splits a streaming tool's stdout into lines as it is written and reports
+/// each `@progress:` line. Only the unfinished line is buffered; the caller
+/// keeps the complete output for envelope parsing.
+#[derive(Debug)]
+pub struct ProgressLines {
+    tool: String,
+    sink: ProgressSink,
+    partial: Vec<u8>,
+}
+
+impl ProgressLines {
+    pub fn new(tool: impl Into<String>, sink: ProgressSink) -> Self {
+        Self {
+            tool: tool.into(),
+            sink,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Feed the next chunk of stdout, which may end mid-line.
+    pub fn push(&mut self, bytes: &[u8]) {
+        for piece in bytes.split_inclusive(|byte| *byte == b'\n') {
+            let room = MAX_LINE_BYTES.saturating_sub(self.partial.len());
+            let (text, ends_line) = match piece.strip_suffix(b"\n") {
+                Some(text) => (text, true),
+                None => (piece, false),
+            };
+            self.partial
+                .extend_from_slice(&text[..text.len().min(room)]);
+            if ends_line {
+                let line = std::mem::take(&mut self.partial);
+                let line = String::from_utf8_lossy(&line);
+                if let Some(message) = progress_message(line.trim_end_matches('\r')) {
+                    self.sink.report(&self.tool, message);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain(rx: &mut mpsc::UnboundedReceiver<ToolProgress>) -> Vec<String> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|progress| progress.message)
+            .collect()
+    }
+
+    #[test]
+    fn progress_lines_are_reported_once_complete() {
+        let (sink, mut rx) = ProgressSink::channel();
+        let mut lines = ProgressLines::new("crawl", sink);
+
+        lines.push(b"@progress: fetching ");
+        assert!(drain(&mut rx).is_empty());
+        lines.push(b"page 1\r\n@progress: page 2\nnot progress\n{\"success\":true,");
+        assert_eq!(drain(&mut rx), ["fetching page 1", "page 2"]);
+
+        // Only lines that start with the prefix count.
+        lines.push(b"\"output\":\"@progress: no\"}\n");
+        assert!(drain(&mut rx).is_empty());
+
+        let long = format!("@progress: {}\n", "x".repeat(2 * MAX_LINE_BYTES));
+        lines.push(long.as_bytes());
+        let reported = drain(&mut rx);
+        assert_eq!(reported.len(), 1);
+        assert!(reported[0].len() < MAX_LINE_BYTES);
+    }
+
+    #[tokio::test]
+    async fn sink_is_only_visible_inside_its_scope() {
+        assert!(current_sink().is_none());
+        let (sink, mut rx) = ProgressSink::channel();
+        with_sink(Some(sink), async {
+            current_sink().unwrap().report("crawl", "working");
+        })
+        .await;
+        assert!(with_sink(None, async { current_sink() }).await.is_none());
+        assert_eq!(drain(&mut rx), ["working"]);
+    }
+}
@@ -0,0 +1,96 @@
+use super::traits::{Tool, ToolResult};
+use crate::config::Config;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Let the agent see which skills are installed.
+pub struct SkillListTool {
+    config: Arc<Config>,
+}
+
+impl SkillListTool {
+    pub fn new(config: Arc<Config>) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl Tool for SkillListTool {
+    fn name(&self) -> &str {
+        "skill_list"
+    }
+
+    fn description(&self) -> &str {
+        "List the installed skills with their version, description, source and tools"
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, _args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let config = Arc::clone(&self.config);
+        let skills = tokio::task::spawn_blocking(move || {
+            crate::skills::load_skills_with_config(&config.workspace_dir, &config)
+        })
+        .await?;
+        let listed: Vec<serde_json::Value> = skills
+            .iter()
+            .map(|skill| {
+                json!({
+                    "name": skill.name,
+                    "version": skill.version,
+                    "description": skill.description,
+                    "source": skill.source,
+                    "tools": skill.tools.iter().map(|tool| &tool.name).collect::<Vec<_>>(),
+                    "location": skill.location.as_ref().map(|path| path.display().to_string()),
+                })
+            })
+            .collect();
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&listed)?,
+            error: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn lists_installed_skills() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = Config {
+            workspace_dir: tmp.path().join("workspace"),
+            config_path: tmp.path().join("config.toml"),
+            ..Config::default()
+        };
+        config.skills.open_skills_enabled = false;
+        let skill_dir = config.workspace_dir.join("skills").join("weather");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.toml"),
+            "[skill]\nname = \"weather\"\ndescription = \"Looks up the weather\"\nversion = \"1.2.0\"\n",
+        )
+        .unwrap();
+
+        let result = SkillListTool::new(Arc::new(config))
+            .execute(json!({}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let listed: Vec<serde_json::Value> = serde_json::from_str(&result.output).unwrap();
+        assert_eq!(listed.len(), 1, "{listed:?}");
+        assert_eq!(listed[0]["name"], "weather");
+        assert_eq!(listed[0]["version"], "1.2.0");
+        assert_eq!(listed[0]["description"], "Looks up the weather");
+    }
+}
@@ -172,7 +172,26 @@ impl Tool for WasmModuleTool {
                     .and_then(serde_json::Value::as_str)
                     .ok_or_else(|| anyhow::anyhow!("Missing 'module' parameter for action=run"))?;
                 let caps = Self::parse_caps(&args)?;
-                match wasm_runtime.execute_module(module, &self.security.workspace_dir, &caps) {
+                // Execution blocks, so it waits for a queue slot on a blocking thread.
+                let priority = super::wasm_queue::current_priority();
+                let runtime = Arc::clone(&self.runtime);
+                let owned_module = module.to_string();
+                let workspace_dir = self.security.workspace_dir.clone();
+                let queued = tokio::task::spawn_blocking(move || {
+                    super::wasm_queue::global().run(priority, || {
+                        runtime
+                            .as_any()
+                            .downcast_ref::<WasmRuntime>()
+                            .ok_or_else(|| anyhow::anyhow!("runtime is no longer a WASM runtime"))?
+                            .execute_module(&owned_module, &workspace_dir, &caps)
+                    })
+                })
+                .await?;
+                let executed = match queued {
+                    Ok(executed) => executed,
+                    Err(full) => return Ok(full.into()),
+                };
+                match executed {
                     Ok(result) => {
                         let output = serde_json::to_string_pretty(&json!({
                             "module": module,
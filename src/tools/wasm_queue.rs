@@ -0,0 +1,412 @@
+//! Process-wide limit on concurrent WASM executions (`[wasm] max_concurrent`).
+//!
+//! Each execution path (skill tools run in-process, the `wasm_module` tool,
+//! and the `wasmtime` CLI behind `skill test` and `skill bench`) takes a slot
+//! from [`global`] before it starts. When every slot is busy, callers wait
+//! in arrival order for up to `queue_timeout_secs` and are then turned away
+//! with [`QueueFull`], so a burst never hangs the agent loop. With
+//! `scheduled_priority = "low"`, executions started by cron jobs wait until
+//! no interactive execution is queued.
+//!
+//! Queue depth, running executions and rejections are published as health
+//! metrics, which the daemon writes to its state file for `zeroclaw status`.
+
+use super::traits::ToolResult;
+use crate::config::{WasmConfig, WasmSchedulePriority};
+use parking_lot::{Condvar, Mutex};
+use std::fmt;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const METRIC_RUNNING: &str = "wasm_executions_running";
+pub const METRIC_QUEUE_DEPTH: &str = "wasm_queue_depth";
+pub const METRIC_REJECTED: &str = "wasm_queue_rejected_total";
+
+/// Who an execution is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmPriority {
+    /// A chat or CLI tool call someone is waiting on.
+    Interactive,
+    /// Started by the cron scheduler.
+    Scheduled,
+}
+
+tokio::task_local! {
+    static PRIORITY: WasmPriority;
+}
+
+/// Run `future` so that WASM executions it starts queue as
+/// [`WasmPriority::Scheduled`].
+pub async fn scheduled<F: std::future::Future>(future: F) -> F::Output {
+    PRIORITY.scope(WasmPriority::Scheduled, future).await
+}
+
+/// Priority of executions started from the current task. Read it before
+/// moving work to a blocking thread, where the task-local is not visible.
+pub fn current_priority() -> WasmPriority {
+    PRIORITY
+        .try_with(|priority| *priority)
+        .unwrap_or(WasmPriority::Interactive)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueueLimits {
+    pub max_concurrent: usize,
+    pub queue_timeout: Duration,
+    /// Scheduled executions wait until no interactive one is queued.
+    pub deprioritize_scheduled: bool,
+}
+
+impl QueueLimits {
+    pub fn from_config(config: &WasmConfig) -> Self {
+        Self {
+            max_concurrent: config.max_concurrent.max(1),
+            queue_timeout: Duration::from_secs(config.queue_timeout_secs),
+            deprioritize_scheduled: config.scheduled_priority == WasmSchedulePriority::Low,
+        }
+    }
+}
+
+impl Default for QueueLimits {
+    fn default() -> Self {
+        Self::from_config(&WasmConfig::default())
+    }
+}
+
+/// No slot freed up within the queue timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueFull {
+    pub max_concurrent: usize,
+    pub waited: Duration,
+}
+
+impl fmt::Display for QueueFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tool execution queue full: all {} WASM execution slots stayed busy for {}s; \
+             try again later or raise [wasm].max_concurrent",
+            self.max_concurrent,
+            self.waited.as_secs()
+        )
+    }
+}
+
+impl std::error::Error for QueueFull {}
+
+impl From<QueueFull> for ToolResult {
+    fn from(full: QueueFull) -> Self {
+        Self {
+            success: false,
+            output: String::new(),
+            error: Some(full.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    pub running: usize,
+    pub waiting: usize,
+    pub rejected: u64,
+}
+
+struct Waiter {
+    ticket: u64,
+    scheduled: bool,
+}
+
+struct QueueState {
+    limits: QueueLimits,
+    running: usize,
+    /// Queued executions in arrival order.
+    waiting: Vec<Waiter>,
+    next_ticket: u64,
+    rejected: u64,
+}
+
+impl QueueState {
+    fn next_in_line(&self) -> Option<u64> {
+        let skip_scheduled = self.limits.deprioritize_scheduled;
+        self.waiting
+            .iter()
+            .find(|waiter| !(skip_scheduled && waiter.scheduled))
+            .or_else(|| self.waiting.first())
+            .map(|waiter| waiter.ticket)
+    }
+
+    fn stats(&self) -> QueueStats {
+        QueueStats {
+            running: self.running,
+            waiting: self.waiting.len(),
+            rejected: self.rejected,
+        }
+    }
+}
+
+pub struct WasmExecutionQueue {
+    state: Mutex<QueueState>,
+    changed: Condvar,
+    publish_metrics: bool,
+}
+
+/// The queue shared by every WASM execution path in this process.
+pub fn global() -> &'static WasmExecutionQueue {
+    static QUEUE: OnceLock<WasmExecutionQueue> = OnceLock::new();
+    QUEUE.get_or_init(|| WasmExecutionQueue {
+        publish_metrics: true,
+        ..WasmExecutionQueue::new(QueueLimits::default())
+    })
+}
+
+impl WasmExecutionQueue {
+    pub fn new(limits: QueueLimits) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                limits,
+                running: 0,
+                waiting: Vec::new(),
+                next_ticket: 0,
+                rejected: 0,
+            }),
+            changed: Condvar::new(),
+            publish_metrics: false,
+        }
+    }
+
+    /// Apply new limits. Running executions keep their slots; queued ones
+    /// are re-checked against the new limit right away.
+    pub fn configure(&self, limits: QueueLimits) {
+        let mut state = self.state.lock();
+        state.limits = limits;
+        self.publish(&state);
+        self.changed.notify_all();
+    }
+
+    pub fn stats(&self) -> QueueStats {
+        self.state.lock().stats()
+    }
+
+    /// Wait for a free slot, blocking the calling thread for at most the
+    /// queue timeout. The slot is released when the permit drops, including
+    /// during a panic unwind.
+    pub fn acquire(&self, priority: WasmPriority) -> Result<WasmPermit<'_>, QueueFull> {
+        let started = Instant::now();
+        let mut state = self.state.lock();
+        let ticket = state.next_ticket;
+        state.next_ticket += 1;
+        state.waiting.push(Waiter {
+            ticket,
+            scheduled: priority == WasmPriority::Scheduled,
+        });
+        self.publish(&state);
+
+        loop {
+            if state.running < state.limits.max_concurrent && state.next_in_line() == Some(ticket) {
+                state.waiting.retain(|waiter| waiter.ticket != ticket);
+                state.running += 1;
+                self.publish(&state);
+                // The next waiter may fit into another free slot.
+                self.changed.notify_all();
+                return Ok(WasmPermit { queue: self });
+            }
+            let deadline = started + state.limits.queue_timeout;
+            if Instant::now() >= deadline {
+                state.waiting.retain(|waiter| waiter.ticket != ticket);
+                state.rejected += 1;
+                self.publish(&state);
+                self.changed.notify_all();
+                return Err(QueueFull {
+                    max_concurrent: state.limits.max_concurrent,
+                    waited: started.elapsed(),
+                });
+            }
+            self.changed.wait_until(&mut state, deadline);
+        }
+    }
+
+    /// Run `execute` in a slot; see [`Self::acquire`].
+    pub fn run<T>(
+        &self,
+        priority: WasmPriority,
+        execute: impl FnOnce() -> T,
+    ) -> Result<T, QueueFull> {
+        let _permit = self.acquire(priority)?;
+        Ok(execute())
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock();
+        state.running = state.running.saturating_sub(1);
+        self.publish(&state);
+        self.changed.notify_all();
+    }
+
+    fn publish(&self, state: &QueueState) {
+        if !self.publish_metrics {
+            return;
+        }
+        let stats = state.stats();
+        crate::health::set_metric(METRIC_RUNNING, stats.running as u64);
+        crate::health::set_metric(METRIC_QUEUE_DEPTH, stats.waiting as u64);
+        crate::health::set_metric(METRIC_REJECTED, stats.rejected);
+    }
+}
+
+/// A held execution slot.
+pub struct WasmPermit<'a> {
+    queue: &'a WasmExecutionQueue,
+}
+
+impl Drop for WasmPermit<'_> {
+    fn drop(&mut self) {
+        self.queue.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn queue(max_concurrent: usize, timeout_ms: u64) -> Arc<WasmExecutionQueue> {
+        Arc::new(WasmExecutionQueue::new(QueueLimits {
+            max_concurrent,
+            queue_timeout: Duration::from_millis(timeout_ms),
+            deprioritize_scheduled: true,
+        }))
+    }
+
+    /// Wait until `check` holds for the queue's stats.
+    fn wait_for(queue: &WasmExecutionQueue, check: impl Fn(QueueStats) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !check(queue.stats()) {
+            assert!(Instant::now() < deadline, "stats: {:?}", queue.stats());
+            thread::sleep(Duration::from_millis(2));
+        }
+    }
+
+    /// A fake execution that holds its slot until `release` is sent and
+    /// reports its label on `started` once it gets a slot.
+    fn spawn_execution(
+        queue: &Arc<WasmExecutionQueue>,
+        label: &'static str,
+        priority: WasmPriority,
+        started: &mpsc::Sender<&'static str>,
+    ) -> (mpsc::Sender<()>, thread::JoinHandle<Result<(), QueueFull>>) {
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let queue = Arc::clone(queue);
+        let started = started.clone();
+        let handle = thread::spawn(move || {
+            queue.run(priority, || {
+                started.send(label).unwrap();
+                let _ = release_rx.recv();
+            })
+        });
+        (release_tx, handle)
+    }
+
+    #[test]
+    fn excess_executions_queue_in_order_with_interactive_first() {
+        let queue = queue(2, 5_000);
+        let (started_tx, started) = mpsc::channel();
+
+        let a = spawn_execution(&queue, "a", WasmPriority::Interactive, &started_tx);
+        let b = spawn_execution(&queue, "b", WasmPriority::Interactive, &started_tx);
+        wait_for(&queue, |stats| stats.running == 2);
+        let mut first_two = [started.recv().unwrap(), started.recv().unwrap()];
+        first_two.sort_unstable();
+        assert_eq!(first_two, ["a", "b"]);
+
+        // Queue: a cron execution first, then two chat executions.
+        let cron = spawn_execution(&queue, "cron", WasmPriority::Scheduled, &started_tx);
+        wait_for(&queue, |stats| stats.waiting == 1);
+        let c = spawn_execution(&queue, "c", WasmPriority::Interactive, &started_tx);
+        wait_for(&queue, |stats| stats.waiting == 2);
+        let d = spawn_execution(&queue, "d", WasmPriority::Interactive, &started_tx);
+        wait_for(&queue, |stats| stats.waiting == 3);
+
+        // Each finished execution hands its slot to the next in line.
+        let mut order = Vec::new();
+        for (release, handle) in [a, b, c] {
+            release.send(()).unwrap();
+            handle.join().unwrap().unwrap();
+            order.push(started.recv().unwrap());
+        }
+        assert_eq!(order, ["c", "d", "cron"]);
+        for (release, handle) in [d, cron] {
+            release.send(()).unwrap();
+            handle.join().unwrap().unwrap();
+        }
+        let stats = queue.stats();
+        assert_eq!((stats.running, stats.waiting, stats.rejected), (0, 0, 0));
+    }
+
+    #[test]
+    fn queued_execution_is_rejected_after_the_timeout() {
+        let queue = queue(1, 50);
+        let (started_tx, started) = mpsc::channel();
+        let (release, holder) =
+            spawn_execution(&queue, "a", WasmPriority::Interactive, &started_tx);
+        started.recv().unwrap();
+
+        let err = queue.run(WasmPriority::Interactive, || ()).unwrap_err();
+        assert_eq!(err.max_concurrent, 1);
+        assert!(err.waited >= Duration::from_millis(50));
+        let result = ToolResult::from(err);
+        assert!(!result.success);
+        assert!(result
+            .error
+            .unwrap()
+            .starts_with("tool execution queue full"));
+        assert_eq!(queue.stats().rejected, 1);
+        assert_eq!(queue.stats().waiting, 0);
+
+        release.send(()).unwrap();
+        holder.join().unwrap().unwrap();
+        assert!(queue.run(WasmPriority::Interactive, || ()).is_ok());
+    }
+
+    #[test]
+    fn slots_are_released_on_error_and_panic() {
+        let queue = queue(1, 0);
+
+        let failed: Result<Result<(), &str>, QueueFull> =
+            queue.run(WasmPriority::Interactive, || Err("trap"));
+        assert_eq!(failed.unwrap(), Err("trap"));
+        assert_eq!(queue.stats().running, 0);
+
+        let panicking = Arc::clone(&queue);
+        let panicked = thread::spawn(move || {
+            panicking.run(WasmPriority::Scheduled, || panic!("fixture panic"))
+        })
+        .join();
+        assert!(panicked.is_err());
+        assert_eq!(queue.stats().running, 0);
+
+        // With no wait allowed, this only succeeds if the slot came back.
+        assert_eq!(queue.run(WasmPriority::Interactive, || 7).unwrap(), 7);
+    }
+
+    #[test]
+    fn raising_the_limit_admits_queued_executions() {
+        let queue = queue(1, 5_000);
+        let (started_tx, _started) = mpsc::channel();
+        let a = spawn_execution(&queue, "a", WasmPriority::Interactive, &started_tx);
+        let b = spawn_execution(&queue, "b", WasmPriority::Interactive, &started_tx);
+        wait_for(&queue, |stats| stats.running == 1 && stats.waiting == 1);
+
+        queue.configure(QueueLimits {
+            max_concurrent: 2,
+            ..QueueLimits::default()
+        });
+        wait_for(&queue, |stats| stats.running == 2);
+
+        for (release, handle) in [a, b] {
+            release.send(()).unwrap();
+            handle.join().unwrap().unwrap();
+        }
+    }
+}
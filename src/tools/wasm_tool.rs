@@ -13,15 +13,19 @@
 //! Host ← stdout : UTF-8 JSON of ToolResult
 //! ```
 //!
-//! Expected stdout shape:
+//! Expected stdout shape (canonical definition: [`crate::skills::protocol`]):
 //! ```json
-//! { "success": true, "output": "...", "error": null }
+//! { "success": true, "output": "...", "error": null, "data": {} }
 //! ```
 //!
 //! This means **any language** that can read stdin / write stdout works:
 //! TypeScript (Javy), Rust (wasm32-wasip1), Go (TinyGo), Python (componentize-py), etc.
 //! No custom SDK or ABI boilerplate required.
 //!
+//! A tool whose manifest sets `"streaming": true` may write `@progress:` lines
+//! before the envelope; they are forwarded to the current
+//! [`crate::tools::progress`] sink as they are written.
+//!
 //! # Security
 //! - No filesystem preopened dirs (deny-by-default).
 //! - No network sockets (WASI sockets not enabled).
@@ -30,38 +34,110 @@
 //!   [`WASM_TIMEOUT_SECS`] epochs so runaway modules are preempted without
 //!   relying on OS-level process signals.
 //! - Output capped at 1 MiB (enforced by [`MemoryOutputPipe`] capacity).
+//! - Input capped at 8 MiB ([`MAX_INPUT_BYTES`]).
 
 use super::traits::{Tool, ToolResult};
+use crate::skills::env::SkillEnv;
 use anyhow::{bail, Context};
 use async_trait::async_trait;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Maximum tool output size (1 MiB).
-const MAX_OUTPUT_BYTES: usize = 1_048_576;
+pub(crate) const MAX_OUTPUT_BYTES: usize = 1_048_576;
+
+/// Maximum tool input (stdin) size (8 MiB).
+pub(crate) const MAX_INPUT_BYTES: usize = 8 * 1_048_576;
 
 /// Wall-clock timeout for a single WASM invocation.
-const WASM_TIMEOUT_SECS: u64 = 30;
+pub(crate) const WASM_TIMEOUT_SECS: u64 = 30;
+
+/// Size of a WebAssembly linear memory page.
+const WASM_PAGE_BYTES: u64 = 64 * 1024;
+
+/// Raw result of one in-process run, before stdout is parsed as a tool
+/// result. Used by `zeroclaw skill test --compare-engines` and `skill bench`.
+#[derive(Debug)]
+pub struct WasmRun {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// Exit code passed to WASI `proc_exit`; `Some(0)` when `_start` returned.
+    pub exit_code: Option<i32>,
+    /// Why the module did not run to completion (trap, missing `_start`, ...).
+    pub error: Option<anyhow::Error>,
+    /// The epoch deadline ([`WASM_TIMEOUT_SECS`]) interrupted the module.
+    pub timed_out: bool,
+    /// Size of the exported `memory` when the run ended. Linear memory never
+    /// shrinks, so this is also the run's peak.
+    pub memory_bytes: Option<u64>,
+    /// Maximum size the module declares for its `memory`, if any.
+    pub memory_limit_bytes: Option<u64>,
+}
 
 // ─── Feature-gated implementation ─────────────────────────────────────────────
 
 #[cfg(feature = "wasm-tools")]
 mod inner {
     use super::{
-        async_trait, bail, Context, Path, Tool, ToolResult, Value, MAX_OUTPUT_BYTES,
-        WASM_TIMEOUT_SECS,
+        async_trait, bail, Context, Path, SkillEnv, Tool, ToolResult, Value, WasmRun,
+        MAX_OUTPUT_BYTES, WASM_PAGE_BYTES, WASM_TIMEOUT_SECS,
     };
+    use crate::tools::progress::{ProgressLines, ProgressSink};
+    use std::sync::Arc;
     use wasmtime::{Config as WtConfig, Engine, Linker, Module, Store};
     use wasmtime_wasi::{
         pipe::{MemoryInputPipe, MemoryOutputPipe},
         preview1::{self, WasiP1Ctx},
-        WasiCtxBuilder,
+        HostOutputStream, StdoutStream, StreamResult, Subscribe, WasiCtxBuilder,
     };
 
+    /// Guest stdout for a streaming tool: everything goes to the capped pipe,
+    /// and complete lines are scanned for progress as they arrive.
+    #[derive(Clone)]
+    struct ProgressStdout {
+        pipe: MemoryOutputPipe,
+        lines: Arc<parking_lot::Mutex<ProgressLines>>,
+    }
+
+    impl StdoutStream for ProgressStdout {
+        fn stream(&self) -> Box<dyn HostOutputStream> {
+            Box::new(self.clone())
+        }
+
+        fn isatty(&self) -> bool {
+            false
+        }
+    }
+
+    #[async_trait]
+    impl Subscribe for ProgressStdout {
+        async fn ready(&mut self) {}
+    }
+
+    impl HostOutputStream for ProgressStdout {
+        fn write(&mut self, bytes: bytes::Bytes) -> StreamResult<()> {
+            self.lines.lock().push(&bytes);
+            self.pipe.write(bytes)
+        }
+
+        fn flush(&mut self) -> StreamResult<()> {
+            self.pipe.flush()
+        }
+
+        fn check_write(&mut self) -> StreamResult<usize> {
+            self.pipe.check_write()
+        }
+    }
+
     pub struct WasmTool {
         name: String,
         description: String,
         parameters_schema: Value,
+        /// `"streaming": true` in the manifest.
+        streaming: bool,
+        /// Guest environment from the skill's `[skills.env.<skill>]` table.
+        env: SkillEnv,
         engine: Engine,
         module: Module,
         /// Guards against concurrent invocations: epoch tickers from concurrent
@@ -91,22 +167,95 @@ mod inner {
                 name,
                 description,
                 parameters_schema,
+                streaming: false,
+                env: SkillEnv::default(),
                 engine,
                 module,
                 is_running: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
             })
         }
 
-        fn invoke_sync(&self, args: &Value) -> anyhow::Result<ToolResult> {
+        /// Forward `@progress:` lines while the tool runs.
+        #[must_use]
+        pub fn with_streaming(mut self, streaming: bool) -> Self {
+            self.streaming = streaming;
+            self
+        }
+
+        /// Run with `env` as the guest environment. Calls fail while one of
+        /// its declared variables has no value.
+        #[must_use]
+        pub fn with_env(mut self, env: SkillEnv) -> Self {
+            self.env = env;
+            self
+        }
+
+        fn invoke_sync(
+            &self,
+            args: &Value,
+            progress: Option<ProgressSink>,
+        ) -> anyhow::Result<ToolResult> {
+            let env = self.env.vars()?;
             let input_bytes = serde_json::to_vec(args)?;
+            let lines = progress.map(|sink| ProgressLines::new(self.name.clone(), sink));
+            let run = self.run(&input_bytes, env, lines)?;
+            if let Some(err) = run.error {
+                return Err(err);
+            }
 
+            if run.stdout.is_empty() {
+                bail!("WASM tool wrote nothing to stdout");
+            }
+            // Note: MemoryOutputPipe::new(MAX_OUTPUT_BYTES) already caps writes
+            // at construction time, so no separate size check is needed here.
+
+            let envelope = if self.streaming {
+                crate::skills::protocol::ToolEnvelope::parse_streamed(&run.stdout)
+            } else {
+                crate::skills::protocol::ToolEnvelope::parse(&run.stdout)
+            };
+            envelope
+                .map(ToolResult::from)
+                .context("WASM tool stdout is not a valid tool result envelope")
+        }
+
+        /// Run `_start` once with `input` on stdin and `env` as the guest
+        /// environment. Errors only when the module cannot be instantiated;
+        /// traps and timeouts are reported in the returned [`WasmRun`].
+        pub fn run_raw(&self, input: &[u8], env: &[(String, String)]) -> anyhow::Result<WasmRun> {
+            self.run(input, env, None)
+        }
+
+        /// [`Self::run_raw`], scanning stdout with `progress` as it is written.
+        fn run(
+            &self,
+            input: &[u8],
+            env: &[(String, String)],
+            progress: Option<ProgressLines>,
+        ) -> anyhow::Result<WasmRun> {
+            if input.len() > MAX_INPUT_BYTES {
+                bail!("WASM tool input exceeds {MAX_INPUT_BYTES} bytes");
+            }
             let stdout_pipe = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
             let stdout_for_read = stdout_pipe.clone();
-
-            let wasi_ctx: WasiP1Ctx = WasiCtxBuilder::new()
-                .stdin(MemoryInputPipe::new(input_bytes))
-                .stdout(stdout_pipe)
-                .build_p1();
+            let stderr_pipe = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
+            let stderr_for_read = stderr_pipe.clone();
+
+            let mut builder = WasiCtxBuilder::new();
+            builder
+                .stdin(MemoryInputPipe::new(input.to_vec()))
+                .stderr(stderr_pipe);
+            match progress {
+                Some(lines) => builder.stdout(ProgressStdout {
+                    pipe: stdout_pipe,
+                    lines: Arc::new(parking_lot::Mutex::new(lines)),
+                }),
+                None => builder.stdout(stdout_pipe),
+            };
+            for (key, value) in env {
+                builder.env(key, value);
+            }
+            let wasi_ctx: WasiP1Ctx = builder.build_p1();
 
             let mut store = Store::new(&self.engine, wasi_ctx);
             // epoch_deadline is in ticks; the incrementer thread below fires at 1 Hz.
@@ -135,27 +284,54 @@ mod inner {
             let call_result = instance
                 .get_typed_func::<(), ()>(&mut store, "_start")
                 .context("WASM module must export '_start' (compile as a WASI binary)")
-                .and_then(|start| {
-                    start
-                        .call(&mut store, ())
-                        .context("WASM execution failed or timed out")
-                });
+                .and_then(|start| start.call(&mut store, ()));
 
             // Stop the epoch ticker regardless of outcome.
             let _ = stop_tx.send(());
             let _ = ticker.join();
 
-            call_result?;
-
-            let raw = stdout_for_read.contents().to_vec();
-            if raw.is_empty() {
-                bail!("WASM tool wrote nothing to stdout");
-            }
-            // Note: MemoryOutputPipe::new(MAX_OUTPUT_BYTES) already caps writes
-            // at construction time, so no separate size check is needed here.
-
-            serde_json::from_slice::<ToolResult>(&raw)
-                .context("WASM tool stdout is not valid ToolResult JSON")
+            let memory = instance.get_memory(&mut store, "memory");
+            let memory_bytes = memory.map(|memory| memory.data_size(&store) as u64);
+            let memory_limit_bytes = memory
+                .and_then(|memory| memory.ty(&store).maximum())
+                .map(|pages| pages.saturating_mul(WASM_PAGE_BYTES));
+
+            let (exit_code, error, timed_out) = match call_result {
+                Ok(()) => (Some(0), None, false),
+                Err(err) => match err.downcast_ref::<wasmtime_wasi::I32Exit>() {
+                    // `proc_exit(0)` is a normal return; other codes are failures.
+                    Some(exit) if exit.0 == 0 => (Some(0), None, false),
+                    Some(exit) => {
+                        let code = exit.0;
+                        (
+                            Some(code),
+                            Some(err.context("WASM tool exited with an error")),
+                            false,
+                        )
+                    }
+                    None => {
+                        let timed_out = matches!(
+                            err.downcast_ref::<wasmtime::Trap>(),
+                            Some(wasmtime::Trap::Interrupt)
+                        );
+                        (
+                            None,
+                            Some(err.context("WASM execution failed or timed out")),
+                            timed_out,
+                        )
+                    }
+                },
+            };
+
+            Ok(WasmRun {
+                stdout: stdout_for_read.contents().to_vec(),
+                stderr: stderr_for_read.contents().to_vec(),
+                exit_code,
+                error,
+                timed_out,
+                memory_bytes,
+                memory_limit_bytes,
+            })
         }
     }
 
@@ -195,18 +371,33 @@ mod inner {
             let schema = self.parameters_schema.clone();
             let desc = self.description.clone();
             let is_running = self.is_running.clone();
+            let streaming = self.streaming;
+            let priority = crate::tools::wasm_queue::current_priority();
+            let progress = if streaming {
+                crate::tools::progress::current_sink()
+            } else {
+                None
+            };
 
             tokio::task::spawn_blocking(move || {
+                let _permit = match crate::tools::wasm_queue::global().acquire(priority) {
+                    Ok(permit) => permit,
+                    Err(full) => {
+                        is_running.store(false, Ordering::Release);
+                        return Ok(full.into());
+                    }
+                };
                 let tool = WasmTool {
                     name,
                     description: desc,
                     parameters_schema: schema,
+                    streaming,
                     engine,
                     module,
                     is_running: is_running.clone(),
                 };
                 let result = tool
-                    .invoke_sync(&args)
+                    .invoke_sync(&args, progress)
                     .with_context(|| format!("WASM tool '{}' execution failed", tool.name));
                 is_running.store(false, Ordering::Release);
                 result
@@ -246,6 +437,26 @@ mod inner {
                 parameters_schema,
             })
         }
+
+        /// Stub: nothing runs, so there is nothing to stream.
+        #[must_use]
+        pub fn with_streaming(self, _streaming: bool) -> Self {
+            self
+        }
+
+        /// Stub: nothing runs, so there is no environment to pass.
+        #[must_use]
+        pub fn with_env(self, _env: SkillEnv) -> Self {
+            self
+        }
+
+        /// Stub: there is no in-process engine without the feature.
+        pub fn run_raw(&self, _input: &[u8], _env: &[(String, String)]) -> anyhow::Result<WasmRun> {
+            bail!(
+                "WASM tools are not enabled in this build. \
+                 Recompile with '--features wasm-tools'."
+            )
+        }
     }
 
     #[async_trait]
@@ -301,6 +512,10 @@ pub struct WasmManifest {
     /// Optional homepage / source URL (shown in `zeroclaw skill list`).
     #[serde(default)]
     pub homepage: Option<String>,
+    /// The tool writes `@progress:` lines before its envelope, forwarded to
+    /// the channel while it runs.
+    #[serde(default)]
+    pub streaming: bool,
 }
 
 fn default_manifest_version() -> String {
@@ -333,7 +548,13 @@ impl WasmManifest {
 /// skills/<skill-name>/tool.wasm
 /// skills/<skill-name>/manifest.json
 /// ```
-pub fn load_wasm_tools_from_skills(skills_dir: &std::path::Path) -> Vec<Box<dyn Tool>> {
+///
+/// Tools run with their skill's `required_env`, valued from `skills_env`
+/// (`[skills.env]`); see [`SkillEnv`].
+pub fn load_wasm_tools_from_skills(
+    skills_dir: &std::path::Path,
+    skills_env: &HashMap<String, HashMap<String, String>>,
+) -> Vec<Box<dyn Tool>> {
     let mut tools: Vec<Box<dyn Tool>> = Vec::new();
 
     let entries = match std::fs::read_dir(skills_dir) {
@@ -343,12 +564,22 @@ pub fn load_wasm_tools_from_skills(skills_dir: &std::path::Path) -> Vec<Box<dyn
 
     for entry in entries.flatten() {
         let skill_dir = entry.path();
+        if !skill_dir.is_dir() {
+            continue;
+        }
+        let env = match SkillEnv::for_dir(&skill_dir, skills_env) {
+            Ok(env) => env,
+            Err(e) => {
+                tracing::warn!(path = %skill_dir.display(), error = %e, "skipping WASM skill: bad SKILL.toml");
+                continue;
+            }
+        };
 
         // Dev layout: tool.wasm + manifest.json at skill root
         let wasm = skill_dir.join("tool.wasm");
         let manifest_path = skill_dir.join("manifest.json");
         if wasm.exists() && manifest_path.exists() {
-            load_single_tool(&wasm, &manifest_path, &mut tools);
+            load_single_tool(&wasm, &manifest_path, &env, &mut tools);
             continue;
         }
 
@@ -360,7 +591,7 @@ pub fn load_wasm_tools_from_skills(skills_dir: &std::path::Path) -> Vec<Box<dyn
                 let wasm = tool_dir.join("tool.wasm");
                 let manifest_path = tool_dir.join("manifest.json");
                 if wasm.exists() && manifest_path.exists() {
-                    load_single_tool(&wasm, &manifest_path, &mut tools);
+                    load_single_tool(&wasm, &manifest_path, &env, &mut tools);
                 }
             }
         }
@@ -418,6 +649,7 @@ pub fn wasm_tool_names_from_skills(skills_dir: &std::path::Path) -> Vec<String>
 fn load_single_tool(
     wasm: &std::path::Path,
     manifest_path: &std::path::Path,
+    env: &SkillEnv,
     out: &mut Vec<Box<dyn Tool>>,
 ) {
     let manifest = match WasmManifest::load_from(manifest_path) {
@@ -450,7 +682,11 @@ fn load_single_tool(
         manifest.name.clone(),
         manifest.description.clone(),
         manifest.parameters.clone(),
-    ) {
+    )
+    .map(|tool| {
+        tool.with_streaming(manifest.streaming)
+            .with_env(env.clone())
+    }) {
         Ok(t) => {
             tracing::debug!(name = %manifest.name, "loaded WASM tool");
             out.push(Box::new(t));
@@ -489,9 +725,10 @@ mod tests {
 
     #[test]
     fn load_from_empty_dir_returns_empty() {
-        let tools = load_wasm_tools_from_skills(std::path::Path::new(
-            "/tmp/zeroclaw_wasm_test_nonexistent_xyz",
-        ));
+        let tools = load_wasm_tools_from_skills(
+            std::path::Path::new("/tmp/zeroclaw_wasm_test_nonexistent_xyz"),
+            &HashMap::new(),
+        );
         assert!(tools.is_empty());
     }
 
@@ -565,7 +802,7 @@ mod tests {
         std::fs::create_dir_all(&skill_dir).unwrap();
         // tool.wasm present but no manifest.json — should be skipped silently
         std::fs::write(skill_dir.join("tool.wasm"), b"\x00asm\x01\x00\x00\x00").unwrap();
-        let tools = load_wasm_tools_from_skills(dir.path());
+        let tools = load_wasm_tools_from_skills(dir.path(), &HashMap::new());
         assert!(tools.is_empty());
     }
 
@@ -585,7 +822,7 @@ mod tests {
             .to_string(),
         )
         .unwrap();
-        let tools = load_wasm_tools_from_skills(dir.path());
+        let tools = load_wasm_tools_from_skills(dir.path(), &HashMap::new());
         assert!(tools.is_empty());
     }
 
@@ -596,7 +833,7 @@ mod tests {
         std::fs::create_dir_all(&skill_dir).unwrap();
         std::fs::write(skill_dir.join("tool.wasm"), b"\x00asm\x01\x00\x00\x00").unwrap();
         std::fs::write(skill_dir.join("manifest.json"), b"not valid json").unwrap();
-        let tools = load_wasm_tools_from_skills(dir.path());
+        let tools = load_wasm_tools_from_skills(dir.path(), &HashMap::new());
         assert!(tools.is_empty(), "bad manifest should be skipped");
     }
 
@@ -612,7 +849,7 @@ mod tests {
         std::fs::create_dir_all(&tool_dir).unwrap();
         std::fs::write(tool_dir.join("tool.wasm"), b"\x00asm\x01\x00\x00\x00").unwrap();
         std::fs::write(tool_dir.join("manifest.json"), b"{ invalid }").unwrap();
-        let tools = load_wasm_tools_from_skills(dir.path());
+        let tools = load_wasm_tools_from_skills(dir.path(), &HashMap::new());
         assert!(
             tools.is_empty(),
             "bad installed-layout manifest should be skipped"
@@ -624,7 +861,7 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         // A file at the skills root — not a directory, must be ignored
         std::fs::write(dir.path().join("not-a-skill.txt"), b"noise").unwrap();
-        let tools = load_wasm_tools_from_skills(dir.path());
+        let tools = load_wasm_tools_from_skills(dir.path(), &HashMap::new());
         assert!(tools.is_empty());
     }
 
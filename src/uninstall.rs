@@ -0,0 +1,605 @@
+//! `zeroclaw uninstall`: inventory and remove ZeroClaw-owned state.
+//!
+//! Only paths enumerated by [`build_inventory`] are ever removed. The config
+//! directory is owned outright; a workspace outside it is treated as shared and
+//! only the entries ZeroClaw creates there are removed. Symlinked roots are
+//! refused and symlinks inside them are unlinked, never followed.
+
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+
+/// Workspace entries ZeroClaw creates, removed even from a workspace that
+/// lives outside the config directory.
+const WORKSPACE_ENTRIES: &[&str] = &[
+    "AGENTS.md",
+    "SOUL.md",
+    "TOOLS.md",
+    "IDENTITY.md",
+    "USER.md",
+    "HEARTBEAT.md",
+    "BOOTSTRAP.md",
+    "MEMORY.md",
+    "memory",
+    "skills",
+    "sessions",
+    "state",
+    "cron",
+    "sops",
+];
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UninstallOptions {
+    pub keep_memory: bool,
+    pub keep_skills: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Config,
+    Secrets,
+    Workspace,
+    Memory,
+    Skills,
+    OpenSkills,
+    Transcripts,
+    Scheduler,
+    AgentsIpc,
+    State,
+}
+
+impl Category {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Secrets => "secrets",
+            Self::Workspace => "workspace",
+            Self::Memory => "memory",
+            Self::Skills => "skills",
+            Self::OpenSkills => "open-skills",
+            Self::Transcripts => "transcripts",
+            Self::Scheduler => "cron jobs",
+            Self::AgentsIpc => "agents ipc",
+            Self::State => "state",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InventoryItem {
+    pub path: PathBuf,
+    pub category: Category,
+    pub bytes: u64,
+    /// Removed as a link; the target is never touched.
+    pub symlink: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Inventory {
+    pub remove: Vec<InventoryItem>,
+    /// Left behind on purpose, with the reason.
+    pub keep: Vec<(PathBuf, String)>,
+    /// Directories removed afterwards if (and only if) they are empty.
+    pub roots: Vec<PathBuf>,
+    pub workspace_outside_home: bool,
+}
+
+impl Inventory {
+    pub fn total_bytes(&self) -> u64 {
+        self.remove.iter().map(|item| item.bytes).sum()
+    }
+}
+
+/// Every location uninstall looks at.
+#[derive(Debug, Clone)]
+pub struct UninstallPaths {
+    pub config_dir: PathBuf,
+    pub workspace_dir: PathBuf,
+    pub open_skills_dir: Option<PathBuf>,
+    pub agents_ipc_db: Option<PathBuf>,
+    pub home_dir: Option<PathBuf>,
+}
+
+impl UninstallPaths {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            config_dir: config
+                .config_path
+                .parent()
+                .map_or_else(|| config.workspace_dir.clone(), Path::to_path_buf),
+            workspace_dir: config.workspace_dir.clone(),
+            open_skills_dir: crate::skills::resolve_open_skills_dir(
+                config.skills.open_skills_dir.as_deref(),
+            ),
+            agents_ipc_db: Some(PathBuf::from(
+                shellexpand::tilde(&config.agents_ipc.db_path).as_ref(),
+            )),
+            home_dir: directories::UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf()),
+        }
+    }
+}
+
+/// Enumerate what uninstall would remove and what it leaves behind.
+pub fn build_inventory(paths: &UninstallPaths, options: UninstallOptions) -> Result<Inventory> {
+    let mut inventory = Inventory::default();
+    for root in [&paths.config_dir, &paths.workspace_dir] {
+        refuse_unsafe_root(root, paths.home_dir.as_deref())?;
+    }
+    inventory.workspace_outside_home = paths
+        .home_dir
+        .as_ref()
+        .is_some_and(|home| !paths.workspace_dir.starts_with(home));
+
+    let open_skills = paths
+        .open_skills_dir
+        .as_ref()
+        .filter(|dir| dir.symlink_metadata().is_ok());
+    // The SQLite database plus its WAL/SHM sidecars.
+    let agents_ipc: Vec<PathBuf> = paths
+        .agents_ipc_db
+        .iter()
+        .filter(|db| db.is_file())
+        .flat_map(|db| {
+            ["", "-wal", "-shm"].map(|suffix| {
+                let mut name = db.as_os_str().to_os_string();
+                name.push(suffix);
+                PathBuf::from(name)
+            })
+        })
+        .filter(|path| path.symlink_metadata().is_ok())
+        .collect();
+    let handled_elsewhere = |path: &Path| {
+        path == paths.workspace_dir
+            || open_skills.is_some_and(|dir| path == dir)
+            || agents_ipc.iter().any(|db| path == db)
+    };
+
+    // Workspace: owned outright when nested in the config dir.
+    if paths.workspace_dir.symlink_metadata().is_ok() {
+        let owned = paths.workspace_dir.starts_with(&paths.config_dir);
+        for entry in sorted_entries(&paths.workspace_dir)? {
+            let name = entry.file_name().map(|n| n.to_string_lossy().into_owned());
+            let name = name.as_deref().unwrap_or_default();
+            if !owned && !WORKSPACE_ENTRIES.contains(&name) {
+                inventory
+                    .keep
+                    .push((entry, "not created by zeroclaw".to_string()));
+                continue;
+            }
+            let category = match name {
+                "memory" | "MEMORY.md" => Category::Memory,
+                "skills" => Category::Skills,
+                "sessions" => Category::Transcripts,
+                "cron" => Category::Scheduler,
+                "state" => Category::State,
+                _ => Category::Workspace,
+            };
+            push(&mut inventory, entry, category, options)?;
+        }
+        inventory.roots.push(paths.workspace_dir.clone());
+    }
+
+    // Config dir: everything in it is ours.
+    if paths.config_dir.symlink_metadata().is_ok() {
+        for entry in sorted_entries(&paths.config_dir)? {
+            if handled_elsewhere(&entry) {
+                continue;
+            }
+            let category = match entry.file_name().and_then(|n| n.to_str()) {
+                Some("config.toml") => Category::Config,
+                Some(".secret_key" | ".secret_salt") => Category::Secrets,
+                _ => Category::State,
+            };
+            push(&mut inventory, entry, category, options)?;
+        }
+        inventory.roots.push(paths.config_dir.clone());
+    }
+
+    if let Some(dir) = open_skills {
+        refuse_unsafe_root(dir, paths.home_dir.as_deref())?;
+        if dir.join(crate::skills::OPEN_SKILLS_SYNC_MARKER).exists() {
+            push(&mut inventory, dir.clone(), Category::OpenSkills, options)?;
+        } else {
+            inventory
+                .keep
+                .push((dir.clone(), "not cloned by zeroclaw".to_string()));
+        }
+    }
+
+    for path in agents_ipc {
+        push(&mut inventory, path, Category::AgentsIpc, options)?;
+    }
+
+    Ok(inventory)
+}
+
+fn push(
+    inventory: &mut Inventory,
+    path: PathBuf,
+    category: Category,
+    options: UninstallOptions,
+) -> Result<()> {
+    let kept_by = match category {
+        Category::Memory if options.keep_memory => Some("--keep-memory"),
+        Category::Skills | Category::OpenSkills if options.keep_skills => Some("--keep-skills"),
+        _ => None,
+    };
+    if let Some(flag) = kept_by {
+        inventory.keep.push((path, format!("kept by {flag}")));
+        return Ok(());
+    }
+    let metadata = path
+        .symlink_metadata()
+        .with_context(|| format!("failed to inspect {}", path.display()))?;
+    let symlink = metadata.file_type().is_symlink();
+    let bytes = if symlink { 0 } else { disk_usage(&path) };
+    inventory.remove.push(InventoryItem {
+        path,
+        category,
+        bytes,
+        symlink,
+    });
+    Ok(())
+}
+
+/// Refuse roots that are symlinks, the filesystem root, or the home
+/// directory (or one of its ancestors).
+fn refuse_unsafe_root(root: &Path, home_dir: Option<&Path>) -> Result<()> {
+    if let Ok(metadata) = root.symlink_metadata() {
+        if metadata.file_type().is_symlink() {
+            let target = fs::read_link(root).unwrap_or_default();
+            bail!(
+                "refusing to follow symlink {} -> {}; remove it by hand",
+                root.display(),
+                target.display()
+            );
+        }
+    }
+    if root.parent().is_none() || home_dir.is_some_and(|home| home.starts_with(root)) {
+        bail!(
+            "refusing to uninstall from {}: it is the filesystem root or contains the home directory",
+            root.display()
+        );
+    }
+    Ok(())
+}
+
+fn sorted_entries(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+/// Apparent size of `path`, not following symlinks.
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return if metadata.is_file() {
+            metadata.len()
+        } else {
+            0
+        };
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+/// Remove every inventoried item, then any root left empty.
+pub fn execute(inventory: &Inventory) -> Result<()> {
+    for item in &inventory.remove {
+        let result = if !item.symlink && item.path.is_dir() {
+            // `remove_dir_all` unlinks nested symlinks without following them.
+            fs::remove_dir_all(&item.path)
+        } else {
+            fs::remove_file(&item.path)
+        };
+        match result {
+            Ok(()) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(error)
+                    .with_context(|| format!("failed to remove {}", item.path.display()))
+            }
+        }
+    }
+    for root in &inventory.roots {
+        // Non-recursive: fails (and is ignored) when something was kept.
+        let _ = fs::remove_dir(root);
+    }
+    Ok(())
+}
+
+pub fn handle_command(
+    config: &Config,
+    options: UninstallOptions,
+    yes: bool,
+    allow_outside_home: bool,
+) -> Result<()> {
+    let paths = UninstallPaths::from_config(config);
+    let inventory = build_inventory(&paths, options)?;
+    print_inventory(&inventory);
+    if inventory.remove.is_empty() {
+        println!("Nothing to remove.");
+        return Ok(());
+    }
+
+    if yes {
+        if inventory.workspace_outside_home && !allow_outside_home {
+            bail!(
+                "workspace {} is outside the home directory; pass --allow-outside-home with --yes to remove it",
+                paths.workspace_dir.display()
+            );
+        }
+    } else {
+        let expected = paths
+            .workspace_dir
+            .file_name()
+            .map_or_else(|| "workspace".into(), |n| n.to_string_lossy().into_owned());
+        confirm(
+            &format!("Type the workspace directory name ({expected}) to confirm"),
+            &expected,
+        )?;
+        if inventory.workspace_outside_home {
+            let full = paths.workspace_dir.display().to_string();
+            confirm(
+                "The workspace is outside your home directory. Type its full path to confirm",
+                &full,
+            )?;
+        }
+    }
+
+    execute(&inventory)?;
+    println!(
+        "Removed {} item(s), {}.",
+        inventory.remove.len(),
+        format_size(inventory.total_bytes())
+    );
+    print_left_behind(&inventory);
+    Ok(())
+}
+
+fn confirm(prompt: &str, expected: &str) -> Result<()> {
+    let typed: String = dialoguer::Input::new()
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()?;
+    if typed.trim() != expected {
+        bail!("confirmation did not match; nothing was removed");
+    }
+    Ok(())
+}
+
+fn print_inventory(inventory: &Inventory) {
+    println!("zeroclaw uninstall will remove:");
+    for item in &inventory.remove {
+        let size = if item.symlink {
+            "link only".to_string()
+        } else {
+            format_size(item.bytes)
+        };
+        println!(
+            "  {:<12} {:>10}  {}",
+            item.category.label(),
+            size,
+            item.path.display()
+        );
+    }
+    println!(
+        "  {:<12} {:>10}",
+        "total",
+        format_size(inventory.total_bytes())
+    );
+    println!();
+    print_left_behind(inventory);
+}
+
+fn print_left_behind(inventory: &Inventory) {
+    println!("Left behind:");
+    for (path, reason) in &inventory.keep {
+        println!("  {} ({reason})", path.display());
+    }
+    println!(
+        "  the zeroclaw binary; an installed OS service (run `zeroclaw service uninstall` first)"
+    );
+    println!("  nothing for [proxy]: its variables only exist inside zeroclaw processes");
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixture {
+        _tmp: tempfile::TempDir,
+        home: PathBuf,
+        paths: UninstallPaths,
+    }
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn fixture() -> Fixture {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = tmp.path().join("home");
+        let config_dir = home.join(".zeroclaw");
+        let workspace_dir = config_dir.join("workspace");
+        write(
+            &config_dir.join("config.toml"),
+            "default_temperature = 0.7\n",
+        );
+        write(&config_dir.join(".secret_key"), "k");
+        write(&config_dir.join("agents.db"), "ipc");
+        write(&workspace_dir.join("SOUL.md"), "# Soul\n");
+        write(&workspace_dir.join("memory/brain.db"), "memories");
+        write(
+            &workspace_dir.join("skills/weather/SKILL.md"),
+            "# Weather\n",
+        );
+        write(&workspace_dir.join("sessions/cli.jsonl"), "{}\n");
+        write(&workspace_dir.join("cron/jobs.db"), "jobs");
+        let open_skills = home.join("open-skills");
+        write(&open_skills.join("skills/pdf/SKILL.md"), "# PDF\n");
+        write(
+            &open_skills.join(crate::skills::OPEN_SKILLS_SYNC_MARKER),
+            "",
+        );
+
+        let paths = UninstallPaths {
+            config_dir: config_dir.clone(),
+            workspace_dir,
+            open_skills_dir: Some(open_skills),
+            agents_ipc_db: Some(config_dir.join("agents.db")),
+            home_dir: Some(home.clone()),
+        };
+        Fixture {
+            _tmp: tmp,
+            home,
+            paths,
+        }
+    }
+
+    #[test]
+    fn full_uninstall_removes_every_inventoried_path() {
+        let fx = fixture();
+        let inventory = build_inventory(&fx.paths, UninstallOptions::default()).unwrap();
+        let categories: Vec<Category> = inventory.remove.iter().map(|i| i.category).collect();
+        for expected in [
+            Category::Config,
+            Category::Secrets,
+            Category::Memory,
+            Category::Skills,
+            Category::OpenSkills,
+            Category::Transcripts,
+            Category::Scheduler,
+            Category::AgentsIpc,
+        ] {
+            assert!(categories.contains(&expected), "missing {expected:?}");
+        }
+        assert!(inventory.total_bytes() > 0);
+        assert!(!inventory.workspace_outside_home);
+
+        execute(&inventory).unwrap();
+        assert!(!fx.paths.config_dir.exists());
+        assert!(!fx.home.join("open-skills").exists());
+        assert!(fx.home.exists());
+    }
+
+    #[test]
+    fn keep_flags_leave_memory_and_skills_in_place() {
+        let fx = fixture();
+        let options = UninstallOptions {
+            keep_memory: true,
+            keep_skills: true,
+        };
+        let inventory = build_inventory(&fx.paths, options).unwrap();
+        execute(&inventory).unwrap();
+
+        let workspace = &fx.paths.workspace_dir;
+        assert!(workspace.join("memory/brain.db").exists());
+        assert!(workspace.join("skills/weather/SKILL.md").exists());
+        assert!(fx.home.join("open-skills/skills/pdf/SKILL.md").exists());
+        assert!(!workspace.join("SOUL.md").exists());
+        assert!(!workspace.join("sessions").exists());
+        assert!(!fx.paths.config_dir.join("config.toml").exists());
+        assert!(inventory
+            .keep
+            .iter()
+            .any(|(path, reason)| path.ends_with("memory") && reason.contains("--keep-memory")));
+    }
+
+    #[test]
+    fn shared_workspace_keeps_foreign_files_and_unmarked_open_skills() {
+        let mut fx = fixture();
+        let shared = fx.home.join("projects/agent");
+        write(&shared.join("AGENTS.md"), "# Agents\n");
+        write(&shared.join("notes.txt"), "mine");
+        fx.paths.workspace_dir = shared.clone();
+        fs::remove_file(
+            fx.home
+                .join("open-skills")
+                .join(crate::skills::OPEN_SKILLS_SYNC_MARKER),
+        )
+        .unwrap();
+
+        let inventory = build_inventory(&fx.paths, UninstallOptions::default()).unwrap();
+        execute(&inventory).unwrap();
+        assert!(!shared.join("AGENTS.md").exists());
+        assert!(shared.join("notes.txt").exists());
+        assert!(fx.home.join("open-skills/skills/pdf/SKILL.md").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_refused_as_roots_and_unlinked_inside() {
+        let fx = fixture();
+        let outside = fx.home.join("precious");
+        write(&outside.join("data.txt"), "keep me");
+
+        // A symlink inside an owned dir is unlinked, its target untouched.
+        std::os::unix::fs::symlink(&outside, fx.paths.workspace_dir.join("link")).unwrap();
+        let inventory = build_inventory(&fx.paths, UninstallOptions::default()).unwrap();
+        let link = inventory
+            .remove
+            .iter()
+            .find(|item| item.path.ends_with("link"))
+            .unwrap();
+        assert!(link.symlink);
+        assert_eq!(link.bytes, 0);
+        execute(&inventory).unwrap();
+        assert!(outside.join("data.txt").exists());
+
+        // A symlinked workspace root is refused outright.
+        let mut fx = fixture();
+        let real = fx.home.join("real-workspace");
+        write(&real.join("SOUL.md"), "# Soul\n");
+        let linked = fx.home.join("linked-workspace");
+        std::os::unix::fs::symlink(&real, &linked).unwrap();
+        fx.paths.workspace_dir = linked;
+        let err = build_inventory(&fx.paths, UninstallOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("refusing to follow symlink"));
+        assert!(real.join("SOUL.md").exists());
+    }
+
+    #[test]
+    fn home_and_outside_home_workspaces_are_flagged() {
+        let mut fx = fixture();
+        fx.paths.workspace_dir = fx.home.clone();
+        assert!(build_inventory(&fx.paths, UninstallOptions::default()).is_err());
+
+        let elsewhere = tempfile::tempdir().unwrap();
+        write(&elsewhere.path().join("SOUL.md"), "# Soul\n");
+        fx.paths.workspace_dir = elsewhere.path().to_path_buf();
+        let inventory = build_inventory(&fx.paths, UninstallOptions::default()).unwrap();
+        assert!(inventory.workspace_outside_home);
+    }
+}
@@ -0,0 +1,516 @@
+use crate::config::Config;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Canonical bootstrap files created by `workspace init`, in prompt order.
+const BOOTSTRAP_FILES: &[&str] = &[
+    "AGENTS.md",
+    "SOUL.md",
+    "TOOLS.md",
+    "IDENTITY.md",
+    "USER.md",
+    "HEARTBEAT.md",
+    "MEMORY.md",
+];
+
+/// Answers used to personalize the starter templates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceInitAnswers {
+    pub agent_name: String,
+    pub user_name: String,
+    pub tone: String,
+    pub timezone: String,
+}
+
+impl Default for WorkspaceInitAnswers {
+    fn default() -> Self {
+        Self {
+            agent_name: "ZeroClaw".to_string(),
+            user_name: "User".to_string(),
+            tone: "Direct, warm, and concise.".to_string(),
+            timezone: "UTC".to_string(),
+        }
+    }
+}
+
+/// What happened to a single bootstrap file during `workspace init`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileOutcome {
+    Created,
+    /// Existing non-empty file left untouched.
+    Skipped,
+    /// `--force`: section headings appended because they were missing.
+    SectionsAdded(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct WorkspaceInitReport {
+    pub files: Vec<(String, FileOutcome)>,
+    pub created_dirs: Vec<PathBuf>,
+}
+
+pub async fn handle_command(command: crate::WorkspaceCommands, config: &Config) -> Result<()> {
+    match command {
+        crate::WorkspaceCommands::Init { interactive, force } => {
+            let answers = if interactive {
+                prompt_answers()?
+            } else {
+                WorkspaceInitAnswers::default()
+            };
+            let report = init_workspace(&config.workspace_dir, &answers, force)?;
+            print_report(&config.workspace_dir, &report);
+            println!();
+            print_prompt_section_sizes(config);
+            Ok(())
+        }
+    }
+}
+
+fn prompt_answers() -> Result<WorkspaceInitAnswers> {
+    let defaults = WorkspaceInitAnswers::default();
+    let ask = |prompt: &str, default: &str| -> Result<String> {
+        let value: String = dialoguer::Input::new()
+            .with_prompt(prompt)
+            .default(default.to_string())
+            .interact_text()?;
+        Ok(value.trim().to_string())
+    };
+
+    Ok(WorkspaceInitAnswers {
+        agent_name: ask("Agent name", &defaults.agent_name)?,
+        user_name: ask("Your name", &defaults.user_name)?,
+        tone: ask("Preferred tone", &defaults.tone)?,
+        timezone: ask(
+            "Your timezone (IANA, e.g. Europe/Berlin)",
+            &defaults.timezone,
+        )?,
+    })
+}
+
+/// Create the canonical bootstrap files, `skills/`, and `memory/` in `workspace_dir`.
+///
+/// Existing non-empty files are never overwritten. With `force`, template
+/// `## ` sections missing from an existing file are appended to it.
+pub fn init_workspace(
+    workspace_dir: &Path,
+    answers: &WorkspaceInitAnswers,
+    force: bool,
+) -> Result<WorkspaceInitReport> {
+    fs::create_dir_all(workspace_dir).with_context(|| {
+        format!(
+            "failed to create workspace directory {}",
+            workspace_dir.display()
+        )
+    })?;
+
+    let mut report = WorkspaceInitReport::default();
+
+    let skills_dir = workspace_dir.join("skills");
+    let memory_dir = workspace_dir.join("memory");
+    for dir in [&skills_dir, &memory_dir] {
+        if !dir.exists() {
+            report.created_dirs.push(dir.clone());
+        }
+    }
+    crate::skills::init_skills_dir(workspace_dir)?;
+    fs::create_dir_all(&memory_dir)
+        .with_context(|| format!("failed to create {}", memory_dir.display()))?;
+
+    for filename in BOOTSTRAP_FILES {
+        let template = template_for(filename, answers);
+        let path = workspace_dir.join(filename);
+        let existing = match fs::read_to_string(&path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+
+        let outcome = match existing {
+            Some(content) if !content.trim().is_empty() => {
+                if force {
+                    let missing = missing_sections(&content, &template);
+                    if missing.is_empty() {
+                        FileOutcome::Skipped
+                    } else {
+                        let mut merged = content.trim_end().to_string();
+                        for (_, body) in &missing {
+                            merged.push_str("\n\n");
+                            merged.push_str(body.trim_end());
+                        }
+                        merged.push('\n');
+                        fs::write(&path, merged)
+                            .with_context(|| format!("failed to update {}", path.display()))?;
+                        FileOutcome::SectionsAdded(
+                            missing.into_iter().map(|(heading, _)| heading).collect(),
+                        )
+                    }
+                } else {
+                    FileOutcome::Skipped
+                }
+            }
+            _ => {
+                fs::write(&path, &template)
+                    .with_context(|| format!("failed to write {}", path.display()))?;
+                FileOutcome::Created
+            }
+        };
+        report.files.push(((*filename).to_string(), outcome));
+    }
+
+    Ok(report)
+}
+
+/// Split markdown into `## ` sections, returning `(heading, section_text)` pairs.
+fn sections(markdown: &str) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = Vec::new();
+    for line in markdown.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            out.push((heading.trim().to_string(), String::new()));
+        }
+        if let Some((_, body)) = out.last_mut() {
+            body.push_str(line);
+            body.push('\n');
+        }
+    }
+    out
+}
+
+fn missing_sections(existing: &str, template: &str) -> Vec<(String, String)> {
+    let present: Vec<String> = sections(existing)
+        .into_iter()
+        .map(|(heading, _)| heading.to_ascii_lowercase())
+        .collect();
+    sections(template)
+        .into_iter()
+        .filter(|(heading, _)| !present.contains(&heading.to_ascii_lowercase()))
+        .collect()
+}
+
+fn print_report(workspace_dir: &Path, report: &WorkspaceInitReport) {
+    println!("Workspace: {}", workspace_dir.display());
+    for dir in &report.created_dirs {
+        println!("  ✅ Created {}/", dir.display());
+    }
+    for (filename, outcome) in &report.files {
+        match outcome {
+            FileOutcome::Created => println!("  ✅ Created {filename}"),
+            FileOutcome::Skipped => {
+                println!("  ⏭️  Skipped {filename} (already has content; never overwritten)");
+            }
+            FileOutcome::SectionsAdded(headings) => {
+                println!(
+                    "  ➕ {filename}: added missing section(s): {}",
+                    headings.join(", ")
+                );
+            }
+        }
+    }
+}
+
+/// Print which prompt sections are enabled and how many characters each
+/// bootstrap file contributes, so users can see the effect of what they just
+/// created.
+fn print_prompt_section_sizes(config: &Config) {
+    let workspace_dir = &config.workspace_dir;
+    let skills = crate::skills::load_prompt_skills(workspace_dir, config);
+    let profile = config.agent.prompt_profile();
+    let sections = &profile.sections;
+    let bootstrap_max_chars = profile.bootstrap_max_chars.unwrap_or(20_000);
+    let prompt = crate::channels::build_system_prompt_with_sections(
+        workspace_dir,
+        config.default_model.as_deref().unwrap_or("(default)"),
+        &[],
+        &skills,
+        Some(&config.identity),
+        Some(bootstrap_max_chars),
+        false,
+        config.skills.prompt_injection_mode,
+        sections,
+    );
+
+    println!("System prompt preview:");
+    println!(
+        "  {:<14} {:<8} {:>8} {:>9}  Note",
+        "Section", "Status", "Chars", "~Tokens"
+    );
+    for (name, enabled, note) in section_toggle_rows(sections) {
+        println!(
+            "  {name:<14} {:<8} {:>8} {:>9}  {note}",
+            status_label(enabled),
+            "-",
+            "-"
+        );
+    }
+    for filename in BOOTSTRAP_FILES {
+        let enabled = *filename == "HEARTBEAT.md" || sections.bootstrap_file_enabled(filename);
+        let (chars, note) = if !enabled {
+            (0, "")
+        } else {
+            match fs::read_to_string(workspace_dir.join(filename)) {
+                Ok(content) => {
+                    let count = content.trim().chars().count();
+                    if *filename == "HEARTBEAT.md" {
+                        (0, "read by heartbeat, not injected")
+                    } else if count == 0 {
+                        (0, "empty, skipped")
+                    } else if count > bootstrap_max_chars {
+                        (bootstrap_max_chars, "truncated")
+                    } else {
+                        (count, "")
+                    }
+                }
+                Err(_) => (0, "missing"),
+            }
+        };
+        let status = if *filename == "HEARTBEAT.md" {
+            "-"
+        } else {
+            status_label(enabled)
+        };
+        println!(
+            "  {filename:<14} {status:<8} {chars:>8} {:>9}  {note}",
+            chars / 4
+        );
+    }
+    let total = prompt.chars().count();
+    println!(
+        "  {:<14} {:<8} {:>8} {:>9}  incl. skills, runtime; excl. tools",
+        "Total",
+        "",
+        total,
+        total / 4
+    );
+}
+
+/// (name, enabled, note) for each non-file prompt section.
+fn section_toggle_rows(
+    sections: &crate::config::PromptSectionsConfig,
+) -> Vec<(&'static str, bool, &'static str)> {
+    let safety_note = if !sections.safety && sections.safety_enabled() {
+        "forced on: set i_understand_no_safety = true to disable"
+    } else {
+        ""
+    };
+    vec![
+        ("Tools", sections.tools, ""),
+        ("Safety", sections.safety_enabled(), safety_note),
+        ("Skills", sections.skills, ""),
+        ("Workspace", sections.workspace, ""),
+        ("Date & Time", sections.datetime, ""),
+        ("Runtime", sections.runtime, ""),
+    ]
+}
+
+fn status_label(enabled: bool) -> &'static str {
+    if enabled {
+        "enabled"
+    } else {
+        "disabled"
+    }
+}
+
+fn template_for(filename: &str, answers: &WorkspaceInitAnswers) -> String {
+    let agent = &answers.agent_name;
+    let user = &answers.user_name;
+    let tone = &answers.tone;
+    let tz = &answers.timezone;
+    match filename {
+        "AGENTS.md" => format!(
+            "# AGENTS.md — How {agent} Works\n\n\
+             <!-- Operating rules for every session. Injected into the system prompt.\n\
+             \x20    Keep it short: every character here costs tokens on every turn. -->\n\n\
+             ## Every Session\n\n\
+             1. Read `SOUL.md` — who you are\n\
+             2. Read `USER.md` — who you're helping\n\
+             3. Use `memory_recall` for recent context\n\n\
+             ## Safety\n\n\
+             - Don't exfiltrate private data.\n\
+             - Ask before destructive commands or anything that leaves the machine.\n\n\
+             ## Conventions\n\n\
+             <!-- Example: \"Always run `cargo test` before proposing a commit.\" -->\n"
+        ),
+        "SOUL.md" => format!(
+            "# SOUL.md — Who You Are\n\n\
+             <!-- Personality, values, and voice. Describe behavior, not capabilities. -->\n\n\
+             ## Identity\n\n\
+             You are **{agent}**. Always introduce yourself as {agent} if asked.\n\n\
+             ## Communication\n\n\
+             {tone}\n\n\
+             ## Boundaries\n\n\
+             - Private things stay private.\n\
+             - When in doubt, ask before acting externally.\n"
+        ),
+        "TOOLS.md" => "# TOOLS.md — Local Notes\n\n\
+             <!-- Environment-specific details the agent can't discover on its own.\n\
+             \x20    Skills describe HOW tools work; this file holds YOUR specifics. -->\n\n\
+             ## Hosts & Devices\n\n\
+             <!-- Example: - `nas` → 192.168.1.20 (ssh user: admin) -->\n\n\
+             ## Preferences\n\n\
+             <!-- Example: - Prefer `rg` over `grep` for code search -->\n"
+            .to_string(),
+        "IDENTITY.md" => format!(
+            "# IDENTITY.md — Who Am I?\n\n\
+             <!-- Name and persona details. Update as the agent evolves. -->\n\n\
+             ## Profile\n\n\
+             - **Name:** {agent}\n\
+             - **Tone:** {tone}\n\
+             - **Emoji:** \u{1f980}\n"
+        ),
+        "USER.md" => format!(
+            "# USER.md — Who You're Helping\n\n\
+             <!-- Facts about the human: name, timezone, preferences, work context. -->\n\n\
+             ## About\n\n\
+             - **Name:** {user}\n\
+             - **Timezone:** {tz}\n\n\
+             ## Preferences\n\n\
+             <!-- Example: - I work mostly in Rust and TypeScript -->\n\n\
+             ## Work Context\n\n\
+             <!-- Example: - Maintaining a home-automation server -->\n"
+        ),
+        "HEARTBEAT.md" => format!(
+            "# HEARTBEAT.md\n\n\
+             <!-- Periodic tasks for {agent}. Leave only comments here to skip heartbeat work. -->\n\n\
+             ## Tasks\n\n\
+             <!-- Example: - Check my inbox for anything urgent -->\n"
+        ),
+        "MEMORY.md" => "# MEMORY.md — Long-Term Memory\n\n\
+             <!-- Curated facts worth keeping across sessions. Raw daily notes live in memory/.\n\
+             \x20    Keep it concise — this file is injected into the main session prompt. -->\n\n\
+             ## Key Facts\n\n\
+             ## Decisions & Preferences\n\n\
+             ## Open Loops\n"
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn init_creates_bootstrap_files_and_dirs() {
+        let tmp = TempDir::new().unwrap();
+        let report = init_workspace(tmp.path(), &WorkspaceInitAnswers::default(), false).unwrap();
+
+        for filename in BOOTSTRAP_FILES {
+            let content = fs::read_to_string(tmp.path().join(filename)).unwrap();
+            assert!(!content.trim().is_empty(), "{filename} should be non-empty");
+        }
+        assert!(tmp.path().join("skills").is_dir());
+        assert!(tmp.path().join("memory").is_dir());
+        assert!(report
+            .files
+            .iter()
+            .all(|(_, outcome)| *outcome == FileOutcome::Created));
+    }
+
+    #[test]
+    fn section_rows_report_forced_safety() {
+        let sections = crate::config::PromptSectionsConfig {
+            tools: false,
+            safety: false,
+            ..crate::config::PromptSectionsConfig::default()
+        };
+        let rows = section_toggle_rows(&sections);
+        assert_eq!(rows[0], ("Tools", false, ""));
+        assert_eq!(rows[1].0, "Safety");
+        assert!(rows[1].1);
+        assert!(rows[1].2.contains("i_understand_no_safety"));
+
+        let acknowledged = crate::config::PromptSectionsConfig {
+            i_understand_no_safety: true,
+            ..sections
+        };
+        assert_eq!(section_toggle_rows(&acknowledged)[1], ("Safety", false, ""));
+    }
+
+    #[test]
+    fn init_is_idempotent() {
+        let tmp = TempDir::new().unwrap();
+        init_workspace(tmp.path(), &WorkspaceInitAnswers::default(), false).unwrap();
+        let before = fs::read_to_string(tmp.path().join("SOUL.md")).unwrap();
+
+        let report = init_workspace(tmp.path(), &WorkspaceInitAnswers::default(), false).unwrap();
+
+        assert!(report
+            .files
+            .iter()
+            .all(|(_, outcome)| *outcome == FileOutcome::Skipped));
+        assert!(report.created_dirs.is_empty());
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("SOUL.md")).unwrap(),
+            before
+        );
+    }
+
+    #[test]
+    fn init_never_overwrites_non_empty_files() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("USER.md"), "# Mine\n\nhand-written\n").unwrap();
+        fs::write(tmp.path().join("SOUL.md"), "   \n").unwrap();
+
+        let answers = WorkspaceInitAnswers {
+            user_name: "Ada".into(),
+            ..WorkspaceInitAnswers::default()
+        };
+        init_workspace(tmp.path(), &answers, false).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(tmp.path().join("USER.md")).unwrap(),
+            "# Mine\n\nhand-written\n"
+        );
+        // Whitespace-only files count as empty and get the template.
+        assert!(fs::read_to_string(tmp.path().join("SOUL.md"))
+            .unwrap()
+            .contains("## Identity"));
+    }
+
+    #[test]
+    fn force_only_appends_missing_sections() {
+        let tmp = TempDir::new().unwrap();
+        let original = "# USER.md\n\n## About\n\n- **Name:** Ada\n";
+        fs::write(tmp.path().join("USER.md"), original).unwrap();
+
+        let report = init_workspace(tmp.path(), &WorkspaceInitAnswers::default(), true).unwrap();
+
+        let content = fs::read_to_string(tmp.path().join("USER.md")).unwrap();
+        assert!(content.starts_with(original.trim_end()));
+        assert_eq!(content.matches("## About").count(), 1);
+        assert!(content.contains("## Preferences"));
+        assert!(content.contains("## Work Context"));
+        assert!(!content.contains("**Name:** User"));
+        let (_, outcome) = report
+            .files
+            .iter()
+            .find(|(name, _)| name == "USER.md")
+            .unwrap();
+        assert_eq!(
+            *outcome,
+            FileOutcome::SectionsAdded(vec!["Preferences".into(), "Work Context".into()])
+        );
+    }
+
+    #[test]
+    fn interactive_answers_fill_identity_and_user() {
+        let tmp = TempDir::new().unwrap();
+        let answers = WorkspaceInitAnswers {
+            agent_name: "Nova".into(),
+            user_name: "Ada".into(),
+            tone: "Playful".into(),
+            timezone: "Europe/Berlin".into(),
+        };
+        init_workspace(tmp.path(), &answers, false).unwrap();
+
+        let identity = fs::read_to_string(tmp.path().join("IDENTITY.md")).unwrap();
+        assert!(identity.contains("**Name:** Nova"));
+        assert!(identity.contains("**Tone:** Playful"));
+        let user = fs::read_to_string(tmp.path().join("USER.md")).unwrap();
+        assert!(user.contains("**Name:** Ada"));
+        assert!(user.contains("**Timezone:** Europe/Berlin"));
+    }
+}
@@ -1,13 +1,15 @@
 //! __SKILL_NAME__ — ZeroClaw Skill (Rust / WASI)
 //!
 //! Performs arithmetic: add, subtract, multiply, divide.
-//! Protocol: read JSON from stdin, write JSON result to stdout.
+//! Protocol: handled by `src/sdk.rs` (JSON args on stdin, result envelope on stdout).
 //! Build:    cargo build --target wasm32-wasip1 --release
 //!           cp target/wasm32-wasip1/release/__BIN_NAME__.wasm tool.wasm
 //! Test:     zeroclaw skill test . --args '{"op":"add","a":3,"b":7}'
 
-use std::io::{self, Read, Write};
-use serde::{Deserialize, Serialize};
+mod sdk;
+
+use serde::Deserialize;
+use sdk::{ToolError, ToolOutput};
 
 #[derive(Deserialize)]
 struct Args {
@@ -16,79 +18,25 @@ struct Args {
     b: f64,
 }
 
-#[derive(Serialize)]
-struct ToolResult {
-    success: bool,
-    output: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    result: Option<f64>,
-}
-
-fn write_result(r: &ToolResult) {
-    let out = serde_json::to_string(r)
-        .unwrap_or_else(|_| r#"{"success":false,"output":"","error":"serialization error"}"#.to_string());
-    let _ = io::stdout().write_all(out.as_bytes());
-}
-
 fn main() {
-    let mut buf = String::new();
-    if let Err(e) = io::stdin().read_to_string(&mut buf) {
-        write_result(&ToolResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("failed to read stdin: {e}")),
-            result: None,
-        });
-        return;
-    }
-
-    let result = match serde_json::from_str::<Args>(&buf) {
-        Ok(args) => calculate(args),
-        Err(e) => ToolResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!(
-                "invalid input: {e} — expected {{\"op\":\"add|sub|mul|div\",\"a\":1,\"b\":2}}"
-            )),
-            result: None,
-        },
-    };
-
-    write_result(&result);
+    sdk::run_tool(calculate);
 }
 
-fn calculate(args: Args) -> ToolResult {
+fn calculate(args: Args) -> Result<ToolOutput<f64>, ToolError> {
     let (value, label) = match args.op.as_str() {
         "add" | "+" => (args.a + args.b, format!("{} + {}", args.a, args.b)),
         "sub" | "-" => (args.a - args.b, format!("{} - {}", args.a, args.b)),
         "mul" | "*" | "x" => (args.a * args.b, format!("{} × {}", args.a, args.b)),
         "div" | "/" => {
             if args.b == 0.0 {
-                return ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some("division by zero".into()),
-                    result: None,
-                };
+                return Err(ToolError::new("division by zero"));
             }
             (args.a / args.b, format!("{} ÷ {}", args.a, args.b))
         }
         op => {
-            return ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!("unknown op '{op}' — use: add, sub, mul, div")),
-                result: None,
-            };
+            return Err(ToolError::new(format!("unknown op '{op}' — use: add, sub, mul, div")));
         }
     };
 
-    ToolResult {
-        success: true,
-        output: format!("{label} = {value}"),
-        error: None,
-        result: Some(value),
-    }
+    Ok(ToolOutput::with_data(format!("{label} = {value}"), value))
 }
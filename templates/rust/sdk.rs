@@ -0,0 +1,315 @@
+//! ZeroClaw skill SDK: the stdin/stdout protocol in one file.
+//!
+//! Add `mod sdk;` to `main.rs` and hand your tool function to [`run_tool`]:
+//!
+//! ```ignore
+//! fn main() {
+//!     sdk::run_tool(|args: Args| {
+//!         Ok(sdk::ToolOutput::with_data(format!("hello {}", args.name), args.name))
+//!     });
+//! }
+//! ```
+//!
+//! [`run_tool`] reads the JSON arguments from stdin, deserializes them into
+//! your `Args` type, and writes the result envelope to stdout (see
+//! `zeroclaw skill protocol-schema`). Bad input, a returned [`ToolError`] and
+//! a panic all become a `{"success":false,...}` envelope instead of a trap.
+//! Needs only `serde` (with `derive`) and `serde_json`.
+
+#![allow(dead_code)]
+
+use serde::de::{self, DeserializeOwned, Visitor};
+use serde::Serialize;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+/// A successful result: text for the model, plus an optional JSON payload.
+pub struct ToolOutput<D = ()> {
+    output: String,
+    data: Option<D>,
+}
+
+impl ToolOutput<()> {
+    /// Text only, no `data`.
+    pub fn text(output: impl Into<String>) -> Self {
+        Self {
+            output: output.into(),
+            data: None,
+        }
+    }
+}
+
+impl<D> ToolOutput<D> {
+    /// Text plus a structured `data` payload.
+    pub fn with_data(output: impl Into<String>, data: D) -> Self {
+        Self {
+            output: output.into(),
+            data: Some(data),
+        }
+    }
+}
+
+/// A failure reported to the model as the envelope's `error`. Any
+/// `Display` error converts into it, so `?` works in tool functions.
+#[derive(Debug)]
+pub struct ToolError(String);
+
+impl ToolError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl<E: fmt::Display> From<E> for ToolError {
+    fn from(err: E) -> Self {
+        Self(err.to_string())
+    }
+}
+
+/// The result envelope, as in zeroclaw's `src/skills/protocol.rs`.
+#[derive(Serialize)]
+struct Envelope<D> {
+    success: bool,
+    output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<D>,
+}
+
+fn failure(error: String) -> Envelope<()> {
+    Envelope {
+        success: false,
+        output: String::new(),
+        error: Some(error),
+        data: None,
+    }
+}
+
+/// Run `tool` on stdin and write its envelope to stdout. Call once from
+/// `main`.
+pub fn run_tool<A, D, F>(tool: F)
+where
+    A: DeserializeOwned,
+    D: Serialize,
+    F: FnOnce(A) -> Result<ToolOutput<D>, ToolError>,
+{
+    // wasm32-wasip1 aborts on panic, so `catch_unwind` in `run_with` never
+    // sees it: report the panic from the hook and exit before the abort.
+    #[cfg(panic = "abort")]
+    std::panic::set_hook(Box::new(|info| {
+        let message = panic_message(info.payload());
+        let _ = write_envelope(&mut io::stdout(), &failure(message));
+        std::process::exit(0);
+    }));
+    let _ = run_with(tool, io::stdin().lock(), io::stdout().lock());
+}
+
+/// [`run_tool`] with the input and output streams passed in.
+pub fn run_with<A, D, F>(tool: F, mut input: impl Read, mut output: impl Write) -> io::Result<()>
+where
+    A: DeserializeOwned,
+    D: Serialize,
+    F: FnOnce(A) -> Result<ToolOutput<D>, ToolError>,
+{
+    let mut bytes = Vec::new();
+    if let Err(err) = input.read_to_end(&mut bytes) {
+        return write_envelope(
+            &mut output,
+            &failure(format!("failed to read stdin: {err}")),
+        );
+    }
+    let args = match serde_json::from_slice::<A>(&bytes) {
+        Ok(args) => args,
+        Err(err) => {
+            let message = format!("invalid input: {err} — expected {}", expected_shape::<A>());
+            return write_envelope(&mut output, &failure(message));
+        }
+    };
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| tool(args))) {
+        Ok(Ok(result)) => write_envelope(
+            &mut output,
+            &Envelope {
+                success: true,
+                output: result.output,
+                error: None,
+                data: result.data,
+            },
+        ),
+        Ok(Err(ToolError(error))) => write_envelope(&mut output, &failure(error)),
+        Err(payload) => write_envelope(&mut output, &failure(panic_message(&*payload))),
+    }
+}
+
+fn write_envelope<D: Serialize>(output: &mut impl Write, envelope: &Envelope<D>) -> io::Result<()> {
+    let json = serde_json::to_string(envelope).unwrap_or_else(|err| {
+        serde_json::to_string(&failure(format!("failed to serialize the result: {err}")))
+            .unwrap_or_default()
+    });
+    output.write_all(json.as_bytes())?;
+    output.flush()
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    format!("tool panicked: {detail}")
+}
+
+/// What `A` looks like as JSON, for the invalid-input message: the field
+/// names of a struct, e.g. `{"city": ...}`.
+fn expected_shape<A: DeserializeOwned>() -> String {
+    let mut fields = None;
+    let _ = A::deserialize(ShapeProbe(&mut fields));
+    match fields {
+        Some(fields) if !fields.is_empty() => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|name| format!("\"{name}\": ..."))
+                .collect();
+            format!("{{{}}}", fields.join(", "))
+        }
+        _ => "a JSON object".to_string(),
+    }
+}
+
+/// A deserializer that records the field list `A` asks for and stops.
+struct ShapeProbe<'a>(&'a mut Option<&'static [&'static str]>);
+
+#[derive(Debug)]
+struct Stop;
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("shape probe")
+    }
+}
+
+impl std::error::Error for Stop {}
+
+impl de::Error for Stop {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        Stop
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ShapeProbe<'_> {
+    type Error = Stop;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Stop> {
+        Err(Stop)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Stop> {
+        *self.0 = Some(fields);
+        Err(Stop)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Args {
+        city: String,
+        days: u8,
+    }
+
+    fn run<D: Serialize>(
+        stdin: &str,
+        tool: impl FnOnce(Args) -> Result<ToolOutput<D>, ToolError>,
+    ) -> serde_json::Value {
+        let mut stdout = Vec::new();
+        run_with(tool, stdin.as_bytes(), &mut stdout).unwrap();
+        serde_json::from_slice(&stdout).unwrap()
+    }
+
+    #[test]
+    fn success_writes_output_and_data() {
+        let envelope = run(r#"{"city":"Hanoi","days":2}"#, |args| {
+            Ok(ToolOutput::with_data(
+                format!("{} for {} days", args.city, args.days),
+                serde_json::json!({"temperature_c": 28.5}),
+            ))
+        });
+        assert_eq!(
+            envelope,
+            serde_json::json!({
+                "success": true,
+                "output": "Hanoi for 2 days",
+                "data": {"temperature_c": 28.5}
+            })
+        );
+
+        let envelope = run(r#"{"city":"Hanoi","days":2}"#, |_| {
+            Ok(ToolOutput::text("ok"))
+        });
+        assert_eq!(
+            envelope,
+            serde_json::json!({"success": true, "output": "ok"})
+        );
+    }
+
+    #[test]
+    fn bad_input_names_the_expected_fields() {
+        let envelope = run(r#"{"town":"Hanoi"}"#, |_| {
+            Ok(ToolOutput::text("unreachable"))
+        });
+        assert_eq!(envelope["success"], false);
+        let error = envelope["error"].as_str().unwrap();
+        assert!(error.starts_with("invalid input: missing field"), "{error}");
+        assert!(
+            error.ends_with(r#"expected {"city": ..., "days": ...}"#),
+            "{error}"
+        );
+
+        let envelope = run("", |_| Ok(ToolOutput::text("unreachable")));
+        assert!(envelope["error"].as_str().unwrap().contains("expected {"));
+    }
+
+    #[test]
+    fn errors_and_panics_become_failure_envelopes() {
+        let envelope = run(r#"{"city":"Atlantis","days":1}"#, |args| {
+            Err::<ToolOutput, _>(ToolError::new(format!("city '{}' not found", args.city)))
+        });
+        assert_eq!(
+            envelope,
+            serde_json::json!({"success": false, "output": "", "error": "city 'Atlantis' not found"})
+        );
+
+        let envelope = run(r#"{"city":"Hanoi","days":0}"#, |args| {
+            let per_day = 10 / u32::from(args.days);
+            Ok(ToolOutput::text(per_day.to_string()))
+        });
+        assert_eq!(envelope["success"], false);
+        assert!(
+            envelope["error"]
+                .as_str()
+                .unwrap()
+                .starts_with("tool panicked: "),
+            "{envelope}"
+        );
+
+        let envelope = run(r#"{"city":"Hanoi","days":1}"#, |_| {
+            let days: u8 = "many".parse()?;
+            Ok(ToolOutput::text(days.to_string()))
+        });
+        assert_eq!(envelope["error"], "invalid digit found in string");
+    }
+}
@@ -1,13 +1,15 @@
 //! __SKILL_NAME__ — ZeroClaw Skill (Rust / WASI)
 //!
 //! Returns mock weather data for a given city.
-//! Protocol: read JSON from stdin, write JSON result to stdout.
+//! Protocol: handled by `src/sdk.rs` (JSON args on stdin, result envelope on stdout).
 //! Build:    cargo build --target wasm32-wasip1 --release
 //!           cp target/wasm32-wasip1/release/__BIN_NAME__.wasm tool.wasm
 //! Test:     zeroclaw skill test . --args '{"city":"hanoi"}'
 
-use std::io::{self, Read, Write};
+mod sdk;
+
 use serde::{Deserialize, Serialize};
+use sdk::{ToolError, ToolOutput};
 
 #[derive(Deserialize)]
 struct Args {
@@ -23,48 +25,11 @@ struct WeatherData {
     wind_kmh: u8,
 }
 
-#[derive(Serialize)]
-struct ToolResult {
-    success: bool,
-    output: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    error: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    data: Option<WeatherData>,
-}
-
-fn write_result(r: &ToolResult) {
-    let out = serde_json::to_string(r)
-        .unwrap_or_else(|_| r#"{"success":false,"output":"","error":"serialization error"}"#.to_string());
-    let _ = io::stdout().write_all(out.as_bytes());
-}
-
 fn main() {
-    let mut buf = String::new();
-    if let Err(e) = io::stdin().read_to_string(&mut buf) {
-        write_result(&ToolResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("failed to read stdin: {e}")),
-            data: None,
-        });
-        return;
-    }
-
-    let result = match serde_json::from_str::<Args>(&buf) {
-        Ok(args) => lookup_weather(&args.city),
-        Err(e) => ToolResult {
-            success: false,
-            output: String::new(),
-            error: Some(format!("invalid input: {e} — expected {{\"city\": \"<name>\"}}")),
-            data: None,
-        },
-    };
-
-    write_result(&result);
+    sdk::run_tool(|args: Args| lookup_weather(&args.city));
 }
 
-fn lookup_weather(city: &str) -> ToolResult {
+fn lookup_weather(city: &str) -> Result<ToolOutput<WeatherData>, ToolError> {
     // Mock weather database — no HTTP inside WASI sandbox
     let weather = match city.to_lowercase().as_str() {
         "hanoi" | "ha noi" => WeatherData {
@@ -124,14 +89,9 @@ fn lookup_weather(city: &str) -> ToolResult {
             wind_kmh: 14,
         },
         _ => {
-            return ToolResult {
-                success: false,
-                output: String::new(),
-                error: Some(format!(
-                    "city '{city}' not found. Supported: Hanoi, Ho Chi Minh, Da Nang, London, Tokyo, New York, Paris, Singapore"
-                )),
-                data: None,
-            };
+            return Err(ToolError::new(format!(
+                "city '{city}' not found. Supported: Hanoi, Ho Chi Minh, Da Nang, London, Tokyo, New York, Paris, Singapore"
+            )));
         }
     };
 
@@ -140,5 +100,5 @@ fn lookup_weather(city: &str) -> ToolResult {
         weather.city, weather.temperature_c, weather.condition, weather.humidity_pct, weather.wind_kmh
     );
 
-    ToolResult { success: true, output, error: None, data: Some(weather) }
+    Ok(ToolOutput::with_data(output, weather))
 }
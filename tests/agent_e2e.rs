@@ -20,7 +20,8 @@ use zeroclaw::memory::Memory;
 use zeroclaw::observability::{NoopObserver, Observer};
 use zeroclaw::providers::traits::ChatMessage;
 use zeroclaw::providers::{
-    ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderRuntimeOptions, ToolCall,
+    ChatOutcome, ChatRequest, ChatResponse, ConversationMessage, Provider, ProviderRuntimeOptions,
+    ToolCall,
 };
 use zeroclaw::tools::{Tool, ToolResult};
 
@@ -66,6 +67,7 @@ impl Provider for MockProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
         Ok(guard.remove(0))
@@ -192,6 +194,7 @@ impl Provider for RecordingProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
         Ok(guard.remove(0))
@@ -241,6 +244,7 @@ fn text_response(text: &str) -> ChatResponse {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -250,6 +254,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
         tool_calls: calls,
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -375,6 +380,7 @@ async fn e2e_xml_dispatcher_tool_call() {
             tool_calls: vec![],
             usage: None,
             reasoning_content: None,
+            outcome: ChatOutcome::Completed,
         },
         text_response("XML tool executed"),
     ]));
@@ -1013,6 +1019,7 @@ async fn e2e_agent_research_prompt_guided() {
                     tool_calls: vec![],
                     usage: None,
                     reasoning_content: None,
+                    outcome: ChatOutcome::Completed,
                 });
             }
             Ok(guard.remove(0))
@@ -1031,6 +1038,7 @@ async fn e2e_agent_research_prompt_guided() {
         tool_calls: vec![], // Empty! Tool call is in text
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     // Response 2: Research complete
@@ -1039,6 +1047,7 @@ async fn e2e_agent_research_prompt_guided() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     // Response 3: Main turn response
@@ -17,7 +17,7 @@ use zeroclaw::config::MemoryConfig;
 use zeroclaw::memory;
 use zeroclaw::memory::Memory;
 use zeroclaw::observability::{NoopObserver, Observer};
-use zeroclaw::providers::{ChatRequest, ChatResponse, Provider, ToolCall};
+use zeroclaw::providers::{ChatOutcome, ChatRequest, ChatResponse, Provider, ToolCall};
 use zeroclaw::tools::{Tool, ToolResult};
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -61,6 +61,7 @@ impl Provider for MockProvider {
                 tool_calls: vec![],
                 usage: None,
                 reasoning_content: None,
+                outcome: ChatOutcome::Completed,
             });
         }
         Ok(guard.remove(0))
@@ -183,6 +184,7 @@ fn text_response(text: &str) -> ChatResponse {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -192,6 +194,7 @@ fn tool_response(calls: Vec<ToolCall>) -> ChatResponse {
         tool_calls: calls,
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }
 }
 
@@ -361,6 +364,7 @@ async fn agent_handles_empty_provider_response() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);
@@ -376,6 +380,7 @@ async fn agent_handles_none_text_response() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     }]));
 
     let mut agent = build_agent(provider, vec![Box::new(EchoTool)]);
@@ -7,9 +7,11 @@
 //! to provider construction, verifying factory resolution, URL construction,
 //! credential wiring, and auth header format.
 
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
 use zeroclaw::providers::compatible::{AuthStyle, OpenAiCompatibleProvider};
 use zeroclaw::providers::{
-    create_provider, create_provider_with_options, create_provider_with_url,
+    create_provider, create_provider_with_options, create_provider_with_url, Provider,
 };
 
 /// Helper: assert provider creation succeeds
@@ -159,13 +161,24 @@ fn compatible_provider_xapikey_auth_style() {
 }
 
 #[test]
-fn compatible_provider_custom_auth_header() {
-    // Construction with Custom auth should succeed
+fn compatible_provider_header_auth_style() {
+    // Construction with a named auth header should succeed
     let _provider = OpenAiCompatibleProvider::new(
         "TestProvider",
         "https://api.test.com",
         Some("sk-test-key-12345"),
-        AuthStyle::Custom("X-Custom-Auth".into()),
+        AuthStyle::Header("X-Custom-Auth".into()),
+    );
+}
+
+#[test]
+fn compatible_provider_query_param_auth_style() {
+    // Construction with query-parameter auth should succeed
+    let _provider = OpenAiCompatibleProvider::new(
+        "TestProvider",
+        "https://api.test.com",
+        Some("sk-test-key-12345"),
+        AuthStyle::QueryParam("key".into()),
     );
 }
 
@@ -485,3 +498,98 @@ fn factory_anthropic_custom_endpoint_resolves() {
         None,
     );
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Auth style on the wire: each style puts the key where the endpoint expects it
+// ─────────────────────────────────────────────────────────────────────────────
+
+const CHAT_COMPLETION_OK: &str =
+    r#"{"choices":[{"message":{"role":"assistant","content":"pong"}}]}"#;
+
+/// Serve a chat completion only when `matcher` accepts the request, so a key
+/// sent in the wrong place surfaces as a provider error.
+async fn mock_chat_completion(server: &MockServer, matcher: impl wiremock::Match + 'static) {
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(matcher)
+        .respond_with(
+            ResponseTemplate::new(200).set_body_raw(CHAT_COMPLETION_OK, "application/json"),
+        )
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+async fn chat_with_auth_style(server: &MockServer, style: AuthStyle) -> String {
+    let provider = OpenAiCompatibleProvider::new(
+        "TestProvider",
+        &format!("{}/v1", server.uri()),
+        Some("sk-test-key-12345"),
+        style,
+    );
+    provider
+        .chat_with_system(None, "ping", "test-model", 0.0)
+        .await
+        .expect("request should authenticate")
+}
+
+#[tokio::test]
+async fn bearer_auth_style_sends_authorization_header() {
+    let server = MockServer::start().await;
+    mock_chat_completion(&server, header("authorization", "Bearer sk-test-key-12345")).await;
+
+    assert_eq!(
+        chat_with_auth_style(&server, AuthStyle::Bearer).await,
+        "pong"
+    );
+}
+
+#[tokio::test]
+async fn xapikey_auth_style_sends_x_api_key_header() {
+    let server = MockServer::start().await;
+    mock_chat_completion(&server, header("x-api-key", "sk-test-key-12345")).await;
+
+    assert_eq!(
+        chat_with_auth_style(&server, AuthStyle::XApiKey).await,
+        "pong"
+    );
+}
+
+#[tokio::test]
+async fn header_auth_style_sends_named_header() {
+    let server = MockServer::start().await;
+    mock_chat_completion(&server, header("api-key", "sk-test-key-12345")).await;
+
+    let style = AuthStyle::Header("api-key".into());
+    assert_eq!(chat_with_auth_style(&server, style).await, "pong");
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("authorization").is_none());
+}
+
+#[tokio::test]
+async fn query_param_auth_style_sends_key_in_url() {
+    let server = MockServer::start().await;
+    mock_chat_completion(&server, query_param("key", "sk-test-key-12345")).await;
+
+    let style = AuthStyle::QueryParam("key".into());
+    assert_eq!(chat_with_auth_style(&server, style).await, "pong");
+    let requests = server.received_requests().await.unwrap();
+    assert!(requests[0].headers.get("authorization").is_none());
+}
+
+#[tokio::test]
+async fn factory_custom_url_provider_uses_bearer_auth() {
+    let server = MockServer::start().await;
+    mock_chat_completion(&server, header("authorization", "Bearer sk-test-key-12345")).await;
+
+    let provider = create_provider(
+        &format!("custom:{}/v1", server.uri()),
+        Some("sk-test-key-12345"),
+    )
+    .expect("custom provider should resolve");
+    let reply = provider
+        .chat_with_system(None, "ping", "test-model", 0.0)
+        .await
+        .expect("request should authenticate");
+    assert_eq!(reply, "pong");
+}
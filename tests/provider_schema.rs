@@ -8,7 +8,7 @@
 //! ToolCall, and AuthStyle serialization contracts.
 
 use zeroclaw::providers::compatible::AuthStyle;
-use zeroclaw::providers::traits::{ChatMessage, ChatResponse, ToolCall};
+use zeroclaw::providers::traits::{ChatMessage, ChatOutcome, ChatResponse, ToolCall};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // ChatMessage serialization
@@ -155,6 +155,7 @@ fn chat_response_text_only() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     assert_eq!(resp.text_or_empty(), "Hello world");
@@ -172,6 +173,7 @@ fn chat_response_with_tool_calls() {
         }],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     assert!(resp.has_tool_calls());
@@ -186,6 +188,7 @@ fn chat_response_text_or_empty_handles_none() {
         tool_calls: vec![],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     assert_eq!(resp.text_or_empty(), "");
@@ -209,6 +212,7 @@ fn chat_response_multiple_tool_calls() {
         ],
         usage: None,
         reasoning_content: None,
+        outcome: ChatOutcome::Completed,
     };
 
     assert!(resp.has_tool_calls());
@@ -234,12 +238,22 @@ fn auth_style_xapikey_is_constructible() {
 }
 
 #[test]
-fn auth_style_custom_header() {
-    let style = AuthStyle::Custom("X-Custom-Auth".into());
-    if let AuthStyle::Custom(header) = style {
+fn auth_style_named_header() {
+    let style = AuthStyle::Header("X-Custom-Auth".into());
+    if let AuthStyle::Header(header) = style {
         assert_eq!(header, "X-Custom-Auth");
     } else {
-        panic!("expected AuthStyle::Custom");
+        panic!("expected AuthStyle::Header");
+    }
+}
+
+#[test]
+fn auth_style_query_param() {
+    let style = AuthStyle::QueryParam("key".into());
+    if let AuthStyle::QueryParam(name) = style {
+        assert_eq!(name, "key");
+    } else {
+        panic!("expected AuthStyle::QueryParam");
     }
 }
 
@@ -278,11 +292,17 @@ fn provider_construction_with_different_auth_styles() {
         Some("key"),
         AuthStyle::XApiKey,
     );
-    let _custom = OpenAiCompatibleProvider::new(
+    let _header = OpenAiCompatibleProvider::new(
+        "Test",
+        "https://api.test.com",
+        Some("key"),
+        AuthStyle::Header("X-My-Auth".into()),
+    );
+    let _query = OpenAiCompatibleProvider::new(
         "Test",
         "https://api.test.com",
         Some("key"),
-        AuthStyle::Custom("X-My-Auth".into()),
+        AuthStyle::QueryParam("key".into()),
     );
 }
 